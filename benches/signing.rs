@@ -0,0 +1,76 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        SignFromKey, VerifyFromKey,
+    },
+    jwt::{Header, Payload, JWT},
+    signer::sign,
+    verifier::{verify, PreparedVerifier},
+};
+use serde_json::json;
+
+const MESSAGE: &str = "eyJhbGciOiJFUzI1NiIsInR5cCI6IkpXVCJ9.eyJzdWIiOiJkaWQ6ZXhhbXBsZToxMjMifQ";
+
+fn signing(c: &mut Criterion) {
+    for alg in [Algorithm::ES256, Algorithm::EdDSA] {
+        let key = AnySigningKey::generate(alg).unwrap();
+        let verifying_key = key.verifying_key();
+        let signature = key.sign_bytes(MESSAGE.as_bytes(), alg).unwrap();
+
+        c.bench_function(&format!("{} sign owned", alg.to_str()), |b| {
+            b.iter(|| sign(black_box(String::from(MESSAGE)), &key, alg))
+        });
+        c.bench_function(&format!("{} sign borrowed", alg.to_str()), |b| {
+            b.iter(|| sign(black_box(MESSAGE), &key, alg))
+        });
+        c.bench_function(&format!("{} verify owned", alg.to_str()), |b| {
+            b.iter(|| {
+                verify(
+                    black_box(String::from(MESSAGE)),
+                    black_box(signature.clone()),
+                    &verifying_key,
+                    alg,
+                )
+            })
+        });
+        c.bench_function(&format!("{} verify borrowed", alg.to_str()), |b| {
+            b.iter(|| {
+                verifying_key.verify_bytes(
+                    black_box(MESSAGE.as_bytes()),
+                    black_box(&signature),
+                    alg,
+                )
+            })
+        });
+    }
+}
+
+fn prepared(c: &mut Criterion) {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let jwk = key.verifying_key().to_jwk().unwrap();
+    let mut jwt = JWT {
+        header: Header::new(String::from("key-1"), Algorithm::ES256),
+        payload: Payload(json!({ "exp": 4102444800i64 })),
+        signature: None,
+    };
+    jwt.sign(&key).unwrap();
+    let token = jwt.to_token().unwrap();
+
+    let verifier = PreparedVerifier::new();
+    verifier.add_jwk("key-1", &jwk).unwrap();
+    c.bench_function("ES256 prepared verify_token", |b| {
+        b.iter(|| verifier.verify_token(black_box(&token)))
+    });
+    c.bench_function("ES256 parse and validate", |b| {
+        b.iter(|| {
+            JWT::from_token(black_box(&token))
+                .unwrap()
+                .validate(AnyVerifyingKey::from_jwk(&jwk).unwrap())
+        })
+    });
+}
+
+criterion_group!(benches, signing, prepared);
+criterion_main!(benches);
@@ -0,0 +1,4 @@
+fn main() {
+    #[cfg(feature = "uniffi")]
+    uniffi::generate_scaffolding("src/did_crypto.udl").unwrap();
+}
@@ -0,0 +1,53 @@
+#ifndef DID_CRYPTO_H
+#define DID_CRYPTO_H
+
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
+#define DID_CRYPTO_OK 0
+#define DID_CRYPTO_ERROR_NULL_POINTER -1
+#define DID_CRYPTO_ERROR_INVALID_UTF8 -2
+#define DID_CRYPTO_ERROR_BUFFER_TOO_SMALL -3
+#define DID_CRYPTO_ERROR_UNKNOWN -4
+
+/*
+ * Output buffers: `*out_len` holds the capacity of `out` on input and the
+ * written (or required) length on return. DID_CRYPTO_ERROR_BUFFER_TOO_SMALL
+ * is returned when the capacity is insufficient. Positive return values are
+ * library error codes, see did_crypto_error_message.
+ */
+
+int32_t did_crypto_error_message(int32_t code, uint8_t *out, size_t *out_len);
+
+int32_t did_crypto_generate_key(const char *alg, uint8_t *out, size_t *out_len);
+
+int32_t did_crypto_public_key(const char *alg, const uint8_t *private_key,
+                              size_t private_key_len, uint8_t *out,
+                              size_t *out_len);
+
+int32_t did_crypto_sign(const char *alg, const uint8_t *private_key,
+                        size_t private_key_len, const uint8_t *message,
+                        size_t message_len, uint8_t *out, size_t *out_len);
+
+int32_t did_crypto_verify(const char *alg, const uint8_t *public_key,
+                          size_t public_key_len, const uint8_t *message,
+                          size_t message_len, const char *signature,
+                          bool *valid);
+
+int32_t did_crypto_public_key_to_jwk(const char *alg, const uint8_t *public_key,
+                                     size_t public_key_len, uint8_t *out,
+                                     size_t *out_len);
+
+int32_t did_crypto_public_key_from_jwk(const char *jwk, uint8_t *out,
+                                       size_t *out_len);
+
+int32_t did_crypto_private_key_to_jwk(const char *alg,
+                                      const uint8_t *private_key,
+                                      size_t private_key_len, uint8_t *out,
+                                      size_t *out_len);
+
+int32_t did_crypto_private_key_from_jwk(const char *jwk, uint8_t *out,
+                                        size_t *out_len);
+
+#endif
@@ -1,6 +1,10 @@
+use std::{fmt, str::FromStr};
+
 use serde::{Deserialize, Serialize};
 
-#[derive(PartialEq, Eq, Serialize, Deserialize, Copy, Clone)]
+use crate::errors::Error;
+
+#[derive(PartialEq, Eq, Hash, Serialize, Deserialize, Copy, Clone, Debug)]
 pub enum Algorithm {
     HS256,
     HS384,
@@ -15,7 +19,13 @@ pub enum Algorithm {
     ES384,
     ES512,
     ES256K,
+    #[serde(rename = "ES256K-R")]
+    ES256KR,
     EdDSA,
+    #[serde(rename = "ML-DSA-65")]
+    MLDSA65,
+    #[serde(rename = "ML-DSA-65-Ed25519")]
+    MLDSA65Ed25519,
 }
 
 impl Algorithm {
@@ -34,27 +44,10 @@ impl Algorithm {
             Algorithm::ES384 => "ES384",
             Algorithm::ES512 => "ES512",
             Algorithm::ES256K => "ES256K",
+            Algorithm::ES256KR => "ES256K-R",
             Algorithm::EdDSA => "EdDSA",
-        }
-    }
-
-    pub fn from_str(alg: &str) -> Option<Self> {
-        match alg {
-            "HS256" => Some(Algorithm::HS256),
-            "HS384" => Some(Algorithm::HS384),
-            "HS512" => Some(Algorithm::HS512),
-            "RS256" => Some(Algorithm::RS256),
-            "RS384" => Some(Algorithm::RS384),
-            "RS512" => Some(Algorithm::RS512),
-            "PS256" => Some(Algorithm::PS256),
-            "PS384" => Some(Algorithm::PS384),
-            "PS512" => Some(Algorithm::PS512),
-            "ES256" => Some(Algorithm::ES256),
-            "ES384" => Some(Algorithm::ES384),
-            "ES512" => Some(Algorithm::ES512),
-            "ES256K" => Some(Algorithm::ES256K),
-            "EdDSA" => Some(Algorithm::EdDSA),
-            _ => None,
+            Algorithm::MLDSA65 => "ML-DSA-65",
+            Algorithm::MLDSA65Ed25519 => "ML-DSA-65-Ed25519",
         }
     }
 
@@ -73,16 +66,131 @@ impl Algorithm {
             Algorithm::ES384 => AlgorithmFamily::EC,
             Algorithm::ES512 => AlgorithmFamily::EC,
             Algorithm::ES256K => AlgorithmFamily::EC,
+            Algorithm::ES256KR => AlgorithmFamily::EC,
             Algorithm::EdDSA => AlgorithmFamily::OKP,
+            Algorithm::MLDSA65 => AlgorithmFamily::AKP,
+            Algorithm::MLDSA65Ed25519 => AlgorithmFamily::AKP,
+        }
+    }
+}
+
+impl FromStr for Algorithm {
+    type Err = Error;
+
+    fn from_str(alg: &str) -> Result<Self, Error> {
+        match alg {
+            "HS256" => Ok(Algorithm::HS256),
+            "HS384" => Ok(Algorithm::HS384),
+            "HS512" => Ok(Algorithm::HS512),
+            "RS256" => Ok(Algorithm::RS256),
+            "RS384" => Ok(Algorithm::RS384),
+            "RS512" => Ok(Algorithm::RS512),
+            "PS256" => Ok(Algorithm::PS256),
+            "PS384" => Ok(Algorithm::PS384),
+            "PS512" => Ok(Algorithm::PS512),
+            "ES256" => Ok(Algorithm::ES256),
+            "ES384" => Ok(Algorithm::ES384),
+            "ES512" => Ok(Algorithm::ES512),
+            "ES256K" => Ok(Algorithm::ES256K),
+            "ES256K-R" => Ok(Algorithm::ES256KR),
+            "EdDSA" => Ok(Algorithm::EdDSA),
+            "ML-DSA-65" => Ok(Algorithm::MLDSA65),
+            "ML-DSA-65-Ed25519" => Ok(Algorithm::MLDSA65Ed25519),
+            _ => Err(Error::UNKNOWN_ALGORITHM),
+        }
+    }
+}
+
+impl fmt::Display for Algorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.to_str())
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct AlgorithmPolicy(u32);
+
+pub const FIPS_MODE: bool = cfg!(feature = "fips");
+
+pub const FIPS_ALGORITHMS: [Algorithm; 12] = [
+    Algorithm::HS256,
+    Algorithm::HS384,
+    Algorithm::HS512,
+    Algorithm::RS256,
+    Algorithm::RS384,
+    Algorithm::RS512,
+    Algorithm::PS256,
+    Algorithm::PS384,
+    Algorithm::PS512,
+    Algorithm::ES256,
+    Algorithm::ES384,
+    Algorithm::ES512,
+];
+
+impl Default for AlgorithmPolicy {
+    fn default() -> Self {
+        match FIPS_MODE {
+            true => Self::fips(),
+            false => Self::allow_all(),
         }
     }
 }
 
+impl AlgorithmPolicy {
+    pub fn new(allowed: &[Algorithm]) -> Self {
+        AlgorithmPolicy(
+            allowed
+                .iter()
+                .fold(0, |mask, alg| mask | Self::algorithm_bit(*alg)),
+        )
+    }
+
+    pub fn allow_all() -> Self {
+        AlgorithmPolicy(u32::MAX)
+    }
+
+    pub fn fips() -> Self {
+        Self::new(&FIPS_ALGORITHMS)
+    }
+
+    pub fn asymmetric() -> Self {
+        Self::allow_all()
+            .deny(Algorithm::HS256)
+            .deny(Algorithm::HS384)
+            .deny(Algorithm::HS512)
+    }
+
+    pub fn allow(self, alg: Algorithm) -> Self {
+        AlgorithmPolicy(self.0 | Self::algorithm_bit(alg))
+    }
+
+    pub fn deny(self, alg: Algorithm) -> Self {
+        AlgorithmPolicy(self.0 & !Self::algorithm_bit(alg))
+    }
+
+    pub fn is_allowed(&self, alg: Algorithm) -> bool {
+        self.0 & Self::algorithm_bit(alg) != 0
+    }
+
+    pub fn check(&self, alg: Algorithm) -> Result<(), Error> {
+        match self.is_allowed(alg) {
+            true => Ok(()),
+            false => Err(Error::ALGORITHM_NOT_ALLOWED.with_algorithm(alg)),
+        }
+    }
+
+    fn algorithm_bit(alg: Algorithm) -> u32 {
+        1 << alg as u32
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum AlgorithmFamily {
     HMAC,
     EC,
     RSA,
     OKP,
+    AKP,
     None,
 }
 
@@ -93,6 +201,7 @@ impl AlgorithmFamily {
             AlgorithmFamily::EC => "EC",
             AlgorithmFamily::RSA => "RSA",
             AlgorithmFamily::OKP => "OKP",
+            AlgorithmFamily::AKP => "AKP",
             _ => "None",
         }
     }
@@ -0,0 +1,66 @@
+use std::sync::{Arc, RwLock};
+
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::{algorithms::Algorithm, clock, metrics::Operation};
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AuditRecord {
+    pub timestamp: DateTime<Utc>,
+    pub key_id: Option<String>,
+    pub operation: Operation,
+    pub algorithm: Algorithm,
+    pub payload_digest: String,
+}
+
+pub trait AuditSink: Send + Sync {
+    fn record(&self, record: &AuditRecord);
+}
+
+impl<F: Fn(&AuditRecord) + Send + Sync> AuditSink for F {
+    fn record(&self, record: &AuditRecord) {
+        self(record)
+    }
+}
+
+static AUDIT_SINK: RwLock<Option<Arc<dyn AuditSink>>> = RwLock::new(None);
+
+pub fn set_audit_sink(sink: Arc<dyn AuditSink>) {
+    if let Ok(mut val) = AUDIT_SINK.write() {
+        *val = Some(sink);
+    }
+}
+
+pub fn clear_audit_sink() {
+    if let Ok(mut val) = AUDIT_SINK.write() {
+        *val = None;
+    }
+}
+
+pub(crate) fn audit(
+    operation: Operation,
+    alg: Algorithm,
+    key_id: impl FnOnce() -> Option<String>,
+    payloads: &[&[u8]],
+) {
+    let sink = match AUDIT_SINK.read() {
+        Ok(val) => match val.as_ref() {
+            Some(val) => val.clone(),
+            None => return,
+        },
+        Err(_) => return,
+    };
+
+    let timestamp = clock::now();
+    let key_id = key_id();
+    for payload in payloads {
+        sink.record(&AuditRecord {
+            timestamp,
+            key_id: key_id.clone(),
+            operation,
+            algorithm: alg,
+            payload_digest: base64_url::encode(Sha256::digest(payload).as_slice()),
+        });
+    }
+}
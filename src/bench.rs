@@ -0,0 +1,340 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::{Duration, Instant},
+};
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroizing;
+
+use crate::{
+    algorithms::{Algorithm, AlgorithmFamily},
+    crypto::{any::AnySigningKey, SignFromKey, VerifyFromKey},
+    encoding::{self, JsonFormat},
+    errors::Error,
+    log,
+    metrics::Operation,
+};
+
+pub const DEFAULT_ITERATIONS: usize = 100;
+pub const DEFAULT_WARMUP: usize = 10;
+pub const DEFAULT_MESSAGE_SIZE: usize = 256;
+
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static ALLOCATED_BYTES: AtomicU64 = AtomicU64::new(0);
+
+pub struct CountingAllocator;
+
+impl CountingAllocator {
+    fn count(size: usize) {
+        COUNTING.store(true, Ordering::Relaxed);
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    }
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        Self::count(layout.size());
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        Self::count(layout.size());
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        Self::count(new_size);
+        System.realloc(ptr, layout, new_size)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+fn allocation_counters() -> Option<(u64, u64)> {
+    match COUNTING.load(Ordering::Relaxed) {
+        true => Some((
+            ALLOCATIONS.load(Ordering::Relaxed),
+            ALLOCATED_BYTES.load(Ordering::Relaxed),
+        )),
+        false => None,
+    }
+}
+
+pub fn default_algorithms() -> Vec<Algorithm> {
+    vec![
+        Algorithm::HS256,
+        Algorithm::HS384,
+        Algorithm::HS512,
+        #[cfg(feature = "rsa")]
+        Algorithm::RS256,
+        #[cfg(feature = "rsa")]
+        Algorithm::RS384,
+        #[cfg(feature = "rsa")]
+        Algorithm::RS512,
+        #[cfg(feature = "rsa")]
+        Algorithm::PS256,
+        #[cfg(feature = "rsa")]
+        Algorithm::PS384,
+        #[cfg(feature = "rsa")]
+        Algorithm::PS512,
+        Algorithm::ES256,
+        #[cfg(feature = "es384")]
+        Algorithm::ES384,
+        #[cfg(feature = "es512")]
+        Algorithm::ES512,
+        #[cfg(feature = "es256k")]
+        Algorithm::ES256K,
+        #[cfg(feature = "eddsa")]
+        Algorithm::EdDSA,
+        #[cfg(feature = "mldsa")]
+        Algorithm::MLDSA65,
+        #[cfg(feature = "mldsa")]
+        Algorithm::MLDSA65Ed25519,
+    ]
+}
+
+#[derive(Clone, Debug)]
+pub struct BenchOptions {
+    algorithms: Vec<Algorithm>,
+    iterations: usize,
+    warmup: usize,
+    message_size: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        BenchOptions {
+            algorithms: default_algorithms(),
+            iterations: DEFAULT_ITERATIONS,
+            warmup: DEFAULT_WARMUP,
+            message_size: DEFAULT_MESSAGE_SIZE,
+        }
+    }
+}
+
+impl BenchOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn algorithms(mut self, algorithms: &[Algorithm]) -> Self {
+        self.algorithms = algorithms.to_vec();
+        self
+    }
+
+    pub fn iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations.max(1);
+        self
+    }
+
+    pub fn warmup(mut self, warmup: usize) -> Self {
+        self.warmup = warmup;
+        self
+    }
+
+    pub fn message_size(mut self, message_size: usize) -> Self {
+        self.message_size = message_size;
+        self
+    }
+}
+
+#[derive(Clone, PartialEq, Debug, Serialize, Deserialize)]
+pub struct BenchResult {
+    pub alg: Algorithm,
+    pub operation: Operation,
+    pub iterations: usize,
+    pub message_size: usize,
+    pub total: Duration,
+    pub mean: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub p50: Duration,
+    pub p99: Duration,
+    pub ops_per_sec: f64,
+    pub allocations: Option<u64>,
+    pub allocated_bytes: Option<u64>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RegressionMetric {
+    Throughput,
+    Allocations,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Regression {
+    pub alg: Algorithm,
+    pub operation: Operation,
+    pub metric: RegressionMetric,
+    pub baseline: f64,
+    pub current: f64,
+}
+
+#[derive(Clone, PartialEq, Debug, Default, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub results: Vec<BenchResult>,
+}
+
+impl BenchReport {
+    pub fn get(&self, alg: Algorithm, operation: Operation) -> Option<&BenchResult> {
+        self.results
+            .iter()
+            .find(|result| result.alg == alg && result.operation == operation)
+    }
+
+    pub fn compare(&self, baseline: &BenchReport, tolerance: f64) -> Vec<Regression> {
+        let mut regressions = Vec::new();
+        for result in &self.results {
+            let previous = match baseline.get(result.alg, result.operation) {
+                Some(val) => val,
+                None => continue,
+            };
+
+            if result.ops_per_sec < previous.ops_per_sec * (1.0 - tolerance) {
+                regressions.push(Regression {
+                    alg: result.alg,
+                    operation: result.operation,
+                    metric: RegressionMetric::Throughput,
+                    baseline: previous.ops_per_sec,
+                    current: result.ops_per_sec,
+                });
+            }
+
+            if let (Some(current), Some(before)) = (result.allocations, previous.allocations) {
+                if current > before {
+                    regressions.push(Regression {
+                        alg: result.alg,
+                        operation: result.operation,
+                        metric: RegressionMetric::Allocations,
+                        baseline: before as f64,
+                        current: current as f64,
+                    });
+                }
+            }
+        }
+        regressions
+    }
+
+    pub fn check_regressions(&self, baseline: &BenchReport, tolerance: f64) -> Result<(), Error> {
+        match self.compare(baseline, tolerance).first() {
+            Some(regression) => Err(Error::BENCH_REGRESSION
+                .with_algorithm(regression.alg)
+                .with_source(format!(
+                    "{} {:?} went from {:.2} to {:.2}",
+                    regression.operation.to_str(),
+                    regression.metric,
+                    regression.baseline,
+                    regression.current
+                ))),
+            None => Ok(()),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        match serde_json::from_str(json) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::BENCH_REPORT_MALFORMED.with_source(error))
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        self.to_json_with_format(JsonFormat::Canonical)
+    }
+
+    pub fn to_json_with_format(&self, format: JsonFormat) -> Result<String, Error> {
+        encoding::to_json(self, format)
+    }
+}
+
+pub fn run(options: &BenchOptions) -> Result<BenchReport, Error> {
+    let message: Vec<u8> = (0..options.message_size)
+        .map(|index| (index % 251) as u8)
+        .collect();
+    let mut rsa_key: Option<Zeroizing<Vec<u8>>> = None;
+    let mut report = BenchReport::default();
+
+    for alg in &options.algorithms {
+        let key = match (alg.get_family(), &rsa_key) {
+            (AlgorithmFamily::RSA, Some(val)) => AnySigningKey::from_bytes(*alg, val)?,
+            _ => AnySigningKey::generate(*alg)?,
+        };
+        if alg.get_family() == AlgorithmFamily::RSA && rsa_key.is_none() {
+            rsa_key = Some(key.to_private_bytes()?);
+        }
+
+        let verifying_key = key.verifying_key();
+
+        let signature = key.sign_bytes(&message, *alg)?;
+        if !verifying_key.verify_bytes(&message, &signature, *alg)? {
+            return Err(Error::SELF_TEST_FAILED.with_algorithm(*alg));
+        }
+
+        let sign = measure(*alg, Operation::Sign, options, || {
+            key.sign_bytes(&message, *alg).map(|_| ())
+        })?;
+        let verify = measure(*alg, Operation::Verify, options, || {
+            verifying_key
+                .verify_bytes(&message, &signature, *alg)
+                .map(|_| ())
+        })?;
+
+        report.results.push(sign);
+        report.results.push(verify);
+    }
+
+    Ok(report)
+}
+
+fn measure(
+    alg: Algorithm,
+    operation: Operation,
+    options: &BenchOptions,
+    mut call: impl FnMut() -> Result<(), Error>,
+) -> Result<BenchResult, Error> {
+    for _ in 0..options.warmup {
+        call()?;
+    }
+
+    let mut samples = Vec::with_capacity(options.iterations);
+    let counters = allocation_counters();
+    let start = Instant::now();
+    for _ in 0..options.iterations {
+        let iteration = Instant::now();
+        call()?;
+        samples.push(iteration.elapsed());
+    }
+    let total = start.elapsed();
+    let allocated = match (counters, allocation_counters()) {
+        (Some((allocations, bytes)), Some((allocations_after, bytes_after))) => Some((
+            (allocations_after - allocations) / options.iterations as u64,
+            (bytes_after - bytes) / options.iterations as u64,
+        )),
+        _ => None,
+    };
+
+    samples.sort();
+    let count = samples.len();
+    Ok(BenchResult {
+        alg,
+        operation,
+        iterations: count,
+        message_size: options.message_size,
+        total,
+        mean: total / count as u32,
+        min: samples[0],
+        max: samples[count - 1],
+        p50: samples[count / 2],
+        p99: samples[(count * 99 / 100).min(count - 1)],
+        ops_per_sec: count as f64 / total.as_secs_f64().max(f64::MIN_POSITIVE),
+        allocations: allocated.map(|(allocations, _)| allocations),
+        allocated_bytes: allocated.map(|(_, bytes)| bytes),
+    })
+}
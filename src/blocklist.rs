@@ -0,0 +1,171 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, RwLock},
+};
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::{clock, errors::Error, jwt::JWT};
+
+const JTI_PREFIX: &str = "jti:";
+const HASH_PREFIX: &str = "sha256:";
+const MIN_PURGE_THRESHOLD: usize = 1024;
+
+pub trait TokenBlocklist: Send + Sync {
+    fn block(&self, key: &str, until: DateTime<Utc>) -> Result<(), Error>;
+
+    fn is_blocked(&self, key: &str) -> Result<bool, Error>;
+
+    fn revoke(&self, jwt: &JWT) -> Result<(), Error> {
+        let until = match jwt.payload.0.get("exp").and_then(Value::as_i64) {
+            Some(val) => match DateTime::from_timestamp(val, 0) {
+                Some(val) => val,
+                None => return Err(Error::FAILED_TO_CONVERT_TIMESTAMP_TO_DATETTIME),
+            },
+            None => DateTime::<Utc>::MAX_UTC,
+        };
+
+        match jwt.payload.0.get("jti").and_then(Value::as_str) {
+            Some(jti) => self.block(&jti_key(jti), until),
+            None => self.block(&hash_key(jwt)?, until),
+        }
+    }
+
+    fn check(&self, jwt: &JWT) -> Result<(), Error> {
+        for key in token_keys(jwt)? {
+            if self.is_blocked(&key)? {
+                return Err(Error::JWT_REVOKED);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub fn jti_key(jti: &str) -> String {
+    format!("{}{}", JTI_PREFIX, jti)
+}
+
+pub fn hash_key(jwt: &JWT) -> Result<String, Error> {
+    match &jwt.signature {
+        Some(signature) => Ok(format!(
+            "{}{}",
+            HASH_PREFIX,
+            base64_url::encode(Sha256::digest(signature.to_string().as_bytes()).as_slice())
+        )),
+        None => Err(Error::JWT_TOKEN_NOT_SIGNED),
+    }
+}
+
+pub fn token_keys(jwt: &JWT) -> Result<Vec<String>, Error> {
+    let mut keys = Vec::new();
+    if let Some(jti) = jwt.payload.0.get("jti").and_then(Value::as_str) {
+        keys.push(jti_key(jti));
+    }
+    keys.push(hash_key(jwt)?);
+    Ok(keys)
+}
+
+struct MemoryEntries {
+    values: HashMap<String, DateTime<Utc>>,
+    purge_at: usize,
+}
+
+pub struct MemoryTokenBlocklist {
+    entries: Mutex<MemoryEntries>,
+}
+
+impl Default for MemoryTokenBlocklist {
+    fn default() -> Self {
+        MemoryTokenBlocklist {
+            entries: Mutex::new(MemoryEntries {
+                values: HashMap::new(),
+                purge_at: MIN_PURGE_THRESHOLD,
+            }),
+        }
+    }
+}
+
+impl MemoryTokenBlocklist {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entries(&self) -> MutexGuard<'_, MemoryEntries> {
+        match self.entries.lock() {
+            Ok(val) => val,
+            Err(error) => error.into_inner(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries().values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn unblock(&self, key: &str) -> bool {
+        self.entries().values.remove(key).is_some()
+    }
+
+    pub fn purge_expired(&self) {
+        let now = clock::now();
+        self.entries().values.retain(|_, until| *until > now);
+    }
+}
+
+impl TokenBlocklist for MemoryTokenBlocklist {
+    fn block(&self, key: &str, until: DateTime<Utc>) -> Result<(), Error> {
+        let now = clock::now();
+        let mut entries = self.entries();
+        if entries.values.len() >= entries.purge_at {
+            entries.values.retain(|_, until| *until > now);
+            entries.purge_at = MIN_PURGE_THRESHOLD.max(entries.values.len() * 2);
+        }
+
+        let until = match entries.values.get(key) {
+            Some(val) => until.max(*val),
+            None => until,
+        };
+        entries.values.insert(String::from(key), until);
+        Ok(())
+    }
+
+    fn is_blocked(&self, key: &str) -> Result<bool, Error> {
+        Ok(self
+            .entries()
+            .values
+            .get(key)
+            .is_some_and(|until| *until > clock::now()))
+    }
+}
+
+static BLOCKLIST: RwLock<Option<Arc<dyn TokenBlocklist>>> = RwLock::new(None);
+
+pub fn set_token_blocklist(blocklist: Arc<dyn TokenBlocklist>) {
+    if let Ok(mut val) = BLOCKLIST.write() {
+        *val = Some(blocklist);
+    }
+}
+
+pub fn clear_token_blocklist() {
+    if let Ok(mut val) = BLOCKLIST.write() {
+        *val = None;
+    }
+}
+
+pub(crate) fn check(jwt: &JWT) -> Result<(), Error> {
+    let blocklist = match BLOCKLIST.read() {
+        Ok(val) => val.clone(),
+        Err(_) => None,
+    };
+
+    match blocklist {
+        Some(blocklist) => blocklist.check(jwt),
+        None => Ok(()),
+    }
+}
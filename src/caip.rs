@@ -0,0 +1,222 @@
+use std::{fmt, str::FromStr};
+
+use sha3::{Digest, Keccak256};
+
+#[cfg(feature = "es256k")]
+use crate::crypto::ecdsa::_256k::P256kVerifyingKey;
+#[cfg(feature = "eddsa")]
+use crate::crypto::eddsa::EDDSAVerifyingKey;
+#[cfg(feature = "eddsa")]
+use crate::encoding::base58_encode;
+use crate::{encoding::base58_decode, errors::Error};
+
+pub const EIP155: &str = "eip155";
+pub const SOLANA: &str = "solana";
+pub const SOLANA_MAINNET: &str = "5eykt4UsFv8P8NJdTREpY1vzqKqZKvdp";
+
+pub(crate) fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+fn is_valid(value: &str, min: usize, max: usize, allowed: impl Fn(char) -> bool) -> bool {
+    (min..=max).contains(&value.len()) && value.chars().all(allowed)
+}
+
+#[cfg(feature = "es256k")]
+pub fn eip155_address(key: &P256kVerifyingKey) -> [u8; 20] {
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&keccak256(&key.to_bytes()[1..])[12..]);
+    address
+}
+
+pub fn checksum_address(address: &[u8; 20]) -> String {
+    let lower = hex::encode(address);
+    let hash = keccak256(lower.as_bytes());
+
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(index, c)| {
+            let nibble = (hash[index / 2] >> (4 * (1 - index % 2))) & 0x0f;
+            match nibble >= 8 {
+                true => c.to_ascii_uppercase(),
+                false => c,
+            }
+        })
+        .collect();
+
+    format!("0x{}", checksummed)
+}
+
+pub fn parse_eip155_address(address: &str) -> Result<[u8; 20], Error> {
+    let mut bytes = [0u8; 20];
+    match address.strip_prefix("0x") {
+        Some(val) if hex::decode_to_slice(val, &mut bytes).is_ok() => {}
+        _ => return Err(Error::CAIP_INVALID_ACCOUNT_ID),
+    }
+
+    let mixed_case = address.chars().any(|c| c.is_ascii_lowercase())
+        && address[2..].chars().any(|c| c.is_ascii_uppercase());
+    if mixed_case && checksum_address(&bytes) != address {
+        return Err(Error::CAIP_INVALID_ACCOUNT_ID);
+    }
+
+    Ok(bytes)
+}
+
+#[cfg(feature = "eddsa")]
+pub fn solana_address(key: &EDDSAVerifyingKey) -> String {
+    base58_encode(key.to_bytes())
+}
+
+pub fn parse_solana_address(address: &str) -> Result<[u8; 32], Error> {
+    match base58_decode(address) {
+        Ok(val) => match val.try_into() {
+            Ok(val) => Ok(val),
+            Err(_) => Err(Error::CAIP_INVALID_ACCOUNT_ID),
+        },
+        Err(error) => Err(Error::CAIP_INVALID_ACCOUNT_ID.with_source(error)),
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ChainId {
+    pub namespace: String,
+    pub reference: String,
+}
+
+impl ChainId {
+    pub fn new(namespace: &str, reference: &str) -> Result<Self, Error> {
+        let valid_namespace = is_valid(namespace, 3, 8, |c| {
+            c == '-' || c.is_ascii_lowercase() || c.is_ascii_digit()
+        });
+        let valid_reference = is_valid(reference, 1, 32, |c| {
+            c == '-' || c == '_' || c.is_ascii_alphanumeric()
+        });
+        if !valid_namespace || !valid_reference {
+            return Err(Error::CAIP_INVALID_CHAIN_ID);
+        }
+
+        Ok(ChainId {
+            namespace: String::from(namespace),
+            reference: String::from(reference),
+        })
+    }
+
+    pub fn eip155(chain_id: u64) -> Self {
+        ChainId {
+            namespace: String::from(EIP155),
+            reference: chain_id.to_string(),
+        }
+    }
+
+    pub fn solana(reference: &str) -> Result<Self, Error> {
+        Self::new(SOLANA, reference)
+    }
+
+    pub fn eip155_chain_id(&self) -> Option<u64> {
+        match self.namespace == EIP155 {
+            true => self.reference.parse().ok(),
+            false => None,
+        }
+    }
+}
+
+impl FromStr for ChainId {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.split_once(':') {
+            Some((namespace, reference)) => Self::new(namespace, reference),
+            None => Err(Error::CAIP_INVALID_CHAIN_ID),
+        }
+    }
+}
+
+impl fmt::Display for ChainId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.namespace, self.reference)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct AccountId {
+    pub chain_id: ChainId,
+    pub address: String,
+}
+
+impl AccountId {
+    pub fn new(chain_id: ChainId, address: &str) -> Result<Self, Error> {
+        if !is_valid(address, 1, 128, |c| {
+            c == '-' || c == '.' || c == '%' || c.is_ascii_alphanumeric()
+        }) {
+            return Err(Error::CAIP_INVALID_ACCOUNT_ID);
+        }
+
+        match chain_id.namespace.as_str() {
+            EIP155 => {
+                parse_eip155_address(address)?;
+            }
+            SOLANA => {
+                parse_solana_address(address)?;
+            }
+            _ => {}
+        }
+
+        Ok(AccountId {
+            chain_id,
+            address: String::from(address),
+        })
+    }
+
+    pub fn eip155(chain_id: u64, address: &[u8; 20]) -> Self {
+        AccountId {
+            chain_id: ChainId::eip155(chain_id),
+            address: checksum_address(address),
+        }
+    }
+
+    #[cfg(feature = "es256k")]
+    pub fn from_secp256k1_key(chain_id: u64, key: &P256kVerifyingKey) -> Self {
+        Self::eip155(chain_id, &eip155_address(key))
+    }
+
+    #[cfg(feature = "es256k")]
+    pub fn verify_recoverable(&self, message: &[u8], signature: &str) -> Result<bool, Error> {
+        if self.chain_id.namespace != EIP155 {
+            return Err(Error::CAIP_INVALID_ACCOUNT_ID);
+        }
+
+        let address = parse_eip155_address(&self.address)?;
+        let key = P256kVerifyingKey::recover(message, signature)?;
+        Ok(eip155_address(&key) == address)
+    }
+
+    #[cfg(feature = "eddsa")]
+    pub fn from_ed25519_key(reference: &str, key: &EDDSAVerifyingKey) -> Result<Self, Error> {
+        Ok(AccountId {
+            chain_id: ChainId::solana(reference)?,
+            address: solana_address(key),
+        })
+    }
+}
+
+impl FromStr for AccountId {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.rsplit_once(':') {
+            Some((chain_id, address)) => match ChainId::from_str(chain_id) {
+                Ok(val) => Self::new(val, address),
+                Err(_) => Err(Error::CAIP_INVALID_ACCOUNT_ID),
+            },
+            None => Err(Error::CAIP_INVALID_ACCOUNT_ID),
+        }
+    }
+}
+
+impl fmt::Display for AccountId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.chain_id, self.address)
+    }
+}
@@ -0,0 +1,99 @@
+use std::sync::{Arc, RwLock};
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+pub trait Clock: Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+}
+
+impl<F: Fn() -> DateTime<Utc> + Send + Sync> Clock for F {
+    fn now(&self) -> DateTime<Utc> {
+        self()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+#[derive(Debug)]
+pub struct FixedClock {
+    time: RwLock<DateTime<Utc>>,
+}
+
+impl FixedClock {
+    pub fn new(time: DateTime<Utc>) -> Self {
+        FixedClock {
+            time: RwLock::new(time),
+        }
+    }
+
+    pub fn set(&self, time: DateTime<Utc>) {
+        if let Ok(mut val) = self.time.write() {
+            *val = time;
+        }
+    }
+
+    pub fn advance(&self, delta: TimeDelta) {
+        if let Ok(mut val) = self.time.write() {
+            *val += delta;
+        }
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        match self.time.read() {
+            Ok(val) => *val,
+            Err(error) => *error.into_inner(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OffsetClock {
+    offset: TimeDelta,
+}
+
+impl OffsetClock {
+    pub fn new(offset: TimeDelta) -> Self {
+        OffsetClock { offset }
+    }
+}
+
+impl Clock for OffsetClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now() + self.offset
+    }
+}
+
+static CLOCK: RwLock<Option<Arc<dyn Clock>>> = RwLock::new(None);
+
+pub fn set_clock(clock: Arc<dyn Clock>) {
+    if let Ok(mut val) = CLOCK.write() {
+        *val = Some(clock);
+    }
+}
+
+pub fn clear_clock() {
+    if let Ok(mut val) = CLOCK.write() {
+        *val = None;
+    }
+}
+
+pub fn now() -> DateTime<Utc> {
+    let clock = match CLOCK.read() {
+        Ok(val) => val.clone(),
+        Err(_) => None,
+    };
+
+    match clock {
+        Some(clock) => clock.now(),
+        None => Utc::now(),
+    }
+}
@@ -0,0 +1,284 @@
+use serde_json::Value;
+
+use crate::{
+    errors::Error,
+    jws::GeneralJws,
+    resolver::{absolute_id, did_from_url, DidResolver},
+    verification_method::{has_key_material, VerificationMethod},
+};
+
+pub const CONDITIONAL_PROOF_2022: &str = "ConditionalProof2022";
+pub const CONDITION_THRESHOLD: &str = "conditionThreshold";
+pub const CONDITION_WEIGHTED_THRESHOLD: &str = "conditionWeightedThreshold";
+pub const CONDITION_AND: &str = "conditionAnd";
+pub const CONDITION_OR: &str = "conditionOr";
+pub const CONDITION_DELEGATED: &str = "conditionDelegated";
+pub const MAX_CONDITION_DEPTH: usize = 8;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Condition {
+    Key(Box<VerificationMethod>),
+    Threshold {
+        id: String,
+        threshold: u64,
+        conditions: Vec<Condition>,
+    },
+    WeightedThreshold {
+        id: String,
+        threshold: u64,
+        conditions: Vec<(Condition, u64)>,
+    },
+    And {
+        id: String,
+        conditions: Vec<Condition>,
+    },
+    Or {
+        id: String,
+        conditions: Vec<Condition>,
+    },
+    Delegated {
+        id: String,
+        delegate: String,
+    },
+}
+
+fn document_did(document: &Value) -> Result<&str, Error> {
+    match document.get("id").and_then(Value::as_str) {
+        Some(val) => Ok(val),
+        None => Err(Error::DID_RESOLUTION_ERROR),
+    }
+}
+
+fn find_method<'a>(document: &'a Value, did: &str, method_id: &str) -> Result<&'a Value, Error> {
+    let method_id = absolute_id(did, method_id);
+    let matches = |method: &&Value| {
+        method
+            .get("id")
+            .and_then(Value::as_str)
+            .map(|id| absolute_id(did, id))
+            == Some(method_id.clone())
+    };
+
+    match document.get("verificationMethod") {
+        Some(Value::Array(methods)) => match methods.iter().find(matches) {
+            Some(val) => Ok(val),
+            None => Err(Error::DID_VERIFICATION_METHOD_NOT_FOUND.with_key(&method_id)),
+        },
+        _ => Err(Error::DID_VERIFICATION_METHOD_NOT_FOUND.with_key(&method_id)),
+    }
+}
+
+fn threshold(method: &Value) -> Result<u64, Error> {
+    match method.get("threshold").and_then(Value::as_u64) {
+        Some(val) if val > 0 => Ok(val),
+        _ => Err(Error::CONDITIONAL_PROOF_MALFORMED),
+    }
+}
+
+fn members<'a>(method: &'a Value, name: &str) -> Result<&'a [Value], Error> {
+    match method.get(name) {
+        Some(Value::Array(val)) if !val.is_empty() => Ok(val),
+        _ => Err(Error::CONDITIONAL_PROOF_MALFORMED),
+    }
+}
+
+impl Condition {
+    pub fn from_document(document: &Value, method_id: &str) -> Result<Self, Error> {
+        let did = document_did(document)?;
+        Self::parse(document, did, find_method(document, did, method_id)?, 0)
+    }
+
+    pub fn resolve(resolver: &impl DidResolver, method_id: &str) -> Result<Self, Error> {
+        Self::from_document(&resolver.resolve(did_from_url(method_id))?, method_id)
+    }
+
+    fn parse(document: &Value, did: &str, method: &Value, depth: usize) -> Result<Self, Error> {
+        if depth > MAX_CONDITION_DEPTH {
+            return Err(Error::CONDITIONAL_PROOF_MALFORMED);
+        }
+
+        let method = match method {
+            Value::String(id) => find_method(document, did, id)?,
+            val => val,
+        };
+
+        if method.get("type").and_then(Value::as_str) != Some(CONDITIONAL_PROOF_2022) {
+            return match has_key_material(method) {
+                true => {
+                    let mut method = VerificationMethod::from_value(method)?;
+                    method.id = absolute_id(did, &method.id);
+                    Ok(Condition::Key(Box::new(method)))
+                }
+                false => Err(Error::CONDITIONAL_PROOF_MALFORMED),
+            };
+        }
+
+        let id = match method.get("id").and_then(Value::as_str) {
+            Some(val) => absolute_id(did, val),
+            None => return Err(Error::CONDITIONAL_PROOF_MALFORMED),
+        };
+        let parse_all = |name: &str| -> Result<Vec<Condition>, Error> {
+            members(method, name)?
+                .iter()
+                .map(|member| Self::parse(document, did, member, depth + 1))
+                .collect()
+        };
+
+        if method.get(CONDITION_THRESHOLD).is_some() {
+            Ok(Condition::Threshold {
+                id,
+                threshold: threshold(method)?,
+                conditions: parse_all(CONDITION_THRESHOLD)?,
+            })
+        } else if method.get(CONDITION_WEIGHTED_THRESHOLD).is_some() {
+            let conditions = members(method, CONDITION_WEIGHTED_THRESHOLD)?
+                .iter()
+                .map(|member| {
+                    match (
+                        member.get("condition"),
+                        member.get("weight").and_then(Value::as_u64),
+                    ) {
+                        (Some(condition), Some(weight)) => {
+                            Ok((Self::parse(document, did, condition, depth + 1)?, weight))
+                        }
+                        _ => Err(Error::CONDITIONAL_PROOF_MALFORMED),
+                    }
+                })
+                .collect::<Result<Vec<(Condition, u64)>, Error>>()?;
+
+            Ok(Condition::WeightedThreshold {
+                id,
+                threshold: threshold(method)?,
+                conditions,
+            })
+        } else if method.get(CONDITION_AND).is_some() {
+            Ok(Condition::And {
+                id,
+                conditions: parse_all(CONDITION_AND)?,
+            })
+        } else if method.get(CONDITION_OR).is_some() {
+            Ok(Condition::Or {
+                id,
+                conditions: parse_all(CONDITION_OR)?,
+            })
+        } else {
+            match method.get(CONDITION_DELEGATED).and_then(Value::as_str) {
+                Some(delegate) => Ok(Condition::Delegated {
+                    id,
+                    delegate: absolute_id(did, delegate),
+                }),
+                None => Err(Error::CONDITIONAL_PROOF_MALFORMED),
+            }
+        }
+    }
+
+    pub fn id(&self) -> &str {
+        match self {
+            Condition::Key(method) => &method.id,
+            Condition::Threshold { id, .. }
+            | Condition::WeightedThreshold { id, .. }
+            | Condition::And { id, .. }
+            | Condition::Or { id, .. }
+            | Condition::Delegated { id, .. } => id,
+        }
+    }
+
+    pub fn evaluate(
+        &self,
+        verify: &mut impl FnMut(&VerificationMethod) -> bool,
+        resolver: &impl DidResolver,
+    ) -> Result<bool, Error> {
+        self.evaluate_at(verify, resolver, 0)
+    }
+
+    fn evaluate_at(
+        &self,
+        verify: &mut impl FnMut(&VerificationMethod) -> bool,
+        resolver: &impl DidResolver,
+        depth: usize,
+    ) -> Result<bool, Error> {
+        if depth > MAX_CONDITION_DEPTH {
+            return Err(Error::CONDITIONAL_PROOF_MALFORMED);
+        }
+
+        match self {
+            Condition::Key(method) => Ok(verify(method)),
+            Condition::Threshold {
+                threshold,
+                conditions,
+                ..
+            } => {
+                let mut satisfied = 0u64;
+                for condition in conditions {
+                    if condition.evaluate_at(verify, resolver, depth + 1)? {
+                        satisfied += 1;
+                    }
+                }
+                Ok(satisfied >= *threshold)
+            }
+            Condition::WeightedThreshold {
+                threshold,
+                conditions,
+                ..
+            } => {
+                let mut weight = 0u64;
+                for (condition, value) in conditions {
+                    if condition.evaluate_at(verify, resolver, depth + 1)? {
+                        weight = weight.saturating_add(*value);
+                    }
+                }
+                Ok(weight >= *threshold)
+            }
+            Condition::And { conditions, .. } => {
+                for condition in conditions {
+                    if !condition.evaluate_at(verify, resolver, depth + 1)? {
+                        return Ok(false);
+                    }
+                }
+                Ok(true)
+            }
+            Condition::Or { conditions, .. } => {
+                for condition in conditions {
+                    if condition.evaluate_at(verify, resolver, depth + 1)? {
+                        return Ok(true);
+                    }
+                }
+                Ok(false)
+            }
+            Condition::Delegated { delegate, .. } => {
+                let document = resolver.resolve(did_from_url(delegate))?;
+                let did = document_did(&document)?;
+                let delegated = Self::parse(
+                    &document,
+                    did,
+                    find_method(&document, did, delegate)?,
+                    depth + 1,
+                )?;
+                delegated.evaluate_at(verify, resolver, depth + 1)
+            }
+        }
+    }
+
+    pub fn verify_jws(
+        &self,
+        jws: &GeneralJws,
+        resolver: &impl DidResolver,
+    ) -> Result<Vec<String>, Error> {
+        let mut signers: Vec<String> = Vec::new();
+        let mut verify = |method: &VerificationMethod| {
+            let verified = jws.signatures.iter().any(|signature| {
+                signature.kid().as_deref() == Some(method.id.as_str())
+                    && matches!(jws.verify_with_key(signature, &method.key), Ok(true))
+            });
+            if verified && !signers.contains(&method.id) {
+                signers.push(method.id.clone());
+            }
+            verified
+        };
+
+        match self.evaluate(&mut verify, resolver)? {
+            true => Ok(signers),
+            false => Err(Error::CONDITIONAL_PROOF_NOT_SATISFIED),
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use coset::{
+    cbor::value::Value, iana, sig_structure_data, CborSerializable, CoseKey, CoseSign1Builder,
+    Header, HeaderBuilder, KeyType, Label, RegisteredLabelWithPrivate, SignatureContext,
+    TaggedCborSerializable,
+};
+
+use crate::{
+    algorithms::Algorithm,
+    crypto::{any::AnyVerifyingKey, SignFromKey, VerifyFromKey},
+    errors::Error,
+    jwk::Jwk,
+    log, signer,
+    verifier::VerificationOptions,
+};
+
+pub fn algorithm_to_cose(alg: Algorithm) -> Result<iana::Algorithm, Error> {
+    match alg {
+        Algorithm::RS256 => Ok(iana::Algorithm::RS256),
+        Algorithm::RS384 => Ok(iana::Algorithm::RS384),
+        Algorithm::RS512 => Ok(iana::Algorithm::RS512),
+        Algorithm::PS256 => Ok(iana::Algorithm::PS256),
+        Algorithm::PS384 => Ok(iana::Algorithm::PS384),
+        Algorithm::PS512 => Ok(iana::Algorithm::PS512),
+        Algorithm::ES256 => Ok(iana::Algorithm::ES256),
+        Algorithm::ES384 => Ok(iana::Algorithm::ES384),
+        Algorithm::ES512 => Ok(iana::Algorithm::ES512),
+        Algorithm::ES256K => Ok(iana::Algorithm::ES256K),
+        Algorithm::EdDSA => Ok(iana::Algorithm::EdDSA),
+        _ => Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
+    }
+}
+
+pub fn algorithm_from_cose(alg: &coset::Algorithm) -> Result<Algorithm, Error> {
+    match alg {
+        RegisteredLabelWithPrivate::Assigned(iana::Algorithm::RS256) => Ok(Algorithm::RS256),
+        RegisteredLabelWithPrivate::Assigned(iana::Algorithm::RS384) => Ok(Algorithm::RS384),
+        RegisteredLabelWithPrivate::Assigned(iana::Algorithm::RS512) => Ok(Algorithm::RS512),
+        RegisteredLabelWithPrivate::Assigned(iana::Algorithm::PS256) => Ok(Algorithm::PS256),
+        RegisteredLabelWithPrivate::Assigned(iana::Algorithm::PS384) => Ok(Algorithm::PS384),
+        RegisteredLabelWithPrivate::Assigned(iana::Algorithm::PS512) => Ok(Algorithm::PS512),
+        RegisteredLabelWithPrivate::Assigned(iana::Algorithm::ES256) => Ok(Algorithm::ES256),
+        RegisteredLabelWithPrivate::Assigned(iana::Algorithm::ES384) => Ok(Algorithm::ES384),
+        RegisteredLabelWithPrivate::Assigned(iana::Algorithm::ES512) => Ok(Algorithm::ES512),
+        RegisteredLabelWithPrivate::Assigned(iana::Algorithm::ES256K) => Ok(Algorithm::ES256K),
+        RegisteredLabelWithPrivate::Assigned(iana::Algorithm::EdDSA) => Ok(Algorithm::EdDSA),
+        _ => Err(Error::UNKNOWN_ALGORITHM),
+    }
+}
+
+fn key_param(key: &CoseKey, label: i64) -> Result<&[u8], Error> {
+    match key
+        .params
+        .iter()
+        .find(|(name, _)| *name == Label::Int(label))
+    {
+        Some((_, Value::Bytes(val))) => Ok(val),
+        _ => Err(Error::COSE_MALFORMED),
+    }
+}
+
+fn key_curve(key: &CoseKey) -> Option<i64> {
+    key.params
+        .iter()
+        .find(|(name, _)| *name == Label::Int(iana::Ec2KeyParameter::Crv as i64))
+        .and_then(|(_, val)| val.as_integer())
+        .and_then(|val| i64::try_from(val).ok())
+}
+
+pub fn verifying_key_from_cose(key: &CoseKey) -> Result<AnyVerifyingKey, Error> {
+    let crv = match key_curve(key) {
+        Some(val) if val == iana::EllipticCurve::P_256 as i64 => Some("P-256"),
+        Some(val) if val == iana::EllipticCurve::P_384 as i64 => Some("P-384"),
+        Some(val) if val == iana::EllipticCurve::P_521 as i64 => Some("P-521"),
+        Some(val) if val == iana::EllipticCurve::Secp256k1 as i64 => Some("secp256k1"),
+        Some(val) if val == iana::EllipticCurve::Ed25519 as i64 => Some("Ed25519"),
+        _ => None,
+    };
+
+    let verifying_key = match (&key.kty, crv) {
+        (KeyType::Assigned(iana::KeyType::EC2), Some(crv)) if crv != "Ed25519" => {
+            AnyVerifyingKey::from_jwk(&Jwk {
+                kty: String::from("EC"),
+                crv: Some(String::from(crv)),
+                x: Some(base64_url::encode(key_param(
+                    key,
+                    iana::Ec2KeyParameter::X as i64,
+                )?)),
+                y: Some(base64_url::encode(key_param(
+                    key,
+                    iana::Ec2KeyParameter::Y as i64,
+                )?)),
+                ..Default::default()
+            })
+        }
+        (KeyType::Assigned(iana::KeyType::OKP), Some("Ed25519")) => {
+            AnyVerifyingKey::from_jwk(&Jwk {
+                kty: String::from("OKP"),
+                crv: Some(String::from("Ed25519")),
+                x: Some(base64_url::encode(key_param(
+                    key,
+                    iana::OkpKeyParameter::X as i64,
+                )?)),
+                ..Default::default()
+            })
+        }
+        #[cfg(feature = "rsa")]
+        (KeyType::Assigned(iana::KeyType::RSA), _) => {
+            let n = key_param(key, iana::RsaKeyParameter::N as i64)?;
+            let e = key_param(key, iana::RsaKeyParameter::E as i64)?;
+            Ok(AnyVerifyingKey::Rsa(
+                crate::crypto::rsa::RsaVerifyingKey::from_components(
+                    rsa::BigUint::from_bytes_be(n),
+                    rsa::BigUint::from_bytes_be(e),
+                )?,
+                Algorithm::RS256,
+            ))
+        }
+        _ => return Err(Error::COSE_KEY_UNSUPPORTED),
+    };
+
+    match (verifying_key, &key.alg) {
+        (Err(error), _) if error == Error::JWK_KEY_TYPE_MISMATCH => {
+            Err(Error::COSE_KEY_UNSUPPORTED)
+        }
+        (Ok(verifying_key), Some(alg)) => {
+            Ok(verifying_key.with_algorithm(algorithm_from_cose(alg)?))
+        }
+        (verifying_key, _) => verifying_key,
+    }
+}
+
+pub fn protected_header(alg: Algorithm, kid: &[u8]) -> Result<Header, Error> {
+    Ok(HeaderBuilder::new()
+        .algorithm(algorithm_to_cose(alg)?)
+        .key_id(kid.to_vec())
+        .build())
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoseSign1(pub coset::CoseSign1);
+
+impl CoseSign1 {
+    fn create(
+        protected: Header,
+        unprotected: Header,
+        payload: &[u8],
+        detached: bool,
+        external_aad: &[u8],
+        key: impl SignFromKey,
+    ) -> Result<Self, Error> {
+        let alg = match &protected.alg {
+            Some(val) => algorithm_from_cose(val)?,
+            None => return Err(Error::UNKNOWN_ALGORITHM),
+        };
+
+        let mut sign1 = CoseSign1Builder::new()
+            .protected(protected)
+            .unprotected(unprotected)
+            .build();
+
+        let signature = signer::sign(sign1.tbs_detached_data(payload, external_aad), key, alg)?;
+        sign1.signature = match base64_url::decode(&signature) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::DECODING_ERROR.with_source(error));
+            }
+        };
+
+        if !detached {
+            sign1.payload = Some(payload.to_vec());
+        }
+        Ok(CoseSign1(sign1))
+    }
+
+    pub fn sign(
+        protected: Header,
+        unprotected: Header,
+        payload: &[u8],
+        external_aad: &[u8],
+        key: impl SignFromKey,
+    ) -> Result<Self, Error> {
+        Self::create(protected, unprotected, payload, false, external_aad, key)
+    }
+
+    pub fn sign_detached(
+        protected: Header,
+        unprotected: Header,
+        payload: &[u8],
+        external_aad: &[u8],
+        key: impl SignFromKey,
+    ) -> Result<Self, Error> {
+        Self::create(protected, unprotected, payload, true, external_aad, key)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let sign1 = match coset::CoseSign1::from_tagged_slice(bytes) {
+            Ok(val) => Ok(val),
+            Err(_) => coset::CoseSign1::from_slice(bytes),
+        };
+
+        match sign1 {
+            Ok(val) => Ok(CoseSign1(val)),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::COSE_MALFORMED.with_source(error.to_string()))
+            }
+        }
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        match self.0.clone().to_tagged_vec() {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENCODING_ERROR.with_source(error.to_string()))
+            }
+        }
+    }
+
+    pub fn algorithm(&self) -> Result<Algorithm, Error> {
+        match &self.0.protected.header.alg {
+            Some(val) => algorithm_from_cose(val),
+            None => Err(Error::UNKNOWN_ALGORITHM),
+        }
+    }
+
+    pub fn key_id(&self) -> &[u8] {
+        match self.0.protected.header.key_id.is_empty() {
+            true => &self.0.unprotected.key_id,
+            false => &self.0.protected.header.key_id,
+        }
+    }
+
+    pub fn payload(&self) -> Option<&[u8]> {
+        self.0.payload.as_deref()
+    }
+
+    pub fn verify(&self, external_aad: &[u8], key: impl VerifyFromKey) -> Result<bool, Error> {
+        match &self.0.payload {
+            Some(payload) => self.verify_detached(payload, external_aad, key),
+            None => Err(Error::COSE_MISSING_PAYLOAD),
+        }
+    }
+
+    pub fn verify_with_options(
+        &self,
+        external_aad: &[u8],
+        key: impl VerifyFromKey,
+        options: &VerificationOptions,
+    ) -> Result<bool, Error> {
+        options.algorithm_policy.check(self.algorithm()?)?;
+        options.apply(self.verify(external_aad, key))
+    }
+
+    pub fn verify_detached(
+        &self,
+        payload: &[u8],
+        external_aad: &[u8],
+        key: impl VerifyFromKey,
+    ) -> Result<bool, Error> {
+        let tbs = sig_structure_data(
+            SignatureContext::CoseSign1,
+            self.0.protected.clone(),
+            None,
+            external_aad,
+            payload,
+        );
+
+        key.verify_bytes(
+            &tbs,
+            &base64_url::encode(&self.0.signature),
+            self.algorithm()?,
+        )
+    }
+}
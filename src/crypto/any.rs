@@ -0,0 +1,1129 @@
+#[cfg(feature = "mldsa")]
+use pkcs8::{
+    der::{asn1::BitStringRef, Encode},
+    AlgorithmIdentifierRef,
+};
+use pkcs8::{
+    pkcs5::pbes2,
+    spki::{EncodePublicKey, SubjectPublicKeyInfoRef},
+    DecodePrivateKey, EncodePrivateKey, EncryptedPrivateKeyInfo, LineEnding, ObjectIdentifier,
+    PrivateKeyInfo, SecretDocument,
+};
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroizing;
+
+use crate::{algorithms::Algorithm, errors::Error, jwk::Jwk, log, multicodec};
+
+use super::entropy;
+
+#[cfg(feature = "es256k")]
+use super::ecdsa::_256k::{P256kSigningKey, P256kVerifyingKey};
+#[cfg(feature = "es384")]
+use super::ecdsa::_384::{P384SigningKey, P384VerifyingKey};
+#[cfg(feature = "es512")]
+use super::ecdsa::_512::{P512SigningKey, P512VerifyingKey};
+#[cfg(feature = "eddsa")]
+use super::eddsa::{EDDSASigningKey, EDDSAVerifyingKey};
+#[cfg(feature = "rsa")]
+use super::rsa::{RsaSigningKey, RsaVerifyingKey};
+#[cfg(feature = "mldsa")]
+use super::{
+    composite::{MLDSA65Ed25519SigningKey, MLDSA65Ed25519VerifyingKey},
+    mldsa::{MLDSA65SigningKey, MLDSA65VerifyingKey, MLDSA65_OID, MLDSA65_SEED_LENGTH},
+};
+use super::{
+    decode_hex,
+    ecdsa::{
+        ec_jwk, NonceMode,
+        _256::{P256SigningKey, P256VerifyingKey},
+    },
+    encode_hex_prefixed,
+    hmac::HMACKey,
+    signature::Signature,
+    x509, SignFromKey, VerifyFromKey,
+};
+
+#[cfg(feature = "rsa")]
+const RSA_KEY_SIZE: usize = 2048;
+
+pub const PKCS8_PBKDF2_ITERATIONS: u32 = 600_000;
+const ENCRYPTED_PRIVATE_KEY_LABEL: &str = "ENCRYPTED PRIVATE KEY";
+#[cfg(feature = "rsa")]
+const RSA_ENCRYPTION_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1");
+const EC_PUBLIC_KEY_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.2.1");
+const P256_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.3.1.7");
+#[cfg(feature = "es256k")]
+const P256K_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.10");
+#[cfg(feature = "es384")]
+const P384_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.34");
+#[cfg(feature = "es512")]
+const P521_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.132.0.35");
+#[cfg(feature = "eddsa")]
+const ED25519_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+#[cfg(feature = "mldsa")]
+const MLDSA_SEED_TAG: u8 = 0x80;
+
+#[derive(Debug)]
+pub enum AnySigningKey {
+    Hmac(HMACKey, Algorithm),
+    #[cfg(feature = "rsa")]
+    Rsa(RsaSigningKey, Algorithm),
+    P256(P256SigningKey),
+    #[cfg(feature = "es256k")]
+    P256k(P256kSigningKey),
+    #[cfg(feature = "es384")]
+    P384(P384SigningKey),
+    #[cfg(feature = "es512")]
+    P512(P512SigningKey),
+    #[cfg(feature = "eddsa")]
+    EdDSA(EDDSASigningKey),
+    #[cfg(feature = "mldsa")]
+    MLDSA65(MLDSA65SigningKey),
+    #[cfg(feature = "mldsa")]
+    MLDSA65Ed25519(MLDSA65Ed25519SigningKey),
+}
+
+impl AnySigningKey {
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            AnySigningKey::Hmac(_, alg) => *alg,
+            #[cfg(feature = "rsa")]
+            AnySigningKey::Rsa(_, alg) => *alg,
+            AnySigningKey::P256(_) => Algorithm::ES256,
+            #[cfg(feature = "es256k")]
+            AnySigningKey::P256k(_) => Algorithm::ES256K,
+            #[cfg(feature = "es384")]
+            AnySigningKey::P384(_) => Algorithm::ES384,
+            #[cfg(feature = "es512")]
+            AnySigningKey::P512(_) => Algorithm::ES512,
+            #[cfg(feature = "eddsa")]
+            AnySigningKey::EdDSA(_) => Algorithm::EdDSA,
+            #[cfg(feature = "mldsa")]
+            AnySigningKey::MLDSA65(_) => Algorithm::MLDSA65,
+            #[cfg(feature = "mldsa")]
+            AnySigningKey::MLDSA65Ed25519(_) => Algorithm::MLDSA65Ed25519,
+        }
+    }
+
+    pub fn generate(alg: Algorithm) -> Result<Self, Error> {
+        Self::generate_with_rng(alg, &mut entropy::rng()?)
+    }
+
+    pub fn generate_with_rng(
+        alg: Algorithm,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<Self, Error> {
+        match alg {
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+                let mut secret = [0u8; 64];
+                rng.fill_bytes(&mut secret);
+                Ok(AnySigningKey::Hmac(
+                    HMACKey::new(base64_url::encode(&secret)),
+                    alg,
+                ))
+            }
+            #[cfg(feature = "rsa")]
+            Algorithm::RS256
+            | Algorithm::RS384
+            | Algorithm::RS512
+            | Algorithm::PS256
+            | Algorithm::PS384
+            | Algorithm::PS512 => Ok(AnySigningKey::Rsa(
+                RsaSigningKey::generate_with_rng(RSA_KEY_SIZE, rng)?,
+                alg,
+            )),
+            Algorithm::ES256 => {
+                Self::from_bytes(alg, &p256::ecdsa::SigningKey::random(&mut *rng).to_bytes())
+            }
+            #[cfg(feature = "es256k")]
+            Algorithm::ES256K => {
+                Self::from_bytes(alg, &k256::ecdsa::SigningKey::random(&mut *rng).to_bytes())
+            }
+            #[cfg(feature = "es384")]
+            Algorithm::ES384 => {
+                Self::from_bytes(alg, &p384::ecdsa::SigningKey::random(&mut *rng).to_bytes())
+            }
+            #[cfg(feature = "es512")]
+            Algorithm::ES512 => {
+                Self::from_bytes(alg, &p521::ecdsa::SigningKey::random(&mut *rng).to_bytes())
+            }
+            #[cfg(feature = "eddsa")]
+            Algorithm::EdDSA => {
+                let mut secret = [0u8; 32];
+                rng.fill_bytes(&mut secret);
+                Self::from_bytes(alg, &secret)
+            }
+            #[cfg(feature = "mldsa")]
+            Algorithm::MLDSA65 => Ok(AnySigningKey::MLDSA65(
+                MLDSA65SigningKey::generate_with_rng(rng),
+            )),
+            #[cfg(feature = "mldsa")]
+            Algorithm::MLDSA65Ed25519 => Ok(AnySigningKey::MLDSA65Ed25519(
+                MLDSA65Ed25519SigningKey::generate_with_rng(rng),
+            )),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
+        }
+    }
+
+    pub fn from_bytes(alg: Algorithm, bytes: &[u8]) -> Result<Self, Error> {
+        match alg {
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+                match String::from_utf8(bytes.to_vec()) {
+                    Ok(val) => Ok(AnySigningKey::Hmac(HMACKey::new(val), alg)),
+                    Err(error) => {
+                        log::error(error.to_string().as_str());
+                        Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error))
+                    }
+                }
+            }
+            #[cfg(feature = "rsa")]
+            Algorithm::RS256
+            | Algorithm::RS384
+            | Algorithm::RS512
+            | Algorithm::PS256
+            | Algorithm::PS384
+            | Algorithm::PS512 => Ok(AnySigningKey::Rsa(RsaSigningKey::from_bytes(bytes)?, alg)),
+            Algorithm::ES256 => Ok(AnySigningKey::P256(P256SigningKey::from_bytes(bytes)?)),
+            #[cfg(feature = "es256k")]
+            Algorithm::ES256K => Ok(AnySigningKey::P256k(P256kSigningKey::from_bytes(bytes)?)),
+            #[cfg(feature = "es384")]
+            Algorithm::ES384 => Ok(AnySigningKey::P384(P384SigningKey::from_bytes(bytes)?)),
+            #[cfg(feature = "es512")]
+            Algorithm::ES512 => Ok(AnySigningKey::P512(P512SigningKey::from_bytes(bytes)?)),
+            #[cfg(feature = "eddsa")]
+            Algorithm::EdDSA => Ok(AnySigningKey::EdDSA(EDDSASigningKey::from_bytes(
+                &mut bytes.to_vec(),
+            )?)),
+            #[cfg(feature = "mldsa")]
+            Algorithm::MLDSA65 => Ok(AnySigningKey::MLDSA65(MLDSA65SigningKey::from_bytes(
+                bytes,
+            )?)),
+            #[cfg(feature = "mldsa")]
+            Algorithm::MLDSA65Ed25519 => Ok(AnySigningKey::MLDSA65Ed25519(
+                MLDSA65Ed25519SigningKey::from_bytes(bytes)?,
+            )),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
+        }
+    }
+
+    pub fn with_nonce_mode(self, nonce_mode: NonceMode) -> Self {
+        match self {
+            AnySigningKey::P256(key) => AnySigningKey::P256(key.with_nonce_mode(nonce_mode)),
+            #[cfg(feature = "es256k")]
+            AnySigningKey::P256k(key) => AnySigningKey::P256k(key.with_nonce_mode(nonce_mode)),
+            key => key,
+        }
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.verifying_key().fingerprint()
+    }
+
+    pub fn nonce_mode(&self) -> Option<NonceMode> {
+        match self {
+            AnySigningKey::P256(key) => Some(key.nonce_mode()),
+            #[cfg(feature = "es256k")]
+            AnySigningKey::P256k(key) => Some(key.nonce_mode()),
+            #[cfg(feature = "es384")]
+            AnySigningKey::P384(_) => Some(NonceMode::Deterministic),
+            #[cfg(feature = "es512")]
+            AnySigningKey::P512(_) => Some(NonceMode::Deterministic),
+            _ => None,
+        }
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        match self {
+            AnySigningKey::Hmac(key, _) => Ok(key.to_bytes()),
+            #[cfg(feature = "rsa")]
+            AnySigningKey::Rsa(key, _) => key.to_bytes(),
+            AnySigningKey::P256(key) => Ok(key.to_bytes()),
+            #[cfg(feature = "es256k")]
+            AnySigningKey::P256k(key) => Ok(key.to_bytes()),
+            #[cfg(feature = "es384")]
+            AnySigningKey::P384(key) => Ok(key.to_bytes()),
+            #[cfg(feature = "es512")]
+            AnySigningKey::P512(key) => Ok(key.to_bytes()),
+            #[cfg(feature = "eddsa")]
+            AnySigningKey::EdDSA(key) => Ok(key.to_bytes()),
+            #[cfg(feature = "mldsa")]
+            AnySigningKey::MLDSA65(key) => Ok(key.to_bytes()),
+            #[cfg(feature = "mldsa")]
+            AnySigningKey::MLDSA65Ed25519(key) => Ok(key.to_bytes()),
+        }
+    }
+
+    pub fn to_private_bytes(&self) -> Result<Zeroizing<Vec<u8>>, Error> {
+        Ok(Zeroizing::new(self.to_bytes()?))
+    }
+
+    pub fn to_public_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.verifying_key().to_bytes()
+    }
+
+    pub fn from_hex(alg: Algorithm, key: &str) -> Result<Self, Error> {
+        Self::from_bytes(alg, &decode_hex(key)?)
+    }
+
+    pub fn to_hex(&self) -> Result<String, Error> {
+        Ok(encode_hex_prefixed(&self.to_bytes()?))
+    }
+
+    pub fn from_sec1_der(der: &[u8]) -> Result<Self, Error> {
+        if let Ok(val) = p256::SecretKey::from_sec1_der(der) {
+            return Self::from_bytes(Algorithm::ES256, &val.to_bytes());
+        }
+        #[cfg(feature = "es256k")]
+        if let Ok(val) = k256::SecretKey::from_sec1_der(der) {
+            return Self::from_bytes(Algorithm::ES256K, &val.to_bytes());
+        }
+        #[cfg(feature = "es384")]
+        if let Ok(val) = p384::SecretKey::from_sec1_der(der) {
+            return Self::from_bytes(Algorithm::ES384, &val.to_bytes());
+        }
+        #[cfg(feature = "es512")]
+        if let Ok(val) = p521::SecretKey::from_sec1_der(der) {
+            return Self::from_bytes(Algorithm::ES512, &val.to_bytes());
+        }
+
+        Err(Error::EC_PEM_ERROR)
+    }
+
+    pub fn from_multicodec(bytes: &[u8]) -> Result<Self, Error> {
+        let (code, key) = multicodec::decode(bytes)?;
+        let alg = match code {
+            multicodec::P256_PRIV => Algorithm::ES256,
+            #[cfg(feature = "es256k")]
+            multicodec::SECP256K1_PRIV => Algorithm::ES256K,
+            #[cfg(feature = "es384")]
+            multicodec::P384_PRIV => Algorithm::ES384,
+            #[cfg(feature = "es512")]
+            multicodec::P521_PRIV => Algorithm::ES512,
+            #[cfg(feature = "eddsa")]
+            multicodec::ED25519_PRIV => Algorithm::EdDSA,
+            _ => return Err(Error::MULTICODEC_ERROR),
+        };
+
+        Self::from_bytes(alg, key)
+    }
+
+    pub fn from_pkcs8_der(der: &[u8]) -> Result<Self, Error> {
+        let info = match PrivateKeyInfo::try_from(der) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
+            }
+        };
+
+        match info.algorithm.oid {
+            #[cfg(feature = "rsa")]
+            RSA_ENCRYPTION_OID => Ok(AnySigningKey::Rsa(
+                RsaSigningKey::from_bytes(der)?,
+                Algorithm::RS256,
+            )),
+            #[cfg(feature = "eddsa")]
+            ED25519_OID => Self::from_bytes(
+                Algorithm::EdDSA,
+                &decode_private_key::<ed25519_dalek::SigningKey>(der)?.to_bytes(),
+            ),
+            EC_PUBLIC_KEY_OID => match info.algorithm.parameters_oid() {
+                Ok(P256_OID) => Self::from_bytes(
+                    Algorithm::ES256,
+                    &decode_private_key::<p256::SecretKey>(der)?.to_bytes(),
+                ),
+                #[cfg(feature = "es256k")]
+                Ok(P256K_OID) => Self::from_bytes(
+                    Algorithm::ES256K,
+                    &decode_private_key::<k256::SecretKey>(der)?.to_bytes(),
+                ),
+                #[cfg(feature = "es384")]
+                Ok(P384_OID) => Self::from_bytes(
+                    Algorithm::ES384,
+                    &decode_private_key::<p384::SecretKey>(der)?.to_bytes(),
+                ),
+                #[cfg(feature = "es512")]
+                Ok(P521_OID) => Self::from_bytes(
+                    Algorithm::ES512,
+                    &decode_private_key::<p521::SecretKey>(der)?.to_bytes(),
+                ),
+                _ => Err(Error::PKCS8_UNSUPPORTED_KEY_TYPE),
+            },
+            #[cfg(feature = "mldsa")]
+            MLDSA65_OID => {
+                let private_key = info.private_key;
+                let seed = match private_key {
+                    [MLDSA_SEED_TAG, 0x20, seed @ ..] => seed,
+                    [0x30, 0x82, _, _, 0x04, 0x20, rest @ ..] if rest.len() > 32 => &rest[..32],
+                    _ => return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR),
+                };
+
+                Self::from_bytes(Algorithm::MLDSA65, seed)
+            }
+            _ => Err(Error::PKCS8_UNSUPPORTED_KEY_TYPE),
+        }
+    }
+
+    pub fn to_pkcs8_der(&self) -> Result<Zeroizing<Vec<u8>>, Error> {
+        let document = match self {
+            #[cfg(feature = "rsa")]
+            AnySigningKey::Rsa(key, _) => return Ok(Zeroizing::new(key.to_bytes()?)),
+            AnySigningKey::P256(key) => {
+                encode_private_key(&secret_key::<p256::NistP256>(&key.to_bytes())?)?
+            }
+            #[cfg(feature = "es256k")]
+            AnySigningKey::P256k(key) => {
+                encode_private_key(&secret_key::<k256::Secp256k1>(&key.to_bytes())?)?
+            }
+            #[cfg(feature = "es384")]
+            AnySigningKey::P384(key) => {
+                encode_private_key(&secret_key::<p384::NistP384>(&key.to_bytes())?)?
+            }
+            #[cfg(feature = "es512")]
+            AnySigningKey::P512(key) => {
+                encode_private_key(&secret_key::<p521::NistP521>(&key.to_bytes())?)?
+            }
+            #[cfg(feature = "eddsa")]
+            AnySigningKey::EdDSA(key) => {
+                let bytes = Zeroizing::new(key.to_bytes());
+                match bytes.as_slice().try_into() {
+                    Ok(val) => encode_private_key(&ed25519_dalek::SigningKey::from_bytes(val))?,
+                    Err(_) => return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR),
+                }
+            }
+            #[cfg(feature = "mldsa")]
+            AnySigningKey::MLDSA65(key) => {
+                let mut private_key =
+                    Zeroizing::new(vec![MLDSA_SEED_TAG, MLDSA65_SEED_LENGTH as u8]);
+                private_key.extend_from_slice(&key.to_bytes());
+
+                let info = PrivateKeyInfo::new(
+                    AlgorithmIdentifierRef {
+                        oid: MLDSA65_OID,
+                        parameters: None,
+                    },
+                    &private_key,
+                );
+                match SecretDocument::try_from(info) {
+                    Ok(val) => val,
+                    Err(error) => {
+                        log::error(error.to_string().as_str());
+                        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
+                    }
+                }
+            }
+            AnySigningKey::Hmac(_, _) => return Err(Error::PKCS8_UNSUPPORTED_KEY_TYPE),
+            #[cfg(feature = "mldsa")]
+            AnySigningKey::MLDSA65Ed25519(_) => return Err(Error::PKCS8_UNSUPPORTED_KEY_TYPE),
+        };
+
+        Ok(Zeroizing::new(document.as_bytes().to_vec()))
+    }
+
+    pub fn from_encrypted_pem(pem: &str, passphrase: &str) -> Result<Self, Error> {
+        let (label, document) = match SecretDocument::from_pem(pem) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
+            }
+        };
+
+        if label != ENCRYPTED_PRIVATE_KEY_LABEL {
+            return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+        }
+
+        let info = match EncryptedPrivateKeyInfo::try_from(document.as_bytes()) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
+            }
+        };
+
+        match info.decrypt(passphrase) {
+            Ok(val) => Self::from_pkcs8_der(val.as_bytes()),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::PKCS8_DECRYPTION_FAILED.with_source(error))
+            }
+        }
+    }
+
+    pub fn to_encrypted_pem(&self, passphrase: &str) -> Result<Zeroizing<String>, Error> {
+        self.to_encrypted_pem_with_iterations(passphrase, PKCS8_PBKDF2_ITERATIONS)
+    }
+
+    pub fn to_encrypted_pem_with_iterations(
+        &self,
+        passphrase: &str,
+        iterations: u32,
+    ) -> Result<Zeroizing<String>, Error> {
+        let der = self.to_pkcs8_der()?;
+        let mut rng = rand::thread_rng();
+        let mut salt = [0u8; 16];
+        let mut iv = [0u8; 16];
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut iv);
+
+        let encrypted = PrivateKeyInfo::try_from(der.as_slice()).and_then(|info| {
+            info.encrypt_with_params(
+                pbes2::Parameters::pbkdf2_sha256_aes256cbc(iterations, &salt, &iv)?,
+                passphrase,
+            )
+        });
+
+        match encrypted.map(|val| val.to_pem(ENCRYPTED_PRIVATE_KEY_LABEL, LineEnding::LF)) {
+            Ok(Ok(val)) => Ok(val),
+            Ok(Err(error)) => {
+                log::error(error.to_string().as_str());
+                Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error))
+            }
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn sign_with_context(&self, content: &[u8], context: &[u8]) -> Result<String, Error> {
+        if context.is_empty() {
+            return self.sign_bytes(content, self.algorithm());
+        }
+
+        match self {
+            #[cfg(feature = "eddsa")]
+            AnySigningKey::EdDSA(key) => key.sign_with_context(content, context),
+            #[cfg(feature = "mldsa")]
+            AnySigningKey::MLDSA65(key) => {
+                if context.len() > super::eddsa::MAX_CONTEXT_LENGTH {
+                    return Err(Error::SIGNATURE_CONTEXT_INVALID);
+                }
+                Ok(base64_url::encode(
+                    &key.sign_with_context(content, context)?,
+                ))
+            }
+            _ => Err(Error::SIGNATURE_CONTEXT_UNSUPPORTED.with_algorithm(self.algorithm())),
+        }
+    }
+
+    pub fn verifying_key(&self) -> AnyVerifyingKey {
+        match self {
+            AnySigningKey::Hmac(key, alg) => AnyVerifyingKey::Hmac(key.clone(), *alg),
+            #[cfg(feature = "rsa")]
+            AnySigningKey::Rsa(key, alg) => AnyVerifyingKey::Rsa(key.verifying_key(), *alg),
+            AnySigningKey::P256(key) => AnyVerifyingKey::P256(key.verifying_key()),
+            #[cfg(feature = "es256k")]
+            AnySigningKey::P256k(key) => AnyVerifyingKey::P256k(key.verifying_key()),
+            #[cfg(feature = "es384")]
+            AnySigningKey::P384(key) => AnyVerifyingKey::P384(key.verifying_key()),
+            #[cfg(feature = "es512")]
+            AnySigningKey::P512(key) => AnyVerifyingKey::P512(key.verifying_key()),
+            #[cfg(feature = "eddsa")]
+            AnySigningKey::EdDSA(key) => AnyVerifyingKey::EdDSA(key.verifying_key()),
+            #[cfg(feature = "mldsa")]
+            AnySigningKey::MLDSA65(key) => AnyVerifyingKey::MLDSA65(key.verifying_key()),
+            #[cfg(feature = "mldsa")]
+            AnySigningKey::MLDSA65Ed25519(key) => {
+                AnyVerifyingKey::MLDSA65Ed25519(key.verifying_key())
+            }
+        }
+    }
+
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        match (jwk.kty.as_str(), jwk.alg.as_deref()) {
+            #[cfg(feature = "mldsa")]
+            ("AKP", Some("ML-DSA-65")) => {
+                return Ok(AnySigningKey::MLDSA65(MLDSA65SigningKey::from_jwk(jwk)?))
+            }
+            #[cfg(feature = "mldsa")]
+            ("AKP", Some("ML-DSA-65-Ed25519")) => {
+                return Ok(AnySigningKey::MLDSA65Ed25519(
+                    MLDSA65Ed25519SigningKey::from_jwk(jwk)?,
+                ))
+            }
+            _ => {}
+        }
+
+        let verifying_key = AnyVerifyingKey::from_jwk(jwk)?;
+        let key = Self::from_bytes(verifying_key.algorithm(), &Jwk::decode_member(&jwk.d)?)?;
+        if key.verifying_key() != verifying_key {
+            return Err(Error::JWK_KEY_TYPE_MISMATCH);
+        }
+
+        Ok(key)
+    }
+
+    pub fn to_jwk(&self) -> Result<Jwk, Error> {
+        let jwk = match self {
+            AnySigningKey::Hmac(_, _) => return Err(Error::JWK_KEY_TYPE_MISMATCH),
+            #[cfg(feature = "rsa")]
+            AnySigningKey::Rsa(_, _) => return Err(Error::JWK_KEY_TYPE_MISMATCH),
+            #[cfg(feature = "mldsa")]
+            AnySigningKey::MLDSA65(key) => key.to_jwk(),
+            #[cfg(feature = "mldsa")]
+            AnySigningKey::MLDSA65Ed25519(key) => key.to_jwk(),
+            _ => Jwk {
+                d: Some(base64_url::encode(&self.to_bytes()?)),
+                ..self.verifying_key().to_jwk()?
+            },
+        };
+
+        Ok(Jwk {
+            alg: Some(String::from(self.algorithm().to_str())),
+            ..jwk
+        })
+    }
+}
+
+fn supports_algorithm(key_alg: Algorithm, alg: Algorithm) -> bool {
+    key_alg == alg || (key_alg == Algorithm::ES256K && alg == Algorithm::ES256KR)
+}
+
+fn decode_private_key<K: DecodePrivateKey>(der: &[u8]) -> Result<K, Error> {
+    match K::from_pkcs8_der(der) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error))
+        }
+    }
+}
+
+fn encode_private_key(key: &impl EncodePrivateKey) -> Result<SecretDocument, Error> {
+    match key.to_pkcs8_der() {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error))
+        }
+    }
+}
+
+fn secret_key<C: elliptic_curve::Curve>(
+    bytes: &[u8],
+) -> Result<elliptic_curve::SecretKey<C>, Error> {
+    match elliptic_curve::SecretKey::from_slice(bytes) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error))
+        }
+    }
+}
+
+impl SignFromKey for AnySigningKey {
+    fn key_fingerprint(&self) -> Option<String> {
+        self.fingerprint().ok()
+    }
+
+    fn sign_bytes(&self, content: &[u8], alg: Algorithm) -> Result<String, Error> {
+        if !supports_algorithm(self.algorithm(), alg) {
+            return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg));
+        }
+
+        match self {
+            AnySigningKey::Hmac(key, _) => key.sign_bytes(content, alg),
+            #[cfg(feature = "rsa")]
+            AnySigningKey::Rsa(key, _) => key.sign_bytes(content, alg),
+            AnySigningKey::P256(key) => key.sign_bytes(content, alg),
+            #[cfg(feature = "es256k")]
+            AnySigningKey::P256k(key) => key.sign_bytes(content, alg),
+            #[cfg(feature = "es384")]
+            AnySigningKey::P384(key) => key.sign_bytes(content, alg),
+            #[cfg(feature = "es512")]
+            AnySigningKey::P512(key) => key.sign_bytes(content, alg),
+            #[cfg(feature = "eddsa")]
+            AnySigningKey::EdDSA(key) => key.sign_bytes(content, alg),
+            #[cfg(feature = "mldsa")]
+            AnySigningKey::MLDSA65(key) => key.sign_bytes(content, alg),
+            #[cfg(feature = "mldsa")]
+            AnySigningKey::MLDSA65Ed25519(key) => key.sign_bytes(content, alg),
+        }
+    }
+
+    fn sign_batch(&self, items: &[&[u8]], alg: Algorithm) -> Result<Vec<Signature>, Error> {
+        match self {
+            #[cfg(feature = "rsa")]
+            AnySigningKey::Rsa(key, _) if alg == self.algorithm() => key.sign_batch(items, alg),
+            _ => items
+                .iter()
+                .map(|content| self.sign_signature(content, alg))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum AnyVerifyingKey {
+    Hmac(HMACKey, Algorithm),
+    #[cfg(feature = "rsa")]
+    Rsa(RsaVerifyingKey, Algorithm),
+    P256(P256VerifyingKey),
+    #[cfg(feature = "es256k")]
+    P256k(P256kVerifyingKey),
+    #[cfg(feature = "es384")]
+    P384(P384VerifyingKey),
+    #[cfg(feature = "es512")]
+    P512(P512VerifyingKey),
+    #[cfg(feature = "eddsa")]
+    EdDSA(EDDSAVerifyingKey),
+    #[cfg(feature = "mldsa")]
+    MLDSA65(MLDSA65VerifyingKey),
+    #[cfg(feature = "mldsa")]
+    MLDSA65Ed25519(MLDSA65Ed25519VerifyingKey),
+}
+
+impl AnyVerifyingKey {
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            AnyVerifyingKey::Hmac(_, alg) => *alg,
+            #[cfg(feature = "rsa")]
+            AnyVerifyingKey::Rsa(_, alg) => *alg,
+            AnyVerifyingKey::P256(_) => Algorithm::ES256,
+            #[cfg(feature = "es256k")]
+            AnyVerifyingKey::P256k(_) => Algorithm::ES256K,
+            #[cfg(feature = "es384")]
+            AnyVerifyingKey::P384(_) => Algorithm::ES384,
+            #[cfg(feature = "es512")]
+            AnyVerifyingKey::P512(_) => Algorithm::ES512,
+            #[cfg(feature = "eddsa")]
+            AnyVerifyingKey::EdDSA(_) => Algorithm::EdDSA,
+            #[cfg(feature = "mldsa")]
+            AnyVerifyingKey::MLDSA65(_) => Algorithm::MLDSA65,
+            #[cfg(feature = "mldsa")]
+            AnyVerifyingKey::MLDSA65Ed25519(_) => Algorithm::MLDSA65Ed25519,
+        }
+    }
+
+    pub(crate) fn with_algorithm(self, alg: Algorithm) -> Self {
+        match self {
+            AnyVerifyingKey::Hmac(key, _) => AnyVerifyingKey::Hmac(key, alg),
+            #[cfg(feature = "rsa")]
+            AnyVerifyingKey::Rsa(key, _) => AnyVerifyingKey::Rsa(key, alg),
+            key => key,
+        }
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        match self {
+            AnyVerifyingKey::Hmac(key, _) => key.fingerprint(),
+            #[cfg(feature = "rsa")]
+            AnyVerifyingKey::Rsa(key, _) => key.fingerprint(),
+            AnyVerifyingKey::P256(key) => key.fingerprint(),
+            #[cfg(feature = "es256k")]
+            AnyVerifyingKey::P256k(key) => key.fingerprint(),
+            #[cfg(feature = "es384")]
+            AnyVerifyingKey::P384(key) => key.fingerprint(),
+            #[cfg(feature = "es512")]
+            AnyVerifyingKey::P512(key) => key.fingerprint(),
+            #[cfg(feature = "eddsa")]
+            AnyVerifyingKey::EdDSA(key) => key.fingerprint(),
+            #[cfg(feature = "mldsa")]
+            AnyVerifyingKey::MLDSA65(key) => key.fingerprint(),
+            #[cfg(feature = "mldsa")]
+            AnyVerifyingKey::MLDSA65Ed25519(key) => key.fingerprint(),
+        }
+    }
+
+    pub fn from_bytes(alg: Algorithm, bytes: &[u8]) -> Result<Self, Error> {
+        match alg {
+            Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512 => {
+                match String::from_utf8(bytes.to_vec()) {
+                    Ok(val) => Ok(AnyVerifyingKey::Hmac(HMACKey::new(val), alg)),
+                    Err(error) => {
+                        log::error(error.to_string().as_str());
+                        Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error))
+                    }
+                }
+            }
+            #[cfg(feature = "rsa")]
+            Algorithm::RS256
+            | Algorithm::RS384
+            | Algorithm::RS512
+            | Algorithm::PS256
+            | Algorithm::PS384
+            | Algorithm::PS512 => Ok(AnyVerifyingKey::Rsa(
+                RsaVerifyingKey::from_spki_der(bytes)?,
+                alg,
+            )),
+            Algorithm::ES256 => Ok(AnyVerifyingKey::P256(P256VerifyingKey::from_bytes(bytes)?)),
+            #[cfg(feature = "es256k")]
+            Algorithm::ES256K => Ok(AnyVerifyingKey::P256k(P256kVerifyingKey::from_bytes(
+                bytes,
+            )?)),
+            #[cfg(feature = "es384")]
+            Algorithm::ES384 => Ok(AnyVerifyingKey::P384(P384VerifyingKey::from_bytes(bytes)?)),
+            #[cfg(feature = "es512")]
+            Algorithm::ES512 => Ok(AnyVerifyingKey::P512(P512VerifyingKey::from_bytes(bytes)?)),
+            #[cfg(feature = "eddsa")]
+            Algorithm::EdDSA => Ok(AnyVerifyingKey::EdDSA(EDDSAVerifyingKey::from_bytes(
+                &mut bytes.to_vec(),
+            )?)),
+            #[cfg(feature = "mldsa")]
+            Algorithm::MLDSA65 => Ok(AnyVerifyingKey::MLDSA65(MLDSA65VerifyingKey::from_bytes(
+                bytes,
+            )?)),
+            #[cfg(feature = "mldsa")]
+            Algorithm::MLDSA65Ed25519 => Ok(AnyVerifyingKey::MLDSA65Ed25519(
+                MLDSA65Ed25519VerifyingKey::from_bytes(bytes)?,
+            )),
+            #[allow(unreachable_patterns)]
+            _ => Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        match self {
+            AnyVerifyingKey::Hmac(key, _) => Ok(key.to_bytes()),
+            #[cfg(feature = "rsa")]
+            AnyVerifyingKey::Rsa(key, _) => key.to_spki_der(),
+            AnyVerifyingKey::P256(key) => Ok(key.to_bytes()),
+            #[cfg(feature = "es256k")]
+            AnyVerifyingKey::P256k(key) => Ok(key.to_bytes()),
+            #[cfg(feature = "es384")]
+            AnyVerifyingKey::P384(key) => Ok(key.to_bytes()),
+            #[cfg(feature = "es512")]
+            AnyVerifyingKey::P512(key) => Ok(key.to_bytes()),
+            #[cfg(feature = "eddsa")]
+            AnyVerifyingKey::EdDSA(key) => Ok(key.to_bytes()),
+            #[cfg(feature = "mldsa")]
+            AnyVerifyingKey::MLDSA65(key) => Ok(key.to_bytes()),
+            #[cfg(feature = "mldsa")]
+            AnyVerifyingKey::MLDSA65Ed25519(key) => Ok(key.to_bytes()),
+        }
+    }
+
+    pub fn from_hex(alg: Algorithm, key: &str) -> Result<Self, Error> {
+        Self::from_bytes(alg, &decode_hex(key)?)
+    }
+
+    pub fn to_hex(&self) -> Result<String, Error> {
+        Ok(encode_hex_prefixed(&self.to_bytes()?))
+    }
+
+    pub fn to_sec1_bytes(&self, compressed: bool) -> Result<Vec<u8>, Error> {
+        match self {
+            AnyVerifyingKey::P256(key) => Ok(key.to_sec1_bytes(compressed)),
+            #[cfg(feature = "es256k")]
+            AnyVerifyingKey::P256k(key) => Ok(key.to_sec1_bytes(compressed)),
+            #[cfg(feature = "es384")]
+            AnyVerifyingKey::P384(key) => Ok(key.to_sec1_bytes(compressed)),
+            #[cfg(feature = "es512")]
+            AnyVerifyingKey::P512(key) => Ok(key.to_sec1_bytes(compressed)),
+            _ => Err(Error::KEY_FORMAT_UNSUPPORTED.with_algorithm(self.algorithm())),
+        }
+    }
+
+    pub fn from_x509_pem(cert_str: &str) -> Result<Self, Error> {
+        Self::from_x509_der(&x509::certificate_pem_to_der(cert_str)?)
+    }
+
+    pub fn from_x509_der(cert: &[u8]) -> Result<Self, Error> {
+        Self::from_spki_der(&x509::subject_public_key_info(cert)?)
+    }
+
+    pub fn from_spki_der(der: &[u8]) -> Result<Self, Error> {
+        let info = match SubjectPublicKeyInfoRef::try_from(der) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
+            }
+        };
+
+        let public_key = match info.subject_public_key.as_bytes() {
+            Some(val) => val,
+            None => return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR),
+        };
+
+        match info.algorithm.oid {
+            #[cfg(feature = "rsa")]
+            RSA_ENCRYPTION_OID => Ok(AnyVerifyingKey::Rsa(
+                RsaVerifyingKey::from_spki_der(der)?,
+                Algorithm::RS256,
+            )),
+            #[cfg(feature = "eddsa")]
+            ED25519_OID => Self::from_bytes(Algorithm::EdDSA, public_key),
+            EC_PUBLIC_KEY_OID => match info.algorithm.parameters_oid() {
+                Ok(P256_OID) => Self::from_bytes(Algorithm::ES256, public_key),
+                #[cfg(feature = "es256k")]
+                Ok(P256K_OID) => Self::from_bytes(Algorithm::ES256K, public_key),
+                #[cfg(feature = "es384")]
+                Ok(P384_OID) => Self::from_bytes(Algorithm::ES384, public_key),
+                #[cfg(feature = "es512")]
+                Ok(P521_OID) => Self::from_bytes(Algorithm::ES512, public_key),
+                _ => Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR),
+            },
+            #[cfg(feature = "mldsa")]
+            MLDSA65_OID => Self::from_bytes(Algorithm::MLDSA65, public_key),
+            _ => Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR),
+        }
+    }
+
+    pub fn from_multicodec(bytes: &[u8]) -> Result<Self, Error> {
+        let (code, key) = multicodec::decode(bytes)?;
+        let alg = match code {
+            multicodec::P256_PUB => Algorithm::ES256,
+            #[cfg(feature = "es256k")]
+            multicodec::SECP256K1_PUB => Algorithm::ES256K,
+            #[cfg(feature = "es384")]
+            multicodec::P384_PUB => Algorithm::ES384,
+            #[cfg(feature = "es512")]
+            multicodec::P521_PUB => Algorithm::ES512,
+            #[cfg(feature = "eddsa")]
+            multicodec::ED25519_PUB => Algorithm::EdDSA,
+            #[cfg(feature = "mldsa")]
+            multicodec::MLDSA_65_PUB => Algorithm::MLDSA65,
+            _ => return Err(Error::MULTICODEC_ERROR),
+        };
+
+        Self::from_bytes(alg, key)
+    }
+
+    pub fn to_multicodec(&self) -> Result<Vec<u8>, Error> {
+        let (code, bytes) = match self {
+            AnyVerifyingKey::P256(key) => (multicodec::P256_PUB, key.to_sec1_bytes(true)),
+            #[cfg(feature = "es256k")]
+            AnyVerifyingKey::P256k(key) => (multicodec::SECP256K1_PUB, key.to_sec1_bytes(true)),
+            #[cfg(feature = "es384")]
+            AnyVerifyingKey::P384(key) => (multicodec::P384_PUB, key.to_sec1_bytes(true)),
+            #[cfg(feature = "es512")]
+            AnyVerifyingKey::P512(key) => (multicodec::P521_PUB, key.to_sec1_bytes(true)),
+            #[cfg(feature = "eddsa")]
+            AnyVerifyingKey::EdDSA(key) => (multicodec::ED25519_PUB, key.to_bytes()),
+            #[cfg(feature = "mldsa")]
+            AnyVerifyingKey::MLDSA65(key) => return Ok(key.to_multicodec()),
+            _ => return Err(Error::KEY_FORMAT_UNSUPPORTED.with_algorithm(self.algorithm())),
+        };
+
+        Ok(multicodec::encode(code, &bytes))
+    }
+
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        let sec1_bytes = || -> Result<Vec<u8>, Error> {
+            let mut bytes = vec![0x04];
+            bytes.extend_from_slice(&Jwk::decode_member(&jwk.x)?);
+            bytes.extend_from_slice(&Jwk::decode_member(&jwk.y)?);
+            Ok(bytes)
+        };
+
+        match (jwk.kty.as_str(), jwk.crv.as_deref(), jwk.alg.as_deref()) {
+            #[cfg(feature = "eddsa")]
+            ("OKP", Some("Ed25519"), _) => {
+                Ok(AnyVerifyingKey::EdDSA(EDDSAVerifyingKey::from_jwk(jwk)?))
+            }
+            ("EC", Some("P-256"), _) => Ok(AnyVerifyingKey::P256(P256VerifyingKey::from_bytes(
+                &sec1_bytes()?,
+            )?)),
+            #[cfg(feature = "es256k")]
+            ("EC", Some("secp256k1"), _) => Ok(AnyVerifyingKey::P256k(
+                P256kVerifyingKey::from_bytes(&sec1_bytes()?)?,
+            )),
+            #[cfg(feature = "es384")]
+            ("EC", Some("P-384"), _) => Ok(AnyVerifyingKey::P384(P384VerifyingKey::from_bytes(
+                &sec1_bytes()?,
+            )?)),
+            #[cfg(feature = "es512")]
+            ("EC", Some("P-521"), _) => Ok(AnyVerifyingKey::P512(P512VerifyingKey::from_bytes(
+                &sec1_bytes()?,
+            )?)),
+            #[cfg(feature = "mldsa")]
+            ("AKP", _, Some("ML-DSA-65")) => Ok(AnyVerifyingKey::MLDSA65(
+                MLDSA65VerifyingKey::from_jwk(jwk)?,
+            )),
+            #[cfg(feature = "mldsa")]
+            ("AKP", _, Some("ML-DSA-65-Ed25519")) => Ok(AnyVerifyingKey::MLDSA65Ed25519(
+                MLDSA65Ed25519VerifyingKey::from_jwk(jwk)?,
+            )),
+            _ => Err(Error::JWK_KEY_TYPE_MISMATCH),
+        }
+    }
+
+    pub fn verify_with_context(
+        &self,
+        content: &[u8],
+        signature: &str,
+        context: &[u8],
+    ) -> Result<bool, Error> {
+        if context.is_empty() {
+            return self.verify_bytes(content, signature, self.algorithm());
+        }
+
+        match self {
+            #[cfg(feature = "eddsa")]
+            AnyVerifyingKey::EdDSA(key) => key.verify_with_context(content, signature, context),
+            #[cfg(feature = "mldsa")]
+            AnyVerifyingKey::MLDSA65(key) => {
+                if context.len() > super::eddsa::MAX_CONTEXT_LENGTH {
+                    return Err(Error::SIGNATURE_CONTEXT_INVALID);
+                }
+                let signature = match base64_url::decode(signature) {
+                    Ok(val) => val,
+                    Err(error) => {
+                        log::error(error.to_string().as_str());
+                        return Err(Error::DECODING_ERROR.with_source(error));
+                    }
+                };
+                key.verify_with_context(content, &signature, context)
+            }
+            _ => Err(Error::SIGNATURE_CONTEXT_UNSUPPORTED.with_algorithm(self.algorithm())),
+        }
+    }
+
+    pub fn to_jwk(&self) -> Result<Jwk, Error> {
+        let jwk = match self {
+            AnyVerifyingKey::Hmac(_, _) => return Err(Error::JWK_KEY_TYPE_MISMATCH),
+            #[cfg(feature = "rsa")]
+            AnyVerifyingKey::Rsa(_, _) => return Err(Error::JWK_KEY_TYPE_MISMATCH),
+            AnyVerifyingKey::P256(key) => ec_jwk("P-256", key.to_bytes()),
+            #[cfg(feature = "es256k")]
+            AnyVerifyingKey::P256k(key) => ec_jwk("secp256k1", key.to_bytes()),
+            #[cfg(feature = "es384")]
+            AnyVerifyingKey::P384(key) => ec_jwk("P-384", key.to_bytes()),
+            #[cfg(feature = "es512")]
+            AnyVerifyingKey::P512(key) => ec_jwk("P-521", key.to_bytes()),
+            #[cfg(feature = "eddsa")]
+            AnyVerifyingKey::EdDSA(key) => key.to_jwk(),
+            #[cfg(feature = "mldsa")]
+            AnyVerifyingKey::MLDSA65(key) => key.to_jwk(),
+            #[cfg(feature = "mldsa")]
+            AnyVerifyingKey::MLDSA65Ed25519(key) => key.to_jwk(),
+        };
+
+        Ok(Jwk {
+            alg: Some(String::from(self.algorithm().to_str())),
+            ..jwk
+        })
+    }
+
+    pub fn to_spki_der(&self) -> Result<Vec<u8>, Error> {
+        let der =
+            match self {
+                #[cfg(feature = "rsa")]
+                AnyVerifyingKey::Rsa(key, _) => return key.to_spki_der(),
+                AnyVerifyingKey::P256(key) => p256::PublicKey::from_sec1_bytes(&key.to_bytes())
+                    .map(|val| val.to_public_key_der()),
+                #[cfg(feature = "es256k")]
+                AnyVerifyingKey::P256k(key) => k256::PublicKey::from_sec1_bytes(&key.to_bytes())
+                    .map(|val| val.to_public_key_der()),
+                #[cfg(feature = "es384")]
+                AnyVerifyingKey::P384(key) => p384::PublicKey::from_sec1_bytes(&key.to_bytes())
+                    .map(|val| val.to_public_key_der()),
+                #[cfg(feature = "es512")]
+                AnyVerifyingKey::P512(key) => p521::PublicKey::from_sec1_bytes(&key.to_bytes())
+                    .map(|val| val.to_public_key_der()),
+                #[cfg(feature = "eddsa")]
+                AnyVerifyingKey::EdDSA(key) => {
+                    let public_key = match key.to_bytes().as_slice().try_into() {
+                        Ok(val) => ed25519_dalek::VerifyingKey::from_bytes(val),
+                        Err(_) => return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR),
+                    };
+
+                    match public_key {
+                        Ok(val) => Ok(val.to_public_key_der()),
+                        Err(error) => {
+                            log::error(error.to_string().as_str());
+                            return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
+                        }
+                    }
+                }
+                #[cfg(feature = "mldsa")]
+                AnyVerifyingKey::MLDSA65(key) => {
+                    let public_key = key.to_bytes();
+                    let subject_public_key = match BitStringRef::from_bytes(&public_key) {
+                        Ok(val) => val,
+                        Err(error) => {
+                            log::error(error.to_string().as_str());
+                            return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
+                        }
+                    };
+
+                    return match (SubjectPublicKeyInfoRef {
+                        algorithm: AlgorithmIdentifierRef {
+                            oid: MLDSA65_OID,
+                            parameters: None,
+                        },
+                        subject_public_key,
+                    })
+                    .to_der()
+                    {
+                        Ok(val) => Ok(val),
+                        Err(error) => {
+                            log::error(error.to_string().as_str());
+                            Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error))
+                        }
+                    };
+                }
+                AnyVerifyingKey::Hmac(_, _) => return Err(Error::PKCS8_UNSUPPORTED_KEY_TYPE),
+                #[cfg(feature = "mldsa")]
+                AnyVerifyingKey::MLDSA65Ed25519(_) => {
+                    return Err(Error::PKCS8_UNSUPPORTED_KEY_TYPE)
+                }
+            };
+
+        match der {
+            Ok(Ok(val)) => Ok(val.into_vec()),
+            Ok(Err(error)) => {
+                log::error(error.to_string().as_str());
+                Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error))
+            }
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error))
+            }
+        }
+    }
+}
+
+impl VerifyFromKey for AnyVerifyingKey {
+    fn verifying_algorithms(&self) -> Option<Vec<Algorithm>> {
+        match self {
+            #[cfg(feature = "es256k")]
+            AnyVerifyingKey::P256k(key) => key.verifying_algorithms(),
+            _ => Some(vec![self.algorithm()]),
+        }
+    }
+
+    fn key_fingerprint(&self) -> Option<String> {
+        self.fingerprint().ok()
+    }
+
+    fn verify_batch(&self, items: &[(&[u8], &Signature)]) -> Vec<Result<bool, Error>> {
+        match self {
+            #[cfg(feature = "eddsa")]
+            AnyVerifyingKey::EdDSA(key) => key.verify_batch(items),
+            _ => items
+                .iter()
+                .map(|(content, signature)| self.verify_signature(content, signature))
+                .collect(),
+        }
+    }
+
+    fn verify_bytes(&self, content: &[u8], signature: &str, alg: Algorithm) -> Result<bool, Error> {
+        if !supports_algorithm(self.algorithm(), alg) {
+            return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg));
+        }
+
+        match self {
+            AnyVerifyingKey::Hmac(key, _) => key.verify_bytes(content, signature, alg),
+            #[cfg(feature = "rsa")]
+            AnyVerifyingKey::Rsa(key, _) => key.verify_bytes(content, signature, alg),
+            AnyVerifyingKey::P256(key) => key.verify_bytes(content, signature, alg),
+            #[cfg(feature = "es256k")]
+            AnyVerifyingKey::P256k(key) => key.verify_bytes(content, signature, alg),
+            #[cfg(feature = "es384")]
+            AnyVerifyingKey::P384(key) => key.verify_bytes(content, signature, alg),
+            #[cfg(feature = "es512")]
+            AnyVerifyingKey::P512(key) => key.verify_bytes(content, signature, alg),
+            #[cfg(feature = "eddsa")]
+            AnyVerifyingKey::EdDSA(key) => key.verify_bytes(content, signature, alg),
+            #[cfg(feature = "mldsa")]
+            AnyVerifyingKey::MLDSA65(key) => key.verify_bytes(content, signature, alg),
+            #[cfg(feature = "mldsa")]
+            AnyVerifyingKey::MLDSA65Ed25519(key) => key.verify_bytes(content, signature, alg),
+        }
+    }
+}
@@ -0,0 +1,585 @@
+use chrono::{DateTime, Utc};
+use coset::{
+    cbor::value::Value,
+    iana::{self, EnumI64},
+    AsCborValue, CoseKey,
+};
+use sha2::{Digest, Sha256};
+use x509_cert::{ext::pkix::BasicConstraints, spki::ObjectIdentifier, Certificate};
+
+use crate::{
+    algorithms::{Algorithm, AlgorithmFamily},
+    clock,
+    cose::{algorithm_from_cose, verifying_key_from_cose},
+    errors::Error,
+    log,
+};
+
+use super::{
+    any::AnyVerifyingKey,
+    ecdsa::signature_from_der,
+    hardware::KeyAttestation,
+    x509::{certificate_from_der, validate_chain},
+    VerifyFromKey,
+};
+
+pub const ATTESTATION_FORMAT_PACKED: &str = "packed";
+pub const ATTESTATION_FORMAT_ANDROID_KEY: &str = "android-key";
+pub const ATTESTATION_FORMAT_APPLE: &str = "apple";
+
+const AAGUID_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.4.1.45724.1.1.4");
+const ANDROID_KEY_DESCRIPTION_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("1.3.6.1.4.1.11129.2.1.17");
+const APPLE_NONCE_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113635.100.8.2");
+
+const RP_ID_HASH_LENGTH: usize = 32;
+const AAGUID_LENGTH: usize = 16;
+const FLAG_ATTESTED_CREDENTIAL_DATA: u8 = 0x40;
+const FLAG_EXTENSION_DATA: u8 = 0x80;
+
+const KM_TAG_PURPOSE: u32 = 1;
+const KM_TAG_ALL_APPLICATIONS: u32 = 600;
+const KM_TAG_ORIGIN: u32 = 702;
+const KM_PURPOSE_SIGN: u64 = 2;
+const KM_ORIGIN_GENERATED: u64 = 0;
+
+const DER_INTEGER: u32 = 0x02;
+const DER_OCTET_STRING: u32 = 0x04;
+const DER_ENUMERATED: u32 = 0x0a;
+const DER_SEQUENCE: u32 = 0x10;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SecurityLevel {
+    SelfAttested,
+    Software,
+    TrustedEnvironment,
+    StrongBox,
+    SecureEnclave,
+    Authenticator,
+}
+
+impl SecurityLevel {
+    pub fn is_hardware_backed(&self) -> bool {
+        !matches!(self, SecurityLevel::SelfAttested | SecurityLevel::Software)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct VerifiedAttestation {
+    pub format: String,
+    pub security_level: SecurityLevel,
+    pub key: AnyVerifyingKey,
+    pub aaguid: Vec<u8>,
+    pub certificate_chain: Vec<Vec<u8>>,
+}
+
+impl VerifiedAttestation {
+    pub fn require_hardware(&self) -> Result<(), Error> {
+        match self.security_level.is_hardware_backed() {
+            true => Ok(()),
+            false => Err(Error::ATTESTATION_NOT_HARDWARE_BACKED),
+        }
+    }
+
+    pub fn check_key(&self, key: &AnyVerifyingKey) -> Result<(), Error> {
+        match same_key(&self.key, key)? {
+            true => Ok(()),
+            false => Err(Error::ATTESTATION_KEY_MISMATCH),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct AuthenticatorData {
+    pub rp_id_hash: Vec<u8>,
+    pub flags: u8,
+    pub sign_count: u32,
+    pub aaguid: Vec<u8>,
+    pub credential_id: Vec<u8>,
+    pub credential_public_key: CoseKey,
+    bytes: Vec<u8>,
+}
+
+fn take<'a>(input: &mut &'a [u8], length: usize) -> Result<&'a [u8], Error> {
+    if input.len() < length {
+        return Err(Error::ATTESTATION_MALFORMED);
+    }
+
+    let (head, rest) = input.split_at(length);
+    *input = rest;
+    Ok(head)
+}
+
+impl AuthenticatorData {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut input = bytes;
+        let rp_id_hash = take(&mut input, RP_ID_HASH_LENGTH)?.to_vec();
+        let flags = take(&mut input, 1)?[0];
+        let sign_count = take(&mut input, 4)?
+            .iter()
+            .fold(0u32, |acc, byte| acc << 8 | *byte as u32);
+        if flags & FLAG_ATTESTED_CREDENTIAL_DATA == 0 {
+            return Err(Error::ATTESTATION_MALFORMED);
+        }
+
+        let aaguid = take(&mut input, AAGUID_LENGTH)?.to_vec();
+        let length = take(&mut input, 2)?;
+        let credential_id = take(
+            &mut input,
+            u16::from_be_bytes([length[0], length[1]]) as usize,
+        )?
+        .to_vec();
+
+        let value: Value = match coset::cbor::de::from_reader(&mut input) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ATTESTATION_MALFORMED.with_source(error));
+            }
+        };
+        let credential_public_key = match CoseKey::from_cbor_value(value) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ATTESTATION_MALFORMED.with_source(error.to_string()));
+            }
+        };
+        if !input.is_empty() && flags & FLAG_EXTENSION_DATA == 0 {
+            return Err(Error::ATTESTATION_MALFORMED);
+        }
+
+        Ok(AuthenticatorData {
+            rp_id_hash,
+            flags,
+            sign_count,
+            aaguid,
+            credential_id,
+            credential_public_key,
+            bytes: bytes.to_vec(),
+        })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn verifying_key(&self) -> Result<AnyVerifyingKey, Error> {
+        verifying_key_from_cose(&self.credential_public_key)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct AttestationObject {
+    pub format: String,
+    pub statement: Vec<(String, Value)>,
+    pub auth_data: AuthenticatorData,
+}
+
+fn text_map(value: Value) -> Result<Vec<(String, Value)>, Error> {
+    let entries = match value {
+        Value::Map(val) => val,
+        _ => return Err(Error::ATTESTATION_MALFORMED),
+    };
+
+    entries
+        .into_iter()
+        .map(|(label, value)| match label {
+            Value::Text(val) => Ok((val, value)),
+            _ => Err(Error::ATTESTATION_MALFORMED),
+        })
+        .collect()
+}
+
+impl AttestationObject {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let value: Value = match coset::cbor::de::from_reader(bytes) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ATTESTATION_MALFORMED.with_source(error));
+            }
+        };
+
+        let mut format = None;
+        let mut statement = None;
+        let mut auth_data = None;
+        for (label, value) in text_map(value)? {
+            match (label.as_str(), value) {
+                ("fmt", Value::Text(val)) => format = Some(val),
+                ("attStmt", val) => statement = Some(text_map(val)?),
+                ("authData", Value::Bytes(val)) => auth_data = Some(val),
+                _ => return Err(Error::ATTESTATION_MALFORMED),
+            }
+        }
+
+        match (format, statement, auth_data) {
+            (Some(format), Some(statement), Some(auth_data)) => Ok(AttestationObject {
+                format,
+                statement,
+                auth_data: AuthenticatorData::from_bytes(&auth_data)?,
+            }),
+            _ => Err(Error::ATTESTATION_MALFORMED),
+        }
+    }
+
+    fn statement_value(&self, name: &str) -> Option<&Value> {
+        self.statement
+            .iter()
+            .find(|(label, _)| label == name)
+            .map(|(_, value)| value)
+    }
+
+    fn algorithm(&self) -> Result<Algorithm, Error> {
+        let alg = match self
+            .statement_value("alg")
+            .and_then(Value::as_integer)
+            .and_then(|val| i64::try_from(val).ok())
+            .and_then(iana::Algorithm::from_i64)
+        {
+            Some(val) => val,
+            None => return Err(Error::ATTESTATION_MALFORMED),
+        };
+
+        algorithm_from_cose(&coset::Algorithm::Assigned(alg))
+    }
+
+    fn signature(&self) -> Result<&[u8], Error> {
+        match self.statement_value("sig") {
+            Some(Value::Bytes(val)) => Ok(val),
+            _ => Err(Error::ATTESTATION_MALFORMED),
+        }
+    }
+
+    fn certificate_chain(&self) -> Result<Vec<Vec<u8>>, Error> {
+        match self.statement_value("x5c") {
+            Some(Value::Array(values)) if !values.is_empty() => values
+                .iter()
+                .map(|val| match val {
+                    Value::Bytes(val) => Ok(val.clone()),
+                    _ => Err(Error::ATTESTATION_MALFORMED),
+                })
+                .collect(),
+            Some(_) => Err(Error::ATTESTATION_MALFORMED),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    fn signed_data(&self, client_data_hash: &[u8]) -> Vec<u8> {
+        let mut data = self.auth_data.as_bytes().to_vec();
+        data.extend_from_slice(client_data_hash);
+        data
+    }
+
+    pub fn verify(
+        &self,
+        client_data_hash: &[u8],
+        trust_anchors: &[Vec<u8>],
+    ) -> Result<VerifiedAttestation, Error> {
+        self.verify_at(client_data_hash, trust_anchors, clock::now())
+    }
+
+    pub fn verify_at(
+        &self,
+        client_data_hash: &[u8],
+        trust_anchors: &[Vec<u8>],
+        at: DateTime<Utc>,
+    ) -> Result<VerifiedAttestation, Error> {
+        let key = self.auth_data.verifying_key()?;
+        let chain = self.certificate_chain()?;
+        let signed_data = self.signed_data(client_data_hash);
+
+        let security_level = match self.format.as_str() {
+            ATTESTATION_FORMAT_PACKED if chain.is_empty() => {
+                let alg = self.algorithm()?;
+                if alg != key.algorithm() {
+                    return Err(Error::ATTESTATION_INVALID);
+                }
+                verify_statement_signature(key.clone(), alg, &signed_data, self.signature()?)?;
+                SecurityLevel::SelfAttested
+            }
+            ATTESTATION_FORMAT_PACKED => {
+                let leaf_key = validate_chain(&chain, trust_anchors, at)?;
+                verify_statement_signature(
+                    leaf_key,
+                    self.algorithm()?,
+                    &signed_data,
+                    self.signature()?,
+                )?;
+                check_packed_certificate(&chain[0], &self.auth_data.aaguid)?;
+                SecurityLevel::Authenticator
+            }
+            ATTESTATION_FORMAT_ANDROID_KEY => {
+                let (leaf_key, security_level) =
+                    verify_android_chain(&chain, client_data_hash, trust_anchors, at)?;
+                if !same_key(&leaf_key, &key)? {
+                    return Err(Error::ATTESTATION_KEY_MISMATCH);
+                }
+                verify_statement_signature(
+                    leaf_key,
+                    self.algorithm()?,
+                    &signed_data,
+                    self.signature()?,
+                )?;
+                security_level
+            }
+            ATTESTATION_FORMAT_APPLE => {
+                let leaf_key = validate_chain(&chain, trust_anchors, at)?;
+                if !same_key(&leaf_key, &key)? {
+                    return Err(Error::ATTESTATION_KEY_MISMATCH);
+                }
+                check_apple_nonce(&chain[0], &Sha256::digest(&signed_data))?;
+                SecurityLevel::SecureEnclave
+            }
+            _ => return Err(Error::ATTESTATION_UNSUPPORTED_FORMAT),
+        };
+
+        Ok(VerifiedAttestation {
+            format: self.format.clone(),
+            security_level,
+            key,
+            aaguid: self.auth_data.aaguid.clone(),
+            certificate_chain: chain,
+        })
+    }
+}
+
+pub fn verify_key_attestation(
+    attestation: &KeyAttestation,
+    challenge: &[u8],
+    trust_anchors: &[Vec<u8>],
+) -> Result<VerifiedAttestation, Error> {
+    verify_key_attestation_at(attestation, challenge, trust_anchors, clock::now())
+}
+
+pub fn verify_key_attestation_at(
+    attestation: &KeyAttestation,
+    challenge: &[u8],
+    trust_anchors: &[Vec<u8>],
+    at: DateTime<Utc>,
+) -> Result<VerifiedAttestation, Error> {
+    if !attestation.statement.is_empty() {
+        let object = AttestationObject::from_bytes(&attestation.statement)?;
+        if object.format != attestation.format {
+            return Err(Error::ATTESTATION_MALFORMED);
+        }
+        return object.verify_at(challenge, trust_anchors, at);
+    }
+
+    match attestation.format.as_str() {
+        ATTESTATION_FORMAT_ANDROID_KEY => {
+            let (key, security_level) =
+                verify_android_chain(&attestation.certificate_chain, challenge, trust_anchors, at)?;
+            Ok(VerifiedAttestation {
+                format: attestation.format.clone(),
+                security_level,
+                key,
+                aaguid: Vec::new(),
+                certificate_chain: attestation.certificate_chain.clone(),
+            })
+        }
+        _ => Err(Error::ATTESTATION_UNSUPPORTED_FORMAT),
+    }
+}
+
+fn same_key(left: &AnyVerifyingKey, right: &AnyVerifyingKey) -> Result<bool, Error> {
+    Ok(left.to_spki_der()? == right.to_spki_der()?)
+}
+
+fn verify_statement_signature(
+    key: AnyVerifyingKey,
+    alg: Algorithm,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<(), Error> {
+    let signature = match alg.get_family() {
+        AlgorithmFamily::EC => match signature_from_der(signature, alg) {
+            Ok(val) => val,
+            Err(_) => return Err(Error::ATTESTATION_INVALID),
+        },
+        _ => base64_url::encode(signature),
+    };
+
+    match key
+        .with_algorithm(alg)
+        .verify_bytes(message, &signature, alg)
+    {
+        Ok(true) => Ok(()),
+        _ => Err(Error::ATTESTATION_INVALID),
+    }
+}
+
+fn extension_value(certificate: &Certificate, oid: ObjectIdentifier) -> Option<&[u8]> {
+    certificate
+        .tbs_certificate
+        .extensions
+        .as_ref()?
+        .iter()
+        .find(|extension| extension.extn_id == oid)
+        .map(|extension| extension.extn_value.as_bytes())
+}
+
+fn check_packed_certificate(der: &[u8], aaguid: &[u8]) -> Result<(), Error> {
+    let certificate = certificate_from_der(der)?;
+    if certificate.tbs_certificate.version != x509_cert::certificate::Version::V3 {
+        return Err(Error::ATTESTATION_INVALID);
+    }
+    if let Ok(Some((_, constraints))) = certificate.tbs_certificate.get::<BasicConstraints>() {
+        if constraints.ca {
+            return Err(Error::ATTESTATION_INVALID);
+        }
+    }
+
+    match extension_value(&certificate, AAGUID_OID) {
+        Some(value) => {
+            let mut input = value;
+            match read_tlv(&mut input)? {
+                (DER_OCTET_STRING, false, val) if val == aaguid && input.is_empty() => Ok(()),
+                _ => Err(Error::ATTESTATION_INVALID),
+            }
+        }
+        None => Ok(()),
+    }
+}
+
+fn check_apple_nonce(der: &[u8], nonce: &[u8]) -> Result<(), Error> {
+    let certificate = certificate_from_der(der)?;
+    let mut input = match extension_value(&certificate, APPLE_NONCE_OID) {
+        Some(val) => val,
+        None => return Err(Error::ATTESTATION_INVALID),
+    };
+
+    let mut sequence = match read_tlv(&mut input)? {
+        (DER_SEQUENCE, true, val) => val,
+        _ => return Err(Error::ATTESTATION_MALFORMED),
+    };
+    let mut tagged = match read_tlv(&mut sequence)? {
+        (1, true, val) => val,
+        _ => return Err(Error::ATTESTATION_MALFORMED),
+    };
+    match read_tlv(&mut tagged)? {
+        (DER_OCTET_STRING, false, val) if val == nonce => Ok(()),
+        _ => Err(Error::ATTESTATION_INVALID),
+    }
+}
+
+fn verify_android_chain(
+    chain: &[Vec<u8>],
+    challenge: &[u8],
+    trust_anchors: &[Vec<u8>],
+    at: DateTime<Utc>,
+) -> Result<(AnyVerifyingKey, SecurityLevel), Error> {
+    let key = validate_chain(chain, trust_anchors, at)?;
+    let certificate = certificate_from_der(&chain[0])?;
+    let mut input = match extension_value(&certificate, ANDROID_KEY_DESCRIPTION_OID) {
+        Some(val) => val,
+        None => return Err(Error::ATTESTATION_INVALID),
+    };
+
+    let mut description = match read_tlv(&mut input)? {
+        (DER_SEQUENCE, true, val) => val,
+        _ => return Err(Error::ATTESTATION_MALFORMED),
+    };
+    let _version = read_integer(&mut description, DER_INTEGER)?;
+    let security_level = match read_integer(&mut description, DER_ENUMERATED)? {
+        0 => SecurityLevel::Software,
+        1 => SecurityLevel::TrustedEnvironment,
+        2 => SecurityLevel::StrongBox,
+        _ => return Err(Error::ATTESTATION_MALFORMED),
+    };
+    let _keymint_version = read_integer(&mut description, DER_INTEGER)?;
+    let _keymint_security_level = read_integer(&mut description, DER_ENUMERATED)?;
+    match read_tlv(&mut description)? {
+        (DER_OCTET_STRING, false, val) if val == challenge => {}
+        (DER_OCTET_STRING, false, _) => return Err(Error::ATTESTATION_INVALID),
+        _ => return Err(Error::ATTESTATION_MALFORMED),
+    }
+    let _unique_id = read_tlv(&mut description)?;
+    let software_enforced = authorization_list(&mut description)?;
+    let hardware_enforced = authorization_list(&mut description)?;
+
+    if software_enforced.all_applications || hardware_enforced.all_applications {
+        return Err(Error::ATTESTATION_INVALID);
+    }
+
+    let enforced = match security_level {
+        SecurityLevel::Software => &software_enforced,
+        _ => &hardware_enforced,
+    };
+    if enforced.origin != Some(KM_ORIGIN_GENERATED) || !enforced.purposes.contains(&KM_PURPOSE_SIGN)
+    {
+        return Err(Error::ATTESTATION_INVALID);
+    }
+
+    Ok((key, security_level))
+}
+
+#[derive(Default)]
+struct AuthorizationList {
+    purposes: Vec<u64>,
+    origin: Option<u64>,
+    all_applications: bool,
+}
+
+fn authorization_list(input: &mut &[u8]) -> Result<AuthorizationList, Error> {
+    let mut entries = match read_tlv(input)? {
+        (DER_SEQUENCE, true, val) => val,
+        _ => return Err(Error::ATTESTATION_MALFORMED),
+    };
+
+    let mut list = AuthorizationList::default();
+    while !entries.is_empty() {
+        let (tag, _, mut value) = read_tlv(&mut entries)?;
+        match tag {
+            KM_TAG_PURPOSE => {
+                let (_, _, mut set) = read_tlv(&mut value)?;
+                while !set.is_empty() {
+                    list.purposes.push(read_integer(&mut set, DER_INTEGER)?);
+                }
+            }
+            KM_TAG_ORIGIN => list.origin = Some(read_integer(&mut value, DER_INTEGER)?),
+            KM_TAG_ALL_APPLICATIONS => list.all_applications = true,
+            _ => {}
+        }
+    }
+
+    Ok(list)
+}
+
+fn read_integer(input: &mut &[u8], expected: u32) -> Result<u64, Error> {
+    match read_tlv(input)? {
+        (tag, false, val) if tag == expected && !val.is_empty() && val.len() <= 9 => {
+            Ok(val.iter().fold(0u64, |acc, byte| acc << 8 | *byte as u64))
+        }
+        _ => Err(Error::ATTESTATION_MALFORMED),
+    }
+}
+
+fn read_tlv<'a>(input: &mut &'a [u8]) -> Result<(u32, bool, &'a [u8]), Error> {
+    let first = take(input, 1)?[0];
+    let constructed = first & 0x20 != 0;
+    let mut tag = (first & 0x1f) as u32;
+    if tag == 0x1f {
+        tag = 0;
+        loop {
+            let byte = take(input, 1)?[0];
+            tag = match tag.checked_mul(128) {
+                Some(val) => val | (byte & 0x7f) as u32,
+                None => return Err(Error::ATTESTATION_MALFORMED),
+            };
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+    }
+
+    let first_length = take(input, 1)?[0];
+    let length = match first_length {
+        0..=0x7f => first_length as usize,
+        0x81..=0x84 => take(input, (first_length & 0x7f) as usize)?
+            .iter()
+            .fold(0usize, |acc, byte| acc << 8 | *byte as usize),
+        _ => return Err(Error::ATTESTATION_MALFORMED),
+    };
+
+    Ok((tag, constructed, take(input, length)?))
+}
@@ -0,0 +1,490 @@
+use std::fmt;
+
+use bls12_381_plus::{
+    elliptic_curve::hash2curve::{ExpandMsg, ExpandMsgXmd, Expander},
+    ff::Field,
+    group::{Curve, Group},
+    multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Scalar,
+};
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+use sha2::Sha256;
+
+use crate::{errors::Error, log};
+
+use super::{bls::Bls12381G2VerifyingKey, entropy, fmt_key};
+
+const CIPHERSUITE_ID: &str = "BBS_BLS12381G1_XMD:SHA-256_SSWU_RO_";
+const API_ID: &str = "BBS_BLS12381G1_XMD:SHA-256_SSWU_RO_H2G_HM2S_";
+const EXPAND_LEN: usize = 48;
+const POINT_LEN: usize = 48;
+const SCALAR_LEN: usize = 32;
+const SIGNATURE_LEN: usize = POINT_LEN + SCALAR_LEN;
+
+fn expand_message(msg: &[u8], dst: &[u8]) -> Result<[u8; EXPAND_LEN], Error> {
+    let dsts = [dst];
+    let mut expander = match ExpandMsgXmd::<Sha256>::expand_message(&[msg], &dsts, EXPAND_LEN) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::ENCODING_ERROR.with_source(error));
+        }
+    };
+
+    let mut okm = [0u8; EXPAND_LEN];
+    expander.fill_bytes(&mut okm);
+    Ok(okm)
+}
+
+fn hash_to_scalar(msg: &[u8], dst: &str) -> Scalar {
+    Scalar::hash::<ExpandMsgXmd<Sha256>>(msg, dst.as_bytes())
+}
+
+fn create_generators(count: usize, api_id: &str, seed: &str) -> Result<Vec<G1Projective>, Error> {
+    let seed_dst = format!("{}SIG_GENERATOR_SEED_", api_id);
+    let generator_dst = format!("{}SIG_GENERATOR_DST_", api_id);
+
+    let mut v = expand_message(
+        format!("{}{}", api_id, seed).as_bytes(),
+        seed_dst.as_bytes(),
+    )?;
+    let mut generators = Vec::with_capacity(count);
+    for i in 1..=count {
+        let mut input = v.to_vec();
+        input.extend_from_slice(&(i as u64).to_be_bytes());
+        v = expand_message(&input, seed_dst.as_bytes())?;
+        generators.push(G1Projective::hash::<ExpandMsgXmd<Sha256>>(
+            &v,
+            generator_dst.as_bytes(),
+        ));
+    }
+
+    Ok(generators)
+}
+
+fn base_point() -> Result<G1Projective, Error> {
+    Ok(create_generators(1, API_ID, "BP_MESSAGE_GENERATOR_SEED")?[0])
+}
+
+fn messages_to_scalars(messages: &[&[u8]]) -> Vec<Scalar> {
+    let map_dst = format!("{}MAP_MSG_TO_SCALAR_AS_HASH_", API_ID);
+    messages
+        .iter()
+        .map(|message| hash_to_scalar(message, &map_dst))
+        .collect()
+}
+
+fn calculate_domain(
+    public_key: &G2Affine,
+    q_1: &G1Projective,
+    h_points: &[G1Projective],
+    header: &[u8],
+) -> Scalar {
+    let mut dom_input = public_key.to_compressed().to_vec();
+    dom_input.extend_from_slice(&(h_points.len() as u64).to_be_bytes());
+    dom_input.extend_from_slice(&q_1.to_compressed());
+    for h in h_points {
+        dom_input.extend_from_slice(&h.to_compressed());
+    }
+    dom_input.extend_from_slice(API_ID.as_bytes());
+    dom_input.extend_from_slice(&(header.len() as u64).to_be_bytes());
+    dom_input.extend_from_slice(header);
+
+    hash_to_scalar(&dom_input, &format!("{}H2S_", API_ID))
+}
+
+fn calculate_b(
+    domain: &Scalar,
+    generators: &[G1Projective],
+    messages: &[(usize, Scalar)],
+) -> Result<G1Projective, Error> {
+    let mut b = base_point()? + generators[0] * domain;
+    for (index, message) in messages {
+        b += generators[index + 1] * message;
+    }
+
+    Ok(b)
+}
+
+fn pairing_is_identity(terms: &[(&G1Affine, &G2Affine)]) -> bool {
+    let prepared: Vec<(&G1Affine, G2Prepared)> = terms
+        .iter()
+        .map(|(g1, g2)| (*g1, G2Prepared::from(**g2)))
+        .collect();
+    let refs: Vec<(&G1Affine, &G2Prepared)> = prepared.iter().map(|(g1, g2)| (*g1, g2)).collect();
+
+    bool::from(
+        multi_miller_loop(refs.as_slice())
+            .final_exponentiation()
+            .is_identity(),
+    )
+}
+
+fn decode_point(bytes: &[u8]) -> Result<G1Projective, Error> {
+    let bytes: [u8; POINT_LEN] = match bytes.try_into() {
+        Ok(val) => val,
+        Err(_) => return Err(Error::SIGNATURE_IDENTIFICATION_FAILED),
+    };
+
+    match Option::<G1Affine>::from(G1Affine::from_compressed(&bytes)) {
+        Some(val) if !bool::from(val.is_identity()) => Ok(G1Projective::from(val)),
+        _ => Err(Error::SIGNATURE_IDENTIFICATION_FAILED),
+    }
+}
+
+fn decode_scalar(bytes: &[u8]) -> Result<Scalar, Error> {
+    let bytes: [u8; SCALAR_LEN] = match bytes.try_into() {
+        Ok(val) => val,
+        Err(_) => return Err(Error::SIGNATURE_IDENTIFICATION_FAILED),
+    };
+
+    match Option::<Scalar>::from(Scalar::from_be_bytes(&bytes)) {
+        Some(val) if !bool::from(val.is_zero()) => Ok(val),
+        _ => Err(Error::SIGNATURE_IDENTIFICATION_FAILED),
+    }
+}
+
+fn decode_base64(content: &str) -> Result<Vec<u8>, Error> {
+    match base64_url::decode(content) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::DECODING_ERROR.with_source(error))
+        }
+    }
+}
+
+fn challenge(
+    init_res: &[G1Projective; 5],
+    domain: &Scalar,
+    disclosed_messages: &[(usize, Scalar)],
+    presentation_header: &[u8],
+) -> Scalar {
+    let mut c_octs = (disclosed_messages.len() as u64).to_be_bytes().to_vec();
+    for (index, message) in disclosed_messages {
+        c_octs.extend_from_slice(&(*index as u64).to_be_bytes());
+        c_octs.extend_from_slice(&message.to_be_bytes());
+    }
+    for point in init_res {
+        c_octs.extend_from_slice(&point.to_compressed());
+    }
+    c_octs.extend_from_slice(&domain.to_be_bytes());
+    c_octs.extend_from_slice(&(presentation_header.len() as u64).to_be_bytes());
+    c_octs.extend_from_slice(presentation_header);
+
+    hash_to_scalar(&c_octs, &format!("{}H2S_", API_ID))
+}
+
+pub struct BbsSigningKey {
+    key: Scalar,
+}
+
+impl BbsSigningKey {
+    pub fn generate() -> Result<Self, Error> {
+        Self::generate_with_rng(&mut entropy::rng()?)
+    }
+
+    pub fn generate_with_rng(rng: &mut (impl CryptoRng + RngCore)) -> Result<Self, Error> {
+        let mut key_material = [0u8; 32];
+        rng.fill_bytes(&mut key_material);
+        Self::from_key_material(&key_material, &[])
+    }
+
+    pub fn from_key_material(key_material: &[u8], key_info: &[u8]) -> Result<Self, Error> {
+        if key_material.len() < 32 || key_info.len() > u16::MAX as usize {
+            return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+        }
+
+        let mut derive_input = key_material.to_vec();
+        derive_input.extend_from_slice(&(key_info.len() as u16).to_be_bytes());
+        derive_input.extend_from_slice(key_info);
+
+        let key = hash_to_scalar(&derive_input, &format!("{}KEYGEN_DST_", CIPHERSUITE_ID));
+        if bool::from(key.is_zero()) {
+            return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+        }
+
+        Ok(BbsSigningKey { key })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        match decode_scalar(bytes) {
+            Ok(key) => Ok(BbsSigningKey { key }),
+            Err(_) => Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.to_be_bytes().to_vec()
+    }
+
+    pub fn verifying_key(&self) -> BbsVerifyingKey {
+        BbsVerifyingKey {
+            key: (G2Projective::GENERATOR * self.key).to_affine(),
+        }
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.verifying_key().fingerprint()
+    }
+
+    pub fn sign(&self, header: &[u8], messages: &[&[u8]]) -> Result<String, Error> {
+        let public_key = self.verifying_key().key;
+        let generators = create_generators(messages.len() + 1, API_ID, "MESSAGE_GENERATOR_SEED")?;
+        let message_scalars = messages_to_scalars(messages);
+        let domain = calculate_domain(&public_key, &generators[0], &generators[1..], header);
+
+        let mut e_input = self.key.to_be_bytes().to_vec();
+        for message in &message_scalars {
+            e_input.extend_from_slice(&message.to_be_bytes());
+        }
+        e_input.extend_from_slice(&domain.to_be_bytes());
+        let e = hash_to_scalar(&e_input, &format!("{}H2S_", API_ID));
+
+        let indexed: Vec<(usize, Scalar)> = message_scalars.into_iter().enumerate().collect();
+        let b = calculate_b(&domain, &generators, &indexed)?;
+
+        let inverse = match Option::<Scalar>::from((self.key + e).invert()) {
+            Some(val) => val,
+            None => return Err(Error::SIGNING_FAILED),
+        };
+
+        let mut signature = (b * inverse).to_compressed().to_vec();
+        signature.extend_from_slice(&e.to_be_bytes());
+        Ok(base64_url::encode(&signature))
+    }
+}
+
+impl fmt::Debug for BbsSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(f, "BbsSigningKey", "BLS12-381 G2", self.fingerprint())
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct BbsVerifyingKey {
+    key: G2Affine,
+}
+
+impl BbsVerifyingKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let bytes: [u8; 96] = match bytes.try_into() {
+            Ok(val) => val,
+            Err(_) => return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR),
+        };
+
+        match Option::<G2Affine>::from(G2Affine::from_compressed(&bytes)) {
+            Some(val) if !bool::from(val.is_identity()) => Ok(BbsVerifyingKey { key: val }),
+            _ => Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.to_compressed().to_vec()
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        Bls12381G2VerifyingKey::from_bytes(&self.to_bytes())?.fingerprint()
+    }
+
+    pub fn verify(
+        &self,
+        signature: &str,
+        header: &[u8],
+        messages: &[&[u8]],
+    ) -> Result<bool, Error> {
+        let signature = decode_base64(signature)?;
+        if signature.len() != SIGNATURE_LEN {
+            return Err(Error::SIGNATURE_IDENTIFICATION_FAILED);
+        }
+
+        let a = decode_point(&signature[..POINT_LEN])?;
+        let e = decode_scalar(&signature[POINT_LEN..])?;
+
+        let generators = create_generators(messages.len() + 1, API_ID, "MESSAGE_GENERATOR_SEED")?;
+        let domain = calculate_domain(&self.key, &generators[0], &generators[1..], header);
+        let indexed: Vec<(usize, Scalar)> = messages_to_scalars(messages)
+            .into_iter()
+            .enumerate()
+            .collect();
+        let b = calculate_b(&domain, &generators, &indexed)?;
+
+        let w_e = (G2Projective::from(self.key) + G2Projective::GENERATOR * e).to_affine();
+        let neg_bp2 = (-G2Projective::GENERATOR).to_affine();
+
+        Ok(pairing_is_identity(&[
+            (&a.to_affine(), &w_e),
+            (&b.to_affine(), &neg_bp2),
+        ]))
+    }
+
+    pub fn proof_verify(
+        &self,
+        proof: &str,
+        header: &[u8],
+        presentation_header: &[u8],
+        disclosed_messages: &[(usize, &[u8])],
+    ) -> Result<bool, Error> {
+        let proof = decode_base64(proof)?;
+        if proof.len() < 3 * POINT_LEN + 4 * SCALAR_LEN
+            || !(proof.len() - 3 * POINT_LEN).is_multiple_of(SCALAR_LEN)
+        {
+            return Err(Error::SIGNATURE_IDENTIFICATION_FAILED);
+        }
+
+        let a_bar = decode_point(&proof[..POINT_LEN])?;
+        let b_bar = decode_point(&proof[POINT_LEN..2 * POINT_LEN])?;
+        let d = decode_point(&proof[2 * POINT_LEN..3 * POINT_LEN])?;
+
+        let mut scalars: Vec<Scalar> = Vec::new();
+        for chunk in proof[3 * POINT_LEN..].chunks(SCALAR_LEN) {
+            scalars.push(decode_scalar(chunk)?);
+        }
+
+        let (e_hat, r1_hat, r3_hat) = (scalars[0], scalars[1], scalars[2]);
+        let c = scalars[scalars.len() - 1];
+        let commitments = &scalars[3..scalars.len() - 1];
+
+        let total = disclosed_messages.len() + commitments.len();
+        let mut disclosed: Vec<(usize, Scalar)> = Vec::new();
+        for (index, message) in disclosed_messages {
+            if *index >= total || disclosed.iter().any(|(i, _)| i >= index) {
+                return Err(Error::BBS_INVALID_DISCLOSED_INDEX);
+            }
+            disclosed.push((*index, messages_to_scalars(&[message])[0]));
+        }
+
+        let generators = create_generators(total + 1, API_ID, "MESSAGE_GENERATOR_SEED")?;
+        let domain = calculate_domain(&self.key, &generators[0], &generators[1..], header);
+
+        let t1 = b_bar * c + a_bar * e_hat + d * r1_hat;
+        let mut t2 = calculate_b(&domain, &generators, &disclosed)? * c + d * r3_hat;
+        let undisclosed = (0..total).filter(|index| !disclosed.iter().any(|(i, _)| i == index));
+        for (index, commitment) in undisclosed.zip(commitments) {
+            t2 += generators[index + 1] * commitment;
+        }
+
+        if challenge(
+            &[a_bar, b_bar, d, t1, t2],
+            &domain,
+            &disclosed,
+            presentation_header,
+        ) != c
+        {
+            return Ok(false);
+        }
+
+        let neg_bp2 = (-G2Projective::GENERATOR).to_affine();
+        Ok(pairing_is_identity(&[
+            (&a_bar.to_affine(), &self.key),
+            (&b_bar.to_affine(), &neg_bp2),
+        ]))
+    }
+}
+
+impl fmt::Debug for BbsVerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(f, "BbsVerifyingKey", "BLS12-381 G2", self.fingerprint())
+    }
+}
+
+pub fn proof_gen(
+    verifying_key: &BbsVerifyingKey,
+    signature: &str,
+    header: &[u8],
+    presentation_header: &[u8],
+    messages: &[&[u8]],
+    disclosed_indexes: &[usize],
+) -> Result<String, Error> {
+    proof_gen_with_rng(
+        verifying_key,
+        signature,
+        header,
+        presentation_header,
+        messages,
+        disclosed_indexes,
+        &mut OsRng,
+    )
+}
+
+pub fn proof_gen_with_rng(
+    verifying_key: &BbsVerifyingKey,
+    signature: &str,
+    header: &[u8],
+    presentation_header: &[u8],
+    messages: &[&[u8]],
+    disclosed_indexes: &[usize],
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<String, Error> {
+    let mut disclosed_indexes = disclosed_indexes.to_vec();
+    disclosed_indexes.sort_unstable();
+    disclosed_indexes.dedup();
+    if disclosed_indexes
+        .iter()
+        .any(|index| *index >= messages.len())
+    {
+        return Err(Error::BBS_INVALID_DISCLOSED_INDEX);
+    }
+
+    let signature = decode_base64(signature)?;
+    if signature.len() != SIGNATURE_LEN {
+        return Err(Error::SIGNATURE_IDENTIFICATION_FAILED);
+    }
+    let a = decode_point(&signature[..POINT_LEN])?;
+    let e = decode_scalar(&signature[POINT_LEN..])?;
+
+    let message_scalars = messages_to_scalars(messages);
+    let generators = create_generators(messages.len() + 1, API_ID, "MESSAGE_GENERATOR_SEED")?;
+    let domain = calculate_domain(&verifying_key.key, &generators[0], &generators[1..], header);
+
+    let indexed: Vec<(usize, Scalar)> = message_scalars.iter().cloned().enumerate().collect();
+    let (disclosed, undisclosed): (Vec<_>, Vec<_>) = indexed
+        .iter()
+        .cloned()
+        .partition(|(index, _)| disclosed_indexes.contains(index));
+
+    let r1 = Scalar::random(&mut *rng);
+    let r2 = Scalar::random(&mut *rng);
+    let e_tilde = Scalar::random(&mut *rng);
+    let r1_tilde = Scalar::random(&mut *rng);
+    let r3_tilde = Scalar::random(&mut *rng);
+    let m_tilde: Vec<Scalar> = undisclosed
+        .iter()
+        .map(|_| Scalar::random(&mut *rng))
+        .collect();
+
+    let b = calculate_b(&domain, &generators, &indexed)?;
+    let d = b * r2;
+    let a_bar = a * (r1 * r2);
+    let b_bar = d * r1 - a_bar * e;
+    let t1 = a_bar * e_tilde + d * r1_tilde;
+    let mut t2 = d * r3_tilde;
+    for ((index, _), tilde) in undisclosed.iter().zip(&m_tilde) {
+        t2 += generators[index + 1] * tilde;
+    }
+
+    let c = challenge(
+        &[a_bar, b_bar, d, t1, t2],
+        &domain,
+        &disclosed,
+        presentation_header,
+    );
+
+    let r3 = match Option::<Scalar>::from(r2.invert()) {
+        Some(val) => val,
+        None => return Err(Error::SIGNING_FAILED),
+    };
+
+    let mut proof = Vec::new();
+    for point in [a_bar, b_bar, d] {
+        proof.extend_from_slice(&point.to_compressed());
+    }
+    for scalar in [e_tilde + e * c, r1_tilde - r1 * c, r3_tilde - r3 * c] {
+        proof.extend_from_slice(&scalar.to_be_bytes());
+    }
+    for ((_, message), tilde) in undisclosed.iter().zip(&m_tilde) {
+        proof.extend_from_slice(&(*tilde + message * c).to_be_bytes());
+    }
+    proof.extend_from_slice(&c.to_be_bytes());
+
+    Ok(base64_url::encode(&proof))
+}
@@ -0,0 +1,570 @@
+use std::fmt;
+
+use bls12_381_plus::{
+    elliptic_curve::hash2curve::ExpandMsgXmd,
+    ff::Field,
+    group::{Curve, Group},
+    multi_miller_loop, G1Affine, G1Projective, G2Affine, G2Prepared, G2Projective, Scalar,
+};
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+
+use crate::{errors::Error, jwk::Jwk, log, multicodec};
+
+use super::{
+    entropy, fmt_key,
+    kdf::{hkdf, HashFunction},
+};
+
+const G2_SIGNATURE_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+const G2_POP_DST: &[u8] = b"BLS_POP_BLS12381G2_XMD:SHA-256_SSWU_RO_POP_";
+const G1_SIGNATURE_DST: &[u8] = b"BLS_SIG_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+const G1_POP_DST: &[u8] = b"BLS_POP_BLS12381G1_XMD:SHA-256_SSWU_RO_POP_";
+
+const G1_JWK_CURVE: &str = "Bls12381G1";
+const G2_JWK_CURVE: &str = "Bls12381G2";
+const G1_EC_JWK_CURVE: &str = "BLS12381_G1";
+const G2_EC_JWK_CURVE: &str = "BLS12381_G2";
+
+fn key_gen(key_material: &[u8], key_info: &[u8]) -> Result<Scalar, Error> {
+    if key_material.len() < 32 {
+        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+    }
+
+    let mut ikm = key_material.to_vec();
+    ikm.push(0);
+    let mut info = key_info.to_vec();
+    info.extend_from_slice(&48u16.to_be_bytes());
+
+    let mut salt = Sha256::digest(b"BLS-SIG-KEYGEN-SALT-").to_vec();
+    loop {
+        let okm = match hkdf(HashFunction::Sha256, Some(&salt), &ikm, &info, 48) {
+            Ok(val) => val,
+            Err(_) => return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR),
+        };
+
+        let key = Scalar::from_okm(okm.as_slice().try_into().unwrap());
+        if !bool::from(key.is_zero()) {
+            return Ok(key);
+        }
+        salt = Sha256::digest(&salt).to_vec();
+    }
+}
+
+fn random_key(rng: &mut (impl CryptoRng + RngCore)) -> Result<Scalar, Error> {
+    let mut key_material = [0u8; 32];
+    rng.fill_bytes(&mut key_material);
+    key_gen(&key_material, &[])
+}
+
+fn decode_secret(bytes: &[u8]) -> Result<Scalar, Error> {
+    let bytes: [u8; 32] = match bytes.try_into() {
+        Ok(val) => val,
+        Err(_) => return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR),
+    };
+
+    match Option::<Scalar>::from(Scalar::from_be_bytes(&bytes)) {
+        Some(val) if !bool::from(val.is_zero()) => Ok(val),
+        _ => Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR),
+    }
+}
+
+fn decode_signature(signature: &str) -> Result<Vec<u8>, Error> {
+    match base64_url::decode(signature) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::DECODING_ERROR.with_source(error))
+        }
+    }
+}
+
+fn decode_g1(bytes: &[u8], error: Error) -> Result<G1Affine, Error> {
+    let point = match bytes.len() {
+        48 => G1Affine::from_compressed(bytes.try_into().unwrap()),
+        96 => G1Affine::from_uncompressed(bytes.try_into().unwrap()),
+        _ => return Err(error),
+    };
+
+    match Option::<G1Affine>::from(point) {
+        Some(val) if !bool::from(val.is_identity()) => Ok(val),
+        _ => Err(error),
+    }
+}
+
+fn decode_g2(bytes: &[u8], error: Error) -> Result<G2Affine, Error> {
+    let point = match bytes.len() {
+        96 => G2Affine::from_compressed(bytes.try_into().unwrap()),
+        192 => G2Affine::from_uncompressed(bytes.try_into().unwrap()),
+        _ => return Err(error),
+    };
+
+    match Option::<G2Affine>::from(point) {
+        Some(val) if !bool::from(val.is_identity()) => Ok(val),
+        _ => Err(error),
+    }
+}
+
+fn pairing_is_identity(terms: &[(G1Affine, G2Affine)]) -> bool {
+    let prepared: Vec<(G1Affine, G2Prepared)> = terms
+        .iter()
+        .map(|(g1, g2)| (*g1, G2Prepared::from(*g2)))
+        .collect();
+    let refs: Vec<(&G1Affine, &G2Prepared)> = prepared.iter().map(|(g1, g2)| (g1, g2)).collect();
+
+    bool::from(
+        multi_miller_loop(refs.as_slice())
+            .final_exponentiation()
+            .is_identity(),
+    )
+}
+
+fn public_key_from_jwk(jwk: &Jwk, okp_curve: &str, ec_curve: &str) -> Result<Vec<u8>, Error> {
+    match (jwk.kty.as_str(), jwk.crv.as_deref()) {
+        ("OKP", Some(crv)) if crv == okp_curve => Jwk::decode_member(&jwk.x),
+        ("EC", Some(crv)) if crv == ec_curve => {
+            let mut bytes = Jwk::decode_member(&jwk.x)?;
+            bytes.extend_from_slice(&Jwk::decode_member(&jwk.y)?);
+            Ok(bytes)
+        }
+        _ => Err(Error::JWK_KEY_TYPE_MISMATCH),
+    }
+}
+
+pub struct Bls12381G1SigningKey {
+    key: Scalar,
+}
+
+impl Bls12381G1SigningKey {
+    pub fn generate() -> Result<Self, Error> {
+        Self::generate_with_rng(&mut entropy::rng()?)
+    }
+
+    pub fn generate_with_rng(rng: &mut (impl CryptoRng + RngCore)) -> Result<Self, Error> {
+        Ok(Bls12381G1SigningKey {
+            key: random_key(rng)?,
+        })
+    }
+
+    pub fn from_key_material(key_material: &[u8], key_info: &[u8]) -> Result<Self, Error> {
+        Ok(Bls12381G1SigningKey {
+            key: key_gen(key_material, key_info)?,
+        })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Bls12381G1SigningKey {
+            key: decode_secret(bytes)?,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.to_be_bytes().to_vec()
+    }
+
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        public_key_from_jwk(jwk, G1_JWK_CURVE, G1_EC_JWK_CURVE)?;
+        Self::from_bytes(&Jwk::decode_member(&jwk.d)?)
+    }
+
+    pub fn to_jwk(&self) -> Jwk {
+        Jwk {
+            d: Some(base64_url::encode(&self.to_bytes())),
+            ..self.verifying_key().to_jwk()
+        }
+    }
+
+    pub fn verifying_key(&self) -> Bls12381G1VerifyingKey {
+        Bls12381G1VerifyingKey {
+            key: (G1Projective::GENERATOR * self.key).to_affine(),
+        }
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.verifying_key().fingerprint()
+    }
+
+    pub fn sign(&self, message: &[u8]) -> String {
+        let point = G2Projective::hash::<ExpandMsgXmd<Sha256>>(message, G2_SIGNATURE_DST);
+        base64_url::encode(&(point * self.key).to_compressed())
+    }
+
+    pub fn prove_possession(&self) -> String {
+        let point = G2Projective::hash::<ExpandMsgXmd<Sha256>>(
+            &self.verifying_key().to_bytes(),
+            G2_POP_DST,
+        );
+        base64_url::encode(&(point * self.key).to_compressed())
+    }
+}
+
+impl fmt::Debug for Bls12381G1SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(
+            f,
+            "Bls12381G1SigningKey",
+            "BLS12-381 G1",
+            self.fingerprint(),
+        )
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Bls12381G1VerifyingKey {
+    key: G1Affine,
+}
+
+impl Bls12381G1VerifyingKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Bls12381G1VerifyingKey {
+            key: decode_g1(bytes, Error::PUBLIC_KEY_IDENTIFICATION_ERROR)?,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.to_compressed().to_vec()
+    }
+
+    pub fn from_multicodec(bytes: &[u8]) -> Result<Self, Error> {
+        match multicodec::decode(bytes)? {
+            (multicodec::BLS12_381_G1_PUB, key) => Self::from_bytes(key),
+            _ => Err(Error::MULTICODEC_ERROR),
+        }
+    }
+
+    pub fn to_multicodec(&self) -> Vec<u8> {
+        multicodec::encode(multicodec::BLS12_381_G1_PUB, &self.to_bytes())
+    }
+
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        Self::from_bytes(&public_key_from_jwk(jwk, G1_JWK_CURVE, G1_EC_JWK_CURVE)?)
+    }
+
+    pub fn to_jwk(&self) -> Jwk {
+        Jwk {
+            kty: String::from("OKP"),
+            crv: Some(String::from(G1_JWK_CURVE)),
+            x: Some(base64_url::encode(&self.to_bytes())),
+            ..Default::default()
+        }
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.to_jwk().thumbprint()
+    }
+
+    fn verify_point(&self, message: &[u8], dst: &[u8], signature: &str) -> Result<bool, Error> {
+        let signature = decode_g2(
+            &decode_signature(signature)?,
+            Error::SIGNATURE_IDENTIFICATION_FAILED,
+        )?;
+        let point = G2Projective::hash::<ExpandMsgXmd<Sha256>>(message, dst).to_affine();
+
+        Ok(pairing_is_identity(&[
+            (self.key, point),
+            (-G1Affine::generator(), signature),
+        ]))
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &str) -> Result<bool, Error> {
+        self.verify_point(message, G2_SIGNATURE_DST, signature)
+    }
+
+    pub fn verify_possession(&self, proof: &str) -> Result<bool, Error> {
+        self.verify_point(&self.to_bytes(), G2_POP_DST, proof)
+    }
+
+    pub fn aggregate_signatures(signatures: &[&str]) -> Result<String, Error> {
+        if signatures.is_empty() {
+            return Err(Error::BLS_EMPTY_AGGREGATE);
+        }
+
+        let mut aggregate = G2Projective::IDENTITY;
+        for signature in signatures {
+            aggregate += decode_g2(
+                &decode_signature(signature)?,
+                Error::SIGNATURE_IDENTIFICATION_FAILED,
+            )?;
+        }
+
+        Ok(base64_url::encode(&aggregate.to_compressed()))
+    }
+
+    pub fn aggregate_verify(
+        keys: &[Self],
+        messages: &[&[u8]],
+        signature: &str,
+    ) -> Result<bool, Error> {
+        if keys.is_empty() || keys.len() != messages.len() {
+            return Err(Error::BLS_EMPTY_AGGREGATE);
+        }
+
+        let signature = decode_g2(
+            &decode_signature(signature)?,
+            Error::SIGNATURE_IDENTIFICATION_FAILED,
+        )?;
+
+        let mut terms: Vec<(G1Affine, G2Affine)> = keys
+            .iter()
+            .zip(messages)
+            .map(|(key, message)| {
+                (
+                    key.key,
+                    G2Projective::hash::<ExpandMsgXmd<Sha256>>(message, G2_SIGNATURE_DST)
+                        .to_affine(),
+                )
+            })
+            .collect();
+        terms.push((-G1Affine::generator(), signature));
+
+        Ok(pairing_is_identity(&terms))
+    }
+
+    pub fn fast_aggregate_verify(
+        keys: &[Self],
+        message: &[u8],
+        signature: &str,
+    ) -> Result<bool, Error> {
+        if keys.is_empty() {
+            return Err(Error::BLS_EMPTY_AGGREGATE);
+        }
+
+        let aggregate = keys
+            .iter()
+            .fold(G1Projective::IDENTITY, |sum, key| sum + key.key);
+        Bls12381G1VerifyingKey {
+            key: aggregate.to_affine(),
+        }
+        .verify(message, signature)
+    }
+}
+
+impl fmt::Debug for Bls12381G1VerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(
+            f,
+            "Bls12381G1VerifyingKey",
+            "BLS12-381 G1",
+            self.fingerprint(),
+        )
+    }
+}
+
+pub struct Bls12381G2SigningKey {
+    key: Scalar,
+}
+
+impl Bls12381G2SigningKey {
+    pub fn generate() -> Result<Self, Error> {
+        Self::generate_with_rng(&mut entropy::rng()?)
+    }
+
+    pub fn generate_with_rng(rng: &mut (impl CryptoRng + RngCore)) -> Result<Self, Error> {
+        Ok(Bls12381G2SigningKey {
+            key: random_key(rng)?,
+        })
+    }
+
+    pub fn from_key_material(key_material: &[u8], key_info: &[u8]) -> Result<Self, Error> {
+        Ok(Bls12381G2SigningKey {
+            key: key_gen(key_material, key_info)?,
+        })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Bls12381G2SigningKey {
+            key: decode_secret(bytes)?,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.to_be_bytes().to_vec()
+    }
+
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        public_key_from_jwk(jwk, G2_JWK_CURVE, G2_EC_JWK_CURVE)?;
+        Self::from_bytes(&Jwk::decode_member(&jwk.d)?)
+    }
+
+    pub fn to_jwk(&self) -> Jwk {
+        Jwk {
+            d: Some(base64_url::encode(&self.to_bytes())),
+            ..self.verifying_key().to_jwk()
+        }
+    }
+
+    pub fn verifying_key(&self) -> Bls12381G2VerifyingKey {
+        Bls12381G2VerifyingKey {
+            key: (G2Projective::GENERATOR * self.key).to_affine(),
+        }
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.verifying_key().fingerprint()
+    }
+
+    pub fn sign(&self, message: &[u8]) -> String {
+        let point = G1Projective::hash::<ExpandMsgXmd<Sha256>>(message, G1_SIGNATURE_DST);
+        base64_url::encode(&(point * self.key).to_compressed())
+    }
+
+    pub fn prove_possession(&self) -> String {
+        let point = G1Projective::hash::<ExpandMsgXmd<Sha256>>(
+            &self.verifying_key().to_bytes(),
+            G1_POP_DST,
+        );
+        base64_url::encode(&(point * self.key).to_compressed())
+    }
+}
+
+impl fmt::Debug for Bls12381G2SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(
+            f,
+            "Bls12381G2SigningKey",
+            "BLS12-381 G2",
+            self.fingerprint(),
+        )
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Bls12381G2VerifyingKey {
+    key: G2Affine,
+}
+
+impl Bls12381G2VerifyingKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(Bls12381G2VerifyingKey {
+            key: decode_g2(bytes, Error::PUBLIC_KEY_IDENTIFICATION_ERROR)?,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.to_compressed().to_vec()
+    }
+
+    pub fn from_multicodec(bytes: &[u8]) -> Result<Self, Error> {
+        match multicodec::decode(bytes)? {
+            (multicodec::BLS12_381_G2_PUB, key) => Self::from_bytes(key),
+            _ => Err(Error::MULTICODEC_ERROR),
+        }
+    }
+
+    pub fn to_multicodec(&self) -> Vec<u8> {
+        multicodec::encode(multicodec::BLS12_381_G2_PUB, &self.to_bytes())
+    }
+
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        Self::from_bytes(&public_key_from_jwk(jwk, G2_JWK_CURVE, G2_EC_JWK_CURVE)?)
+    }
+
+    pub fn to_jwk(&self) -> Jwk {
+        Jwk {
+            kty: String::from("OKP"),
+            crv: Some(String::from(G2_JWK_CURVE)),
+            x: Some(base64_url::encode(&self.to_bytes())),
+            ..Default::default()
+        }
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.to_jwk().thumbprint()
+    }
+
+    fn verify_point(&self, message: &[u8], dst: &[u8], signature: &str) -> Result<bool, Error> {
+        let signature = decode_g1(
+            &decode_signature(signature)?,
+            Error::SIGNATURE_IDENTIFICATION_FAILED,
+        )?;
+        let point = G1Projective::hash::<ExpandMsgXmd<Sha256>>(message, dst).to_affine();
+
+        Ok(pairing_is_identity(&[
+            (point, self.key),
+            (signature, -G2Affine::generator()),
+        ]))
+    }
+
+    pub fn verify(&self, message: &[u8], signature: &str) -> Result<bool, Error> {
+        self.verify_point(message, G1_SIGNATURE_DST, signature)
+    }
+
+    pub fn verify_possession(&self, proof: &str) -> Result<bool, Error> {
+        self.verify_point(&self.to_bytes(), G1_POP_DST, proof)
+    }
+
+    pub fn aggregate_signatures(signatures: &[&str]) -> Result<String, Error> {
+        if signatures.is_empty() {
+            return Err(Error::BLS_EMPTY_AGGREGATE);
+        }
+
+        let mut aggregate = G1Projective::IDENTITY;
+        for signature in signatures {
+            aggregate += decode_g1(
+                &decode_signature(signature)?,
+                Error::SIGNATURE_IDENTIFICATION_FAILED,
+            )?;
+        }
+
+        Ok(base64_url::encode(&aggregate.to_compressed()))
+    }
+
+    pub fn aggregate_verify(
+        keys: &[Self],
+        messages: &[&[u8]],
+        signature: &str,
+    ) -> Result<bool, Error> {
+        if keys.is_empty() || keys.len() != messages.len() {
+            return Err(Error::BLS_EMPTY_AGGREGATE);
+        }
+
+        let signature = decode_g1(
+            &decode_signature(signature)?,
+            Error::SIGNATURE_IDENTIFICATION_FAILED,
+        )?;
+
+        let mut terms: Vec<(G1Affine, G2Affine)> = keys
+            .iter()
+            .zip(messages)
+            .map(|(key, message)| {
+                (
+                    G1Projective::hash::<ExpandMsgXmd<Sha256>>(message, G1_SIGNATURE_DST)
+                        .to_affine(),
+                    key.key,
+                )
+            })
+            .collect();
+        terms.push((signature, -G2Affine::generator()));
+
+        Ok(pairing_is_identity(&terms))
+    }
+
+    pub fn fast_aggregate_verify(
+        keys: &[Self],
+        message: &[u8],
+        signature: &str,
+    ) -> Result<bool, Error> {
+        if keys.is_empty() {
+            return Err(Error::BLS_EMPTY_AGGREGATE);
+        }
+
+        let aggregate = keys
+            .iter()
+            .fold(G2Projective::IDENTITY, |sum, key| sum + key.key);
+        Bls12381G2VerifyingKey {
+            key: aggregate.to_affine(),
+        }
+        .verify(message, signature)
+    }
+}
+
+impl fmt::Debug for Bls12381G2VerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(
+            f,
+            "Bls12381G2VerifyingKey",
+            "BLS12-381 G2",
+            self.fingerprint(),
+        )
+    }
+}
@@ -0,0 +1,251 @@
+use std::fmt;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+use sha2::{Digest, Sha512};
+
+use crate::{algorithms::Algorithm, errors::Error, jwk::Jwk, log, verifier::VerificationOptions};
+
+use super::{
+    fmt_key,
+    mldsa::{
+        MLDSA65SigningKey, MLDSA65VerifyingKey, AKP_KEY_TYPE, MLDSA65_PUBLIC_KEY_LENGTH,
+        MLDSA65_SEED_LENGTH, MLDSA65_SIGNATURE_LENGTH,
+    },
+    SignFromKey, VerifyFromKey,
+};
+
+const PREFIX: &[u8] = b"CompositeAlgorithmSignatures2025";
+const MLDSA65_ED25519_LABEL: &[u8] = b"COMPSIG-MLDSA65-Ed25519-SHA512";
+const ED25519_KEY_LENGTH: usize = 32;
+const ED25519_SIGNATURE_LENGTH: usize = 64;
+
+fn message_representative(content: &[u8]) -> Vec<u8> {
+    let mut message = PREFIX.to_vec();
+    message.extend_from_slice(MLDSA65_ED25519_LABEL);
+    message.push(0);
+    message.extend_from_slice(&Sha512::digest(content));
+    message
+}
+
+fn check_jwk(jwk: &Jwk) -> Result<(), Error> {
+    match (jwk.kty.as_str(), jwk.alg.as_deref()) {
+        (AKP_KEY_TYPE, Some(alg)) if alg == Algorithm::MLDSA65Ed25519.to_str() => Ok(()),
+        _ => Err(Error::JWK_KEY_TYPE_MISMATCH),
+    }
+}
+
+pub struct MLDSA65Ed25519SigningKey {
+    mldsa: MLDSA65SigningKey,
+    ed25519: SigningKey,
+}
+
+impl SignFromKey for MLDSA65Ed25519SigningKey {
+    fn sign_bytes(&self, content: &[u8], alg: Algorithm) -> Result<String, Error> {
+        if alg != Algorithm::MLDSA65Ed25519 {
+            return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg));
+        }
+
+        let message = message_representative(content);
+        let mut signature = self
+            .mldsa
+            .sign_with_context(&message, MLDSA65_ED25519_LABEL)?;
+        match self.ed25519.try_sign(&message) {
+            Ok(val) => signature.extend_from_slice(&val.to_bytes()),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::SIGNING_FAILED.with_source(error));
+            }
+        };
+
+        Ok(base64_url::encode(&signature))
+    }
+}
+
+impl MLDSA65Ed25519SigningKey {
+    pub fn generate() -> Self {
+        Self::generate_with_rng(&mut OsRng)
+    }
+
+    pub fn generate_with_rng(rng: &mut (impl CryptoRng + RngCore)) -> Self {
+        let mut bytes = [0u8; MLDSA65_SEED_LENGTH + ED25519_KEY_LENGTH];
+        rng.fill_bytes(&mut bytes);
+        Self::from_bytes(&bytes).unwrap()
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != MLDSA65_SEED_LENGTH + ED25519_KEY_LENGTH {
+            return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+        }
+
+        let (mldsa, ed25519) = bytes.split_at(MLDSA65_SEED_LENGTH);
+        Ok(MLDSA65Ed25519SigningKey {
+            mldsa: MLDSA65SigningKey::from_bytes(mldsa)?,
+            ed25519: SigningKey::from_bytes(ed25519.try_into().unwrap()),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.mldsa.to_bytes();
+        bytes.extend_from_slice(&self.ed25519.to_bytes());
+        bytes
+    }
+
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        check_jwk(jwk)?;
+        let key = Self::from_bytes(&Jwk::decode_member(&jwk.private)?)?;
+        if let Some(public) = &jwk.public {
+            if *public != base64_url::encode(&key.verifying_key().to_bytes()) {
+                return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+            }
+        }
+
+        Ok(key)
+    }
+
+    pub fn to_jwk(&self) -> Jwk {
+        Jwk {
+            private: Some(base64_url::encode(&self.to_bytes())),
+            ..self.verifying_key().to_jwk()
+        }
+    }
+
+    pub fn verifying_key(&self) -> MLDSA65Ed25519VerifyingKey {
+        MLDSA65Ed25519VerifyingKey {
+            mldsa: self.mldsa.verifying_key(),
+            ed25519: self.ed25519.verifying_key(),
+        }
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.verifying_key().fingerprint()
+    }
+}
+
+impl fmt::Debug for MLDSA65Ed25519SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(
+            f,
+            "MLDSA65Ed25519SigningKey",
+            "ML-DSA-65+Ed25519",
+            self.fingerprint(),
+        )
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct MLDSA65Ed25519VerifyingKey {
+    mldsa: MLDSA65VerifyingKey,
+    ed25519: VerifyingKey,
+}
+
+impl VerifyFromKey for MLDSA65Ed25519VerifyingKey {
+    fn verifying_algorithms(&self) -> Option<Vec<Algorithm>> {
+        Some(vec![Algorithm::MLDSA65Ed25519])
+    }
+
+    fn verify_bytes(&self, content: &[u8], sig: &str, alg: Algorithm) -> Result<bool, Error> {
+        if alg != Algorithm::MLDSA65Ed25519 {
+            return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg));
+        }
+
+        self.verify_with_options(content, sig, &VerificationOptions::default())
+    }
+}
+
+impl MLDSA65Ed25519VerifyingKey {
+    pub fn verify_with_options(
+        &self,
+        content: &[u8],
+        sig: &str,
+        options: &VerificationOptions,
+    ) -> Result<bool, Error> {
+        options.algorithm_policy.check(Algorithm::MLDSA65Ed25519)?;
+
+        let decoded_sig = match base64_url::decode(sig.as_bytes()) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return options.apply(Err(Error::DECODING_ERROR.with_source(error)));
+            }
+        };
+
+        if decoded_sig.len() != MLDSA65_SIGNATURE_LENGTH + ED25519_SIGNATURE_LENGTH {
+            return options.apply(Err(Error::SIGNATURE_IDENTIFICATION_FAILED));
+        }
+
+        let (mldsa_sig, ed25519_sig) = decoded_sig.split_at(MLDSA65_SIGNATURE_LENGTH);
+        let message = message_representative(content);
+        let ed25519_verified = match self
+            .ed25519
+            .verify(&message, &Signature::from_slice(ed25519_sig).unwrap())
+        {
+            Ok(_) => Ok(true),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Ok(false)
+            }
+        };
+
+        options.combine([
+            self.mldsa
+                .verify_with_context(&message, mldsa_sig, MLDSA65_ED25519_LABEL),
+            ed25519_verified,
+        ])
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != MLDSA65_PUBLIC_KEY_LENGTH + ED25519_KEY_LENGTH {
+            return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR);
+        }
+
+        let (mldsa, ed25519) = bytes.split_at(MLDSA65_PUBLIC_KEY_LENGTH);
+        let ed25519 = match VerifyingKey::from_bytes(ed25519.try_into().unwrap()) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
+            }
+        };
+
+        Ok(MLDSA65Ed25519VerifyingKey {
+            mldsa: MLDSA65VerifyingKey::from_bytes(mldsa)?,
+            ed25519,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.mldsa.to_bytes();
+        bytes.extend_from_slice(self.ed25519.as_bytes());
+        bytes
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.to_jwk().thumbprint()
+    }
+
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        check_jwk(jwk)?;
+        Self::from_bytes(&Jwk::decode_member(&jwk.public)?)
+    }
+
+    pub fn to_jwk(&self) -> Jwk {
+        Jwk {
+            kty: String::from(AKP_KEY_TYPE),
+            alg: Some(String::from(Algorithm::MLDSA65Ed25519.to_str())),
+            public: Some(base64_url::encode(&self.to_bytes())),
+            ..Default::default()
+        }
+    }
+}
+
+impl fmt::Debug for MLDSA65Ed25519VerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(
+            f,
+            "MLDSA65Ed25519VerifyingKey",
+            "ML-DSA-65+Ed25519",
+            self.fingerprint(),
+        )
+    }
+}
@@ -0,0 +1,65 @@
+use zeroize::Zeroizing;
+
+use crate::{errors::Error, log};
+
+use super::{
+    any::{AnySigningKey, AnyVerifyingKey},
+    kdf::{hkdf, HashFunction},
+};
+
+fn public_key_error(error: impl std::error::Error + Send + Sync + 'static) -> Error {
+    log::error(error.to_string().as_str());
+    Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error)
+}
+
+fn secret_key_error(error: impl std::error::Error + Send + Sync + 'static) -> Error {
+    log::error(error.to_string().as_str());
+    Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error)
+}
+
+fn shared_secret(key: &AnySigningKey, peer: &AnyVerifyingKey) -> Result<Zeroizing<Vec<u8>>, Error> {
+    match (key, peer) {
+        (AnySigningKey::P256(key), AnyVerifyingKey::P256(peer)) => {
+            let secret_key = p256::SecretKey::from_slice(&Zeroizing::new(key.to_bytes()))
+                .map_err(secret_key_error)?;
+            let public_key = p256::PublicKey::from_sec1_bytes(&peer.to_sec1_bytes(false))
+                .map_err(public_key_error)?;
+            Ok(Zeroizing::new(
+                p256::ecdh::diffie_hellman(secret_key.to_nonzero_scalar(), public_key.as_affine())
+                    .raw_secret_bytes()
+                    .to_vec(),
+            ))
+        }
+        #[cfg(feature = "es384")]
+        (AnySigningKey::P384(key), AnyVerifyingKey::P384(peer)) => {
+            let secret_key = p384::SecretKey::from_slice(&Zeroizing::new(key.to_bytes()))
+                .map_err(secret_key_error)?;
+            let public_key = p384::PublicKey::from_sec1_bytes(&peer.to_sec1_bytes(false))
+                .map_err(public_key_error)?;
+            Ok(Zeroizing::new(
+                p384::ecdh::diffie_hellman(secret_key.to_nonzero_scalar(), public_key.as_affine())
+                    .raw_secret_bytes()
+                    .to_vec(),
+            ))
+        }
+        _ => Err(Error::ECDH_UNSUPPORTED_KEY.with_algorithm(key.algorithm())),
+    }
+}
+
+pub fn derive_shared_key(
+    key: &AnySigningKey,
+    peer: &AnyVerifyingKey,
+    hash: HashFunction,
+    salt: Option<&[u8]>,
+    info: &[u8],
+    length: usize,
+) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let shared_secret = shared_secret(key, peer)?;
+    Ok(Zeroizing::new(hkdf(
+        hash,
+        salt,
+        &shared_secret,
+        info,
+        length,
+    )?))
+}
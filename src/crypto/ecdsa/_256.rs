@@ -1,30 +1,76 @@
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
 use crate::{
     algorithms::Algorithm,
-    crypto::{SignFromKey, VerifyFromKey},
+    crypto::{
+        ecdsa::{ec_jwk, NonceMode},
+        fmt_key, x509, SignFromKey, VerifyFromKey,
+    },
     errors::Error,
     log,
 };
 use elliptic_curve::pkcs8::DecodePublicKey;
 use p256::{
-    ecdsa::{signature::Signer, signature::Verifier, Signature, SigningKey, VerifyingKey},
+    ecdsa::{
+        signature::RandomizedSigner, signature::Signer, signature::Verifier, Signature, SigningKey,
+        VerifyingKey,
+    },
     NistP256,
 };
+use rand::{rngs::OsRng, CryptoRng, RngCore};
 
 pub struct P256SigningKey {
     pub key: SigningKey,
+    nonce_mode: NonceMode,
 }
 
 impl SignFromKey for P256SigningKey {
-    fn sign(&self, content: String, _alg: Algorithm) -> Result<String, Error> {
-        let sig_result: Result<Signature, p256::ecdsa::Error> =
-            self.key.try_sign(content.as_bytes());
+    fn sign_bytes(&self, content: &[u8], _alg: Algorithm) -> Result<String, Error> {
+        self.sign_bytes_with_nonce_mode(content, self.nonce_mode)
+    }
+}
+
+impl P256SigningKey {
+    pub fn with_nonce_mode(mut self, nonce_mode: NonceMode) -> Self {
+        self.nonce_mode = nonce_mode;
+        self
+    }
+
+    pub fn nonce_mode(&self) -> NonceMode {
+        self.nonce_mode
+    }
+
+    pub fn sign_bytes_with_nonce_mode(
+        &self,
+        content: &[u8],
+        nonce_mode: NonceMode,
+    ) -> Result<String, Error> {
+        if nonce_mode == NonceMode::Hedged {
+            return self.sign_bytes_with_rng(content, &mut OsRng);
+        }
+
+        let sig_result: Result<Signature, p256::ecdsa::Error> = self.key.try_sign(content);
         let signature = match sig_result {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::SIGNING_FAILED);
+                return Err(Error::SIGNING_FAILED.with_source(error));
+            }
+        };
+
+        Ok(base64_url::encode(signature.to_bytes().as_slice()))
+    }
+
+    pub fn sign_bytes_with_rng(
+        &self,
+        content: &[u8],
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<String, Error> {
+        let signature: Signature = match self.key.try_sign_with_rng(rng, content) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::SIGNING_FAILED.with_source(error));
             }
         };
 
@@ -41,7 +87,7 @@ impl P256SigningKey {
                         Ok(val) => val,
                         Err(error) => {
                             log::error(error.to_string().as_str());
-                            return Err(Error::EC_PEM_ERROR);
+                            return Err(Error::EC_PEM_ERROR.with_source(error));
                         }
                     };
 
@@ -49,7 +95,7 @@ impl P256SigningKey {
                     Ok(val) => val,
                     Err(error) => {
                         log::error(error.to_string().as_str());
-                        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+                        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
                     }
                 }
             }
@@ -59,7 +105,7 @@ impl P256SigningKey {
                         Ok(val) => val,
                         Err(error) => {
                             log::error(error.to_string().as_str());
-                            return Err(Error::EC_PEM_ERROR);
+                            return Err(Error::EC_PEM_ERROR.with_source(error));
                         }
                     };
 
@@ -67,13 +113,16 @@ impl P256SigningKey {
                     Ok(val) => val,
                     Err(error) => {
                         log::error(error.to_string().as_str());
-                        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+                        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
                     }
                 }
             }
         };
 
-        Ok(P256SigningKey { key: ec_key })
+        Ok(P256SigningKey {
+            key: ec_key,
+            nonce_mode: NonceMode::default(),
+        })
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
@@ -81,24 +130,57 @@ impl P256SigningKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR);
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
             }
         };
-        Ok(P256SigningKey { key: ec_key })
+        Ok(P256SigningKey {
+            key: ec_key,
+            nonce_mode: NonceMode::default(),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.to_bytes().to_vec()
+    }
+
+    pub fn verifying_key(&self) -> P256VerifyingKey {
+        P256VerifyingKey {
+            key: VerifyingKey::from(&self.key),
+        }
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.verifying_key().fingerprint()
+    }
+}
+
+impl fmt::Debug for P256SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(f, "P256SigningKey", "P-256", self.fingerprint())
     }
 }
 
+#[derive(Clone, PartialEq, Eq)]
 pub struct P256VerifyingKey {
     key: VerifyingKey,
 }
 
 impl VerifyFromKey for P256VerifyingKey {
-    fn verify(&self, content: String, signature: String, _alg: Algorithm) -> Result<bool, Error> {
+    fn verifying_algorithms(&self) -> Option<Vec<Algorithm>> {
+        Some(vec![Algorithm::ES256])
+    }
+
+    fn verify_bytes(
+        &self,
+        content: &[u8],
+        signature: &str,
+        _alg: Algorithm,
+    ) -> Result<bool, Error> {
         let decoded_sig = match base64_url::decode(signature.as_bytes()) {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::DECODING_ERROR);
+                return Err(Error::DECODING_ERROR.with_source(error));
             }
         };
 
@@ -106,12 +188,11 @@ impl VerifyFromKey for P256VerifyingKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::SIGNATURE_IDENTIFICATION_FAILED);
+                return Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_source(error));
             }
         };
 
-        let verify_result: Result<(), p256::ecdsa::Error> =
-            self.key.verify(content.as_bytes(), &sig);
+        let verify_result: Result<(), p256::ecdsa::Error> = self.key.verify(content, &sig);
         if verify_result.is_ok() {
             return Ok(true);
         } else {
@@ -127,13 +208,32 @@ impl VerifyFromKey for P256VerifyingKey {
 }
 
 impl P256VerifyingKey {
+    pub fn from_x509_pem(cert_str: &str) -> Result<Self, Error> {
+        Self::from_x509_der(&x509::certificate_pem_to_der(cert_str)?)
+    }
+
+    pub fn from_x509_der(cert: &[u8]) -> Result<Self, Error> {
+        let public_key: elliptic_curve::PublicKey<NistP256> =
+            match elliptic_curve::PublicKey::from_public_key_der(&x509::subject_public_key_info(
+                cert,
+            )?) {
+                Ok(val) => val,
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
+                }
+            };
+
+        Self::from_bytes(&public_key.to_sec1_bytes())
+    }
+
     pub fn from_pem(key_str: &str) -> Result<Self, Error> {
         let key_scalar: elliptic_curve::PublicKey<NistP256> =
             match elliptic_curve::PublicKey::from_public_key_pem(key_str) {
                 Ok(val) => val,
                 Err(error) => {
                     log::error(error.to_string().as_str());
-                    return Err(Error::EC_PEM_ERROR);
+                    return Err(Error::EC_PEM_ERROR.with_source(error));
                 }
             };
 
@@ -141,7 +241,7 @@ impl P256VerifyingKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR);
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
             }
         };
         Ok(P256VerifyingKey { key: ec_key })
@@ -152,18 +252,40 @@ impl P256VerifyingKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR);
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
             }
         };
 
         Ok(P256VerifyingKey { key: ec_key })
     }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.to_encoded_point(false).as_bytes().to_vec()
+    }
+
+    pub fn to_sec1_bytes(&self, compressed: bool) -> Vec<u8> {
+        self.key.to_encoded_point(compressed).as_bytes().to_vec()
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        ec_jwk("P-256", self.to_bytes()).thumbprint()
+    }
+}
+
+impl fmt::Debug for P256VerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(f, "P256VerifyingKey", "P-256", self.fingerprint())
+    }
 }
 
-pub fn ec_256_sign(message: String, key: impl SignFromKey) -> Result<String, Error> {
-    key.sign(message, Algorithm::ES256)
+pub fn ec_256_sign(message: impl AsRef<[u8]>, key: impl SignFromKey) -> Result<String, Error> {
+    key.sign_bytes(message.as_ref(), Algorithm::ES256)
 }
 
-pub fn ec_256_verify(message: String, sig: String, key: impl VerifyFromKey) -> Result<bool, Error> {
-    key.verify(message, sig, Algorithm::ES256)
+pub fn ec_256_verify(
+    message: impl AsRef<[u8]>,
+    sig: impl AsRef<str>,
+    key: impl VerifyFromKey,
+) -> Result<bool, Error> {
+    key.verify_bytes(message.as_ref(), sig.as_ref(), Algorithm::ES256)
 }
@@ -1,30 +1,80 @@
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
 use crate::{
     algorithms::Algorithm,
-    crypto::{SignFromKey, VerifyFromKey},
+    crypto::{
+        ecdsa::{ec_jwk, NonceMode},
+        fmt_key, x509, SignFromKey, VerifyFromKey,
+    },
     errors::Error,
     log,
 };
 use elliptic_curve::pkcs8::DecodePublicKey;
 use k256::{
-    ecdsa::{signature::Signer, signature::Verifier, Signature, SigningKey, VerifyingKey},
+    ecdsa::{
+        signature::RandomizedSigner, signature::Signer, signature::Verifier, RecoveryId, Signature,
+        SigningKey, VerifyingKey,
+    },
     Secp256k1,
 };
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
 
 pub struct P256kSigningKey {
     key: SigningKey,
+    nonce_mode: NonceMode,
 }
 
 impl SignFromKey for P256kSigningKey {
-    fn sign(&self, content: String, _alg: Algorithm) -> Result<String, Error> {
-        let sig_result: Result<Signature, k256::ecdsa::Error> =
-            self.key.try_sign(content.as_bytes());
+    fn sign_bytes(&self, content: &[u8], alg: Algorithm) -> Result<String, Error> {
+        match alg {
+            Algorithm::ES256KR => Ok(base64_url::encode(&self.sign_recoverable(content)?)),
+            _ => self.sign_bytes_with_nonce_mode(content, self.nonce_mode),
+        }
+    }
+}
+
+impl P256kSigningKey {
+    pub fn with_nonce_mode(mut self, nonce_mode: NonceMode) -> Self {
+        self.nonce_mode = nonce_mode;
+        self
+    }
+
+    pub fn nonce_mode(&self) -> NonceMode {
+        self.nonce_mode
+    }
+
+    pub fn sign_bytes_with_nonce_mode(
+        &self,
+        content: &[u8],
+        nonce_mode: NonceMode,
+    ) -> Result<String, Error> {
+        if nonce_mode == NonceMode::Hedged {
+            return self.sign_bytes_with_rng(content, &mut OsRng);
+        }
+
+        let sig_result: Result<Signature, k256::ecdsa::Error> = self.key.try_sign(content);
         let signature = match sig_result {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::SIGNING_FAILED);
+                return Err(Error::SIGNING_FAILED.with_source(error));
+            }
+        };
+
+        Ok(base64_url::encode(signature.to_bytes().as_slice()))
+    }
+
+    pub fn sign_bytes_with_rng(
+        &self,
+        content: &[u8],
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<String, Error> {
+        let signature: Signature = match self.key.try_sign_with_rng(rng, content) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::SIGNING_FAILED.with_source(error));
             }
         };
 
@@ -41,7 +91,7 @@ impl P256kSigningKey {
                         Ok(val) => val,
                         Err(error) => {
                             log::error(error.to_string().as_str());
-                            return Err(Error::EC_PEM_ERROR);
+                            return Err(Error::EC_PEM_ERROR.with_source(error));
                         }
                     };
 
@@ -49,7 +99,7 @@ impl P256kSigningKey {
                     Ok(val) => val,
                     Err(error) => {
                         log::error(error.to_string().as_str());
-                        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+                        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
                     }
                 }
             }
@@ -59,7 +109,7 @@ impl P256kSigningKey {
                         Ok(val) => val,
                         Err(error) => {
                             log::error(error.to_string().as_str());
-                            return Err(Error::EC_PEM_ERROR);
+                            return Err(Error::EC_PEM_ERROR.with_source(error));
                         }
                     };
 
@@ -67,13 +117,16 @@ impl P256kSigningKey {
                     Ok(val) => val,
                     Err(error) => {
                         log::error(error.to_string().as_str());
-                        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+                        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
                     }
                 }
             }
         };
 
-        Ok(P256kSigningKey { key: ec_key })
+        Ok(P256kSigningKey {
+            key: ec_key,
+            nonce_mode: NonceMode::default(),
+        })
     }
 
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
@@ -81,24 +134,75 @@ impl P256kSigningKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR);
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
             }
         };
-        Ok(P256kSigningKey { key: ec_key })
+        Ok(P256kSigningKey {
+            key: ec_key,
+            nonce_mode: NonceMode::default(),
+        })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.to_bytes().to_vec()
+    }
+
+    pub fn verifying_key(&self) -> P256kVerifyingKey {
+        P256kVerifyingKey {
+            key: VerifyingKey::from(&self.key),
+        }
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.verifying_key().fingerprint()
+    }
+
+    pub fn sign_prehash_recoverable(&self, prehash: &[u8]) -> Result<[u8; 65], Error> {
+        let (signature, recovery_id) = match self.key.sign_prehash_recoverable(prehash) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::SIGNING_FAILED.with_source(error));
+            }
+        };
+
+        let mut bytes = [0u8; 65];
+        bytes[..64].copy_from_slice(signature.to_bytes().as_slice());
+        bytes[64] = recovery_id.to_byte();
+        Ok(bytes)
+    }
+
+    pub fn sign_recoverable(&self, content: &[u8]) -> Result<[u8; 65], Error> {
+        self.sign_prehash_recoverable(&Sha256::digest(content))
+    }
+}
+
+impl fmt::Debug for P256kSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(f, "P256kSigningKey", "secp256k1", self.fingerprint())
     }
 }
 
+#[derive(Clone, PartialEq, Eq)]
 pub struct P256kVerifyingKey {
     key: VerifyingKey,
 }
 
 impl VerifyFromKey for P256kVerifyingKey {
-    fn verify(&self, content: String, signature: String, _alg: Algorithm) -> Result<bool, Error> {
+    fn verifying_algorithms(&self) -> Option<Vec<Algorithm>> {
+        Some(vec![Algorithm::ES256K, Algorithm::ES256KR])
+    }
+
+    fn verify_bytes(&self, content: &[u8], signature: &str, alg: Algorithm) -> Result<bool, Error> {
+        if alg == Algorithm::ES256KR {
+            return Ok(&Self::recover(content, signature)? == self);
+        }
+
         let decoded_sig = match base64_url::decode(signature.as_bytes()) {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::DECODING_ERROR);
+                return Err(Error::DECODING_ERROR.with_source(error));
             }
         };
 
@@ -106,12 +210,11 @@ impl VerifyFromKey for P256kVerifyingKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::SIGNATURE_IDENTIFICATION_FAILED);
+                return Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_source(error));
             }
         };
 
-        let verify_result: Result<(), k256::ecdsa::Error> =
-            self.key.verify(content.as_bytes(), &sig);
+        let verify_result: Result<(), k256::ecdsa::Error> = self.key.verify(content, &sig);
         if verify_result.is_ok() {
             return Ok(true);
         } else {
@@ -127,20 +230,39 @@ impl VerifyFromKey for P256kVerifyingKey {
 }
 
 impl P256kVerifyingKey {
+    pub fn from_x509_pem(cert_str: &str) -> Result<Self, Error> {
+        Self::from_x509_der(&x509::certificate_pem_to_der(cert_str)?)
+    }
+
+    pub fn from_x509_der(cert: &[u8]) -> Result<Self, Error> {
+        let public_key: elliptic_curve::PublicKey<Secp256k1> =
+            match elliptic_curve::PublicKey::from_public_key_der(&x509::subject_public_key_info(
+                cert,
+            )?) {
+                Ok(val) => val,
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
+                }
+            };
+
+        Self::from_bytes(&public_key.to_sec1_bytes())
+    }
+
     pub fn from_pem(key_str: &str) -> Result<Self, Error> {
         let key_scalar: elliptic_curve::PublicKey<Secp256k1> =
             match elliptic_curve::PublicKey::from_public_key_pem(key_str) {
                 Ok(val) => val,
                 Err(error) => {
                     log::error(error.to_string().as_str());
-                    return Err(Error::EC_PEM_ERROR);
+                    return Err(Error::EC_PEM_ERROR.with_source(error));
                 }
             };
         let ec_key = match VerifyingKey::from_sec1_bytes(&key_scalar.to_sec1_bytes()) {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR);
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
             }
         };
 
@@ -152,22 +274,95 @@ impl P256kVerifyingKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR);
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
             }
         };
 
         Ok(P256kVerifyingKey { key: ec_key })
     }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.to_encoded_point(false).as_bytes().to_vec()
+    }
+
+    pub fn to_sec1_bytes(&self, compressed: bool) -> Vec<u8> {
+        self.key.to_encoded_point(compressed).as_bytes().to_vec()
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        ec_jwk("secp256k1", self.to_bytes()).thumbprint()
+    }
+
+    pub fn recover(content: &[u8], signature: &str) -> Result<Self, Error> {
+        let decoded = match base64_url::decode(signature.as_bytes()) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::DECODING_ERROR.with_source(error));
+            }
+        };
+
+        Self::recover_from_prehash(&Sha256::digest(content), &decoded)
+    }
+
+    pub fn recover_from_prehash(prehash: &[u8], signature: &[u8]) -> Result<Self, Error> {
+        if signature.len() != 65 {
+            return Err(Error::SIGNATURE_IDENTIFICATION_FAILED);
+        }
+
+        let sig = match Signature::from_slice(&signature[..64]) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_source(error));
+            }
+        };
+
+        let recovery_id = match signature[64] {
+            27 | 28 => RecoveryId::from_byte(signature[64] - 27),
+            val => RecoveryId::from_byte(val),
+        };
+        let recovery_id = match recovery_id {
+            Some(val) => val,
+            None => return Err(Error::SIGNATURE_IDENTIFICATION_FAILED),
+        };
+
+        match VerifyingKey::recover_from_prehash(prehash, &sig, recovery_id) {
+            Ok(val) => Ok(P256kVerifyingKey { key: val }),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_source(error))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for P256kVerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(f, "P256kVerifyingKey", "secp256k1", self.fingerprint())
+    }
 }
 
-pub fn ec_256k_sign(message: String, key: impl SignFromKey) -> Result<String, Error> {
-    key.sign(message, Algorithm::ES256K)
+pub fn ec_256k_sign(message: impl AsRef<[u8]>, key: impl SignFromKey) -> Result<String, Error> {
+    key.sign_bytes(message.as_ref(), Algorithm::ES256K)
 }
 
 pub fn ec_256k_verify(
-    message: String,
-    sig: String,
+    message: impl AsRef<[u8]>,
+    sig: impl AsRef<str>,
+    key: impl VerifyFromKey,
+) -> Result<bool, Error> {
+    key.verify_bytes(message.as_ref(), sig.as_ref(), Algorithm::ES256K)
+}
+
+pub fn ec_256kr_sign(message: impl AsRef<[u8]>, key: impl SignFromKey) -> Result<String, Error> {
+    key.sign_bytes(message.as_ref(), Algorithm::ES256KR)
+}
+
+pub fn ec_256kr_verify(
+    message: impl AsRef<[u8]>,
+    sig: impl AsRef<str>,
     key: impl VerifyFromKey,
 ) -> Result<bool, Error> {
-    key.verify(message, sig, Algorithm::ES256K)
+    key.verify_bytes(message.as_ref(), sig.as_ref(), Algorithm::ES256KR)
 }
@@ -1,8 +1,8 @@
-use std::str::FromStr;
+use std::{fmt, str::FromStr};
 
 use crate::{
     algorithms::Algorithm,
-    crypto::{SignFromKey, VerifyFromKey},
+    crypto::{ecdsa::ec_jwk, fmt_key, x509, SignFromKey, VerifyFromKey},
     errors::Error,
     log,
 };
@@ -20,14 +20,13 @@ pub struct P512SigningKey {
 }
 
 impl SignFromKey for P512SigningKey {
-    fn sign(&self, content: String, _alg: Algorithm) -> Result<String, Error> {
-        let sig_result: Result<Signature, p521::ecdsa::Error> =
-            self.key.try_sign(content.as_bytes());
+    fn sign_bytes(&self, content: &[u8], _alg: Algorithm) -> Result<String, Error> {
+        let sig_result: Result<Signature, p521::ecdsa::Error> = self.key.try_sign(content);
         let signature = match sig_result {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::SIGNING_FAILED);
+                return Err(Error::SIGNING_FAILED.with_source(error));
             }
         };
 
@@ -44,7 +43,7 @@ impl P512SigningKey {
                         Ok(val) => val,
                         Err(error) => {
                             log::error(error.to_string().as_str());
-                            return Err(Error::EC_PEM_ERROR);
+                            return Err(Error::EC_PEM_ERROR.with_source(error));
                         }
                     };
 
@@ -52,7 +51,7 @@ impl P512SigningKey {
                     Ok(val) => val,
                     Err(error) => {
                         log::error(error.to_string().as_str());
-                        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+                        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
                     }
                 }
             }
@@ -62,7 +61,7 @@ impl P512SigningKey {
                         Ok(val) => val,
                         Err(error) => {
                             log::error(error.to_string().as_str());
-                            return Err(Error::EC_PEM_ERROR);
+                            return Err(Error::EC_PEM_ERROR.with_source(error));
                         }
                     };
 
@@ -70,7 +69,7 @@ impl P512SigningKey {
                     Ok(val) => val,
                     Err(error) => {
                         log::error(error.to_string().as_str());
-                        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+                        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
                     }
                 }
             }
@@ -84,24 +83,62 @@ impl P512SigningKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR);
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
             }
         };
         Ok(P512SigningKey { key: ec_key })
     }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.to_bytes().to_vec()
+    }
+
+    pub fn verifying_key(&self) -> P512VerifyingKey {
+        P512VerifyingKey {
+            key: VerifyingKey::from(&self.key),
+        }
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.verifying_key().fingerprint()
+    }
+}
+
+impl fmt::Debug for P512SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(f, "P512SigningKey", "P-521", self.fingerprint())
+    }
 }
 
+#[derive(Clone)]
 pub struct P512VerifyingKey {
     key: VerifyingKey,
 }
 
+impl PartialEq for P512VerifyingKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_bytes() == other.to_bytes()
+    }
+}
+
+impl Eq for P512VerifyingKey {}
+
 impl VerifyFromKey for P512VerifyingKey {
-    fn verify(&self, content: String, signature: String, _alg: Algorithm) -> Result<bool, Error> {
+    fn verifying_algorithms(&self) -> Option<Vec<Algorithm>> {
+        Some(vec![Algorithm::ES512])
+    }
+
+    fn verify_bytes(
+        &self,
+        content: &[u8],
+        signature: &str,
+        _alg: Algorithm,
+    ) -> Result<bool, Error> {
         let decoded_sig = match base64_url::decode(signature.as_bytes()) {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::DECODING_ERROR);
+                return Err(Error::DECODING_ERROR.with_source(error));
             }
         };
 
@@ -109,12 +146,11 @@ impl VerifyFromKey for P512VerifyingKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::SIGNATURE_IDENTIFICATION_FAILED);
+                return Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_source(error));
             }
         };
 
-        let verify_result: Result<(), p521::ecdsa::Error> =
-            self.key.verify(content.as_bytes(), &sig);
+        let verify_result: Result<(), p521::ecdsa::Error> = self.key.verify(content, &sig);
         if verify_result.is_ok() {
             return Ok(true);
         } else {
@@ -130,20 +166,39 @@ impl VerifyFromKey for P512VerifyingKey {
 }
 
 impl P512VerifyingKey {
+    pub fn from_x509_pem(cert_str: &str) -> Result<Self, Error> {
+        Self::from_x509_der(&x509::certificate_pem_to_der(cert_str)?)
+    }
+
+    pub fn from_x509_der(cert: &[u8]) -> Result<Self, Error> {
+        let public_key: elliptic_curve::PublicKey<NistP521> =
+            match elliptic_curve::PublicKey::from_public_key_der(&x509::subject_public_key_info(
+                cert,
+            )?) {
+                Ok(val) => val,
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
+                }
+            };
+
+        Self::from_bytes(&public_key.to_sec1_bytes())
+    }
+
     pub fn from_pem(key_str: &str) -> Result<Self, Error> {
         let key_scalar: elliptic_curve::PublicKey<NistP521> =
             match elliptic_curve::PublicKey::from_public_key_pem(key_str) {
                 Ok(val) => val,
                 Err(error) => {
                     log::error(error.to_string().as_str());
-                    return Err(Error::EC_PEM_ERROR);
+                    return Err(Error::EC_PEM_ERROR.with_source(error));
                 }
             };
         let ec_key = match VerifyingKey::from_sec1_bytes(&key_scalar.to_sec1_bytes()) {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR);
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
             }
         };
 
@@ -155,18 +210,40 @@ impl P512VerifyingKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR);
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
             }
         };
 
         Ok(P512VerifyingKey { key: ec_key })
     }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.to_encoded_point(false).as_bytes().to_vec()
+    }
+
+    pub fn to_sec1_bytes(&self, compressed: bool) -> Vec<u8> {
+        self.key.to_encoded_point(compressed).as_bytes().to_vec()
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        ec_jwk("P-521", self.to_bytes()).thumbprint()
+    }
+}
+
+impl fmt::Debug for P512VerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(f, "P512VerifyingKey", "P-521", self.fingerprint())
+    }
 }
 
-pub fn ec_512_sign(message: String, key: impl SignFromKey) -> Result<String, Error> {
-    key.sign(message, Algorithm::ES512)
+pub fn ec_512_sign(message: impl AsRef<[u8]>, key: impl SignFromKey) -> Result<String, Error> {
+    key.sign_bytes(message.as_ref(), Algorithm::ES512)
 }
 
-pub fn ec_512_verify(message: String, sig: String, key: impl VerifyFromKey) -> Result<bool, Error> {
-    key.verify(message, sig, Algorithm::ES512)
+pub fn ec_512_verify(
+    message: impl AsRef<[u8]>,
+    sig: impl AsRef<str>,
+    key: impl VerifyFromKey,
+) -> Result<bool, Error> {
+    key.verify_bytes(message.as_ref(), sig.as_ref(), Algorithm::ES512)
 }
@@ -2,41 +2,157 @@ use crate::{
     algorithms::Algorithm,
     crypto::{SignFromKey, VerifyFromKey},
     errors::Error,
+    jwk::Jwk,
+    log,
 };
 
-use self::{
-    _256k::{ec_256k_sign, ec_256k_verify},
-    _256::{ec_256_sign, ec_256_verify},
-    _384::{ec_384_sign, ec_384_verify},
-    _512::{ec_512_sign, ec_512_verify},
-};
+#[cfg(feature = "es256k")]
+use self::_256k::{ec_256k_sign, ec_256k_verify, ec_256kr_sign, ec_256kr_verify};
+use self::_256::{ec_256_sign, ec_256_verify};
+#[cfg(feature = "es384")]
+use self::_384::{ec_384_sign, ec_384_verify};
+#[cfg(feature = "es512")]
+use self::_512::{ec_512_sign, ec_512_verify};
 
 pub mod _256;
+#[cfg(feature = "es256k")]
 pub mod _256k;
+#[cfg(feature = "es384")]
 pub mod _384;
+#[cfg(feature = "es512")]
 pub mod _512;
 
-pub fn sign_ec(message: String, key: impl SignFromKey, alg: Algorithm) -> Result<String, Error> {
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NonceMode {
+    #[default]
+    Deterministic,
+    Hedged,
+}
+
+pub(crate) fn ec_jwk(crv: &str, bytes: Vec<u8>) -> Jwk {
+    let (x, y) = bytes[1..].split_at((bytes.len() - 1) / 2);
+    Jwk {
+        kty: String::from("EC"),
+        crv: Some(String::from(crv)),
+        x: Some(base64_url::encode(x)),
+        y: Some(base64_url::encode(y)),
+        ..Default::default()
+    }
+}
+
+pub fn sign_ec(
+    message: impl AsRef<[u8]>,
+    key: impl SignFromKey,
+    alg: Algorithm,
+) -> Result<String, Error> {
     match alg {
         Algorithm::ES256 => ec_256_sign(message, key),
+        #[cfg(feature = "es384")]
         Algorithm::ES384 => ec_384_sign(message, key),
+        #[cfg(feature = "es512")]
         Algorithm::ES512 => ec_512_sign(message, key),
+        #[cfg(feature = "es256k")]
         Algorithm::ES256K => ec_256k_sign(message, key),
-        _ => return Err(Error::UNKNOWN_ALGORITHM),
+        #[cfg(feature = "es256k")]
+        Algorithm::ES256KR => ec_256kr_sign(message, key),
+        _ => return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
     }
 }
 
 pub fn verify_ec(
-    message: String,
-    signature: String,
+    message: impl AsRef<[u8]>,
+    signature: impl AsRef<str>,
     key: impl VerifyFromKey,
     alg: Algorithm,
 ) -> Result<bool, Error> {
     match alg {
         Algorithm::ES256 => ec_256_verify(message, signature, key),
+        #[cfg(feature = "es384")]
         Algorithm::ES384 => ec_384_verify(message, signature, key),
+        #[cfg(feature = "es512")]
         Algorithm::ES512 => ec_512_verify(message, signature, key),
+        #[cfg(feature = "es256k")]
         Algorithm::ES256K => ec_256k_verify(message, signature, key),
-        _ => return Err(Error::UNKNOWN_ALGORITHM),
+        #[cfg(feature = "es256k")]
+        Algorithm::ES256KR => ec_256kr_verify(message, signature, key),
+        _ => return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
+    }
+}
+
+pub fn p1363_length(alg: Algorithm) -> Option<usize> {
+    match alg {
+        Algorithm::ES256 | Algorithm::ES256K => Some(64),
+        Algorithm::ES384 => Some(96),
+        Algorithm::ES512 => Some(132),
+        _ => None,
+    }
+}
+
+pub fn der_to_p1363(der: &[u8], alg: Algorithm) -> Result<Vec<u8>, Error> {
+    let signature = match alg {
+        Algorithm::ES256 => p256::ecdsa::Signature::from_der(der).map(|val| val.to_vec()),
+        #[cfg(feature = "es384")]
+        Algorithm::ES384 => p384::ecdsa::Signature::from_der(der).map(|val| val.to_vec()),
+        #[cfg(feature = "es512")]
+        Algorithm::ES512 => p521::ecdsa::Signature::from_der(der).map(|val| val.to_vec()),
+        #[cfg(feature = "es256k")]
+        Algorithm::ES256K => k256::ecdsa::Signature::from_der(der)
+            .map(|val| val.normalize_s().unwrap_or(val).to_vec()),
+        _ => return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
+    };
+
+    match signature {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_source(error))
+        }
     }
 }
+
+pub fn p1363_to_der(signature: &[u8], alg: Algorithm) -> Result<Vec<u8>, Error> {
+    match p1363_length(alg) {
+        Some(val) if val == signature.len() => {}
+        Some(_) => return Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_algorithm(alg)),
+        None => return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
+    }
+
+    let der = match alg {
+        Algorithm::ES256 => p256::ecdsa::Signature::from_slice(signature)
+            .map(|val| val.to_der().as_bytes().to_vec()),
+        #[cfg(feature = "es384")]
+        Algorithm::ES384 => p384::ecdsa::Signature::from_slice(signature)
+            .map(|val| val.to_der().as_bytes().to_vec()),
+        #[cfg(feature = "es512")]
+        Algorithm::ES512 => p521::ecdsa::Signature::from_slice(signature)
+            .map(|val| val.to_der().as_bytes().to_vec()),
+        #[cfg(feature = "es256k")]
+        Algorithm::ES256K => k256::ecdsa::Signature::from_slice(signature)
+            .map(|val| val.to_der().as_bytes().to_vec()),
+        _ => return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
+    };
+
+    match der {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_source(error))
+        }
+    }
+}
+
+pub fn signature_from_der(der: &[u8], alg: Algorithm) -> Result<String, Error> {
+    Ok(base64_url::encode(&der_to_p1363(der, alg)?))
+}
+
+pub fn signature_to_der(signature: &str, alg: Algorithm) -> Result<Vec<u8>, Error> {
+    let bytes = match base64_url::decode(signature) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::DECODING_ERROR.with_source(error));
+        }
+    };
+
+    p1363_to_der(&bytes, alg)
+}
@@ -0,0 +1,223 @@
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+use zeroize::Zeroizing;
+
+use crate::{errors::Error, log, metrics::Operation, trace};
+
+use super::{
+    any::{AnySigningKey, AnyVerifyingKey},
+    kdf::{hkdf, HashFunction},
+};
+
+pub const ECIES_INFO: &[u8] = b"did-crypto ECIES AES-256-GCM";
+pub const ECIES_EPHEMERAL_KEY_LENGTH: usize = 33;
+pub const ECIES_NONCE_LENGTH: usize = 12;
+pub const ECIES_TAG_LENGTH: usize = 16;
+
+struct Agreement {
+    ephemeral_key: Vec<u8>,
+    recipient_key: Vec<u8>,
+    shared_secret: Zeroizing<Vec<u8>>,
+}
+
+fn public_key_error(error: impl std::error::Error + Send + Sync + 'static) -> Error {
+    log::error(error.to_string().as_str());
+    Error::ECIES_MALFORMED.with_source(error)
+}
+
+fn secret_key_error(error: impl std::error::Error + Send + Sync + 'static) -> Error {
+    log::error(error.to_string().as_str());
+    Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error)
+}
+
+fn sender_agreement(
+    recipient: &AnyVerifyingKey,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<Agreement, Error> {
+    match recipient {
+        AnyVerifyingKey::P256(key) => {
+            let recipient_key = key.to_sec1_bytes(true);
+            let public_key =
+                p256::PublicKey::from_sec1_bytes(&recipient_key).map_err(public_key_error)?;
+            let ephemeral = p256::ecdh::EphemeralSecret::random(rng);
+            Ok(Agreement {
+                ephemeral_key: p256::EncodedPoint::from(ephemeral.public_key())
+                    .compress()
+                    .as_bytes()
+                    .to_vec(),
+                recipient_key,
+                shared_secret: Zeroizing::new(
+                    ephemeral
+                        .diffie_hellman(&public_key)
+                        .raw_secret_bytes()
+                        .to_vec(),
+                ),
+            })
+        }
+        #[cfg(feature = "es256k")]
+        AnyVerifyingKey::P256k(key) => {
+            let recipient_key = key.to_sec1_bytes(true);
+            let public_key =
+                k256::PublicKey::from_sec1_bytes(&recipient_key).map_err(public_key_error)?;
+            let ephemeral = k256::ecdh::EphemeralSecret::random(rng);
+            Ok(Agreement {
+                ephemeral_key: k256::EncodedPoint::from(ephemeral.public_key())
+                    .compress()
+                    .as_bytes()
+                    .to_vec(),
+                recipient_key,
+                shared_secret: Zeroizing::new(
+                    ephemeral
+                        .diffie_hellman(&public_key)
+                        .raw_secret_bytes()
+                        .to_vec(),
+                ),
+            })
+        }
+        _ => Err(Error::ECIES_UNSUPPORTED_KEY.with_algorithm(recipient.algorithm())),
+    }
+}
+
+fn recipient_agreement(key: &AnySigningKey, ephemeral_key: &[u8]) -> Result<Agreement, Error> {
+    match key {
+        AnySigningKey::P256(key) => {
+            let secret_key = p256::SecretKey::from_slice(&Zeroizing::new(key.to_bytes()))
+                .map_err(secret_key_error)?;
+            let public_key =
+                p256::PublicKey::from_sec1_bytes(ephemeral_key).map_err(public_key_error)?;
+            Ok(Agreement {
+                ephemeral_key: ephemeral_key.to_vec(),
+                recipient_key: key.verifying_key().to_sec1_bytes(true),
+                shared_secret: Zeroizing::new(
+                    p256::ecdh::diffie_hellman(
+                        secret_key.to_nonzero_scalar(),
+                        public_key.as_affine(),
+                    )
+                    .raw_secret_bytes()
+                    .to_vec(),
+                ),
+            })
+        }
+        #[cfg(feature = "es256k")]
+        AnySigningKey::P256k(key) => {
+            let secret_key = k256::SecretKey::from_slice(&Zeroizing::new(key.to_bytes()))
+                .map_err(secret_key_error)?;
+            let public_key =
+                k256::PublicKey::from_sec1_bytes(ephemeral_key).map_err(public_key_error)?;
+            Ok(Agreement {
+                ephemeral_key: ephemeral_key.to_vec(),
+                recipient_key: key.verifying_key().to_sec1_bytes(true),
+                shared_secret: Zeroizing::new(
+                    k256::ecdh::diffie_hellman(
+                        secret_key.to_nonzero_scalar(),
+                        public_key.as_affine(),
+                    )
+                    .raw_secret_bytes()
+                    .to_vec(),
+                ),
+            })
+        }
+        _ => Err(Error::ECIES_UNSUPPORTED_KEY.with_algorithm(key.algorithm())),
+    }
+}
+
+impl Agreement {
+    fn cipher(&self) -> Result<Aes256Gcm, Error> {
+        let mut salt = self.ephemeral_key.clone();
+        salt.extend_from_slice(&self.recipient_key);
+        let content_key = Zeroizing::new(hkdf(
+            HashFunction::Sha256,
+            Some(&salt),
+            &self.shared_secret,
+            ECIES_INFO,
+            32,
+        )?);
+
+        match Aes256Gcm::new_from_slice(&content_key) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::KEY_AGREEMENT_FAILED.with_source(error))
+            }
+        }
+    }
+}
+
+pub fn encrypt(
+    recipient: &AnyVerifyingKey,
+    plaintext: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, Error> {
+    encrypt_with_rng(recipient, plaintext, aad, &mut OsRng)
+}
+
+pub fn encrypt_with_rng(
+    recipient: &AnyVerifyingKey,
+    plaintext: &[u8],
+    aad: &[u8],
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<Vec<u8>, Error> {
+    trace::traced(
+        Operation::Encrypt,
+        Some(recipient.algorithm().to_str()),
+        || recipient.fingerprint().ok(),
+        || {
+            let agreement = sender_agreement(recipient, rng)?;
+            let mut nonce = [0u8; ECIES_NONCE_LENGTH];
+            rng.fill_bytes(&mut nonce);
+
+            let ciphertext = match agreement.cipher()?.encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: plaintext,
+                    aad,
+                },
+            ) {
+                Ok(val) => val,
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::ECIES_ENCRYPTION_FAILED.with_source(error.to_string()));
+                }
+            };
+
+            let mut message = agreement.ephemeral_key;
+            message.extend_from_slice(&nonce);
+            message.extend_from_slice(&ciphertext);
+            Ok(message)
+        },
+    )
+}
+
+pub fn decrypt(key: &AnySigningKey, message: &[u8], aad: &[u8]) -> Result<Vec<u8>, Error> {
+    trace::traced(
+        Operation::Decrypt,
+        Some(key.algorithm().to_str()),
+        || key.verifying_key().fingerprint().ok(),
+        || {
+            if message.len() < ECIES_EPHEMERAL_KEY_LENGTH + ECIES_NONCE_LENGTH + ECIES_TAG_LENGTH {
+                return Err(Error::ECIES_MALFORMED);
+            }
+
+            let (ephemeral_key, rest) = message.split_at(ECIES_EPHEMERAL_KEY_LENGTH);
+            let (nonce, ciphertext) = rest.split_at(ECIES_NONCE_LENGTH);
+            let agreement = recipient_agreement(key, ephemeral_key)?;
+
+            match agreement.cipher()?.decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad,
+                },
+            ) {
+                Ok(val) => Ok(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    Err(Error::ECIES_DECRYPTION_FAILED.with_source(error.to_string()))
+                }
+            }
+        },
+    )
+}
@@ -1,27 +1,73 @@
+use std::fmt;
+
+use curve25519_dalek::{
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar,
+    traits::IsIdentity,
+};
 use ed25519_dalek::{
     pkcs8::DecodePrivateKey, pkcs8::DecodePublicKey, Signer, SigningKey, Verifier,
 };
 use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest, Sha512};
+use zeroize::Zeroizing;
 
 use crate::algorithms::Algorithm;
 use crate::errors::Error;
+use crate::jwk::Jwk;
 use crate::log;
 
-use super::{SignFromKey, VerifyFromKey};
+use super::{
+    fmt_key,
+    x25519::{X25519PublicKey, X25519SecretKey},
+    x509, SignFromKey, VerifyFromKey,
+};
+
+pub const MAX_CONTEXT_LENGTH: usize = 255;
+const DOM2_PREFIX: &[u8] = b"SigEd25519 no Ed25519 collisions";
+const PURE_FLAG: u8 = 0;
+const PREHASH_FLAG: u8 = 1;
+
+fn dom2(flag: u8, context: &[u8]) -> Result<Vec<u8>, Error> {
+    if context.len() > MAX_CONTEXT_LENGTH {
+        return Err(Error::SIGNATURE_CONTEXT_INVALID);
+    }
+
+    let mut dom = DOM2_PREFIX.to_vec();
+    dom.push(flag);
+    dom.push(context.len() as u8);
+    dom.extend_from_slice(context);
+    Ok(dom)
+}
+
+fn hash_to_scalar(parts: &[&[u8]]) -> Scalar {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    Scalar::from_bytes_mod_order_wide(&hasher.finalize().into())
+}
+
+fn decode_point(bytes: &[u8]) -> Option<EdwardsPoint> {
+    match CompressedEdwardsY::from_slice(bytes).ok()?.decompress() {
+        Some(val) if !val.is_small_order() => Some(val),
+        _ => None,
+    }
+}
 
 pub struct EDDSASigningKey {
     key: SigningKey,
 }
 
 impl SignFromKey for EDDSASigningKey {
-    fn sign(&self, content: String, _alg: Algorithm) -> Result<String, Error> {
+    fn sign_bytes(&self, content: &[u8], _alg: Algorithm) -> Result<String, Error> {
         let sig_result: Result<Signature, ed25519_dalek::ed25519::Error> =
-            self.key.try_sign(content.as_bytes());
+            self.key.try_sign(content);
         let signature = match sig_result {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::SIGNING_FAILED);
+                return Err(Error::SIGNING_FAILED.with_source(error));
             }
         };
 
@@ -35,7 +81,7 @@ impl EDDSASigningKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+                return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
             }
         };
 
@@ -47,24 +93,107 @@ impl EDDSASigningKey {
             return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
         }
 
-        let ec_bytes: [u8; 32] = [0; 32];
-        bytes.copy_from_slice(&ec_bytes);
+        let mut ec_bytes: [u8; 32] = [0; 32];
+        ec_bytes.copy_from_slice(bytes);
         let ec_key = SigningKey::from_bytes(&ec_bytes);
         Ok(EDDSASigningKey { key: ec_key })
     }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.to_bytes().to_vec()
+    }
+
+    pub fn verifying_key(&self) -> EDDSAVerifyingKey {
+        EDDSAVerifyingKey {
+            key: self.key.verifying_key(),
+        }
+    }
+
+    pub fn to_x25519(&self) -> Result<X25519SecretKey, Error> {
+        X25519SecretKey::from_bytes(Zeroizing::new(self.key.to_scalar_bytes()).as_slice())
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.verifying_key().fingerprint()
+    }
+
+    pub fn sign_with_context(&self, content: &[u8], context: &[u8]) -> Result<String, Error> {
+        if context.is_empty() {
+            return Err(Error::SIGNATURE_CONTEXT_INVALID);
+        }
+
+        self.sign_dom2(PURE_FLAG, context, content)
+    }
+
+    pub fn sign_prehashed(&self, content: &[u8], context: &[u8]) -> Result<String, Error> {
+        self.sign_dom2(PREHASH_FLAG, context, &Sha512::digest(content))
+    }
+
+    fn sign_dom2(&self, flag: u8, context: &[u8], message: &[u8]) -> Result<String, Error> {
+        let dom = dom2(flag, context)?;
+        let expanded = Zeroizing::new(Sha512::digest(self.key.to_bytes()));
+        let nonce = hash_to_scalar(&[&dom, &expanded[32..], message]);
+        let commitment = EdwardsPoint::mul_base(&nonce).compress();
+        let challenge = hash_to_scalar(&[
+            &dom,
+            commitment.as_bytes(),
+            self.key.verifying_key().as_bytes(),
+            message,
+        ]);
+
+        let mut signature = commitment.to_bytes().to_vec();
+        signature.extend_from_slice(&(nonce + challenge * self.key.to_scalar()).to_bytes());
+        Ok(base64_url::encode(&signature))
+    }
+}
+
+impl fmt::Debug for EDDSASigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(f, "EDDSASigningKey", "Ed25519", self.fingerprint())
+    }
 }
 
+#[derive(Clone, PartialEq, Eq)]
 pub struct EDDSAVerifyingKey {
     key: VerifyingKey,
 }
 
 impl VerifyFromKey for EDDSAVerifyingKey {
-    fn verify(&self, content: String, sig: String, _alg: Algorithm) -> Result<bool, Error> {
+    fn verifying_algorithms(&self) -> Option<Vec<Algorithm>> {
+        Some(vec![Algorithm::EdDSA])
+    }
+
+    fn verify_batch(
+        &self,
+        items: &[(&[u8], &super::signature::Signature)],
+    ) -> Vec<Result<bool, Error>> {
+        let signatures: Option<Vec<Signature>> = items
+            .iter()
+            .map(|(_, signature)| Signature::from_slice(signature.as_bytes()).ok())
+            .collect();
+
+        if let Some(signatures) = signatures {
+            let messages: Vec<&[u8]> = items.iter().map(|(content, _)| *content).collect();
+            let keys = vec![self.key; items.len()];
+            if !items.is_empty()
+                && ed25519_dalek::verify_batch(&messages, &signatures, &keys).is_ok()
+            {
+                return items.iter().map(|_| Ok(true)).collect();
+            }
+        }
+
+        items
+            .iter()
+            .map(|(content, signature)| self.verify_signature(content, signature))
+            .collect()
+    }
+
+    fn verify_bytes(&self, content: &[u8], sig: &str, _alg: Algorithm) -> Result<bool, Error> {
         let decoded_sig = match base64_url::decode(sig.as_bytes()) {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::DECODING_ERROR);
+                return Err(Error::DECODING_ERROR.with_source(error));
             }
         };
 
@@ -72,12 +201,12 @@ impl VerifyFromKey for EDDSAVerifyingKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::SIGNATURE_IDENTIFICATION_FAILED);
+                return Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_source(error));
             }
         };
 
         let verify_result: Result<(), ed25519_dalek::ed25519::Error> =
-            self.key.verify(content.as_bytes(), &signature);
+            self.key.verify(content, &signature);
         if verify_result.is_ok() {
             return Ok(true);
         } else {
@@ -98,40 +227,160 @@ impl EDDSAVerifyingKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR);
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
             }
         };
 
         Ok(EDDSAVerifyingKey { key: pkc8_key })
     }
 
+    pub fn from_x509_pem(cert_str: &str) -> Result<Self, Error> {
+        Self::from_x509_der(&x509::certificate_pem_to_der(cert_str)?)
+    }
+
+    pub fn from_x509_der(cert: &[u8]) -> Result<Self, Error> {
+        match VerifyingKey::from_public_key_der(&x509::subject_public_key_info(cert)?) {
+            Ok(val) => Ok(EDDSAVerifyingKey { key: val }),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error))
+            }
+        }
+    }
+
     pub fn from_bytes(bytes: &mut [u8]) -> Result<Self, Error> {
         if bytes.len() != 32 {
             return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR);
         }
 
-        let ec_bytes: [u8; 32] = [0; 32];
-        bytes.copy_from_slice(&ec_bytes);
+        let mut ec_bytes: [u8; 32] = [0; 32];
+        ec_bytes.copy_from_slice(bytes);
         let ec_key = match VerifyingKey::from_bytes(&ec_bytes) {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR);
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
             }
         };
         Ok(EDDSAVerifyingKey { key: ec_key })
     }
+
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        if jwk.kty != "OKP" || jwk.crv.as_deref() != Some("Ed25519") {
+            return Err(Error::JWK_KEY_TYPE_MISMATCH);
+        }
+
+        let ec_bytes: [u8; 32] = match Jwk::decode_member(&jwk.x)?.try_into() {
+            Ok(val) => val,
+            Err(_) => return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR),
+        };
+        let ec_key = match VerifyingKey::from_bytes(&ec_bytes) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
+            }
+        };
+        Ok(EDDSAVerifyingKey { key: ec_key })
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.as_bytes().to_vec()
+    }
+
+    pub fn to_x25519(&self) -> Result<X25519PublicKey, Error> {
+        X25519PublicKey::from_bytes(self.key.to_montgomery().as_bytes())
+    }
+
+    pub fn verify_with_context(
+        &self,
+        content: &[u8],
+        sig: &str,
+        context: &[u8],
+    ) -> Result<bool, Error> {
+        if context.is_empty() {
+            return Err(Error::SIGNATURE_CONTEXT_INVALID);
+        }
+
+        self.verify_dom2(PURE_FLAG, context, content, sig)
+    }
+
+    pub fn verify_prehashed(
+        &self,
+        content: &[u8],
+        sig: &str,
+        context: &[u8],
+    ) -> Result<bool, Error> {
+        self.verify_dom2(PREHASH_FLAG, context, &Sha512::digest(content), sig)
+    }
+
+    fn verify_dom2(
+        &self,
+        flag: u8,
+        context: &[u8],
+        message: &[u8],
+        sig: &str,
+    ) -> Result<bool, Error> {
+        let dom = dom2(flag, context)?;
+        let signature = match base64_url::decode(sig.as_bytes()) {
+            Ok(val) if val.len() == 64 => val,
+            Ok(_) => return Err(Error::SIGNATURE_IDENTIFICATION_FAILED),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::DECODING_ERROR.with_source(error));
+            }
+        };
+
+        let (commitment, response) = signature.split_at(32);
+        let response: [u8; 32] = response.try_into().unwrap();
+        let (public_key, commitment_point, response) = match (
+            decode_point(self.key.as_bytes()),
+            decode_point(commitment),
+            Option::<Scalar>::from(Scalar::from_canonical_bytes(response)),
+        ) {
+            (Some(key), Some(point), Some(scalar)) => (key, point, scalar),
+            _ => return Ok(false),
+        };
+
+        let challenge = hash_to_scalar(&[&dom, commitment, self.key.as_bytes(), message]);
+        let expected =
+            EdwardsPoint::vartime_double_scalar_mul_basepoint(&challenge, &-public_key, &response);
+        Ok((expected - commitment_point).is_identity())
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.to_jwk().thumbprint()
+    }
+
+    pub fn to_jwk(&self) -> Jwk {
+        Jwk {
+            kty: String::from("OKP"),
+            crv: Some(String::from("Ed25519")),
+            x: Some(base64_url::encode(self.key.as_bytes())),
+            ..Default::default()
+        }
+    }
 }
 
-pub fn sign_eddsa(message: String, key: impl SignFromKey, alg: Algorithm) -> Result<String, Error> {
-    key.sign(message, alg)
+impl fmt::Debug for EDDSAVerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(f, "EDDSAVerifyingKey", "Ed25519", self.fingerprint())
+    }
+}
+
+pub fn sign_eddsa(
+    message: impl AsRef<[u8]>,
+    key: impl SignFromKey,
+    alg: Algorithm,
+) -> Result<String, Error> {
+    key.sign_bytes(message.as_ref(), alg)
 }
 
 pub fn verify_eddsa(
-    message: String,
-    sig: String,
+    message: impl AsRef<[u8]>,
+    sig: impl AsRef<str>,
     key: impl VerifyFromKey,
     alg: Algorithm,
 ) -> Result<bool, Error> {
-    key.verify(message, sig, alg)
+    key.verify_bytes(message.as_ref(), sig.as_ref(), alg)
 }
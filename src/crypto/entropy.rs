@@ -0,0 +1,201 @@
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, RwLock};
+
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+
+use crate::{errors::Error, log};
+
+pub const REPETITION_CUTOFF: usize = 21;
+pub const PROPORTION_WINDOW: usize = 512;
+pub const PROPORTION_CUTOFF: usize = 410;
+pub const STARTUP_SAMPLES: usize = 1024;
+const BLOCK_LENGTH: usize = 16;
+
+pub trait EntropySource: Send + Sync {
+    fn fill(&self, dest: &mut [u8]) -> Result<(), Error>;
+}
+
+impl<F: Fn(&mut [u8]) -> Result<(), Error> + Send + Sync> EntropySource for F {
+    fn fill(&self, dest: &mut [u8]) -> Result<(), Error> {
+        self(dest)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct OsEntropy;
+
+impl EntropySource for OsEntropy {
+    fn fill(&self, dest: &mut [u8]) -> Result<(), Error> {
+        match OsRng.try_fill_bytes(dest) {
+            Ok(_) => Ok(()),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENTROPY_SOURCE_FAILED.with_source(error.to_string()))
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct HealthState {
+    last: Option<u8>,
+    run: usize,
+    block: Option<[u8; BLOCK_LENGTH]>,
+    failed: bool,
+}
+
+impl HealthState {
+    fn check(&mut self, sample: &[u8]) -> bool {
+        for byte in sample {
+            match self.last == Some(*byte) {
+                true => self.run += 1,
+                false => {
+                    self.last = Some(*byte);
+                    self.run = 1;
+                }
+            }
+            if self.run >= REPETITION_CUTOFF {
+                return false;
+            }
+        }
+
+        for chunk in sample.chunks_exact(BLOCK_LENGTH) {
+            let mut block = [0u8; BLOCK_LENGTH];
+            block.copy_from_slice(chunk);
+            if self.block == Some(block) {
+                return false;
+            }
+            self.block = Some(block);
+        }
+
+        true
+    }
+}
+
+fn proportion_check(sample: &[u8]) -> bool {
+    sample
+        .chunks_exact(PROPORTION_WINDOW)
+        .all(|window| window.iter().filter(|byte| **byte == window[0]).count() < PROPORTION_CUTOFF)
+}
+
+#[derive(Clone)]
+pub struct HealthCheckedRng {
+    source: Arc<dyn EntropySource>,
+    state: Arc<Mutex<HealthState>>,
+}
+
+impl HealthCheckedRng {
+    pub fn new(source: Arc<dyn EntropySource>) -> Result<Self, Error> {
+        let rng = HealthCheckedRng {
+            source,
+            state: Arc::new(Mutex::new(HealthState::default())),
+        };
+
+        let mut sample = vec![0u8; STARTUP_SAMPLES];
+        rng.try_fill(&mut sample)?;
+        if !proportion_check(&sample) {
+            return Err(rng.fail());
+        }
+
+        Ok(rng)
+    }
+
+    pub fn os() -> Result<Self, Error> {
+        Self::new(Arc::new(OsEntropy))
+    }
+
+    fn state(&self) -> MutexGuard<'_, HealthState> {
+        match self.state.lock() {
+            Ok(val) => val,
+            Err(error) => error.into_inner(),
+        }
+    }
+
+    fn fail(&self) -> Error {
+        self.state().failed = true;
+        log::error(Error::ENTROPY_HEALTH_CHECK_FAILED.to_string().as_str());
+        Error::ENTROPY_HEALTH_CHECK_FAILED
+    }
+
+    pub fn is_failed(&self) -> bool {
+        self.state().failed
+    }
+
+    pub fn try_fill(&self, dest: &mut [u8]) -> Result<(), Error> {
+        if self.is_failed() {
+            return Err(Error::ENTROPY_HEALTH_CHECK_FAILED);
+        }
+
+        self.source.fill(dest)?;
+        let mut state = self.state();
+        if state.failed || !state.check(dest) {
+            drop(state);
+            dest.fill(0);
+            return Err(self.fail());
+        }
+
+        Ok(())
+    }
+}
+
+impl RngCore for HealthCheckedRng {
+    fn next_u32(&mut self) -> u32 {
+        let mut bytes = [0u8; 4];
+        self.fill_bytes(&mut bytes);
+        u32::from_le_bytes(bytes)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let mut bytes = [0u8; 8];
+        self.fill_bytes(&mut bytes);
+        u64::from_le_bytes(bytes)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        if let Err(error) = self.try_fill(dest) {
+            panic!("{}", error);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.try_fill(dest).map_err(rand::Error::new)
+    }
+}
+
+impl CryptoRng for HealthCheckedRng {}
+
+static ENTROPY: RwLock<Option<HealthCheckedRng>> = RwLock::new(None);
+static OS_ENTROPY: OnceLock<Option<HealthCheckedRng>> = OnceLock::new();
+
+pub fn set_entropy_source(source: Arc<dyn EntropySource>) -> Result<(), Error> {
+    let rng = HealthCheckedRng::new(source)?;
+    if let Ok(mut val) = ENTROPY.write() {
+        *val = Some(rng);
+    }
+    Ok(())
+}
+
+pub fn clear_entropy_source() {
+    if let Ok(mut val) = ENTROPY.write() {
+        *val = None;
+    }
+}
+
+pub fn rng() -> Result<HealthCheckedRng, Error> {
+    let configured = match ENTROPY.read() {
+        Ok(val) => val.clone(),
+        Err(_) => None,
+    };
+
+    let rng = match configured {
+        Some(val) => val,
+        None => match OS_ENTROPY.get_or_init(|| HealthCheckedRng::os().ok()) {
+            Some(val) => val.clone(),
+            None => return Err(Error::ENTROPY_HEALTH_CHECK_FAILED),
+        },
+    };
+
+    match rng.is_failed() {
+        true => Err(Error::ENTROPY_HEALTH_CHECK_FAILED),
+        false => Ok(rng),
+    }
+}
@@ -0,0 +1,820 @@
+use std::{
+    fmt,
+    ops::{Add, Mul, Sub},
+};
+
+use bls12_381_plus::elliptic_curve::hash2curve::{ExpandMsg, ExpandMsgXmd, Expander};
+use curve25519_dalek::{
+    edwards::{CompressedEdwardsY, EdwardsPoint},
+    scalar::Scalar as Ed25519Scalar,
+    traits::{Identity, IsIdentity},
+};
+use k256::{
+    elliptic_curve::{
+        bigint::U512,
+        ops::Reduce,
+        sec1::{FromEncodedPoint, ToEncodedPoint},
+        Field, PrimeField,
+    },
+    AffinePoint, EncodedPoint, FieldBytes, ProjectivePoint, Scalar as Secp256k1Scalar, WideBytes,
+};
+use rand::{CryptoRng, RngCore};
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::Zeroizing;
+
+use crate::{algorithms::Algorithm, errors::Error};
+
+use super::any::{AnySigningKey, AnyVerifyingKey};
+
+const IDENTIFIER_LENGTH: usize = 2;
+
+pub trait Ciphersuite: Clone + PartialEq + fmt::Debug {
+    const CONTEXT: &'static str;
+    const SCALAR_LENGTH: usize;
+    const ELEMENT_LENGTH: usize;
+
+    type Scalar: Copy
+        + PartialEq
+        + fmt::Debug
+        + Add<Output = Self::Scalar>
+        + Sub<Output = Self::Scalar>
+        + Mul<Output = Self::Scalar>;
+    type Element: Copy
+        + PartialEq
+        + fmt::Debug
+        + Add<Output = Self::Element>
+        + Mul<Self::Scalar, Output = Self::Element>;
+
+    fn random_scalar(rng: &mut (impl CryptoRng + RngCore)) -> Self::Scalar;
+    fn scalar_from_u64(value: u64) -> Self::Scalar;
+    fn invert(scalar: Self::Scalar) -> Option<Self::Scalar>;
+    fn base_mul(scalar: Self::Scalar) -> Self::Element;
+    fn identity() -> Self::Element;
+    fn encode_scalar(scalar: &Self::Scalar) -> Vec<u8>;
+    fn decode_scalar(bytes: &[u8]) -> Result<Self::Scalar, Error>;
+    fn encode_element(element: &Self::Element) -> Vec<u8>;
+    fn decode_element(bytes: &[u8]) -> Result<Self::Element, Error>;
+    fn h1(message: &[u8]) -> Self::Scalar;
+    fn h2(message: &[u8]) -> Self::Scalar;
+    fn h3(message: &[u8]) -> Self::Scalar;
+    fn h4(message: &[u8]) -> Vec<u8>;
+    fn h5(message: &[u8]) -> Vec<u8>;
+    fn secret_from_key(key: &AnySigningKey) -> Result<Self::Scalar, Error>;
+    fn verifying_key(element: &Self::Element) -> Result<AnyVerifyingKey, Error>;
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Ed25519Sha512;
+
+fn sha512_scalar(parts: &[&[u8]]) -> Ed25519Scalar {
+    let mut hasher = Sha512::new();
+    for part in parts {
+        hasher.update(part);
+    }
+    Ed25519Scalar::from_bytes_mod_order_wide(&hasher.finalize().into())
+}
+
+impl Ciphersuite for Ed25519Sha512 {
+    const CONTEXT: &'static str = "FROST-ED25519-SHA512-v1";
+    const SCALAR_LENGTH: usize = 32;
+    const ELEMENT_LENGTH: usize = 32;
+
+    type Scalar = Ed25519Scalar;
+    type Element = EdwardsPoint;
+
+    fn random_scalar(rng: &mut (impl CryptoRng + RngCore)) -> Self::Scalar {
+        loop {
+            let mut bytes = [0u8; 64];
+            rng.fill_bytes(&mut bytes);
+            let scalar = Ed25519Scalar::from_bytes_mod_order_wide(&bytes);
+            if scalar != Ed25519Scalar::ZERO {
+                return scalar;
+            }
+        }
+    }
+
+    fn scalar_from_u64(value: u64) -> Self::Scalar {
+        Ed25519Scalar::from(value)
+    }
+
+    fn invert(scalar: Self::Scalar) -> Option<Self::Scalar> {
+        match scalar == Ed25519Scalar::ZERO {
+            true => None,
+            false => Some(scalar.invert()),
+        }
+    }
+
+    fn base_mul(scalar: Self::Scalar) -> Self::Element {
+        EdwardsPoint::mul_base(&scalar)
+    }
+
+    fn identity() -> Self::Element {
+        EdwardsPoint::identity()
+    }
+
+    fn encode_scalar(scalar: &Self::Scalar) -> Vec<u8> {
+        scalar.to_bytes().to_vec()
+    }
+
+    fn decode_scalar(bytes: &[u8]) -> Result<Self::Scalar, Error> {
+        let bytes: [u8; 32] = match bytes.try_into() {
+            Ok(val) => val,
+            Err(_) => return Err(Error::FROST_MALFORMED),
+        };
+
+        match Option::<Ed25519Scalar>::from(Ed25519Scalar::from_canonical_bytes(bytes)) {
+            Some(val) => Ok(val),
+            None => Err(Error::FROST_MALFORMED),
+        }
+    }
+
+    fn encode_element(element: &Self::Element) -> Vec<u8> {
+        element.compress().to_bytes().to_vec()
+    }
+
+    fn decode_element(bytes: &[u8]) -> Result<Self::Element, Error> {
+        let point = match CompressedEdwardsY::from_slice(bytes) {
+            Ok(val) => val.decompress(),
+            Err(_) => return Err(Error::FROST_MALFORMED),
+        };
+
+        match point {
+            Some(val) if val.is_torsion_free() && !val.is_identity() => Ok(val),
+            _ => Err(Error::FROST_MALFORMED),
+        }
+    }
+
+    fn h1(message: &[u8]) -> Self::Scalar {
+        sha512_scalar(&[Self::CONTEXT.as_bytes(), b"rho", message])
+    }
+
+    fn h2(message: &[u8]) -> Self::Scalar {
+        sha512_scalar(&[message])
+    }
+
+    fn h3(message: &[u8]) -> Self::Scalar {
+        sha512_scalar(&[Self::CONTEXT.as_bytes(), b"nonce", message])
+    }
+
+    fn h4(message: &[u8]) -> Vec<u8> {
+        Sha512::new()
+            .chain_update(Self::CONTEXT)
+            .chain_update(b"msg")
+            .chain_update(message)
+            .finalize()
+            .to_vec()
+    }
+
+    fn h5(message: &[u8]) -> Vec<u8> {
+        Sha512::new()
+            .chain_update(Self::CONTEXT)
+            .chain_update(b"com")
+            .chain_update(message)
+            .finalize()
+            .to_vec()
+    }
+
+    fn secret_from_key(key: &AnySigningKey) -> Result<Self::Scalar, Error> {
+        let seed: [u8; 32] = match key {
+            AnySigningKey::EdDSA(key) => match key.to_bytes().as_slice().try_into() {
+                Ok(val) => val,
+                Err(_) => return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR),
+            },
+            _ => return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(key.algorithm())),
+        };
+
+        Ok(ed25519_dalek::SigningKey::from_bytes(&seed).to_scalar())
+    }
+
+    fn verifying_key(element: &Self::Element) -> Result<AnyVerifyingKey, Error> {
+        AnyVerifyingKey::from_bytes(Algorithm::EdDSA, &Self::encode_element(element))
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Secp256k1Sha256;
+
+fn hash_to_scalar(dst: &[u8], message: &[u8]) -> Secp256k1Scalar {
+    let mut okm = WideBytes::default();
+    match ExpandMsgXmd::<Sha256>::expand_message(&[message], &[dst], 48) {
+        Ok(mut expander) => expander.fill_bytes(&mut okm[16..]),
+        Err(_) => unreachable!("expand_message_xmd with a 48 byte output and non-empty dst"),
+    }
+    <Secp256k1Scalar as Reduce<U512>>::reduce_bytes(&okm)
+}
+
+impl Secp256k1Sha256 {
+    fn dst(label: &str) -> Vec<u8> {
+        format!("{}{}", Self::CONTEXT, label).into_bytes()
+    }
+}
+
+impl Ciphersuite for Secp256k1Sha256 {
+    const CONTEXT: &'static str = "FROST-secp256k1-SHA256-v1";
+    const SCALAR_LENGTH: usize = 32;
+    const ELEMENT_LENGTH: usize = 33;
+
+    type Scalar = Secp256k1Scalar;
+    type Element = ProjectivePoint;
+
+    fn random_scalar(rng: &mut (impl CryptoRng + RngCore)) -> Self::Scalar {
+        loop {
+            let scalar = Secp256k1Scalar::random(&mut *rng);
+            if !bool::from(scalar.is_zero()) {
+                return scalar;
+            }
+        }
+    }
+
+    fn scalar_from_u64(value: u64) -> Self::Scalar {
+        Secp256k1Scalar::from(value)
+    }
+
+    fn invert(scalar: Self::Scalar) -> Option<Self::Scalar> {
+        Option::from(scalar.invert())
+    }
+
+    fn base_mul(scalar: Self::Scalar) -> Self::Element {
+        ProjectivePoint::GENERATOR * scalar
+    }
+
+    fn identity() -> Self::Element {
+        ProjectivePoint::IDENTITY
+    }
+
+    fn encode_scalar(scalar: &Self::Scalar) -> Vec<u8> {
+        scalar.to_bytes().to_vec()
+    }
+
+    fn decode_scalar(bytes: &[u8]) -> Result<Self::Scalar, Error> {
+        if bytes.len() != Self::SCALAR_LENGTH {
+            return Err(Error::FROST_MALFORMED);
+        }
+
+        match Option::<Secp256k1Scalar>::from(Secp256k1Scalar::from_repr(
+            FieldBytes::clone_from_slice(bytes),
+        )) {
+            Some(val) => Ok(val),
+            None => Err(Error::FROST_MALFORMED),
+        }
+    }
+
+    fn encode_element(element: &Self::Element) -> Vec<u8> {
+        element
+            .to_affine()
+            .to_encoded_point(true)
+            .as_bytes()
+            .to_vec()
+    }
+
+    fn decode_element(bytes: &[u8]) -> Result<Self::Element, Error> {
+        if bytes.len() != Self::ELEMENT_LENGTH {
+            return Err(Error::FROST_MALFORMED);
+        }
+
+        let point = match EncodedPoint::from_bytes(bytes) {
+            Ok(val) => Option::<AffinePoint>::from(AffinePoint::from_encoded_point(&val)),
+            Err(_) => None,
+        };
+
+        match point {
+            Some(val) => Ok(ProjectivePoint::from(val)),
+            None => Err(Error::FROST_MALFORMED),
+        }
+    }
+
+    fn h1(message: &[u8]) -> Self::Scalar {
+        hash_to_scalar(&Self::dst("rho"), message)
+    }
+
+    fn h2(message: &[u8]) -> Self::Scalar {
+        hash_to_scalar(&Self::dst("chal"), message)
+    }
+
+    fn h3(message: &[u8]) -> Self::Scalar {
+        hash_to_scalar(&Self::dst("nonce"), message)
+    }
+
+    fn h4(message: &[u8]) -> Vec<u8> {
+        Sha256::new()
+            .chain_update(Self::dst("msg"))
+            .chain_update(message)
+            .finalize()
+            .to_vec()
+    }
+
+    fn h5(message: &[u8]) -> Vec<u8> {
+        Sha256::new()
+            .chain_update(Self::dst("com"))
+            .chain_update(message)
+            .finalize()
+            .to_vec()
+    }
+
+    fn secret_from_key(key: &AnySigningKey) -> Result<Self::Scalar, Error> {
+        match key {
+            AnySigningKey::P256k(key) => match Self::decode_scalar(&key.to_bytes()) {
+                Ok(val) if !bool::from(val.is_zero()) => Ok(val),
+                _ => Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR),
+            },
+            _ => Err(Error::UNKNOWN_ALGORITHM.with_algorithm(key.algorithm())),
+        }
+    }
+
+    fn verifying_key(element: &Self::Element) -> Result<AnyVerifyingKey, Error> {
+        AnyVerifyingKey::from_bytes(Algorithm::ES256K, &Self::encode_element(element))
+    }
+}
+
+fn decode_identifier(bytes: &[u8]) -> Result<u16, Error> {
+    match bytes.try_into() {
+        Ok(val) if u16::from_be_bytes(val) != 0 => Ok(u16::from_be_bytes(val)),
+        _ => Err(Error::FROST_MALFORMED),
+    }
+}
+
+fn evaluate_polynomial<C: Ciphersuite>(coefficients: &[C::Scalar], x: C::Scalar) -> C::Scalar {
+    coefficients
+        .iter()
+        .rev()
+        .fold(C::scalar_from_u64(0), |acc, coefficient| {
+            acc * x + *coefficient
+        })
+}
+
+fn evaluate_commitment<C: Ciphersuite>(commitment: &[C::Element], x: C::Scalar) -> C::Element {
+    commitment
+        .iter()
+        .rev()
+        .fold(C::identity(), |acc, coefficient| acc * x + *coefficient)
+}
+
+fn lagrange_coefficient<C: Ciphersuite>(
+    identifier: u16,
+    participants: &[u16],
+) -> Result<C::Scalar, Error> {
+    let x_i = C::scalar_from_u64(identifier as u64);
+    let mut numerator = C::scalar_from_u64(1);
+    let mut denominator = C::scalar_from_u64(1);
+    for participant in participants.iter().filter(|val| **val != identifier) {
+        let x_j = C::scalar_from_u64(*participant as u64);
+        numerator = numerator * x_j;
+        denominator = denominator * (x_j - x_i);
+    }
+
+    match C::invert(denominator) {
+        Some(val) => Ok(numerator * val),
+        None => Err(Error::FROST_INVALID_COMMITMENTS),
+    }
+}
+
+pub struct KeyShare<C: Ciphersuite> {
+    identifier: u16,
+    min_signers: u16,
+    secret: C::Scalar,
+    group_public_key: C::Element,
+}
+
+impl<C: Ciphersuite> KeyShare<C> {
+    pub fn identifier(&self) -> u16 {
+        self.identifier
+    }
+
+    pub fn min_signers(&self) -> u16 {
+        self.min_signers
+    }
+
+    pub fn verifying_share(&self) -> C::Element {
+        C::base_mul(self.secret)
+    }
+
+    pub fn group_public_key(&self) -> C::Element {
+        self.group_public_key
+    }
+
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut bytes = Zeroizing::new(self.identifier.to_be_bytes().to_vec());
+        bytes.extend_from_slice(&self.min_signers.to_be_bytes());
+        bytes.extend_from_slice(&C::encode_scalar(&self.secret));
+        bytes.extend_from_slice(&C::encode_element(&self.group_public_key));
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let secret_start = 2 * IDENTIFIER_LENGTH;
+        let key_start = secret_start + C::SCALAR_LENGTH;
+        if bytes.len() != key_start + C::ELEMENT_LENGTH {
+            return Err(Error::FROST_INVALID_SHARE);
+        }
+
+        let share = KeyShare {
+            identifier: decode_identifier(&bytes[..IDENTIFIER_LENGTH])?,
+            min_signers: u16::from_be_bytes([bytes[2], bytes[3]]),
+            secret: C::decode_scalar(&bytes[secret_start..key_start])?,
+            group_public_key: C::decode_element(&bytes[key_start..])?,
+        };
+        match share.min_signers >= 2 {
+            true => Ok(share),
+            false => Err(Error::FROST_INVALID_SHARE),
+        }
+    }
+}
+
+impl<C: Ciphersuite> fmt::Debug for KeyShare<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyShare")
+            .field("ciphersuite", &C::CONTEXT)
+            .field("identifier", &self.identifier)
+            .field("min_signers", &self.min_signers)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct PublicKeyPackage<C: Ciphersuite> {
+    min_signers: u16,
+    group_public_key: C::Element,
+    verifying_shares: Vec<(u16, C::Element)>,
+    commitment: Vec<C::Element>,
+}
+
+impl<C: Ciphersuite> PublicKeyPackage<C> {
+    pub fn min_signers(&self) -> u16 {
+        self.min_signers
+    }
+
+    pub fn max_signers(&self) -> u16 {
+        self.verifying_shares.len() as u16
+    }
+
+    pub fn group_public_key(&self) -> C::Element {
+        self.group_public_key
+    }
+
+    pub fn verifying_key(&self) -> Result<AnyVerifyingKey, Error> {
+        C::verifying_key(&self.group_public_key)
+    }
+
+    pub fn verifying_share(&self, identifier: u16) -> Option<C::Element> {
+        self.verifying_shares
+            .iter()
+            .find(|(val, _)| *val == identifier)
+            .map(|(_, share)| *share)
+    }
+
+    pub fn verify_share(&self, share: &KeyShare<C>) -> Result<(), Error> {
+        let expected = evaluate_commitment::<C>(
+            &self.commitment,
+            C::scalar_from_u64(share.identifier as u64),
+        );
+        let verifying_share = share.verifying_share();
+
+        match verifying_share == expected
+            && self.verifying_share(share.identifier) == Some(verifying_share)
+            && share.group_public_key == self.group_public_key
+            && share.min_signers == self.min_signers
+        {
+            true => Ok(()),
+            false => Err(Error::FROST_INVALID_SHARE),
+        }
+    }
+}
+
+fn deal<C: Ciphersuite>(
+    secret: C::Scalar,
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<(Vec<KeyShare<C>>, PublicKeyPackage<C>), Error> {
+    if min_signers < 2 || min_signers > max_signers {
+        return Err(Error::FROST_INVALID_PARAMETERS);
+    }
+
+    let mut coefficients = vec![secret];
+    for _ in 1..min_signers {
+        coefficients.push(C::random_scalar(rng));
+    }
+
+    let group_public_key = C::base_mul(secret);
+    let mut shares = Vec::new();
+    let mut verifying_shares = Vec::new();
+    for identifier in 1..=max_signers {
+        let share = evaluate_polynomial::<C>(&coefficients, C::scalar_from_u64(identifier as u64));
+        verifying_shares.push((identifier, C::base_mul(share)));
+        shares.push(KeyShare {
+            identifier,
+            min_signers,
+            secret: share,
+            group_public_key,
+        });
+    }
+
+    let package = PublicKeyPackage {
+        min_signers,
+        group_public_key,
+        verifying_shares,
+        commitment: coefficients.iter().map(|val| C::base_mul(*val)).collect(),
+    };
+    Ok((shares, package))
+}
+
+pub fn generate_with_dealer<C: Ciphersuite>(
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<(Vec<KeyShare<C>>, PublicKeyPackage<C>), Error> {
+    let secret = C::random_scalar(rng);
+    deal(secret, min_signers, max_signers, rng)
+}
+
+pub fn split_key<C: Ciphersuite>(
+    key: &AnySigningKey,
+    min_signers: u16,
+    max_signers: u16,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<(Vec<KeyShare<C>>, PublicKeyPackage<C>), Error> {
+    deal(C::secret_from_key(key)?, min_signers, max_signers, rng)
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SigningCommitments<C: Ciphersuite> {
+    identifier: u16,
+    hiding: C::Element,
+    binding: C::Element,
+}
+
+impl<C: Ciphersuite> SigningCommitments<C> {
+    pub fn identifier(&self) -> u16 {
+        self.identifier
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.identifier.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&C::encode_element(&self.hiding));
+        bytes.extend_from_slice(&C::encode_element(&self.binding));
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let binding_start = IDENTIFIER_LENGTH + C::ELEMENT_LENGTH;
+        if bytes.len() != binding_start + C::ELEMENT_LENGTH {
+            return Err(Error::FROST_MALFORMED);
+        }
+
+        Ok(SigningCommitments {
+            identifier: decode_identifier(&bytes[..IDENTIFIER_LENGTH])?,
+            hiding: C::decode_element(&bytes[IDENTIFIER_LENGTH..binding_start])?,
+            binding: C::decode_element(&bytes[binding_start..])?,
+        })
+    }
+}
+
+pub struct SigningNonces<C: Ciphersuite> {
+    hiding: C::Scalar,
+    binding: C::Scalar,
+    commitments: SigningCommitments<C>,
+}
+
+impl<C: Ciphersuite> SigningNonces<C> {
+    pub fn commitments(&self) -> &SigningCommitments<C> {
+        &self.commitments
+    }
+}
+
+impl<C: Ciphersuite> fmt::Debug for SigningNonces<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SigningNonces")
+            .field("identifier", &self.commitments.identifier)
+            .finish_non_exhaustive()
+    }
+}
+
+fn generate_nonce<C: Ciphersuite>(
+    secret: &C::Scalar,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> C::Scalar {
+    let mut input = Zeroizing::new(vec![0u8; 32]);
+    rng.fill_bytes(&mut input);
+    input.extend_from_slice(&C::encode_scalar(secret));
+    C::h3(&input)
+}
+
+pub fn commit<C: Ciphersuite>(
+    share: &KeyShare<C>,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> (SigningNonces<C>, SigningCommitments<C>) {
+    let hiding = generate_nonce::<C>(&share.secret, rng);
+    let binding = generate_nonce::<C>(&share.secret, rng);
+    let commitments = SigningCommitments {
+        identifier: share.identifier,
+        hiding: C::base_mul(hiding),
+        binding: C::base_mul(binding),
+    };
+
+    let nonces = SigningNonces {
+        hiding,
+        binding,
+        commitments: commitments.clone(),
+    };
+    (nonces, commitments)
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct SignatureShare<C: Ciphersuite> {
+    identifier: u16,
+    share: C::Scalar,
+}
+
+impl<C: Ciphersuite> SignatureShare<C> {
+    pub fn identifier(&self) -> u16 {
+        self.identifier
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.identifier.to_be_bytes().to_vec();
+        bytes.extend_from_slice(&C::encode_scalar(&self.share));
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != IDENTIFIER_LENGTH + C::SCALAR_LENGTH {
+            return Err(Error::FROST_MALFORMED);
+        }
+
+        Ok(SignatureShare {
+            identifier: decode_identifier(&bytes[..IDENTIFIER_LENGTH])?,
+            share: C::decode_scalar(&bytes[IDENTIFIER_LENGTH..])?,
+        })
+    }
+}
+
+struct SigningPackage<'a, C: Ciphersuite> {
+    commitments: Vec<&'a SigningCommitments<C>>,
+    binding_factors: Vec<C::Scalar>,
+    group_commitment: C::Element,
+    challenge: C::Scalar,
+}
+
+impl<'a, C: Ciphersuite> SigningPackage<'a, C> {
+    fn new(
+        group_public_key: &C::Element,
+        message: &[u8],
+        commitments: &'a [SigningCommitments<C>],
+        min_signers: u16,
+    ) -> Result<Self, Error> {
+        let mut sorted: Vec<&SigningCommitments<C>> = commitments.iter().collect();
+        sorted.sort_by_key(|val| val.identifier);
+        sorted.dedup_by_key(|val| val.identifier);
+        if sorted.len() != commitments.len() || sorted.len() < min_signers as usize {
+            return Err(Error::FROST_INVALID_COMMITMENTS);
+        }
+
+        let mut encoded = Vec::new();
+        for commitment in &sorted {
+            encoded.extend_from_slice(&C::encode_scalar(&C::scalar_from_u64(
+                commitment.identifier as u64,
+            )));
+            encoded.extend_from_slice(&C::encode_element(&commitment.hiding));
+            encoded.extend_from_slice(&C::encode_element(&commitment.binding));
+        }
+
+        let group_public_key = C::encode_element(group_public_key);
+        let mut prefix = group_public_key.clone();
+        prefix.extend_from_slice(&C::h4(message));
+        prefix.extend_from_slice(&C::h5(&encoded));
+
+        let mut binding_factors = Vec::new();
+        let mut group_commitment = C::identity();
+        for commitment in &sorted {
+            let mut input = prefix.clone();
+            input.extend_from_slice(&C::encode_scalar(&C::scalar_from_u64(
+                commitment.identifier as u64,
+            )));
+            let binding_factor = C::h1(&input);
+            group_commitment =
+                group_commitment + commitment.hiding + commitment.binding * binding_factor;
+            binding_factors.push(binding_factor);
+        }
+
+        let mut input = C::encode_element(&group_commitment);
+        input.extend_from_slice(&group_public_key);
+        input.extend_from_slice(message);
+
+        Ok(SigningPackage {
+            commitments: sorted,
+            binding_factors,
+            group_commitment,
+            challenge: C::h2(&input),
+        })
+    }
+
+    fn identifiers(&self) -> Vec<u16> {
+        self.commitments.iter().map(|val| val.identifier).collect()
+    }
+
+    fn position(&self, identifier: u16) -> Option<usize> {
+        self.commitments
+            .iter()
+            .position(|val| val.identifier == identifier)
+    }
+}
+
+pub fn sign<C: Ciphersuite>(
+    message: &[u8],
+    share: &KeyShare<C>,
+    nonces: SigningNonces<C>,
+    commitments: &[SigningCommitments<C>],
+) -> Result<SignatureShare<C>, Error> {
+    let package = SigningPackage::new(
+        &share.group_public_key,
+        message,
+        commitments,
+        share.min_signers,
+    )?;
+    let index = match package.position(share.identifier) {
+        Some(val) if *package.commitments[val] == nonces.commitments => val,
+        _ => return Err(Error::FROST_INVALID_COMMITMENTS),
+    };
+
+    let lambda = lagrange_coefficient::<C>(share.identifier, &package.identifiers())?;
+    Ok(SignatureShare {
+        identifier: share.identifier,
+        share: nonces.hiding
+            + nonces.binding * package.binding_factors[index]
+            + lambda * share.secret * package.challenge,
+    })
+}
+
+pub fn aggregate<C: Ciphersuite>(
+    message: &[u8],
+    commitments: &[SigningCommitments<C>],
+    shares: &[SignatureShare<C>],
+    public_key_package: &PublicKeyPackage<C>,
+) -> Result<String, Error> {
+    let package = SigningPackage::new(
+        &public_key_package.group_public_key,
+        message,
+        commitments,
+        public_key_package.min_signers,
+    )?;
+    if shares.len() != package.commitments.len() {
+        return Err(Error::FROST_INVALID_COMMITMENTS);
+    }
+
+    let identifiers = package.identifiers();
+    let mut z = C::scalar_from_u64(0);
+    for share in shares {
+        let invalid =
+            || Error::FROST_INVALID_SIGNATURE_SHARE.with_key(&share.identifier.to_string());
+        let index = match package.position(share.identifier) {
+            Some(val) => val,
+            None => return Err(invalid()),
+        };
+        let verifying_share = match public_key_package.verifying_share(share.identifier) {
+            Some(val) => val,
+            None => return Err(invalid()),
+        };
+
+        let commitment = package.commitments[index];
+        let lambda = lagrange_coefficient::<C>(share.identifier, &identifiers)?;
+        let expected = commitment.hiding
+            + commitment.binding * package.binding_factors[index]
+            + verifying_share * (package.challenge * lambda);
+        if C::base_mul(share.share) != expected {
+            return Err(invalid());
+        }
+
+        z = z + share.share;
+    }
+
+    let mut signature = C::encode_element(&package.group_commitment);
+    signature.extend_from_slice(&C::encode_scalar(&z));
+    Ok(base64_url::encode(&signature))
+}
+
+pub fn verify_signature<C: Ciphersuite>(
+    group_public_key: &C::Element,
+    message: &[u8],
+    signature: &str,
+) -> Result<bool, Error> {
+    let signature = match base64_url::decode(signature) {
+        Ok(val) if val.len() == C::ELEMENT_LENGTH + C::SCALAR_LENGTH => val,
+        _ => return Err(Error::SIGNATURE_IDENTIFICATION_FAILED),
+    };
+
+    let commitment = match C::decode_element(&signature[..C::ELEMENT_LENGTH]) {
+        Ok(val) => val,
+        Err(_) => return Ok(false),
+    };
+    let z = match C::decode_scalar(&signature[C::ELEMENT_LENGTH..]) {
+        Ok(val) => val,
+        Err(_) => return Ok(false),
+    };
+
+    let mut input = signature[..C::ELEMENT_LENGTH].to_vec();
+    input.extend_from_slice(&C::encode_element(group_public_key));
+    input.extend_from_slice(message);
+    let challenge = C::h2(&input);
+
+    Ok(C::base_mul(z) == commitment + *group_public_key * challenge)
+}
@@ -0,0 +1,214 @@
+use std::{collections::HashMap, fmt, sync::Mutex};
+
+use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+use rand::RngCore;
+
+use crate::{
+    algorithms::{Algorithm, AlgorithmFamily},
+    errors::Error,
+    jwk::Jwk,
+    log,
+};
+
+use super::{any::AnyVerifyingKey, ecdsa::signature_from_der, SignFromKey};
+
+pub const ATTESTATION_FORMAT_NONE: &str = "none";
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct KeyHandle(pub String);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct KeyAttestation {
+    pub format: String,
+    pub statement: Vec<u8>,
+    pub certificate_chain: Vec<Vec<u8>>,
+}
+
+pub trait HardwareKeyBackend {
+    fn generate_key(&self, alias: &str, alg: Algorithm) -> Result<KeyHandle, Error>;
+
+    fn find_key(&self, alias: &str) -> Result<KeyHandle, Error>;
+
+    fn algorithm(&self, handle: &KeyHandle) -> Result<Algorithm, Error>;
+
+    fn public_key(&self, handle: &KeyHandle) -> Result<Jwk, Error>;
+
+    fn sign(&self, handle: &KeyHandle, content: &[u8]) -> Result<Vec<u8>, Error>;
+
+    fn attest(&self, handle: &KeyHandle, challenge: &[u8]) -> Result<KeyAttestation, Error>;
+
+    fn delete_key(&self, handle: &KeyHandle) -> Result<(), Error>;
+}
+
+pub struct HardwareSigningKey<'a, B: HardwareKeyBackend + ?Sized> {
+    backend: &'a B,
+    handle: KeyHandle,
+}
+
+impl<'a, B: HardwareKeyBackend + ?Sized> HardwareSigningKey<'a, B> {
+    pub fn new(backend: &'a B, handle: KeyHandle) -> Self {
+        HardwareSigningKey { backend, handle }
+    }
+
+    pub fn handle(&self) -> &KeyHandle {
+        &self.handle
+    }
+
+    pub fn verifying_key(&self) -> Result<AnyVerifyingKey, Error> {
+        AnyVerifyingKey::from_jwk(&self.backend.public_key(&self.handle)?)
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.verifying_key()?.fingerprint()
+    }
+}
+
+impl<B: HardwareKeyBackend + ?Sized> fmt::Debug for HardwareSigningKey<'_, B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HardwareSigningKey")
+            .field("handle", &self.handle)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<B: HardwareKeyBackend + ?Sized> SignFromKey for HardwareSigningKey<'_, B> {
+    fn sign_bytes(&self, content: &[u8], alg: Algorithm) -> Result<String, Error> {
+        if alg != self.backend.algorithm(&self.handle)? {
+            return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg));
+        }
+
+        let signature = self.backend.sign(&self.handle, content)?;
+        match alg.get_family() {
+            AlgorithmFamily::EC => signature_from_der(&signature, alg),
+            _ => Ok(base64_url::encode(&signature)),
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct InMemoryKeyBackend {
+    keys: Mutex<HashMap<String, (String, SigningKey)>>,
+}
+
+impl InMemoryKeyBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn with_key<T>(
+        &self,
+        handle: &KeyHandle,
+        f: impl FnOnce(&SigningKey) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let keys = match self.keys.lock() {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::HARDWARE_KEY_BACKEND_ERROR.with_source(error.to_string()));
+            }
+        };
+
+        match keys.get(&handle.0) {
+            Some((_, key)) => f(key),
+            None => Err(Error::HARDWARE_KEY_NOT_FOUND),
+        }
+    }
+}
+
+impl HardwareKeyBackend for InMemoryKeyBackend {
+    fn generate_key(&self, alias: &str, alg: Algorithm) -> Result<KeyHandle, Error> {
+        if alg != Algorithm::ES256 {
+            return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg));
+        }
+
+        let mut id = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut id);
+        let handle = KeyHandle(hex::encode(id));
+
+        match self.keys.lock() {
+            Ok(mut val) => {
+                val.insert(
+                    handle.0.clone(),
+                    (
+                        String::from(alias),
+                        SigningKey::random(&mut rand::thread_rng()),
+                    ),
+                );
+                Ok(handle)
+            }
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::HARDWARE_KEY_BACKEND_ERROR.with_source(error.to_string()))
+            }
+        }
+    }
+
+    fn find_key(&self, alias: &str) -> Result<KeyHandle, Error> {
+        let keys = match self.keys.lock() {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::HARDWARE_KEY_BACKEND_ERROR.with_source(error.to_string()));
+            }
+        };
+
+        match keys.iter().find(|(_, (name, _))| name == alias) {
+            Some((id, _)) => Ok(KeyHandle(id.clone())),
+            None => Err(Error::HARDWARE_KEY_NOT_FOUND),
+        }
+    }
+
+    fn algorithm(&self, handle: &KeyHandle) -> Result<Algorithm, Error> {
+        self.with_key(handle, |_| Ok(Algorithm::ES256))
+    }
+
+    fn public_key(&self, handle: &KeyHandle) -> Result<Jwk, Error> {
+        self.with_key(handle, |key| {
+            let point = key.verifying_key().to_encoded_point(false);
+            Ok(Jwk {
+                kty: String::from("EC"),
+                crv: Some(String::from("P-256")),
+                x: point.x().map(|val| base64_url::encode(val.as_slice())),
+                y: point.y().map(|val| base64_url::encode(val.as_slice())),
+                kid: Some(handle.0.clone()),
+                ..Default::default()
+            })
+        })
+    }
+
+    fn sign(&self, handle: &KeyHandle, content: &[u8]) -> Result<Vec<u8>, Error> {
+        self.with_key(handle, |key| {
+            let sig_result: Result<Signature, p256::ecdsa::Error> = key.try_sign(content);
+            match sig_result {
+                Ok(val) => Ok(val.to_der().as_bytes().to_vec()),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    Err(Error::SIGNING_FAILED.with_source(error))
+                }
+            }
+        })
+    }
+
+    fn attest(&self, handle: &KeyHandle, _challenge: &[u8]) -> Result<KeyAttestation, Error> {
+        self.with_key(handle, |_| {
+            Ok(KeyAttestation {
+                format: String::from(ATTESTATION_FORMAT_NONE),
+                statement: Vec::new(),
+                certificate_chain: Vec::new(),
+            })
+        })
+    }
+
+    fn delete_key(&self, handle: &KeyHandle) -> Result<(), Error> {
+        match self.keys.lock() {
+            Ok(mut val) => match val.remove(&handle.0) {
+                Some(_) => Ok(()),
+                None => Err(Error::HARDWARE_KEY_NOT_FOUND),
+            },
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::HARDWARE_KEY_BACKEND_ERROR.with_source(error.to_string()))
+            }
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::fmt;
+
+use elliptic_curve::{sec1::ToEncodedPoint, PrimeField};
+use hmac::{Hmac, Mac};
+use k256::{FieldBytes, Scalar, SecretKey};
+use sha2::Sha512;
+use zeroize::Zeroizing;
+
+use crate::{errors::Error, log};
+
+use super::{ecdsa::_256k::P256kSigningKey, eddsa::EDDSASigningKey, fmt_key};
+
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HdCurve {
+    Secp256k1,
+    Ed25519,
+}
+
+impl HdCurve {
+    fn seed_key(&self) -> &'static [u8] {
+        match self {
+            HdCurve::Secp256k1 => b"Bitcoin seed",
+            HdCurve::Ed25519 => b"ed25519 seed",
+        }
+    }
+}
+
+pub fn parse_derivation_path(path: &str) -> Result<Vec<u32>, Error> {
+    let mut components = path.split('/');
+    if components.next() != Some("m") {
+        return Err(Error::INVALID_DERIVATION_PATH);
+    }
+
+    let mut indexes = Vec::new();
+    for component in components {
+        let (index, offset) = match component
+            .strip_suffix('\'')
+            .or_else(|| component.strip_suffix('h'))
+        {
+            Some(val) => (val, HARDENED_OFFSET),
+            None => (component, 0),
+        };
+
+        match index.parse::<u32>() {
+            Ok(val) if val < HARDENED_OFFSET => indexes.push(val | offset),
+            _ => return Err(Error::INVALID_DERIVATION_PATH),
+        }
+    }
+
+    Ok(indexes)
+}
+
+pub struct ExtendedPrivateKey {
+    curve: HdCurve,
+    key: Zeroizing<[u8; 32]>,
+    chain_code: Zeroizing<[u8; 32]>,
+    depth: u8,
+}
+
+impl ExtendedPrivateKey {
+    pub fn master(curve: HdCurve, seed: &[u8]) -> Result<Self, Error> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(Error::KEY_DERIVATION_FAILED);
+        }
+
+        let (key, chain_code) = hmac_sha512(curve.seed_key(), &[seed])?;
+        if curve == HdCurve::Secp256k1 {
+            secp256k1_scalar(&key)?;
+        }
+
+        Ok(ExtendedPrivateKey {
+            curve,
+            key,
+            chain_code,
+            depth: 0,
+        })
+    }
+
+    pub fn curve(&self) -> HdCurve {
+        self.curve
+    }
+
+    pub fn depth(&self) -> u8 {
+        self.depth
+    }
+
+    pub fn private_key(&self) -> &[u8] {
+        self.key.as_slice()
+    }
+
+    pub fn chain_code(&self) -> &[u8] {
+        self.chain_code.as_slice()
+    }
+
+    pub fn derive_child(&self, index: u32) -> Result<Self, Error> {
+        let hardened = index >= HARDENED_OFFSET;
+        let depth = match self.depth.checked_add(1) {
+            Some(val) => val,
+            None => return Err(Error::KEY_DERIVATION_FAILED),
+        };
+
+        let (key, chain_code) = match self.curve {
+            HdCurve::Ed25519 => {
+                if !hardened {
+                    return Err(Error::INVALID_DERIVATION_PATH);
+                }
+
+                hmac_sha512(
+                    self.chain_code.as_slice(),
+                    &[&[0], self.key.as_slice(), &index.to_be_bytes()],
+                )?
+            }
+            HdCurve::Secp256k1 => {
+                let (tweak, chain_code) = match hardened {
+                    true => hmac_sha512(
+                        self.chain_code.as_slice(),
+                        &[&[0], self.key.as_slice(), &index.to_be_bytes()],
+                    )?,
+                    false => hmac_sha512(
+                        self.chain_code.as_slice(),
+                        &[&secp256k1_public_key(&self.key)?, &index.to_be_bytes()],
+                    )?,
+                };
+
+                let child = secp256k1_scalar(&tweak)? + secp256k1_scalar(&self.key)?;
+                if bool::from(child.is_zero()) {
+                    return Err(Error::KEY_DERIVATION_FAILED);
+                }
+
+                (Zeroizing::new(child.to_repr().into()), chain_code)
+            }
+        };
+
+        Ok(ExtendedPrivateKey {
+            curve: self.curve,
+            key,
+            chain_code,
+            depth,
+        })
+    }
+
+    pub fn derive_path(&self, path: &str) -> Result<Self, Error> {
+        let mut key = ExtendedPrivateKey {
+            curve: self.curve,
+            key: self.key.clone(),
+            chain_code: self.chain_code.clone(),
+            depth: self.depth,
+        };
+
+        for index in parse_derivation_path(path)? {
+            key = key.derive_child(index)?;
+        }
+
+        Ok(key)
+    }
+
+    pub fn ed25519_signing_key(&self) -> Result<EDDSASigningKey, Error> {
+        match self.curve {
+            HdCurve::Ed25519 => EDDSASigningKey::from_bytes(&mut self.key.to_vec()),
+            HdCurve::Secp256k1 => Err(Error::KEY_DERIVATION_FAILED),
+        }
+    }
+
+    pub fn secp256k1_signing_key(&self) -> Result<P256kSigningKey, Error> {
+        match self.curve {
+            HdCurve::Secp256k1 => P256kSigningKey::from_bytes(self.key.as_slice()),
+            HdCurve::Ed25519 => Err(Error::KEY_DERIVATION_FAILED),
+        }
+    }
+}
+
+impl fmt::Debug for ExtendedPrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fingerprint = match self.curve {
+            HdCurve::Ed25519 => self.ed25519_signing_key().and_then(|key| key.fingerprint()),
+            HdCurve::Secp256k1 => self
+                .secp256k1_signing_key()
+                .and_then(|key| key.fingerprint()),
+        };
+        fmt_key(
+            f,
+            "ExtendedPrivateKey",
+            &format!("{:?}", self.curve),
+            fingerprint,
+        )
+    }
+}
+
+type KeyAndChainCode = (Zeroizing<[u8; 32]>, Zeroizing<[u8; 32]>);
+
+fn hmac_sha512(key: &[u8], data: &[&[u8]]) -> Result<KeyAndChainCode, Error> {
+    let mut mac = match Hmac::<Sha512>::new_from_slice(key) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::KEY_DERIVATION_FAILED.with_source(error));
+        }
+    };
+
+    for part in data {
+        mac.update(part);
+    }
+
+    let output = Zeroizing::new(mac.finalize().into_bytes());
+    let mut left = Zeroizing::new([0u8; 32]);
+    let mut right = Zeroizing::new([0u8; 32]);
+    left.copy_from_slice(&output[..32]);
+    right.copy_from_slice(&output[32..]);
+    Ok((left, right))
+}
+
+fn secp256k1_scalar(bytes: &[u8; 32]) -> Result<Scalar, Error> {
+    let scalar: Option<Scalar> = Scalar::from_repr(FieldBytes::clone_from_slice(bytes)).into();
+    match scalar {
+        Some(val) if !bool::from(val.is_zero()) => Ok(val),
+        _ => Err(Error::KEY_DERIVATION_FAILED),
+    }
+}
+
+fn secp256k1_public_key(key: &[u8; 32]) -> Result<Vec<u8>, Error> {
+    match SecretKey::from_slice(key) {
+        Ok(val) => Ok(val.public_key().to_encoded_point(true).as_bytes().to_vec()),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::KEY_DERIVATION_FAILED.with_source(error))
+        }
+    }
+}
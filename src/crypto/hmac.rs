@@ -1,8 +1,11 @@
+use std::fmt;
+
 use crate::errors::Error;
 use crate::{algorithms::Algorithm, log};
 use generic_array::typenum::{IsLess, Le, NonZero, U256};
 use hmac::Hmac;
 use hmac::Mac;
+use sha2::Digest;
 use sha2::Sha384;
 use sha2::Sha512;
 use sha2::{
@@ -14,6 +17,8 @@ use sha2::{
     Sha256,
 };
 
+use super::constant_time::constant_time_eq;
+use super::fmt_key;
 use super::SignFromKey;
 use super::VerifyFromKey;
 
@@ -27,7 +32,25 @@ impl HMACKey {
         HMACKey { key: pass }
     }
 
-    fn hmac_sign<T>(&self, content: String) -> Result<String, Error>
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.as_bytes().to_vec()
+    }
+
+    pub fn verifying_key(&self) -> HMACKey {
+        self.clone()
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        let thumbprint_input = format!(
+            "{{\"k\":\"{}\",\"kty\":\"oct\"}}",
+            base64_url::encode(self.key.as_bytes())
+        );
+        Ok(base64_url::encode(
+            Sha256::digest(thumbprint_input.as_bytes()).as_slice(),
+        ))
+    }
+
+    fn hmac_sign<T>(&self, content: &[u8]) -> Result<String, Error>
     where
         T: CoreProxy,
         T::Core: HashMarker
@@ -43,17 +66,17 @@ impl HMACKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::HMAC_KEY_ERROR);
+                return Err(Error::HMAC_KEY_ERROR.with_source(error));
             }
         };
 
-        hmac_wrapper.update(content.as_bytes());
+        hmac_wrapper.update(content);
 
         let signed_bytes = hmac_wrapper.finalize().into_bytes();
         Ok(base64_url::encode(&signed_bytes.to_vec()))
     }
 
-    fn hmac_verify<T>(&self, content: String, signature: String) -> Result<bool, Error>
+    fn hmac_verify<T>(&self, content: &[u8], signature: &str) -> Result<bool, Error>
     where
         T: CoreProxy,
         T::Core: HashMarker
@@ -69,7 +92,7 @@ impl HMACKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::DECODING_ERROR);
+                return Err(Error::DECODING_ERROR.with_source(error));
             }
         };
 
@@ -77,62 +100,79 @@ impl HMACKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::HMAC_KEY_ERROR);
+                return Err(Error::HMAC_KEY_ERROR.with_source(error));
             }
         };
 
-        hmac_wrapper.update(content.as_bytes());
+        hmac_wrapper.update(content);
 
-        match hmac_wrapper.verify_slice(sig.as_slice()) {
-            Ok(()) => Ok(true),
-            Err(error) => {
-                log::error(error.to_string().as_str());
-                return Ok(false);
-            }
-        }
+        let expected = hmac_wrapper.finalize().into_bytes();
+        Ok(constant_time_eq(expected.as_slice(), sig.as_slice()))
     }
 }
 
+impl fmt::Debug for HMACKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(f, "HMACKey", "oct", self.fingerprint())
+    }
+}
+
+impl PartialEq for HMACKey {
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(self.key.as_bytes(), other.key.as_bytes())
+    }
+}
+
+impl Eq for HMACKey {}
+
 impl SignFromKey for HMACKey {
-    fn sign(
+    fn sign_bytes(
         &self,
-        content: String,
+        content: &[u8],
         alg: crate::algorithms::Algorithm,
     ) -> Result<String, crate::errors::Error> {
         match alg {
             Algorithm::HS256 => self.hmac_sign::<Sha256>(content),
             Algorithm::HS384 => self.hmac_sign::<Sha384>(content),
             Algorithm::HS512 => self.hmac_sign::<Sha512>(content),
-            _ => Err(Error::UNKNOWN_ALGORITHM),
+            _ => Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
         }
     }
 }
 
 impl VerifyFromKey for HMACKey {
-    fn verify(
+    fn verifying_algorithms(&self) -> Option<Vec<Algorithm>> {
+        Some(vec![Algorithm::HS256, Algorithm::HS384, Algorithm::HS512])
+    }
+
+    fn verify_bytes(
         &self,
-        content: String,
-        signature: String,
+        content: &[u8],
+        signature: &str,
         alg: Algorithm,
     ) -> Result<bool, crate::errors::Error> {
         match alg {
             Algorithm::HS256 => self.hmac_verify::<Sha256>(content, signature),
             Algorithm::HS384 => self.hmac_verify::<Sha384>(content, signature),
             Algorithm::HS512 => self.hmac_verify::<Sha512>(content, signature),
-            _ => Err(Error::UNKNOWN_ALGORITHM),
+            _ => Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
         }
     }
 }
 
-pub fn sign_hmac(message: String, key: impl SignFromKey, alg: Algorithm) -> Result<String, Error> {
-    key.sign(message, alg)
+pub fn sign_hmac(
+    message: impl AsRef<[u8]>,
+    key: impl SignFromKey,
+    alg: Algorithm,
+) -> Result<String, Error> {
+    key.sign_bytes(message.as_ref(), alg)
 }
 
 pub fn verify_hmac(
-    message: String,
-    signature: String,
+    message: impl AsRef<[u8]>,
+    signature: impl AsRef<str>,
     key: impl VerifyFromKey,
     alg: Algorithm,
 ) -> Result<bool, Error> {
-    key.verify(message, signature, alg)
+    key.verify_bytes(message.as_ref(), signature.as_ref(), alg)
 }
@@ -0,0 +1,287 @@
+use argon2::{Argon2, Version};
+use hkdf::Hkdf;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Sha256, Sha384, Sha512};
+use zeroize::Zeroizing;
+
+use crate::{errors::Error, jwk::Jwk, log};
+
+pub const PASSWORD_KDF_VERSION: u32 = 1;
+pub const PASSWORD_KDF_SALT_LENGTH: usize = 16;
+pub const SCRYPT_DEFAULT_LOG_N: u8 = 17;
+pub const SCRYPT_DEFAULT_R: u32 = 8;
+pub const SCRYPT_DEFAULT_P: u32 = 1;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum HashFunction {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl HashFunction {
+    pub fn output_length(&self) -> usize {
+        match self {
+            HashFunction::Sha256 => 32,
+            HashFunction::Sha384 => 48,
+            HashFunction::Sha512 => 64,
+        }
+    }
+}
+
+pub fn hkdf_extract(hash: HashFunction, salt: Option<&[u8]>, ikm: &[u8]) -> Vec<u8> {
+    match hash {
+        HashFunction::Sha256 => Hkdf::<Sha256>::extract(salt, ikm).0.to_vec(),
+        HashFunction::Sha384 => Hkdf::<Sha384>::extract(salt, ikm).0.to_vec(),
+        HashFunction::Sha512 => Hkdf::<Sha512>::extract(salt, ikm).0.to_vec(),
+    }
+}
+
+pub fn hkdf_expand(
+    hash: HashFunction,
+    prk: &[u8],
+    info: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, Error> {
+    let mut okm = vec![0u8; length];
+    let result = match hash {
+        HashFunction::Sha256 => Hkdf::<Sha256>::from_prk(prk)
+            .map_err(|error| error.to_string())
+            .and_then(|val| {
+                val.expand(info, &mut okm)
+                    .map_err(|error| error.to_string())
+            }),
+        HashFunction::Sha384 => Hkdf::<Sha384>::from_prk(prk)
+            .map_err(|error| error.to_string())
+            .and_then(|val| {
+                val.expand(info, &mut okm)
+                    .map_err(|error| error.to_string())
+            }),
+        HashFunction::Sha512 => Hkdf::<Sha512>::from_prk(prk)
+            .map_err(|error| error.to_string())
+            .and_then(|val| {
+                val.expand(info, &mut okm)
+                    .map_err(|error| error.to_string())
+            }),
+    };
+
+    match result {
+        Ok(_) => Ok(okm),
+        Err(error) => {
+            log::error(error.as_str());
+            Err(Error::KEY_DERIVATION_FAILED)
+        }
+    }
+}
+
+pub fn hkdf(
+    hash: HashFunction,
+    salt: Option<&[u8]>,
+    ikm: &[u8],
+    info: &[u8],
+    length: usize,
+) -> Result<Vec<u8>, Error> {
+    hkdf_expand(hash, &hkdf_extract(hash, salt, ikm), info, length)
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(tag = "kdf", rename_all = "lowercase")]
+pub enum PasswordKdf {
+    Argon2id {
+        salt: String,
+        m_cost: u32,
+        t_cost: u32,
+        p_cost: u32,
+    },
+    Scrypt {
+        salt: String,
+        log_n: u8,
+        r: u32,
+        p: u32,
+    },
+}
+
+impl PasswordKdf {
+    pub fn argon2id(m_cost: u32, t_cost: u32, p_cost: u32) -> Result<Self, Error> {
+        let kdf = PasswordKdf::Argon2id {
+            salt: random_salt(),
+            m_cost,
+            t_cost,
+            p_cost,
+        };
+        kdf.validate()?;
+        Ok(kdf)
+    }
+
+    pub fn scrypt(log_n: u8, r: u32, p: u32) -> Result<Self, Error> {
+        let kdf = PasswordKdf::Scrypt {
+            salt: random_salt(),
+            log_n,
+            r,
+            p,
+        };
+        kdf.validate()?;
+        Ok(kdf)
+    }
+
+    pub fn argon2id_default() -> Self {
+        let defaults = argon2::Params::default();
+        PasswordKdf::Argon2id {
+            salt: random_salt(),
+            m_cost: defaults.m_cost(),
+            t_cost: defaults.t_cost(),
+            p_cost: defaults.p_cost(),
+        }
+    }
+
+    pub fn scrypt_default() -> Self {
+        PasswordKdf::Scrypt {
+            salt: random_salt(),
+            log_n: SCRYPT_DEFAULT_LOG_N,
+            r: SCRYPT_DEFAULT_R,
+            p: SCRYPT_DEFAULT_P,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PasswordKdf::Argon2id { .. } => "argon2id",
+            PasswordKdf::Scrypt { .. } => "scrypt",
+        }
+    }
+
+    pub fn salt(&self) -> Result<Vec<u8>, Error> {
+        let salt = match self {
+            PasswordKdf::Argon2id { salt, .. } => salt,
+            PasswordKdf::Scrypt { salt, .. } => salt,
+        };
+        Jwk::decode_member(&Some(salt.clone()))
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        let salt = self.salt()?;
+        let result = match self {
+            PasswordKdf::Argon2id { .. } if salt.len() < argon2::MIN_SALT_LEN => {
+                return Err(Error::KDF_INVALID_PARAMETERS)
+            }
+            PasswordKdf::Argon2id {
+                m_cost,
+                t_cost,
+                p_cost,
+                ..
+            } => argon2::Params::new(*m_cost, *t_cost, *p_cost, None)
+                .map(|_| ())
+                .map_err(|error| error.to_string()),
+            PasswordKdf::Scrypt { log_n, r, p, .. } => {
+                scrypt::Params::new(*log_n, *r, *p, scrypt::Params::RECOMMENDED_LEN)
+                    .map(|_| ())
+                    .map_err(|error| error.to_string())
+            }
+        };
+
+        match result {
+            Ok(_) => Ok(()),
+            Err(error) => {
+                log::error(error.as_str());
+                Err(Error::KDF_INVALID_PARAMETERS)
+            }
+        }
+    }
+
+    pub fn derive_key(
+        &self,
+        passphrase: &[u8],
+        length: usize,
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
+        self.validate()?;
+        let salt = self.salt()?;
+        let mut key = Zeroizing::new(vec![0u8; length]);
+
+        let result = match self {
+            PasswordKdf::Argon2id {
+                m_cost,
+                t_cost,
+                p_cost,
+                ..
+            } => argon2::Params::new(*m_cost, *t_cost, *p_cost, Some(length))
+                .map_err(|error| error.to_string())
+                .and_then(|val| {
+                    Argon2::new(argon2::Algorithm::Argon2id, Version::V0x13, val)
+                        .hash_password_into(passphrase, &salt, key.as_mut_slice())
+                        .map_err(|error| error.to_string())
+                }),
+            PasswordKdf::Scrypt { log_n, r, p, .. } => scrypt::Params::new(*log_n, *r, *p, length)
+                .map_err(|error| error.to_string())
+                .and_then(|val| {
+                    scrypt::scrypt(passphrase, &salt, &val, key.as_mut_slice())
+                        .map_err(|error| error.to_string())
+                }),
+        };
+
+        match result {
+            Ok(_) => Ok(key),
+            Err(error) => {
+                log::error(error.as_str());
+                Err(Error::KEY_DERIVATION_FAILED)
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct PasswordKdfHeader {
+    pub version: u32,
+    #[serde(flatten)]
+    pub kdf: PasswordKdf,
+}
+
+impl PasswordKdfHeader {
+    pub fn new(kdf: PasswordKdf) -> Self {
+        PasswordKdfHeader {
+            version: PASSWORD_KDF_VERSION,
+            kdf,
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        match serde_json::to_string(self) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::KDF_INVALID_PARAMETERS.with_source(error))
+            }
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let header: PasswordKdfHeader = match serde_json::from_str(json) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::KDF_INVALID_PARAMETERS.with_source(error));
+            }
+        };
+
+        if header.version == 0 || header.version > PASSWORD_KDF_VERSION {
+            return Err(Error::KDF_UNSUPPORTED_VERSION);
+        }
+
+        header.kdf.validate()?;
+        Ok(header)
+    }
+
+    pub fn derive_key(
+        &self,
+        passphrase: &[u8],
+        length: usize,
+    ) -> Result<Zeroizing<Vec<u8>>, Error> {
+        self.kdf.derive_key(passphrase, length)
+    }
+}
+
+fn random_salt() -> String {
+    let mut salt = [0u8; PASSWORD_KDF_SALT_LENGTH];
+    rand::thread_rng().fill_bytes(&mut salt);
+    base64_url::encode(&salt)
+}
@@ -0,0 +1,161 @@
+use std::fmt;
+
+use aws_sdk_kms::{
+    primitives::Blob,
+    types::{MessageType, SigningAlgorithmSpec},
+    Client,
+};
+use elliptic_curve::{pkcs8::DecodePublicKey, sec1::ToEncodedPoint};
+use sha2::{Digest, Sha256, Sha384};
+
+use crate::{algorithms::Algorithm, errors::Error, log};
+
+#[cfg(feature = "es256k")]
+use super::ecdsa::_256k::P256kVerifyingKey;
+#[cfg(feature = "es384")]
+use super::ecdsa::_384::P384VerifyingKey;
+use super::{
+    any::AnyVerifyingKey,
+    ecdsa::{signature_from_der, _256::P256VerifyingKey},
+    AsyncSignFromKey,
+};
+
+pub struct AwsKmsSigningKey {
+    client: Client,
+    key_id: String,
+    alg: Algorithm,
+}
+
+impl AwsKmsSigningKey {
+    pub fn new(client: Client, key_id: &str, alg: Algorithm) -> Result<Self, Error> {
+        match alg {
+            Algorithm::ES256 => {}
+            #[cfg(feature = "es256k")]
+            Algorithm::ES256K => {}
+            #[cfg(feature = "es384")]
+            Algorithm::ES384 => {}
+            _ => return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
+        }
+
+        Ok(AwsKmsSigningKey {
+            client,
+            key_id: String::from(key_id),
+            alg,
+        })
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.alg
+    }
+
+    pub async fn verifying_key(&self) -> Result<AnyVerifyingKey, Error> {
+        let output = match self
+            .client
+            .get_public_key()
+            .key_id(&self.key_id)
+            .send()
+            .await
+        {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::KMS_REQUEST_FAILED.with_source(error));
+            }
+        };
+
+        let der = match output.public_key() {
+            Some(val) => val.as_ref(),
+            None => return Err(Error::KMS_REQUEST_FAILED),
+        };
+
+        let sec1_bytes = match self.alg {
+            #[cfg(feature = "es256k")]
+            Algorithm::ES256K => k256::PublicKey::from_public_key_der(der)
+                .map(|val| val.to_encoded_point(false).as_bytes().to_vec()),
+            #[cfg(feature = "es384")]
+            Algorithm::ES384 => p384::PublicKey::from_public_key_der(der)
+                .map(|val| val.to_encoded_point(false).as_bytes().to_vec()),
+            _ => p256::PublicKey::from_public_key_der(der)
+                .map(|val| val.to_encoded_point(false).as_bytes().to_vec()),
+        };
+        let sec1_bytes = match sec1_bytes {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
+            }
+        };
+
+        match self.alg {
+            #[cfg(feature = "es256k")]
+            Algorithm::ES256K => Ok(AnyVerifyingKey::P256k(P256kVerifyingKey::from_bytes(
+                &sec1_bytes,
+            )?)),
+            #[cfg(feature = "es384")]
+            Algorithm::ES384 => Ok(AnyVerifyingKey::P384(P384VerifyingKey::from_bytes(
+                &sec1_bytes,
+            )?)),
+            _ => Ok(AnyVerifyingKey::P256(P256VerifyingKey::from_bytes(
+                &sec1_bytes,
+            )?)),
+        }
+    }
+
+    pub async fn fingerprint(&self) -> Result<String, Error> {
+        self.verifying_key().await?.fingerprint()
+    }
+}
+
+impl fmt::Debug for AwsKmsSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AwsKmsSigningKey")
+            .field("key_id", &self.key_id)
+            .field("alg", &self.alg)
+            .finish_non_exhaustive()
+    }
+}
+
+impl AsyncSignFromKey for AwsKmsSigningKey {
+    async fn sign_bytes_async(&self, content: &[u8], alg: Algorithm) -> Result<String, Error> {
+        if alg != self.alg {
+            return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg));
+        }
+
+        let (digest, signing_algorithm) = match alg {
+            Algorithm::ES384 => (
+                Sha384::digest(content).to_vec(),
+                SigningAlgorithmSpec::EcdsaSha384,
+            ),
+            _ => (
+                Sha256::digest(content).to_vec(),
+                SigningAlgorithmSpec::EcdsaSha256,
+            ),
+        };
+
+        let output = match self
+            .client
+            .sign()
+            .key_id(&self.key_id)
+            .message(Blob::new(digest))
+            .message_type(MessageType::Digest)
+            .signing_algorithm(signing_algorithm)
+            .send()
+            .await
+        {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::KMS_REQUEST_FAILED.with_source(error));
+            }
+        };
+
+        match output.signature() {
+            Some(val) => signature_from_der(val.as_ref(), alg),
+            None => Err(Error::KMS_REQUEST_FAILED),
+        }
+    }
+}
@@ -0,0 +1,244 @@
+use std::fmt;
+
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use sha3::{Digest, Keccak256};
+
+use crate::{errors::Error, log};
+
+use super::{ecdsa::_256k::P256kVerifyingKey, hd};
+
+pub const ETHEREUM_CLA: u8 = 0xe0;
+pub const ETHEREUM_INS_GET_PUBLIC_KEY: u8 = 0x02;
+pub const ETHEREUM_INS_SIGN_PERSONAL_MESSAGE: u8 = 0x08;
+pub const APDU_STATUS_OK: u16 = 0x9000;
+
+const FIRST_CHUNK: u8 = 0x00;
+const SUBSEQUENT_CHUNK: u8 = 0x80;
+const MAX_APDU_DATA_LENGTH: usize = 255;
+const PERSONAL_MESSAGE_PREFIX: &str = "\x19Ethereum Signed Message:\n";
+
+pub trait LedgerTransport {
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+pub fn parse_derivation_path(path: &str) -> Result<Vec<u32>, Error> {
+    let indexes = hd::parse_derivation_path(path)?;
+    if indexes.is_empty() || indexes.len() > 10 {
+        return Err(Error::INVALID_DERIVATION_PATH);
+    }
+
+    Ok(indexes)
+}
+
+pub fn personal_message_hash(message: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    hasher.update(PERSONAL_MESSAGE_PREFIX.as_bytes());
+    hasher.update(message.len().to_string().as_bytes());
+    hasher.update(message);
+    hasher.finalize().into()
+}
+
+pub fn ethereum_address(public_key: &[u8]) -> Result<String, Error> {
+    let key = match VerifyingKey::from_sec1_bytes(public_key) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
+        }
+    };
+
+    let point = key.to_encoded_point(false);
+    let hash = Keccak256::digest(&point.as_bytes()[1..]);
+    Ok(format!("0x{}", hex::encode(&hash[12..])))
+}
+
+pub fn recover_personal_message_signer(message: &[u8], signature: &[u8]) -> Result<String, Error> {
+    if signature.len() != 65 {
+        return Err(Error::SIGNATURE_IDENTIFICATION_FAILED);
+    }
+
+    let sig = match Signature::from_slice(&signature[..64]) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_source(error));
+        }
+    };
+
+    let recovery_id = match RecoveryId::from_byte(signature[64].wrapping_sub(27)) {
+        Some(val) => val,
+        None => return Err(Error::SIGNATURE_IDENTIFICATION_FAILED),
+    };
+
+    let key = match VerifyingKey::recover_from_prehash(
+        &personal_message_hash(message),
+        &sig,
+        recovery_id,
+    ) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_source(error));
+        }
+    };
+
+    ethereum_address(key.to_encoded_point(false).as_bytes())
+}
+
+pub fn verify_personal_message(
+    message: &[u8],
+    signature: &[u8],
+    address: &str,
+) -> Result<bool, Error> {
+    Ok(recover_personal_message_signer(message, signature)?.eq_ignore_ascii_case(address))
+}
+
+pub struct LedgerEthereumSigner<T: LedgerTransport> {
+    transport: T,
+    path: Vec<u32>,
+}
+
+impl<T: LedgerTransport> LedgerEthereumSigner<T> {
+    pub fn new(transport: T, path: &str) -> Result<Self, Error> {
+        Ok(LedgerEthereumSigner {
+            transport,
+            path: parse_derivation_path(path)?,
+        })
+    }
+
+    fn encoded_path(&self) -> Vec<u8> {
+        let mut bytes = vec![self.path.len() as u8];
+        for index in &self.path {
+            bytes.extend_from_slice(&index.to_be_bytes());
+        }
+        bytes
+    }
+
+    fn send(&self, ins: u8, p1: u8, data: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut apdu = vec![ETHEREUM_CLA, ins, p1, 0x00, data.len() as u8];
+        apdu.extend_from_slice(data);
+
+        let mut response = self.transport.exchange(&apdu)?;
+        if response.len() < 2 {
+            return Err(Error::LEDGER_TRANSPORT_ERROR);
+        }
+
+        let status = response.split_off(response.len() - 2);
+        if u16::from_be_bytes([status[0], status[1]]) != APDU_STATUS_OK {
+            log::error(format!("Ledger status word {}", hex::encode(&status)).as_str());
+            return Err(Error::LEDGER_REQUEST_REJECTED);
+        }
+
+        Ok(response)
+    }
+
+    fn public_key_bytes(&self) -> Result<Vec<u8>, Error> {
+        let response = self.send(ETHEREUM_INS_GET_PUBLIC_KEY, 0x00, &self.encoded_path())?;
+        match response.first() {
+            Some(&len) if response.len() > len as usize => Ok(response[1..=len as usize].to_vec()),
+            _ => Err(Error::LEDGER_TRANSPORT_ERROR),
+        }
+    }
+
+    pub fn public_key(&self) -> Result<P256kVerifyingKey, Error> {
+        P256kVerifyingKey::from_bytes(&self.public_key_bytes()?)
+    }
+
+    pub fn verifying_key(&self) -> Result<P256kVerifyingKey, Error> {
+        self.public_key()
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.public_key()?.fingerprint()
+    }
+
+    pub fn address(&self) -> Result<String, Error> {
+        ethereum_address(&self.public_key_bytes()?)
+    }
+
+    pub fn sign_personal_message(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let mut data = self.encoded_path();
+        data.extend_from_slice(&(message.len() as u32).to_be_bytes());
+
+        let first_length = message
+            .len()
+            .min(MAX_APDU_DATA_LENGTH.saturating_sub(data.len()));
+        data.extend_from_slice(&message[..first_length]);
+
+        let mut response = self.send(ETHEREUM_INS_SIGN_PERSONAL_MESSAGE, FIRST_CHUNK, &data)?;
+        for chunk in message[first_length..].chunks(MAX_APDU_DATA_LENGTH) {
+            response = self.send(ETHEREUM_INS_SIGN_PERSONAL_MESSAGE, SUBSEQUENT_CHUNK, chunk)?;
+        }
+
+        if response.len() != 65 {
+            return Err(Error::LEDGER_TRANSPORT_ERROR);
+        }
+
+        let mut signature = response[1..].to_vec();
+        signature.push(response[0]);
+        Ok(signature)
+    }
+}
+
+impl<T: LedgerTransport> fmt::Debug for LedgerEthereumSigner<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LedgerEthereumSigner")
+            .field("path", &self.path)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "ledger-hid")]
+pub struct HidTransport {
+    transport: ledger_transport_hid::TransportNativeHID,
+}
+
+#[cfg(feature = "ledger-hid")]
+impl HidTransport {
+    pub fn new() -> Result<Self, Error> {
+        let api = match ledger_transport_hid::hidapi::HidApi::new() {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::LEDGER_TRANSPORT_ERROR.with_source(error));
+            }
+        };
+
+        match ledger_transport_hid::TransportNativeHID::new(&api) {
+            Ok(val) => Ok(HidTransport { transport: val }),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::LEDGER_TRANSPORT_ERROR.with_source(error))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ledger-hid")]
+impl LedgerTransport for HidTransport {
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, Error> {
+        if apdu.len() < 5 {
+            return Err(Error::LEDGER_TRANSPORT_ERROR);
+        }
+
+        let command = ledger_apdu::APDUCommand {
+            cla: apdu[0],
+            ins: apdu[1],
+            p1: apdu[2],
+            p2: apdu[3],
+            data: apdu[5..].to_vec(),
+        };
+
+        match self.transport.exchange(&command) {
+            Ok(val) => {
+                let mut response = val.apdu_data().to_vec();
+                response.extend_from_slice(&val.retcode().to_be_bytes());
+                Ok(response)
+            }
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::LEDGER_TRANSPORT_ERROR.with_source(error))
+            }
+        }
+    }
+}
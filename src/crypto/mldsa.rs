@@ -0,0 +1,264 @@
+use std::fmt;
+
+use mysten_mldsa_native_rs::{
+    Signature, SigningKey, SigningKeySeed, VerifyingKey, PUBLIC_KEY_LENGTH, RND_LENGTH,
+    SEED_LENGTH, SIGNATURE_LENGTH,
+};
+use pkcs8::{spki::SubjectPublicKeyInfoRef, ObjectIdentifier};
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+
+use crate::{algorithms::Algorithm, errors::Error, jwk::Jwk, log, multicodec};
+
+use super::{fmt_key, x509, SignFromKey, VerifyFromKey};
+
+pub(crate) const AKP_KEY_TYPE: &str = "AKP";
+pub(crate) const MLDSA65_SEED_LENGTH: usize = SEED_LENGTH;
+pub(crate) const MLDSA65_PUBLIC_KEY_LENGTH: usize = PUBLIC_KEY_LENGTH;
+pub(crate) const MLDSA65_SIGNATURE_LENGTH: usize = SIGNATURE_LENGTH;
+pub(crate) const MLDSA65_OID: ObjectIdentifier =
+    ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.3.18");
+
+fn check_jwk(jwk: &Jwk) -> Result<(), Error> {
+    match (jwk.kty.as_str(), jwk.alg.as_deref()) {
+        (AKP_KEY_TYPE, Some(alg)) if alg == Algorithm::MLDSA65.to_str() => Ok(()),
+        _ => Err(Error::JWK_KEY_TYPE_MISMATCH),
+    }
+}
+
+pub struct MLDSA65SigningKey {
+    seed: SigningKeySeed,
+    key: SigningKey,
+    verifying_key: Box<VerifyingKey>,
+}
+
+impl SignFromKey for MLDSA65SigningKey {
+    fn sign_bytes(&self, content: &[u8], alg: Algorithm) -> Result<String, Error> {
+        if alg != Algorithm::MLDSA65 {
+            return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg));
+        }
+
+        Ok(base64_url::encode(&self.sign_with_context(content, &[])?))
+    }
+}
+
+impl MLDSA65SigningKey {
+    pub fn generate() -> Self {
+        Self::generate_with_rng(&mut OsRng)
+    }
+
+    pub fn generate_with_rng(rng: &mut (impl CryptoRng + RngCore)) -> Self {
+        let mut seed = [0u8; SEED_LENGTH];
+        rng.fill_bytes(&mut seed);
+        Self::from_seed_bytes(seed)
+    }
+
+    fn from_seed_bytes(seed: [u8; SEED_LENGTH]) -> Self {
+        let seed = SigningKeySeed::from(seed);
+        let (key, verifying_key) = seed.expand();
+        MLDSA65SigningKey {
+            seed,
+            key,
+            verifying_key: Box::new(verifying_key),
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        match bytes.try_into() {
+            Ok(val) => Ok(Self::from_seed_bytes(val)),
+            Err(_) => Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.seed.as_bytes().to_vec()
+    }
+
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        check_jwk(jwk)?;
+        let key = Self::from_bytes(&Jwk::decode_member(&jwk.private)?)?;
+        if let Some(public) = &jwk.public {
+            if *public != base64_url::encode(key.verifying_key.as_bytes()) {
+                return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+            }
+        }
+
+        Ok(key)
+    }
+
+    pub fn to_jwk(&self) -> Jwk {
+        Jwk {
+            private: Some(base64_url::encode(&self.to_bytes())),
+            ..self.verifying_key().to_jwk()
+        }
+    }
+
+    pub fn verifying_key(&self) -> MLDSA65VerifyingKey {
+        MLDSA65VerifyingKey {
+            key: self.verifying_key.clone(),
+        }
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.verifying_key().fingerprint()
+    }
+
+    pub(crate) fn sign_with_context(
+        &self,
+        content: &[u8],
+        context: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let mut rnd = [0u8; RND_LENGTH];
+        rand::thread_rng().fill_bytes(&mut rnd);
+        match self.key.sign(content, context, &rnd) {
+            Ok(val) => Ok(val.as_bytes().to_vec()),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::SIGNING_FAILED.with_source(error))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for MLDSA65SigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(f, "MLDSA65SigningKey", "ML-DSA-65", self.fingerprint())
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct MLDSA65VerifyingKey {
+    key: Box<VerifyingKey>,
+}
+
+impl VerifyFromKey for MLDSA65VerifyingKey {
+    fn verifying_algorithms(&self) -> Option<Vec<Algorithm>> {
+        Some(vec![Algorithm::MLDSA65])
+    }
+
+    fn verify_bytes(&self, content: &[u8], sig: &str, alg: Algorithm) -> Result<bool, Error> {
+        if alg != Algorithm::MLDSA65 {
+            return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg));
+        }
+
+        let decoded_sig = match base64_url::decode(sig.as_bytes()) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::DECODING_ERROR.with_source(error));
+            }
+        };
+
+        self.verify_with_context(content, &decoded_sig, &[])
+    }
+}
+
+impl MLDSA65VerifyingKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        match VerifyingKey::from_bytes(bytes) {
+            Ok(val) => Ok(MLDSA65VerifyingKey { key: Box::new(val) }),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.key.as_bytes().to_vec()
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.to_jwk().thumbprint()
+    }
+
+    pub fn from_x509_pem(cert_str: &str) -> Result<Self, Error> {
+        Self::from_x509_der(&x509::certificate_pem_to_der(cert_str)?)
+    }
+
+    pub fn from_x509_der(cert: &[u8]) -> Result<Self, Error> {
+        let spki = x509::subject_public_key_info(cert)?;
+        let info = match SubjectPublicKeyInfoRef::try_from(spki.as_slice()) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error));
+            }
+        };
+
+        match (info.algorithm.oid, info.subject_public_key.as_bytes()) {
+            (MLDSA65_OID, Some(val)) => Self::from_bytes(val),
+            _ => Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR),
+        }
+    }
+
+    pub fn from_multicodec(bytes: &[u8]) -> Result<Self, Error> {
+        match multicodec::decode(bytes)? {
+            (multicodec::MLDSA_65_PUB, key) => Self::from_bytes(key),
+            _ => Err(Error::MULTICODEC_ERROR),
+        }
+    }
+
+    pub fn to_multicodec(&self) -> Vec<u8> {
+        multicodec::encode(multicodec::MLDSA_65_PUB, &self.to_bytes())
+    }
+
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        check_jwk(jwk)?;
+        Self::from_bytes(&Jwk::decode_member(&jwk.public)?)
+    }
+
+    pub(crate) fn verify_with_context(
+        &self,
+        content: &[u8],
+        signature: &[u8],
+        context: &[u8],
+    ) -> Result<bool, Error> {
+        let signature = match Signature::from_bytes(signature) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_source(error));
+            }
+        };
+
+        match self.key.verify(content, context, &signature) {
+            Ok(_) => Ok(true),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Ok(false)
+            }
+        }
+    }
+
+    pub fn to_jwk(&self) -> Jwk {
+        Jwk {
+            kty: String::from(AKP_KEY_TYPE),
+            alg: Some(String::from(Algorithm::MLDSA65.to_str())),
+            public: Some(base64_url::encode(&self.to_bytes())),
+            ..Default::default()
+        }
+    }
+}
+
+impl fmt::Debug for MLDSA65VerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(f, "MLDSA65VerifyingKey", "ML-DSA-65", self.fingerprint())
+    }
+}
+
+pub fn sign_mldsa(
+    message: impl AsRef<[u8]>,
+    key: impl SignFromKey,
+    alg: Algorithm,
+) -> Result<String, Error> {
+    key.sign_bytes(message.as_ref(), alg)
+}
+
+pub fn verify_mldsa(
+    message: impl AsRef<[u8]>,
+    sig: impl AsRef<str>,
+    key: impl VerifyFromKey,
+    alg: Algorithm,
+) -> Result<bool, Error> {
+    key.verify_bytes(message.as_ref(), sig.as_ref(), alg)
+}
@@ -0,0 +1,94 @@
+use std::fmt;
+
+use rand::{CryptoRng, RngCore};
+use zeroize::Zeroizing;
+
+use crate::{errors::Error, log};
+
+use super::{
+    ecdsa::_256k::P256kSigningKey,
+    eddsa::EDDSASigningKey,
+    entropy,
+    hd::{ExtendedPrivateKey, HdCurve},
+};
+
+pub struct Mnemonic {
+    inner: bip39::Mnemonic,
+}
+
+impl Mnemonic {
+    pub fn generate(word_count: usize) -> Result<Self, Error> {
+        Self::generate_with_rng(word_count, &mut entropy::rng()?)
+    }
+
+    pub fn generate_with_rng(
+        word_count: usize,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<Self, Error> {
+        if !(12..=24).contains(&word_count) || !word_count.is_multiple_of(3) {
+            return Err(Error::MNEMONIC_INVALID);
+        }
+
+        let mut entropy = Zeroizing::new(vec![0u8; word_count / 3 * 4]);
+        rng.fill_bytes(&mut entropy);
+        Self::from_entropy(&entropy)
+    }
+
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, Error> {
+        match bip39::Mnemonic::from_entropy(entropy) {
+            Ok(val) => Ok(Mnemonic { inner: val }),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::MNEMONIC_INVALID.with_source(error))
+            }
+        }
+    }
+
+    pub fn from_phrase(phrase: &str) -> Result<Self, Error> {
+        match bip39::Mnemonic::parse(phrase) {
+            Ok(val) => Ok(Mnemonic { inner: val }),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::MNEMONIC_INVALID.with_source(error))
+            }
+        }
+    }
+
+    pub fn phrase(&self) -> String {
+        self.inner.to_string()
+    }
+
+    pub fn word_count(&self) -> usize {
+        self.inner.word_count()
+    }
+
+    pub fn to_seed(&self, passphrase: &str) -> Zeroizing<[u8; 64]> {
+        Zeroizing::new(self.inner.to_seed(passphrase))
+    }
+
+    pub fn master_key(
+        &self,
+        curve: HdCurve,
+        passphrase: &str,
+    ) -> Result<ExtendedPrivateKey, Error> {
+        ExtendedPrivateKey::master(curve, self.to_seed(passphrase).as_slice())
+    }
+
+    pub fn ed25519_signing_key(&self, passphrase: &str) -> Result<EDDSASigningKey, Error> {
+        self.master_key(HdCurve::Ed25519, passphrase)?
+            .ed25519_signing_key()
+    }
+
+    pub fn secp256k1_signing_key(&self, passphrase: &str) -> Result<P256kSigningKey, Error> {
+        self.master_key(HdCurve::Secp256k1, passphrase)?
+            .secp256k1_signing_key()
+    }
+}
+
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mnemonic")
+            .field("word_count", &self.word_count())
+            .finish_non_exhaustive()
+    }
+}
@@ -1,14 +1,245 @@
-use crate::{algorithms::Algorithm, errors::Error};
+use std::{fmt, future::Future, rc::Rc, sync::Arc};
 
+use crate::{algorithms::Algorithm, errors::Error, log};
+
+use self::{shared::SharedKey, signature::Signature};
+
+pub mod any;
+pub mod attestation;
+pub mod bbs;
+pub mod bls;
+#[cfg(feature = "mldsa")]
+pub mod composite;
+pub mod constant_time;
+pub mod ecdh;
 pub mod ecdsa;
+pub mod ecies;
+#[cfg(feature = "eddsa")]
 pub mod eddsa;
+pub mod entropy;
+#[cfg(all(feature = "es256k", feature = "eddsa"))]
+pub mod frost;
+pub mod hardware;
+#[cfg(all(feature = "es256k", feature = "eddsa"))]
+pub mod hd;
 pub mod hmac;
+pub mod kdf;
+#[cfg(feature = "aws-kms")]
+pub mod kms;
+#[cfg(all(feature = "es256k", feature = "eddsa"))]
+pub mod ledger;
+#[cfg(feature = "mldsa")]
+pub mod mldsa;
+#[cfg(all(feature = "es256k", feature = "eddsa"))]
+pub mod mnemonic;
+#[cfg(feature = "nacl")]
+pub mod nacl;
+#[cfg(feature = "openpgp")]
+pub mod openpgp;
+pub mod parse;
+#[cfg(feature = "pkcs12")]
+pub mod pkcs12;
+pub mod policy;
+#[cfg(feature = "rsa")]
 pub mod rsa;
+#[cfg(feature = "serde")]
+mod serialization;
+pub mod shamir;
+pub mod shared;
+pub mod signature;
+pub mod ssh;
+pub mod x25519;
+pub mod x509;
+
+pub(crate) fn decode_hex(value: &str) -> Result<Vec<u8>, Error> {
+    let value = value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+        .unwrap_or(value);
+
+    match hex::decode(value) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::DECODING_ERROR.with_source(error))
+        }
+    }
+}
+
+pub(crate) fn encode_hex_prefixed(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+pub(crate) fn fmt_key(
+    f: &mut fmt::Formatter<'_>,
+    name: &str,
+    curve: &str,
+    fingerprint: Result<String, Error>,
+) -> fmt::Result {
+    let mut debug = f.debug_struct(name);
+    debug.field("curve", &curve);
+    if let Ok(fingerprint) = fingerprint {
+        debug.field("fingerprint", &fingerprint);
+    }
+    debug.finish_non_exhaustive()
+}
 
 pub trait SignFromKey {
-    fn sign(&self, content: String, alg: Algorithm) -> Result<String, Error>;
+    #[deprecated(note = "use `sign_bytes`, which borrows the content")]
+    fn sign(&self, content: String, alg: Algorithm) -> Result<String, Error> {
+        self.sign_bytes(content.as_bytes(), alg)
+    }
+
+    fn sign_bytes(&self, content: &[u8], alg: Algorithm) -> Result<String, Error>;
+
+    fn sign_signature(&self, content: &[u8], alg: Algorithm) -> Result<Signature, Error> {
+        Signature::from_base64url(&self.sign_bytes(content, alg)?, alg)
+    }
+
+    fn sign_batch(&self, items: &[&[u8]], alg: Algorithm) -> Result<Vec<Signature>, Error> {
+        items
+            .iter()
+            .map(|content| self.sign_signature(content, alg))
+            .collect()
+    }
+
+    fn key_fingerprint(&self) -> Option<String> {
+        None
+    }
 }
 
 pub trait VerifyFromKey {
-    fn verify(&self, content: String, signature: String, alg: Algorithm) -> Result<bool, Error>;
+    #[deprecated(note = "use `verify_bytes`, which borrows the content and signature")]
+    fn verify(&self, content: String, signature: String, alg: Algorithm) -> Result<bool, Error> {
+        self.verify_bytes(content.as_bytes(), &signature, alg)
+    }
+
+    fn verify_bytes(&self, content: &[u8], signature: &str, alg: Algorithm) -> Result<bool, Error>;
+
+    fn verify_signature(&self, content: &[u8], signature: &Signature) -> Result<bool, Error> {
+        self.verify_bytes(content, &signature.to_base64url(), signature.algorithm())
+    }
+
+    fn verify_batch(&self, items: &[(&[u8], &Signature)]) -> Vec<Result<bool, Error>> {
+        items
+            .iter()
+            .map(|(content, signature)| self.verify_signature(content, signature))
+            .collect()
+    }
+
+    fn verifying_algorithms(&self) -> Option<Vec<Algorithm>> {
+        None
+    }
+
+    fn key_fingerprint(&self) -> Option<String> {
+        None
+    }
 }
+
+pub trait AsyncSignFromKey: Sync {
+    #[deprecated(note = "use `sign_bytes_async`, which borrows the content")]
+    fn sign_async(
+        &self,
+        content: String,
+        alg: Algorithm,
+    ) -> impl Future<Output = Result<String, Error>> + Send {
+        async move { self.sign_bytes_async(content.as_bytes(), alg).await }
+    }
+
+    fn sign_bytes_async(
+        &self,
+        content: &[u8],
+        alg: Algorithm,
+    ) -> impl Future<Output = Result<String, Error>> + Send;
+}
+
+pub trait AsyncVerifyFromKey: Sync {
+    #[deprecated(note = "use `verify_bytes_async`, which borrows the content and signature")]
+    fn verify_async(
+        &self,
+        content: String,
+        signature: String,
+        alg: Algorithm,
+    ) -> impl Future<Output = Result<bool, Error>> + Send {
+        async move {
+            self.verify_bytes_async(content.as_bytes(), &signature, alg)
+                .await
+        }
+    }
+
+    fn verify_bytes_async(
+        &self,
+        content: &[u8],
+        signature: &str,
+        alg: Algorithm,
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+}
+
+impl<T: SignFromKey + Sync + ?Sized> AsyncSignFromKey for T {
+    async fn sign_bytes_async(&self, content: &[u8], alg: Algorithm) -> Result<String, Error> {
+        self.sign_bytes(content, alg)
+    }
+}
+
+impl<T: VerifyFromKey + Sync + ?Sized> AsyncVerifyFromKey for T {
+    async fn verify_bytes_async(
+        &self,
+        content: &[u8],
+        signature: &str,
+        alg: Algorithm,
+    ) -> Result<bool, Error> {
+        self.verify_bytes(content, signature, alg)
+    }
+}
+
+macro_rules! delegate_key_traits {
+    ($($pointer:ty),*) => {
+        $(
+            impl<T: SignFromKey + ?Sized> SignFromKey for $pointer {
+                fn sign_bytes(&self, content: &[u8], alg: Algorithm) -> Result<String, Error> {
+                    (**self).sign_bytes(content, alg)
+                }
+
+                fn sign_batch(
+                    &self,
+                    items: &[&[u8]],
+                    alg: Algorithm,
+                ) -> Result<Vec<Signature>, Error> {
+                    (**self).sign_batch(items, alg)
+                }
+
+                fn key_fingerprint(&self) -> Option<String> {
+                    (**self).key_fingerprint()
+                }
+            }
+
+            impl<T: VerifyFromKey + ?Sized> VerifyFromKey for $pointer {
+                fn verify_bytes(
+                    &self,
+                    content: &[u8],
+                    signature: &str,
+                    alg: Algorithm,
+                ) -> Result<bool, Error> {
+                    (**self).verify_bytes(content, signature, alg)
+                }
+
+                fn verify_batch(
+                    &self,
+                    items: &[(&[u8], &Signature)],
+                ) -> Vec<Result<bool, Error>> {
+                    (**self).verify_batch(items)
+                }
+
+                fn verifying_algorithms(&self) -> Option<Vec<Algorithm>> {
+                    (**self).verifying_algorithms()
+                }
+
+                fn key_fingerprint(&self) -> Option<String> {
+                    (**self).key_fingerprint()
+                }
+            }
+        )*
+    };
+}
+
+delegate_key_traits!(&T, Box<T>, Rc<T>, Arc<T>, SharedKey<T>);
@@ -0,0 +1,186 @@
+use crypto_box::{
+    aead::{Aead, AeadCore},
+    ChaChaBox, Nonce, PublicKey, SalsaBox, SecretKey,
+};
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+
+use crate::{errors::Error, log, metrics::Operation, trace};
+
+use super::x25519::{X25519PublicKey, X25519SecretKey};
+
+pub const NACL_NONCE_LENGTH: usize = 24;
+pub const NACL_TAG_LENGTH: usize = 16;
+pub const SEALED_BOX_OVERHEAD: usize = 32 + NACL_TAG_LENGTH;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BoxCipher {
+    #[default]
+    XSalsa20Poly1305,
+    XChaCha20Poly1305,
+}
+
+impl BoxCipher {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            BoxCipher::XSalsa20Poly1305 => "XSalsa20Poly1305",
+            BoxCipher::XChaCha20Poly1305 => "XChaCha20Poly1305",
+        }
+    }
+}
+
+enum CryptoBox {
+    Salsa(SalsaBox),
+    ChaCha(ChaChaBox),
+}
+
+impl CryptoBox {
+    fn new(
+        cipher: BoxCipher,
+        public_key: &X25519PublicKey,
+        secret_key: &X25519SecretKey,
+    ) -> Result<Self, Error> {
+        secret_key.diffie_hellman(public_key)?;
+        let public_key = PublicKey::from_bytes(public_key.to_bytes());
+        let secret_key = SecretKey::from_bytes(secret_key.to_bytes());
+        Ok(match cipher {
+            BoxCipher::XSalsa20Poly1305 => {
+                CryptoBox::Salsa(SalsaBox::new(&public_key, &secret_key))
+            }
+            BoxCipher::XChaCha20Poly1305 => {
+                CryptoBox::ChaCha(ChaChaBox::new(&public_key, &secret_key))
+            }
+        })
+    }
+
+    fn encrypt(&self, nonce: &Nonce, plaintext: &[u8]) -> Result<Vec<u8>, crypto_box::aead::Error> {
+        match self {
+            CryptoBox::Salsa(val) => val.encrypt(nonce, plaintext),
+            CryptoBox::ChaCha(val) => val.encrypt(nonce, plaintext),
+        }
+    }
+
+    fn decrypt(
+        &self,
+        nonce: &Nonce,
+        ciphertext: &[u8],
+    ) -> Result<Vec<u8>, crypto_box::aead::Error> {
+        match self {
+            CryptoBox::Salsa(val) => val.decrypt(nonce, ciphertext),
+            CryptoBox::ChaCha(val) => val.decrypt(nonce, ciphertext),
+        }
+    }
+}
+
+fn nonce(bytes: &[u8]) -> Result<&Nonce, Error> {
+    match bytes.len() {
+        NACL_NONCE_LENGTH => Ok(Nonce::from_slice(bytes)),
+        _ => Err(Error::NACL_MALFORMED),
+    }
+}
+
+pub fn generate_nonce() -> [u8; NACL_NONCE_LENGTH] {
+    generate_nonce_with_rng(&mut OsRng)
+}
+
+pub fn generate_nonce_with_rng(rng: &mut (impl CryptoRng + RngCore)) -> [u8; NACL_NONCE_LENGTH] {
+    SalsaBox::generate_nonce(rng).into()
+}
+
+pub fn box_encrypt(
+    cipher: BoxCipher,
+    plaintext: &[u8],
+    nonce_bytes: &[u8],
+    recipient: &X25519PublicKey,
+    sender: &X25519SecretKey,
+) -> Result<Vec<u8>, Error> {
+    trace::traced(
+        Operation::Encrypt,
+        Some(cipher.to_str()),
+        || recipient.fingerprint().ok(),
+        || match CryptoBox::new(cipher, recipient, sender)?.encrypt(nonce(nonce_bytes)?, plaintext)
+        {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::NACL_ENCRYPTION_FAILED.with_source(error.to_string()))
+            }
+        },
+    )
+}
+
+pub fn box_decrypt(
+    cipher: BoxCipher,
+    ciphertext: &[u8],
+    nonce_bytes: &[u8],
+    sender: &X25519PublicKey,
+    recipient: &X25519SecretKey,
+) -> Result<Vec<u8>, Error> {
+    trace::traced(
+        Operation::Decrypt,
+        Some(cipher.to_str()),
+        || recipient.fingerprint().ok(),
+        || {
+            if ciphertext.len() < NACL_TAG_LENGTH {
+                return Err(Error::NACL_MALFORMED);
+            }
+
+            match CryptoBox::new(cipher, sender, recipient)?
+                .decrypt(nonce(nonce_bytes)?, ciphertext)
+            {
+                Ok(val) => Ok(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    Err(Error::NACL_DECRYPTION_FAILED.with_source(error.to_string()))
+                }
+            }
+        },
+    )
+}
+
+pub fn seal(plaintext: &[u8], recipient: &X25519PublicKey) -> Result<Vec<u8>, Error> {
+    seal_with_rng(plaintext, recipient, &mut OsRng)
+}
+
+pub fn seal_with_rng(
+    plaintext: &[u8],
+    recipient: &X25519PublicKey,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<Vec<u8>, Error> {
+    trace::traced(
+        Operation::Encrypt,
+        Some(BoxCipher::XSalsa20Poly1305.to_str()),
+        || recipient.fingerprint().ok(),
+        || {
+            X25519SecretKey::generate_with_rng(rng).diffie_hellman(recipient)?;
+            match PublicKey::from_bytes(recipient.to_bytes()).seal(rng, plaintext) {
+                Ok(val) => Ok(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    Err(Error::NACL_ENCRYPTION_FAILED.with_source(error.to_string()))
+                }
+            }
+        },
+    )
+}
+
+pub fn unseal(ciphertext: &[u8], recipient: &X25519SecretKey) -> Result<Vec<u8>, Error> {
+    trace::traced(
+        Operation::Decrypt,
+        Some(BoxCipher::XSalsa20Poly1305.to_str()),
+        || recipient.fingerprint().ok(),
+        || {
+            if ciphertext.len() < SEALED_BOX_OVERHEAD {
+                return Err(Error::NACL_MALFORMED);
+            }
+
+            recipient.diffie_hellman(&X25519PublicKey::from_bytes(&ciphertext[..32])?)?;
+            match SecretKey::from_bytes(recipient.to_bytes()).unseal(ciphertext) {
+                Ok(val) => Ok(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    Err(Error::NACL_DECRYPTION_FAILED.with_source(error.to_string()))
+                }
+            }
+        },
+    )
+}
@@ -0,0 +1,479 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, Utc};
+use sha1::{Digest, Sha1};
+use zeroize::Zeroizing;
+
+use crate::{
+    algorithms::Algorithm,
+    errors::Error,
+    log,
+    verification_method::{VerificationMethod, VerificationMethodType},
+};
+
+use super::any::{AnySigningKey, AnyVerifyingKey};
+#[cfg(feature = "rsa")]
+use super::rsa::{RsaSigningKey, RsaVerifyingKey};
+
+pub const ARMOR_PUBLIC_KEY: &str = "PGP PUBLIC KEY BLOCK";
+pub const ARMOR_PRIVATE_KEY: &str = "PGP PRIVATE KEY BLOCK";
+
+const TAG_SECRET_KEY: u8 = 5;
+const TAG_PUBLIC_KEY: u8 = 6;
+const TAG_SECRET_SUBKEY: u8 = 7;
+const TAG_USER_ID: u8 = 13;
+const TAG_PUBLIC_SUBKEY: u8 = 14;
+
+#[cfg(feature = "rsa")]
+const ALG_RSA: u8 = 1;
+#[cfg(feature = "rsa")]
+const ALG_RSA_SIGN: u8 = 3;
+const ALG_ECDSA: u8 = 19;
+#[cfg(feature = "eddsa")]
+const ALG_EDDSA_LEGACY: u8 = 22;
+const ALG_ED25519: u8 = 27;
+
+#[cfg(feature = "eddsa")]
+const OID_ED25519_LEGACY: &[u8] = &[0x2B, 0x06, 0x01, 0x04, 0x01, 0xDA, 0x47, 0x0F, 0x01];
+const OID_P256: &[u8] = &[0x2A, 0x86, 0x48, 0xCE, 0x3D, 0x03, 0x01, 0x07];
+#[cfg(feature = "es384")]
+const OID_P384: &[u8] = &[0x2B, 0x81, 0x04, 0x00, 0x22];
+#[cfg(feature = "es512")]
+const OID_P521: &[u8] = &[0x2B, 0x81, 0x04, 0x00, 0x23];
+#[cfg(feature = "es256k")]
+const OID_SECP256K1: &[u8] = &[0x2B, 0x81, 0x04, 0x00, 0x0A];
+
+const CRC24_INIT: u32 = 0xB704CE;
+const CRC24_POLY: u32 = 0x1864CFB;
+
+#[derive(Debug)]
+pub struct OpenPgpKey {
+    pub fingerprint: String,
+    pub key_id: String,
+    pub created: DateTime<Utc>,
+    pub subkey: bool,
+    pub key: AnyVerifyingKey,
+    secret: Option<AnySigningKey>,
+}
+
+impl OpenPgpKey {
+    pub fn signing_key(&self) -> Option<&AnySigningKey> {
+        self.secret.as_ref()
+    }
+
+    pub fn to_verification_method(&self, did: &str) -> Result<VerificationMethod, Error> {
+        VerificationMethod::new(
+            &format!("{}#{}", did, self.fingerprint),
+            did,
+            VerificationMethodType::preferred_for(&self.key),
+            self.key.clone(),
+        )
+    }
+}
+
+#[derive(Debug)]
+pub struct OpenPgpCertificate {
+    user_ids: Vec<String>,
+    keys: Vec<OpenPgpKey>,
+}
+
+impl OpenPgpCertificate {
+    pub fn from_armored(data: &str) -> Result<Self, Error> {
+        Self::from_bytes(&dearmor(data)?)
+    }
+
+    pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        let mut certificates = Self::parse_keyring(data)?;
+        match certificates.len() {
+            1 => Ok(certificates.remove(0)),
+            _ => Err(Error::OPENPGP_MALFORMED),
+        }
+    }
+
+    pub fn keyring_from_armored(data: &str) -> Result<Vec<Self>, Error> {
+        Self::parse_keyring(&dearmor(data)?)
+    }
+
+    pub fn parse_keyring(data: &[u8]) -> Result<Vec<Self>, Error> {
+        let mut certificates: Vec<(Vec<String>, Vec<OpenPgpKey>)> = Vec::new();
+        for (tag, body) in packets(data)? {
+            match tag {
+                TAG_PUBLIC_KEY | TAG_SECRET_KEY => certificates.push((Vec::new(), Vec::new())),
+                _ if certificates.is_empty() => return Err(Error::OPENPGP_MALFORMED),
+                _ => {}
+            }
+
+            let (user_ids, keys) = match certificates.last_mut() {
+                Some(val) => val,
+                None => return Err(Error::OPENPGP_MALFORMED),
+            };
+            match tag {
+                TAG_USER_ID => user_ids.push(String::from_utf8_lossy(body).into_owned()),
+                TAG_PUBLIC_KEY | TAG_PUBLIC_SUBKEY | TAG_SECRET_KEY | TAG_SECRET_SUBKEY => {
+                    if let Some(key) = parse_key(tag, body)? {
+                        keys.push(key);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if certificates.is_empty() {
+            return Err(Error::OPENPGP_MALFORMED);
+        }
+
+        certificates
+            .into_iter()
+            .map(|(user_ids, keys)| match keys.is_empty() {
+                true => Err(Error::OPENPGP_UNSUPPORTED_KEY),
+                false => Ok(OpenPgpCertificate { user_ids, keys }),
+            })
+            .collect()
+    }
+
+    pub fn user_ids(&self) -> &[String] {
+        &self.user_ids
+    }
+
+    pub fn keys(&self) -> &[OpenPgpKey] {
+        &self.keys
+    }
+
+    pub fn key(&self, id: &str) -> Option<&OpenPgpKey> {
+        self.keys.iter().find(|key| {
+            key.fingerprint.eq_ignore_ascii_case(id) || key.key_id.eq_ignore_ascii_case(id)
+        })
+    }
+
+    pub fn to_verification_methods(&self, did: &str) -> Result<Vec<VerificationMethod>, Error> {
+        self.keys
+            .iter()
+            .map(|key| key.to_verification_method(did))
+            .collect()
+    }
+}
+
+fn crc24(data: &[u8]) -> u32 {
+    let mut crc = CRC24_INIT;
+    for byte in data {
+        crc ^= (*byte as u32) << 16;
+        for _ in 0..8 {
+            crc <<= 1;
+            if crc & 0x1000000 != 0 {
+                crc ^= CRC24_POLY;
+            }
+        }
+    }
+    crc & 0xFFFFFF
+}
+
+pub fn dearmor(data: &str) -> Result<Vec<u8>, Error> {
+    let mut lines = data
+        .lines()
+        .map(str::trim)
+        .skip_while(|line| line.is_empty());
+    match lines.next() {
+        Some(line)
+            if line == format!("-----BEGIN {}-----", ARMOR_PUBLIC_KEY)
+                || line == format!("-----BEGIN {}-----", ARMOR_PRIVATE_KEY) => {}
+        _ => return Err(Error::OPENPGP_MALFORMED),
+    }
+
+    let mut lines = lines.skip_while(|line| !line.is_empty() && line.contains(": "));
+    let mut body = String::new();
+    let mut checksum = None;
+    let mut terminated = false;
+    for line in lines.by_ref() {
+        if line.starts_with("-----END ") {
+            terminated = true;
+            break;
+        } else if let Some(val) = line.strip_prefix('=') {
+            checksum = Some(String::from(val));
+        } else {
+            body.push_str(line);
+        }
+    }
+
+    if !terminated {
+        return Err(Error::OPENPGP_MALFORMED);
+    }
+
+    let decode = |value: &str| match STANDARD.decode(value) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::OPENPGP_MALFORMED.with_source(error))
+        }
+    };
+
+    let bytes = decode(&body)?;
+    if let Some(checksum) = checksum {
+        let crc = crc24(&bytes).to_be_bytes();
+        if decode(&checksum)? != crc[1..] {
+            return Err(Error::OPENPGP_MALFORMED);
+        }
+    }
+
+    Ok(bytes)
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Reader { data, position: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.position >= self.data.len()
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        match self
+            .data
+            .get(self.position..self.position.saturating_add(len))
+        {
+            Some(val) => {
+                self.position += len;
+                Ok(val)
+            }
+            None => Err(Error::OPENPGP_MALFORMED),
+        }
+    }
+
+    fn rest(&mut self) -> &'a [u8] {
+        let rest = &self.data[self.position.min(self.data.len())..];
+        self.position = self.data.len();
+        rest
+    }
+
+    fn byte(&mut self) -> Result<u8, Error> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn uint(&mut self, len: usize) -> Result<u32, Error> {
+        Ok(self
+            .take(len)?
+            .iter()
+            .fold(0u32, |acc, byte| (acc << 8) | *byte as u32))
+    }
+
+    fn mpi(&mut self) -> Result<&'a [u8], Error> {
+        let bits = self.uint(2)? as usize;
+        self.take(bits.div_ceil(8))
+    }
+}
+
+fn packets(data: &[u8]) -> Result<Vec<(u8, &[u8])>, Error> {
+    let mut reader = Reader::new(data);
+    let mut packets = Vec::new();
+    while !reader.is_empty() {
+        let header = reader.byte()?;
+        if header & 0x80 == 0 {
+            return Err(Error::OPENPGP_MALFORMED);
+        }
+
+        let (tag, len) = match header & 0x40 {
+            0 => match header & 0x03 {
+                3 => ((header >> 2) & 0x0F, None),
+                val => ((header >> 2) & 0x0F, Some(reader.uint(1 << val)? as usize)),
+            },
+            _ => {
+                let len = match reader.byte()? {
+                    val @ 0..=191 => val as usize,
+                    val @ 192..=223 => ((val as usize - 192) << 8) + reader.byte()? as usize + 192,
+                    255 => reader.uint(4)? as usize,
+                    _ => return Err(Error::OPENPGP_MALFORMED),
+                };
+                (header & 0x3F, Some(len))
+            }
+        };
+
+        let body = match len {
+            Some(len) => reader.take(len)?,
+            None => reader.rest(),
+        };
+        packets.push((tag, body));
+    }
+
+    Ok(packets)
+}
+
+fn curve_algorithm(oid: &[u8]) -> Option<Algorithm> {
+    match oid {
+        OID_P256 => Some(Algorithm::ES256),
+        #[cfg(feature = "es384")]
+        OID_P384 => Some(Algorithm::ES384),
+        #[cfg(feature = "es512")]
+        OID_P521 => Some(Algorithm::ES512),
+        #[cfg(feature = "es256k")]
+        OID_SECP256K1 => Some(Algorithm::ES256K),
+        _ => None,
+    }
+}
+
+fn scalar_len(alg: Algorithm) -> usize {
+    match alg {
+        Algorithm::ES384 => 48,
+        Algorithm::ES512 => 66,
+        _ => 32,
+    }
+}
+
+fn left_pad(bytes: &[u8], len: usize) -> Result<Zeroizing<Vec<u8>>, Error> {
+    if bytes.len() > len {
+        return Err(Error::OPENPGP_MALFORMED);
+    }
+
+    let mut padded = Zeroizing::new(vec![0u8; len]);
+    padded[len - bytes.len()..].copy_from_slice(bytes);
+    Ok(padded)
+}
+
+enum PublicMaterial<'a> {
+    #[cfg(feature = "rsa")]
+    Rsa(&'a [u8], &'a [u8]),
+    Curve(Algorithm, &'a [u8]),
+}
+
+fn parse_public<'a>(alg: u8, reader: &mut Reader<'a>) -> Result<Option<PublicMaterial<'a>>, Error> {
+    match alg {
+        #[cfg(feature = "rsa")]
+        ALG_RSA | ALG_RSA_SIGN => Ok(Some(PublicMaterial::Rsa(reader.mpi()?, reader.mpi()?))),
+        ALG_ECDSA => {
+            let oid_len = reader.byte()? as usize;
+            let oid = reader.take(oid_len)?;
+            let point = reader.mpi()?;
+            Ok(curve_algorithm(oid).map(|alg| PublicMaterial::Curve(alg, point)))
+        }
+        #[cfg(feature = "eddsa")]
+        ALG_EDDSA_LEGACY => {
+            let oid_len = reader.byte()? as usize;
+            let oid = reader.take(oid_len)?;
+            let point = reader.mpi()?;
+            match (oid, point.split_first()) {
+                (OID_ED25519_LEGACY, Some((0x40, point))) => {
+                    Ok(Some(PublicMaterial::Curve(Algorithm::EdDSA, point)))
+                }
+                (OID_ED25519_LEGACY, _) => Err(Error::OPENPGP_MALFORMED),
+                _ => Ok(None),
+            }
+        }
+        #[cfg(feature = "eddsa")]
+        ALG_ED25519 => Ok(Some(PublicMaterial::Curve(
+            Algorithm::EdDSA,
+            reader.take(32)?,
+        ))),
+        _ => Ok(None),
+    }
+}
+
+fn verifying_key(material: &PublicMaterial) -> Result<AnyVerifyingKey, Error> {
+    match material {
+        #[cfg(feature = "rsa")]
+        PublicMaterial::Rsa(n, e) => Ok(AnyVerifyingKey::Rsa(
+            RsaVerifyingKey::from_components(
+                rsa::BigUint::from_bytes_be(n),
+                rsa::BigUint::from_bytes_be(e),
+            )?,
+            Algorithm::RS256,
+        )),
+        PublicMaterial::Curve(alg, point) => AnyVerifyingKey::from_bytes(*alg, point),
+    }
+}
+
+fn signing_key(
+    alg: u8,
+    material: &PublicMaterial,
+    reader: &mut Reader,
+) -> Result<AnySigningKey, Error> {
+    if reader.byte()? != 0 {
+        return Err(Error::OPENPGP_ENCRYPTED_KEY);
+    }
+
+    let start = reader.position;
+    let key = match material {
+        #[cfg(feature = "rsa")]
+        PublicMaterial::Rsa(n, e) => {
+            let d = rsa::BigUint::from_bytes_be(reader.mpi()?);
+            let p = rsa::BigUint::from_bytes_be(reader.mpi()?);
+            let q = rsa::BigUint::from_bytes_be(reader.mpi()?);
+            reader.mpi()?;
+            AnySigningKey::Rsa(
+                RsaSigningKey::from_components(
+                    rsa::BigUint::from_bytes_be(n),
+                    rsa::BigUint::from_bytes_be(e),
+                    d,
+                    p,
+                    q,
+                )?,
+                Algorithm::RS256,
+            )
+        }
+        PublicMaterial::Curve(key_alg, _) => {
+            let secret = match alg {
+                ALG_ED25519 => reader.take(32)?,
+                _ => reader.mpi()?,
+            };
+            AnySigningKey::from_bytes(*key_alg, &left_pad(secret, scalar_len(*key_alg))?)?
+        }
+    };
+
+    let sum = reader.data[start..reader.position]
+        .iter()
+        .fold(0u16, |acc, byte| acc.wrapping_add(*byte as u16));
+    if reader.uint(2)? as u16 != sum {
+        return Err(Error::OPENPGP_MALFORMED);
+    }
+
+    Ok(key)
+}
+
+fn parse_key(tag: u8, body: &[u8]) -> Result<Option<OpenPgpKey>, Error> {
+    let mut reader = Reader::new(body);
+    if reader.byte()? != 4 {
+        return Err(Error::OPENPGP_UNSUPPORTED_KEY);
+    }
+
+    let created = reader.uint(4)?;
+    let alg = reader.byte()?;
+    let material = match parse_public(alg, &mut reader)? {
+        Some(val) => val,
+        None => return Ok(None),
+    };
+    let public = &body[..reader.position];
+
+    let mut hasher = Sha1::new();
+    hasher.update([0x99]);
+    hasher.update((public.len() as u16).to_be_bytes());
+    hasher.update(public);
+    let fingerprint = hex::encode_upper(hasher.finalize());
+
+    let key = verifying_key(&material)?;
+    let secret = match tag {
+        TAG_SECRET_KEY | TAG_SECRET_SUBKEY => {
+            let secret = signing_key(alg, &material, &mut reader)?;
+            if secret.verifying_key() != key {
+                return Err(Error::OPENPGP_MALFORMED);
+            }
+            Some(secret)
+        }
+        _ => None,
+    };
+
+    let created = match DateTime::from_timestamp(created as i64, 0) {
+        Some(val) => val,
+        None => return Err(Error::OPENPGP_MALFORMED),
+    };
+
+    Ok(Some(OpenPgpKey {
+        key_id: String::from(&fingerprint[24..]),
+        fingerprint,
+        created,
+        subkey: matches!(tag, TAG_PUBLIC_SUBKEY | TAG_SECRET_SUBKEY),
+        key,
+        secret,
+    }))
+}
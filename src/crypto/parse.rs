@@ -0,0 +1,241 @@
+use pkcs8::SecretDocument;
+
+#[cfg(feature = "rsa")]
+use super::rsa::{RsaSigningKey, RsaVerifyingKey};
+use super::{
+    any::{AnySigningKey, AnyVerifyingKey},
+    decode_hex,
+};
+use crate::{
+    algorithms::Algorithm,
+    encoding::{base58_decode, multibase_decode, MULTIBASE_BASE58BTC},
+    errors::Error,
+    jwk::Jwk,
+    log,
+};
+
+const PEM_PREFIX: &str = "-----BEGIN ";
+const PRIVATE_KEY_LABEL: &str = "PRIVATE KEY";
+const EC_PRIVATE_KEY_LABEL: &str = "EC PRIVATE KEY";
+const PUBLIC_KEY_LABEL: &str = "PUBLIC KEY";
+const CERTIFICATE_LABEL: &str = "CERTIFICATE";
+#[cfg(feature = "rsa")]
+const RSA_PRIVATE_KEY_LABEL: &str = "RSA PRIVATE KEY";
+#[cfg(feature = "rsa")]
+const RSA_PUBLIC_KEY_LABEL: &str = "RSA PUBLIC KEY";
+const DER_SEQUENCE_TAG: u8 = 0x30;
+
+fn as_text(input: &[u8]) -> Option<&str> {
+    match std::str::from_utf8(input) {
+        Ok(val)
+            if val
+                .chars()
+                .all(|c| c.is_ascii_graphic() || c.is_ascii_whitespace()) =>
+        {
+            Some(val.trim())
+        }
+        _ => None,
+    }
+}
+
+fn decode_pem(text: &str) -> Result<(&str, SecretDocument), Error> {
+    match SecretDocument::from_pem(text) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::KEY_FORMAT_UNRECOGNIZED.with_source(error))
+        }
+    }
+}
+
+fn decode_jwk(text: &str) -> Result<Jwk, Error> {
+    match serde_json::from_str(text) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::KEY_FORMAT_UNRECOGNIZED.with_source(error))
+        }
+    }
+}
+
+fn is_hex(text: &str) -> bool {
+    let text = text
+        .strip_prefix("0x")
+        .or_else(|| text.strip_prefix("0X"))
+        .unwrap_or(text);
+
+    !text.is_empty() && text.len().is_multiple_of(2) && text.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn decode_text(text: &str) -> Result<Vec<u8>, Error> {
+    let bytes = match is_hex(text) {
+        true => decode_hex(text),
+        false => base58_decode(text),
+    };
+
+    match bytes {
+        Ok(val) => Ok(val),
+        Err(error) => Err(Error::KEY_FORMAT_UNRECOGNIZED.with_source(error)),
+    }
+}
+
+fn multibase_bytes(text: &str) -> Option<Vec<u8>> {
+    match text.starts_with(MULTIBASE_BASE58BTC) {
+        true => multibase_decode(text).ok(),
+        false => None,
+    }
+}
+
+fn unique<T>(candidates: Vec<T>) -> Result<T, Error> {
+    let mut candidates = candidates.into_iter();
+    match (candidates.next(), candidates.next()) {
+        (Some(val), None) => Ok(val),
+        (Some(_), Some(_)) => Err(Error::KEY_FORMAT_AMBIGUOUS),
+        (None, _) => Err(Error::KEY_FORMAT_UNRECOGNIZED),
+    }
+}
+
+fn raw_signing_algorithms(length: usize) -> Vec<Algorithm> {
+    match length {
+        32 => vec![
+            Algorithm::ES256,
+            #[cfg(feature = "es256k")]
+            Algorithm::ES256K,
+            #[cfg(feature = "eddsa")]
+            Algorithm::EdDSA,
+        ],
+        #[cfg(feature = "es384")]
+        48 => vec![Algorithm::ES384],
+        #[cfg(feature = "es512")]
+        66 => vec![Algorithm::ES512],
+        _ => vec![],
+    }
+}
+
+fn raw_verifying_algorithms(length: usize) -> Vec<Algorithm> {
+    match length {
+        33 | 65 => vec![
+            Algorithm::ES256,
+            #[cfg(feature = "es256k")]
+            Algorithm::ES256K,
+        ],
+        #[cfg(feature = "eddsa")]
+        32 => vec![Algorithm::EdDSA],
+        #[cfg(feature = "es384")]
+        49 | 97 => vec![Algorithm::ES384],
+        #[cfg(feature = "es512")]
+        67 | 133 => vec![Algorithm::ES512],
+        _ => vec![],
+    }
+}
+
+fn signing_key_from_binary(bytes: &[u8]) -> Result<AnySigningKey, Error> {
+    if bytes.first() == Some(&DER_SEQUENCE_TAG) {
+        if let Ok(val) = AnySigningKey::from_pkcs8_der(bytes) {
+            return Ok(val);
+        }
+        if let Ok(val) = AnySigningKey::from_sec1_der(bytes) {
+            return Ok(val);
+        }
+    }
+
+    #[cfg(feature = "eddsa")]
+    if bytes.len() == crate::solana::KEYPAIR_LENGTH {
+        if let Ok(val) = crate::solana::keypair_from_bytes(bytes) {
+            return Ok(AnySigningKey::EdDSA(val));
+        }
+    }
+
+    unique(
+        raw_signing_algorithms(bytes.len())
+            .into_iter()
+            .filter_map(|alg| AnySigningKey::from_bytes(alg, bytes).ok())
+            .collect(),
+    )
+}
+
+fn verifying_key_from_binary(bytes: &[u8]) -> Result<AnyVerifyingKey, Error> {
+    if bytes.first() == Some(&DER_SEQUENCE_TAG) {
+        if let Ok(val) = AnyVerifyingKey::from_spki_der(bytes) {
+            return Ok(val);
+        }
+        if let Ok(val) = AnyVerifyingKey::from_x509_der(bytes) {
+            return Ok(val);
+        }
+    }
+
+    unique(
+        raw_verifying_algorithms(bytes.len())
+            .into_iter()
+            .filter_map(|alg| AnyVerifyingKey::from_bytes(alg, bytes).ok())
+            .collect(),
+    )
+}
+
+pub fn parse_signing_key(input: impl AsRef<[u8]>) -> Result<AnySigningKey, Error> {
+    let input = input.as_ref();
+    let text = match as_text(input) {
+        Some(val) => val,
+        None => return signing_key_from_binary(input),
+    };
+
+    if text.starts_with(PEM_PREFIX) {
+        let (label, der) = decode_pem(text)?;
+        return match label {
+            PRIVATE_KEY_LABEL => AnySigningKey::from_pkcs8_der(der.as_bytes()),
+            EC_PRIVATE_KEY_LABEL => AnySigningKey::from_sec1_der(der.as_bytes()),
+            #[cfg(feature = "rsa")]
+            RSA_PRIVATE_KEY_LABEL => Ok(AnySigningKey::Rsa(
+                RsaSigningKey::from_pem(text)?,
+                Algorithm::RS256,
+            )),
+            _ => Err(Error::KEY_FORMAT_UNRECOGNIZED),
+        };
+    }
+
+    if text.starts_with('{') {
+        return AnySigningKey::from_jwk(&decode_jwk(text)?);
+    }
+
+    if let Some(bytes) = multibase_bytes(text) {
+        if let Ok(val) = AnySigningKey::from_multicodec(&bytes) {
+            return Ok(val);
+        }
+    }
+
+    signing_key_from_binary(&decode_text(text)?)
+}
+
+pub fn parse_verifying_key(input: impl AsRef<[u8]>) -> Result<AnyVerifyingKey, Error> {
+    let input = input.as_ref();
+    let text = match as_text(input) {
+        Some(val) => val,
+        None => return verifying_key_from_binary(input),
+    };
+
+    if text.starts_with(PEM_PREFIX) {
+        let (label, der) = decode_pem(text)?;
+        return match label {
+            PUBLIC_KEY_LABEL => AnyVerifyingKey::from_spki_der(der.as_bytes()),
+            CERTIFICATE_LABEL => AnyVerifyingKey::from_x509_der(der.as_bytes()),
+            #[cfg(feature = "rsa")]
+            RSA_PUBLIC_KEY_LABEL => Ok(AnyVerifyingKey::Rsa(
+                RsaVerifyingKey::from_pem(text)?,
+                Algorithm::RS256,
+            )),
+            _ => Err(Error::KEY_FORMAT_UNRECOGNIZED),
+        };
+    }
+
+    if text.starts_with('{') {
+        return AnyVerifyingKey::from_jwk(&decode_jwk(text)?);
+    }
+
+    if let Some(bytes) = multibase_bytes(text) {
+        if let Ok(val) = AnyVerifyingKey::from_multicodec(&bytes) {
+            return Ok(val);
+        }
+    }
+
+    verifying_key_from_binary(&decode_text(text)?)
+}
@@ -0,0 +1,79 @@
+use p12_keystore::{KeyStore, KeyStoreEntry};
+
+use crate::{errors::Error, log};
+
+use super::any::AnySigningKey;
+
+pub struct Pkcs12Certificate {
+    pub subject: String,
+    pub issuer: String,
+    pub der: Vec<u8>,
+}
+
+pub struct Pkcs12KeyEntry {
+    pub alias: String,
+    pub key: AnySigningKey,
+    pub chain: Vec<Pkcs12Certificate>,
+}
+
+pub struct Pkcs12Bundle {
+    keys: Vec<Pkcs12KeyEntry>,
+    certificates: Vec<(String, Pkcs12Certificate)>,
+}
+
+impl Pkcs12Bundle {
+    pub fn from_der(data: &[u8], password: &str) -> Result<Self, Error> {
+        let store = match KeyStore::from_pkcs12(data, password) {
+            Ok(val) => val,
+            Err(p12_keystore::error::Error::MacError(error)) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::PKCS12_WRONG_PASSWORD.with_source(error));
+            }
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::PKCS12_ERROR.with_source(error));
+            }
+        };
+
+        let mut keys = Vec::new();
+        let mut certificates = Vec::new();
+        for (alias, entry) in store.entries() {
+            match entry {
+                KeyStoreEntry::PrivateKeyChain(chain) => keys.push(Pkcs12KeyEntry {
+                    alias: alias.clone(),
+                    key: AnySigningKey::from_pkcs8_der(chain.key())?,
+                    chain: chain.chain().iter().map(to_certificate).collect(),
+                }),
+                KeyStoreEntry::Certificate(certificate) => {
+                    certificates.push((alias.clone(), to_certificate(certificate)))
+                }
+            }
+        }
+
+        Ok(Pkcs12Bundle { keys, certificates })
+    }
+
+    pub fn keys(&self) -> &[Pkcs12KeyEntry] {
+        &self.keys
+    }
+
+    pub fn key(&self, alias: &str) -> Option<&Pkcs12KeyEntry> {
+        self.keys.iter().find(|entry| entry.alias == alias)
+    }
+
+    pub fn certificates(&self) -> &[(String, Pkcs12Certificate)] {
+        &self.certificates
+    }
+
+    pub fn into_keys(self) -> Vec<Pkcs12KeyEntry> {
+        self.keys
+    }
+}
+
+fn to_certificate(certificate: &p12_keystore::Certificate) -> Pkcs12Certificate {
+    Pkcs12Certificate {
+        subject: String::from(certificate.subject()),
+        issuer: String::from(certificate.issuer()),
+        der: certificate.as_der().to_vec(),
+    }
+}
@@ -0,0 +1,206 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+
+use crate::{
+    algorithms::AlgorithmPolicy,
+    crypto::any::AnyVerifyingKey,
+    errors::Error,
+    jwk::Jwk,
+    keystore::{KeyValidity, VerifyingKeyResolver},
+};
+
+pub const DEFAULT_MIN_RSA_BITS: usize = 2048;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Curve {
+    P256,
+    Secp256k1,
+    P384,
+    P521,
+    Ed25519,
+}
+
+impl Curve {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Curve::P256 => "P-256",
+            Curve::Secp256k1 => "secp256k1",
+            Curve::P384 => "P-384",
+            Curve::P521 => "P-521",
+            Curve::Ed25519 => "Ed25519",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            Curve::P256,
+            Curve::Secp256k1,
+            Curve::P384,
+            Curve::P521,
+            Curve::Ed25519,
+        ]
+        .into_iter()
+        .find(|curve| curve.name() == name)
+    }
+
+    pub fn of(key: &AnyVerifyingKey) -> Option<Self> {
+        match key {
+            AnyVerifyingKey::P256(_) => Some(Curve::P256),
+            #[cfg(feature = "es256k")]
+            AnyVerifyingKey::P256k(_) => Some(Curve::Secp256k1),
+            #[cfg(feature = "es384")]
+            AnyVerifyingKey::P384(_) => Some(Curve::P384),
+            #[cfg(feature = "es512")]
+            AnyVerifyingKey::P512(_) => Some(Curve::P521),
+            #[cfg(feature = "eddsa")]
+            AnyVerifyingKey::EdDSA(_) => Some(Curve::Ed25519),
+            _ => None,
+        }
+    }
+
+    fn bit(&self) -> u8 {
+        1 << *self as u8
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct KeyPolicy {
+    min_rsa_bits: usize,
+    banned_curves: u8,
+    algorithms: AlgorithmPolicy,
+}
+
+impl Default for KeyPolicy {
+    fn default() -> Self {
+        KeyPolicy {
+            min_rsa_bits: DEFAULT_MIN_RSA_BITS,
+            banned_curves: 0,
+            algorithms: AlgorithmPolicy::default(),
+        }
+    }
+}
+
+impl KeyPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn permissive() -> Self {
+        KeyPolicy {
+            min_rsa_bits: 0,
+            ..Self::default()
+        }
+    }
+
+    pub fn min_rsa_bits(mut self, bits: usize) -> Self {
+        self.min_rsa_bits = bits;
+        self
+    }
+
+    pub fn ban_curve(mut self, curve: Curve) -> Self {
+        self.banned_curves |= curve.bit();
+        self
+    }
+
+    pub fn allow_curve(mut self, curve: Curve) -> Self {
+        self.banned_curves &= !curve.bit();
+        self
+    }
+
+    pub fn algorithms(mut self, policy: AlgorithmPolicy) -> Self {
+        self.algorithms = policy;
+        self
+    }
+
+    pub fn is_curve_banned(&self, curve: Curve) -> bool {
+        self.banned_curves & curve.bit() != 0
+    }
+
+    pub fn check(&self, key: &AnyVerifyingKey) -> Result<(), Error> {
+        let alg = key.algorithm();
+        if !self.algorithms.is_allowed(alg) {
+            return Err(Error::KEY_POLICY_VIOLATION.with_algorithm(alg));
+        }
+
+        if matches!(Curve::of(key), Some(curve) if self.is_curve_banned(curve)) {
+            return Err(Error::KEY_POLICY_VIOLATION.with_algorithm(alg));
+        }
+
+        #[cfg(feature = "rsa")]
+        if let AnyVerifyingKey::Rsa(key, _) = key {
+            if key.modulus_bits() < self.min_rsa_bits {
+                return Err(Error::KEY_POLICY_VIOLATION.with_algorithm(alg));
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn check_jwk(&self, jwk: &Jwk) -> Result<AnyVerifyingKey, Error> {
+        let key = AnyVerifyingKey::from_jwk(jwk)?;
+        self.check(&key)?;
+        Ok(key)
+    }
+
+    pub fn check_all(&self, keys: Vec<AnyVerifyingKey>) -> Result<Vec<AnyVerifyingKey>, Error> {
+        let mut violation = None;
+        let mut allowed = Vec::new();
+        for key in keys {
+            match self.check(&key) {
+                Ok(_) => allowed.push(key),
+                Err(error) => violation = Some(error),
+            }
+        }
+
+        match (allowed.is_empty(), violation) {
+            (true, Some(error)) => Err(error),
+            _ => Ok(allowed),
+        }
+    }
+}
+
+pub struct PolicyResolver<R: VerifyingKeyResolver> {
+    resolver: R,
+    policy: KeyPolicy,
+}
+
+impl<R: VerifyingKeyResolver> PolicyResolver<R> {
+    pub fn new(resolver: R, policy: KeyPolicy) -> Self {
+        PolicyResolver { resolver, policy }
+    }
+
+    pub fn inner(&self) -> &R {
+        &self.resolver
+    }
+
+    pub fn policy(&self) -> &KeyPolicy {
+        &self.policy
+    }
+}
+
+impl<R: VerifyingKeyResolver> VerifyingKeyResolver for PolicyResolver<R> {
+    fn resolve(&self, kid: &str) -> Result<Arc<AnyVerifyingKey>, Error> {
+        let key = self.resolver.resolve(kid)?;
+        match self.policy.check(&key) {
+            Ok(_) => Ok(key),
+            Err(error) => Err(error.with_key(kid)),
+        }
+    }
+
+    fn resolve_validity(&self, kid: &str) -> Result<KeyValidity, Error> {
+        self.resolver.resolve_validity(kid)
+    }
+
+    fn resolve_at(
+        &self,
+        kid: &str,
+        at: Option<DateTime<Utc>>,
+    ) -> Result<Arc<AnyVerifyingKey>, Error> {
+        let key = self.resolver.resolve_at(kid, at)?;
+        match self.policy.check(&key) {
+            Ok(_) => Ok(key),
+            Err(error) => Err(error.with_key(kid)),
+        }
+    }
+}
@@ -1,14 +1,21 @@
+use std::fmt;
+
 use crate::{
-    algorithms::Algorithm, crypto::SignFromKey, crypto::VerifyFromKey, errors::Error, log,
+    algorithms::Algorithm, crypto::entropy, crypto::fmt_key, crypto::x509, crypto::SignFromKey,
+    crypto::VerifyFromKey, errors::Error, log,
 };
+use rand::{rngs::OsRng, CryptoRng, RngCore};
 use rsa::pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey};
 use rsa::pkcs1v15::Signature;
-use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey};
-use rsa::sha2::{Sha256, Sha384, Sha512};
+use rsa::pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePrivateKey, EncodePublicKey};
+use rsa::sha2::{Digest, Sha256, Sha384, Sha512};
 use rsa::signature::{RandomizedSigner, SignatureEncoding, SignerMut, Verifier};
+use rsa::traits::PublicKeyParts;
 use rsa::BigUint;
 
-#[derive(Debug)]
+pub const RSA_KEY_SIZES: [usize; 3] = [2048, 3072, 4096];
+pub const RSA_PUBLIC_EXPONENT: u32 = 65537;
+
 pub struct RsaSigningKey {
     key: rsa::RsaPrivateKey,
 }
@@ -20,14 +27,14 @@ impl RsaSigningKey {
                 Ok(val) => val,
                 Err(error) => {
                     log::error(error.to_string().as_str());
-                    return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+                    return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
                 }
             },
             false => match rsa::RsaPrivateKey::from_pkcs8_pem(key_str) {
                 Ok(val) => val,
                 Err(error) => {
                     log::error(error.to_string().as_str());
-                    return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+                    return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
                 }
             },
         };
@@ -35,6 +42,61 @@ impl RsaSigningKey {
         Ok(RsaSigningKey { key: rsa_key })
     }
 
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        match rsa::RsaPrivateKey::from_pkcs8_der(bytes) {
+            Ok(val) => Ok(RsaSigningKey { key: val }),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        match self.key.to_pkcs8_der() {
+            Ok(val) => Ok(val.as_bytes().to_vec()),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn generate(bits: usize) -> Result<Self, Error> {
+        Self::generate_with_rng(bits, &mut entropy::rng()?)
+    }
+
+    pub fn generate_with_rng(
+        bits: usize,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<Self, Error> {
+        if !RSA_KEY_SIZES.contains(&bits) {
+            return Err(Error::RSA_UNSUPPORTED_KEY_SIZE);
+        }
+
+        match rsa::RsaPrivateKey::new_with_exp(rng, bits, &BigUint::from(RSA_PUBLIC_EXPONENT)) {
+            Ok(val) => Ok(RsaSigningKey { key: val }),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn modulus_bits(&self) -> usize {
+        self.key.size() * 8
+    }
+
+    pub fn verifying_key(&self) -> RsaVerifyingKey {
+        RsaVerifyingKey {
+            key: self.key.to_public_key(),
+        }
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.verifying_key().fingerprint()
+    }
+
     pub fn from_components(
         n: BigUint,
         e: BigUint,
@@ -46,7 +108,7 @@ impl RsaSigningKey {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+                return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
             }
         };
 
@@ -54,26 +116,106 @@ impl RsaSigningKey {
     }
 }
 
+impl fmt::Debug for RsaSigningKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(
+            f,
+            "RsaSigningKey",
+            &format!("RSA-{}", self.key.size() * 8),
+            self.fingerprint(),
+        )
+    }
+}
+
 impl SignFromKey for RsaSigningKey {
-    fn sign(&self, message: String, alg: Algorithm) -> Result<String, Error> {
+    fn sign_bytes(&self, message: &[u8], alg: Algorithm) -> Result<String, Error> {
+        self.sign_bytes_with_rng(message, alg, &mut OsRng)
+    }
+
+    fn sign_batch(
+        &self,
+        items: &[&[u8]],
+        alg: Algorithm,
+    ) -> Result<Vec<super::signature::Signature>, Error> {
+        let key = self.key.clone();
+
+        let signatures: Vec<Vec<u8>> = match alg {
+            Algorithm::RS256 => {
+                let mut signing_key = rsa::pkcs1v15::SigningKey::<Sha256>::new(key);
+                items
+                    .iter()
+                    .map(|val| signing_key.sign(val).to_vec())
+                    .collect()
+            }
+            Algorithm::RS384 => {
+                let mut signing_key = rsa::pkcs1v15::SigningKey::<Sha384>::new(key);
+                items
+                    .iter()
+                    .map(|val| signing_key.sign(val).to_vec())
+                    .collect()
+            }
+            Algorithm::RS512 => {
+                let mut signing_key = rsa::pkcs1v15::SigningKey::<Sha512>::new(key);
+                items
+                    .iter()
+                    .map(|val| signing_key.sign(val).to_vec())
+                    .collect()
+            }
+            Algorithm::PS256 => {
+                let signing_key = rsa::pss::SigningKey::<Sha256>::new(key);
+                items
+                    .iter()
+                    .map(|val| signing_key.sign_with_rng(&mut OsRng, val).to_vec())
+                    .collect()
+            }
+            Algorithm::PS384 => {
+                let signing_key = rsa::pss::SigningKey::<Sha384>::new(key);
+                items
+                    .iter()
+                    .map(|val| signing_key.sign_with_rng(&mut OsRng, val).to_vec())
+                    .collect()
+            }
+            Algorithm::PS512 => {
+                let signing_key = rsa::pss::SigningKey::<Sha512>::new(key);
+                items
+                    .iter()
+                    .map(|val| signing_key.sign_with_rng(&mut OsRng, val).to_vec())
+                    .collect()
+            }
+            _ => return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
+        };
+
+        Ok(signatures
+            .into_iter()
+            .map(|val| super::signature::Signature::new(val, alg))
+            .collect())
+    }
+}
+
+impl RsaSigningKey {
+    pub fn sign_bytes_with_rng(
+        &self,
+        message: &[u8],
+        alg: Algorithm,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<String, Error> {
         let key = self.key.clone();
 
-        let mut rng = rand::thread_rng();
         if alg.to_str().starts_with("RS") {
             let sig: Signature = match alg {
                 Algorithm::RS256 => {
                     let mut signing_key = rsa::pkcs1v15::SigningKey::<Sha256>::new(key);
-                    signing_key.sign(message.as_bytes())
+                    signing_key.sign(message)
                 }
                 Algorithm::RS384 => {
                     let mut signing_key = rsa::pkcs1v15::SigningKey::<Sha384>::new(key);
-                    signing_key.sign(message.as_bytes())
+                    signing_key.sign(message)
                 }
                 Algorithm::RS512 => {
                     let mut signing_key = rsa::pkcs1v15::SigningKey::<Sha512>::new(key);
-                    signing_key.sign(message.as_bytes())
+                    signing_key.sign(message)
                 }
-                _ => return Err(Error::UNKNOWN_ALGORITHM),
+                _ => return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
             };
             let bytes = sig.to_bytes();
             Ok(base64_url::encode(&bytes))
@@ -81,17 +223,17 @@ impl SignFromKey for RsaSigningKey {
             let sig = match alg {
                 Algorithm::PS256 => {
                     let signing_key = rsa::pss::SigningKey::<Sha256>::new(key);
-                    signing_key.sign_with_rng(&mut rng, message.as_bytes())
+                    signing_key.sign_with_rng(rng, message)
                 }
                 Algorithm::PS384 => {
                     let signing_key = rsa::pss::SigningKey::<Sha384>::new(key);
-                    signing_key.sign_with_rng(&mut rng, message.as_bytes())
+                    signing_key.sign_with_rng(rng, message)
                 }
                 Algorithm::PS512 => {
                     let signing_key = rsa::pss::SigningKey::<Sha512>::new(key);
-                    signing_key.sign_with_rng(&mut rng, message.as_bytes())
+                    signing_key.sign_with_rng(rng, message)
                 }
-                _ => return Err(Error::UNKNOWN_ALGORITHM),
+                _ => return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
             };
 
             let bytes = sig.to_bytes();
@@ -100,7 +242,7 @@ impl SignFromKey for RsaSigningKey {
     }
 }
 
-#[derive(Debug)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct RsaVerifyingKey {
     key: rsa::RsaPublicKey,
 }
@@ -112,31 +254,94 @@ impl RsaVerifyingKey {
                 Ok(val) => val,
                 Err(error) => {
                     log::error(error.to_string().as_str());
-                    return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+                    return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
                 }
             },
             false => match rsa::RsaPublicKey::from_public_key_pem(key_str) {
                 Ok(val) => val,
                 Err(error) => {
                     log::error(error.to_string().as_str());
-                    return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+                    return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error));
                 }
             },
         };
 
         Ok(RsaVerifyingKey { key: rsa_key })
     }
+
+    pub fn to_spki_der(&self) -> Result<Vec<u8>, Error> {
+        match self.key.to_public_key_der() {
+            Ok(val) => Ok(val.into_vec()),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn modulus_bits(&self) -> usize {
+        self.key.size() * 8
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        Ok(base64_url::encode(
+            Sha256::digest(self.to_spki_der()?).as_slice(),
+        ))
+    }
+
+    pub fn from_x509_pem(cert_str: &str) -> Result<Self, Error> {
+        Self::from_x509_der(&x509::certificate_pem_to_der(cert_str)?)
+    }
+
+    pub fn from_x509_der(cert: &[u8]) -> Result<Self, Error> {
+        Self::from_spki_der(&x509::subject_public_key_info(cert)?)
+    }
+
+    pub fn from_spki_der(der: &[u8]) -> Result<Self, Error> {
+        match rsa::RsaPublicKey::from_public_key_der(der) {
+            Ok(val) => Ok(RsaVerifyingKey { key: val }),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn from_components(n: BigUint, e: BigUint) -> Result<Self, Error> {
+        match rsa::RsaPublicKey::new(n, e) {
+            Ok(val) => Ok(RsaVerifyingKey { key: val }),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR.with_source(error))
+            }
+        }
+    }
+}
+
+impl fmt::Debug for RsaVerifyingKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(
+            f,
+            "RsaVerifyingKey",
+            &format!("RSA-{}", self.key.size() * 8),
+            self.fingerprint(),
+        )
+    }
 }
 
 impl VerifyFromKey for RsaVerifyingKey {
-    fn verify(&self, message: String, signature: String, alg: Algorithm) -> Result<bool, Error> {
+    fn verifying_algorithms(&self) -> Option<Vec<Algorithm>> {
+        Some(vec![Algorithm::RS256, Algorithm::RS384, Algorithm::RS512])
+    }
+
+    fn verify_bytes(&self, message: &[u8], signature: &str, alg: Algorithm) -> Result<bool, Error> {
         let key = self.key.clone();
 
-        let decoded_sig_data = match base64_url::decode(&signature) {
+        let decoded_sig_data = match base64_url::decode(signature) {
             Ok(val) => val,
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::DECODING_ERROR);
+                return Err(Error::DECODING_ERROR.with_source(error));
             }
         };
 
@@ -145,24 +350,24 @@ impl VerifyFromKey for RsaVerifyingKey {
                 Ok(val) => val,
                 Err(error) => {
                     log::error(error.to_string().as_str());
-                    return Err(Error::SIGNATURE_IDENTIFICATION_FAILED);
+                    return Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_source(error));
                 }
             };
 
             let verification = match alg {
                 Algorithm::RS256 => {
                     let verifying_key = rsa::pkcs1v15::VerifyingKey::<Sha256>::new(key);
-                    verifying_key.verify(message.as_bytes(), &sig)
+                    verifying_key.verify(message, &sig)
                 }
                 Algorithm::RS384 => {
                     let verifying_key = rsa::pkcs1v15::VerifyingKey::<Sha384>::new(key);
-                    verifying_key.verify(message.as_bytes(), &sig)
+                    verifying_key.verify(message, &sig)
                 }
                 Algorithm::RS512 => {
                     let verifying_key = rsa::pkcs1v15::VerifyingKey::<Sha512>::new(key);
-                    verifying_key.verify(message.as_bytes(), &sig)
+                    verifying_key.verify(message, &sig)
                 }
-                _ => return Err(Error::UNKNOWN_ALGORITHM),
+                _ => return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
             };
 
             if verification.is_ok() {
@@ -181,24 +386,24 @@ impl VerifyFromKey for RsaVerifyingKey {
                 Ok(val) => val,
                 Err(error) => {
                     log::error(error.to_string().as_str());
-                    return Err(Error::SIGNATURE_IDENTIFICATION_FAILED);
+                    return Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_source(error));
                 }
             };
 
             let verification = match alg {
-                Algorithm::RS256 => {
+                Algorithm::PS256 => {
                     let verifying_key = rsa::pss::VerifyingKey::<Sha256>::new(key);
-                    verifying_key.verify(message.as_bytes(), &sig)
+                    verifying_key.verify(message, &sig)
                 }
-                Algorithm::RS384 => {
+                Algorithm::PS384 => {
                     let verifying_key = rsa::pss::VerifyingKey::<Sha384>::new(key);
-                    verifying_key.verify(message.as_bytes(), &sig)
+                    verifying_key.verify(message, &sig)
                 }
-                Algorithm::RS512 => {
+                Algorithm::PS512 => {
                     let verifying_key = rsa::pss::VerifyingKey::<Sha512>::new(key);
-                    verifying_key.verify(message.as_bytes(), &sig)
+                    verifying_key.verify(message, &sig)
                 }
-                _ => return Err(Error::UNKNOWN_ALGORITHM),
+                _ => return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
             };
 
             if verification.is_ok() {
@@ -216,15 +421,19 @@ impl VerifyFromKey for RsaVerifyingKey {
     }
 }
 
-pub fn sign_rsa(message: String, key: impl SignFromKey, alg: Algorithm) -> Result<String, Error> {
-    key.sign(message, alg)
+pub fn sign_rsa(
+    message: impl AsRef<[u8]>,
+    key: impl SignFromKey,
+    alg: Algorithm,
+) -> Result<String, Error> {
+    key.sign_bytes(message.as_ref(), alg)
 }
 
 pub fn verify_rsa(
-    message: String,
-    signature: String,
+    message: impl AsRef<[u8]>,
+    signature: impl AsRef<str>,
     key: impl VerifyFromKey,
     alg: Algorithm,
 ) -> Result<bool, Error> {
-    key.verify(message, signature, alg)
+    key.verify_bytes(message.as_ref(), signature.as_ref(), alg)
 }
@@ -0,0 +1,145 @@
+use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{errors::Error, jwk::Jwk};
+
+#[cfg(feature = "es256k")]
+use super::ecdsa::_256k::P256kVerifyingKey;
+#[cfg(feature = "es384")]
+use super::ecdsa::_384::P384VerifyingKey;
+#[cfg(feature = "es512")]
+use super::ecdsa::_512::P512VerifyingKey;
+#[cfg(feature = "eddsa")]
+use super::eddsa::EDDSAVerifyingKey;
+#[cfg(feature = "serde-secret")]
+use super::{
+    any::AnySigningKey,
+    bls::{Bls12381G1SigningKey, Bls12381G2SigningKey},
+    x25519::X25519SecretKey,
+};
+use super::{
+    any::AnyVerifyingKey,
+    bls::{Bls12381G1VerifyingKey, Bls12381G2VerifyingKey},
+    ecdsa::_256::P256VerifyingKey,
+    x25519::X25519PublicKey,
+};
+#[cfg(all(feature = "mldsa", feature = "serde-secret"))]
+use super::{composite::MLDSA65Ed25519SigningKey, mldsa::MLDSA65SigningKey};
+#[cfg(feature = "mldsa")]
+use super::{composite::MLDSA65Ed25519VerifyingKey, mldsa::MLDSA65VerifyingKey};
+
+macro_rules! jwk_serde {
+    ($key:ty, $to_jwk:expr, $from_jwk:expr) => {
+        impl Serialize for $key {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                let to_jwk: fn(&$key) -> Result<Jwk, Error> = $to_jwk;
+                match to_jwk(self) {
+                    Ok(val) => val.serialize(serializer),
+                    Err(error) => Err(S::Error::custom(error)),
+                }
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $key {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let from_jwk: fn(&Jwk) -> Result<$key, Error> = $from_jwk;
+                from_jwk(&Jwk::deserialize(deserializer)?).map_err(D::Error::custom)
+            }
+        }
+    };
+}
+
+macro_rules! any_jwk_serde {
+    ($key:ty, $variant:ident) => {
+        jwk_serde!(
+            $key,
+            |key| AnyVerifyingKey::$variant(key.clone()).to_jwk(),
+            |jwk| match AnyVerifyingKey::from_jwk(jwk)? {
+                AnyVerifyingKey::$variant(key) => Ok(key),
+                _ => Err(Error::JWK_KEY_TYPE_MISMATCH),
+            }
+        );
+    };
+}
+
+jwk_serde!(
+    AnyVerifyingKey,
+    AnyVerifyingKey::to_jwk,
+    AnyVerifyingKey::from_jwk
+);
+any_jwk_serde!(P256VerifyingKey, P256);
+#[cfg(feature = "es256k")]
+any_jwk_serde!(P256kVerifyingKey, P256k);
+#[cfg(feature = "es384")]
+any_jwk_serde!(P384VerifyingKey, P384);
+#[cfg(feature = "es512")]
+any_jwk_serde!(P512VerifyingKey, P512);
+#[cfg(feature = "eddsa")]
+jwk_serde!(
+    EDDSAVerifyingKey,
+    |key| Ok(key.to_jwk()),
+    EDDSAVerifyingKey::from_jwk
+);
+#[cfg(feature = "mldsa")]
+jwk_serde!(
+    MLDSA65VerifyingKey,
+    |key| Ok(key.to_jwk()),
+    MLDSA65VerifyingKey::from_jwk
+);
+#[cfg(feature = "mldsa")]
+jwk_serde!(
+    MLDSA65Ed25519VerifyingKey,
+    |key| Ok(key.to_jwk()),
+    MLDSA65Ed25519VerifyingKey::from_jwk
+);
+jwk_serde!(
+    Bls12381G1VerifyingKey,
+    |key| Ok(key.to_jwk()),
+    Bls12381G1VerifyingKey::from_jwk
+);
+jwk_serde!(
+    Bls12381G2VerifyingKey,
+    |key| Ok(key.to_jwk()),
+    Bls12381G2VerifyingKey::from_jwk
+);
+jwk_serde!(
+    X25519PublicKey,
+    |key| Ok(key.to_jwk()),
+    X25519PublicKey::from_jwk
+);
+
+#[cfg(feature = "serde-secret")]
+jwk_serde!(
+    AnySigningKey,
+    AnySigningKey::to_jwk,
+    AnySigningKey::from_jwk
+);
+#[cfg(all(feature = "mldsa", feature = "serde-secret"))]
+jwk_serde!(
+    MLDSA65SigningKey,
+    |key| Ok(key.to_jwk()),
+    MLDSA65SigningKey::from_jwk
+);
+#[cfg(all(feature = "mldsa", feature = "serde-secret"))]
+jwk_serde!(
+    MLDSA65Ed25519SigningKey,
+    |key| Ok(key.to_jwk()),
+    MLDSA65Ed25519SigningKey::from_jwk
+);
+#[cfg(feature = "serde-secret")]
+jwk_serde!(
+    Bls12381G1SigningKey,
+    |key| Ok(key.to_jwk()),
+    Bls12381G1SigningKey::from_jwk
+);
+#[cfg(feature = "serde-secret")]
+jwk_serde!(
+    Bls12381G2SigningKey,
+    |key| Ok(key.to_jwk()),
+    Bls12381G2SigningKey::from_jwk
+);
+#[cfg(feature = "serde-secret")]
+jwk_serde!(
+    X25519SecretKey,
+    |key| Ok(key.to_jwk()),
+    X25519SecretKey::from_jwk
+);
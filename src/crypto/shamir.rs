@@ -0,0 +1,229 @@
+use std::fmt;
+
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+use crate::{
+    algorithms::Algorithm,
+    encoding::{base58check_decode, base58check_encode},
+    errors::Error,
+};
+
+use super::{any::AnySigningKey, constant_time::constant_time_eq};
+
+const SHARE_VERSION: u8 = 1;
+const HEADER_LENGTH: usize = 5;
+const DIGEST_LENGTH: usize = 4;
+const CHECKSUM_LENGTH: usize = 4;
+
+fn gf_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        let carry = 0u8.wrapping_sub(a >> 7);
+        a = (a << 1) ^ (carry & 0x1b);
+        b >>= 1;
+    }
+    product
+}
+
+fn gf_inv(a: u8) -> u8 {
+    let mut result = 1u8;
+    let mut base = a;
+    let mut exponent = 254u8;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = gf_mul(result, base);
+        }
+        base = gf_mul(base, base);
+        exponent >>= 1;
+    }
+    result
+}
+
+fn secret_digest(secret: &[u8]) -> [u8; DIGEST_LENGTH] {
+    let digest = Sha256::digest(secret);
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+fn checksum(bytes: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+    let digest = Sha256::digest(bytes);
+    [digest[0], digest[1], digest[2], digest[3]]
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Share {
+    id: u16,
+    threshold: u8,
+    index: u8,
+    value: Zeroizing<Vec<u8>>,
+}
+
+impl Share {
+    pub fn id(&self) -> u16 {
+        self.id
+    }
+
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    pub fn index(&self) -> u8 {
+        self.index
+    }
+
+    pub fn to_bytes(&self) -> Zeroizing<Vec<u8>> {
+        let mut bytes = Zeroizing::new(vec![SHARE_VERSION]);
+        bytes.extend_from_slice(&self.id.to_be_bytes());
+        bytes.push(self.threshold);
+        bytes.push(self.index);
+        bytes.extend_from_slice(&self.value);
+        let checksum = checksum(&bytes);
+        bytes.extend_from_slice(&checksum);
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() <= HEADER_LENGTH + DIGEST_LENGTH + CHECKSUM_LENGTH
+            || bytes[0] != SHARE_VERSION
+        {
+            return Err(Error::SHAMIR_INVALID_SHARE);
+        }
+
+        let (body, expected) = bytes.split_at(bytes.len() - CHECKSUM_LENGTH);
+        if !constant_time_eq(&checksum(body), expected) {
+            return Err(Error::SHAMIR_INVALID_SHARE);
+        }
+
+        let share = Share {
+            id: u16::from_be_bytes([body[1], body[2]]),
+            threshold: body[3],
+            index: body[4],
+            value: Zeroizing::new(body[HEADER_LENGTH..].to_vec()),
+        };
+        match share.threshold >= 2 && share.index != 0 {
+            true => Ok(share),
+            false => Err(Error::SHAMIR_INVALID_SHARE),
+        }
+    }
+
+    pub fn to_base58(&self) -> Zeroizing<String> {
+        Zeroizing::new(base58check_encode(self.to_bytes().as_slice()))
+    }
+
+    pub fn from_base58(value: &str) -> Result<Self, Error> {
+        let bytes = Zeroizing::new(base58check_decode(value.trim())?);
+        Self::from_bytes(&bytes)
+    }
+}
+
+impl fmt::Debug for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("id", &self.id)
+            .field("threshold", &self.threshold)
+            .field("index", &self.index)
+            .finish_non_exhaustive()
+    }
+}
+
+pub fn split(secret: &[u8], threshold: u8, shares: u8) -> Result<Vec<Share>, Error> {
+    split_with_rng(secret, threshold, shares, &mut OsRng)
+}
+
+pub fn split_with_rng(
+    secret: &[u8],
+    threshold: u8,
+    shares: u8,
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<Vec<Share>, Error> {
+    if secret.is_empty() || threshold < 2 || threshold > shares {
+        return Err(Error::SHAMIR_INVALID_PARAMETERS);
+    }
+
+    let mut payload = Zeroizing::new(secret.to_vec());
+    payload.extend_from_slice(&secret_digest(secret));
+
+    let mut coefficients = Zeroizing::new(vec![0u8; payload.len() * (threshold as usize - 1)]);
+    rng.fill_bytes(&mut coefficients);
+    let id = rng.next_u32() as u16;
+
+    Ok((1..=shares)
+        .map(|index| {
+            let value = payload
+                .iter()
+                .enumerate()
+                .map(|(position, byte)| {
+                    let higher = coefficients
+                        .chunks(payload.len())
+                        .rev()
+                        .fold(0u8, |acc, chunk| gf_mul(acc, index) ^ chunk[position]);
+                    gf_mul(higher, index) ^ byte
+                })
+                .collect();
+
+            Share {
+                id,
+                threshold,
+                index,
+                value: Zeroizing::new(value),
+            }
+        })
+        .collect())
+}
+
+pub fn recover(shares: &[Share]) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let first = match shares.first() {
+        Some(val) => val,
+        None => return Err(Error::SHAMIR_INSUFFICIENT_SHARES),
+    };
+
+    let mut selected: Vec<&Share> = Vec::new();
+    for share in shares {
+        if share.id != first.id
+            || share.threshold != first.threshold
+            || share.value.len() != first.value.len()
+        {
+            return Err(Error::SHAMIR_RECOVERY_FAILED);
+        }
+
+        if !selected.iter().any(|val| val.index == share.index) {
+            selected.push(share);
+        }
+    }
+
+    if selected.len() < first.threshold as usize {
+        return Err(Error::SHAMIR_INSUFFICIENT_SHARES);
+    }
+    selected.truncate(first.threshold as usize);
+
+    let mut payload = Zeroizing::new(vec![0u8; first.value.len()]);
+    for share in &selected {
+        let mut basis = 1u8;
+        for other in selected.iter().filter(|val| val.index != share.index) {
+            basis = gf_mul(
+                basis,
+                gf_mul(other.index, gf_inv(other.index ^ share.index)),
+            );
+        }
+
+        for (byte, value) in payload.iter_mut().zip(share.value.iter()) {
+            *byte ^= gf_mul(basis, *value);
+        }
+    }
+
+    let (secret, digest) = payload.split_at(payload.len() - DIGEST_LENGTH);
+    match constant_time_eq(&secret_digest(secret), digest) {
+        true => Ok(Zeroizing::new(secret.to_vec())),
+        false => Err(Error::SHAMIR_RECOVERY_FAILED),
+    }
+}
+
+pub fn split_key(key: &AnySigningKey, threshold: u8, shares: u8) -> Result<Vec<Share>, Error> {
+    split(&key.to_private_bytes()?, threshold, shares)
+}
+
+pub fn recover_key(shares: &[Share], alg: Algorithm) -> Result<AnySigningKey, Error> {
+    AnySigningKey::from_bytes(alg, &recover(shares)?)
+}
@@ -0,0 +1,84 @@
+use std::{fmt, ops::Deref, sync::Arc};
+
+use super::any::{AnySigningKey, AnyVerifyingKey};
+
+pub type SharedSigningKey = SharedKey<AnySigningKey>;
+pub type SharedVerifyingKey = SharedKey<AnyVerifyingKey>;
+
+const _: fn() = || {
+    fn shareable<T: Send + Sync>() {}
+    shareable::<AnySigningKey>();
+    shareable::<AnyVerifyingKey>();
+    shareable::<SharedSigningKey>();
+    shareable::<SharedVerifyingKey>();
+};
+
+pub struct SharedKey<T: ?Sized>(Arc<T>);
+
+impl<T> SharedKey<T> {
+    pub fn new(key: T) -> Self {
+        SharedKey(Arc::new(key))
+    }
+}
+
+impl<T: ?Sized> SharedKey<T> {
+    pub fn as_arc(&self) -> &Arc<T> {
+        &self.0
+    }
+
+    pub fn into_arc(self) -> Arc<T> {
+        self.0
+    }
+
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+
+    pub fn handles(&self) -> usize {
+        Arc::strong_count(&self.0)
+    }
+}
+
+impl<T: ?Sized> Clone for SharedKey<T> {
+    fn clone(&self) -> Self {
+        SharedKey(Arc::clone(&self.0))
+    }
+}
+
+impl<T: ?Sized> Deref for SharedKey<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: ?Sized> AsRef<T> for SharedKey<T> {
+    fn as_ref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T> From<T> for SharedKey<T> {
+    fn from(key: T) -> Self {
+        Self::new(key)
+    }
+}
+
+impl<T: ?Sized> From<Arc<T>> for SharedKey<T> {
+    fn from(key: Arc<T>) -> Self {
+        SharedKey(key)
+    }
+}
+
+impl<T: fmt::Debug + ?Sized> fmt::Debug for SharedKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl SharedKey<AnySigningKey> {
+    pub fn shared_verifying_key(&self) -> SharedVerifyingKey {
+        SharedKey::new(self.0.verifying_key())
+    }
+}
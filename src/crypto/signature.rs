@@ -0,0 +1,243 @@
+use std::fmt;
+
+use base64::{
+    engine::general_purpose::{STANDARD, STANDARD_NO_PAD, URL_SAFE, URL_SAFE_NO_PAD},
+    Engine,
+};
+
+use crate::{
+    algorithms::Algorithm,
+    crypto::{decode_hex, ecdsa::p1363_length, encode_hex_prefixed},
+    encoding,
+    errors::Error,
+    log,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Base64Variant {
+    #[default]
+    UrlSafe,
+    UrlSafePadded,
+    Standard,
+    StandardPadded,
+    Auto,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SignatureEncoding {
+    #[default]
+    Base64Url,
+    Base64(Base64Variant),
+    Hex,
+    Multibase,
+    Base58,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Signature {
+    bytes: Vec<u8>,
+    alg: Algorithm,
+}
+
+impl Signature {
+    pub fn new(bytes: Vec<u8>, alg: Algorithm) -> Self {
+        Signature { bytes, alg }
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.alg
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    pub fn decode(
+        signature: &str,
+        alg: Algorithm,
+        encoding: SignatureEncoding,
+    ) -> Result<Self, Error> {
+        match encoding {
+            SignatureEncoding::Base64Url => Self::from_base64url(signature, alg),
+            SignatureEncoding::Base64(variant) => Self::from_base64(signature, alg, variant),
+            SignatureEncoding::Hex => Self::from_hex(signature, alg),
+            SignatureEncoding::Multibase => Self::from_multibase(signature, alg),
+            SignatureEncoding::Base58 => Self::from_base58(signature, alg),
+        }
+    }
+
+    pub fn encode(&self, encoding: SignatureEncoding) -> String {
+        match encoding {
+            SignatureEncoding::Base64Url => self.to_base64url(),
+            SignatureEncoding::Base64(variant) => self.to_base64(variant),
+            SignatureEncoding::Hex => self.to_hex(),
+            SignatureEncoding::Multibase => self.to_multibase(),
+            SignatureEncoding::Base58 => self.to_base58(),
+        }
+    }
+
+    pub fn from_base64url(signature: &str, alg: Algorithm) -> Result<Self, Error> {
+        match base64_url::decode(signature) {
+            Ok(val) => Ok(Signature::new(val, alg)),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::DECODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn to_base64url(&self) -> String {
+        base64_url::encode(&self.bytes)
+    }
+
+    pub fn from_base64(
+        signature: &str,
+        alg: Algorithm,
+        variant: Base64Variant,
+    ) -> Result<Self, Error> {
+        let decoded = match variant {
+            Base64Variant::UrlSafe => URL_SAFE_NO_PAD.decode(signature),
+            Base64Variant::UrlSafePadded => URL_SAFE.decode(signature),
+            Base64Variant::Standard => STANDARD_NO_PAD.decode(signature),
+            Base64Variant::StandardPadded => STANDARD.decode(signature),
+            Base64Variant::Auto => {
+                let normalized: String = signature
+                    .trim_end_matches('=')
+                    .chars()
+                    .map(|val| match val {
+                        '+' => '-',
+                        '/' => '_',
+                        val => val,
+                    })
+                    .collect();
+                URL_SAFE_NO_PAD.decode(normalized)
+            }
+        };
+
+        match decoded {
+            Ok(val) => Ok(Signature::new(val, alg)),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::DECODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn to_base64(&self, variant: Base64Variant) -> String {
+        match variant {
+            Base64Variant::UrlSafe | Base64Variant::Auto => URL_SAFE_NO_PAD.encode(&self.bytes),
+            Base64Variant::UrlSafePadded => URL_SAFE.encode(&self.bytes),
+            Base64Variant::Standard => STANDARD_NO_PAD.encode(&self.bytes),
+            Base64Variant::StandardPadded => STANDARD.encode(&self.bytes),
+        }
+    }
+
+    pub fn from_hex(signature: &str, alg: Algorithm) -> Result<Self, Error> {
+        Ok(Signature::new(decode_hex(signature)?, alg))
+    }
+
+    pub fn to_hex(&self) -> String {
+        hex::encode(&self.bytes)
+    }
+
+    pub fn to_hex_prefixed(&self) -> String {
+        encode_hex_prefixed(&self.bytes)
+    }
+
+    pub fn from_multibase(signature: &str, alg: Algorithm) -> Result<Self, Error> {
+        let encoded = match signature.strip_prefix(encoding::MULTIBASE_BASE58BTC) {
+            Some(val) => val,
+            None => return Err(Error::SIGNATURE_ENCODING_UNSUPPORTED),
+        };
+
+        Self::from_base58(encoded, alg)
+    }
+
+    pub fn to_multibase(&self) -> String {
+        encoding::multibase_encode(&self.bytes)
+    }
+
+    pub fn from_base58(signature: &str, alg: Algorithm) -> Result<Self, Error> {
+        Ok(Signature::new(encoding::base58_decode(signature)?, alg))
+    }
+
+    pub fn to_base58(&self) -> String {
+        encoding::base58_encode(&self.bytes)
+    }
+
+    pub fn from_p1363(bytes: &[u8], alg: Algorithm) -> Result<Self, Error> {
+        match p1363_length(alg) {
+            Some(val) if val == bytes.len() => Ok(Signature::new(bytes.to_vec(), alg)),
+            Some(_) => Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_algorithm(alg)),
+            None => Err(Error::SIGNATURE_ENCODING_UNSUPPORTED.with_algorithm(alg)),
+        }
+    }
+
+    pub fn from_der(der: &[u8], alg: Algorithm) -> Result<Self, Error> {
+        let bytes = match alg {
+            Algorithm::ES256 => p256::ecdsa::Signature::from_der(der).map(|val| val.to_vec()),
+            #[cfg(feature = "es256k")]
+            Algorithm::ES256K => k256::ecdsa::Signature::from_der(der).map(|val| val.to_vec()),
+            #[cfg(feature = "es384")]
+            Algorithm::ES384 => p384::ecdsa::Signature::from_der(der).map(|val| val.to_vec()),
+            #[cfg(feature = "es512")]
+            Algorithm::ES512 => p521::ecdsa::Signature::from_der(der).map(|val| val.to_vec()),
+            _ => return Err(Error::SIGNATURE_ENCODING_UNSUPPORTED.with_algorithm(alg)),
+        };
+
+        match bytes {
+            Ok(val) => Ok(Signature::new(val, alg)),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_source(error))
+            }
+        }
+    }
+
+    pub fn to_der(&self) -> Result<Vec<u8>, Error> {
+        let der = match self.alg {
+            Algorithm::ES256 => p256::ecdsa::Signature::from_slice(&self.bytes)
+                .map(|val| val.to_der().as_bytes().to_vec()),
+            #[cfg(feature = "es256k")]
+            Algorithm::ES256K => k256::ecdsa::Signature::from_slice(&self.bytes)
+                .map(|val| val.to_der().as_bytes().to_vec()),
+            #[cfg(feature = "es384")]
+            Algorithm::ES384 => p384::ecdsa::Signature::from_slice(&self.bytes)
+                .map(|val| val.to_der().as_bytes().to_vec()),
+            #[cfg(feature = "es512")]
+            Algorithm::ES512 => p521::ecdsa::Signature::from_slice(&self.bytes)
+                .map(|val| val.to_der().as_bytes().to_vec()),
+            _ => return Err(Error::SIGNATURE_ENCODING_UNSUPPORTED.with_algorithm(self.alg)),
+        };
+
+        match der {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::SIGNATURE_IDENTIFICATION_FAILED.with_source(error))
+            }
+        }
+    }
+}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_base64url())
+    }
+}
+
+impl From<Signature> for String {
+    fn from(signature: Signature) -> Self {
+        signature.to_base64url()
+    }
+}
+
+impl AsRef<[u8]> for Signature {
+    fn as_ref(&self) -> &[u8] {
+        &self.bytes
+    }
+}
@@ -0,0 +1,426 @@
+use std::io::Read;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::{algorithms::Algorithm, errors::Error, hash::HashAlgorithm, log, signer, verifier};
+
+use super::{
+    any::{AnySigningKey, AnyVerifyingKey},
+    ecdsa::p1363_length,
+    signature::Signature,
+};
+
+pub const ARMOR_SSH_SIGNATURE: &str = "SSH SIGNATURE";
+
+const SSHSIG_MAGIC: &[u8] = b"SSHSIG";
+const SSHSIG_VERSION: u32 = 1;
+const ARMOR_LINE_LENGTH: usize = 70;
+
+#[cfg(feature = "eddsa")]
+const KEY_TYPE_ED25519: &str = "ssh-ed25519";
+const KEY_TYPE_NISTP256: &str = "ecdsa-sha2-nistp256";
+#[cfg(feature = "es384")]
+const KEY_TYPE_NISTP384: &str = "ecdsa-sha2-nistp384";
+#[cfg(feature = "es512")]
+const KEY_TYPE_NISTP521: &str = "ecdsa-sha2-nistp521";
+
+fn key_type(alg: Algorithm) -> Result<(&'static str, Option<&'static str>), Error> {
+    match alg {
+        #[cfg(feature = "eddsa")]
+        Algorithm::EdDSA => Ok((KEY_TYPE_ED25519, None)),
+        Algorithm::ES256 => Ok((KEY_TYPE_NISTP256, Some("nistp256"))),
+        #[cfg(feature = "es384")]
+        Algorithm::ES384 => Ok((KEY_TYPE_NISTP384, Some("nistp384"))),
+        #[cfg(feature = "es512")]
+        Algorithm::ES512 => Ok((KEY_TYPE_NISTP521, Some("nistp521"))),
+        _ => Err(Error::KEY_FORMAT_UNSUPPORTED.with_algorithm(alg)),
+    }
+}
+
+fn key_algorithm(key_type: &str) -> Result<Algorithm, Error> {
+    match key_type {
+        #[cfg(feature = "eddsa")]
+        KEY_TYPE_ED25519 => Ok(Algorithm::EdDSA),
+        KEY_TYPE_NISTP256 => Ok(Algorithm::ES256),
+        #[cfg(feature = "es384")]
+        KEY_TYPE_NISTP384 => Ok(Algorithm::ES384),
+        #[cfg(feature = "es512")]
+        KEY_TYPE_NISTP521 => Ok(Algorithm::ES512),
+        _ => Err(Error::KEY_FORMAT_UNSUPPORTED.with_key(key_type)),
+    }
+}
+
+fn hash_name(hash: HashAlgorithm) -> Result<&'static str, Error> {
+    match hash {
+        HashAlgorithm::Sha256 => Ok("sha256"),
+        HashAlgorithm::Sha512 => Ok("sha512"),
+        _ => Err(Error::SSH_SIGNATURE_MALFORMED.with_key(hash.to_str())),
+    }
+}
+
+fn hash_algorithm(name: &str) -> Result<HashAlgorithm, Error> {
+    match name {
+        "sha256" => Ok(HashAlgorithm::Sha256),
+        "sha512" => Ok(HashAlgorithm::Sha512),
+        _ => Err(Error::SSH_SIGNATURE_MALFORMED.with_key(name)),
+    }
+}
+
+fn put_string(out: &mut Vec<u8>, value: &[u8]) {
+    out.extend_from_slice(&(value.len() as u32).to_be_bytes());
+    out.extend_from_slice(value);
+}
+
+fn put_mpint(out: &mut Vec<u8>, value: &[u8]) {
+    let start = value
+        .iter()
+        .position(|byte| *byte != 0)
+        .unwrap_or(value.len());
+    let value = &value[start..];
+    match value.first() {
+        Some(byte) if byte & 0x80 != 0 => put_string(out, &[&[0u8], value].concat()),
+        _ => put_string(out, value),
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Reader { data, position: 0 }
+    }
+
+    fn finish(&self) -> Result<(), Error> {
+        match self.position == self.data.len() {
+            true => Ok(()),
+            false => Err(Error::SSH_SIGNATURE_MALFORMED),
+        }
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        match self
+            .data
+            .get(self.position..self.position.saturating_add(len))
+        {
+            Some(val) => {
+                self.position += len;
+                Ok(val)
+            }
+            None => Err(Error::SSH_SIGNATURE_MALFORMED),
+        }
+    }
+
+    fn uint32(&mut self) -> Result<u32, Error> {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(self.take(4)?);
+        Ok(u32::from_be_bytes(bytes))
+    }
+
+    fn string(&mut self) -> Result<&'a [u8], Error> {
+        let len = self.uint32()? as usize;
+        self.take(len)
+    }
+
+    fn text(&mut self) -> Result<&'a str, Error> {
+        match std::str::from_utf8(self.string()?) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::SSH_SIGNATURE_MALFORMED.with_source(error))
+            }
+        }
+    }
+
+    fn mpint(&mut self, len: usize) -> Result<Vec<u8>, Error> {
+        let value = self.string()?;
+        let start = value
+            .iter()
+            .position(|byte| *byte != 0)
+            .unwrap_or(value.len());
+        let value = &value[start..];
+        if value.len() > len {
+            return Err(Error::SSH_SIGNATURE_MALFORMED);
+        }
+
+        let mut padded = vec![0u8; len - value.len()];
+        padded.extend_from_slice(value);
+        Ok(padded)
+    }
+}
+
+pub fn to_ssh_public_key(key: &AnyVerifyingKey) -> Result<Vec<u8>, Error> {
+    let mut out = Vec::new();
+    match key_type(key.algorithm())? {
+        (name, None) => {
+            put_string(&mut out, name.as_bytes());
+            put_string(&mut out, &key.to_bytes()?);
+        }
+        (name, Some(curve)) => {
+            put_string(&mut out, name.as_bytes());
+            put_string(&mut out, curve.as_bytes());
+            put_string(&mut out, &key.to_sec1_bytes(false)?);
+        }
+    }
+    Ok(out)
+}
+
+pub fn from_ssh_public_key(bytes: &[u8]) -> Result<AnyVerifyingKey, Error> {
+    let mut reader = Reader::new(bytes);
+    let alg = key_algorithm(reader.text()?)?;
+    if let (_, Some(curve)) = key_type(alg)? {
+        if reader.text()? != curve {
+            return Err(Error::SSH_SIGNATURE_MALFORMED);
+        }
+    }
+    let key = AnyVerifyingKey::from_bytes(alg, reader.string()?)?;
+    reader.finish()?;
+
+    Ok(key)
+}
+
+pub fn to_openssh_public_key(
+    key: &AnyVerifyingKey,
+    comment: Option<&str>,
+) -> Result<String, Error> {
+    let (name, _) = key_type(key.algorithm())?;
+    let encoded = STANDARD.encode(to_ssh_public_key(key)?);
+    match comment {
+        Some(comment) => Ok(format!("{} {} {}", name, encoded, comment)),
+        None => Ok(format!("{} {}", name, encoded)),
+    }
+}
+
+pub fn from_openssh_public_key(line: &str) -> Result<AnyVerifyingKey, Error> {
+    let mut fields = line.split_whitespace();
+    let (name, encoded) = match (fields.next(), fields.next()) {
+        (Some(name), Some(encoded)) => (name, encoded),
+        _ => return Err(Error::KEY_FORMAT_UNRECOGNIZED),
+    };
+
+    let bytes = match STANDARD.decode(encoded) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::KEY_FORMAT_UNRECOGNIZED.with_source(error));
+        }
+    };
+    let key = from_ssh_public_key(&bytes)?;
+    match key_type(key.algorithm())?.0 == name {
+        true => Ok(key),
+        false => Err(Error::KEY_FORMAT_UNRECOGNIZED.with_key(name)),
+    }
+}
+
+fn signed_data(namespace: &str, hash: HashAlgorithm, digest: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut out = SSHSIG_MAGIC.to_vec();
+    put_string(&mut out, namespace.as_bytes());
+    put_string(&mut out, &[]);
+    put_string(&mut out, hash_name(hash)?.as_bytes());
+    put_string(&mut out, digest);
+    Ok(out)
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SshSignature {
+    public_key: AnyVerifyingKey,
+    namespace: String,
+    hash: HashAlgorithm,
+    signature: Signature,
+}
+
+impl SshSignature {
+    pub fn sign(
+        message: impl AsRef<[u8]>,
+        key: &AnySigningKey,
+        namespace: &str,
+    ) -> Result<Self, Error> {
+        Self::sign_digest(
+            &HashAlgorithm::Sha512.digest(message),
+            key,
+            namespace,
+            HashAlgorithm::Sha512,
+        )
+    }
+
+    pub fn sign_reader(
+        reader: impl Read,
+        key: &AnySigningKey,
+        namespace: &str,
+        hash: HashAlgorithm,
+    ) -> Result<Self, Error> {
+        Self::sign_digest(&hash.digest_reader(reader)?, key, namespace, hash)
+    }
+
+    fn sign_digest(
+        digest: &[u8],
+        key: &AnySigningKey,
+        namespace: &str,
+        hash: HashAlgorithm,
+    ) -> Result<Self, Error> {
+        if namespace.is_empty() {
+            return Err(Error::SSH_SIGNATURE_NAMESPACE_MISMATCH);
+        }
+
+        let alg = key.algorithm();
+        key_type(alg)?;
+        let data = signed_data(namespace, hash, digest)?;
+
+        Ok(SshSignature {
+            public_key: key.verifying_key(),
+            namespace: String::from(namespace),
+            hash,
+            signature: signer::sign_signature(data, key, alg)?,
+        })
+    }
+
+    pub fn public_key(&self) -> &AnyVerifyingKey {
+        &self.public_key
+    }
+
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        self.hash
+    }
+
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    pub fn verify(&self, message: impl AsRef<[u8]>, namespace: &str) -> Result<bool, Error> {
+        self.verify_digest(&self.hash.digest(message), namespace)
+    }
+
+    pub fn verify_reader(&self, reader: impl Read, namespace: &str) -> Result<bool, Error> {
+        self.verify_digest(&self.hash.digest_reader(reader)?, namespace)
+    }
+
+    pub fn verify_with_key(
+        &self,
+        message: impl AsRef<[u8]>,
+        namespace: &str,
+        key: &AnyVerifyingKey,
+    ) -> Result<bool, Error> {
+        match self.public_key == *key {
+            true => self.verify(message, namespace),
+            false => Ok(false),
+        }
+    }
+
+    fn verify_digest(&self, digest: &[u8], namespace: &str) -> Result<bool, Error> {
+        if self.namespace != namespace {
+            return Err(Error::SSH_SIGNATURE_NAMESPACE_MISMATCH.with_key(&self.namespace));
+        }
+
+        verifier::verify_signature(
+            signed_data(&self.namespace, self.hash, digest)?,
+            &self.signature,
+            &self.public_key,
+        )
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let alg = self.signature.algorithm();
+        let (name, curve) = key_type(alg)?;
+
+        let mut blob = Vec::new();
+        put_string(&mut blob, name.as_bytes());
+        match curve {
+            None => put_string(&mut blob, self.signature.as_bytes()),
+            Some(_) => {
+                let (r, s) = self
+                    .signature
+                    .as_bytes()
+                    .split_at(self.signature.as_bytes().len() / 2);
+                let mut rs = Vec::new();
+                put_mpint(&mut rs, r);
+                put_mpint(&mut rs, s);
+                put_string(&mut blob, &rs);
+            }
+        }
+
+        let mut out = SSHSIG_MAGIC.to_vec();
+        out.extend_from_slice(&SSHSIG_VERSION.to_be_bytes());
+        put_string(&mut out, &to_ssh_public_key(&self.public_key)?);
+        put_string(&mut out, self.namespace.as_bytes());
+        put_string(&mut out, &[]);
+        put_string(&mut out, hash_name(self.hash)?.as_bytes());
+        put_string(&mut out, &blob);
+        Ok(out)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader::new(bytes);
+        if reader.take(SSHSIG_MAGIC.len())? != SSHSIG_MAGIC || reader.uint32()? != SSHSIG_VERSION {
+            return Err(Error::SSH_SIGNATURE_MALFORMED);
+        }
+
+        let public_key = from_ssh_public_key(reader.string()?)?;
+        let namespace = String::from(reader.text()?);
+        reader.string()?;
+        let hash = hash_algorithm(reader.text()?)?;
+        let mut blob = Reader::new(reader.string()?);
+        reader.finish()?;
+
+        let alg = public_key.algorithm();
+        let (name, curve) = key_type(alg)?;
+        if blob.text()? != name {
+            return Err(Error::SSH_SIGNATURE_MALFORMED);
+        }
+        let signature = match (curve, p1363_length(alg)) {
+            (Some(_), Some(len)) => {
+                let mut rs = Reader::new(blob.string()?);
+                let mut bytes = rs.mpint(len / 2)?;
+                bytes.extend(rs.mpint(len / 2)?);
+                rs.finish()?;
+                Signature::from_p1363(&bytes, alg)?
+            }
+            _ => Signature::new(blob.string()?.to_vec(), alg),
+        };
+        blob.finish()?;
+
+        Ok(SshSignature {
+            public_key,
+            namespace,
+            hash,
+            signature,
+        })
+    }
+
+    pub fn to_armored(&self) -> Result<String, Error> {
+        let encoded = STANDARD.encode(self.to_bytes()?);
+        let mut out = format!("-----BEGIN {}-----\n", ARMOR_SSH_SIGNATURE);
+        for line in encoded.as_bytes().chunks(ARMOR_LINE_LENGTH) {
+            out.push_str(&String::from_utf8_lossy(line));
+            out.push('\n');
+        }
+        out.push_str(&format!("-----END {}-----\n", ARMOR_SSH_SIGNATURE));
+        Ok(out)
+    }
+
+    pub fn from_armored(armored: &str) -> Result<Self, Error> {
+        let begin = format!("-----BEGIN {}-----", ARMOR_SSH_SIGNATURE);
+        let end = format!("-----END {}-----", ARMOR_SSH_SIGNATURE);
+        let body = match armored
+            .trim()
+            .strip_prefix(begin.as_str())
+            .and_then(|val| val.strip_suffix(end.as_str()))
+        {
+            Some(val) => val.split_whitespace().collect::<String>(),
+            None => return Err(Error::SSH_SIGNATURE_MALFORMED),
+        };
+
+        match STANDARD.decode(body) {
+            Ok(val) => Self::from_bytes(&val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::SSH_SIGNATURE_MALFORMED.with_source(error))
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use std::fmt;
+
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::{errors::Error, jwk::Jwk, multicodec};
+
+use super::fmt_key;
+
+const X25519_JWK_CURVE: &str = "X25519";
+
+fn decode_key(bytes: &[u8], error: Error) -> Result<[u8; 32], Error> {
+    match bytes.try_into() {
+        Ok(val) => Ok(val),
+        Err(_) => Err(error),
+    }
+}
+
+fn public_key_from_jwk(jwk: &Jwk) -> Result<Vec<u8>, Error> {
+    match (jwk.kty.as_str(), jwk.crv.as_deref()) {
+        ("OKP", Some(X25519_JWK_CURVE)) => Jwk::decode_member(&jwk.x),
+        _ => Err(Error::JWK_KEY_TYPE_MISMATCH),
+    }
+}
+
+#[derive(Clone)]
+pub struct X25519SecretKey {
+    key: StaticSecret,
+}
+
+impl X25519SecretKey {
+    pub fn generate() -> Self {
+        Self::generate_with_rng(&mut OsRng)
+    }
+
+    pub fn generate_with_rng(rng: &mut (impl CryptoRng + RngCore)) -> Self {
+        X25519SecretKey {
+            key: StaticSecret::random_from_rng(rng),
+        }
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(X25519SecretKey {
+            key: StaticSecret::from(decode_key(bytes, Error::PRIVATE_KEY_IDENTIFICATION_ERROR)?),
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.key.to_bytes()
+    }
+
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        let secret_key = Self::from_bytes(&Jwk::decode_member(&jwk.d)?)?;
+        if public_key_from_jwk(jwk)? != secret_key.public_key().to_bytes() {
+            return Err(Error::JWK_KEY_TYPE_MISMATCH);
+        }
+
+        Ok(secret_key)
+    }
+
+    pub fn to_jwk(&self) -> Jwk {
+        Jwk {
+            d: Some(base64_url::encode(&self.to_bytes())),
+            ..self.public_key().to_jwk()
+        }
+    }
+
+    pub fn public_key(&self) -> X25519PublicKey {
+        X25519PublicKey {
+            key: PublicKey::from(&self.key),
+        }
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.public_key().fingerprint()
+    }
+
+    pub fn diffie_hellman(&self, public_key: &X25519PublicKey) -> Result<[u8; 32], Error> {
+        let shared_secret = self.key.diffie_hellman(&public_key.key);
+        if !shared_secret.was_contributory() {
+            return Err(Error::KEY_AGREEMENT_FAILED);
+        }
+
+        Ok(shared_secret.to_bytes())
+    }
+}
+
+impl fmt::Debug for X25519SecretKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(f, "X25519SecretKey", "X25519", self.fingerprint())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct X25519PublicKey {
+    key: PublicKey,
+}
+
+impl X25519PublicKey {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        Ok(X25519PublicKey {
+            key: PublicKey::from(decode_key(bytes, Error::PUBLIC_KEY_IDENTIFICATION_ERROR)?),
+        })
+    }
+
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.key.to_bytes()
+    }
+
+    pub fn from_multicodec(bytes: &[u8]) -> Result<Self, Error> {
+        match multicodec::decode(bytes)? {
+            (multicodec::X25519_PUB, key) => Self::from_bytes(key),
+            _ => Err(Error::MULTICODEC_ERROR),
+        }
+    }
+
+    pub fn to_multicodec(&self) -> Vec<u8> {
+        multicodec::encode(multicodec::X25519_PUB, &self.to_bytes())
+    }
+
+    pub fn from_jwk(jwk: &Jwk) -> Result<Self, Error> {
+        Self::from_bytes(&public_key_from_jwk(jwk)?)
+    }
+
+    pub fn to_jwk(&self) -> Jwk {
+        Jwk {
+            kty: String::from("OKP"),
+            crv: Some(String::from(X25519_JWK_CURVE)),
+            x: Some(base64_url::encode(&self.to_bytes())),
+            ..Default::default()
+        }
+    }
+
+    pub fn fingerprint(&self) -> Result<String, Error> {
+        self.to_jwk().thumbprint()
+    }
+}
+
+impl fmt::Debug for X25519PublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt_key(f, "X25519PublicKey", "X25519", self.fingerprint())
+    }
+}
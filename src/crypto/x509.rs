@@ -0,0 +1,424 @@
+use std::{str::FromStr, time::SystemTime};
+
+use chrono::{DateTime, Utc};
+use rand::RngCore;
+use x509_cert::{
+    certificate::{TbsCertificate, Version},
+    der::{
+        asn1::{Any, BitString, Ia5String, OctetString, SetOfVec},
+        oid::AssociatedOid,
+        pem::LineEnding,
+        Decode, DecodePem, Encode, EncodePem,
+    },
+    ext::{
+        pkix::{name::GeneralName, BasicConstraints, SubjectAltName},
+        Extension,
+    },
+    name::Name,
+    request::{CertReq, CertReqInfo, ExtensionReq},
+    serial_number::SerialNumber,
+    spki::{AlgorithmIdentifierOwned, ObjectIdentifier, SubjectPublicKeyInfoOwned},
+    time::{Time, Validity},
+    Certificate,
+};
+
+use crate::{algorithms::Algorithm, errors::Error, log};
+
+#[cfg(feature = "mldsa")]
+use super::mldsa::MLDSA65_OID;
+use super::{
+    any::{AnySigningKey, AnyVerifyingKey},
+    ecdsa::{signature_from_der, signature_to_der},
+    SignFromKey, VerifyFromKey,
+};
+
+const ECDSA_WITH_SHA256_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.2");
+const ECDSA_WITH_SHA384_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.3");
+const ECDSA_WITH_SHA512_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.10045.4.3.4");
+const SHA256_WITH_RSA_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.11");
+const SHA384_WITH_RSA_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.12");
+const SHA512_WITH_RSA_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.13");
+const ED25519_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.101.112");
+const SERIAL_NUMBER_LENGTH: usize = 16;
+
+pub fn certificate_from_pem(pem: &str) -> Result<Certificate, Error> {
+    match Certificate::from_pem(pem) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::X509_CERTIFICATE_ERROR.with_source(error))
+        }
+    }
+}
+
+pub fn certificate_from_der(der: &[u8]) -> Result<Certificate, Error> {
+    match Certificate::from_der(der) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::X509_CERTIFICATE_ERROR.with_source(error))
+        }
+    }
+}
+
+pub fn certificate_pem_to_der(pem: &str) -> Result<Vec<u8>, Error> {
+    match certificate_from_pem(pem)?.to_der() {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::X509_CERTIFICATE_ERROR.with_source(error))
+        }
+    }
+}
+
+pub fn subject_public_key_info(der: &[u8]) -> Result<Vec<u8>, Error> {
+    match certificate_from_der(der)?
+        .tbs_certificate
+        .subject_public_key_info
+        .to_der()
+    {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::X509_CERTIFICATE_ERROR.with_source(error))
+        }
+    }
+}
+
+pub fn certificate_der_to_pem(der: &[u8]) -> Result<String, Error> {
+    match certificate_from_der(der)?.to_pem(LineEnding::LF) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::X509_CERTIFICATE_ERROR.with_source(error))
+        }
+    }
+}
+
+pub struct SelfSignedCertificate {
+    pub subject: String,
+    pub not_before: DateTime<Utc>,
+    pub not_after: DateTime<Utc>,
+    pub subject_alt_names: Vec<String>,
+}
+
+impl SelfSignedCertificate {
+    pub fn new(subject: &str, not_before: DateTime<Utc>, not_after: DateTime<Utc>) -> Self {
+        SelfSignedCertificate {
+            subject: String::from(subject),
+            not_before,
+            not_after,
+            subject_alt_names: Vec::new(),
+        }
+    }
+
+    pub fn did(mut self, did: &str) -> Self {
+        self.subject_alt_names.push(String::from(did));
+        self
+    }
+
+    pub fn sign(&self, key: &AnySigningKey) -> Result<Vec<u8>, Error> {
+        let subject = match self.subject.is_empty() {
+            true => Name::default(),
+            false => encoded(Name::from_str(&self.subject))?,
+        };
+
+        let signature_algorithm = signature_algorithm(key.algorithm())?;
+        let tbs_certificate = TbsCertificate {
+            version: Version::V3,
+            serial_number: serial_number()?,
+            signature: signature_algorithm.clone(),
+            issuer: subject.clone(),
+            validity: Validity {
+                not_before: to_time(self.not_before)?,
+                not_after: to_time(self.not_after)?,
+            },
+            subject_public_key_info: encoded(SubjectPublicKeyInfoOwned::from_der(
+                &key.verifying_key().to_spki_der()?,
+            ))?,
+            issuer_unique_id: None,
+            subject_unique_id: None,
+            extensions: match self.subject_alt_names.is_empty() {
+                true => None,
+                false => Some(vec![subject_alt_name(
+                    &self.subject_alt_names,
+                    subject.0.is_empty(),
+                )?]),
+            },
+            subject,
+        };
+
+        let tbs = encoded(tbs_certificate.to_der())?;
+        let signature = sign(key, &tbs)?;
+        encoded(
+            Certificate {
+                tbs_certificate,
+                signature_algorithm,
+                signature: encoded(BitString::from_bytes(&signature))?,
+            }
+            .to_der(),
+        )
+    }
+
+    pub fn sign_pem(&self, key: &AnySigningKey) -> Result<String, Error> {
+        certificate_der_to_pem(&self.sign(key)?)
+    }
+}
+
+pub fn certificate_request_from_pem(pem: &str) -> Result<CertReq, Error> {
+    encoded(CertReq::from_pem(pem))
+}
+
+pub fn certificate_request_from_der(der: &[u8]) -> Result<CertReq, Error> {
+    encoded(CertReq::from_der(der))
+}
+
+pub struct CertificateSigningRequest {
+    pub subject: String,
+    pub subject_alt_names: Vec<String>,
+    pub extensions: Vec<Extension>,
+}
+
+impl CertificateSigningRequest {
+    pub fn new(subject: &str) -> Self {
+        CertificateSigningRequest {
+            subject: String::from(subject),
+            subject_alt_names: Vec::new(),
+            extensions: Vec::new(),
+        }
+    }
+
+    pub fn did(mut self, did: &str) -> Self {
+        self.subject_alt_names.push(String::from(did));
+        self
+    }
+
+    pub fn extension(mut self, extension: Extension) -> Self {
+        self.extensions.push(extension);
+        self
+    }
+
+    pub fn sign(&self, key: &AnySigningKey) -> Result<Vec<u8>, Error> {
+        let subject = match self.subject.is_empty() {
+            true => Name::default(),
+            false => encoded(Name::from_str(&self.subject))?,
+        };
+
+        let mut extensions = Vec::new();
+        if !self.subject_alt_names.is_empty() {
+            extensions.push(subject_alt_name(
+                &self.subject_alt_names,
+                subject.0.is_empty(),
+            )?);
+        }
+        extensions.extend(self.extensions.iter().cloned());
+
+        let mut attributes = SetOfVec::new();
+        if !extensions.is_empty() {
+            encoded(attributes.insert(encoded(ExtensionReq(extensions).try_into())?))?;
+        }
+
+        let info = CertReqInfo {
+            version: x509_cert::request::Version::V1,
+            subject,
+            public_key: encoded(SubjectPublicKeyInfoOwned::from_der(
+                &key.verifying_key().to_spki_der()?,
+            ))?,
+            attributes,
+        };
+
+        let signature = sign(key, &encoded(info.to_der())?)?;
+        encoded(
+            CertReq {
+                info,
+                algorithm: signature_algorithm(key.algorithm())?,
+                signature: encoded(BitString::from_bytes(&signature))?,
+            }
+            .to_der(),
+        )
+    }
+
+    pub fn sign_pem(&self, key: &AnySigningKey) -> Result<String, Error> {
+        encoded(certificate_request_from_der(&self.sign(key)?)?.to_pem(LineEnding::LF))
+    }
+}
+
+pub fn validate_chain(
+    chain: &[Vec<u8>],
+    trust_anchors: &[Vec<u8>],
+    at: DateTime<Utc>,
+) -> Result<AnyVerifyingKey, Error> {
+    let leaf = match chain.first() {
+        Some(val) => val,
+        None => return Err(Error::X509_CHAIN_INVALID),
+    };
+
+    let mut certificates = Vec::new();
+    for der in chain {
+        let certificate = certificate_from_der(der)?;
+        check_validity(&certificate, at)?;
+        certificates.push(certificate);
+    }
+
+    for (index, pair) in certificates.windows(2).enumerate() {
+        verify_issued_by(&pair[0], &pair[1], &chain[index + 1])?;
+    }
+
+    let last = &certificates[certificates.len() - 1];
+    let last_der = &chain[chain.len() - 1];
+    let mut trusted = false;
+    for anchor_der in trust_anchors {
+        if anchor_der == last_der {
+            trusted = true;
+            break;
+        }
+
+        let anchor = certificate_from_der(anchor_der)?;
+        if check_validity(&anchor, at).is_ok()
+            && verify_issued_by(last, &anchor, anchor_der).is_ok()
+        {
+            trusted = true;
+            break;
+        }
+    }
+
+    if !trusted {
+        return Err(Error::X509_CHAIN_UNTRUSTED);
+    }
+
+    AnyVerifyingKey::from_x509_der(leaf)
+}
+
+pub(crate) fn signature_algorithm(alg: Algorithm) -> Result<AlgorithmIdentifierOwned, Error> {
+    let (oid, parameters) = match alg {
+        Algorithm::ES256 | Algorithm::ES256K => (ECDSA_WITH_SHA256_OID, None),
+        Algorithm::ES384 => (ECDSA_WITH_SHA384_OID, None),
+        Algorithm::ES512 => (ECDSA_WITH_SHA512_OID, None),
+        Algorithm::RS256 => (SHA256_WITH_RSA_OID, Some(Any::null())),
+        Algorithm::RS384 => (SHA384_WITH_RSA_OID, Some(Any::null())),
+        Algorithm::RS512 => (SHA512_WITH_RSA_OID, Some(Any::null())),
+        Algorithm::EdDSA => (ED25519_OID, None),
+        #[cfg(feature = "mldsa")]
+        Algorithm::MLDSA65 => (MLDSA65_OID, None),
+        _ => return Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
+    };
+
+    Ok(AlgorithmIdentifierOwned { oid, parameters })
+}
+
+pub(crate) fn sign(key: &AnySigningKey, content: &[u8]) -> Result<Vec<u8>, Error> {
+    let alg = key.algorithm();
+    let signature = key.sign_bytes(content, alg)?;
+    match alg {
+        Algorithm::ES256 | Algorithm::ES256K | Algorithm::ES384 | Algorithm::ES512 => {
+            signature_to_der(&signature, alg)
+        }
+        _ => match base64_url::decode(&signature) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::DECODING_ERROR.with_source(error))
+            }
+        },
+    }
+}
+
+pub(crate) fn subject_alt_name(uris: &[String], critical: bool) -> Result<Extension, Error> {
+    let mut names = Vec::new();
+    for uri in uris {
+        names.push(GeneralName::UniformResourceIdentifier(encoded(
+            Ia5String::new(uri),
+        )?));
+    }
+
+    Ok(Extension {
+        extn_id: SubjectAltName::OID,
+        critical,
+        extn_value: encoded(OctetString::new(encoded(SubjectAltName(names).to_der())?))?,
+    })
+}
+
+fn check_validity(certificate: &Certificate, at: DateTime<Utc>) -> Result<(), Error> {
+    let validity = &certificate.tbs_certificate.validity;
+    let not_before = DateTime::<Utc>::from(validity.not_before.to_system_time());
+    let not_after = DateTime::<Utc>::from(validity.not_after.to_system_time());
+    if at < not_before || at > not_after {
+        return Err(Error::X509_CERTIFICATE_EXPIRED);
+    }
+
+    Ok(())
+}
+
+fn verify_issued_by(
+    certificate: &Certificate,
+    issuer: &Certificate,
+    issuer_der: &[u8],
+) -> Result<(), Error> {
+    if certificate.tbs_certificate.issuer != issuer.tbs_certificate.subject {
+        return Err(Error::X509_CHAIN_INVALID);
+    }
+
+    match issuer.tbs_certificate.get::<BasicConstraints>() {
+        Ok(Some((_, val))) if val.ca => {}
+        _ => return Err(Error::X509_CHAIN_INVALID),
+    }
+
+    let key = AnyVerifyingKey::from_x509_der(issuer_der)?;
+    let alg = match (certificate.signature_algorithm.oid, key.algorithm()) {
+        (ECDSA_WITH_SHA256_OID, alg @ (Algorithm::ES256 | Algorithm::ES256K)) => alg,
+        (ECDSA_WITH_SHA384_OID, Algorithm::ES384) => Algorithm::ES384,
+        (ECDSA_WITH_SHA512_OID, Algorithm::ES512) => Algorithm::ES512,
+        (SHA256_WITH_RSA_OID, Algorithm::RS256) => Algorithm::RS256,
+        (SHA384_WITH_RSA_OID, Algorithm::RS256) => Algorithm::RS384,
+        (SHA512_WITH_RSA_OID, Algorithm::RS256) => Algorithm::RS512,
+        (ED25519_OID, Algorithm::EdDSA) => Algorithm::EdDSA,
+        #[cfg(feature = "mldsa")]
+        (MLDSA65_OID, Algorithm::MLDSA65) => Algorithm::MLDSA65,
+        _ => return Err(Error::X509_CHAIN_INVALID),
+    };
+
+    let key = key.with_algorithm(alg);
+
+    let signature = match certificate.signature.as_bytes() {
+        Some(val) => val,
+        None => return Err(Error::X509_CHAIN_INVALID),
+    };
+
+    let signature = match alg {
+        Algorithm::ES256 | Algorithm::ES256K | Algorithm::ES384 | Algorithm::ES512 => {
+            match signature_from_der(signature, alg) {
+                Ok(val) => val,
+                Err(_) => return Err(Error::X509_CHAIN_INVALID),
+            }
+        }
+        _ => base64_url::encode(signature),
+    };
+
+    let tbs = encoded(certificate.tbs_certificate.to_der())?;
+    match key.verify_bytes(&tbs, &signature, alg) {
+        Ok(true) => Ok(()),
+        _ => Err(Error::X509_CHAIN_INVALID),
+    }
+}
+
+fn serial_number() -> Result<SerialNumber, Error> {
+    let mut bytes = [0u8; SERIAL_NUMBER_LENGTH];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes[0] &= 0x7f;
+    bytes[0] |= 0x01;
+    encoded(SerialNumber::new(&bytes))
+}
+
+fn to_time(time: DateTime<Utc>) -> Result<Time, Error> {
+    encoded(Time::try_from(SystemTime::from(time)))
+}
+
+fn encoded<T, E: std::fmt::Display>(result: Result<T, E>) -> Result<T, Error> {
+    match result {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::X509_CERTIFICATE_ERROR.with_source(error.to_string()))
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use coset::{
+    cwt::{ClaimsSet, Timestamp},
+    CborSerializable, Header,
+};
+
+use crate::{
+    algorithms::Algorithm,
+    clock,
+    cose::{protected_header, CoseSign1},
+    crypto::{SignFromKey, VerifyFromKey},
+    errors::Error,
+    log,
+    verifier::VerificationOptions,
+};
+
+const CWT_TAG: u8 = 0xd8;
+const CWT_TAG_VALUE: u8 = 61;
+
+fn timestamp_secs(timestamp: &Timestamp) -> f64 {
+    match timestamp {
+        Timestamp::WholeSeconds(val) => *val as f64,
+        Timestamp::FractionalSeconds(val) => *val,
+    }
+}
+
+pub struct CWT {
+    pub alg: Algorithm,
+    pub kid: Vec<u8>,
+    pub claims: ClaimsSet,
+    signed: Option<CoseSign1>,
+}
+
+impl CWT {
+    pub fn new(kid: Vec<u8>, alg: Algorithm, claims: ClaimsSet) -> Self {
+        CWT {
+            alg,
+            kid,
+            claims,
+            signed: None,
+        }
+    }
+
+    pub fn to_token(&self) -> Result<Vec<u8>, Error> {
+        match &self.signed {
+            Some(val) => val.to_bytes(),
+            None => Err(Error::CWT_NOT_SIGNED),
+        }
+    }
+
+    pub fn sign(&mut self, private_key: impl SignFromKey) -> Result<(), Error> {
+        let claims = match self.claims.clone().to_vec() {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ENCODING_ERROR.with_source(error.to_string()));
+            }
+        };
+
+        self.signed = Some(CoseSign1::sign(
+            protected_header(self.alg, &self.kid)?,
+            Header::default(),
+            &claims,
+            &[],
+            private_key,
+        )?);
+        Ok(())
+    }
+
+    pub fn from_token(token: &[u8]) -> Result<Self, Error> {
+        let token = match token {
+            [CWT_TAG, CWT_TAG_VALUE, rest @ ..] => rest,
+            _ => token,
+        };
+
+        let signed = CoseSign1::from_bytes(token)?;
+        let claims = match signed.payload().map(ClaimsSet::from_slice) {
+            Some(Ok(val)) => val,
+            Some(Err(error)) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::CWT_CLAIMS_DESERIALIZING_ERROR.with_source(error.to_string()));
+            }
+            None => return Err(Error::COSE_MISSING_PAYLOAD),
+        };
+
+        Ok(CWT {
+            alg: signed.algorithm()?,
+            kid: signed.key_id().to_vec(),
+            claims,
+            signed: Some(signed),
+        })
+    }
+
+    pub fn validate(&self, public_key: impl VerifyFromKey) -> Result<bool, Error> {
+        let signed = match &self.signed {
+            Some(val) => val,
+            None => return Err(Error::CWT_NOT_SIGNED),
+        };
+
+        if !signed.verify(&[], public_key)? {
+            return Ok(false);
+        }
+
+        let now = clock::now().timestamp() as f64;
+        if let Some(exp) = &self.claims.expiration_time {
+            if timestamp_secs(exp) <= now {
+                return Ok(false);
+            }
+        }
+
+        if let Some(nbf) = &self.claims.not_before {
+            if timestamp_secs(nbf) > now {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    pub fn validate_with_options(
+        &self,
+        public_key: impl VerifyFromKey,
+        options: &VerificationOptions,
+    ) -> Result<bool, Error> {
+        options.algorithm_policy.check(self.alg)?;
+        options.apply(self.validate(public_key))
+    }
+
+    pub fn validate_token(
+        token: &[u8],
+        public_key: impl VerifyFromKey,
+    ) -> Result<(Self, bool), Error> {
+        let token = Self::from_token(token)?;
+        let verified = token.validate(public_key)?;
+        Ok((token, verified))
+    }
+}
@@ -0,0 +1,419 @@
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    algorithms::Algorithm,
+    clock,
+    crypto::{any::AnyVerifyingKey, policy::KeyPolicy, signature::Signature, SignFromKey},
+    encoding::canonical_json,
+    errors::Error,
+    hash::HashAlgorithm,
+    log,
+    resolver::{
+        resolve_verifying_keys, DidResolver, ASSERTION_METHOD, AUTHENTICATION,
+        CAPABILITY_DELEGATION, CAPABILITY_INVOCATION, DEFAULT_CLOCK_LEEWAY, KEY_AGREEMENT,
+    },
+    signer::sign_signature,
+    vc::one_or_many,
+    verifier::verify_signature,
+};
+
+pub const DATA_INTEGRITY_PROOF: &str = "DataIntegrityProof";
+pub const EDDSA_JCS_2022: &str = "eddsa-jcs-2022";
+pub const ECDSA_JCS_2019: &str = "ecdsa-jcs-2019";
+
+const PROOF_PURPOSES: [&str; 5] = [
+    AUTHENTICATION,
+    ASSERTION_METHOD,
+    KEY_AGREEMENT,
+    CAPABILITY_INVOCATION,
+    CAPABILITY_DELEGATION,
+];
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ProofOptions {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "type")]
+    pub proof_type: String,
+    pub cryptosuite: String,
+    pub verification_method: String,
+    pub proof_purpose: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<DateTime<Utc>>,
+    #[serde(
+        default,
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub domain: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub challenge: Option<String>,
+    #[serde(
+        default,
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub previous_proof: Vec<String>,
+}
+
+impl ProofOptions {
+    pub fn new(cryptosuite: &str, verification_method: &str) -> Self {
+        ProofOptions {
+            id: None,
+            proof_type: String::from(DATA_INTEGRITY_PROOF),
+            cryptosuite: String::from(cryptosuite),
+            verification_method: String::from(verification_method),
+            proof_purpose: String::from(ASSERTION_METHOD),
+            created: None,
+            expires: None,
+            domain: Vec::new(),
+            challenge: None,
+            previous_proof: Vec::new(),
+        }
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(String::from(id));
+        self
+    }
+
+    pub fn with_proof_purpose(mut self, proof_purpose: &str) -> Self {
+        self.proof_purpose = String::from(proof_purpose);
+        self
+    }
+
+    pub fn with_created(mut self, created: DateTime<Utc>) -> Self {
+        self.created = Some(created);
+        self
+    }
+
+    pub fn with_expires(mut self, expires: DateTime<Utc>) -> Self {
+        self.expires = Some(expires);
+        self
+    }
+
+    pub fn with_domain(mut self, domain: &str) -> Self {
+        self.domain.push(String::from(domain));
+        self
+    }
+
+    pub fn with_challenge(mut self, challenge: &str) -> Self {
+        self.challenge = Some(String::from(challenge));
+        self
+    }
+
+    pub fn with_previous_proof(mut self, previous_proof: &str) -> Self {
+        self.previous_proof.push(String::from(previous_proof));
+        self
+    }
+
+    pub fn validate(&self) -> Result<(), Error> {
+        let valid = self.proof_type == DATA_INTEGRITY_PROOF
+            && self.id.as_ref().is_none_or(|val| !val.is_empty())
+            && !self.cryptosuite.is_empty()
+            && !self.verification_method.is_empty()
+            && PROOF_PURPOSES.contains(&self.proof_purpose.as_str())
+            && self.domain.iter().all(|val| !val.is_empty())
+            && self.challenge.as_ref().is_none_or(|val| !val.is_empty())
+            && self.previous_proof.iter().all(|val| !val.is_empty());
+
+        let ordered = match (self.created, self.expires) {
+            (Some(created), Some(expires)) => created < expires,
+            _ => true,
+        };
+
+        match valid && ordered {
+            true => Ok(()),
+            false => Err(Error::DATA_INTEGRITY_INVALID_OPTIONS),
+        }
+    }
+
+    pub fn from_value(value: &Value) -> Result<Self, Error> {
+        let options: Self = match serde_json::from_value(value.clone()) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::DATA_INTEGRITY_PROOF_MALFORMED.with_source(error));
+            }
+        };
+
+        options.validate()?;
+        Ok(options)
+    }
+
+    pub fn to_value(&self) -> Result<Value, Error> {
+        self.validate()?;
+        match serde_json::to_value(self) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENCODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn check(&self, expected: &ProofCheck) -> Result<(), Error> {
+        self.check_at(expected, clock::now())
+    }
+
+    pub fn check_at(&self, expected: &ProofCheck, at: DateTime<Utc>) -> Result<(), Error> {
+        self.validate()?;
+
+        let leeway = TimeDelta::from_std(expected.leeway).unwrap_or(TimeDelta::zero());
+        if self.created.is_some_and(|created| created > at + leeway)
+            || self.expires.is_some_and(|expires| expires + leeway <= at)
+        {
+            return Err(Error::DATA_INTEGRITY_PROOF_EXPIRED);
+        }
+
+        let matches = self.proof_purpose == expected.proof_purpose
+            && expected
+                .domain
+                .as_ref()
+                .is_none_or(|domain| self.domain.contains(domain))
+            && expected
+                .challenge
+                .as_ref()
+                .is_none_or(|challenge| self.challenge.as_ref() == Some(challenge));
+
+        match matches {
+            true => Ok(()),
+            false => Err(Error::DATA_INTEGRITY_PROOF_MISMATCH),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ProofCheck {
+    pub proof_purpose: String,
+    pub domain: Option<String>,
+    pub challenge: Option<String>,
+    pub leeway: Duration,
+}
+
+impl Default for ProofCheck {
+    fn default() -> Self {
+        ProofCheck {
+            proof_purpose: String::from(ASSERTION_METHOD),
+            domain: None,
+            challenge: None,
+            leeway: DEFAULT_CLOCK_LEEWAY,
+        }
+    }
+}
+
+impl ProofCheck {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn proof_purpose(mut self, proof_purpose: &str) -> Self {
+        self.proof_purpose = String::from(proof_purpose);
+        self
+    }
+
+    pub fn domain(mut self, domain: &str) -> Self {
+        self.domain = Some(String::from(domain));
+        self
+    }
+
+    pub fn challenge(mut self, challenge: &str) -> Self {
+        self.challenge = Some(String::from(challenge));
+        self
+    }
+
+    pub fn leeway(mut self, leeway: Duration) -> Self {
+        self.leeway = leeway;
+        self
+    }
+}
+
+fn jcs_suite(alg: Algorithm) -> Result<(&'static str, HashAlgorithm), Error> {
+    match alg {
+        Algorithm::EdDSA => Ok((EDDSA_JCS_2022, HashAlgorithm::Sha256)),
+        Algorithm::ES256 => Ok((ECDSA_JCS_2019, HashAlgorithm::Sha256)),
+        Algorithm::ES384 => Ok((ECDSA_JCS_2019, HashAlgorithm::Sha384)),
+        alg => Err(Error::ALGORITHM_NOT_ALLOWED.with_algorithm(alg)),
+    }
+}
+
+pub fn jcs_cryptosuite(alg: Algorithm) -> Result<&'static str, Error> {
+    Ok(jcs_suite(alg)?.0)
+}
+
+pub fn proofs(document: &Value) -> Vec<Value> {
+    match document.get("proof") {
+        Some(Value::Array(val)) => val.clone(),
+        Some(Value::Null) | None => Vec::new(),
+        Some(val) => vec![val.clone()],
+    }
+}
+
+fn previous_proofs(document: &Value, previous_proof: &[String]) -> Result<Vec<Value>, Error> {
+    let proofs = proofs(document);
+    previous_proof
+        .iter()
+        .map(|id| {
+            match proofs
+                .iter()
+                .find(|proof| proof.get("id").and_then(Value::as_str) == Some(id.as_str()))
+            {
+                Some(val) => Ok(val.clone()),
+                None => Err(Error::DATA_INTEGRITY_PROOF_CHAIN_INVALID),
+            }
+        })
+        .collect()
+}
+
+fn jcs_hash_data(document: &Value, config: &Value, hash: HashAlgorithm) -> Result<Vec<u8>, Error> {
+    let previous_proof = ProofOptions::from_value(config)?.previous_proof;
+    let previous = previous_proofs(document, &previous_proof)?;
+
+    let mut document = document.clone();
+    if let Some(map) = document.as_object_mut() {
+        map.remove("proof");
+        if !previous.is_empty() {
+            map.insert(String::from("proof"), Value::Array(previous));
+        }
+    }
+
+    let mut hash_data = hash.digest(canonical_json(config));
+    hash_data.extend(hash.digest(canonical_json(&document)));
+    Ok(hash_data)
+}
+
+pub fn sign_jcs(
+    document: &Value,
+    config: &Value,
+    key: impl SignFromKey,
+    alg: Algorithm,
+) -> Result<Value, Error> {
+    let (cryptosuite, hash) = jcs_suite(alg)?;
+    if ProofOptions::from_value(config)?.cryptosuite != cryptosuite {
+        return Err(Error::DATA_INTEGRITY_INVALID_OPTIONS);
+    }
+
+    let signature = sign_signature(jcs_hash_data(document, config, hash)?, key, alg)?;
+    let mut proof = config.clone();
+    proof["proofValue"] = Value::from(signature.to_multibase());
+    Ok(proof)
+}
+
+pub fn verify_jcs(document: &Value, proof: &Value, key: &AnyVerifyingKey) -> Result<bool, Error> {
+    let (cryptosuite, hash) = jcs_suite(key.algorithm())?;
+    if ProofOptions::from_value(proof)?.cryptosuite != cryptosuite {
+        return Ok(false);
+    }
+
+    let signature = match proof.get("proofValue").and_then(Value::as_str) {
+        Some(val) => Signature::from_multibase(val, key.algorithm())?,
+        None => return Err(Error::DATA_INTEGRITY_PROOF_MALFORMED),
+    };
+
+    let mut config = proof.clone();
+    if let Some(map) = config.as_object_mut() {
+        map.remove("proofValue");
+    }
+
+    verify_signature(jcs_hash_data(document, &config, hash)?, &signature, key)
+}
+
+pub fn add_proof(
+    document: &Value,
+    config: &Value,
+    key: impl SignFromKey,
+    alg: Algorithm,
+) -> Result<Value, Error> {
+    let proof = sign_jcs(document, config, key, alg)?;
+
+    let mut proofs = proofs(document);
+    proofs.push(proof);
+    let mut secured = document.clone();
+    match secured.as_object_mut() {
+        Some(map) => match proofs.len() {
+            1 => map.insert(String::from("proof"), proofs.remove(0)),
+            _ => map.insert(String::from("proof"), Value::Array(proofs)),
+        },
+        None => return Err(Error::DATA_INTEGRITY_PROOF_MALFORMED),
+    };
+
+    Ok(secured)
+}
+
+fn check_chain(proofs: &[ProofOptions]) -> Result<(), Error> {
+    for (index, proof) in proofs.iter().enumerate() {
+        let earlier = &proofs[..index];
+        if proof
+            .id
+            .as_ref()
+            .is_some_and(|id| earlier.iter().any(|val| val.id.as_ref() == Some(id)))
+        {
+            return Err(Error::DATA_INTEGRITY_PROOF_CHAIN_INVALID);
+        }
+
+        for previous in &proof.previous_proof {
+            if !earlier.iter().any(|val| val.id.as_ref() == Some(previous)) {
+                return Err(Error::DATA_INTEGRITY_PROOF_CHAIN_INVALID);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn verify_proofs(
+    document: &Value,
+    resolver: &impl DidResolver,
+    expected: &ProofCheck,
+) -> Result<bool, Error> {
+    verify_proofs_at(document, resolver, expected, clock::now())
+}
+
+pub fn verify_proofs_at(
+    document: &Value,
+    resolver: &impl DidResolver,
+    expected: &ProofCheck,
+    at: DateTime<Utc>,
+) -> Result<bool, Error> {
+    let proofs = proofs(document);
+    if proofs.is_empty() {
+        return Err(Error::DATA_INTEGRITY_PROOF_MALFORMED);
+    }
+
+    let options = proofs
+        .iter()
+        .map(ProofOptions::from_value)
+        .collect::<Result<Vec<ProofOptions>, Error>>()?;
+    check_chain(&options)?;
+
+    for (proof, options) in proofs.iter().zip(options) {
+        options.check_at(expected, at)?;
+
+        let keys = resolve_verifying_keys(
+            resolver,
+            &options.verification_method,
+            &expected.proof_purpose,
+            &KeyPolicy::permissive(),
+        )?;
+        let mut verified = false;
+        for key in keys {
+            if jcs_cryptosuite(key.algorithm()).is_ok() && verify_jcs(document, proof, &key)? {
+                verified = true;
+                break;
+            }
+        }
+
+        if !verified {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
@@ -0,0 +1,93 @@
+use std::{
+    fs::{self, File},
+    io::Read,
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    algorithms::Algorithm,
+    crypto::{
+        signature::{Signature, SignatureEncoding},
+        SignFromKey, VerifyFromKey,
+    },
+    errors::Error,
+    hash::HashAlgorithm,
+    log, signer, verifier,
+};
+
+pub fn digest_reader(reader: impl Read, alg: Algorithm) -> Result<Vec<u8>, Error> {
+    HashAlgorithm::for_algorithm(alg).digest_reader(reader)
+}
+
+pub fn sign_reader(
+    reader: impl Read,
+    key: impl SignFromKey,
+    alg: Algorithm,
+) -> Result<Signature, Error> {
+    signer::sign_signature(digest_reader(reader, alg)?, key, alg)
+}
+
+pub fn verify_reader(
+    reader: impl Read,
+    signature: &Signature,
+    key: impl VerifyFromKey,
+) -> Result<bool, Error> {
+    verifier::verify_signature(
+        digest_reader(reader, signature.algorithm())?,
+        signature,
+        key,
+    )
+}
+
+pub fn signature_path(path: impl AsRef<Path>) -> PathBuf {
+    let mut val = path.as_ref().as_os_str().to_owned();
+    val.push(".sig");
+    PathBuf::from(val)
+}
+
+fn open(path: &Path) -> Result<File, Error> {
+    match File::open(path) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::FILE_IO_ERROR.with_source(error))
+        }
+    }
+}
+
+pub fn sign_file(
+    path: impl AsRef<Path>,
+    signature_path: impl AsRef<Path>,
+    key: impl SignFromKey,
+    alg: Algorithm,
+    encoding: SignatureEncoding,
+) -> Result<Signature, Error> {
+    let signature = sign_reader(open(path.as_ref())?, key, alg)?;
+
+    match fs::write(signature_path, signature.encode(encoding)) {
+        Ok(_) => Ok(signature),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::FILE_IO_ERROR.with_source(error))
+        }
+    }
+}
+
+pub fn verify_file(
+    path: impl AsRef<Path>,
+    signature_path: impl AsRef<Path>,
+    key: impl VerifyFromKey,
+    alg: Algorithm,
+    encoding: SignatureEncoding,
+) -> Result<bool, Error> {
+    let encoded = match fs::read_to_string(signature_path) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::FILE_IO_ERROR.with_source(error));
+        }
+    };
+    let signature = Signature::decode(encoded.trim(), alg, encoding)?;
+
+    verify_reader(open(path.as_ref())?, &signature, key)
+}
@@ -0,0 +1,467 @@
+use chrono::{DateTime, SubsecRound, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+#[cfg(feature = "http")]
+use crate::http::HttpFetcher;
+use crate::{
+    algorithms::{Algorithm, AlgorithmFamily},
+    clock,
+    crypto::{any::AnyVerifyingKey, SignFromKey},
+    data_integrity::{jcs_cryptosuite, sign_jcs, verify_jcs, ProofOptions},
+    errors::Error,
+    log,
+    resolver::{did_from_url, resolve_keys, DidResolver, ASSERTION_METHOD},
+    signer::sign,
+    vc::VERIFIABLE_CREDENTIAL_TYPE,
+    verifier::verify,
+};
+
+pub const DID_CONFIGURATION_CONTEXT: &str =
+    "https://identity.foundation/.well-known/did-configuration/v1";
+pub const VC_CONTEXT_V1: &str = "https://www.w3.org/2018/credentials/v1";
+pub const DOMAIN_LINKAGE_CREDENTIAL_TYPE: &str = "DomainLinkageCredential";
+pub const WELL_KNOWN_DID_CONFIGURATION_PATH: &str = "/.well-known/did-configuration.json";
+const JWT_TYP: &str = "JWT";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+struct LinkageSubject {
+    id: String,
+    origin: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+struct LinkageCredential {
+    #[serde(rename = "@context")]
+    context: Vec<String>,
+    issuer: String,
+    issuance_date: DateTime<Utc>,
+    expiration_date: DateTime<Utc>,
+    #[serde(rename = "type")]
+    types: Vec<String>,
+    credential_subject: LinkageSubject,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    proof: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LinkageHeader {
+    alg: Algorithm,
+    kid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    typ: Option<String>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct LinkageClaims {
+    exp: i64,
+    iss: String,
+    nbf: i64,
+    sub: String,
+    vc: LinkageCredential,
+}
+
+fn encode_segment(value: &impl Serialize) -> Result<String, Error> {
+    match serde_json::to_string(value) {
+        Ok(val) => Ok(base64_url::encode(val.as_bytes())),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::ENCODING_ERROR.with_source(error))
+        }
+    }
+}
+
+fn decode_segment<T: DeserializeOwned>(segment: &str) -> Result<T, Error> {
+    let decoded = match base64_url::decode(segment) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::DID_CONFIGURATION_MALFORMED.with_source(error));
+        }
+    };
+
+    match serde_json::from_slice(&decoded) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::DID_CONFIGURATION_MALFORMED.with_source(error))
+        }
+    }
+}
+
+fn from_value<T: DeserializeOwned>(value: &Value) -> Result<T, Error> {
+    match serde_json::from_value(value.clone()) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::DID_CONFIGURATION_MALFORMED.with_source(error))
+        }
+    }
+}
+
+fn to_value(value: &impl Serialize) -> Result<Value, Error> {
+    match serde_json::to_value(value) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::ENCODING_ERROR.with_source(error))
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct DomainLinkage {
+    pub did: String,
+    pub origin: String,
+    pub issued: DateTime<Utc>,
+    pub expires: DateTime<Utc>,
+}
+
+impl DomainLinkage {
+    pub fn new(did: &str, origin: &str, expires: DateTime<Utc>) -> Self {
+        DomainLinkage {
+            did: String::from(did),
+            origin: String::from(origin.trim_end_matches('/')),
+            issued: clock::now().trunc_subsecs(0),
+            expires: expires.trunc_subsecs(0),
+        }
+    }
+
+    pub fn with_issued(mut self, issued: DateTime<Utc>) -> Self {
+        self.issued = issued;
+        self
+    }
+
+    fn credential(&self) -> LinkageCredential {
+        LinkageCredential {
+            context: vec![
+                String::from(VC_CONTEXT_V1),
+                String::from(DID_CONFIGURATION_CONTEXT),
+            ],
+            issuer: self.did.clone(),
+            issuance_date: self.issued,
+            expiration_date: self.expires,
+            types: vec![
+                String::from(VERIFIABLE_CREDENTIAL_TYPE),
+                String::from(DOMAIN_LINKAGE_CREDENTIAL_TYPE),
+            ],
+            credential_subject: LinkageSubject {
+                id: self.did.clone(),
+                origin: self.origin.clone(),
+            },
+            proof: None,
+        }
+    }
+
+    fn from_credential(credential: &LinkageCredential) -> Result<Self, Error> {
+        if !credential
+            .context
+            .iter()
+            .any(|val| val == DID_CONFIGURATION_CONTEXT)
+            || !credential
+                .types
+                .iter()
+                .any(|val| val == DOMAIN_LINKAGE_CREDENTIAL_TYPE)
+            || credential.credential_subject.id != credential.issuer
+        {
+            return Err(Error::DID_CONFIGURATION_MALFORMED);
+        }
+
+        Ok(DomainLinkage {
+            did: credential.issuer.clone(),
+            origin: credential.credential_subject.origin.clone(),
+            issued: credential.issuance_date,
+            expires: credential.expiration_date,
+        })
+    }
+
+    pub fn sign_jwt(
+        &self,
+        kid: &str,
+        key: impl SignFromKey,
+        alg: Algorithm,
+    ) -> Result<LinkedDid, Error> {
+        if alg.get_family() == AlgorithmFamily::HMAC {
+            return Err(Error::ALGORITHM_NOT_ALLOWED.with_algorithm(alg));
+        }
+        if did_from_url(kid) != self.did {
+            return Err(Error::DID_CONFIGURATION_MALFORMED.with_key(kid));
+        }
+
+        let header = LinkageHeader {
+            alg,
+            kid: String::from(kid),
+            typ: Some(String::from(JWT_TYP)),
+        };
+        let claims = LinkageClaims {
+            exp: self.expires.timestamp(),
+            iss: self.did.clone(),
+            nbf: self.issued.timestamp(),
+            sub: self.did.clone(),
+            vc: self.credential(),
+        };
+        let content = format!("{}.{}", encode_segment(&header)?, encode_segment(&claims)?);
+        let signature = sign(&content, key, alg)?;
+
+        Ok(LinkedDid::Jwt(format!("{}.{}", content, signature)))
+    }
+
+    pub fn sign_data_integrity(
+        &self,
+        options: ProofOptions,
+        key: impl SignFromKey,
+        alg: Algorithm,
+    ) -> Result<LinkedDid, Error> {
+        if did_from_url(&options.verification_method) != self.did {
+            return Err(Error::DID_CONFIGURATION_MALFORMED.with_key(&options.verification_method));
+        }
+
+        let config = options.with_proof_purpose(ASSERTION_METHOD).to_value()?;
+        let mut document = to_value(&self.credential())?;
+        document["proof"] = sign_jcs(&document, &config, key, alg)?;
+        Ok(LinkedDid::DataIntegrity(document))
+    }
+
+    pub fn check_at(&self, origin: &str, at: DateTime<Utc>) -> Result<(), Error> {
+        if self.origin != origin.trim_end_matches('/') {
+            return Err(Error::DID_CONFIGURATION_ORIGIN_MISMATCH.with_key(&self.origin));
+        }
+        if at < self.issued || at >= self.expires {
+            return Err(Error::DID_CONFIGURATION_EXPIRED.with_key(&self.did));
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum LinkedDid {
+    Jwt(String),
+    DataIntegrity(Value),
+}
+
+impl LinkedDid {
+    pub fn from_value(value: &Value) -> Result<Self, Error> {
+        match value {
+            Value::String(val) => Ok(LinkedDid::Jwt(val.clone())),
+            Value::Object(_) => Ok(LinkedDid::DataIntegrity(value.clone())),
+            _ => Err(Error::DID_CONFIGURATION_MALFORMED),
+        }
+    }
+
+    pub fn to_value(&self) -> Value {
+        match self {
+            LinkedDid::Jwt(token) => Value::from(token.as_str()),
+            LinkedDid::DataIntegrity(document) => document.clone(),
+        }
+    }
+
+    fn jwt_parts(token: &str) -> Result<(LinkageHeader, LinkageClaims, &str, &str), Error> {
+        let (content, signature) = match token.rsplit_once('.') {
+            Some(val) => val,
+            None => return Err(Error::DID_CONFIGURATION_MALFORMED),
+        };
+        let (header, claims) = match content.split_once('.') {
+            Some(val) => val,
+            None => return Err(Error::DID_CONFIGURATION_MALFORMED),
+        };
+
+        let header: LinkageHeader = decode_segment(header)?;
+        let claims: LinkageClaims = decode_segment(claims)?;
+        Ok((header, claims, content, signature))
+    }
+
+    pub fn linkage(&self) -> Result<DomainLinkage, Error> {
+        match self {
+            LinkedDid::Jwt(token) => {
+                let (header, claims, _, _) = Self::jwt_parts(token)?;
+                let linkage = DomainLinkage::from_credential(&claims.vc)?;
+                if claims.iss != linkage.did
+                    || claims.sub != linkage.did
+                    || claims.exp != linkage.expires.timestamp()
+                    || claims.nbf != linkage.issued.timestamp()
+                    || claims.vc.proof.is_some()
+                    || did_from_url(&header.kid) != linkage.did
+                {
+                    return Err(Error::DID_CONFIGURATION_MALFORMED);
+                }
+                Ok(linkage)
+            }
+            LinkedDid::DataIntegrity(document) => {
+                let credential: LinkageCredential = from_value(document)?;
+                let linkage = DomainLinkage::from_credential(&credential)?;
+                match credential.proof {
+                    Some(_) => Ok(linkage),
+                    None => Err(Error::DID_CONFIGURATION_MALFORMED),
+                }
+            }
+        }
+    }
+
+    pub fn verify(&self, origin: &str, resolver: &impl DidResolver) -> Result<bool, Error> {
+        self.verify_at(origin, resolver, clock::now())
+    }
+
+    pub fn verify_at(
+        &self,
+        origin: &str,
+        resolver: &impl DidResolver,
+        at: DateTime<Utc>,
+    ) -> Result<bool, Error> {
+        let linkage = self.linkage()?;
+        linkage.check_at(origin, at)?;
+
+        match self {
+            LinkedDid::Jwt(token) => {
+                let (header, _, content, signature) = Self::jwt_parts(token)?;
+                for jwk in resolve_keys(resolver, &header.kid, ASSERTION_METHOD)? {
+                    if let Ok(key) = AnyVerifyingKey::from_jwk(&jwk) {
+                        if key.algorithm() == header.alg
+                            && verify(content, signature, &key, header.alg)?
+                        {
+                            return Ok(true);
+                        }
+                    }
+                }
+            }
+            LinkedDid::DataIntegrity(document) => {
+                let proof = match document.get("proof") {
+                    Some(val) => val,
+                    None => return Err(Error::DID_CONFIGURATION_MALFORMED),
+                };
+                let options = ProofOptions::from_value(proof)?;
+                if options.proof_purpose != ASSERTION_METHOD
+                    || did_from_url(&options.verification_method) != linkage.did
+                {
+                    return Ok(false);
+                }
+
+                for jwk in resolve_keys(resolver, &options.verification_method, ASSERTION_METHOD)? {
+                    if let Ok(key) = AnyVerifyingKey::from_jwk(&jwk) {
+                        if jcs_cryptosuite(key.algorithm()).is_ok()
+                            && verify_jcs(document, proof, &key)?
+                        {
+                            return Ok(true);
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct DidConfiguration {
+    pub linked_dids: Vec<LinkedDid>,
+}
+
+impl DidConfiguration {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_linked_did(mut self, linked_did: LinkedDid) -> Self {
+        self.linked_dids.push(linked_did);
+        self
+    }
+
+    pub fn url(origin: &str) -> String {
+        format!(
+            "{}{}",
+            origin.trim_end_matches('/'),
+            WELL_KNOWN_DID_CONFIGURATION_PATH
+        )
+    }
+
+    pub fn from_value(value: &Value) -> Result<Self, Error> {
+        if value.get("@context").and_then(Value::as_str) != Some(DID_CONFIGURATION_CONTEXT) {
+            return Err(Error::DID_CONFIGURATION_MALFORMED);
+        }
+
+        let linked_dids = match value.get("linked_dids") {
+            Some(Value::Array(val)) => val
+                .iter()
+                .map(LinkedDid::from_value)
+                .collect::<Result<Vec<LinkedDid>, Error>>()?,
+            _ => return Err(Error::DID_CONFIGURATION_MALFORMED),
+        };
+
+        Ok(DidConfiguration { linked_dids })
+    }
+
+    pub fn to_value(&self) -> Value {
+        serde_json::json!({
+            "@context": DID_CONFIGURATION_CONTEXT,
+            "linked_dids": self
+                .linked_dids
+                .iter()
+                .map(LinkedDid::to_value)
+                .collect::<Vec<Value>>(),
+        })
+    }
+
+    pub fn verify_did(
+        &self,
+        did: &str,
+        origin: &str,
+        resolver: &impl DidResolver,
+    ) -> Result<bool, Error> {
+        self.verify_did_at(did, origin, resolver, clock::now())
+    }
+
+    pub fn verify_did_at(
+        &self,
+        did: &str,
+        origin: &str,
+        resolver: &impl DidResolver,
+        at: DateTime<Utc>,
+    ) -> Result<bool, Error> {
+        let mut result = Err(Error::DID_CONFIGURATION_NOT_LINKED.with_key(did));
+        for linked_did in &self.linked_dids {
+            match linked_did.linkage() {
+                Ok(linkage) if linkage.did == did => {}
+                _ => continue,
+            }
+
+            match linked_did.verify_at(origin, resolver, at) {
+                Ok(true) => return Ok(true),
+                Ok(false) => result = Ok(false),
+                Err(error) if result.is_err() => result = Err(error),
+                Err(_) => {}
+            }
+        }
+
+        result
+    }
+
+    pub fn verified_dids(&self, origin: &str, resolver: &impl DidResolver) -> Vec<String> {
+        let mut dids: Vec<String> = Vec::new();
+        for linked_did in &self.linked_dids {
+            if let Ok(linkage) = linked_did.linkage() {
+                if !dids.contains(&linkage.did)
+                    && linked_did.verify(origin, resolver).unwrap_or(false)
+                {
+                    dids.push(linkage.did);
+                }
+            }
+        }
+        dids
+    }
+}
+
+#[cfg(feature = "http")]
+pub fn fetch_configuration(
+    fetcher: &impl HttpFetcher,
+    origin: &str,
+) -> Result<DidConfiguration, Error> {
+    let response = fetcher.get(&DidConfiguration::url(origin))?;
+    match serde_json::from_slice(&response.body) {
+        Ok(val) => DidConfiguration::from_value(&val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::DID_CONFIGURATION_MALFORMED.with_source(error))
+        }
+    }
+}
@@ -0,0 +1,561 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    algorithms::Algorithm,
+    clock,
+    crypto::{
+        any::AnyVerifyingKey,
+        x25519::{X25519PublicKey, X25519SecretKey},
+        SignFromKey,
+    },
+    encoding::{multibase_decode, multibase_encode},
+    errors::Error,
+    jwe::{ContentEncryption, Jwe},
+    jwk::Jwk,
+    log, nonce,
+    resolver::{did_from_url, resolve_keys, DidResolver, AUTHENTICATION, KEY_AGREEMENT},
+    signer::sign,
+    verifier::verify,
+};
+
+pub const PLAINTEXT_TYP: &str = "application/didcomm-plain+json";
+pub const SIGNED_TYP: &str = "application/didcomm-signed+json";
+pub const ENCRYPTED_TYP: &str = "application/didcomm-encrypted+json";
+
+const MULTIHASH_SHA2_256: [u8; 2] = [0x12, 0x20];
+
+fn decode_json<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    match serde_json::from_slice(bytes) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::DIDCOMM_MALFORMED.with_source(error))
+        }
+    }
+}
+
+fn decode_segment(segment: &str) -> Result<Vec<u8>, Error> {
+    match base64_url::decode(segment) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::DECODING_ERROR.with_source(error))
+        }
+    }
+}
+
+fn verify_with_jwk(
+    content: &str,
+    signature: &str,
+    jwk: &Jwk,
+    alg: Algorithm,
+) -> Result<bool, Error> {
+    let key = AnyVerifyingKey::from_jwk(jwk)?;
+    if key.algorithm() != alg {
+        return Err(Error::JWK_KEY_TYPE_MISMATCH);
+    }
+
+    verify(content, signature, key, alg)
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Message {
+    pub id: String,
+    pub typ: String,
+    #[serde(rename = "type")]
+    pub message_type: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub to: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub thid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pthid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_time: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires_time: Option<i64>,
+    pub body: Value,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub attachments: Vec<Attachment>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct AttachmentData {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub jws: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base64: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub json: Option<Value>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct Attachment {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filename: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub media_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lastmod_time: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub byte_count: Option<u64>,
+    pub data: AttachmentData,
+}
+
+pub fn attachment_hash(content: &[u8]) -> String {
+    let mut multihash = MULTIHASH_SHA2_256.to_vec();
+    multihash.extend_from_slice(&Sha256::digest(content));
+    multibase_encode(multihash)
+}
+
+impl Attachment {
+    fn with_data(id: &str, data: AttachmentData) -> Self {
+        Attachment {
+            id: Some(String::from(id)),
+            description: None,
+            filename: None,
+            media_type: None,
+            format: None,
+            lastmod_time: None,
+            byte_count: None,
+            data,
+        }
+    }
+
+    pub fn base64(id: &str, content: &[u8]) -> Self {
+        let mut attachment = Self::with_data(
+            id,
+            AttachmentData {
+                base64: Some(base64_url::encode(content)),
+                ..Default::default()
+            },
+        );
+        attachment.byte_count = Some(content.len() as u64);
+        attachment
+    }
+
+    pub fn json(id: &str, content: Value) -> Self {
+        Self::with_data(
+            id,
+            AttachmentData {
+                json: Some(content),
+                ..Default::default()
+            },
+        )
+    }
+
+    pub fn links(id: &str, links: &[&str], content: &[u8]) -> Self {
+        let mut attachment = Self::with_data(
+            id,
+            AttachmentData {
+                hash: Some(attachment_hash(content)),
+                links: links.iter().map(|link| String::from(*link)).collect(),
+                ..Default::default()
+            },
+        );
+        attachment.byte_count = Some(content.len() as u64);
+        attachment
+    }
+
+    pub fn media_type(mut self, media_type: &str) -> Self {
+        self.media_type = Some(String::from(media_type));
+        self
+    }
+
+    pub fn filename(mut self, filename: &str) -> Self {
+        self.filename = Some(String::from(filename));
+        self
+    }
+
+    pub fn content(&self) -> Result<Vec<u8>, Error> {
+        match (&self.data.base64, &self.data.json) {
+            (Some(val), None) => decode_segment(val),
+            (None, Some(val)) => match serde_json::to_vec(val) {
+                Ok(val) => Ok(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    Err(Error::ENCODING_ERROR.with_source(error))
+                }
+            },
+            _ => Err(Error::DIDCOMM_ATTACHMENT_MALFORMED),
+        }
+    }
+
+    pub fn with_hash(mut self) -> Result<Self, Error> {
+        self.data.hash = Some(attachment_hash(&self.content()?));
+        Ok(self)
+    }
+
+    pub fn sign(mut self, kid: &str, alg: Algorithm, key: impl SignFromKey) -> Result<Self, Error> {
+        let payload = base64_url::encode(&self.content()?);
+        let protected = json!({ "alg": alg.to_str(), "kid": kid });
+        let protected = base64_url::encode(protected.to_string().as_bytes());
+        let signature = sign(format!("{}.{}", protected, payload), key, alg)?;
+
+        self.data.jws = Some(json!({
+            "protected": protected,
+            "signature": signature,
+            "header": { "kid": kid },
+        }));
+        Ok(self)
+    }
+
+    pub fn verify_hash(&self, content: &[u8]) -> Result<(), Error> {
+        let hash = match &self.data.hash {
+            Some(val) => multibase_decode(val)?,
+            None => return Err(Error::DIDCOMM_ATTACHMENT_MALFORMED),
+        };
+
+        match hash.strip_prefix(MULTIHASH_SHA2_256.as_slice()) {
+            Some(digest) if digest == Sha256::digest(content).as_slice() => Ok(()),
+            Some(_) => Err(Error::DIDCOMM_ATTACHMENT_HASH_MISMATCH),
+            None => Err(Error::DIDCOMM_ATTACHMENT_MALFORMED),
+        }
+    }
+
+    pub fn verify(&self, resolver: &impl DidResolver) -> Result<Option<String>, Error> {
+        let content = self.content()?;
+        if self.data.hash.is_some() {
+            self.verify_hash(&content)?;
+        }
+
+        let jws = match &self.data.jws {
+            Some(val) => val,
+            None => return Ok(None),
+        };
+        let (protected, sig, kid) = match (
+            jws["protected"].as_str(),
+            jws["signature"].as_str(),
+            jws["header"]["kid"].as_str(),
+        ) {
+            (Some(protected), Some(sig), Some(kid)) => (protected, sig, kid),
+            _ => return Err(Error::DIDCOMM_ATTACHMENT_MALFORMED),
+        };
+
+        let header: Value = decode_json(&decode_segment(protected)?)?;
+        let alg = match header["alg"]
+            .as_str()
+            .and_then(|alg| alg.parse::<Algorithm>().ok())
+        {
+            Some(val) => val,
+            None => return Err(Error::UNKNOWN_ALGORITHM),
+        };
+        if header["kid"].as_str().is_some_and(|val| val != kid) {
+            return Err(Error::DIDCOMM_ATTACHMENT_MALFORMED);
+        }
+
+        let signing_input = format!("{}.{}", protected, base64_url::encode(&content));
+        for jwk in resolve_keys(resolver, kid, AUTHENTICATION)? {
+            if verify_with_jwk(&signing_input, sig, &jwk, alg)? {
+                return Ok(Some(String::from(kid)));
+            }
+        }
+
+        Err(Error::DIDCOMM_INVALID_SIGNATURE)
+    }
+}
+
+fn check_attachments(message: &Message) -> Result<(), Error> {
+    for attachment in &message.attachments {
+        if attachment.data.hash.is_some()
+            && (attachment.data.base64.is_some() || attachment.data.json.is_some())
+        {
+            attachment.verify_hash(&attachment.content()?)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, Debug)]
+pub struct UnpackedMessage {
+    pub message: Message,
+    pub encrypted: bool,
+    pub authenticated: bool,
+    pub encrypted_from: Option<String>,
+    pub encrypted_to: Vec<String>,
+    pub signed_by: Option<String>,
+}
+
+impl Message {
+    pub fn new(id: &str, message_type: &str, body: Value) -> Self {
+        Message {
+            id: String::from(id),
+            typ: String::from(PLAINTEXT_TYP),
+            message_type: String::from(message_type),
+            from: None,
+            to: Vec::new(),
+            thid: None,
+            pthid: None,
+            created_time: Some(clock::now().timestamp()),
+            expires_time: None,
+            body,
+            attachments: Vec::new(),
+        }
+    }
+
+    pub fn start_thread(mut self) -> Self {
+        self.thid = Some(nonce::generate());
+        self
+    }
+
+    pub fn attach(mut self, attachment: Attachment) -> Self {
+        self.attachments.push(attachment);
+        self
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let message: Message = decode_json(json.as_bytes())?;
+        if message.typ != PLAINTEXT_TYP {
+            return Err(Error::DIDCOMM_MALFORMED);
+        }
+
+        Ok(message)
+    }
+
+    pub fn pack_plaintext(&self) -> Result<String, Error> {
+        match serde_json::to_string(self) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENCODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn pack_signed(
+        &self,
+        kid: &str,
+        alg: Algorithm,
+        key: impl SignFromKey,
+    ) -> Result<String, Error> {
+        if self.from.as_deref() != Some(did_from_url(kid)) {
+            return Err(Error::DIDCOMM_SENDER_MISMATCH);
+        }
+
+        let protected = json!({ "typ": SIGNED_TYP, "alg": alg.to_str() });
+        let protected = base64_url::encode(protected.to_string().as_bytes());
+        let payload = base64_url::encode(self.pack_plaintext()?.as_bytes());
+        let signature = sign(format!("{}.{}", protected, payload), key, alg)?;
+
+        Ok(json!({
+            "payload": payload,
+            "signatures": [{
+                "protected": protected,
+                "signature": signature,
+                "header": { "kid": kid },
+            }],
+        })
+        .to_string())
+    }
+
+    pub fn pack_encrypted(
+        &self,
+        resolver: &impl DidResolver,
+        sender: Option<(&str, &X25519SecretKey)>,
+    ) -> Result<String, Error> {
+        self.encrypt_envelope(&self.pack_plaintext()?, resolver, sender)
+    }
+
+    pub fn pack_signed_encrypted(
+        &self,
+        kid: &str,
+        alg: Algorithm,
+        key: impl SignFromKey,
+        resolver: &impl DidResolver,
+        sender: Option<(&str, &X25519SecretKey)>,
+    ) -> Result<String, Error> {
+        self.encrypt_envelope(&self.pack_signed(kid, alg, key)?, resolver, sender)
+    }
+
+    fn encrypt_envelope(
+        &self,
+        content: &str,
+        resolver: &impl DidResolver,
+        sender: Option<(&str, &X25519SecretKey)>,
+    ) -> Result<String, Error> {
+        if let Some((skid, _)) = sender {
+            if self.from.as_deref() != Some(did_from_url(skid)) {
+                return Err(Error::DIDCOMM_SENDER_MISMATCH);
+            }
+        }
+
+        let mut recipients = Vec::new();
+        for to in &self.to {
+            for jwk in resolve_keys(resolver, to, KEY_AGREEMENT)? {
+                if let (Some(kid), Ok(key)) = (jwk.kid.clone(), X25519PublicKey::from_jwk(&jwk)) {
+                    recipients.push((kid, key));
+                }
+            }
+        }
+
+        let recipients: Vec<(&str, X25519PublicKey)> = recipients
+            .iter()
+            .map(|(kid, key)| (kid.as_str(), *key))
+            .collect();
+
+        Jwe::encrypt(
+            content.as_bytes(),
+            ENCRYPTED_TYP,
+            ContentEncryption::A256CBCHS512,
+            &recipients,
+            sender,
+        )?
+        .to_json()
+    }
+}
+
+fn unpack_signed(envelope: &Value, resolver: &impl DidResolver) -> Result<UnpackedMessage, Error> {
+    let signature = match envelope["signatures"].as_array().map(|val| val.as_slice()) {
+        Some([val]) => val,
+        _ => return Err(Error::DIDCOMM_MALFORMED),
+    };
+
+    let (payload, protected, sig, kid) = match (
+        envelope["payload"].as_str(),
+        signature["protected"].as_str(),
+        signature["signature"].as_str(),
+        signature["header"]["kid"].as_str(),
+    ) {
+        (Some(payload), Some(protected), Some(sig), Some(kid)) => (payload, protected, sig, kid),
+        _ => return Err(Error::DIDCOMM_MALFORMED),
+    };
+
+    let header: Value = decode_json(&decode_segment(protected)?)?;
+    let alg = match header["alg"]
+        .as_str()
+        .and_then(|alg| alg.parse::<Algorithm>().ok())
+    {
+        Some(val) => val,
+        None => return Err(Error::UNKNOWN_ALGORITHM),
+    };
+
+    let content = format!("{}.{}", protected, payload);
+    let mut verified = false;
+    for jwk in resolve_keys(resolver, kid, AUTHENTICATION)? {
+        if verify_with_jwk(&content, sig, &jwk, alg)? {
+            verified = true;
+            break;
+        }
+    }
+
+    if !verified {
+        return Err(Error::DIDCOMM_INVALID_SIGNATURE);
+    }
+
+    let message: Message = decode_json(&decode_segment(payload)?)?;
+    if message.typ != PLAINTEXT_TYP {
+        return Err(Error::DIDCOMM_MALFORMED);
+    }
+    if message.from.as_deref() != Some(did_from_url(kid)) {
+        return Err(Error::DIDCOMM_SENDER_MISMATCH);
+    }
+    check_attachments(&message)?;
+
+    Ok(UnpackedMessage {
+        message,
+        encrypted: false,
+        authenticated: false,
+        encrypted_from: None,
+        encrypted_to: Vec::new(),
+        signed_by: Some(String::from(kid)),
+    })
+}
+
+fn unpack_encrypted(
+    envelope: Value,
+    resolver: &impl DidResolver,
+    secrets: &[(&str, &X25519SecretKey)],
+) -> Result<UnpackedMessage, Error> {
+    let jwe: Jwe = match serde_json::from_value(envelope) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::JWE_MALFORMED.with_source(error));
+        }
+    };
+
+    let recipient_kids = jwe.recipient_kids();
+    let sender_kid = jwe.sender_kid()?;
+    let (_, plaintext) = match &sender_kid {
+        Some(skid) => {
+            let sender_key = match resolve_keys(resolver, skid, KEY_AGREEMENT)?.first() {
+                Some(jwk) => X25519PublicKey::from_jwk(jwk)?,
+                None => return Err(Error::DID_VERIFICATION_METHOD_NOT_FOUND),
+            };
+            jwe.decrypt_any(secrets, Some(&sender_key))?
+        }
+        None => jwe.decrypt_any(secrets, None)?,
+    };
+
+    let mut unpacked = unpack_value(decode_json(&plaintext)?, resolver, secrets)?;
+    if let Some(skid) = &sender_kid {
+        if unpacked.message.from.as_deref() != Some(did_from_url(skid)) {
+            return Err(Error::DIDCOMM_SENDER_MISMATCH);
+        }
+        unpacked.authenticated = true;
+        unpacked.encrypted_from = sender_kid;
+    }
+
+    unpacked.encrypted = true;
+    unpacked.encrypted_to = recipient_kids.into_iter().map(String::from).collect();
+    Ok(unpacked)
+}
+
+fn unpack_value(
+    envelope: Value,
+    resolver: &impl DidResolver,
+    secrets: &[(&str, &X25519SecretKey)],
+) -> Result<UnpackedMessage, Error> {
+    if envelope.get("ciphertext").is_some() {
+        return unpack_encrypted(envelope, resolver, secrets);
+    }
+
+    if envelope.get("signatures").is_some() {
+        return unpack_signed(&envelope, resolver);
+    }
+
+    let message: Message = match serde_json::from_value(envelope) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::DIDCOMM_MALFORMED.with_source(error));
+        }
+    };
+
+    if message.typ != PLAINTEXT_TYP {
+        return Err(Error::DIDCOMM_MALFORMED);
+    }
+    check_attachments(&message)?;
+
+    Ok(UnpackedMessage {
+        message,
+        encrypted: false,
+        authenticated: false,
+        encrypted_from: None,
+        encrypted_to: Vec::new(),
+        signed_by: None,
+    })
+}
+
+pub fn unpack(
+    envelope: &str,
+    resolver: &impl DidResolver,
+    secrets: &[(&str, &X25519SecretKey)],
+) -> Result<UnpackedMessage, Error> {
+    unpack_value(decode_json(envelope.as_bytes())?, resolver, secrets)
+}
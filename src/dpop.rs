@@ -0,0 +1,273 @@
+use std::time::Duration;
+
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    algorithms::Algorithm,
+    clock,
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    errors::Error,
+    hash::HashAlgorithm,
+    jwk::Jwk,
+    jwt::{check_key_algorithm, reject_alg_none},
+    log, nonce,
+    signer::sign,
+    verifier::verify,
+};
+
+pub const DPOP_TYP: &str = "dpop+jwt";
+pub const DEFAULT_DPOP_MAX_AGE: Duration = Duration::from_secs(300);
+pub const DEFAULT_DPOP_LEEWAY: Duration = Duration::from_secs(5);
+
+#[derive(Serialize, Deserialize, Clone)]
+struct DpopHeader {
+    typ: String,
+    alg: Algorithm,
+    jwk: Jwk,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct DpopClaims {
+    pub jti: String,
+    pub htm: String,
+    pub htu: String,
+    pub iat: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ath: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+}
+
+impl DpopClaims {
+    pub fn new(htm: &str, htu: &str) -> Self {
+        Self::new_with_rng(htm, htu, &mut OsRng)
+    }
+
+    pub fn new_with_rng(htm: &str, htu: &str, rng: &mut (impl CryptoRng + RngCore)) -> Self {
+        DpopClaims {
+            jti: nonce::generate_with_rng(rng),
+            htm: String::from(htm),
+            htu: String::from(htu),
+            iat: clock::now().timestamp(),
+            ath: None,
+            nonce: None,
+        }
+    }
+
+    pub fn with_access_token(mut self, access_token: &str) -> Self {
+        self.ath = Some(access_token_hash(access_token));
+        self
+    }
+
+    pub fn with_nonce(mut self, nonce: &str) -> Self {
+        self.nonce = Some(String::from(nonce));
+        self
+    }
+}
+
+pub fn access_token_hash(access_token: &str) -> String {
+    base64_url::encode(&HashAlgorithm::Sha256.digest(access_token))
+}
+
+fn strip_query(uri: &str) -> &str {
+    match uri.find(['?', '#']) {
+        Some(index) => &uri[..index],
+        None => uri,
+    }
+}
+
+fn encode_segment(value: &impl Serialize) -> Result<String, Error> {
+    match serde_json::to_string(value) {
+        Ok(val) => Ok(base64_url::encode(val.as_bytes())),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::ENCODING_ERROR.with_source(error))
+        }
+    }
+}
+
+fn decode_segment<T: DeserializeOwned>(segment: &str) -> Result<T, Error> {
+    let decoded = match base64_url::decode(segment) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::DPOP_MALFORMED.with_source(error));
+        }
+    };
+
+    match serde_json::from_slice(&decoded) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::DPOP_MALFORMED.with_source(error))
+        }
+    }
+}
+
+pub struct DpopValidation<'a> {
+    pub htm: &'a str,
+    pub htu: &'a str,
+    pub access_token: Option<&'a str>,
+    pub nonce: Option<&'a str>,
+    pub jkt: Option<&'a str>,
+    pub max_age: Duration,
+    pub leeway: Duration,
+}
+
+impl<'a> DpopValidation<'a> {
+    pub fn new(htm: &'a str, htu: &'a str) -> Self {
+        DpopValidation {
+            htm,
+            htu,
+            access_token: None,
+            nonce: None,
+            jkt: None,
+            max_age: DEFAULT_DPOP_MAX_AGE,
+            leeway: DEFAULT_DPOP_LEEWAY,
+        }
+    }
+
+    pub fn access_token(mut self, access_token: &'a str) -> Self {
+        self.access_token = Some(access_token);
+        self
+    }
+
+    pub fn nonce(mut self, nonce: &'a str) -> Self {
+        self.nonce = Some(nonce);
+        self
+    }
+
+    pub fn jkt(mut self, jkt: &'a str) -> Self {
+        self.jkt = Some(jkt);
+        self
+    }
+
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    pub fn leeway(mut self, leeway: Duration) -> Self {
+        self.leeway = leeway;
+        self
+    }
+}
+
+#[derive(Clone)]
+pub struct DpopProof {
+    pub alg: Algorithm,
+    pub jwk: Jwk,
+    pub claims: DpopClaims,
+    token: String,
+}
+
+impl DpopProof {
+    pub fn sign(claims: DpopClaims, key: &AnySigningKey) -> Result<Self, Error> {
+        let alg = key.algorithm();
+        let jwk = match key.verifying_key().to_jwk() {
+            Ok(val) => val,
+            Err(error) => return Err(Error::DPOP_INVALID_KEY.with_source(error.to_string())),
+        };
+
+        let header = DpopHeader {
+            typ: String::from(DPOP_TYP),
+            alg,
+            jwk: jwk.clone(),
+        };
+        let content = format!("{}.{}", encode_segment(&header)?, encode_segment(&claims)?);
+        let signature = sign(&content, key, alg)?;
+
+        Ok(DpopProof {
+            alg,
+            jwk,
+            claims,
+            token: format!("{}.{}", content, signature),
+        })
+    }
+
+    pub fn from_token(token: &str) -> Result<Self, Error> {
+        let parts: Vec<&str> = token.split('.').collect();
+        if parts.len() != 3 {
+            return Err(Error::DPOP_MALFORMED);
+        }
+
+        reject_alg_none(&decode_segment::<Value>(parts[0])?)?;
+        let header: DpopHeader = decode_segment(parts[0])?;
+        if header.typ != DPOP_TYP {
+            return Err(Error::DPOP_INVALID_TYP);
+        }
+
+        if header.jwk.d.is_some() || header.jwk.private.is_some() {
+            return Err(Error::DPOP_INVALID_KEY);
+        }
+
+        Ok(DpopProof {
+            alg: header.alg,
+            jwk: header.jwk,
+            claims: decode_segment(parts[1])?,
+            token: String::from(token),
+        })
+    }
+
+    pub fn to_token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn jkt(&self) -> Result<String, Error> {
+        self.jwk.thumbprint()
+    }
+
+    pub fn verifying_key(&self) -> Result<AnyVerifyingKey, Error> {
+        match AnyVerifyingKey::from_jwk(&self.jwk) {
+            Ok(val) => Ok(val),
+            Err(error) => Err(Error::DPOP_INVALID_KEY.with_source(error.to_string())),
+        }
+    }
+
+    pub fn verify(&self, validation: &DpopValidation) -> Result<bool, Error> {
+        let key = self.verifying_key()?;
+        check_key_algorithm(&key, self.alg)?;
+
+        let verified = match self.token.rsplit_once('.') {
+            Some((content, signature)) => verify(content, signature, key, self.alg)?,
+            None => return Err(Error::DPOP_MALFORMED),
+        };
+        if !verified {
+            return Ok(false);
+        }
+
+        let now = clock::now().timestamp();
+        let max_age = validation.max_age.as_secs() as i64;
+        let leeway = validation.leeway.as_secs() as i64;
+        if self.claims.iat > now + leeway || self.claims.iat < now - max_age - leeway {
+            return Ok(false);
+        }
+
+        if self.claims.htm != validation.htm
+            || strip_query(&self.claims.htu) != strip_query(validation.htu)
+        {
+            return Ok(false);
+        }
+
+        if validation
+            .access_token
+            .is_some_and(|token| self.claims.ath != Some(access_token_hash(token)))
+        {
+            return Ok(false);
+        }
+
+        if validation
+            .nonce
+            .is_some_and(|nonce| self.claims.nonce.as_deref() != Some(nonce))
+        {
+            return Ok(false);
+        }
+
+        match validation.jkt {
+            Some(jkt) => Ok(self.jkt()? == jkt),
+            None => Ok(true),
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use serde::Serialize;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::{errors::Error, log};
+
+pub const MULTIBASE_BASE58BTC: char = 'z';
+pub const MULTIBASE_BASE32: char = 'b';
+const BASE32_ALPHABET: &[u8; 32] = b"abcdefghijklmnopqrstuvwxyz234567";
+const CHECKSUM_LENGTH: usize = 4;
+
+fn checksum(bytes: &[u8]) -> [u8; CHECKSUM_LENGTH] {
+    let digest = Sha256::digest(Sha256::digest(bytes));
+    let mut checksum = [0u8; CHECKSUM_LENGTH];
+    checksum.copy_from_slice(&digest[..CHECKSUM_LENGTH]);
+    checksum
+}
+
+pub fn base58_encode(bytes: impl AsRef<[u8]>) -> String {
+    bs58::encode(bytes).into_string()
+}
+
+pub fn base58_decode(value: &str) -> Result<Vec<u8>, Error> {
+    match bs58::decode(value).into_vec() {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::DECODING_ERROR.with_source(error))
+        }
+    }
+}
+
+pub fn base58check_encode(bytes: impl AsRef<[u8]>) -> String {
+    let bytes = bytes.as_ref();
+    let mut data = Vec::with_capacity(bytes.len() + CHECKSUM_LENGTH);
+    data.extend_from_slice(bytes);
+    data.extend_from_slice(&checksum(bytes));
+    base58_encode(data)
+}
+
+pub fn base58check_decode(value: &str) -> Result<Vec<u8>, Error> {
+    let mut data = base58_decode(value)?;
+    if data.len() < CHECKSUM_LENGTH {
+        return Err(Error::BASE58_INVALID_CHECKSUM);
+    }
+
+    let payload_length = data.len() - CHECKSUM_LENGTH;
+    if checksum(&data[..payload_length]) != data[payload_length..] {
+        return Err(Error::BASE58_INVALID_CHECKSUM);
+    }
+
+    data.truncate(payload_length);
+    Ok(data)
+}
+
+pub fn multibase_encode(bytes: impl AsRef<[u8]>) -> String {
+    format!("{}{}", MULTIBASE_BASE58BTC, base58_encode(bytes))
+}
+
+pub fn multibase_decode(value: &str) -> Result<Vec<u8>, Error> {
+    match value.strip_prefix(MULTIBASE_BASE58BTC) {
+        Some(val) => base58_decode(val),
+        None => Err(Error::DECODING_ERROR),
+    }
+}
+
+pub fn base32_encode(bytes: impl AsRef<[u8]>) -> String {
+    let mut encoded = String::with_capacity(bytes.as_ref().len().div_ceil(5) * 8);
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+    for byte in bytes.as_ref() {
+        buffer = (buffer << 8) | *byte as u16;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            encoded.push(BASE32_ALPHABET[((buffer >> bits) & 0x1f) as usize] as char);
+        }
+    }
+
+    if bits > 0 {
+        encoded.push(BASE32_ALPHABET[((buffer << (5 - bits)) & 0x1f) as usize] as char);
+    }
+    encoded
+}
+
+pub fn base32_decode(value: &str) -> Result<Vec<u8>, Error> {
+    let mut decoded = Vec::with_capacity(value.len() * 5 / 8);
+    let mut buffer: u16 = 0;
+    let mut bits = 0;
+    for char in value.bytes() {
+        let index = match BASE32_ALPHABET
+            .iter()
+            .position(|val| *val == char.to_ascii_lowercase())
+        {
+            Some(val) => val as u16,
+            None => return Err(Error::DECODING_ERROR),
+        };
+
+        buffer = ((buffer << 5) | index) & 0x1fff;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            decoded.push((buffer >> bits) as u8);
+        }
+    }
+
+    match bits < 5 && buffer & ((1 << bits) - 1) == 0 {
+        true => Ok(decoded),
+        false => Err(Error::DECODING_ERROR),
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum JsonFormat {
+    #[default]
+    Canonical,
+    Pretty,
+}
+
+pub fn canonical_json(value: &Value) -> Vec<u8> {
+    let mut canonical = Vec::new();
+    write_canonical_json(value, &mut canonical, None);
+    canonical
+}
+
+pub fn to_json(value: &impl Serialize, format: JsonFormat) -> Result<String, Error> {
+    let value = match serde_json::to_value(value) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::ENCODING_ERROR.with_source(error));
+        }
+    };
+
+    let mut json = Vec::new();
+    match format {
+        JsonFormat::Canonical => write_canonical_json(&value, &mut json, None),
+        JsonFormat::Pretty => write_canonical_json(&value, &mut json, Some(0)),
+    }
+    match String::from_utf8(json) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::ENCODING_ERROR.with_source(error))
+        }
+    }
+}
+
+fn write_indent(canonical: &mut Vec<u8>, indent: Option<usize>) {
+    if let Some(depth) = indent {
+        canonical.push(b'\n');
+        canonical.extend(std::iter::repeat_n(b' ', depth * 2));
+    }
+}
+
+fn write_canonical_json(value: &Value, canonical: &mut Vec<u8>, indent: Option<usize>) {
+    let inner = indent.map(|depth| depth + 1);
+    match value {
+        Value::Array(items) if !items.is_empty() => {
+            canonical.push(b'[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    canonical.push(b',');
+                }
+                write_indent(canonical, inner);
+                write_canonical_json(item, canonical, inner);
+            }
+            write_indent(canonical, indent);
+            canonical.push(b']');
+        }
+        Value::Object(map) if !map.is_empty() => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.encode_utf16().cmp(b.0.encode_utf16()));
+            canonical.push(b'{');
+            for (index, (key, item)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    canonical.push(b',');
+                }
+                write_indent(canonical, inner);
+                canonical.extend_from_slice(Value::from(key.as_str()).to_string().as_bytes());
+                canonical.push(b':');
+                if indent.is_some() {
+                    canonical.push(b' ');
+                }
+                write_canonical_json(item, canonical, inner);
+            }
+            write_indent(canonical, indent);
+            canonical.push(b'}');
+        }
+        _ => canonical.extend_from_slice(value.to_string().as_bytes()),
+    }
+}
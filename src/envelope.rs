@@ -0,0 +1,226 @@
+use chrono::{DateTime, Utc};
+use coset::cbor::value::Value;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    algorithms::Algorithm,
+    clock,
+    crypto::{signature::Signature, SignFromKey, VerifyFromKey},
+    errors::Error,
+    log, signer, verifier,
+};
+
+const ALG: &str = "alg";
+const KID: &str = "kid";
+const SIG: &str = "sig";
+const CREATED: &str = "created";
+const TDATE_TAG: u64 = 0;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EnvelopeFormat {
+    #[default]
+    Json,
+    Cbor,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct JsonEnvelope {
+    alg: Algorithm,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kid: Option<String>,
+    sig: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created: Option<DateTime<Utc>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SignatureEnvelope {
+    signature: Signature,
+    kid: Option<String>,
+    created: Option<DateTime<Utc>>,
+}
+
+fn malformed(error: impl std::error::Error + Send + Sync + 'static) -> Error {
+    log::error(error.to_string().as_str());
+    Error::SIGNATURE_ENVELOPE_MALFORMED.with_source(error)
+}
+
+impl SignatureEnvelope {
+    pub fn new(signature: Signature) -> Self {
+        SignatureEnvelope {
+            signature,
+            kid: None,
+            created: None,
+        }
+    }
+
+    pub fn sign(
+        message: impl AsRef<[u8]>,
+        key: impl SignFromKey,
+        alg: Algorithm,
+    ) -> Result<Self, Error> {
+        Ok(Self::new(signer::sign_signature(message, key, alg)?).with_created(clock::now()))
+    }
+
+    pub fn with_kid(mut self, kid: &str) -> Self {
+        self.kid = Some(String::from(kid));
+        self
+    }
+
+    pub fn with_created(mut self, created: DateTime<Utc>) -> Self {
+        self.created = Some(created);
+        self
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        self.signature.algorithm()
+    }
+
+    pub fn kid(&self) -> Option<&str> {
+        self.kid.as_deref()
+    }
+
+    pub fn created(&self) -> Option<DateTime<Utc>> {
+        self.created
+    }
+
+    pub fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    pub fn verify(
+        &self,
+        message: impl AsRef<[u8]>,
+        key: impl VerifyFromKey,
+    ) -> Result<bool, Error> {
+        verifier::verify_signature(message, &self.signature, key)
+    }
+
+    pub fn verify_with_algorithm(
+        &self,
+        message: impl AsRef<[u8]>,
+        key: impl VerifyFromKey,
+        alg: Algorithm,
+    ) -> Result<bool, Error> {
+        if self.algorithm() != alg {
+            return Err(
+                Error::SIGNATURE_ENVELOPE_ALGORITHM_MISMATCH.with_algorithm(self.algorithm())
+            );
+        }
+
+        self.verify(message, key)
+    }
+
+    pub fn encode(&self, format: EnvelopeFormat) -> Result<Vec<u8>, Error> {
+        match format {
+            EnvelopeFormat::Json => Ok(self.to_json()?.into_bytes()),
+            EnvelopeFormat::Cbor => self.to_cbor(),
+        }
+    }
+
+    pub fn decode(envelope: &[u8], format: EnvelopeFormat) -> Result<Self, Error> {
+        match format {
+            EnvelopeFormat::Json => match std::str::from_utf8(envelope) {
+                Ok(val) => Self::from_json(val),
+                Err(error) => Err(malformed(error)),
+            },
+            EnvelopeFormat::Cbor => Self::from_cbor(envelope),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        let envelope = JsonEnvelope {
+            alg: self.algorithm(),
+            kid: self.kid.clone(),
+            sig: self.signature.to_base64url(),
+            created: self.created,
+        };
+
+        match serde_json::to_string(&envelope) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENCODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn from_json(envelope: &str) -> Result<Self, Error> {
+        let envelope: JsonEnvelope = match serde_json::from_str(envelope) {
+            Ok(val) => val,
+            Err(error) => return Err(malformed(error)),
+        };
+
+        Ok(SignatureEnvelope {
+            signature: Signature::from_base64url(&envelope.sig, envelope.alg)?,
+            kid: envelope.kid,
+            created: envelope.created,
+        })
+    }
+
+    pub fn to_cbor(&self) -> Result<Vec<u8>, Error> {
+        let mut entries = vec![
+            (Value::from(ALG), Value::from(self.algorithm().to_str())),
+            (
+                Value::from(SIG),
+                Value::Bytes(self.signature.as_bytes().to_vec()),
+            ),
+        ];
+        if let Some(kid) = &self.kid {
+            entries.push((Value::from(KID), Value::from(kid.as_str())));
+        }
+        if let Some(created) = self.created {
+            entries.push((
+                Value::from(CREATED),
+                Value::Tag(TDATE_TAG, Box::new(Value::from(created.to_rfc3339()))),
+            ));
+        }
+
+        let mut bytes = Vec::new();
+        match coset::cbor::ser::into_writer(&Value::Map(entries), &mut bytes) {
+            Ok(_) => Ok(bytes),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENCODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn from_cbor(envelope: &[u8]) -> Result<Self, Error> {
+        let entries = match coset::cbor::de::from_reader(envelope) {
+            Ok(Value::Map(val)) => val,
+            Ok(_) => return Err(Error::SIGNATURE_ENVELOPE_MALFORMED),
+            Err(error) => return Err(malformed(error)),
+        };
+
+        let mut alg = None;
+        let mut sig = None;
+        let mut kid = None;
+        let mut created = None;
+        for (label, value) in entries {
+            match (label.as_text(), value) {
+                (Some(ALG), Value::Text(val)) => alg = Some(val.parse::<Algorithm>()?),
+                (Some(SIG), Value::Bytes(val)) => sig = Some(val),
+                (Some(KID), Value::Text(val)) => kid = Some(val),
+                (Some(CREATED), Value::Tag(TDATE_TAG, val)) => match *val {
+                    Value::Text(val) => match DateTime::parse_from_rfc3339(&val) {
+                        Ok(val) => created = Some(val.with_timezone(&Utc)),
+                        Err(error) => return Err(malformed(error)),
+                    },
+                    _ => return Err(Error::SIGNATURE_ENVELOPE_MALFORMED),
+                },
+                _ => return Err(Error::SIGNATURE_ENVELOPE_MALFORMED),
+            }
+        }
+
+        match (alg, sig) {
+            (Some(alg), Some(sig)) => Ok(SignatureEnvelope {
+                signature: Signature::new(sig, alg),
+                kid,
+                created,
+            }),
+            _ => Err(Error::SIGNATURE_ENVELOPE_MALFORMED),
+        }
+    }
+}
@@ -1,35 +1,848 @@
-use std::fmt::Display;
+use crate::algorithms::Algorithm;
 
-#[derive(Debug)]
-pub struct Error(&'static str);
+type Source = Box<dyn std::error::Error + Send + Sync + 'static>;
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0}")]
+    Message(&'static str),
+    #[error("{message}")]
+    Context {
+        message: &'static str,
+        algorithm: Option<Algorithm>,
+        key: Option<String>,
+        #[source]
+        source: Option<Source>,
+    },
+}
 
 impl Error {
-    pub const PRIVATE_KEY_IDENTIFICATION_ERROR: Error = Error("Error identifying private key");
-    pub const PUBLIC_KEY_IDENTIFICATION_ERROR: Error = Error("Error identifying public key");
-    pub const UNKNOWN_ALGORITHM: Error = Error("Unusable or unidentified algorithm");
-    pub const SIGNING_FAILED: Error = Error("Failed to sign the content");
-    pub const ENCODING_ERROR: Error = Error("Invalid encoding");
-    pub const DECODING_ERROR: Error = Error("Invalid decoding");
-    pub const SIGNATURE_IDENTIFICATION_FAILED: Error = Error("Failed to identify the signature");
-    pub const EC_PEM_ERROR: Error = Error("Failed to parse EC pem");
-    pub const JWT_HEADER_DESERIALIZING_ERROR: Error = Error("Failed to deserialize jwt header");
-    pub const JWT_PAYLOAD_DESERIALIZING_ERROR: Error = Error("Failed to deserialize jwt payload");
-    pub const JWT_EXPIRED: Error = Error("JWT token is expired");
-    pub const JWT_UTF8_ERROR: Error = Error("Base64 decoded JWT content is not utf8");
-    pub const JWT_NO_SIGNATURE_FOUND: Error = Error("JWT signature not found");
-    pub const JWT_TOKEN_NOT_SIGNED: Error = Error("Unsigned JWT token");
+    pub const PRIVATE_KEY_IDENTIFICATION_ERROR: Error =
+        Error::Message("Error identifying private key");
+    pub const PUBLIC_KEY_IDENTIFICATION_ERROR: Error =
+        Error::Message("Error identifying public key");
+    pub const UNKNOWN_ALGORITHM: Error = Error::Message("Unusable or unidentified algorithm");
+    pub const SIGNING_FAILED: Error = Error::Message("Failed to sign the content");
+    pub const ENCODING_ERROR: Error = Error::Message("Invalid encoding");
+    pub const DECODING_ERROR: Error = Error::Message("Invalid decoding");
+    pub const SIGNATURE_IDENTIFICATION_FAILED: Error =
+        Error::Message("Failed to identify the signature");
+    pub const SIGNATURE_ENCODING_UNSUPPORTED: Error =
+        Error::Message("Signature encoding is not supported for the algorithm");
+    pub const KEY_FORMAT_UNSUPPORTED: Error =
+        Error::Message("Key format is not supported for the key type");
+    pub const EC_PEM_ERROR: Error = Error::Message("Failed to parse EC pem");
+    pub const JWT_HEADER_DESERIALIZING_ERROR: Error =
+        Error::Message("Failed to deserialize jwt header");
+    pub const JWT_PAYLOAD_DESERIALIZING_ERROR: Error =
+        Error::Message("Failed to deserialize jwt payload");
+    pub const JWT_EXPIRED: Error = Error::Message("JWT token is expired");
+    pub const JWT_UTF8_ERROR: Error = Error::Message("Base64 decoded JWT content is not utf8");
+    pub const JWT_NO_SIGNATURE_FOUND: Error = Error::Message("JWT signature not found");
+    pub const JWT_TOKEN_NOT_SIGNED: Error = Error::Message("Unsigned JWT token");
     pub const JWT_PAYLOAD_MISSING_FIELD_EXP: Error =
-        Error("JWT payload is missing the \"exp\" field");
+        Error::Message("JWT payload is missing the \"exp\" field");
     pub const JWT_PAYLOAD_FIELD_EXP_IDENTIFICATION_ERROR: Error =
-        Error("JWT can't extract the value for field \"exp\"");
-    pub const FAILED_TO_IDENTIFY_ALGORITHM: Error = Error("Failed to identify the algorithm used");
+        Error::Message("JWT can't extract the value for field \"exp\"");
+    pub const FAILED_TO_IDENTIFY_ALGORITHM: Error =
+        Error::Message("Failed to identify the algorithm used");
     pub const FAILED_TO_CONVERT_TIMESTAMP_TO_DATETTIME: Error =
-        Error("Failed to convert the timestamp number into a datetime instance");
-    pub const HMAC_KEY_ERROR: Error = Error("Failed to create the HMAC key");
+        Error::Message("Failed to convert the timestamp number into a datetime instance");
+    pub const HMAC_KEY_ERROR: Error = Error::Message("Failed to create the HMAC key");
+    pub const SD_JWT_MALFORMED: Error = Error::Message("Malformed SD-JWT");
+    pub const SD_JWT_INVALID_DISCLOSURE: Error = Error::Message("Invalid SD-JWT disclosure");
+    pub const SD_JWT_DUPLICATE_DIGEST: Error = Error::Message("Duplicate SD-JWT disclosure digest");
+    pub const SD_JWT_UNSUPPORTED_HASH_ALG: Error = Error::Message("Unsupported SD-JWT \"_sd_alg\"");
+    pub const SD_JWT_RESERVED_CLAIM: Error =
+        Error::Message("Reserved claim names can't be selectively disclosable");
+    pub const SD_JWT_CLAIM_NOT_FOUND: Error =
+        Error::Message("Selectively disclosable claim not found");
+    pub const SD_JWT_KEY_BINDING_MISSING: Error =
+        Error::Message("SD-JWT key binding JWT not found");
+    pub const SD_JWT_VC_INVALID_TYP: Error =
+        Error::Message("SD-JWT VC header \"typ\" is not vc+sd-jwt");
+    pub const SD_JWT_VC_MISSING_VCT: Error =
+        Error::Message("SD-JWT VC is missing the \"vct\" claim");
+    pub const SD_JWT_VC_MISSING_ISSUER: Error =
+        Error::Message("SD-JWT VC is missing the \"iss\" claim");
+    pub const SD_JWT_VC_MISSING_CNF: Error =
+        Error::Message("SD-JWT VC is missing the \"cnf\" claim");
+    pub const SD_JWT_VC_INVALID_STATUS: Error =
+        Error::Message("SD-JWT VC \"status\" claim is invalid");
+    pub const SD_JWT_VC_ISSUER_RESOLUTION_ERROR: Error =
+        Error::Message("Failed to locate the SD-JWT VC issuer key");
+    pub const BBS_INVALID_DISCLOSED_INDEX: Error =
+        Error::Message("Invalid BBS disclosed message index");
+    pub const BLS_EMPTY_AGGREGATE: Error =
+        Error::Message("Nothing to aggregate or mismatched inputs");
+    pub const JWK_PARSING_ERROR: Error = Error::Message("Failed to parse the JWK");
+    pub const JWK_MISSING_MEMBER: Error = Error::Message("JWK is missing a required member");
+    pub const JWK_KEY_TYPE_MISMATCH: Error =
+        Error::Message("JWK key type or curve doesn't match the key");
+    pub const MULTICODEC_ERROR: Error = Error::Message("Invalid or unexpected multicodec prefix");
+    pub const STATUS_LIST_INVALID_SIZE: Error = Error::Message("Invalid status list size");
+    pub const STATUS_LIST_INDEX_OUT_OF_RANGE: Error =
+        Error::Message("Status list index is out of range");
+    pub const STATUS_LIST_INVALID_ENTRY: Error =
+        Error::Message("Invalid or missing credentialStatus entry");
+    pub const STATUS_LIST_INVALID_CREDENTIAL: Error =
+        Error::Message("Invalid status list credential");
+    pub const STATUS_LIST_PURPOSE_MISMATCH: Error =
+        Error::Message("Status list purpose doesn't match");
+    pub const KEY_AGREEMENT_FAILED: Error =
+        Error::Message("Key agreement produced a non-contributory secret");
+    pub const JWE_MALFORMED: Error = Error::Message("Malformed JWE");
+    pub const JWE_UNSUPPORTED_ALGORITHM: Error =
+        Error::Message("Unsupported JWE \"alg\" or \"enc\"");
+    pub const JWE_RECIPIENT_NOT_FOUND: Error =
+        Error::Message("JWE has no recipient for the given key");
+    pub const JWE_ENCRYPTION_FAILED: Error = Error::Message("Failed to encrypt the JWE content");
+    pub const JWE_DECRYPTION_FAILED: Error = Error::Message("Failed to decrypt the JWE content");
+    pub const DID_RESOLUTION_ERROR: Error = Error::Message("Failed to resolve the DID");
+    pub const DID_VERIFICATION_METHOD_NOT_FOUND: Error =
+        Error::Message("No matching verification method in the DID document");
+    pub const DIDCOMM_MALFORMED: Error = Error::Message("Malformed DIDComm message");
+    pub const DIDCOMM_SENDER_MISMATCH: Error =
+        Error::Message("DIDComm envelope sender doesn't match the message \"from\"");
+    pub const DIDCOMM_INVALID_SIGNATURE: Error =
+        Error::Message("DIDComm message signature is invalid");
+    pub const COSE_MALFORMED: Error = Error::Message("Malformed COSE structure");
+    pub const COSE_MISSING_PAYLOAD: Error = Error::Message("COSE structure has no payload");
+    pub const CWT_NOT_SIGNED: Error = Error::Message("Unsigned CWT");
+    pub const CWT_CLAIMS_DESERIALIZING_ERROR: Error =
+        Error::Message("Failed to deserialize CWT claims");
+    pub const MDOC_MALFORMED: Error = Error::Message("Malformed mdoc structure");
+    pub const MDOC_UNSUPPORTED_DEVICE_KEY: Error = Error::Message("Unsupported mdoc device key");
+    pub const MDOC_ELEMENT_NOT_FOUND: Error = Error::Message("mdoc data element not found");
+    pub const KMS_REQUEST_FAILED: Error = Error::Message("Remote KMS signing request failed");
+    pub const HARDWARE_KEY_NOT_FOUND: Error = Error::Message("Hardware-backed key not found");
+    pub const HARDWARE_KEY_BACKEND_ERROR: Error = Error::Message("Hardware key backend failure");
+    pub const LEDGER_TRANSPORT_ERROR: Error = Error::Message("Ledger device communication failed");
+    pub const LEDGER_REQUEST_REJECTED: Error = Error::Message("Ledger device rejected the request");
+    pub const INVALID_DERIVATION_PATH: Error = Error::Message("Invalid BIP-32 derivation path");
+    pub const KEYSTORE_ERROR: Error = Error::Message("Key store failure");
+    pub const KEYSTORE_KEY_NOT_FOUND: Error = Error::Message("Key id not found in key store");
+    pub const KEYSTORE_DUPLICATE_KEY_ID: Error =
+        Error::Message("Key id already exists in key store");
+    pub const KEYSTORE_LOCKED: Error = Error::Message("Key store is locked");
+    pub const KEYSTORE_WRONG_PASSPHRASE: Error =
+        Error::Message("Key store passphrase is incorrect");
+    pub const KEYSTORE_MALFORMED: Error = Error::Message("Malformed key store file");
+    pub const KEYSTORE_KEY_RETIRED: Error =
+        Error::Message("Key has been retired and its grace period has expired");
+    pub const KEYSTORE_KEY_REVOKED: Error = Error::Message("Key has been revoked");
+    pub const KEYSTORE_KEY_OUTSIDE_VALIDITY: Error =
+        Error::Message("Key is not valid at the given time");
+    pub const MNEMONIC_INVALID: Error = Error::Message("Invalid BIP-39 mnemonic");
+    pub const KEY_DERIVATION_FAILED: Error = Error::Message("Key derivation failed");
+    pub const KDF_INVALID_PARAMETERS: Error = Error::Message("Invalid key derivation parameters");
+    pub const KDF_UNSUPPORTED_VERSION: Error =
+        Error::Message("Unsupported key derivation header version");
+    pub const PKCS8_UNSUPPORTED_KEY_TYPE: Error = Error::Message("Key type has no PKCS#8 encoding");
+    pub const PKCS8_DECRYPTION_FAILED: Error =
+        Error::Message("Unable to decrypt PKCS#8 private key");
+    pub const PKCS12_ERROR: Error = Error::Message("Unable to read PKCS#12 bundle");
+    pub const PKCS12_WRONG_PASSWORD: Error = Error::Message("PKCS#12 bundle password is incorrect");
+    pub const X509_CERTIFICATE_ERROR: Error = Error::Message("Unable to parse X.509 certificate");
+    pub const X509_CHAIN_INVALID: Error =
+        Error::Message("Unable to validate X.509 certificate chain");
+    pub const X509_CHAIN_UNTRUSTED: Error =
+        Error::Message("X.509 certificate chain does not terminate at a trust anchor");
+    pub const X509_CERTIFICATE_EXPIRED: Error =
+        Error::Message("X.509 certificate is outside of its validity period");
+    pub const JWT_X5C_MISSING: Error =
+        Error::Message("JWT header does not contain an x5c certificate chain");
+    pub const ALGORITHM_NOT_ALLOWED: Error =
+        Error::Message("Algorithm is not allowed by the verification policy");
+    pub const JWT_MALFORMED: Error = Error::Message("Malformed JWT");
+    pub const JWT_ALG_NONE: Error =
+        Error::Message("Unsecured JWTs with \"alg\": \"none\" are rejected");
+    pub const JWT_ALGORITHM_KEY_MISMATCH: Error =
+        Error::Message("JWT algorithm does not match the verifying key type");
+    pub const JWT_PAYLOAD_FIELD_NBF_IDENTIFICATION_ERROR: Error =
+        Error::Message("JWT can't extract the value for field \"nbf\"");
+    pub const FILE_IO_ERROR: Error = Error::Message("Unable to read or write file");
+    pub const JWKS_KEY_NOT_FOUND: Error =
+        Error::Message("No key in the JWK set matches the token kid and algorithm");
+    pub const HTTP_REQUEST_FAILED: Error = Error::Message("HTTP request failed");
+    pub const DPOP_MALFORMED: Error = Error::Message("Malformed DPoP proof");
+    pub const DPOP_INVALID_TYP: Error = Error::Message("DPoP proof \"typ\" must be \"dpop+jwt\"");
+    pub const DPOP_INVALID_KEY: Error =
+        Error::Message("DPoP proof \"jwk\" must be an asymmetric public key");
+    pub const JWT_CNF_MISSING: Error = Error::Message("JWT payload is missing \"cnf\"");
+    pub const JWT_CNF_INVALID: Error = Error::Message("Invalid JWT \"cnf\" claim");
+    pub const NONCE_ENTROPY_TOO_LOW: Error =
+        Error::Message("Nonce entropy must be at least 8 bytes");
+    pub const NONCE_MALFORMED: Error = Error::Message("Malformed nonce expiry");
+    pub const REPLAY_DETECTED: Error = Error::Message("Nonce or \"jti\" has already been used");
+    pub const JWT_MISSING_JTI: Error = Error::Message("JWT payload is missing \"jti\"");
+    pub const ETHR_INVALID_DID: Error = Error::Message("Invalid did:ethr identifier");
+    pub const ETHR_UNKNOWN_NETWORK: Error = Error::Message("Unknown did:ethr network");
+    pub const ETHR_REGISTRY_ERROR: Error = Error::Message("Malformed ERC-1056 registry response");
+    pub const CAIP_INVALID_CHAIN_ID: Error = Error::Message("Invalid CAIP-2 chain id");
+    pub const CAIP_INVALID_ACCOUNT_ID: Error = Error::Message("Invalid CAIP-10 account id");
+    pub const SIWE_MALFORMED: Error = Error::Message("Malformed Sign-In with Ethereum message");
+    pub const BASE58_INVALID_CHECKSUM: Error = Error::Message("Invalid base58check checksum");
+    pub const RSA_UNSUPPORTED_KEY_SIZE: Error =
+        Error::Message("RSA key size must be 2048, 3072 or 4096 bits");
+    pub const KEY_POLICY_VIOLATION: Error = Error::Message("Key does not satisfy the key policy");
+    pub const KEY_FORMAT_UNRECOGNIZED: Error = Error::Message("Unable to detect the key format");
+    pub const KEY_FORMAT_AMBIGUOUS: Error =
+        Error::Message("Raw key bytes match more than one key type");
+    pub const VERIFICATION_METHOD_MALFORMED: Error =
+        Error::Message("Malformed DID verification method");
+    pub const VERIFICATION_METHOD_UNSUPPORTED: Error =
+        Error::Message("Unsupported verification method type for this key");
+    pub const WEB_INVALID_DID: Error = Error::Message("Invalid did:web identifier");
+    pub const JWS_MALFORMED: Error = Error::Message("Malformed JWS JSON serialization");
+    pub const JWS_THRESHOLD_NOT_MET: Error =
+        Error::Message("Not enough valid JWS signatures to meet the threshold");
+    pub const FROST_INVALID_PARAMETERS: Error =
+        Error::Message("FROST threshold must be at least 2 and at most the number of participants");
+    pub const FROST_INVALID_SHARE: Error = Error::Message("Invalid FROST key share");
+    pub const FROST_MALFORMED: Error =
+        Error::Message("Malformed FROST commitment or signature share");
+    pub const FROST_INVALID_COMMITMENTS: Error =
+        Error::Message("Invalid FROST signing commitments");
+    pub const FROST_INVALID_SIGNATURE_SHARE: Error =
+        Error::Message("FROST signature share failed verification");
+    pub const SHAMIR_INVALID_PARAMETERS: Error =
+        Error::Message("Secret sharing threshold must be at least 2 and at most the share count");
+    pub const SHAMIR_INVALID_SHARE: Error = Error::Message("Invalid or corrupted secret share");
+    pub const SHAMIR_INSUFFICIENT_SHARES: Error =
+        Error::Message("Not enough secret shares to meet the threshold");
+    pub const SHAMIR_RECOVERY_FAILED: Error =
+        Error::Message("Secret shares do not belong to the same secret");
+    pub const EXPORT_BUNDLE_MALFORMED: Error = Error::Message("Malformed exported key bundle");
+    pub const EXPORT_BUNDLE_UNSUPPORTED_VERSION: Error =
+        Error::Message("Unsupported exported key bundle version");
+    pub const EXPORT_BUNDLE_PROTECTION_MISMATCH: Error =
+        Error::Message("Exported key bundle is protected by a different method");
+    pub const SIGNATURE_CONTEXT_UNSUPPORTED: Error =
+        Error::Message("Signature context strings are not supported for the algorithm");
+    pub const SIGNATURE_CONTEXT_INVALID: Error =
+        Error::Message("Signature context must be 1 to 255 bytes");
+    pub const CONDITIONAL_PROOF_MALFORMED: Error =
+        Error::Message("Malformed conditional proof verification method");
+    pub const CONDITIONAL_PROOF_NOT_SATISFIED: Error =
+        Error::Message("Signatures do not satisfy the conditional proof");
+    pub const TIMESTAMP_MALFORMED: Error = Error::Message("Malformed RFC 3161 timestamp token");
+    pub const TIMESTAMP_REJECTED: Error =
+        Error::Message("Timestamp authority rejected the request");
+    pub const TIMESTAMP_MISMATCH: Error =
+        Error::Message("Timestamp token does not match the request or data");
+    pub const TIMESTAMP_INVALID_SIGNATURE: Error =
+        Error::Message("Timestamp token signature is invalid");
+    pub const OPENPGP_MALFORMED: Error = Error::Message("Malformed OpenPGP key data");
+    pub const OPENPGP_ENCRYPTED_KEY: Error =
+        Error::Message("Passphrase-protected OpenPGP secret keys are not supported");
+    pub const OPENPGP_UNSUPPORTED_KEY: Error =
+        Error::Message("OpenPGP key version or algorithm is not supported");
+    pub const ECIES_MALFORMED: Error = Error::Message("Malformed ECIES message");
+    pub const ECIES_UNSUPPORTED_KEY: Error =
+        Error::Message("ECIES requires a P-256 or secp256k1 key");
+    pub const ECIES_ENCRYPTION_FAILED: Error =
+        Error::Message("Failed to encrypt the ECIES message");
+    pub const ECIES_DECRYPTION_FAILED: Error =
+        Error::Message("Failed to decrypt the ECIES message");
+    pub const NACL_MALFORMED: Error = Error::Message("Malformed NaCl box or nonce");
+    pub const NACL_ENCRYPTION_FAILED: Error = Error::Message("Failed to encrypt the NaCl box");
+    pub const NACL_DECRYPTION_FAILED: Error = Error::Message("Failed to decrypt the NaCl box");
+    pub const DIDCOMM_ATTACHMENT_MALFORMED: Error =
+        Error::Message("Malformed DIDComm attachment data");
+    pub const DIDCOMM_ATTACHMENT_HASH_MISMATCH: Error =
+        Error::Message("DIDComm attachment content does not match its hash");
+    pub const OID4VCI_PROOF_MALFORMED: Error = Error::Message("Malformed OID4VCI proof");
+    pub const OID4VCI_PROOF_INVALID_TYP: Error =
+        Error::Message("OID4VCI proof \"typ\" must be \"openid4vci-proof+jwt\"");
+    pub const OID4VCI_PROOF_INVALID_KEY: Error = Error::Message(
+        "OID4VCI proof must carry exactly one of \"kid\" or an asymmetric public \"jwk\"",
+    );
+    pub const OID4VP_MALFORMED: Error =
+        Error::Message("Malformed OID4VP VP token or authorization response");
+    pub const OID4VP_INVALID_KEY: Error = Error::Message(
+        "OID4VP responses must be signed with an asymmetric key and carry a \"kid\"",
+    );
+    pub const VC_MALFORMED: Error = Error::Message("Malformed verifiable credential");
+    pub const VC_JWT_INVALID_TYP: Error =
+        Error::Message("VC-JWT \"typ\" must be \"vc+jwt\" and \"cty\" must be \"vc\"");
+    pub const VC_JWT_CLAIM_MISMATCH: Error =
+        Error::Message("VC-JWT claims do not match the credential they secure");
+    pub const VC_COSE_INVALID_TYP: Error = Error::Message(
+        "VC-COSE \"typ\" must be \"application/vc+cose\" and \"cty\" must be \"application/vc\"",
+    );
+    pub const KEY_PURPOSE_NOT_ALLOWED: Error =
+        Error::Message("Key is not allowed to be used for this purpose");
+    pub const INPUT_TOO_LARGE: Error = Error::Message("Input exceeds the configured size limit");
+    pub const INPUT_NOT_CANONICAL: Error =
+        Error::Message("Input is not canonical unpadded base64url");
+    pub const INPUT_TOO_DEEP: Error =
+        Error::Message("JSON input exceeds the configured nesting depth");
+    pub const JWE_INVALID_KEY_LENGTH: Error =
+        Error::Message("Key encryption key length does not match the JWE algorithm");
+    pub const ECDH_UNSUPPORTED_KEY: Error =
+        Error::Message("ECDH requires P-256 or P-384 keys on the same curve");
+    pub const DATA_INTEGRITY_PROOF_MALFORMED: Error =
+        Error::Message("Malformed Data Integrity proof");
+    pub const DATA_INTEGRITY_INVALID_OPTIONS: Error =
+        Error::Message("Invalid Data Integrity proof options");
+    pub const DATA_INTEGRITY_PROOF_EXPIRED: Error =
+        Error::Message("Data Integrity proof is expired or not yet valid");
+    pub const DATA_INTEGRITY_PROOF_MISMATCH: Error =
+        Error::Message("Data Integrity proof purpose, domain or challenge does not match");
+    pub const MULTIHASH_MALFORMED: Error = Error::Message("Malformed multihash");
+    pub const MULTIHASH_UNSUPPORTED: Error = Error::Message("Unsupported multihash function");
+    pub const CID_MALFORMED: Error = Error::Message("Malformed or unsupported CID");
+    pub const ION_INVALID_DID: Error = Error::Message("Invalid did:ion long-form identifier");
+    pub const ION_INVALID_STATE: Error =
+        Error::Message("did:ion initial state does not match its suffix or delta hash");
+    pub const ION_INVALID_DOCUMENT: Error = Error::Message("Invalid did:ion document content");
+    pub const WEBVH_INVALID_DID: Error = Error::Message("Invalid did:webvh identifier");
+    pub const WEBVH_INVALID_LOG: Error = Error::Message("Invalid did:webvh log entry");
+    pub const WEBVH_UNAUTHORIZED: Error =
+        Error::Message("did:webvh log entry is not signed by an authorized update key");
+    pub const WEBVH_DEACTIVATED: Error = Error::Message("did:webvh DID has been deactivated");
+    pub const KEL_INVALID_EVENT: Error = Error::Message("Invalid or out-of-order key event");
+    pub const KEL_THRESHOLD_NOT_MET: Error =
+        Error::Message("Key event signatures do not meet the signing threshold");
+    pub const KEL_ROTATION_NOT_COMMITTED: Error =
+        Error::Message("Rotated keys were not pre-committed by the previous establishment event");
+    pub const ZCAP_MALFORMED: Error = Error::Message("Malformed authorization capability");
+    pub const ZCAP_INVALID_CHAIN: Error =
+        Error::Message("Capability chain is invalid, too long or does not start at a trusted root");
+    pub const ZCAP_UNAUTHORIZED: Error =
+        Error::Message("Capability does not authorize the requested invocation");
+    pub const ZCAP_EXPIRED: Error = Error::Message("Capability has expired");
+    pub const ZCAP_INVALID_PROOF: Error =
+        Error::Message("Capability proof is not signed by the capability controller");
+    pub const JWT_SIGNATURE_INVALID: Error = Error::Message("JWT signature is invalid");
+    pub const JWT_REFRESH_NOT_ALLOWED: Error =
+        Error::Message("JWT is outside its refresh window or session lifetime");
+    pub const JWT_REFRESH_KEY_MISMATCH: Error =
+        Error::Message("Refresh key does not belong to the DID that signed the JWT");
+    pub const JWT_NOT_NESTED: Error = Error::Message("JWE does not carry a nested JWT (cty: JWT)");
+    pub const JWT_NOT_YET_VALID: Error = Error::Message("JWT is not valid yet (nbf)");
+    pub const SIGNING_CONTEXT_MISMATCH: Error =
+        Error::Message("Message is not framed for the expected signing context");
+    pub const SIGNATURE_INVALID: Error = Error::Message("Signature is invalid");
+    pub const JWE_DUPLICATE_RECIPIENT: Error =
+        Error::Message("JWE recipient key ID is listed more than once");
+    pub const JWE_DECOMPRESSION_LIMIT_EXCEEDED: Error =
+        Error::Message("JWE payload exceeds the decompression size or ratio limit");
+    pub const VC_SCHEMA_VALIDATION_FAILED: Error =
+        Error::Message("Credential does not conform to its credentialSchema");
+    pub const VC_SCHEMA_NOT_FOUND: Error =
+        Error::Message("No schema is registered for the credentialSchema id");
+    pub const VC_SCHEMA_UNSUPPORTED: Error = Error::Message("Unsupported credentialSchema type");
+    pub const VC_SCHEMA_MALFORMED: Error = Error::Message("Malformed credential schema");
+    pub const TRUST_POLICY_ISSUER_NOT_TRUSTED: Error =
+        Error::Message("Issuer is not on the trust list");
+    pub const TRUST_POLICY_ALGORITHM_NOT_ALLOWED: Error =
+        Error::Message("Algorithm or cryptosuite is not allowed by the trust policy");
+    pub const TRUST_POLICY_CREDENTIAL_TOO_OLD: Error =
+        Error::Message("Credential is older than the trust policy allows");
+    pub const CREDENTIAL_REVOKED: Error = Error::Message("Credential has been revoked");
+    pub const CREDENTIAL_SUSPENDED: Error = Error::Message("Credential is suspended");
+    pub const STATUS_LIST_NOT_FOUND: Error = Error::Message("Status list credential not found");
+    pub const STATUS_LIST_ISSUER_MISMATCH: Error =
+        Error::Message("Status list credential was not issued by the credential issuer");
+    pub const TEST_VECTOR_MISMATCH: Error =
+        Error::Message("Output does not match the published test vector");
+    pub const SIGNATURE_ENVELOPE_MALFORMED: Error = Error::Message("Malformed signature envelope");
+    pub const SIGNATURE_ENVELOPE_ALGORITHM_MISMATCH: Error =
+        Error::Message("Signature envelope algorithm does not match the expected algorithm");
+    pub const SSH_SIGNATURE_MALFORMED: Error = Error::Message("Malformed SSH signature");
+    pub const SSH_SIGNATURE_NAMESPACE_MISMATCH: Error =
+        Error::Message("SSH signature namespace does not match the expected namespace");
+    pub const DID_CONFIGURATION_MALFORMED: Error =
+        Error::Message("Malformed DID configuration or domain linkage credential");
+    pub const DID_CONFIGURATION_ORIGIN_MISMATCH: Error =
+        Error::Message("Domain linkage credential origin does not match the expected origin");
+    pub const DID_CONFIGURATION_EXPIRED: Error =
+        Error::Message("Domain linkage credential is expired or not yet valid");
+    pub const DID_CONFIGURATION_NOT_LINKED: Error = Error::Message(
+        "DID configuration does not contain a domain linkage credential for the DID",
+    );
+    pub const JWT_REVOKED: Error = Error::Message("JWT has been revoked");
+    pub const DATA_INTEGRITY_PROOF_CHAIN_INVALID: Error = Error::Message(
+        "Data Integrity previousProof references a missing, duplicate or later proof",
+    );
+    pub const DID_WALLET_MALFORMED: Error = Error::Message("Malformed encrypted DID wallet");
+    pub const DID_WALLET_UNSUPPORTED_VERSION: Error =
+        Error::Message("Unsupported encrypted DID wallet version");
+    pub const ATTESTATION_MALFORMED: Error = Error::Message("Malformed key attestation statement");
+    pub const ATTESTATION_UNSUPPORTED_FORMAT: Error =
+        Error::Message("Unsupported key attestation format or credential key type");
+    pub const ATTESTATION_INVALID: Error =
+        Error::Message("Key attestation signature, challenge or certificate is invalid");
+    pub const ATTESTATION_KEY_MISMATCH: Error =
+        Error::Message("Attested key does not match the credential key");
+    pub const ATTESTATION_NOT_HARDWARE_BACKED: Error =
+        Error::Message("Attested key is not backed by secure hardware");
+    pub const COSE_KEY_UNSUPPORTED: Error = Error::Message("Unsupported COSE key type or curve");
+    pub const WEBAUTHN_ASSERTION_MALFORMED: Error = Error::Message("Malformed WebAuthn assertion");
+    pub const WEBAUTHN_CLIENT_DATA_MISMATCH: Error = Error::Message(
+        "WebAuthn client data type, challenge or origin does not match the expected values",
+    );
+    pub const WEBAUTHN_RP_ID_MISMATCH: Error =
+        Error::Message("WebAuthn authenticator data was not created for the expected RP ID");
+    pub const WEBAUTHN_USER_NOT_PRESENT: Error =
+        Error::Message("WebAuthn assertion does not have the user present flag");
+    pub const WEBAUTHN_USER_NOT_VERIFIED: Error =
+        Error::Message("WebAuthn assertion does not have the user verified flag");
+    pub const WEBAUTHN_SIGN_COUNT_REGRESSED: Error = Error::Message(
+        "WebAuthn signature counter did not increase; the authenticator may be cloned",
+    );
+    pub const ENTROPY_SOURCE_FAILED: Error =
+        Error::Message("Entropy source failed to produce output");
+    pub const ENTROPY_HEALTH_CHECK_FAILED: Error =
+        Error::Message("Entropy source failed its health checks; random output is disabled");
+    pub const SELF_TEST_FAILED: Error =
+        Error::Message("Cryptographic self-test failed a known-answer check");
+    pub const BENCH_REPORT_MALFORMED: Error =
+        Error::Message("Benchmark report could not be parsed");
+    pub const BENCH_REGRESSION: Error =
+        Error::Message("Benchmark result regressed beyond the allowed tolerance");
+}
+
+const ERROR_CODES: [(i32, Error); 243] = [
+    (1, Error::PRIVATE_KEY_IDENTIFICATION_ERROR),
+    (2, Error::PUBLIC_KEY_IDENTIFICATION_ERROR),
+    (3, Error::UNKNOWN_ALGORITHM),
+    (4, Error::SIGNING_FAILED),
+    (5, Error::ENCODING_ERROR),
+    (6, Error::DECODING_ERROR),
+    (7, Error::SIGNATURE_IDENTIFICATION_FAILED),
+    (8, Error::SIGNATURE_ENCODING_UNSUPPORTED),
+    (9, Error::KEY_FORMAT_UNSUPPORTED),
+    (10, Error::EC_PEM_ERROR),
+    (11, Error::JWT_HEADER_DESERIALIZING_ERROR),
+    (12, Error::JWT_PAYLOAD_DESERIALIZING_ERROR),
+    (13, Error::JWT_EXPIRED),
+    (14, Error::JWT_UTF8_ERROR),
+    (15, Error::JWT_NO_SIGNATURE_FOUND),
+    (16, Error::JWT_TOKEN_NOT_SIGNED),
+    (17, Error::JWT_PAYLOAD_MISSING_FIELD_EXP),
+    (18, Error::JWT_PAYLOAD_FIELD_EXP_IDENTIFICATION_ERROR),
+    (19, Error::FAILED_TO_IDENTIFY_ALGORITHM),
+    (20, Error::FAILED_TO_CONVERT_TIMESTAMP_TO_DATETTIME),
+    (21, Error::HMAC_KEY_ERROR),
+    (22, Error::SD_JWT_MALFORMED),
+    (23, Error::SD_JWT_INVALID_DISCLOSURE),
+    (24, Error::SD_JWT_DUPLICATE_DIGEST),
+    (25, Error::SD_JWT_UNSUPPORTED_HASH_ALG),
+    (26, Error::SD_JWT_RESERVED_CLAIM),
+    (27, Error::SD_JWT_CLAIM_NOT_FOUND),
+    (28, Error::SD_JWT_KEY_BINDING_MISSING),
+    (29, Error::SD_JWT_VC_INVALID_TYP),
+    (30, Error::SD_JWT_VC_MISSING_VCT),
+    (31, Error::SD_JWT_VC_MISSING_ISSUER),
+    (32, Error::SD_JWT_VC_MISSING_CNF),
+    (33, Error::SD_JWT_VC_INVALID_STATUS),
+    (34, Error::SD_JWT_VC_ISSUER_RESOLUTION_ERROR),
+    (35, Error::BBS_INVALID_DISCLOSED_INDEX),
+    (36, Error::BLS_EMPTY_AGGREGATE),
+    (37, Error::JWK_PARSING_ERROR),
+    (38, Error::JWK_MISSING_MEMBER),
+    (39, Error::JWK_KEY_TYPE_MISMATCH),
+    (40, Error::MULTICODEC_ERROR),
+    (41, Error::STATUS_LIST_INVALID_SIZE),
+    (42, Error::STATUS_LIST_INDEX_OUT_OF_RANGE),
+    (43, Error::STATUS_LIST_INVALID_ENTRY),
+    (44, Error::STATUS_LIST_INVALID_CREDENTIAL),
+    (45, Error::STATUS_LIST_PURPOSE_MISMATCH),
+    (46, Error::KEY_AGREEMENT_FAILED),
+    (47, Error::JWE_MALFORMED),
+    (48, Error::JWE_UNSUPPORTED_ALGORITHM),
+    (49, Error::JWE_RECIPIENT_NOT_FOUND),
+    (50, Error::JWE_ENCRYPTION_FAILED),
+    (51, Error::JWE_DECRYPTION_FAILED),
+    (52, Error::DID_RESOLUTION_ERROR),
+    (53, Error::DID_VERIFICATION_METHOD_NOT_FOUND),
+    (54, Error::DIDCOMM_MALFORMED),
+    (55, Error::DIDCOMM_SENDER_MISMATCH),
+    (56, Error::DIDCOMM_INVALID_SIGNATURE),
+    (57, Error::COSE_MALFORMED),
+    (58, Error::COSE_MISSING_PAYLOAD),
+    (59, Error::CWT_NOT_SIGNED),
+    (60, Error::CWT_CLAIMS_DESERIALIZING_ERROR),
+    (61, Error::MDOC_MALFORMED),
+    (62, Error::MDOC_UNSUPPORTED_DEVICE_KEY),
+    (63, Error::MDOC_ELEMENT_NOT_FOUND),
+    (64, Error::KMS_REQUEST_FAILED),
+    (65, Error::HARDWARE_KEY_NOT_FOUND),
+    (66, Error::HARDWARE_KEY_BACKEND_ERROR),
+    (67, Error::LEDGER_TRANSPORT_ERROR),
+    (68, Error::LEDGER_REQUEST_REJECTED),
+    (69, Error::INVALID_DERIVATION_PATH),
+    (70, Error::KEYSTORE_ERROR),
+    (71, Error::KEYSTORE_KEY_NOT_FOUND),
+    (72, Error::KEYSTORE_DUPLICATE_KEY_ID),
+    (73, Error::KEYSTORE_LOCKED),
+    (74, Error::KEYSTORE_WRONG_PASSPHRASE),
+    (75, Error::KEYSTORE_MALFORMED),
+    (76, Error::KEYSTORE_KEY_RETIRED),
+    (77, Error::KEYSTORE_KEY_REVOKED),
+    (78, Error::KEYSTORE_KEY_OUTSIDE_VALIDITY),
+    (79, Error::MNEMONIC_INVALID),
+    (80, Error::KEY_DERIVATION_FAILED),
+    (81, Error::KDF_INVALID_PARAMETERS),
+    (82, Error::KDF_UNSUPPORTED_VERSION),
+    (83, Error::PKCS8_UNSUPPORTED_KEY_TYPE),
+    (84, Error::PKCS8_DECRYPTION_FAILED),
+    (85, Error::PKCS12_ERROR),
+    (86, Error::PKCS12_WRONG_PASSWORD),
+    (87, Error::X509_CERTIFICATE_ERROR),
+    (88, Error::X509_CHAIN_INVALID),
+    (89, Error::X509_CHAIN_UNTRUSTED),
+    (90, Error::X509_CERTIFICATE_EXPIRED),
+    (91, Error::JWT_X5C_MISSING),
+    (92, Error::ALGORITHM_NOT_ALLOWED),
+    (93, Error::JWT_MALFORMED),
+    (94, Error::JWT_ALG_NONE),
+    (95, Error::JWT_ALGORITHM_KEY_MISMATCH),
+    (96, Error::JWT_PAYLOAD_FIELD_NBF_IDENTIFICATION_ERROR),
+    (97, Error::FILE_IO_ERROR),
+    (98, Error::JWKS_KEY_NOT_FOUND),
+    (99, Error::HTTP_REQUEST_FAILED),
+    (100, Error::DPOP_MALFORMED),
+    (101, Error::DPOP_INVALID_TYP),
+    (102, Error::DPOP_INVALID_KEY),
+    (103, Error::JWT_CNF_MISSING),
+    (104, Error::JWT_CNF_INVALID),
+    (105, Error::NONCE_ENTROPY_TOO_LOW),
+    (106, Error::NONCE_MALFORMED),
+    (107, Error::REPLAY_DETECTED),
+    (108, Error::JWT_MISSING_JTI),
+    (109, Error::ETHR_INVALID_DID),
+    (110, Error::ETHR_UNKNOWN_NETWORK),
+    (111, Error::ETHR_REGISTRY_ERROR),
+    (112, Error::CAIP_INVALID_CHAIN_ID),
+    (113, Error::CAIP_INVALID_ACCOUNT_ID),
+    (114, Error::SIWE_MALFORMED),
+    (115, Error::BASE58_INVALID_CHECKSUM),
+    (116, Error::RSA_UNSUPPORTED_KEY_SIZE),
+    (117, Error::KEY_POLICY_VIOLATION),
+    (118, Error::KEY_FORMAT_UNRECOGNIZED),
+    (119, Error::KEY_FORMAT_AMBIGUOUS),
+    (120, Error::VERIFICATION_METHOD_MALFORMED),
+    (121, Error::VERIFICATION_METHOD_UNSUPPORTED),
+    (122, Error::WEB_INVALID_DID),
+    (123, Error::JWS_MALFORMED),
+    (124, Error::JWS_THRESHOLD_NOT_MET),
+    (125, Error::FROST_INVALID_PARAMETERS),
+    (126, Error::FROST_INVALID_SHARE),
+    (127, Error::FROST_MALFORMED),
+    (128, Error::FROST_INVALID_COMMITMENTS),
+    (129, Error::FROST_INVALID_SIGNATURE_SHARE),
+    (130, Error::SHAMIR_INVALID_PARAMETERS),
+    (131, Error::SHAMIR_INVALID_SHARE),
+    (132, Error::SHAMIR_INSUFFICIENT_SHARES),
+    (133, Error::SHAMIR_RECOVERY_FAILED),
+    (134, Error::EXPORT_BUNDLE_MALFORMED),
+    (135, Error::EXPORT_BUNDLE_UNSUPPORTED_VERSION),
+    (136, Error::EXPORT_BUNDLE_PROTECTION_MISMATCH),
+    (137, Error::SIGNATURE_CONTEXT_UNSUPPORTED),
+    (138, Error::SIGNATURE_CONTEXT_INVALID),
+    (139, Error::CONDITIONAL_PROOF_MALFORMED),
+    (140, Error::CONDITIONAL_PROOF_NOT_SATISFIED),
+    (141, Error::TIMESTAMP_MALFORMED),
+    (142, Error::TIMESTAMP_REJECTED),
+    (143, Error::TIMESTAMP_MISMATCH),
+    (144, Error::TIMESTAMP_INVALID_SIGNATURE),
+    (145, Error::OPENPGP_MALFORMED),
+    (146, Error::OPENPGP_ENCRYPTED_KEY),
+    (147, Error::OPENPGP_UNSUPPORTED_KEY),
+    (148, Error::ECIES_MALFORMED),
+    (149, Error::ECIES_UNSUPPORTED_KEY),
+    (150, Error::ECIES_ENCRYPTION_FAILED),
+    (151, Error::ECIES_DECRYPTION_FAILED),
+    (152, Error::NACL_MALFORMED),
+    (153, Error::NACL_ENCRYPTION_FAILED),
+    (154, Error::NACL_DECRYPTION_FAILED),
+    (155, Error::DIDCOMM_ATTACHMENT_MALFORMED),
+    (156, Error::DIDCOMM_ATTACHMENT_HASH_MISMATCH),
+    (157, Error::OID4VCI_PROOF_MALFORMED),
+    (158, Error::OID4VCI_PROOF_INVALID_TYP),
+    (159, Error::OID4VCI_PROOF_INVALID_KEY),
+    (160, Error::OID4VP_MALFORMED),
+    (161, Error::OID4VP_INVALID_KEY),
+    (162, Error::VC_MALFORMED),
+    (163, Error::VC_JWT_INVALID_TYP),
+    (164, Error::VC_JWT_CLAIM_MISMATCH),
+    (165, Error::VC_COSE_INVALID_TYP),
+    (166, Error::KEY_PURPOSE_NOT_ALLOWED),
+    (167, Error::INPUT_TOO_LARGE),
+    (168, Error::INPUT_NOT_CANONICAL),
+    (169, Error::INPUT_TOO_DEEP),
+    (170, Error::JWE_INVALID_KEY_LENGTH),
+    (171, Error::ECDH_UNSUPPORTED_KEY),
+    (172, Error::DATA_INTEGRITY_PROOF_MALFORMED),
+    (173, Error::DATA_INTEGRITY_INVALID_OPTIONS),
+    (174, Error::DATA_INTEGRITY_PROOF_EXPIRED),
+    (175, Error::DATA_INTEGRITY_PROOF_MISMATCH),
+    (176, Error::MULTIHASH_MALFORMED),
+    (177, Error::MULTIHASH_UNSUPPORTED),
+    (178, Error::CID_MALFORMED),
+    (179, Error::ION_INVALID_DID),
+    (180, Error::ION_INVALID_STATE),
+    (181, Error::ION_INVALID_DOCUMENT),
+    (182, Error::WEBVH_INVALID_DID),
+    (183, Error::WEBVH_INVALID_LOG),
+    (184, Error::WEBVH_UNAUTHORIZED),
+    (185, Error::WEBVH_DEACTIVATED),
+    (186, Error::KEL_INVALID_EVENT),
+    (187, Error::KEL_THRESHOLD_NOT_MET),
+    (188, Error::KEL_ROTATION_NOT_COMMITTED),
+    (189, Error::ZCAP_MALFORMED),
+    (190, Error::ZCAP_INVALID_CHAIN),
+    (191, Error::ZCAP_UNAUTHORIZED),
+    (192, Error::ZCAP_EXPIRED),
+    (193, Error::ZCAP_INVALID_PROOF),
+    (194, Error::JWT_SIGNATURE_INVALID),
+    (195, Error::JWT_REFRESH_NOT_ALLOWED),
+    (196, Error::JWT_REFRESH_KEY_MISMATCH),
+    (197, Error::JWT_NOT_NESTED),
+    (198, Error::JWT_NOT_YET_VALID),
+    (199, Error::SIGNING_CONTEXT_MISMATCH),
+    (200, Error::SIGNATURE_INVALID),
+    (201, Error::JWE_DUPLICATE_RECIPIENT),
+    (202, Error::JWE_DECOMPRESSION_LIMIT_EXCEEDED),
+    (203, Error::VC_SCHEMA_VALIDATION_FAILED),
+    (204, Error::VC_SCHEMA_NOT_FOUND),
+    (205, Error::VC_SCHEMA_UNSUPPORTED),
+    (206, Error::VC_SCHEMA_MALFORMED),
+    (207, Error::TRUST_POLICY_ISSUER_NOT_TRUSTED),
+    (208, Error::TRUST_POLICY_ALGORITHM_NOT_ALLOWED),
+    (209, Error::TRUST_POLICY_CREDENTIAL_TOO_OLD),
+    (210, Error::CREDENTIAL_REVOKED),
+    (211, Error::CREDENTIAL_SUSPENDED),
+    (212, Error::STATUS_LIST_NOT_FOUND),
+    (213, Error::STATUS_LIST_ISSUER_MISMATCH),
+    (214, Error::TEST_VECTOR_MISMATCH),
+    (215, Error::SIGNATURE_ENVELOPE_MALFORMED),
+    (216, Error::SIGNATURE_ENVELOPE_ALGORITHM_MISMATCH),
+    (217, Error::SSH_SIGNATURE_MALFORMED),
+    (218, Error::SSH_SIGNATURE_NAMESPACE_MISMATCH),
+    (219, Error::DID_CONFIGURATION_MALFORMED),
+    (220, Error::DID_CONFIGURATION_ORIGIN_MISMATCH),
+    (221, Error::DID_CONFIGURATION_EXPIRED),
+    (222, Error::DID_CONFIGURATION_NOT_LINKED),
+    (223, Error::JWT_REVOKED),
+    (224, Error::DATA_INTEGRITY_PROOF_CHAIN_INVALID),
+    (225, Error::DID_WALLET_MALFORMED),
+    (226, Error::DID_WALLET_UNSUPPORTED_VERSION),
+    (227, Error::ATTESTATION_MALFORMED),
+    (228, Error::ATTESTATION_UNSUPPORTED_FORMAT),
+    (229, Error::ATTESTATION_INVALID),
+    (230, Error::ATTESTATION_KEY_MISMATCH),
+    (231, Error::ATTESTATION_NOT_HARDWARE_BACKED),
+    (232, Error::COSE_KEY_UNSUPPORTED),
+    (233, Error::WEBAUTHN_ASSERTION_MALFORMED),
+    (234, Error::WEBAUTHN_CLIENT_DATA_MISMATCH),
+    (235, Error::WEBAUTHN_RP_ID_MISMATCH),
+    (236, Error::WEBAUTHN_USER_NOT_PRESENT),
+    (237, Error::WEBAUTHN_USER_NOT_VERIFIED),
+    (238, Error::WEBAUTHN_SIGN_COUNT_REGRESSED),
+    (239, Error::ENTROPY_SOURCE_FAILED),
+    (240, Error::ENTROPY_HEALTH_CHECK_FAILED),
+    (241, Error::SELF_TEST_FAILED),
+    (242, Error::BENCH_REPORT_MALFORMED),
+    (243, Error::BENCH_REGRESSION),
+];
+
+impl Error {
+    pub fn message(&self) -> &'static str {
+        match self {
+            Error::Message(message) => message,
+            Error::Context { message, .. } => message,
+        }
+    }
+
+    pub fn algorithm(&self) -> Option<Algorithm> {
+        match self {
+            Error::Message(_) => None,
+            Error::Context { algorithm, .. } => *algorithm,
+        }
+    }
+
+    pub fn key(&self) -> Option<&str> {
+        match self {
+            Error::Message(_) => None,
+            Error::Context { key, .. } => key.as_deref(),
+        }
+    }
+
+    pub fn code(&self) -> i32 {
+        match ERROR_CODES.iter().find(|(_, error)| error == self) {
+            Some((code, _)) => *code,
+            None => 0,
+        }
+    }
+
+    pub fn from_code(code: i32) -> Option<Error> {
+        ERROR_CODES
+            .into_iter()
+            .find(|(val, _)| *val == code)
+            .map(|(_, error)| error)
+    }
+
+    pub fn is_malformed_input(&self) -> bool {
+        [
+            Error::DECODING_ERROR,
+            Error::SIGNATURE_IDENTIFICATION_FAILED,
+            Error::SIGNATURE_ENCODING_UNSUPPORTED,
+            Error::JWT_UTF8_ERROR,
+            Error::JWT_HEADER_DESERIALIZING_ERROR,
+            Error::JWT_PAYLOAD_DESERIALIZING_ERROR,
+            Error::JWT_PAYLOAD_FIELD_EXP_IDENTIFICATION_ERROR,
+            Error::JWT_PAYLOAD_FIELD_NBF_IDENTIFICATION_ERROR,
+            Error::FAILED_TO_CONVERT_TIMESTAMP_TO_DATETTIME,
+            Error::SD_JWT_MALFORMED,
+            Error::SD_JWT_INVALID_DISCLOSURE,
+            Error::COSE_MALFORMED,
+            Error::CWT_CLAIMS_DESERIALIZING_ERROR,
+            Error::DPOP_MALFORMED,
+            Error::SIWE_MALFORMED,
+            Error::BASE58_INVALID_CHECKSUM,
+            Error::KEY_FORMAT_UNRECOGNIZED,
+            Error::VERIFICATION_METHOD_MALFORMED,
+            Error::JWS_MALFORMED,
+            Error::FROST_MALFORMED,
+            Error::SHAMIR_INVALID_SHARE,
+            Error::EXPORT_BUNDLE_MALFORMED,
+            Error::CONDITIONAL_PROOF_MALFORMED,
+            Error::TIMESTAMP_MALFORMED,
+            Error::OPENPGP_MALFORMED,
+            Error::SIGNATURE_ENVELOPE_MALFORMED,
+            Error::SSH_SIGNATURE_MALFORMED,
+            Error::DID_CONFIGURATION_MALFORMED,
+            Error::DID_WALLET_MALFORMED,
+            Error::ATTESTATION_MALFORMED,
+            Error::WEBAUTHN_ASSERTION_MALFORMED,
+            Error::ECIES_MALFORMED,
+            Error::NACL_MALFORMED,
+            Error::DIDCOMM_ATTACHMENT_MALFORMED,
+            Error::OID4VCI_PROOF_MALFORMED,
+            Error::OID4VP_MALFORMED,
+            Error::VC_MALFORMED,
+            Error::INPUT_TOO_LARGE,
+            Error::INPUT_NOT_CANONICAL,
+            Error::INPUT_TOO_DEEP,
+            Error::DATA_INTEGRITY_PROOF_MALFORMED,
+            Error::MULTIHASH_MALFORMED,
+            Error::CID_MALFORMED,
+            Error::ION_INVALID_DID,
+            Error::ION_INVALID_STATE,
+            Error::WEBVH_INVALID_DID,
+            Error::WEBVH_INVALID_LOG,
+            Error::KEL_INVALID_EVENT,
+            Error::ZCAP_MALFORMED,
+            Error::BENCH_REPORT_MALFORMED,
+        ]
+        .contains(self)
+    }
+
+    pub fn is_schema_error(&self) -> bool {
+        [
+            Error::VC_SCHEMA_VALIDATION_FAILED,
+            Error::VC_SCHEMA_NOT_FOUND,
+            Error::VC_SCHEMA_UNSUPPORTED,
+            Error::VC_SCHEMA_MALFORMED,
+        ]
+        .contains(self)
+    }
+
+    pub fn is_policy_violation(&self) -> bool {
+        [
+            Error::TRUST_POLICY_ISSUER_NOT_TRUSTED,
+            Error::TRUST_POLICY_ALGORITHM_NOT_ALLOWED,
+            Error::TRUST_POLICY_CREDENTIAL_TOO_OLD,
+        ]
+        .contains(self)
+    }
+
+    pub fn with_source(self, source: impl Into<Source>) -> Self {
+        let (message, algorithm, key, _) = self.into_parts();
+        Error::Context {
+            message,
+            algorithm,
+            key,
+            source: Some(source.into()),
+        }
+    }
+
+    pub fn with_algorithm(self, alg: Algorithm) -> Self {
+        let (message, _, key, source) = self.into_parts();
+        Error::Context {
+            message,
+            algorithm: Some(alg),
+            key,
+            source,
+        }
+    }
+
+    pub fn with_key(self, kid: &str) -> Self {
+        let (message, algorithm, _, source) = self.into_parts();
+        Error::Context {
+            message,
+            algorithm,
+            key: Some(String::from(kid)),
+            source,
+        }
+    }
+
+    fn into_parts(
+        self,
+    ) -> (
+        &'static str,
+        Option<Algorithm>,
+        Option<String>,
+        Option<Source>,
+    ) {
+        match self {
+            Error::Message(message) => (message, None, None, None),
+            Error::Context {
+                message,
+                algorithm,
+                key,
+                source,
+            } => (message, algorithm, key, source),
+        }
+    }
+}
+
+impl PartialEq for Error {
+    fn eq(&self, other: &Self) -> bool {
+        self.message() == other.message()
+    }
+}
+
+#[derive(Debug, PartialEq, thiserror::Error)]
+pub enum VerifyError {
+    #[error("Signature does not match the content")]
+    InvalidSignature,
+    #[error("Signature could not be decoded")]
+    MalformedSignature,
+    #[error("Key cannot be used for the requested algorithm")]
+    KeyMismatch,
+    #[error("Algorithm does not match the key")]
+    AlgorithmMismatch,
+    #[error(transparent)]
+    Failed(Error),
 }
 
-impl Display for Error {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.0)
+impl From<Error> for VerifyError {
+    fn from(error: Error) -> Self {
+        if error == Error::DECODING_ERROR || error == Error::SIGNATURE_IDENTIFICATION_FAILED {
+            VerifyError::MalformedSignature
+        } else if error == Error::UNKNOWN_ALGORITHM {
+            VerifyError::AlgorithmMismatch
+        } else if error == Error::PUBLIC_KEY_IDENTIFICATION_ERROR || error == Error::HMAC_KEY_ERROR
+        {
+            VerifyError::KeyMismatch
+        } else {
+            VerifyError::Failed(error)
+        }
     }
 }
@@ -0,0 +1,713 @@
+use serde_json::{json, Value};
+
+#[cfg(feature = "ethr-registry")]
+use std::time::Duration;
+
+use crate::{
+    caip::{eip155_address, AccountId},
+    crypto::{any::AnyVerifyingKey, ecdsa::_256k::P256kVerifyingKey},
+    errors::Error,
+    jwk::Jwk,
+    resolver::DidResolver,
+};
+
+#[cfg(feature = "ethr-registry")]
+use crate::{caip::keccak256, clock, log};
+
+pub const ETHR_PREFIX: &str = "did:ethr:";
+pub const MAINNET_CHAIN_ID: u64 = 1;
+pub const ERC1056_REGISTRY: &str = "0xdca7ef03e98e0dc2b855be647c39abe984fcf21b";
+const NETWORKS: [(&str, u64); 5] = [
+    ("mainnet", 1),
+    ("goerli", 5),
+    ("sepolia", 11155111),
+    ("polygon", 137),
+    ("linea", 59144),
+];
+const DID_CONTEXT: &str = "https://www.w3.org/ns/did/v1";
+const RECOVERY_CONTEXT: &str = "https://w3id.org/security/suites/secp256k1recovery-2020/v2";
+const JWS_CONTEXT: &str = "https://w3id.org/security/suites/jws-2020/v1";
+const RECOVERY_METHOD: &str = "EcdsaSecp256k1RecoveryMethod2020";
+const JSON_WEB_KEY: &str = "JsonWebKey2020";
+
+fn parse_chain_id(network: &str) -> Result<u64, Error> {
+    if let Some(chain_id) = network.strip_prefix("0x") {
+        return match u64::from_str_radix(chain_id, 16) {
+            Ok(val) => Ok(val),
+            Err(_) => Err(Error::ETHR_UNKNOWN_NETWORK),
+        };
+    }
+
+    match NETWORKS.iter().find(|(name, _)| *name == network) {
+        Some((_, chain_id)) => Ok(*chain_id),
+        None => Err(Error::ETHR_UNKNOWN_NETWORK),
+    }
+}
+
+fn decode_hex(value: &str) -> Result<Vec<u8>, Error> {
+    let value = match value.strip_prefix("0x") {
+        Some(val) => val,
+        None => return Err(Error::ETHR_INVALID_DID),
+    };
+
+    match hex::decode(value) {
+        Ok(val) => Ok(val),
+        Err(_) => Err(Error::ETHR_INVALID_DID),
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct EthrDid {
+    pub did: String,
+    pub chain_id: u64,
+    pub address: [u8; 20],
+    pub public_key: Option<P256kVerifyingKey>,
+}
+
+impl EthrDid {
+    pub fn parse(did: &str) -> Result<Self, Error> {
+        let identifier = match did.strip_prefix(ETHR_PREFIX) {
+            Some(val) => val,
+            None => return Err(Error::ETHR_INVALID_DID),
+        };
+
+        let (chain_id, identifier) = match identifier.rsplit_once(':') {
+            Some((network, identifier)) => (parse_chain_id(network)?, identifier),
+            None => (MAINNET_CHAIN_ID, identifier),
+        };
+
+        let bytes = decode_hex(identifier)?;
+        let (address, public_key) = match bytes.len() {
+            20 => {
+                let mut address = [0u8; 20];
+                address.copy_from_slice(&bytes);
+                (address, None)
+            }
+            33 => {
+                let key = match P256kVerifyingKey::from_bytes(&bytes) {
+                    Ok(val) => val,
+                    Err(error) => {
+                        return Err(Error::ETHR_INVALID_DID.with_source(error.to_string()))
+                    }
+                };
+                (eip155_address(&key), Some(key))
+            }
+            _ => return Err(Error::ETHR_INVALID_DID),
+        };
+
+        Ok(EthrDid {
+            did: String::from(did),
+            chain_id,
+            address,
+            public_key,
+        })
+    }
+
+    pub fn from_key(key: &P256kVerifyingKey, chain_id: u64) -> Self {
+        let identifier = format!("0x{}", hex::encode(key.to_sec1_bytes(true)));
+        EthrDid {
+            did: Self::did_for(chain_id, &identifier),
+            chain_id,
+            address: eip155_address(key),
+            public_key: Some(key.clone()),
+        }
+    }
+
+    pub fn from_address(address: [u8; 20], chain_id: u64) -> Self {
+        EthrDid {
+            did: Self::did_for(chain_id, &format!("0x{}", hex::encode(address))),
+            chain_id,
+            address,
+            public_key: None,
+        }
+    }
+
+    fn did_for(chain_id: u64, identifier: &str) -> String {
+        match chain_id {
+            MAINNET_CHAIN_ID => format!("{}{}", ETHR_PREFIX, identifier),
+            _ => format!("{}0x{:x}:{}", ETHR_PREFIX, chain_id, identifier),
+        }
+    }
+
+    pub fn blockchain_account_id(&self) -> String {
+        account_id(self.chain_id, &self.address)
+    }
+
+    pub fn document(&self) -> Value {
+        DocumentBuilder::new(self).build()
+    }
+}
+
+fn account_id(chain_id: u64, address: &[u8; 20]) -> String {
+    AccountId::eip155(chain_id, address).to_string()
+}
+
+#[cfg(feature = "ethr-registry")]
+fn key_jwk(algorithm: &str, bytes: &[u8]) -> Option<Jwk> {
+    let okp = |crv: &str| Jwk {
+        kty: String::from("OKP"),
+        crv: Some(String::from(crv)),
+        x: Some(base64_url::encode(bytes)),
+        ..Default::default()
+    };
+
+    match algorithm {
+        "Secp256k1" => P256kVerifyingKey::from_bytes(bytes)
+            .and_then(|key| AnyVerifyingKey::P256k(key).to_jwk())
+            .ok(),
+        "Ed25519" if bytes.len() == 32 => Some(okp("Ed25519")),
+        "X25519" if bytes.len() == 32 => Some(okp("X25519")),
+        _ => None,
+    }
+}
+
+fn json_web_key(id: &str, controller: &str, jwk: &Jwk) -> Value {
+    json!({
+        "id": id,
+        "type": JSON_WEB_KEY,
+        "controller": controller,
+        "publicKeyJwk": jwk,
+    })
+}
+
+struct DocumentBuilder<'a> {
+    did: &'a EthrDid,
+    controller: [u8; 20],
+    deactivated: bool,
+    #[cfg_attr(not(feature = "ethr-registry"), allow(dead_code))]
+    delegate_count: usize,
+    #[cfg_attr(not(feature = "ethr-registry"), allow(dead_code))]
+    service_count: usize,
+    methods: Vec<(String, Value)>,
+    authentication: Vec<(String, String)>,
+    key_agreement: Vec<(String, String)>,
+    services: Vec<(String, Value)>,
+}
+
+impl<'a> DocumentBuilder<'a> {
+    fn new(did: &'a EthrDid) -> Self {
+        DocumentBuilder {
+            did,
+            controller: did.address,
+            deactivated: false,
+            delegate_count: 0,
+            service_count: 0,
+            methods: Vec::new(),
+            authentication: Vec::new(),
+            key_agreement: Vec::new(),
+            services: Vec::new(),
+        }
+    }
+
+    fn build(self) -> Value {
+        let did = self.did.did.as_str();
+        if self.deactivated {
+            return json!({ "@context": [DID_CONTEXT], "id": did });
+        }
+
+        let controller_id = format!("{}#controller", did);
+        let mut methods = vec![json!({
+            "id": controller_id,
+            "type": RECOVERY_METHOD,
+            "controller": did,
+            "blockchainAccountId": account_id(self.did.chain_id, &self.controller),
+        })];
+        let mut authentication = vec![controller_id.clone()];
+
+        if let Some(key) = &self.did.public_key {
+            if self.controller == self.did.address {
+                let id = format!("{}#controllerKey", did);
+                if let Ok(jwk) = AnyVerifyingKey::P256k(key.clone()).to_jwk() {
+                    methods.push(json_web_key(&id, did, &jwk));
+                    authentication.push(id);
+                }
+            }
+        }
+
+        let mut assertion_method = authentication.clone();
+        for (key, method) in &self.methods {
+            if let Some(id) = method.get("id").and_then(Value::as_str) {
+                if !self
+                    .key_agreement
+                    .iter()
+                    .any(|(agreement, _)| agreement == key)
+                {
+                    assertion_method.push(String::from(id));
+                }
+            }
+            methods.push(method.clone());
+        }
+        authentication.extend(self.authentication.into_iter().map(|(_, id)| id));
+
+        let mut document = json!({
+            "@context": [DID_CONTEXT, RECOVERY_CONTEXT, JWS_CONTEXT],
+            "id": did,
+            "verificationMethod": methods,
+            "authentication": authentication,
+            "assertionMethod": assertion_method,
+        });
+        if !self.key_agreement.is_empty() {
+            document["keyAgreement"] = Value::from(
+                self.key_agreement
+                    .into_iter()
+                    .map(|(_, id)| id)
+                    .collect::<Vec<String>>(),
+            );
+        }
+        if !self.services.is_empty() {
+            document["service"] = Value::from(
+                self.services
+                    .into_iter()
+                    .map(|(_, service)| service)
+                    .collect::<Vec<Value>>(),
+            );
+        }
+
+        document
+    }
+}
+
+#[cfg(feature = "ethr-registry")]
+const OWNER_CHANGED: &str = "DIDOwnerChanged(address,address,uint256)";
+#[cfg(feature = "ethr-registry")]
+const DELEGATE_CHANGED: &str = "DIDDelegateChanged(address,bytes32,address,uint256,uint256)";
+#[cfg(feature = "ethr-registry")]
+const ATTRIBUTE_CHANGED: &str = "DIDAttributeChanged(address,bytes32,bytes,uint256,uint256)";
+#[cfg(feature = "ethr-registry")]
+const CHANGED_SELECTOR: &str = "changed(address)";
+#[cfg(feature = "ethr-registry")]
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[cfg(feature = "ethr-registry")]
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum RegistryEvent {
+    OwnerChanged {
+        owner: [u8; 20],
+        previous_change: u64,
+    },
+    DelegateChanged {
+        delegate_type: String,
+        delegate: [u8; 20],
+        valid_to: u64,
+        previous_change: u64,
+    },
+    AttributeChanged {
+        name: String,
+        value: Vec<u8>,
+        valid_to: u64,
+        previous_change: u64,
+    },
+}
+
+#[cfg(feature = "ethr-registry")]
+fn topic(signature: &str) -> String {
+    format!("0x{}", hex::encode(keccak256(signature.as_bytes())))
+}
+
+#[cfg(feature = "ethr-registry")]
+fn padded_address(address: &[u8; 20]) -> String {
+    format!("{:0>64}", hex::encode(address))
+}
+
+#[cfg(feature = "ethr-registry")]
+fn word(data: &[u8], index: usize) -> Result<&[u8], Error> {
+    match data.get(index * 32..(index + 1) * 32) {
+        Some(val) => Ok(val),
+        None => Err(Error::ETHR_REGISTRY_ERROR),
+    }
+}
+
+#[cfg(feature = "ethr-registry")]
+fn word_u64(word: &[u8]) -> u64 {
+    match word[..24].iter().all(|byte| *byte == 0) {
+        true => word[24..]
+            .iter()
+            .fold(0u64, |value, byte| (value << 8) | *byte as u64),
+        false => u64::MAX,
+    }
+}
+
+#[cfg(feature = "ethr-registry")]
+fn word_address(word: &[u8]) -> [u8; 20] {
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&word[12..]);
+    address
+}
+
+#[cfg(feature = "ethr-registry")]
+fn word_string(word: &[u8]) -> String {
+    let end = word
+        .iter()
+        .rposition(|byte| *byte != 0)
+        .map_or(0, |index| index + 1);
+    String::from_utf8_lossy(&word[..end]).into_owned()
+}
+
+#[cfg(feature = "ethr-registry")]
+fn quantity(value: &Value) -> Result<u64, Error> {
+    match value
+        .as_str()
+        .and_then(|val| val.strip_prefix("0x"))
+        .and_then(|val| u64::from_str_radix(val, 16).ok())
+    {
+        Some(val) => Ok(val),
+        None => Err(Error::ETHR_REGISTRY_ERROR),
+    }
+}
+
+#[cfg(feature = "ethr-registry")]
+fn hex_data(value: &Value) -> Result<Vec<u8>, Error> {
+    match value
+        .as_str()
+        .and_then(|val| val.strip_prefix("0x"))
+        .and_then(|val| hex::decode(val).ok())
+    {
+        Some(val) => Ok(val),
+        None => Err(Error::ETHR_REGISTRY_ERROR),
+    }
+}
+
+#[cfg(feature = "ethr-registry")]
+impl RegistryEvent {
+    pub fn from_log(log: &Value) -> Result<Option<Self>, Error> {
+        let topic0 = match log.pointer("/topics/0").and_then(Value::as_str) {
+            Some(val) => val.to_ascii_lowercase(),
+            None => return Err(Error::ETHR_REGISTRY_ERROR),
+        };
+        let data = hex_data(&log["data"])?;
+
+        if topic0 == topic(OWNER_CHANGED) {
+            return Ok(Some(RegistryEvent::OwnerChanged {
+                owner: word_address(word(&data, 0)?),
+                previous_change: word_u64(word(&data, 1)?),
+            }));
+        }
+
+        if topic0 == topic(DELEGATE_CHANGED) {
+            return Ok(Some(RegistryEvent::DelegateChanged {
+                delegate_type: word_string(word(&data, 0)?),
+                delegate: word_address(word(&data, 1)?),
+                valid_to: word_u64(word(&data, 2)?),
+                previous_change: word_u64(word(&data, 3)?),
+            }));
+        }
+
+        if topic0 == topic(ATTRIBUTE_CHANGED) {
+            let offset = word_u64(word(&data, 1)?) as usize;
+            let length = match data.get(offset..offset + 32) {
+                Some(val) => word_u64(val) as usize,
+                None => return Err(Error::ETHR_REGISTRY_ERROR),
+            };
+            let value = match data.get(offset + 32..offset + 32 + length) {
+                Some(val) => val.to_vec(),
+                None => return Err(Error::ETHR_REGISTRY_ERROR),
+            };
+
+            return Ok(Some(RegistryEvent::AttributeChanged {
+                name: word_string(word(&data, 0)?),
+                value,
+                valid_to: word_u64(word(&data, 2)?),
+                previous_change: word_u64(word(&data, 3)?),
+            }));
+        }
+
+        Ok(None)
+    }
+
+    pub fn previous_change(&self) -> u64 {
+        match self {
+            RegistryEvent::OwnerChanged {
+                previous_change, ..
+            }
+            | RegistryEvent::DelegateChanged {
+                previous_change, ..
+            }
+            | RegistryEvent::AttributeChanged {
+                previous_change, ..
+            } => *previous_change,
+        }
+    }
+}
+
+#[cfg(feature = "ethr-registry")]
+impl<'a> DocumentBuilder<'a> {
+    fn remove(&mut self, key: &str) {
+        self.methods.retain(|(val, _)| val != key);
+        self.authentication.retain(|(val, _)| val != key);
+        self.key_agreement.retain(|(val, _)| val != key);
+        self.services.retain(|(val, _)| val != key);
+    }
+
+    fn apply(&mut self, event: &RegistryEvent, now: u64) {
+        let did = self.did.did.clone();
+        match event {
+            RegistryEvent::OwnerChanged { owner, .. } => {
+                self.controller = *owner;
+                self.deactivated = owner.iter().all(|byte| *byte == 0);
+            }
+            RegistryEvent::DelegateChanged {
+                delegate_type,
+                delegate,
+                valid_to,
+                ..
+            } => {
+                self.delegate_count += 1;
+                let key = format!("delegate-{}-{}", delegate_type, hex::encode(delegate));
+                self.remove(&key);
+                if *valid_to < now || !matches!(delegate_type.as_str(), "veriKey" | "sigAuth") {
+                    return;
+                }
+
+                let id = format!("{}#delegate-{}", did, self.delegate_count);
+                self.methods.push((
+                    key.clone(),
+                    json!({
+                        "id": id,
+                        "type": RECOVERY_METHOD,
+                        "controller": did,
+                        "blockchainAccountId": account_id(self.did.chain_id, delegate),
+                    }),
+                ));
+                if delegate_type == "sigAuth" {
+                    self.authentication.push((key, id));
+                }
+            }
+            RegistryEvent::AttributeChanged {
+                name,
+                value,
+                valid_to,
+                ..
+            } => {
+                let parts: Vec<&str> = name.split('/').collect();
+                let key = format!("attribute-{}-{}", name, hex::encode(value));
+                match parts.get(1) {
+                    Some(&"pub") => self.delegate_count += 1,
+                    Some(&"svc") => self.service_count += 1,
+                    _ => return,
+                }
+                self.remove(&key);
+                if *valid_to < now {
+                    return;
+                }
+
+                match parts.as_slice() {
+                    ["did", "pub", algorithm, purpose, ..] => {
+                        let jwk = match key_jwk(algorithm, value) {
+                            Some(val) => val,
+                            None => return,
+                        };
+                        let id = format!("{}#delegate-{}", did, self.delegate_count);
+                        self.methods
+                            .push((key.clone(), json_web_key(&id, &did, &jwk)));
+                        match *purpose {
+                            "sigAuth" => self.authentication.push((key, id)),
+                            "enc" => self.key_agreement.push((key, id)),
+                            _ => {}
+                        }
+                    }
+                    ["did", "svc", service_type, ..] => {
+                        self.services.push((
+                            key,
+                            json!({
+                                "id": format!("{}#service-{}", did, self.service_count),
+                                "type": service_type,
+                                "serviceEndpoint": String::from_utf8_lossy(value),
+                            }),
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ethr-registry")]
+pub trait JsonRpc: Send + Sync {
+    fn call(&self, method: &str, params: Value) -> Result<Value, Error>;
+}
+
+#[cfg(feature = "ethr-registry")]
+#[derive(Clone, Debug)]
+pub struct UreqJsonRpc {
+    url: String,
+    agent: ureq::Agent,
+}
+
+#[cfg(feature = "ethr-registry")]
+impl UreqJsonRpc {
+    pub fn new(url: &str) -> Self {
+        Self::with_agent(
+            url,
+            ureq::AgentBuilder::new().timeout(DEFAULT_TIMEOUT).build(),
+        )
+    }
+
+    pub fn with_agent(url: &str, agent: ureq::Agent) -> Self {
+        UreqJsonRpc {
+            url: String::from(url),
+            agent,
+        }
+    }
+}
+
+#[cfg(feature = "ethr-registry")]
+impl JsonRpc for UreqJsonRpc {
+    fn call(&self, method: &str, params: Value) -> Result<Value, Error> {
+        let request = json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params });
+        let response = match self
+            .agent
+            .post(&self.url)
+            .set("Content-Type", "application/json")
+            .send_string(&request.to_string())
+        {
+            Ok(val) => match val.into_string() {
+                Ok(val) => val,
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::HTTP_REQUEST_FAILED.with_source(error));
+                }
+            },
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::HTTP_REQUEST_FAILED.with_source(error.to_string()));
+            }
+        };
+
+        let response: Value = match serde_json::from_str(&response) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ETHR_REGISTRY_ERROR.with_source(error));
+            }
+        };
+
+        match response.get("result") {
+            Some(val) => Ok(val.clone()),
+            None => {
+                log::error(response["error"].to_string().as_str());
+                Err(Error::ETHR_REGISTRY_ERROR.with_source(response["error"].to_string()))
+            }
+        }
+    }
+}
+
+#[cfg(feature = "ethr-registry")]
+pub struct RegistryResolver<C: JsonRpc = UreqJsonRpc> {
+    chain_id: u64,
+    registry: String,
+    client: C,
+}
+
+#[cfg(feature = "ethr-registry")]
+impl RegistryResolver {
+    pub fn new(chain_id: u64, rpc_url: &str) -> Self {
+        Self::with_client(chain_id, UreqJsonRpc::new(rpc_url))
+    }
+}
+
+#[cfg(feature = "ethr-registry")]
+impl<C: JsonRpc> RegistryResolver<C> {
+    pub fn with_client(chain_id: u64, client: C) -> Self {
+        RegistryResolver {
+            chain_id,
+            registry: String::from(ERC1056_REGISTRY),
+            client,
+        }
+    }
+
+    pub fn registry(mut self, address: &str) -> Self {
+        self.registry = address.to_ascii_lowercase();
+        self
+    }
+
+    pub fn client(&self) -> &C {
+        &self.client
+    }
+
+    fn changed(&self, address: &[u8; 20]) -> Result<u64, Error> {
+        let selector = hex::encode(&keccak256(CHANGED_SELECTOR.as_bytes())[..4]);
+        let result = self.client.call(
+            "eth_call",
+            json!([
+                {
+                    "to": self.registry,
+                    "data": format!("0x{}{}", selector, padded_address(address)),
+                },
+                "latest",
+            ]),
+        )?;
+
+        let data = hex_data(&result)?;
+        Ok(word_u64(word(&data, 0)?))
+    }
+
+    pub fn history(&self, address: &[u8; 20]) -> Result<Vec<RegistryEvent>, Error> {
+        let mut history = Vec::new();
+        let mut block = self.changed(address)?;
+
+        while block != 0 {
+            let logs = self.client.call(
+                "eth_getLogs",
+                json!([{
+                    "address": self.registry,
+                    "fromBlock": format!("0x{:x}", block),
+                    "toBlock": format!("0x{:x}", block),
+                    "topics": [Value::Null, format!("0x{}", padded_address(address))],
+                }]),
+            )?;
+            let logs = match logs.as_array() {
+                Some(val) => val,
+                None => return Err(Error::ETHR_REGISTRY_ERROR),
+            };
+
+            let mut previous_change = 0;
+            for log in logs.iter().rev() {
+                if quantity(&log["blockNumber"])? != block {
+                    continue;
+                }
+                if let Some(event) = RegistryEvent::from_log(log)? {
+                    if event.previous_change() < block {
+                        previous_change = event.previous_change();
+                    }
+                    history.push(event);
+                }
+            }
+            block = previous_change;
+        }
+
+        history.reverse();
+        Ok(history)
+    }
+
+    pub fn resolve_did(&self, did: &EthrDid) -> Result<Value, Error> {
+        if did.chain_id != self.chain_id {
+            return Err(Error::ETHR_UNKNOWN_NETWORK);
+        }
+
+        let now = clock::now().timestamp().max(0) as u64;
+        let history = self.history(&did.address)?;
+        let mut builder = DocumentBuilder::new(did);
+        for event in &history {
+            builder.apply(event, now);
+        }
+
+        Ok(builder.build())
+    }
+}
+
+#[cfg(feature = "ethr-registry")]
+impl<C: JsonRpc> DidResolver for RegistryResolver<C> {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        self.resolve_did(&EthrDid::parse(did)?)
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug)]
+pub struct EthrResolver;
+
+impl DidResolver for EthrResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        Ok(EthrDid::parse(did)?.document())
+    }
+}
@@ -0,0 +1,576 @@
+use std::collections::BTreeMap;
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use zeroize::Zeroizing;
+
+use crate::{
+    algorithms::Algorithm,
+    crypto::{
+        any::AnySigningKey,
+        kdf::PasswordKdf,
+        x25519::{X25519PublicKey, X25519SecretKey},
+    },
+    errors::Error,
+    jwe::{ContentEncryption, Jwe},
+    jwk::Jwk,
+    keystore::{
+        additional_data, export_entries, import_entries, EncryptedKeyFile, KeyStore, StoredKey,
+        FILE_KEY_STORE_ENC, FILE_KEY_STORE_VERSION, NONCE_LENGTH,
+    },
+    log,
+    resolver::{CachingResolver, DidResolver},
+};
+
+pub const EXPORT_BUNDLE_VERSION: u32 = 2;
+pub const EXPORT_BUNDLE_TYP: &str = "did-crypto-key-bundle+json";
+pub const DID_WALLET_VERSION: u32 = 1;
+pub const DID_WALLET_TYP: &str = "did-crypto-did-wallet+json";
+const EXPORT_BUNDLE_ENC: &str = "A256GCM";
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct BundleKey {
+    pub kid: String,
+    pub alg: Algorithm,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum BundleProtection {
+    Passphrase {
+        kdf: PasswordKdf,
+        enc: String,
+        nonce: String,
+        ciphertext: String,
+    },
+    Recipient {
+        jwe: Jwe,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct ExportedKeyBundle {
+    pub version: u32,
+    pub keys: Vec<BundleKey>,
+    pub protection: BundleProtection,
+}
+
+#[derive(Serialize)]
+struct BundleAad<'a> {
+    version: u32,
+    keys: &'a [BundleKey],
+    kdf: &'a PasswordKdf,
+}
+
+fn bundle_keys(entries: &BTreeMap<String, StoredKey>) -> Vec<BundleKey> {
+    entries
+        .iter()
+        .map(|(kid, entry)| BundleKey {
+            kid: kid.clone(),
+            alg: entry.alg,
+        })
+        .collect()
+}
+
+fn serialize_entries(entries: &BTreeMap<String, StoredKey>) -> Result<Zeroizing<Vec<u8>>, Error> {
+    match serde_json::to_vec(entries) {
+        Ok(val) => Ok(Zeroizing::new(val)),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::ENCODING_ERROR.with_source(error))
+        }
+    }
+}
+
+fn deserialize_entries(plaintext: &[u8]) -> Result<BTreeMap<String, StoredKey>, Error> {
+    match serde_json::from_slice(plaintext) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::EXPORT_BUNDLE_MALFORMED.with_source(error))
+        }
+    }
+}
+
+fn cipher(key: &[u8]) -> Result<Aes256Gcm, Error> {
+    match Aes256Gcm::new_from_slice(key) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::KEYSTORE_ERROR.with_source(error))
+        }
+    }
+}
+
+fn seal(key: &[u8], plaintext: &[u8], aad: &[u8]) -> Result<(Vec<u8>, Vec<u8>), Error> {
+    let mut nonce = [0u8; NONCE_LENGTH];
+    rand::thread_rng().fill_bytes(&mut nonce);
+    match cipher(key)?.encrypt(
+        Nonce::from_slice(&nonce),
+        Payload {
+            msg: plaintext,
+            aad,
+        },
+    ) {
+        Ok(val) => Ok((nonce.to_vec(), val)),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::KEYSTORE_ERROR.with_source(error.to_string()))
+        }
+    }
+}
+
+fn open(
+    key: &[u8],
+    nonce: &str,
+    ciphertext: &str,
+    aad: &[u8],
+) -> Result<Zeroizing<Vec<u8>>, Error> {
+    let nonce = Jwk::decode_member(&Some(String::from(nonce)))?;
+    if nonce.len() != NONCE_LENGTH {
+        return Err(Error::EXPORT_BUNDLE_MALFORMED);
+    }
+
+    match cipher(key)?.decrypt(
+        Nonce::from_slice(&nonce),
+        Payload {
+            msg: &Jwk::decode_member(&Some(String::from(ciphertext)))?,
+            aad,
+        },
+    ) {
+        Ok(val) => Ok(Zeroizing::new(val)),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::KEYSTORE_WRONG_PASSPHRASE.with_source(error.to_string()))
+        }
+    }
+}
+
+fn aad(version: u32, keys: &[BundleKey], kdf: &PasswordKdf) -> Result<Vec<u8>, Error> {
+    if version == FILE_KEY_STORE_VERSION {
+        return additional_data(kdf);
+    }
+
+    match serde_json::to_vec(&BundleAad { version, keys, kdf }) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::ENCODING_ERROR.with_source(error))
+        }
+    }
+}
+
+impl ExportedKeyBundle {
+    pub fn export_with_passphrase(
+        store: &impl KeyStore<AnySigningKey>,
+        passphrase: &str,
+    ) -> Result<Self, Error> {
+        Self::export_with_kdf(store, passphrase, PasswordKdf::argon2id_default())
+    }
+
+    pub fn export_with_kdf(
+        store: &impl KeyStore<AnySigningKey>,
+        passphrase: &str,
+        kdf: PasswordKdf,
+    ) -> Result<Self, Error> {
+        Self::seal_entries(&export_entries(store)?, passphrase, kdf)
+    }
+
+    pub fn export_to_recipients(
+        store: &impl KeyStore<AnySigningKey>,
+        recipients: &[(&str, X25519PublicKey)],
+    ) -> Result<Self, Error> {
+        let entries = export_entries(store)?;
+        let jwe = Jwe::encrypt(
+            &serialize_entries(&entries)?,
+            EXPORT_BUNDLE_TYP,
+            ContentEncryption::A256GCM,
+            recipients,
+            None,
+        )?;
+
+        Ok(ExportedKeyBundle {
+            version: EXPORT_BUNDLE_VERSION,
+            keys: bundle_keys(&entries),
+            protection: BundleProtection::Recipient { jwe },
+        })
+    }
+
+    fn seal_entries(
+        entries: &BTreeMap<String, StoredKey>,
+        passphrase: &str,
+        kdf: PasswordKdf,
+    ) -> Result<Self, Error> {
+        let keys = bundle_keys(entries);
+        let key = kdf.derive_key(passphrase.as_bytes(), 32)?;
+        let (nonce, ciphertext) = seal(
+            &key,
+            &serialize_entries(entries)?,
+            &aad(EXPORT_BUNDLE_VERSION, &keys, &kdf)?,
+        )?;
+
+        Ok(ExportedKeyBundle {
+            version: EXPORT_BUNDLE_VERSION,
+            keys,
+            protection: BundleProtection::Passphrase {
+                kdf,
+                enc: String::from(EXPORT_BUNDLE_ENC),
+                nonce: base64_url::encode(&nonce),
+                ciphertext: base64_url::encode(&ciphertext),
+            },
+        })
+    }
+
+    fn check_entries(&self, entries: &BTreeMap<String, StoredKey>) -> Result<(), Error> {
+        match self.version == FILE_KEY_STORE_VERSION || bundle_keys(entries) == self.keys {
+            true => Ok(()),
+            false => Err(Error::EXPORT_BUNDLE_MALFORMED),
+        }
+    }
+
+    fn decrypt_with_passphrase(
+        &self,
+        passphrase: &str,
+    ) -> Result<BTreeMap<String, StoredKey>, Error> {
+        let (kdf, nonce, ciphertext) = match &self.protection {
+            BundleProtection::Passphrase {
+                kdf,
+                enc,
+                nonce,
+                ciphertext,
+            } if enc == EXPORT_BUNDLE_ENC => (kdf, nonce, ciphertext),
+            BundleProtection::Passphrase { .. } => return Err(Error::EXPORT_BUNDLE_MALFORMED),
+            BundleProtection::Recipient { .. } => {
+                return Err(Error::EXPORT_BUNDLE_PROTECTION_MISMATCH)
+            }
+        };
+
+        let key = kdf.derive_key(passphrase.as_bytes(), 32)?;
+        let plaintext = open(
+            &key,
+            nonce,
+            ciphertext,
+            &aad(self.version, &self.keys, kdf)?,
+        )?;
+        let entries = deserialize_entries(&plaintext)?;
+        self.check_entries(&entries)?;
+        Ok(entries)
+    }
+
+    pub fn import_with_passphrase(
+        &self,
+        passphrase: &str,
+        store: &impl KeyStore<AnySigningKey>,
+    ) -> Result<Vec<String>, Error> {
+        let entries = self.decrypt_with_passphrase(passphrase)?;
+        let kids = entries.keys().cloned().collect();
+        import_entries(entries, store)?;
+        Ok(kids)
+    }
+
+    pub fn import_with_key(
+        &self,
+        kid: &str,
+        key: &X25519SecretKey,
+        store: &impl KeyStore<AnySigningKey>,
+    ) -> Result<Vec<String>, Error> {
+        let jwe = match &self.protection {
+            BundleProtection::Recipient { jwe } => jwe,
+            BundleProtection::Passphrase { .. } => {
+                return Err(Error::EXPORT_BUNDLE_PROTECTION_MISMATCH)
+            }
+        };
+
+        let plaintext = Zeroizing::new(jwe.decrypt(kid, key, None)?);
+        let entries = deserialize_entries(&plaintext)?;
+        self.check_entries(&entries)?;
+        let kids = entries.keys().cloned().collect();
+        import_entries(entries, store)?;
+        Ok(kids)
+    }
+
+    pub fn migrate(&self, passphrase: &str) -> Result<Self, Error> {
+        let entries = self.decrypt_with_passphrase(passphrase)?;
+        let kdf = match &self.protection {
+            BundleProtection::Passphrase { kdf, .. } => kdf.clone(),
+            BundleProtection::Recipient { .. } => {
+                return Err(Error::EXPORT_BUNDLE_PROTECTION_MISMATCH)
+            }
+        };
+
+        Self::seal_entries(&entries, passphrase, kdf)
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        match serde_json::to_string(self) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENCODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let value: Value = match serde_json::from_str(json) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::EXPORT_BUNDLE_MALFORMED.with_source(error));
+            }
+        };
+
+        match value.get("version").and_then(Value::as_u64) {
+            Some(val) if val == FILE_KEY_STORE_VERSION as u64 => Self::from_key_file(value),
+            Some(val) if val == EXPORT_BUNDLE_VERSION as u64 => match serde_json::from_value(value)
+            {
+                Ok(val) => Ok(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    Err(Error::EXPORT_BUNDLE_MALFORMED.with_source(error))
+                }
+            },
+            Some(_) => Err(Error::EXPORT_BUNDLE_UNSUPPORTED_VERSION),
+            None => Err(Error::EXPORT_BUNDLE_MALFORMED),
+        }
+    }
+
+    fn from_key_file(value: Value) -> Result<Self, Error> {
+        let file: EncryptedKeyFile = match serde_json::from_value(value) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::EXPORT_BUNDLE_MALFORMED.with_source(error));
+            }
+        };
+
+        if file.enc != FILE_KEY_STORE_ENC {
+            return Err(Error::EXPORT_BUNDLE_MALFORMED);
+        }
+
+        Ok(ExportedKeyBundle {
+            version: file.version,
+            keys: Vec::new(),
+            protection: BundleProtection::Passphrase {
+                kdf: file.params,
+                enc: file.enc,
+                nonce: file.nonce,
+                ciphertext: file.ciphertext,
+            },
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct WalletContents {
+    documents: BTreeMap<String, Value>,
+    keys: BTreeMap<String, StoredKey>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct EncryptedDidWallet {
+    pub version: u32,
+    pub typ: String,
+    pub dids: Vec<String>,
+    pub keys: Vec<BundleKey>,
+    pub kdf: PasswordKdf,
+    pub enc: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+#[derive(Serialize)]
+struct WalletAad<'a> {
+    version: u32,
+    typ: &'a str,
+    dids: &'a [String],
+    keys: &'a [BundleKey],
+    kdf: &'a PasswordKdf,
+}
+
+fn wallet_aad(
+    version: u32,
+    dids: &[String],
+    keys: &[BundleKey],
+    kdf: &PasswordKdf,
+) -> Result<Vec<u8>, Error> {
+    let aad = WalletAad {
+        version,
+        typ: DID_WALLET_TYP,
+        dids,
+        keys,
+        kdf,
+    };
+
+    match serde_json::to_vec(&aad) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::ENCODING_ERROR.with_source(error))
+        }
+    }
+}
+
+fn wallet_documents(documents: &[Value]) -> Result<BTreeMap<String, Value>, Error> {
+    let mut entries = BTreeMap::new();
+    for document in documents {
+        let did = match document.get("id").and_then(Value::as_str) {
+            Some(val) if val.starts_with("did:") && !val.contains(['#', '?', '/']) => val,
+            _ => return Err(Error::DID_WALLET_MALFORMED),
+        };
+
+        if entries
+            .insert(String::from(did), document.clone())
+            .is_some()
+        {
+            return Err(Error::DID_WALLET_MALFORMED);
+        }
+    }
+
+    Ok(entries)
+}
+
+impl EncryptedDidWallet {
+    pub fn seal(
+        documents: &[Value],
+        store: &impl KeyStore<AnySigningKey>,
+        passphrase: &str,
+    ) -> Result<Self, Error> {
+        Self::seal_with_kdf(
+            documents,
+            store,
+            passphrase,
+            PasswordKdf::argon2id_default(),
+        )
+    }
+
+    pub fn seal_with_kdf(
+        documents: &[Value],
+        store: &impl KeyStore<AnySigningKey>,
+        passphrase: &str,
+        kdf: PasswordKdf,
+    ) -> Result<Self, Error> {
+        let contents = WalletContents {
+            documents: wallet_documents(documents)?,
+            keys: export_entries(store)?,
+        };
+        let dids: Vec<String> = contents.documents.keys().cloned().collect();
+        let keys = bundle_keys(&contents.keys);
+
+        let plaintext = match serde_json::to_vec(&contents) {
+            Ok(val) => Zeroizing::new(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ENCODING_ERROR.with_source(error));
+            }
+        };
+        let key = kdf.derive_key(passphrase.as_bytes(), 32)?;
+        let (nonce, ciphertext) = seal(
+            &key,
+            &plaintext,
+            &wallet_aad(DID_WALLET_VERSION, &dids, &keys, &kdf)?,
+        )?;
+
+        Ok(EncryptedDidWallet {
+            version: DID_WALLET_VERSION,
+            typ: String::from(DID_WALLET_TYP),
+            dids,
+            keys,
+            kdf,
+            enc: String::from(EXPORT_BUNDLE_ENC),
+            nonce: base64_url::encode(&nonce),
+            ciphertext: base64_url::encode(&ciphertext),
+        })
+    }
+
+    fn decrypt(&self, passphrase: &str) -> Result<WalletContents, Error> {
+        if self.version != DID_WALLET_VERSION {
+            return Err(Error::DID_WALLET_UNSUPPORTED_VERSION);
+        }
+        if self.typ != DID_WALLET_TYP || self.enc != EXPORT_BUNDLE_ENC {
+            return Err(Error::DID_WALLET_MALFORMED);
+        }
+
+        let key = self.kdf.derive_key(passphrase.as_bytes(), 32)?;
+        let plaintext = open(
+            &key,
+            &self.nonce,
+            &self.ciphertext,
+            &wallet_aad(self.version, &self.dids, &self.keys, &self.kdf)?,
+        )?;
+        let contents: WalletContents = match serde_json::from_slice(&plaintext) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::DID_WALLET_MALFORMED.with_source(error));
+            }
+        };
+
+        let dids: Vec<String> = contents.documents.keys().cloned().collect();
+        match dids == self.dids && bundle_keys(&contents.keys) == self.keys {
+            true => Ok(contents),
+            false => Err(Error::DID_WALLET_MALFORMED),
+        }
+    }
+
+    pub fn import(
+        &self,
+        passphrase: &str,
+        store: &impl KeyStore<AnySigningKey>,
+    ) -> Result<BTreeMap<String, Value>, Error> {
+        let contents = self.decrypt(passphrase)?;
+        import_entries(contents.keys, store)?;
+        Ok(contents.documents)
+    }
+
+    pub fn import_with_cache<R: DidResolver + Send + Sync + 'static>(
+        &self,
+        passphrase: &str,
+        store: &impl KeyStore<AnySigningKey>,
+        cache: &CachingResolver<R>,
+    ) -> Result<Vec<String>, Error> {
+        let documents = self.import(passphrase, store)?;
+        for (did, document) in &documents {
+            cache.insert(did, document.clone());
+        }
+
+        Ok(documents.into_keys().collect())
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        match serde_json::to_string(self) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENCODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let value: Value = match serde_json::from_str(json) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::DID_WALLET_MALFORMED.with_source(error));
+            }
+        };
+
+        match value.get("version").and_then(Value::as_u64) {
+            Some(val) if val == DID_WALLET_VERSION as u64 => match serde_json::from_value(value) {
+                Ok(val) => Ok(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    Err(Error::DID_WALLET_MALFORMED.with_source(error))
+                }
+            },
+            Some(_) => Err(Error::DID_WALLET_UNSUPPORTED_VERSION),
+            None => Err(Error::DID_WALLET_MALFORMED),
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use std::{
+    ffi::{c_char, CStr},
+    ptr, slice,
+    str::FromStr,
+};
+
+use crate::{
+    algorithms::Algorithm,
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        VerifyFromKey,
+    },
+    errors::Error,
+    jwk::Jwk,
+    signer,
+};
+
+pub const DID_CRYPTO_OK: i32 = 0;
+pub const DID_CRYPTO_ERROR_NULL_POINTER: i32 = -1;
+pub const DID_CRYPTO_ERROR_INVALID_UTF8: i32 = -2;
+pub const DID_CRYPTO_ERROR_BUFFER_TOO_SMALL: i32 = -3;
+pub const DID_CRYPTO_ERROR_UNKNOWN: i32 = -4;
+
+fn error_code(error: Error) -> i32 {
+    match error.code() {
+        0 => DID_CRYPTO_ERROR_UNKNOWN,
+        code => code,
+    }
+}
+
+unsafe fn read_str<'a>(value: *const c_char) -> Result<&'a str, i32> {
+    if value.is_null() {
+        return Err(DID_CRYPTO_ERROR_NULL_POINTER);
+    }
+
+    match CStr::from_ptr(value).to_str() {
+        Ok(val) => Ok(val),
+        Err(_) => Err(DID_CRYPTO_ERROR_INVALID_UTF8),
+    }
+}
+
+unsafe fn read_bytes<'a>(value: *const u8, len: usize) -> Result<&'a [u8], i32> {
+    match (value.is_null(), len) {
+        (_, 0) => Ok(&[]),
+        (true, _) => Err(DID_CRYPTO_ERROR_NULL_POINTER),
+        (false, _) => Ok(slice::from_raw_parts(value, len)),
+    }
+}
+
+unsafe fn read_alg(alg: *const c_char) -> Result<Algorithm, i32> {
+    Algorithm::from_str(read_str(alg)?).map_err(error_code)
+}
+
+unsafe fn write_bytes(bytes: &[u8], out: *mut u8, out_len: *mut usize) -> i32 {
+    if out_len.is_null() {
+        return DID_CRYPTO_ERROR_NULL_POINTER;
+    }
+
+    let capacity = *out_len;
+    *out_len = bytes.len();
+    if capacity < bytes.len() {
+        return DID_CRYPTO_ERROR_BUFFER_TOO_SMALL;
+    }
+    if out.is_null() {
+        return DID_CRYPTO_ERROR_NULL_POINTER;
+    }
+
+    ptr::copy_nonoverlapping(bytes.as_ptr(), out, bytes.len());
+    DID_CRYPTO_OK
+}
+
+unsafe fn respond(result: Result<impl AsRef<[u8]>, i32>, out: *mut u8, out_len: *mut usize) -> i32 {
+    match result {
+        Ok(val) => write_bytes(val.as_ref(), out, out_len),
+        Err(code) => code,
+    }
+}
+
+/// # Safety
+///
+/// `out_len` must point to the capacity of `out`; it receives the message length.
+#[no_mangle]
+pub unsafe extern "C" fn did_crypto_error_message(
+    code: i32,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    let message = match code {
+        DID_CRYPTO_ERROR_NULL_POINTER => "Null pointer argument",
+        DID_CRYPTO_ERROR_INVALID_UTF8 => "String argument is not valid UTF-8",
+        DID_CRYPTO_ERROR_BUFFER_TOO_SMALL => "Output buffer is too small",
+        code => match Error::from_code(code) {
+            Some(val) => val.message(),
+            None => "Unknown error",
+        },
+    };
+
+    write_bytes(message.as_bytes(), out, out_len)
+}
+
+/// # Safety
+///
+/// `alg` must be a NUL terminated string and `out_len` must point to the capacity of `out`.
+#[no_mangle]
+pub unsafe extern "C" fn did_crypto_generate_key(
+    alg: *const c_char,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    let result = read_alg(alg).and_then(|alg| {
+        AnySigningKey::generate(alg)
+            .and_then(|key| key.to_private_bytes())
+            .map_err(error_code)
+    });
+
+    respond(result, out, out_len)
+}
+
+/// # Safety
+///
+/// `alg` must be a NUL terminated string, `private_key` must point to `private_key_len`
+/// bytes and `out_len` must point to the capacity of `out`.
+#[no_mangle]
+pub unsafe extern "C" fn did_crypto_public_key(
+    alg: *const c_char,
+    private_key: *const u8,
+    private_key_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    let result = (|| {
+        let key =
+            AnySigningKey::from_bytes(read_alg(alg)?, read_bytes(private_key, private_key_len)?)
+                .map_err(error_code)?;
+        key.to_public_bytes().map_err(error_code)
+    })();
+
+    respond(result, out, out_len)
+}
+
+/// # Safety
+///
+/// `alg` must be a NUL terminated string, `private_key` and `message` must point to
+/// their given lengths and `out_len` must point to the capacity of `out`.
+#[no_mangle]
+pub unsafe extern "C" fn did_crypto_sign(
+    alg: *const c_char,
+    private_key: *const u8,
+    private_key_len: usize,
+    message: *const u8,
+    message_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    let result = (|| {
+        let alg = read_alg(alg)?;
+        let key = AnySigningKey::from_bytes(alg, read_bytes(private_key, private_key_len)?)
+            .map_err(error_code)?;
+        signer::sign(read_bytes(message, message_len)?, key, alg)
+            .map(String::into_bytes)
+            .map_err(error_code)
+    })();
+
+    respond(result, out, out_len)
+}
+
+/// # Safety
+///
+/// `alg` and `signature` must be NUL terminated strings, `public_key` and `message` must
+/// point to their given lengths and `valid` must be writable.
+#[no_mangle]
+pub unsafe extern "C" fn did_crypto_verify(
+    alg: *const c_char,
+    public_key: *const u8,
+    public_key_len: usize,
+    message: *const u8,
+    message_len: usize,
+    signature: *const c_char,
+    valid: *mut bool,
+) -> i32 {
+    if valid.is_null() {
+        return DID_CRYPTO_ERROR_NULL_POINTER;
+    }
+
+    let result = (|| {
+        let alg = read_alg(alg)?;
+        let key = AnyVerifyingKey::from_bytes(alg, read_bytes(public_key, public_key_len)?)
+            .map_err(error_code)?;
+        key.verify_bytes(read_bytes(message, message_len)?, read_str(signature)?, alg)
+            .map_err(error_code)
+    })();
+
+    match result {
+        Ok(val) => {
+            *valid = val;
+            DID_CRYPTO_OK
+        }
+        Err(code) => code,
+    }
+}
+
+/// # Safety
+///
+/// `alg` must be a NUL terminated string, `public_key` must point to `public_key_len`
+/// bytes and `out_len` must point to the capacity of `out`.
+#[no_mangle]
+pub unsafe extern "C" fn did_crypto_public_key_to_jwk(
+    alg: *const c_char,
+    public_key: *const u8,
+    public_key_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    let result = (|| {
+        let key =
+            AnyVerifyingKey::from_bytes(read_alg(alg)?, read_bytes(public_key, public_key_len)?)
+                .map_err(error_code)?;
+        key.to_jwk()
+            .and_then(|jwk| jwk.to_json())
+            .map(String::into_bytes)
+            .map_err(error_code)
+    })();
+
+    respond(result, out, out_len)
+}
+
+/// # Safety
+///
+/// `jwk` must be a NUL terminated string and `out_len` must point to the capacity of `out`.
+#[no_mangle]
+pub unsafe extern "C" fn did_crypto_public_key_from_jwk(
+    jwk: *const c_char,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    let result = (|| {
+        let jwk = Jwk::from_json(read_str(jwk)?).map_err(error_code)?;
+        AnyVerifyingKey::from_jwk(&jwk)
+            .and_then(|key| key.to_bytes())
+            .map_err(error_code)
+    })();
+
+    respond(result, out, out_len)
+}
+
+/// # Safety
+///
+/// `alg` must be a NUL terminated string, `private_key` must point to `private_key_len`
+/// bytes and `out_len` must point to the capacity of `out`.
+#[no_mangle]
+pub unsafe extern "C" fn did_crypto_private_key_to_jwk(
+    alg: *const c_char,
+    private_key: *const u8,
+    private_key_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    let result = (|| {
+        let key =
+            AnySigningKey::from_bytes(read_alg(alg)?, read_bytes(private_key, private_key_len)?)
+                .map_err(error_code)?;
+        key.to_jwk()
+            .and_then(|jwk| jwk.to_json())
+            .map(String::into_bytes)
+            .map_err(error_code)
+    })();
+
+    respond(result, out, out_len)
+}
+
+/// # Safety
+///
+/// `jwk` must be a NUL terminated string and `out_len` must point to the capacity of `out`.
+#[no_mangle]
+pub unsafe extern "C" fn did_crypto_private_key_from_jwk(
+    jwk: *const c_char,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    let result = (|| {
+        let jwk = Jwk::from_json(read_str(jwk)?).map_err(error_code)?;
+        AnySigningKey::from_jwk(&jwk)
+            .and_then(|key| key.to_private_bytes())
+            .map_err(error_code)
+    })();
+
+    respond(result, out, out_len)
+}
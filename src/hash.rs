@@ -0,0 +1,247 @@
+use std::io::{self, Read, Write};
+
+use sha2::{Digest, Sha256, Sha384, Sha512};
+use sha3::{
+    digest::{ExtendableOutput, Update, XofReader},
+    Sha3_256, Sha3_384, Sha3_512, Shake128, Shake256,
+};
+
+#[cfg(feature = "blake3")]
+use zeroize::Zeroizing;
+
+use crate::{algorithms::Algorithm, errors::Error, log};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum HashAlgorithm {
+    Sha256,
+    Sha384,
+    Sha512,
+    Sha3_256,
+    Sha3_384,
+    Sha3_512,
+    Shake128,
+    Shake256,
+    #[cfg(feature = "blake3")]
+    Blake3,
+}
+
+impl HashAlgorithm {
+    pub fn to_str<'a>(&self) -> &'a str {
+        match self {
+            HashAlgorithm::Sha256 => "sha-256",
+            HashAlgorithm::Sha384 => "sha-384",
+            HashAlgorithm::Sha512 => "sha-512",
+            HashAlgorithm::Sha3_256 => "sha3-256",
+            HashAlgorithm::Sha3_384 => "sha3-384",
+            HashAlgorithm::Sha3_512 => "sha3-512",
+            HashAlgorithm::Shake128 => "shake128",
+            HashAlgorithm::Shake256 => "shake256",
+            #[cfg(feature = "blake3")]
+            HashAlgorithm::Blake3 => "blake3",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "sha-256" => Some(HashAlgorithm::Sha256),
+            "sha-384" => Some(HashAlgorithm::Sha384),
+            "sha-512" => Some(HashAlgorithm::Sha512),
+            "sha3-256" => Some(HashAlgorithm::Sha3_256),
+            "sha3-384" => Some(HashAlgorithm::Sha3_384),
+            "sha3-512" => Some(HashAlgorithm::Sha3_512),
+            "shake128" => Some(HashAlgorithm::Shake128),
+            "shake256" => Some(HashAlgorithm::Shake256),
+            #[cfg(feature = "blake3")]
+            "blake3" => Some(HashAlgorithm::Blake3),
+            _ => None,
+        }
+    }
+
+    pub fn for_algorithm(alg: Algorithm) -> Self {
+        match alg {
+            Algorithm::HS256
+            | Algorithm::RS256
+            | Algorithm::PS256
+            | Algorithm::ES256
+            | Algorithm::ES256K
+            | Algorithm::ES256KR => HashAlgorithm::Sha256,
+            Algorithm::HS384 | Algorithm::RS384 | Algorithm::PS384 | Algorithm::ES384 => {
+                HashAlgorithm::Sha384
+            }
+            Algorithm::HS512
+            | Algorithm::RS512
+            | Algorithm::PS512
+            | Algorithm::ES512
+            | Algorithm::EdDSA => HashAlgorithm::Sha512,
+            Algorithm::MLDSA65 | Algorithm::MLDSA65Ed25519 => HashAlgorithm::Shake256,
+        }
+    }
+
+    pub fn output_length(&self) -> usize {
+        match self {
+            HashAlgorithm::Sha256 | HashAlgorithm::Sha3_256 | HashAlgorithm::Shake128 => 32,
+            HashAlgorithm::Sha384 | HashAlgorithm::Sha3_384 => 48,
+            HashAlgorithm::Sha512 | HashAlgorithm::Sha3_512 | HashAlgorithm::Shake256 => 64,
+            #[cfg(feature = "blake3")]
+            HashAlgorithm::Blake3 => 32,
+        }
+    }
+
+    pub fn hasher(&self) -> Hasher {
+        match self {
+            HashAlgorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+            HashAlgorithm::Sha384 => Hasher::Sha384(Sha384::new()),
+            HashAlgorithm::Sha512 => Hasher::Sha512(Sha512::new()),
+            HashAlgorithm::Sha3_256 => Hasher::Sha3_256(Sha3_256::new()),
+            HashAlgorithm::Sha3_384 => Hasher::Sha3_384(Sha3_384::new()),
+            HashAlgorithm::Sha3_512 => Hasher::Sha3_512(Sha3_512::new()),
+            HashAlgorithm::Shake128 => Hasher::Shake128(Shake128::default()),
+            HashAlgorithm::Shake256 => Hasher::Shake256(Shake256::default()),
+            #[cfg(feature = "blake3")]
+            HashAlgorithm::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+        }
+    }
+
+    pub fn digest(&self, data: impl AsRef<[u8]>) -> Vec<u8> {
+        let mut hasher = self.hasher();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    pub fn digest_reader(&self, mut reader: impl Read) -> Result<Vec<u8>, Error> {
+        let mut hasher = self.hasher();
+        match io::copy(&mut reader, &mut hasher) {
+            Ok(_) => Ok(hasher.finalize()),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::FILE_IO_ERROR.with_source(error))
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Hasher {
+    Sha256(Sha256),
+    Sha384(Sha384),
+    Sha512(Sha512),
+    Sha3_256(Sha3_256),
+    Sha3_384(Sha3_384),
+    Sha3_512(Sha3_512),
+    Shake128(Shake128),
+    Shake256(Shake256),
+    #[cfg(feature = "blake3")]
+    Blake3(Box<blake3::Hasher>),
+}
+
+impl Hasher {
+    #[cfg(feature = "blake3")]
+    pub fn blake3_keyed(key: &[u8; 32]) -> Self {
+        Hasher::Blake3(Box::new(blake3::Hasher::new_keyed(key)))
+    }
+
+    #[cfg(feature = "blake3")]
+    pub fn blake3_derive_key(context: &str) -> Self {
+        Hasher::Blake3(Box::new(blake3::Hasher::new_derive_key(context)))
+    }
+
+    pub fn algorithm(&self) -> HashAlgorithm {
+        match self {
+            Hasher::Sha256(_) => HashAlgorithm::Sha256,
+            Hasher::Sha384(_) => HashAlgorithm::Sha384,
+            Hasher::Sha512(_) => HashAlgorithm::Sha512,
+            Hasher::Sha3_256(_) => HashAlgorithm::Sha3_256,
+            Hasher::Sha3_384(_) => HashAlgorithm::Sha3_384,
+            Hasher::Sha3_512(_) => HashAlgorithm::Sha3_512,
+            Hasher::Shake128(_) => HashAlgorithm::Shake128,
+            Hasher::Shake256(_) => HashAlgorithm::Shake256,
+            #[cfg(feature = "blake3")]
+            Hasher::Blake3(_) => HashAlgorithm::Blake3,
+        }
+    }
+
+    pub fn update(&mut self, data: impl AsRef<[u8]>) {
+        let data = data.as_ref();
+        match self {
+            Hasher::Sha256(hasher) => Digest::update(hasher, data),
+            Hasher::Sha384(hasher) => Digest::update(hasher, data),
+            Hasher::Sha512(hasher) => Digest::update(hasher, data),
+            Hasher::Sha3_256(hasher) => Digest::update(hasher, data),
+            Hasher::Sha3_384(hasher) => Digest::update(hasher, data),
+            Hasher::Sha3_512(hasher) => Digest::update(hasher, data),
+            Hasher::Shake128(hasher) => Update::update(hasher, data),
+            Hasher::Shake256(hasher) => Update::update(hasher, data),
+            #[cfg(feature = "blake3")]
+            Hasher::Blake3(hasher) => {
+                hasher.update(data);
+            }
+        }
+    }
+
+    pub fn finalize(self) -> Vec<u8> {
+        let length = self.algorithm().output_length();
+        self.finalize_with_length(length)
+    }
+
+    fn finalize_with_length(self, length: usize) -> Vec<u8> {
+        match self {
+            Hasher::Sha256(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha384(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha512(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha3_256(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha3_384(hasher) => hasher.finalize().to_vec(),
+            Hasher::Sha3_512(hasher) => hasher.finalize().to_vec(),
+            Hasher::Shake128(hasher) => read_xof(hasher.finalize_xof(), length),
+            Hasher::Shake256(hasher) => read_xof(hasher.finalize_xof(), length),
+            #[cfg(feature = "blake3")]
+            Hasher::Blake3(hasher) => {
+                let mut output = vec![0u8; length];
+                hasher.finalize_xof().fill(&mut output);
+                output
+            }
+        }
+    }
+}
+
+impl Write for Hasher {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+fn read_xof(mut reader: impl XofReader, length: usize) -> Vec<u8> {
+    let mut output = vec![0u8; length];
+    reader.read(&mut output);
+    output
+}
+
+pub fn shake128(data: impl AsRef<[u8]>, length: usize) -> Vec<u8> {
+    let mut hasher = HashAlgorithm::Shake128.hasher();
+    hasher.update(data);
+    hasher.finalize_with_length(length)
+}
+
+pub fn shake256(data: impl AsRef<[u8]>, length: usize) -> Vec<u8> {
+    let mut hasher = HashAlgorithm::Shake256.hasher();
+    hasher.update(data);
+    hasher.finalize_with_length(length)
+}
+
+#[cfg(feature = "blake3")]
+pub fn blake3_keyed_hash(key: &[u8; 32], data: impl AsRef<[u8]>) -> Vec<u8> {
+    let mut hasher = Hasher::blake3_keyed(key);
+    hasher.update(data);
+    hasher.finalize()
+}
+
+#[cfg(feature = "blake3")]
+pub fn blake3_derive_key(context: &str, key_material: impl AsRef<[u8]>) -> Zeroizing<Vec<u8>> {
+    let mut hasher = Hasher::blake3_derive_key(context);
+    hasher.update(key_material);
+    Zeroizing::new(hasher.finalize())
+}
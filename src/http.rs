@@ -0,0 +1,233 @@
+use std::{
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    algorithms::Algorithm, crypto::any::AnyVerifyingKey, errors::Error, jwk::Jwks, jwt::JWT,
+    keystore::VerifyingKeyResolver, log,
+};
+
+pub const DEFAULT_JWKS_TTL: Duration = Duration::from_secs(300);
+pub const DEFAULT_MIN_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct HttpResponse {
+    pub body: Vec<u8>,
+    pub max_age: Option<Duration>,
+}
+
+pub trait HttpFetcher: Send + Sync {
+    fn get(&self, url: &str) -> Result<HttpResponse, Error>;
+}
+
+pub fn parse_max_age(cache_control: &str) -> Option<Duration> {
+    let mut max_age = None;
+    for directive in cache_control.split(',').map(str::trim) {
+        match directive.split_once('=') {
+            Some((name, value)) if name.eq_ignore_ascii_case("max-age") => {
+                max_age = value
+                    .trim_matches('"')
+                    .parse()
+                    .ok()
+                    .map(Duration::from_secs);
+            }
+            None if directive.eq_ignore_ascii_case("no-store")
+                || directive.eq_ignore_ascii_case("no-cache") =>
+            {
+                return Some(Duration::ZERO);
+            }
+            _ => {}
+        }
+    }
+
+    max_age
+}
+
+#[derive(Clone, Debug)]
+pub struct UreqFetcher {
+    agent: ureq::Agent,
+}
+
+impl Default for UreqFetcher {
+    fn default() -> Self {
+        UreqFetcher {
+            agent: ureq::AgentBuilder::new().timeout(DEFAULT_TIMEOUT).build(),
+        }
+    }
+}
+
+impl UreqFetcher {
+    pub fn new(agent: ureq::Agent) -> Self {
+        UreqFetcher { agent }
+    }
+}
+
+impl HttpFetcher for UreqFetcher {
+    fn get(&self, url: &str) -> Result<HttpResponse, Error> {
+        let response = match self.agent.get(url).call() {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::HTTP_REQUEST_FAILED.with_source(error.to_string()));
+            }
+        };
+
+        let max_age = response.header("cache-control").and_then(parse_max_age);
+        match response.into_string() {
+            Ok(val) => Ok(HttpResponse {
+                body: val.into_bytes(),
+                max_age,
+            }),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::HTTP_REQUEST_FAILED.with_source(error))
+            }
+        }
+    }
+}
+
+struct CachedJwks {
+    jwks: Arc<Jwks>,
+    checked_at: Instant,
+    expires_at: Instant,
+}
+
+pub struct JwksCache<F: HttpFetcher = UreqFetcher> {
+    url: String,
+    fetcher: F,
+    default_ttl: Duration,
+    min_refresh_interval: Duration,
+    cached: RwLock<Option<CachedJwks>>,
+}
+
+impl JwksCache {
+    pub fn new(url: &str) -> Self {
+        Self::with_fetcher(url, UreqFetcher::default())
+    }
+}
+
+impl<F: HttpFetcher> JwksCache<F> {
+    pub fn with_fetcher(url: &str, fetcher: F) -> Self {
+        JwksCache {
+            url: String::from(url),
+            fetcher,
+            default_ttl: DEFAULT_JWKS_TTL,
+            min_refresh_interval: DEFAULT_MIN_REFRESH_INTERVAL,
+            cached: RwLock::new(None),
+        }
+    }
+
+    pub fn default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    pub fn min_refresh_interval(mut self, interval: Duration) -> Self {
+        self.min_refresh_interval = interval;
+        self
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub fn fetcher(&self) -> &F {
+        &self.fetcher
+    }
+
+    fn cached(&self, now: Instant) -> Option<(Arc<Jwks>, bool, bool)> {
+        let cached = match self.cached.read() {
+            Ok(val) => val,
+            Err(error) => error.into_inner(),
+        };
+
+        cached.as_ref().map(|val| {
+            (
+                val.jwks.clone(),
+                now < val.expires_at,
+                now.duration_since(val.checked_at) >= self.min_refresh_interval,
+            )
+        })
+    }
+
+    fn fetch(&self, now: Instant) -> Result<Arc<Jwks>, Error> {
+        let response = self.fetcher.get(&self.url);
+        let mut cached = match self.cached.write() {
+            Ok(val) => val,
+            Err(error) => error.into_inner(),
+        };
+        if let Some(val) = cached.as_mut() {
+            val.checked_at = now;
+        }
+
+        let response = response?;
+        let body = match String::from_utf8(response.body) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::JWK_PARSING_ERROR.with_source(error));
+            }
+        };
+        let jwks = Arc::new(Jwks::from_json(&body)?);
+
+        *cached = Some(CachedJwks {
+            jwks: jwks.clone(),
+            checked_at: now,
+            expires_at: now + response.max_age.unwrap_or(self.default_ttl),
+        });
+        Ok(jwks)
+    }
+
+    pub fn jwks(&self) -> Result<Arc<Jwks>, Error> {
+        let now = Instant::now();
+        match self.cached(now) {
+            Some((jwks, true, _)) | Some((jwks, false, false)) => Ok(jwks),
+            Some((jwks, false, true)) => match self.fetch(now) {
+                Ok(val) => Ok(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    Ok(jwks)
+                }
+            },
+            None => self.fetch(now),
+        }
+    }
+
+    pub fn refresh(&self) -> Result<Arc<Jwks>, Error> {
+        let now = Instant::now();
+        match self.cached(now) {
+            Some((jwks, _, false)) => Ok(jwks),
+            _ => self.fetch(now),
+        }
+    }
+
+    fn jwks_with_kid(&self, kid: &str) -> Result<Arc<Jwks>, Error> {
+        let jwks = self.jwks()?;
+        match kid.is_empty() || jwks.find(kid).is_some() {
+            true => Ok(jwks),
+            false => self.refresh(),
+        }
+    }
+
+    pub fn verifying_keys(&self, kid: &str, alg: Algorithm) -> Result<Vec<AnyVerifyingKey>, Error> {
+        let kid = match kid.is_empty() {
+            true => None,
+            false => Some(kid),
+        };
+        Ok(self
+            .jwks_with_kid(kid.unwrap_or_default())?
+            .verifying_keys(kid, alg))
+    }
+
+    pub fn validate(&self, jwt: &JWT) -> Result<bool, Error> {
+        jwt.validate_with_jwks(self.jwks_with_kid(&jwt.header.kid)?.as_ref())
+    }
+}
+
+impl<F: HttpFetcher> VerifyingKeyResolver for JwksCache<F> {
+    fn resolve(&self, kid: &str) -> Result<Arc<AnyVerifyingKey>, Error> {
+        self.jwks_with_kid(kid)?.resolve(kid)
+    }
+}
@@ -0,0 +1,361 @@
+use serde_json::{json, Map, Value};
+
+use crate::{
+    crypto::any::AnyVerifyingKey,
+    encoding::canonical_json,
+    errors::Error,
+    hash::HashAlgorithm,
+    jwk::Jwk,
+    keystore::{KeyPurpose, DID_CONTEXT_V1},
+    log,
+    multihash::Multihash,
+    resolver::DidResolver,
+    verification_method::VerificationMethodType,
+};
+
+pub const ION_PREFIX: &str = "did:ion:";
+const REPLACE_ACTION: &str = "replace";
+const MAX_ID_LENGTH: usize = 50;
+
+fn is_valid_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_ID_LENGTH
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+fn hash_then_encode(bytes: &[u8]) -> String {
+    base64_url::encode(&Multihash::digest(HashAlgorithm::Sha256, bytes).to_bytes())
+}
+
+fn operation_jwk(key: &AnyVerifyingKey) -> Result<Value, Error> {
+    let jwk = Jwk {
+        alg: None,
+        kid: None,
+        ..key.to_jwk()?
+    };
+
+    match serde_json::to_value(jwk) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::ENCODING_ERROR.with_source(error))
+        }
+    }
+}
+
+pub fn reveal_value(key: &AnyVerifyingKey) -> Result<String, Error> {
+    Ok(hash_then_encode(&canonical_json(&operation_jwk(key)?)))
+}
+
+pub fn commitment(key: &AnyVerifyingKey) -> Result<String, Error> {
+    let digest = HashAlgorithm::Sha256.digest(canonical_json(&operation_jwk(key)?));
+    Ok(hash_then_encode(&digest))
+}
+
+pub struct IonDocumentBuilder {
+    method_type: VerificationMethodType,
+    keys: Vec<(String, AnyVerifyingKey, Vec<KeyPurpose>)>,
+    services: Vec<(String, String, String)>,
+}
+
+impl Default for IonDocumentBuilder {
+    fn default() -> Self {
+        IonDocumentBuilder {
+            method_type: VerificationMethodType::JsonWebKey2020,
+            keys: Vec::new(),
+            services: Vec::new(),
+        }
+    }
+}
+
+impl IonDocumentBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn method_type(mut self, method_type: VerificationMethodType) -> Self {
+        self.method_type = method_type;
+        self
+    }
+
+    pub fn key(mut self, id: &str, key: AnyVerifyingKey, purposes: &[KeyPurpose]) -> Self {
+        self.keys.push((String::from(id), key, purposes.to_vec()));
+        self
+    }
+
+    pub fn service(mut self, id: &str, service_type: &str, endpoint: &str) -> Self {
+        self.services.push((
+            String::from(id),
+            String::from(service_type),
+            String::from(endpoint),
+        ));
+        self
+    }
+
+    pub fn build(&self) -> Result<Value, Error> {
+        let mut ids: Vec<&str> = Vec::new();
+        let mut public_keys = Vec::new();
+        for (id, key, purposes) in &self.keys {
+            if !is_valid_id(id) || ids.contains(&id.as_str()) || !self.method_type.supports(key) {
+                return Err(Error::ION_INVALID_DOCUMENT.with_key(id));
+            }
+            ids.push(id);
+
+            let mut relationships: Vec<&str> = Vec::new();
+            for purpose in purposes {
+                if !relationships.contains(&purpose.relationship()) {
+                    relationships.push(purpose.relationship());
+                }
+            }
+
+            public_keys.push(json!({
+                "id": id,
+                "type": self.method_type.name(),
+                "publicKeyJwk": operation_jwk(key)?,
+                "purposes": relationships,
+            }));
+        }
+
+        let mut services = Vec::new();
+        for (id, service_type, endpoint) in &self.services {
+            if !is_valid_id(id) || ids.contains(&id.as_str()) || service_type.is_empty() {
+                return Err(Error::ION_INVALID_DOCUMENT.with_key(id));
+            }
+            ids.push(id);
+
+            services.push(json!({
+                "id": id,
+                "type": service_type,
+                "serviceEndpoint": endpoint,
+            }));
+        }
+
+        Ok(json!({
+            "publicKeys": public_keys,
+            "services": services,
+        }))
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct IonDid {
+    pub network: Option<String>,
+    pub suffix: String,
+    pub suffix_data: Value,
+    pub delta: Value,
+}
+
+impl IonDid {
+    pub fn create(
+        document: &IonDocumentBuilder,
+        update_key: &AnyVerifyingKey,
+        recovery_key: &AnyVerifyingKey,
+    ) -> Result<Self, Error> {
+        let delta = json!({
+            "patches": [{
+                "action": REPLACE_ACTION,
+                "document": document.build()?,
+            }],
+            "updateCommitment": commitment(update_key)?,
+        });
+        let suffix_data = json!({
+            "deltaHash": hash_then_encode(&canonical_json(&delta)),
+            "recoveryCommitment": commitment(recovery_key)?,
+        });
+
+        Ok(IonDid {
+            network: None,
+            suffix: hash_then_encode(&canonical_json(&suffix_data)),
+            suffix_data,
+            delta,
+        })
+    }
+
+    pub fn with_network(mut self, network: &str) -> Self {
+        self.network = Some(String::from(network));
+        self
+    }
+
+    pub fn parse(did: &str) -> Result<Self, Error> {
+        let identifier = match did.strip_prefix(ION_PREFIX) {
+            Some(val) => val,
+            None => return Err(Error::ION_INVALID_DID),
+        };
+
+        let segments: Vec<&str> = identifier.split(':').collect();
+        let (network, suffix, state) = match segments.as_slice() {
+            [suffix, state] => (None, *suffix, *state),
+            [network, suffix, state] if is_valid_id(network) => {
+                (Some(String::from(*network)), *suffix, *state)
+            }
+            _ => return Err(Error::ION_INVALID_DID),
+        };
+
+        let state = match base64_url::decode(state) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ION_INVALID_DID.with_source(error));
+            }
+        };
+        let state: Value = match serde_json::from_slice(&state) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ION_INVALID_DID.with_source(error));
+            }
+        };
+
+        let (suffix_data, delta) = match (state.get("suffixData"), state.get("delta")) {
+            (Some(suffix_data), Some(delta)) if suffix_data.is_object() && delta.is_object() => {
+                (suffix_data.clone(), delta.clone())
+            }
+            _ => return Err(Error::ION_INVALID_STATE),
+        };
+
+        let valid = suffix == hash_then_encode(&canonical_json(&suffix_data))
+            && suffix_data.get("deltaHash").and_then(Value::as_str)
+                == Some(hash_then_encode(&canonical_json(&delta)).as_str())
+            && suffix_data
+                .get("recoveryCommitment")
+                .is_some_and(Value::is_string)
+            && delta.get("updateCommitment").is_some_and(Value::is_string)
+            && delta.get("patches").is_some_and(Value::is_array);
+        if !valid {
+            return Err(Error::ION_INVALID_STATE);
+        }
+
+        Ok(IonDid {
+            network,
+            suffix: String::from(suffix),
+            suffix_data,
+            delta,
+        })
+    }
+
+    pub fn short_form(&self) -> String {
+        match &self.network {
+            Some(network) => format!("{}{}:{}", ION_PREFIX, network, self.suffix),
+            None => format!("{}{}", ION_PREFIX, self.suffix),
+        }
+    }
+
+    pub fn long_form(&self) -> String {
+        let state = json!({
+            "suffixData": self.suffix_data,
+            "delta": self.delta,
+        });
+
+        format!(
+            "{}:{}",
+            self.short_form(),
+            base64_url::encode(&canonical_json(&state))
+        )
+    }
+
+    pub fn update_commitment(&self) -> Option<&str> {
+        self.delta.get("updateCommitment").and_then(Value::as_str)
+    }
+
+    pub fn recovery_commitment(&self) -> Option<&str> {
+        self.suffix_data
+            .get("recoveryCommitment")
+            .and_then(Value::as_str)
+    }
+
+    fn initial_document(&self) -> Result<&Value, Error> {
+        let patches = match self.delta.get("patches").and_then(Value::as_array) {
+            Some(val) => val,
+            None => return Err(Error::ION_INVALID_DOCUMENT),
+        };
+
+        match patches.as_slice() {
+            [patch] if patch.get("action").and_then(Value::as_str) == Some(REPLACE_ACTION) => {
+                match patch.get("document") {
+                    Some(val) => Ok(val),
+                    None => Err(Error::ION_INVALID_DOCUMENT),
+                }
+            }
+            _ => Err(Error::ION_INVALID_DOCUMENT),
+        }
+    }
+
+    pub fn document(&self) -> Result<Value, Error> {
+        let did = self.long_form();
+        let initial = self.initial_document()?;
+
+        let mut methods = Vec::new();
+        let mut relationships: Map<String, Value> = Map::new();
+        let public_keys = initial.get("publicKeys").and_then(Value::as_array);
+        for key in public_keys.into_iter().flatten() {
+            let id = match key.get("id").and_then(Value::as_str) {
+                Some(val) if is_valid_id(val) => format!("#{}", val),
+                _ => return Err(Error::ION_INVALID_DOCUMENT),
+            };
+            let (method_type, jwk) = match (key.get("type"), key.get("publicKeyJwk")) {
+                (Some(Value::String(method_type)), Some(jwk)) if jwk.is_object() => {
+                    (method_type, jwk)
+                }
+                _ => return Err(Error::ION_INVALID_DOCUMENT.with_key(&id)),
+            };
+
+            methods.push(json!({
+                "id": id,
+                "controller": did,
+                "type": method_type,
+                "publicKeyJwk": jwk,
+            }));
+
+            let purposes = key.get("purposes").and_then(Value::as_array);
+            for purpose in purposes.into_iter().flatten() {
+                let relationship = match purpose.as_str().and_then(KeyPurpose::from_relationship) {
+                    Some(val) => val.relationship(),
+                    None => return Err(Error::ION_INVALID_DOCUMENT.with_key(&id)),
+                };
+
+                match relationships.get_mut(relationship) {
+                    Some(Value::Array(ids)) => ids.push(Value::from(id.as_str())),
+                    _ => {
+                        relationships.insert(String::from(relationship), json!([id]));
+                    }
+                }
+            }
+        }
+
+        let mut document = json!({
+            "@context": [DID_CONTEXT_V1, VerificationMethodType::JsonWebKey2020.context()],
+            "id": did,
+            "verificationMethod": methods,
+        });
+        if let Some(map) = document.as_object_mut() {
+            map.extend(relationships);
+        }
+
+        let services = initial.get("services").and_then(Value::as_array);
+        let mut service_entries = Vec::new();
+        for service in services.into_iter().flatten() {
+            let mut service = service.clone();
+            match service.get("id").and_then(Value::as_str) {
+                Some(val) if is_valid_id(val) => service["id"] = Value::from(format!("#{}", val)),
+                _ => return Err(Error::ION_INVALID_DOCUMENT),
+            }
+            service_entries.push(service);
+        }
+        if !service_entries.is_empty() {
+            document["service"] = Value::from(service_entries);
+        }
+
+        Ok(document)
+    }
+}
+
+#[derive(Clone, Copy, Default, Debug)]
+pub struct IonResolver;
+
+impl DidResolver for IonResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        IonDid::parse(did)?.document()
+    }
+}
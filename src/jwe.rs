@@ -0,0 +1,1257 @@
+use std::io::{Read, Write};
+
+use aes::Aes256;
+use aes_gcm::{
+    aead::{self, Aead, KeyInit, Payload},
+    Aes128Gcm, Aes256Gcm, Nonce,
+};
+use aes_kw::KekAes256;
+use cbc::cipher::{block_padding::Pkcs7, BlockDecryptMut, BlockEncryptMut, KeyIvInit};
+use flate2::{read::DeflateDecoder, write::DeflateEncoder, Compression};
+use hmac::{Hmac, Mac};
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::Zeroizing;
+
+use crate::{
+    crypto::x25519::{X25519PublicKey, X25519SecretKey},
+    errors::Error,
+    jwk::Jwk,
+    jwt::JWT,
+    log,
+    metrics::Operation,
+    trace,
+};
+
+const ECDH_ES_A256KW: &str = "ECDH-ES+A256KW";
+const ECDH_1PU_A256KW: &str = "ECDH-1PU+A256KW";
+const STREAM_NONCE_PREFIX_LEN: usize = 7;
+const JWT_TYPE: &str = "JWT";
+const DEFLATE: &str = "DEF";
+
+pub const STREAM_CHUNK_SIZE: usize = 64 * 1024;
+pub const DEFAULT_MAX_DECOMPRESSED_SIZE: usize = 1024 * 1024;
+pub const DEFAULT_MAX_COMPRESSION_RATIO: usize = 100;
+
+type EncryptedContent = (Vec<u8>, Vec<u8>, Vec<u8>);
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum ContentEncryption {
+    A256GCM,
+    A256CBCHS512,
+}
+
+impl ContentEncryption {
+    pub fn to_str<'a>(&self) -> &'a str {
+        match self {
+            ContentEncryption::A256GCM => "A256GCM",
+            ContentEncryption::A256CBCHS512 => "A256CBC-HS512",
+        }
+    }
+
+    pub fn from_name(enc: &str) -> Option<Self> {
+        match enc {
+            "A256GCM" => Some(ContentEncryption::A256GCM),
+            "A256CBC-HS512" => Some(ContentEncryption::A256CBCHS512),
+            _ => None,
+        }
+    }
+
+    fn key_len(&self) -> usize {
+        match self {
+            ContentEncryption::A256GCM => 32,
+            ContentEncryption::A256CBCHS512 => 64,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum KeyWrapping {
+    A128GCMKW,
+    A256GCMKW,
+}
+
+impl KeyWrapping {
+    pub fn to_str<'a>(&self) -> &'a str {
+        match self {
+            KeyWrapping::A128GCMKW => "A128GCMKW",
+            KeyWrapping::A256GCMKW => "A256GCMKW",
+        }
+    }
+
+    pub fn from_name(alg: &str) -> Option<Self> {
+        match alg {
+            "A128GCMKW" => Some(KeyWrapping::A128GCMKW),
+            "A256GCMKW" => Some(KeyWrapping::A256GCMKW),
+            _ => None,
+        }
+    }
+
+    pub fn key_len(&self) -> usize {
+        match self {
+            KeyWrapping::A128GCMKW => 16,
+            KeyWrapping::A256GCMKW => 32,
+        }
+    }
+
+    fn wrap(&self, kek: &[u8], iv: &[u8], cek: &[u8]) -> Result<Vec<u8>, Error> {
+        match self {
+            KeyWrapping::A128GCMKW => gcm_key_wrap::<Aes128Gcm>(kek, iv, cek, true),
+            KeyWrapping::A256GCMKW => gcm_key_wrap::<Aes256Gcm>(kek, iv, cek, true),
+        }
+    }
+
+    fn unwrap(&self, kek: &[u8], iv: &[u8], encrypted_key: &[u8]) -> Result<Vec<u8>, Error> {
+        match self {
+            KeyWrapping::A128GCMKW => gcm_key_wrap::<Aes128Gcm>(kek, iv, encrypted_key, false),
+            KeyWrapping::A256GCMKW => gcm_key_wrap::<Aes256Gcm>(kek, iv, encrypted_key, false),
+        }
+    }
+}
+
+fn gcm_key_wrap<C: Aead + KeyInit>(
+    kek: &[u8],
+    iv: &[u8],
+    input: &[u8],
+    wrap: bool,
+) -> Result<Vec<u8>, Error> {
+    let error = match wrap {
+        true => Error::JWE_ENCRYPTION_FAILED,
+        false => Error::JWE_DECRYPTION_FAILED,
+    };
+    let cipher = match C::new_from_slice(kek) {
+        Ok(val) => val,
+        Err(source) => {
+            log::error(source.to_string().as_str());
+            return Err(Error::JWE_INVALID_KEY_LENGTH.with_source(source));
+        }
+    };
+
+    let nonce = aead::Nonce::<C>::from_slice(iv);
+    let result = match wrap {
+        true => cipher.encrypt(nonce, input),
+        false => cipher.decrypt(nonce, input),
+    };
+    match result {
+        Ok(val) => Ok(val),
+        Err(source) => {
+            log::error(source.to_string().as_str());
+            Err(error.with_source(source.to_string()))
+        }
+    }
+}
+
+fn random_bytes(len: usize, rng: &mut (impl CryptoRng + RngCore)) -> Vec<u8> {
+    let mut bytes = vec![0u8; len];
+    rng.fill_bytes(&mut bytes);
+    bytes
+}
+
+fn decode_member(member: &str) -> Result<Vec<u8>, Error> {
+    match base64_url::decode(member) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::DECODING_ERROR.with_source(error))
+        }
+    }
+}
+
+fn concat_kdf(shared_secret: &[u8], alg: &str, apu: &[u8], apv: &[u8], tag: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(1u32.to_be_bytes());
+    hasher.update(shared_secret);
+    for info in [alg.as_bytes(), apu, apv] {
+        hasher.update((info.len() as u32).to_be_bytes());
+        hasher.update(info);
+    }
+    hasher.update(256u32.to_be_bytes());
+    if !tag.is_empty() {
+        hasher.update((tag.len() as u32).to_be_bytes());
+        hasher.update(tag);
+    }
+
+    hasher.finalize().into()
+}
+
+fn cbc_hmac(
+    mac_key: &[u8],
+    aad: &[u8],
+    iv: &[u8],
+    ciphertext: &[u8],
+) -> Result<Hmac<Sha512>, Error> {
+    let mut mac = match <Hmac<Sha512> as Mac>::new_from_slice(mac_key) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::HMAC_KEY_ERROR.with_source(error));
+        }
+    };
+
+    mac.update(aad);
+    mac.update(iv);
+    mac.update(ciphertext);
+    mac.update(&(aad.len() as u64 * 8).to_be_bytes());
+    Ok(mac)
+}
+
+fn encrypt_content(
+    enc: ContentEncryption,
+    cek: &[u8],
+    plaintext: &[u8],
+    aad: &[u8],
+    rng: &mut (impl CryptoRng + RngCore),
+) -> Result<EncryptedContent, Error> {
+    match enc {
+        ContentEncryption::A256GCM => {
+            let iv = random_bytes(12, rng);
+            let cipher = match Aes256Gcm::new_from_slice(cek) {
+                Ok(val) => val,
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::JWE_ENCRYPTION_FAILED.with_source(error));
+                }
+            };
+
+            let mut ciphertext = match cipher.encrypt(
+                Nonce::from_slice(&iv),
+                Payload {
+                    msg: plaintext,
+                    aad,
+                },
+            ) {
+                Ok(val) => val,
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::JWE_ENCRYPTION_FAILED.with_source(error.to_string()));
+                }
+            };
+
+            let tag = ciphertext.split_off(ciphertext.len() - 16);
+            Ok((iv, ciphertext, tag))
+        }
+        ContentEncryption::A256CBCHS512 => {
+            let iv = random_bytes(16, rng);
+            let (mac_key, enc_key) = cek.split_at(32);
+            let cipher = match cbc::Encryptor::<Aes256>::new_from_slices(enc_key, &iv) {
+                Ok(val) => val,
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::JWE_ENCRYPTION_FAILED.with_source(error));
+                }
+            };
+
+            let ciphertext = cipher.encrypt_padded_vec_mut::<Pkcs7>(plaintext);
+            let tag = cbc_hmac(mac_key, aad, &iv, &ciphertext)?
+                .finalize()
+                .into_bytes()[..32]
+                .to_vec();
+            Ok((iv, ciphertext, tag))
+        }
+    }
+}
+
+fn decrypt_content(
+    enc: ContentEncryption,
+    cek: &[u8],
+    iv: &[u8],
+    ciphertext: &[u8],
+    tag: &[u8],
+    aad: &[u8],
+) -> Result<Vec<u8>, Error> {
+    match enc {
+        ContentEncryption::A256GCM => {
+            if iv.len() != 12 {
+                return Err(Error::JWE_MALFORMED);
+            }
+
+            let cipher = match Aes256Gcm::new_from_slice(cek) {
+                Ok(val) => val,
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::JWE_DECRYPTION_FAILED.with_source(error));
+                }
+            };
+
+            let mut message = ciphertext.to_vec();
+            message.extend_from_slice(tag);
+            match cipher.decrypt(Nonce::from_slice(iv), Payload { msg: &message, aad }) {
+                Ok(val) => Ok(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    Err(Error::JWE_DECRYPTION_FAILED.with_source(error.to_string()))
+                }
+            }
+        }
+        ContentEncryption::A256CBCHS512 => {
+            let (mac_key, enc_key) = cek.split_at(32);
+            if tag.len() != 32
+                || cbc_hmac(mac_key, aad, iv, ciphertext)?
+                    .verify_truncated_left(tag)
+                    .is_err()
+            {
+                return Err(Error::JWE_DECRYPTION_FAILED);
+            }
+
+            let cipher = match cbc::Decryptor::<Aes256>::new_from_slices(enc_key, iv) {
+                Ok(val) => val,
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::JWE_MALFORMED.with_source(error));
+                }
+            };
+
+            match cipher.decrypt_padded_vec_mut::<Pkcs7>(ciphertext) {
+                Ok(val) => Ok(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    Err(Error::JWE_DECRYPTION_FAILED.with_source(error.to_string()))
+                }
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct StreamKey {
+    k: String,
+    iv: String,
+    chunk: usize,
+}
+
+fn stream_cipher(key: &[u8], error: Error) -> Result<Aes256Gcm, Error> {
+    match Aes256Gcm::new_from_slice(key) {
+        Ok(val) => Ok(val),
+        Err(source) => {
+            log::error(source.to_string().as_str());
+            Err(error.with_source(source))
+        }
+    }
+}
+
+fn stream_nonce(prefix: &[u8], counter: u32, last: bool) -> [u8; 12] {
+    let mut nonce = [0u8; 12];
+    nonce[..STREAM_NONCE_PREFIX_LEN].copy_from_slice(prefix);
+    nonce[STREAM_NONCE_PREFIX_LEN..11].copy_from_slice(&counter.to_be_bytes());
+    nonce[11] = last as u8;
+    nonce
+}
+
+fn read_chunk(reader: &mut impl Read, len: usize) -> Result<Vec<u8>, Error> {
+    let mut chunk = Vec::with_capacity(len);
+    match reader.take(len as u64).read_to_end(&mut chunk) {
+        Ok(_) => Ok(chunk),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::FILE_IO_ERROR.with_source(error))
+        }
+    }
+}
+
+fn transform_chunks(
+    mut reader: impl Read,
+    mut writer: impl Write,
+    chunk_size: usize,
+    prefix: &[u8],
+    transform: impl Fn(&[u8; 12], &[u8]) -> Result<Vec<u8>, Error>,
+) -> Result<u64, Error> {
+    let mut written = 0u64;
+    let mut counter = 0u32;
+    let mut current = read_chunk(&mut reader, chunk_size)?;
+    loop {
+        let next = read_chunk(&mut reader, chunk_size)?;
+        let last = next.is_empty();
+        let output = transform(&stream_nonce(prefix, counter, last), &current)?;
+        if let Err(error) = writer.write_all(&output) {
+            log::error(error.to_string().as_str());
+            return Err(Error::FILE_IO_ERROR.with_source(error));
+        }
+        written += output.len() as u64;
+
+        if last {
+            break;
+        }
+        counter = match counter.checked_add(1) {
+            Some(val) => val,
+            None => return Err(Error::JWE_ENCRYPTION_FAILED),
+        };
+        current = next;
+    }
+
+    match writer.flush() {
+        Ok(_) => Ok(written),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::FILE_IO_ERROR.with_source(error))
+        }
+    }
+}
+
+#[derive(Clone, Copy)]
+struct TypeHeader<'a> {
+    typ: &'a str,
+    cty: Option<&'a str>,
+    kid: Option<&'a str>,
+    zip: bool,
+}
+
+impl<'a> TypeHeader<'a> {
+    fn new(typ: &'a str) -> Self {
+        TypeHeader {
+            typ,
+            cty: None,
+            kid: None,
+            zip: false,
+        }
+    }
+
+    fn compressed(typ: &'a str) -> Self {
+        TypeHeader {
+            zip: true,
+            ..TypeHeader::new(typ)
+        }
+    }
+
+    fn nested(kid: Option<&'a str>) -> Self {
+        TypeHeader {
+            typ: JWT_TYPE,
+            cty: Some(JWT_TYPE),
+            kid,
+            zip: false,
+        }
+    }
+
+    fn to_map(self) -> Map<String, Value> {
+        let mut header = Map::new();
+        header.insert(String::from("typ"), Value::from(self.typ));
+        if let Some(cty) = self.cty {
+            header.insert(String::from("cty"), Value::from(cty));
+        }
+        if let Some(kid) = self.kid {
+            header.insert(String::from("kid"), Value::from(kid));
+        }
+        if self.zip {
+            header.insert(String::from("zip"), Value::from(DEFLATE));
+        }
+        header
+    }
+
+    fn compress(self, plaintext: &[u8]) -> Result<Zeroizing<Vec<u8>>, Error> {
+        if !self.zip {
+            return Ok(Zeroizing::new(plaintext.to_vec()));
+        }
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        match encoder.write_all(plaintext).and_then(|_| encoder.finish()) {
+            Ok(val) => Ok(Zeroizing::new(val)),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::JWE_ENCRYPTION_FAILED.with_source(error))
+            }
+        }
+    }
+}
+
+pub fn inflate(compressed: &[u8], max_size: usize, max_ratio: usize) -> Result<Vec<u8>, Error> {
+    let limit = max_size.min(compressed.len().saturating_mul(max_ratio));
+    let mut decompressed = Vec::new();
+    let mut decoder = DeflateDecoder::new(compressed).take(limit as u64 + 1);
+    if let Err(error) = decoder.read_to_end(&mut decompressed) {
+        log::error(error.to_string().as_str());
+        return Err(Error::JWE_MALFORMED.with_source(error));
+    }
+
+    match decompressed.len() > limit {
+        true => Err(Error::JWE_DECOMPRESSION_LIMIT_EXCEEDED),
+        false => Ok(decompressed),
+    }
+}
+
+fn decompress(header: &Map<String, Value>, plaintext: Vec<u8>) -> Result<Vec<u8>, Error> {
+    match header.get("zip") {
+        None => Ok(plaintext),
+        Some(Value::String(val)) if val == DEFLATE => {
+            let plaintext = Zeroizing::new(plaintext);
+            inflate(
+                &plaintext,
+                DEFAULT_MAX_DECOMPRESSED_SIZE,
+                DEFAULT_MAX_COMPRESSION_RATIO,
+            )
+        }
+        Some(_) => Err(Error::JWE_UNSUPPORTED_ALGORITHM),
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct RecipientHeader {
+    pub kid: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iv: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+}
+
+impl RecipientHeader {
+    pub fn new(kid: &str) -> Self {
+        RecipientHeader {
+            kid: String::from(kid),
+            alg: None,
+            iv: None,
+            tag: None,
+        }
+    }
+}
+
+fn check_recipient_kids<'a>(kids: impl Iterator<Item = &'a str>) -> Result<(), Error> {
+    let mut seen: Vec<&str> = Vec::new();
+    for kid in kids {
+        if seen.contains(&kid) {
+            return Err(Error::JWE_DUPLICATE_RECIPIENT.with_key(kid));
+        }
+        seen.push(kid);
+    }
+
+    match seen.is_empty() {
+        true => Err(Error::JWE_RECIPIENT_NOT_FOUND),
+        false => Ok(()),
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct JweRecipient {
+    pub header: RecipientHeader,
+    pub encrypted_key: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct Jwe {
+    pub protected: String,
+    pub recipients: Vec<JweRecipient>,
+    pub iv: String,
+    pub ciphertext: String,
+    pub tag: String,
+}
+
+impl Jwe {
+    pub fn encrypt(
+        plaintext: &[u8],
+        typ: &str,
+        enc: ContentEncryption,
+        recipients: &[(&str, X25519PublicKey)],
+        sender: Option<(&str, &X25519SecretKey)>,
+    ) -> Result<Self, Error> {
+        Self::encrypt_with_rng(plaintext, typ, enc, recipients, sender, &mut OsRng)
+    }
+
+    pub fn encrypt_with_rng(
+        plaintext: &[u8],
+        typ: &str,
+        enc: ContentEncryption,
+        recipients: &[(&str, X25519PublicKey)],
+        sender: Option<(&str, &X25519SecretKey)>,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<Self, Error> {
+        trace::traced(
+            Operation::Encrypt,
+            Some(enc.to_str()),
+            || sender.map(|(skid, _)| String::from(skid)),
+            || {
+                Self::seal(
+                    plaintext,
+                    TypeHeader::new(typ),
+                    enc,
+                    recipients,
+                    sender,
+                    rng,
+                )
+            },
+        )
+    }
+
+    pub fn encrypt_compressed(
+        plaintext: &[u8],
+        typ: &str,
+        enc: ContentEncryption,
+        recipients: &[(&str, X25519PublicKey)],
+        sender: Option<(&str, &X25519SecretKey)>,
+    ) -> Result<Self, Error> {
+        trace::traced(
+            Operation::Encrypt,
+            Some(enc.to_str()),
+            || sender.map(|(skid, _)| String::from(skid)),
+            || {
+                Self::seal(
+                    plaintext,
+                    TypeHeader::compressed(typ),
+                    enc,
+                    recipients,
+                    sender,
+                    &mut OsRng,
+                )
+            },
+        )
+    }
+
+    fn seal(
+        plaintext: &[u8],
+        typ: TypeHeader,
+        enc: ContentEncryption,
+        recipients: &[(&str, X25519PublicKey)],
+        sender: Option<(&str, &X25519SecretKey)>,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<Self, Error> {
+        check_recipient_kids(recipients.iter().map(|(kid, _)| *kid))?;
+
+        let alg = match sender {
+            Some(_) if enc != ContentEncryption::A256CBCHS512 => {
+                return Err(Error::JWE_UNSUPPORTED_ALGORITHM)
+            }
+            Some(_) => ECDH_1PU_A256KW,
+            None => ECDH_ES_A256KW,
+        };
+
+        let mut kids: Vec<&str> = recipients.iter().map(|(kid, _)| *kid).collect();
+        kids.sort();
+        let apv = Sha256::digest(kids.join(".").as_bytes()).to_vec();
+        let apu = match sender {
+            Some((skid, _)) => skid.as_bytes().to_vec(),
+            None => Vec::new(),
+        };
+
+        let ephemeral_key = X25519SecretKey::generate_with_rng(rng);
+        let epk = match serde_json::to_value(ephemeral_key.public_key().to_jwk()) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ENCODING_ERROR.with_source(error));
+            }
+        };
+
+        let mut header = typ.to_map();
+        header.insert(String::from("alg"), Value::from(alg));
+        header.insert(String::from("enc"), Value::from(enc.to_str()));
+        header.insert(String::from("epk"), epk);
+        header.insert(String::from("apv"), Value::from(base64_url::encode(&apv)));
+        if let Some((skid, _)) = sender {
+            header.insert(String::from("skid"), Value::from(skid));
+            header.insert(String::from("apu"), Value::from(base64_url::encode(&apu)));
+        }
+
+        let protected = match serde_json::to_string(&header) {
+            Ok(val) => base64_url::encode(val.as_bytes()),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ENCODING_ERROR.with_source(error));
+            }
+        };
+
+        let cek = random_bytes(enc.key_len(), rng);
+        let plaintext = typ.compress(plaintext)?;
+        let (iv, ciphertext, tag) =
+            encrypt_content(enc, &cek, &plaintext, protected.as_bytes(), rng)?;
+
+        let mut jwe_recipients = Vec::new();
+        for (kid, key) in recipients {
+            let mut shared_secret = ephemeral_key.diffie_hellman(key)?.to_vec();
+            let cc_tag: &[u8] = match sender {
+                Some((_, sender_key)) => {
+                    shared_secret.extend_from_slice(&sender_key.diffie_hellman(key)?);
+                    &tag
+                }
+                None => &[],
+            };
+
+            let kek = concat_kdf(&shared_secret, alg, &apu, &apv, cc_tag);
+            let encrypted_key = match KekAes256::from(kek).wrap_vec(&cek) {
+                Ok(val) => val,
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::JWE_ENCRYPTION_FAILED.with_source(error.to_string()));
+                }
+            };
+
+            jwe_recipients.push(JweRecipient {
+                header: RecipientHeader::new(kid),
+                encrypted_key: base64_url::encode(&encrypted_key),
+            });
+        }
+
+        Ok(Jwe {
+            protected,
+            recipients: jwe_recipients,
+            iv: base64_url::encode(&iv),
+            ciphertext: base64_url::encode(&ciphertext),
+            tag: base64_url::encode(&tag),
+        })
+    }
+
+    pub fn encrypt_with_kek(
+        plaintext: &[u8],
+        typ: &str,
+        enc: ContentEncryption,
+        kid: &str,
+        alg: KeyWrapping,
+        kek: &[u8],
+    ) -> Result<Self, Error> {
+        trace::traced(
+            Operation::Encrypt,
+            Some(enc.to_str()),
+            || Some(String::from(kid)),
+            || {
+                Self::seal_with_kek(
+                    plaintext,
+                    TypeHeader::new(typ),
+                    enc,
+                    kid,
+                    alg,
+                    kek,
+                    &mut OsRng,
+                )
+            },
+        )
+    }
+
+    pub fn encrypt_with_kek_compressed(
+        plaintext: &[u8],
+        typ: &str,
+        enc: ContentEncryption,
+        kid: &str,
+        alg: KeyWrapping,
+        kek: &[u8],
+    ) -> Result<Self, Error> {
+        trace::traced(
+            Operation::Encrypt,
+            Some(enc.to_str()),
+            || Some(String::from(kid)),
+            || {
+                Self::seal_with_kek(
+                    plaintext,
+                    TypeHeader::compressed(typ),
+                    enc,
+                    kid,
+                    alg,
+                    kek,
+                    &mut OsRng,
+                )
+            },
+        )
+    }
+
+    fn seal_with_kek(
+        plaintext: &[u8],
+        typ: TypeHeader,
+        enc: ContentEncryption,
+        kid: &str,
+        alg: KeyWrapping,
+        kek: &[u8],
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<Self, Error> {
+        if kek.len() != alg.key_len() {
+            return Err(Error::JWE_INVALID_KEY_LENGTH);
+        }
+
+        let cek = Zeroizing::new(random_bytes(enc.key_len(), rng));
+        let wrap_iv = random_bytes(12, rng);
+        let mut encrypted_key = alg.wrap(kek, &wrap_iv, &cek)?;
+        let wrap_tag = encrypted_key.split_off(encrypted_key.len() - 16);
+
+        let mut header = typ.to_map();
+        header.insert(String::from("alg"), Value::from(alg.to_str()));
+        header.insert(String::from("enc"), Value::from(enc.to_str()));
+        header.insert(String::from("kid"), Value::from(kid));
+        header.insert(
+            String::from("iv"),
+            Value::from(base64_url::encode(&wrap_iv)),
+        );
+        header.insert(
+            String::from("tag"),
+            Value::from(base64_url::encode(&wrap_tag)),
+        );
+
+        let protected = match serde_json::to_string(&header) {
+            Ok(val) => base64_url::encode(val.as_bytes()),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ENCODING_ERROR.with_source(error));
+            }
+        };
+
+        let plaintext = typ.compress(plaintext)?;
+        let (iv, ciphertext, tag) =
+            encrypt_content(enc, &cek, &plaintext, protected.as_bytes(), rng)?;
+
+        Ok(Jwe {
+            protected,
+            recipients: vec![JweRecipient {
+                header: RecipientHeader::new(kid),
+                encrypted_key: base64_url::encode(&encrypted_key),
+            }],
+            iv: base64_url::encode(&iv),
+            ciphertext: base64_url::encode(&ciphertext),
+            tag: base64_url::encode(&tag),
+        })
+    }
+
+    pub fn encrypt_with_keks(
+        plaintext: &[u8],
+        typ: &str,
+        enc: ContentEncryption,
+        recipients: &[(&str, KeyWrapping, &[u8])],
+    ) -> Result<Self, Error> {
+        trace::traced(
+            Operation::Encrypt,
+            Some(enc.to_str()),
+            || None,
+            || Self::seal_with_keks(plaintext, typ, enc, recipients, &mut OsRng),
+        )
+    }
+
+    fn seal_with_keks(
+        plaintext: &[u8],
+        typ: &str,
+        enc: ContentEncryption,
+        recipients: &[(&str, KeyWrapping, &[u8])],
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<Self, Error> {
+        check_recipient_kids(recipients.iter().map(|(kid, _, _)| *kid))?;
+        if recipients
+            .iter()
+            .any(|(_, alg, kek)| kek.len() != alg.key_len())
+        {
+            return Err(Error::JWE_INVALID_KEY_LENGTH);
+        }
+
+        let mut header = TypeHeader::new(typ).to_map();
+        header.insert(String::from("enc"), Value::from(enc.to_str()));
+        let protected = match serde_json::to_string(&header) {
+            Ok(val) => base64_url::encode(val.as_bytes()),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ENCODING_ERROR.with_source(error));
+            }
+        };
+
+        let cek = Zeroizing::new(random_bytes(enc.key_len(), rng));
+        let mut jwe_recipients = Vec::new();
+        for (kid, alg, kek) in recipients {
+            let wrap_iv = random_bytes(12, rng);
+            let mut encrypted_key = alg.wrap(kek, &wrap_iv, &cek)?;
+            let wrap_tag = encrypted_key.split_off(encrypted_key.len() - 16);
+
+            jwe_recipients.push(JweRecipient {
+                header: RecipientHeader {
+                    alg: Some(String::from(alg.to_str())),
+                    iv: Some(base64_url::encode(&wrap_iv)),
+                    tag: Some(base64_url::encode(&wrap_tag)),
+                    ..RecipientHeader::new(kid)
+                },
+                encrypted_key: base64_url::encode(&encrypted_key),
+            });
+        }
+
+        let (iv, ciphertext, tag) =
+            encrypt_content(enc, &cek, plaintext, protected.as_bytes(), rng)?;
+
+        Ok(Jwe {
+            protected,
+            recipients: jwe_recipients,
+            iv: base64_url::encode(&iv),
+            ciphertext: base64_url::encode(&ciphertext),
+            tag: base64_url::encode(&tag),
+        })
+    }
+
+    pub fn encrypt_jwt(
+        jwt: &JWT,
+        enc: ContentEncryption,
+        recipients: &[(&str, X25519PublicKey)],
+        sender: Option<(&str, &X25519SecretKey)>,
+    ) -> Result<Self, Error> {
+        let token = jwt.to_token()?;
+        let kid = match recipients {
+            [(kid, _)] => Some(*kid),
+            _ => None,
+        };
+        trace::traced(
+            Operation::Encrypt,
+            Some(enc.to_str()),
+            || sender.map(|(skid, _)| String::from(skid)),
+            || {
+                Self::seal(
+                    token.as_bytes(),
+                    TypeHeader::nested(kid),
+                    enc,
+                    recipients,
+                    sender,
+                    &mut OsRng,
+                )
+            },
+        )
+    }
+
+    pub fn encrypt_jwt_with_kek(
+        jwt: &JWT,
+        enc: ContentEncryption,
+        kid: &str,
+        alg: KeyWrapping,
+        kek: &[u8],
+    ) -> Result<Self, Error> {
+        let token = jwt.to_token()?;
+        trace::traced(
+            Operation::Encrypt,
+            Some(enc.to_str()),
+            || Some(String::from(kid)),
+            || {
+                Self::seal_with_kek(
+                    token.as_bytes(),
+                    TypeHeader::nested(None),
+                    enc,
+                    kid,
+                    alg,
+                    kek,
+                    &mut OsRng,
+                )
+            },
+        )
+    }
+
+    pub fn is_nested_jwt(&self) -> Result<bool, Error> {
+        match self.protected_header()?.get("cty") {
+            Some(Value::String(val)) => Ok(val.eq_ignore_ascii_case(JWT_TYPE)),
+            Some(_) => Err(Error::JWE_MALFORMED),
+            None => Ok(false),
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        match serde_json::from_str(json) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::JWE_MALFORMED.with_source(error))
+            }
+        }
+    }
+
+    pub fn from_compact(token: &str) -> Result<Self, Error> {
+        let parts: Vec<&str> = token.split('.').collect();
+        if parts.len() != 5 {
+            return Err(Error::JWE_MALFORMED);
+        }
+
+        let mut jwe = Jwe {
+            protected: String::from(parts[0]),
+            recipients: Vec::new(),
+            iv: String::from(parts[2]),
+            ciphertext: String::from(parts[3]),
+            tag: String::from(parts[4]),
+        };
+        let kid = match jwe.protected_header()?.get("kid") {
+            Some(Value::String(val)) => val.clone(),
+            Some(_) => return Err(Error::JWE_MALFORMED),
+            None => String::new(),
+        };
+        jwe.recipients.push(JweRecipient {
+            header: RecipientHeader::new(&kid),
+            encrypted_key: String::from(parts[1]),
+        });
+
+        Ok(jwe)
+    }
+
+    pub fn to_compact(&self) -> Result<String, Error> {
+        match self.recipients.as_slice() {
+            [recipient] => Ok(format!(
+                "{}.{}.{}.{}.{}",
+                self.protected, recipient.encrypted_key, self.iv, self.ciphertext, self.tag
+            )),
+            _ => Err(Error::JWE_MALFORMED),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        match serde_json::to_string(self) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENCODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn protected_header(&self) -> Result<Map<String, Value>, Error> {
+        match serde_json::from_slice(&decode_member(&self.protected)?) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::JWE_MALFORMED.with_source(error))
+            }
+        }
+    }
+
+    pub fn sender_kid(&self) -> Result<Option<String>, Error> {
+        match self.protected_header()?.get("skid") {
+            Some(Value::String(val)) => Ok(Some(val.clone())),
+            Some(_) => Err(Error::JWE_MALFORMED),
+            None => Ok(None),
+        }
+    }
+
+    pub fn recipient_kids(&self) -> Vec<&str> {
+        self.recipients
+            .iter()
+            .map(|recipient| recipient.header.kid.as_str())
+            .collect()
+    }
+
+    pub fn decrypt(
+        &self,
+        kid: &str,
+        key: &X25519SecretKey,
+        sender_key: Option<&X25519PublicKey>,
+    ) -> Result<Vec<u8>, Error> {
+        trace::traced(
+            Operation::Decrypt,
+            None,
+            || Some(String::from(kid)),
+            || self.open(kid, key, sender_key),
+        )
+    }
+
+    fn open(
+        &self,
+        kid: &str,
+        key: &X25519SecretKey,
+        sender_key: Option<&X25519PublicKey>,
+    ) -> Result<Vec<u8>, Error> {
+        let header = self.protected_header()?;
+        let header_member = |name: &str| match header.get(name) {
+            Some(Value::String(val)) => Ok(val.as_str()),
+            Some(_) => Err(Error::JWE_MALFORMED),
+            None => Ok(""),
+        };
+
+        let enc = match ContentEncryption::from_name(header_member("enc")?) {
+            Some(val) => val,
+            None => return Err(Error::JWE_UNSUPPORTED_ALGORITHM),
+        };
+        let alg = header_member("alg")?;
+        let apu = decode_member(header_member("apu")?)?;
+        let apv = decode_member(header_member("apv")?)?;
+
+        let epk: Jwk = match header
+            .get("epk")
+            .map(|val| serde_json::from_value(val.clone()))
+        {
+            Some(Ok(val)) => val,
+            Some(Err(error)) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::JWE_MALFORMED.with_source(error));
+            }
+            None => return Err(Error::JWE_MALFORMED),
+        };
+        let ephemeral_key = X25519PublicKey::from_jwk(&epk)?;
+
+        let recipient = match self
+            .recipients
+            .iter()
+            .find(|recipient| recipient.header.kid == kid)
+        {
+            Some(val) => val,
+            None => return Err(Error::JWE_RECIPIENT_NOT_FOUND),
+        };
+
+        let tag = decode_member(&self.tag)?;
+        let mut shared_secret = key.diffie_hellman(&ephemeral_key)?.to_vec();
+        let cc_tag: &[u8] = match (alg, sender_key) {
+            (ECDH_ES_A256KW, _) => &[],
+            (ECDH_1PU_A256KW, Some(sender_key)) if enc == ContentEncryption::A256CBCHS512 => {
+                shared_secret.extend_from_slice(&key.diffie_hellman(sender_key)?);
+                &tag
+            }
+            (ECDH_1PU_A256KW, None) => return Err(Error::PUBLIC_KEY_IDENTIFICATION_ERROR),
+            _ => return Err(Error::JWE_UNSUPPORTED_ALGORITHM),
+        };
+
+        let kek = concat_kdf(&shared_secret, alg, &apu, &apv, cc_tag);
+        let cek = match KekAes256::from(kek).unwrap_vec(&decode_member(&recipient.encrypted_key)?) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::JWE_DECRYPTION_FAILED.with_source(error.to_string()));
+            }
+        };
+
+        if cek.len() != enc.key_len() {
+            return Err(Error::JWE_DECRYPTION_FAILED);
+        }
+
+        let plaintext = decrypt_content(
+            enc,
+            &cek,
+            &decode_member(&self.iv)?,
+            &decode_member(&self.ciphertext)?,
+            &tag,
+            self.protected.as_bytes(),
+        )?;
+        decompress(&header, plaintext)
+    }
+
+    pub fn decrypt_any(
+        &self,
+        keys: &[(&str, &X25519SecretKey)],
+        sender_key: Option<&X25519PublicKey>,
+    ) -> Result<(String, Vec<u8>), Error> {
+        let recipient_kids = self.recipient_kids();
+        match keys.iter().find(|(kid, _)| recipient_kids.contains(kid)) {
+            Some((kid, key)) => Ok((String::from(*kid), self.decrypt(kid, key, sender_key)?)),
+            None => Err(Error::JWE_RECIPIENT_NOT_FOUND),
+        }
+    }
+
+    pub fn decrypt_with_kek(&self, kid: &str, kek: &[u8]) -> Result<Vec<u8>, Error> {
+        trace::traced(
+            Operation::Decrypt,
+            None,
+            || Some(String::from(kid)),
+            || self.open_with_kek(kid, kek),
+        )
+    }
+
+    fn open_with_kek(&self, kid: &str, kek: &[u8]) -> Result<Vec<u8>, Error> {
+        let recipient = match self
+            .recipients
+            .iter()
+            .find(|recipient| recipient.header.kid == kid)
+        {
+            Some(val) => val,
+            None => return Err(Error::JWE_RECIPIENT_NOT_FOUND),
+        };
+
+        let header = self.protected_header()?;
+        let header_member =
+            |name: &str, unprotected: Option<&String>| match (header.get(name), unprotected) {
+                (Some(Value::String(val)), None) => Ok(val.clone()),
+                (None, Some(val)) => Ok(val.clone()),
+                _ => Err(Error::JWE_MALFORMED),
+            };
+
+        let (alg, enc) = match (
+            KeyWrapping::from_name(&header_member("alg", recipient.header.alg.as_ref())?),
+            ContentEncryption::from_name(&header_member("enc", None)?),
+        ) {
+            (Some(alg), Some(enc)) => (alg, enc),
+            _ => return Err(Error::JWE_UNSUPPORTED_ALGORITHM),
+        };
+        if kek.len() != alg.key_len() {
+            return Err(Error::JWE_INVALID_KEY_LENGTH);
+        }
+
+        let wrap_iv = decode_member(&header_member("iv", recipient.header.iv.as_ref())?)?;
+        let wrap_tag = decode_member(&header_member("tag", recipient.header.tag.as_ref())?)?;
+        if wrap_iv.len() != 12 || wrap_tag.len() != 16 {
+            return Err(Error::JWE_MALFORMED);
+        }
+
+        let mut encrypted_key = decode_member(&recipient.encrypted_key)?;
+        encrypted_key.extend_from_slice(&wrap_tag);
+        let cek = Zeroizing::new(alg.unwrap(kek, &wrap_iv, &encrypted_key)?);
+        if cek.len() != enc.key_len() {
+            return Err(Error::JWE_DECRYPTION_FAILED);
+        }
+
+        let plaintext = decrypt_content(
+            enc,
+            &cek,
+            &decode_member(&self.iv)?,
+            &decode_member(&self.ciphertext)?,
+            &decode_member(&self.tag)?,
+            self.protected.as_bytes(),
+        )?;
+        decompress(&header, plaintext)
+    }
+
+    pub fn encrypt_stream(
+        reader: impl Read,
+        writer: impl Write,
+        typ: &str,
+        enc: ContentEncryption,
+        recipients: &[(&str, X25519PublicKey)],
+        sender: Option<(&str, &X25519SecretKey)>,
+    ) -> Result<Self, Error> {
+        let key = Zeroizing::new(random_bytes(32, &mut OsRng));
+        let prefix = random_bytes(STREAM_NONCE_PREFIX_LEN, &mut OsRng);
+        let stream_key = match serde_json::to_vec(&StreamKey {
+            k: base64_url::encode(key.as_slice()),
+            iv: base64_url::encode(&prefix),
+            chunk: STREAM_CHUNK_SIZE,
+        }) {
+            Ok(val) => Zeroizing::new(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ENCODING_ERROR.with_source(error));
+            }
+        };
+        let jwe = Self::encrypt(&stream_key, typ, enc, recipients, sender)?;
+
+        let cipher = stream_cipher(&key, Error::JWE_ENCRYPTION_FAILED)?;
+        transform_chunks(
+            reader,
+            writer,
+            STREAM_CHUNK_SIZE,
+            &prefix,
+            |nonce, chunk| match cipher.encrypt(Nonce::from_slice(nonce), chunk) {
+                Ok(val) => Ok(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    Err(Error::JWE_ENCRYPTION_FAILED.with_source(error.to_string()))
+                }
+            },
+        )?;
+
+        Ok(jwe)
+    }
+
+    pub fn decrypt_stream(
+        &self,
+        kid: &str,
+        key: &X25519SecretKey,
+        sender_key: Option<&X25519PublicKey>,
+        reader: impl Read,
+        writer: impl Write,
+    ) -> Result<u64, Error> {
+        let stream_key = Zeroizing::new(self.decrypt(kid, key, sender_key)?);
+        let stream_key: StreamKey = match serde_json::from_slice(&stream_key) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::JWE_MALFORMED.with_source(error));
+            }
+        };
+
+        let content_key = Zeroizing::new(decode_member(&stream_key.k)?);
+        let prefix = decode_member(&stream_key.iv)?;
+        if prefix.len() != STREAM_NONCE_PREFIX_LEN || stream_key.chunk == 0 {
+            return Err(Error::JWE_MALFORMED);
+        }
+
+        let cipher = stream_cipher(&content_key, Error::JWE_DECRYPTION_FAILED)?;
+        transform_chunks(
+            reader,
+            writer,
+            stream_key.chunk + 16,
+            &prefix,
+            |nonce, chunk| match cipher.decrypt(Nonce::from_slice(nonce), chunk) {
+                Ok(val) => Ok(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    Err(Error::JWE_DECRYPTION_FAILED.with_source(error.to_string()))
+                }
+            },
+        )
+    }
+}
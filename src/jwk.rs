@@ -0,0 +1,194 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    algorithms::Algorithm,
+    crypto::any::AnyVerifyingKey,
+    crypto::policy::KeyPolicy,
+    encoding::{self, JsonFormat},
+    errors::Error,
+    keystore::{KeyLookup, VerifyingKeyResolver},
+    limits::InputLimits,
+    log,
+};
+
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, Debug)]
+pub struct Jwk {
+    pub kty: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crv: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub x: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub y: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub d: Option<String>,
+    #[serde(default, rename = "pub", skip_serializing_if = "Option::is_none")]
+    pub public: Option<String>,
+    #[serde(default, rename = "priv", skip_serializing_if = "Option::is_none")]
+    pub private: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alg: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kid: Option<String>,
+    #[serde(default, rename = "use", skip_serializing_if = "Option::is_none")]
+    pub key_use: Option<String>,
+}
+
+impl Jwk {
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        match serde_json::from_str(json) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::JWK_PARSING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn from_json_with_limits(json: &str, limits: &InputLimits) -> Result<Self, Error> {
+        limits.check_key(json)?;
+        Self::from_json(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        self.to_json_with_format(JsonFormat::Canonical)
+    }
+
+    pub fn to_json_with_format(&self, format: JsonFormat) -> Result<String, Error> {
+        encoding::to_json(self, format)
+    }
+
+    pub fn thumbprint(&self) -> Result<String, Error> {
+        let required: &[(&str, &Option<String>)] = match self.kty.as_str() {
+            "EC" => &[("crv", &self.crv), ("x", &self.x), ("y", &self.y)],
+            "OKP" => &[("crv", &self.crv), ("x", &self.x)],
+            "AKP" => &[("alg", &self.alg), ("pub", &self.public)],
+            _ => return Err(Error::JWK_KEY_TYPE_MISMATCH),
+        };
+
+        let mut members = BTreeMap::new();
+        members.insert("kty", self.kty.as_str());
+        for (name, value) in required {
+            match value {
+                Some(val) => members.insert(name, val.as_str()),
+                None => return Err(Error::JWK_MISSING_MEMBER),
+            };
+        }
+
+        match serde_json::to_vec(&members) {
+            Ok(val) => Ok(base64_url::encode(Sha256::digest(val).as_slice())),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENCODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub(crate) fn decode_member(member: &Option<String>) -> Result<Vec<u8>, Error> {
+        let value = match member {
+            Some(val) => val,
+            None => return Err(Error::JWK_MISSING_MEMBER),
+        };
+
+        match base64_url::decode(value) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::DECODING_ERROR.with_source(error))
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Default, PartialEq, Eq, Debug)]
+pub struct Jwks {
+    pub keys: Vec<Jwk>,
+}
+
+impl Jwks {
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        match serde_json::from_str(json) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::JWK_PARSING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn from_json_with_limits(json: &str, limits: &InputLimits) -> Result<Self, Error> {
+        limits.check_document(json.as_bytes())?;
+        Self::from_json(json)
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        self.to_json_with_format(JsonFormat::Canonical)
+    }
+
+    pub fn to_json_with_format(&self, format: JsonFormat) -> Result<String, Error> {
+        encoding::to_json(self, format)
+    }
+
+    pub fn find(&self, kid: &str) -> Option<&Jwk> {
+        self.keys.iter().find(|key| key.kid.as_deref() == Some(kid))
+    }
+
+    pub fn select(
+        &self,
+        kid: Option<&str>,
+        alg: Option<Algorithm>,
+        key_use: Option<&str>,
+    ) -> Vec<&Jwk> {
+        self.keys
+            .iter()
+            .filter(|key| kid.is_none() || key.kid.as_deref() == kid)
+            .filter(|key| match (alg, key.alg.as_deref()) {
+                (Some(alg), Some(val)) => val == alg.to_str(),
+                _ => true,
+            })
+            .filter(|key| match (key_use, key.key_use.as_deref()) {
+                (Some(key_use), Some(val)) => val == key_use,
+                _ => true,
+            })
+            .collect()
+    }
+
+    pub fn verifying_keys(&self, kid: Option<&str>, alg: Algorithm) -> Vec<AnyVerifyingKey> {
+        self.select(kid, Some(alg), Some("sig"))
+            .into_iter()
+            .filter_map(|jwk| AnyVerifyingKey::from_jwk(jwk).ok())
+            .map(|key| key.with_algorithm(alg))
+            .filter(|key| key.algorithm() == alg)
+            .collect()
+    }
+
+    pub fn verifying_keys_with_policy(
+        &self,
+        kid: Option<&str>,
+        alg: Algorithm,
+        policy: &KeyPolicy,
+    ) -> Result<Vec<AnyVerifyingKey>, Error> {
+        policy.check_all(self.verifying_keys(kid, alg))
+    }
+}
+
+impl VerifyingKeyResolver for Jwks {
+    fn resolve(&self, kid: &str) -> Result<Arc<AnyVerifyingKey>, Error> {
+        match self.find(kid) {
+            Some(val) => Ok(Arc::new(AnyVerifyingKey::from_jwk(val)?)),
+            None => Err(Error::KEYSTORE_KEY_NOT_FOUND.with_key(kid)),
+        }
+    }
+}
+
+impl KeyLookup for Jwks {
+    fn lookup(&self, kid: Option<&str>, alg: Algorithm) -> Result<Arc<AnyVerifyingKey>, Error> {
+        match self.verifying_keys(kid, alg).into_iter().next() {
+            Some(val) => Ok(Arc::new(val)),
+            None => Err(Error::JWKS_KEY_NOT_FOUND.with_algorithm(alg)),
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    algorithms::Algorithm,
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        SignFromKey,
+    },
+    errors::Error,
+    jwt::{check_key_algorithm, reject_alg_none},
+    keystore::{signing_key, KeyLookup, KeyPurpose, KeyStore, VerifyingKeyResolver},
+    log,
+    resolver::did_from_url,
+    signer::sign,
+    verifier::verify,
+};
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct JwsSignature {
+    pub protected: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<Value>,
+    pub signature: String,
+}
+
+impl JwsSignature {
+    pub fn protected_header(&self) -> Result<Value, Error> {
+        decode_json(&decode_segment(&self.protected)?)
+    }
+
+    pub fn kid(&self) -> Option<String> {
+        let protected = self.protected_header().ok();
+        protected
+            .as_ref()
+            .and_then(|val| val.get("kid"))
+            .or_else(|| self.header.as_ref().and_then(|val| val.get("kid")))
+            .and_then(Value::as_str)
+            .map(String::from)
+    }
+
+    pub fn algorithm(&self) -> Result<Algorithm, Error> {
+        let header = self.protected_header()?;
+        reject_alg_none(&header)?;
+        match header
+            .get("alg")
+            .and_then(Value::as_str)
+            .and_then(|alg| alg.parse::<Algorithm>().ok())
+        {
+            Some(val) => Ok(val),
+            None => Err(Error::UNKNOWN_ALGORITHM),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ThresholdPolicy {
+    threshold: usize,
+    signers: Vec<String>,
+}
+
+impl ThresholdPolicy {
+    pub fn new(threshold: usize, signers: &[&str]) -> Self {
+        ThresholdPolicy {
+            threshold,
+            signers: signers.iter().map(|val| String::from(*val)).collect(),
+        }
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn signers(&self) -> &[String] {
+        &self.signers
+    }
+
+    fn signer_for(&self, kid: &str) -> Option<&String> {
+        self.signers
+            .iter()
+            .find(|signer| *signer == kid || signer.as_str() == did_from_url(kid))
+    }
+}
+
+fn decode_segment(segment: &str) -> Result<Vec<u8>, Error> {
+    match base64_url::decode(segment) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::JWS_MALFORMED.with_source(error))
+        }
+    }
+}
+
+fn decode_json<T: serde::de::DeserializeOwned>(bytes: &[u8]) -> Result<T, Error> {
+    match serde_json::from_slice(bytes) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::JWS_MALFORMED.with_source(error))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug)]
+pub struct GeneralJws {
+    pub payload: String,
+    pub signatures: Vec<JwsSignature>,
+}
+
+impl GeneralJws {
+    pub fn new(payload: impl AsRef<[u8]>) -> Self {
+        GeneralJws {
+            payload: base64_url::encode(payload.as_ref()),
+            signatures: Vec::new(),
+        }
+    }
+
+    pub fn payload(&self) -> Result<Vec<u8>, Error> {
+        decode_segment(&self.payload)
+    }
+
+    pub fn sign(&mut self, kid: &str, alg: Algorithm, key: impl SignFromKey) -> Result<(), Error> {
+        let protected = json!({ "alg": alg.to_str(), "kid": kid });
+        let protected = base64_url::encode(protected.to_string().as_bytes());
+        let signature = sign(format!("{}.{}", protected, self.payload), key, alg)?;
+
+        self.signatures.push(JwsSignature {
+            protected,
+            header: None,
+            signature,
+        });
+        Ok(())
+    }
+
+    pub fn sign_with_store(
+        &mut self,
+        store: &impl KeyStore<AnySigningKey>,
+        kid: &str,
+    ) -> Result<(), Error> {
+        let key = signing_key(store, kid, KeyPurpose::AssertionMethod)?;
+        self.sign(kid, key.algorithm(), key)
+    }
+
+    pub fn kids(&self) -> Vec<String> {
+        self.signatures
+            .iter()
+            .filter_map(JwsSignature::kid)
+            .collect()
+    }
+
+    pub fn verify_signature(
+        &self,
+        signature: &JwsSignature,
+        resolver: &impl VerifyingKeyResolver,
+    ) -> Result<bool, Error> {
+        let kid = match signature.kid() {
+            Some(val) => val,
+            None => return Err(Error::KEYSTORE_KEY_NOT_FOUND),
+        };
+
+        self.verify_with_key(signature, resolver.resolve(&kid)?.as_ref())
+    }
+
+    pub fn verify_with_lookup(
+        &self,
+        signature: &JwsSignature,
+        keys: &impl KeyLookup,
+    ) -> Result<bool, Error> {
+        let alg = signature.algorithm()?;
+        let key = keys.lookup(signature.kid().as_deref(), alg)?;
+        self.verify_with_key(signature, key.as_ref())
+    }
+
+    pub fn verify_with_key(
+        &self,
+        signature: &JwsSignature,
+        key: &AnyVerifyingKey,
+    ) -> Result<bool, Error> {
+        let alg = signature.algorithm()?;
+        check_key_algorithm(key, alg)?;
+        verify(
+            format!("{}.{}", signature.protected, self.payload),
+            &signature.signature,
+            key,
+            alg,
+        )
+    }
+
+    pub fn verify_threshold(
+        &self,
+        resolver: &impl VerifyingKeyResolver,
+        policy: &ThresholdPolicy,
+    ) -> Result<Vec<String>, Error> {
+        let mut satisfied: Vec<String> = Vec::new();
+        for signature in &self.signatures {
+            let signer = match signature.kid().and_then(|kid| policy.signer_for(&kid)) {
+                Some(val) => val,
+                None => continue,
+            };
+
+            if satisfied.contains(signer) {
+                continue;
+            }
+
+            if let Ok(true) = self.verify_signature(signature, resolver) {
+                satisfied.push(signer.clone());
+            }
+        }
+
+        match policy.threshold > 0 && satisfied.len() >= policy.threshold {
+            true => Ok(satisfied),
+            false => Err(Error::JWS_THRESHOLD_NOT_MET),
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        match serde_json::to_string(self) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENCODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let jws: GeneralJws = decode_json(json.as_bytes())?;
+        if jws.signatures.is_empty() {
+            return Err(Error::JWS_MALFORMED);
+        }
+
+        Ok(jws)
+    }
+}
@@ -1,15 +1,29 @@
 use crate::{
-    algorithms::Algorithm,
-    crypto::{SignFromKey, VerifyFromKey},
+    algorithms::{Algorithm, AlgorithmPolicy},
+    blocklist, clock,
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        policy::KeyPolicy,
+        x509, AsyncSignFromKey, SignFromKey, VerifyFromKey,
+    },
+    dpop::{DpopProof, DpopValidation},
     errors::Error,
-    log,
+    jwe::Jwe,
+    jwk::{Jwk, Jwks},
+    keystore::{signing_key, KeyLookup, KeyPurpose, KeyRing, KeyStore, VerifyingKeyResolver},
+    limits::InputLimits,
+    log, nonce,
+    resolver::{decode_token_segment, did_from_url, DEFAULT_CLOCK_LEEWAY},
     signer::sign,
-    verifier::verify,
+    verifier::{verify, VerificationOptions},
 };
 use base64::{engine::general_purpose::STANDARD, Engine};
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, TimeDelta, Utc};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::Value;
+use std::time::Duration;
+
+pub const DEFAULT_REFRESH_LIFETIME: Duration = Duration::from_secs(3600);
 
 trait Base64Encode
 where
@@ -30,12 +44,12 @@ where
                 Ok(val) => val,
                 Err(error) => {
                     log::error(error.to_string().as_str());
-                    return Err(Error::JWT_UTF8_ERROR);
+                    return Err(Error::JWT_UTF8_ERROR.with_source(error));
                 }
             },
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::DECODING_ERROR);
+                return Err(Error::DECODING_ERROR.with_source(error));
             }
         };
 
@@ -43,17 +57,20 @@ where
             Ok(val) => Ok(val),
             Err(error) => {
                 log::error(error.to_string().as_str());
-                return Err(Error::JWT_HEADER_DESERIALIZING_ERROR);
+                return Err(Error::JWT_HEADER_DESERIALIZING_ERROR.with_source(error));
             }
         }
     }
 }
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Header {
     pub typ: String,
     pub alg: Algorithm,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub kid: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub x5c: Vec<String>,
 }
 
 impl Header {
@@ -62,7 +79,28 @@ impl Header {
             kid,
             alg,
             typ: String::from("JWT"),
+            x5c: Vec::new(),
+        }
+    }
+
+    pub fn with_x5c(mut self, chain: &[Vec<u8>]) -> Self {
+        self.x5c = chain.iter().map(|der| STANDARD.encode(der)).collect();
+        self
+    }
+
+    pub fn x5c_chain(&self) -> Result<Vec<Vec<u8>>, Error> {
+        let mut chain = Vec::new();
+        for cert in &self.x5c {
+            match STANDARD.decode(cert) {
+                Ok(val) => chain.push(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::DECODING_ERROR.with_source(error));
+                }
+            }
         }
+
+        Ok(chain)
     }
 }
 
@@ -79,9 +117,25 @@ impl ToString for Header {
 }
 
 impl FromBase64Encoded for Header {}
+
+pub(crate) fn reject_alg_none(header: &Value) -> Result<(), Error> {
+    match header.get("alg").and_then(Value::as_str) {
+        Some(alg) if alg.eq_ignore_ascii_case("none") => Err(Error::JWT_ALG_NONE),
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn check_key_algorithm(key: &impl VerifyFromKey, alg: Algorithm) -> Result<(), Error> {
+    match key.verifying_algorithms() {
+        Some(algorithms) if !algorithms.contains(&alg) => {
+            Err(Error::JWT_ALGORITHM_KEY_MISMATCH.with_algorithm(alg))
+        }
+        _ => Ok(()),
+    }
+}
 impl Base64Encode for Header {}
 
-#[derive(Serialize, Deserialize)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct Payload(pub Value);
 
 impl ToString for Payload {
@@ -96,6 +150,136 @@ impl ToString for Payload {
     }
 }
 
+impl Payload {
+    pub fn issued_at(&self) -> Result<Option<DateTime<Utc>>, Error> {
+        match self.0.get("iat") {
+            Some(val) => match val
+                .as_i64()
+                .and_then(|val| DateTime::from_timestamp(val, 0))
+            {
+                Some(val) => Ok(Some(val)),
+                None => Err(Error::FAILED_TO_CONVERT_TIMESTAMP_TO_DATETTIME),
+            },
+            None => Ok(None),
+        }
+    }
+
+    pub fn confirmation(&self) -> Result<Option<Confirmation>, Error> {
+        match self.0.get("cnf") {
+            Some(val) => Ok(Some(Confirmation::from_value(val)?)),
+            None => Ok(None),
+        }
+    }
+
+    pub fn with_confirmation(mut self, confirmation: &Confirmation) -> Result<Self, Error> {
+        match &mut self.0 {
+            Value::Object(claims) => {
+                claims.insert(String::from("cnf"), confirmation.to_value()?);
+                Ok(self)
+            }
+            _ => Err(Error::JWT_PAYLOAD_DESERIALIZING_ERROR),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub enum Confirmation {
+    Jwk(Box<Jwk>),
+    Jkt(String),
+}
+
+impl Confirmation {
+    pub fn from_key(key: &AnyVerifyingKey) -> Result<Self, Error> {
+        Ok(Confirmation::Jwk(Box::new(key.to_jwk()?)))
+    }
+
+    pub fn thumbprint(key: &AnyVerifyingKey) -> Result<Self, Error> {
+        Ok(Confirmation::Jkt(key.to_jwk()?.thumbprint()?))
+    }
+
+    pub fn from_value(value: &Value) -> Result<Self, Error> {
+        if let Some(jwk) = value.get("jwk") {
+            return match serde_json::from_value::<Jwk>(jwk.clone()) {
+                Ok(val) if val.d.is_none() && val.private.is_none() => {
+                    Ok(Confirmation::Jwk(Box::new(val)))
+                }
+                Ok(_) => Err(Error::JWT_CNF_INVALID),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    Err(Error::JWT_CNF_INVALID.with_source(error))
+                }
+            };
+        }
+
+        match value.get("jkt").and_then(Value::as_str) {
+            Some(val) => Ok(Confirmation::Jkt(String::from(val))),
+            None => Err(Error::JWT_CNF_INVALID),
+        }
+    }
+
+    pub fn to_value(&self) -> Result<Value, Error> {
+        let (name, value) = match self {
+            Confirmation::Jwk(jwk) => match serde_json::to_value(jwk) {
+                Ok(val) => ("jwk", val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::ENCODING_ERROR.with_source(error));
+                }
+            },
+            Confirmation::Jkt(jkt) => ("jkt", Value::from(jkt.as_str())),
+        };
+
+        let mut cnf = serde_json::Map::new();
+        cnf.insert(String::from(name), value);
+        Ok(Value::Object(cnf))
+    }
+
+    pub fn jkt(&self) -> Result<String, Error> {
+        match self {
+            Confirmation::Jwk(jwk) => jwk.thumbprint(),
+            Confirmation::Jkt(jkt) => Ok(jkt.clone()),
+        }
+    }
+
+    pub fn verifying_key(&self) -> Result<AnyVerifyingKey, Error> {
+        match self {
+            Confirmation::Jwk(jwk) => AnyVerifyingKey::from_jwk(jwk),
+            Confirmation::Jkt(_) => Err(Error::JWT_CNF_INVALID),
+        }
+    }
+
+    pub fn matches(&self, key: &AnyVerifyingKey) -> Result<bool, Error> {
+        Ok(self.jkt()? == key.to_jwk()?.thumbprint()?)
+    }
+
+    pub fn verify_possession(
+        &self,
+        message: impl AsRef<[u8]>,
+        signature: &str,
+        key: &AnyVerifyingKey,
+        alg: Algorithm,
+    ) -> Result<bool, Error> {
+        if !self.matches(key)? {
+            return Ok(false);
+        }
+
+        check_key_algorithm(key, alg)?;
+        verify(message, signature, key, alg)
+    }
+
+    pub fn verify_dpop(
+        &self,
+        proof: &DpopProof,
+        validation: &DpopValidation,
+    ) -> Result<bool, Error> {
+        let jkt = self.jkt()?;
+        proof.verify(&DpopValidation {
+            jkt: Some(&jkt),
+            ..*validation
+        })
+    }
+}
+
 impl FromBase64Encoded for Payload {}
 impl Base64Encode for Payload {}
 
@@ -137,7 +321,7 @@ impl JWT {
             self.payload.to_base64_encoded()
         );
 
-        match sign(content.clone(), private_key, self.header.alg) {
+        match sign(&content, private_key, self.header.alg) {
             Ok(val) => {
                 self.signature = Some(Signature(val));
                 Ok(())
@@ -146,8 +330,57 @@ impl JWT {
         }
     }
 
+    pub async fn sign_async(&mut self, private_key: &impl AsyncSignFromKey) -> Result<(), Error> {
+        let content = format!(
+            "{}.{}",
+            self.header.to_base64_encoded(),
+            self.payload.to_base64_encoded()
+        );
+
+        let signature = private_key
+            .sign_bytes_async(content.as_bytes(), self.header.alg)
+            .await?;
+        self.signature = Some(Signature(signature));
+        Ok(())
+    }
+
+    pub fn sign_with_store(
+        &mut self,
+        store: &impl KeyStore<AnySigningKey>,
+        kid: &str,
+    ) -> Result<(), Error> {
+        self.sign_with_store_for(store, kid, KeyPurpose::AssertionMethod)
+    }
+
+    pub fn sign_with_store_for(
+        &mut self,
+        store: &impl KeyStore<AnySigningKey>,
+        kid: &str,
+        purpose: KeyPurpose,
+    ) -> Result<(), Error> {
+        let key = signing_key(store, kid, purpose)?;
+        self.header.kid = String::from(kid);
+        self.header.alg = key.algorithm();
+        self.sign(key)
+    }
+
     pub fn from_token(token: &str) -> Result<Self, Error> {
         let token_content: Vec<&str> = token.split(".").collect();
+        if token_content.len() != 3 {
+            return Err(Error::JWT_MALFORMED);
+        }
+
+        if let Some(header) = STANDARD
+            .decode(token_content[0])
+            .ok()
+            .and_then(|val| serde_json::from_slice::<Value>(&val).ok())
+        {
+            reject_alg_none(&header)?;
+        }
+
+        if token_content[2].is_empty() {
+            return Err(Error::JWT_NO_SIGNATURE_FOUND);
+        }
 
         let header = match Header::from_base64_encoded(token_content[0]) {
             Ok(val) => val,
@@ -168,8 +401,19 @@ impl JWT {
         })
     }
 
+    pub fn from_token_with_limits(token: &str, limits: &InputLimits) -> Result<Self, Error> {
+        limits.check_token(token)?;
+        Self::from_token(token)
+    }
+
+    pub fn from_token_with_policy(token: &str, policy: &AlgorithmPolicy) -> Result<Self, Error> {
+        let token = Self::from_token(token)?;
+        policy.check(token.header.alg)?;
+        Ok(token)
+    }
+
     fn check_if_expired(timestamp_secs: i64) -> Result<bool, Error> {
-        let now = Utc::now();
+        let now = clock::now();
         let exp_time = match DateTime::from_timestamp_millis(timestamp_secs * 1000) {
             Some(val) => val,
             None => {
@@ -180,41 +424,152 @@ impl JWT {
         Ok(now < exp_time)
     }
 
-    pub fn validate(&self, public_key: impl VerifyFromKey) -> Result<bool, Error> {
+    fn verify_signature(&self, public_key: impl VerifyFromKey) -> Result<bool, Error> {
         let algorithm = self.header.alg;
 
         let signature = match &self.signature {
-            Some(val) => val.clone(),
+            Some(val) => val,
             None => return Err(Error::JWT_NO_SIGNATURE_FOUND),
         };
 
-        let verified = match verify(
+        check_key_algorithm(&public_key, algorithm)?;
+
+        verify(
             format!(
                 "{}.{}",
                 self.header.to_base64_encoded(),
                 self.payload.to_base64_encoded()
             ),
-            signature.0,
+            &signature.0,
             public_key,
             algorithm,
-        ) {
-            Ok(val) => val,
-            Err(error) => return Err(error),
-        };
+        )
+    }
+
+    fn expires_at(&self) -> Result<i64, Error> {
+        match self.payload.0.get("exp") {
+            Some(val) => match val.as_i64() {
+                Some(val) => Ok(val),
+                None => Err(Error::JWT_PAYLOAD_FIELD_EXP_IDENTIFICATION_ERROR),
+            },
+            None => Err(Error::JWT_PAYLOAD_MISSING_FIELD_EXP),
+        }
+    }
 
-        if !verified {
+    pub fn validate(&self, public_key: impl VerifyFromKey) -> Result<bool, Error> {
+        if !self.verify_signature(public_key)? {
             return Ok(false);
         }
 
-        let exp = match self.payload.0.get("exp") {
-            Some(val) => match val.as_i64() {
-                Some(val) => val,
-                None => return Err(Error::JWT_PAYLOAD_FIELD_EXP_IDENTIFICATION_ERROR),
-            },
-            None => return Err(Error::JWT_PAYLOAD_MISSING_FIELD_EXP),
+        blocklist::check(self)?;
+        Self::check_if_expired(self.expires_at()?)
+    }
+
+    pub fn validate_with_options(
+        &self,
+        public_key: impl VerifyFromKey,
+        options: &VerificationOptions,
+    ) -> Result<bool, Error> {
+        options.algorithm_policy.check(self.header.alg)?;
+        options.apply(self.validate(public_key))
+    }
+
+    pub fn validate_with_store(&self, resolver: &impl VerifyingKeyResolver) -> Result<bool, Error> {
+        if self.header.kid.is_empty() {
+            return Err(Error::KEYSTORE_KEY_NOT_FOUND);
+        }
+
+        self.validate(resolver.resolve_at(&self.header.kid, self.payload.issued_at()?)?)
+    }
+
+    pub fn validate_with_lookup(&self, keys: &impl KeyLookup) -> Result<bool, Error> {
+        let kid = match self.header.kid.is_empty() {
+            true => None,
+            false => Some(self.header.kid.as_str()),
         };
 
-        Self::check_if_expired(exp)
+        self.validate(keys.lookup(kid, self.header.alg)?)
+    }
+
+    pub fn validate_with_jwks(&self, jwks: &Jwks) -> Result<bool, Error> {
+        self.validate_with_jwks_policy(jwks, &KeyPolicy::permissive())
+    }
+
+    pub fn validate_with_jwks_policy(
+        &self,
+        jwks: &Jwks,
+        policy: &KeyPolicy,
+    ) -> Result<bool, Error> {
+        let kid = match self.header.kid.is_empty() {
+            true => None,
+            false => Some(self.header.kid.as_str()),
+        };
+
+        let keys = jwks.verifying_keys_with_policy(kid, self.header.alg, policy)?;
+        if keys.is_empty() {
+            return Err(Error::JWKS_KEY_NOT_FOUND.with_algorithm(self.header.alg));
+        }
+
+        for key in keys {
+            if self.validate(key)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+
+    pub fn confirmation(&self) -> Result<Confirmation, Error> {
+        match self.payload.confirmation()? {
+            Some(val) => Ok(val),
+            None => Err(Error::JWT_CNF_MISSING),
+        }
+    }
+
+    pub fn validate_with_x5c(&self, trust_anchors: &[Vec<u8>]) -> Result<bool, Error> {
+        self.validate_with_x5c_at(trust_anchors, clock::now())
+    }
+
+    pub fn validate_with_x5c_at(
+        &self,
+        trust_anchors: &[Vec<u8>],
+        at: DateTime<Utc>,
+    ) -> Result<bool, Error> {
+        self.validate_with_x5c_policy_at(trust_anchors, at, &KeyPolicy::permissive())
+    }
+
+    pub fn validate_with_x5c_policy(
+        &self,
+        trust_anchors: &[Vec<u8>],
+        policy: &KeyPolicy,
+    ) -> Result<bool, Error> {
+        self.validate_with_x5c_policy_at(trust_anchors, clock::now(), policy)
+    }
+
+    pub fn validate_with_x5c_policy_at(
+        &self,
+        trust_anchors: &[Vec<u8>],
+        at: DateTime<Utc>,
+        policy: &KeyPolicy,
+    ) -> Result<bool, Error> {
+        if self.header.x5c.is_empty() {
+            return Err(Error::JWT_X5C_MISSING);
+        }
+
+        let key = x509::validate_chain(&self.header.x5c_chain()?, trust_anchors, at)?
+            .with_algorithm(self.header.alg);
+        policy.check(&key)?;
+
+        self.validate(key)
+    }
+
+    pub fn validate_token_with_lookup(
+        token_str: &str,
+        keys: &impl KeyLookup,
+    ) -> Result<(Self, bool), Error> {
+        let token = Self::from_token(token_str)?;
+        let verified = token.validate_with_lookup(keys)?;
+        Ok((token, verified))
     }
 
     pub fn validate_token(
@@ -233,4 +588,290 @@ impl JWT {
 
         Ok((token, verified))
     }
+    pub fn refresh(
+        token: &str,
+        keys: &impl KeyLookup,
+        store: &impl KeyStore<AnySigningKey>,
+        kid: &str,
+        options: &RefreshOptions,
+    ) -> Result<String, Error> {
+        Self::refresh_at(token, keys, store, kid, options, clock::now())
+    }
+
+    pub fn refresh_with_ring<S: KeyStore>(
+        token: &str,
+        ring: &KeyRing<S>,
+        options: &RefreshOptions,
+    ) -> Result<String, Error> {
+        Self::refresh(token, ring, ring.store(), &ring.current_kid()?, options)
+    }
+
+    pub fn refresh_at(
+        token: &str,
+        keys: &impl KeyLookup,
+        store: &impl KeyStore<AnySigningKey>,
+        kid: &str,
+        options: &RefreshOptions,
+        at: DateTime<Utc>,
+    ) -> Result<String, Error> {
+        let token = Self::from_token(token)?;
+        options.algorithm_policy.check(token.header.alg)?;
+
+        let previous_kid = match token.header.kid.is_empty() {
+            true => None,
+            false => Some(token.header.kid.as_str()),
+        };
+        if !token.verify_signature(keys.lookup(previous_kid, token.header.alg)?)? {
+            return Err(Error::JWT_SIGNATURE_INVALID);
+        }
+        blocklist::check(&token)?;
+        if previous_kid
+            .is_some_and(|val| val.starts_with("did:") && did_from_url(val) != did_from_url(kid))
+        {
+            return Err(Error::JWT_REFRESH_KEY_MISMATCH.with_key(kid));
+        }
+
+        let mut refreshed = JWT {
+            header: token.header.clone(),
+            payload: token.payload.refreshed(token.expires_at()?, options, at)?,
+            signature: None,
+        };
+        if token.header.kid != kid {
+            refreshed.header.x5c = Vec::new();
+        }
+
+        refreshed.sign_with_store(store, kid)?;
+        refreshed.to_token()
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct RefreshOptions {
+    pub lifetime: Duration,
+    pub refresh_window: Option<Duration>,
+    pub expired_grace: Duration,
+    pub max_session: Option<Duration>,
+    pub algorithm_policy: AlgorithmPolicy,
+}
+
+impl Default for RefreshOptions {
+    fn default() -> Self {
+        RefreshOptions {
+            lifetime: DEFAULT_REFRESH_LIFETIME,
+            refresh_window: None,
+            expired_grace: Duration::ZERO,
+            max_session: None,
+            algorithm_policy: AlgorithmPolicy::default(),
+        }
+    }
+}
+
+impl RefreshOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn lifetime(mut self, lifetime: Duration) -> Self {
+        self.lifetime = lifetime;
+        self
+    }
+
+    pub fn refresh_window(mut self, window: Duration) -> Self {
+        self.refresh_window = Some(window);
+        self
+    }
+
+    pub fn expired_grace(mut self, grace: Duration) -> Self {
+        self.expired_grace = grace;
+        self
+    }
+
+    pub fn max_session(mut self, max_session: Duration) -> Self {
+        self.max_session = Some(max_session);
+        self
+    }
+
+    pub fn algorithm_policy(mut self, policy: AlgorithmPolicy) -> Self {
+        self.algorithm_policy = policy;
+        self
+    }
+}
+
+fn add_duration(time: DateTime<Utc>, duration: Duration) -> Option<DateTime<Utc>> {
+    TimeDelta::from_std(duration)
+        .ok()
+        .and_then(|val| time.checked_add_signed(val))
+}
+
+impl Payload {
+    fn refreshed(
+        &self,
+        exp: i64,
+        options: &RefreshOptions,
+        at: DateTime<Utc>,
+    ) -> Result<Self, Error> {
+        let expires = match DateTime::from_timestamp(exp, 0) {
+            Some(val) => val,
+            None => return Err(Error::FAILED_TO_CONVERT_TIMESTAMP_TO_DATETTIME),
+        };
+        if add_duration(expires, options.expired_grace).is_some_and(|val| val <= at) {
+            return Err(Error::JWT_EXPIRED);
+        }
+        if options
+            .refresh_window
+            .is_some_and(|window| add_duration(at, window).is_some_and(|val| expires > val))
+        {
+            return Err(Error::JWT_REFRESH_NOT_ALLOWED);
+        }
+
+        let mut claims = match &self.0 {
+            Value::Object(val) => val.clone(),
+            _ => return Err(Error::JWT_PAYLOAD_DESERIALIZING_ERROR),
+        };
+
+        let session_start = match claims.get("auth_time") {
+            Some(val) => match val.as_i64() {
+                Some(val) => Some(val),
+                None => return Err(Error::JWT_PAYLOAD_DESERIALIZING_ERROR),
+            },
+            None => self.issued_at()?.map(|val| val.timestamp()),
+        };
+        let mut expires = match add_duration(at, options.lifetime) {
+            Some(val) => val,
+            None => return Err(Error::JWT_REFRESH_NOT_ALLOWED),
+        };
+        if let Some(max_session) = options.max_session {
+            let session_end = match session_start
+                .and_then(|val| DateTime::from_timestamp(val, 0))
+                .and_then(|val| add_duration(val, max_session))
+            {
+                Some(val) => val,
+                None => return Err(Error::JWT_REFRESH_NOT_ALLOWED),
+            };
+            if session_end <= at {
+                return Err(Error::JWT_REFRESH_NOT_ALLOWED);
+            }
+            expires = expires.min(session_end);
+        }
+
+        if let Some(session_start) = session_start {
+            claims.insert(String::from("auth_time"), Value::from(session_start));
+        }
+        claims.insert(String::from("iat"), Value::from(at.timestamp()));
+        claims.insert(String::from("exp"), Value::from(expires.timestamp()));
+        if claims.contains_key("nbf") {
+            claims.insert(String::from("nbf"), Value::from(at.timestamp()));
+        }
+        if claims.contains_key("jti") {
+            claims.insert(String::from("jti"), Value::from(nonce::generate()));
+        }
+
+        Ok(Payload(Value::Object(claims)))
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct UnverifiedJwt {
+    header: Value,
+    claims: Value,
+    token: String,
+}
+
+impl UnverifiedJwt {
+    pub fn untrusted_header(&self) -> &Value {
+        &self.header
+    }
+
+    pub fn untrusted_claims(&self) -> &Value {
+        &self.claims
+    }
+
+    pub fn alg(&self) -> Option<Algorithm> {
+        self.header
+            .get("alg")
+            .and_then(|val| serde_json::from_value(val.clone()).ok())
+    }
+
+    pub fn kid(&self) -> Option<&str> {
+        self.header.get("kid").and_then(Value::as_str)
+    }
+
+    pub fn typ(&self) -> Option<&str> {
+        self.header.get("typ").and_then(Value::as_str)
+    }
+
+    pub fn issuer(&self) -> Option<&str> {
+        self.claims.get("iss").and_then(Value::as_str)
+    }
+
+    pub fn audience(&self) -> Vec<&str> {
+        match self.claims.get("aud") {
+            Some(Value::String(val)) => vec![val.as_str()],
+            Some(Value::Array(val)) => val.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn token(&self) -> &str {
+        &self.token
+    }
+}
+
+pub fn decode_unverified(token: &str) -> Result<UnverifiedJwt, Error> {
+    let jws = token.split('~').next().unwrap_or(token);
+    let parts: Vec<&str> = jws.split('.').collect();
+    if parts.len() != 3 {
+        return Err(Error::JWT_MALFORMED);
+    }
+
+    let header = decode_token_segment(parts[0], Error::JWT_HEADER_DESERIALIZING_ERROR)?;
+    reject_alg_none(&header)?;
+
+    Ok(UnverifiedJwt {
+        header,
+        claims: decode_token_segment(parts[1], Error::JWT_PAYLOAD_DESERIALIZING_ERROR)?,
+        token: String::from(token),
+    })
+}
+
+pub fn decode_nested(
+    token: &str,
+    decrypt: impl FnOnce(&Jwe) -> Result<Vec<u8>, Error>,
+    keys: &impl KeyLookup,
+) -> Result<JWT, Error> {
+    let jwe = Jwe::from_compact(token)?;
+    if !jwe.is_nested_jwt()? {
+        return Err(Error::JWT_NOT_NESTED);
+    }
+
+    let plaintext = decrypt(&jwe)?;
+    let inner = match std::str::from_utf8(&plaintext) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::JWT_UTF8_ERROR.with_source(error));
+        }
+    };
+
+    let jwt = JWT::from_token(inner)?;
+    let kid = match jwt.header.kid.is_empty() {
+        true => None,
+        false => Some(jwt.header.kid.as_str()),
+    };
+    if !jwt.verify_signature(keys.lookup(kid, jwt.header.alg)?)? {
+        return Err(Error::JWT_SIGNATURE_INVALID);
+    }
+
+    let now = clock::now().timestamp();
+    let leeway = DEFAULT_CLOCK_LEEWAY.as_secs() as i64;
+    if jwt.expires_at()? <= now - leeway {
+        return Err(Error::JWT_EXPIRED);
+    }
+    match jwt.payload.0.get("nbf").map(Value::as_i64) {
+        Some(Some(nbf)) if nbf > now + leeway => return Err(Error::JWT_NOT_YET_VALID),
+        Some(None) => return Err(Error::JWT_PAYLOAD_FIELD_NBF_IDENTIFICATION_ERROR),
+        _ => {}
+    }
+
+    Ok(jwt)
 }
@@ -0,0 +1,367 @@
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    algorithms::Algorithm,
+    crypto::{any::AnyVerifyingKey, SignFromKey, VerifyFromKey},
+    encoding::{canonical_json, multibase_decode, multibase_encode},
+    errors::Error,
+    hash::HashAlgorithm,
+    log,
+    multihash::Multihash,
+};
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum KeyEventType {
+    #[serde(rename = "icp")]
+    Inception,
+    #[serde(rename = "rot")]
+    Rotation,
+    #[serde(rename = "ixn")]
+    Interaction,
+}
+
+pub fn encode_key(key: &AnyVerifyingKey) -> Result<String, Error> {
+    Ok(multibase_encode(key.to_multicodec()?))
+}
+
+pub fn decode_key(key: &str) -> Result<AnyVerifyingKey, Error> {
+    match multibase_decode(key).and_then(|val| AnyVerifyingKey::from_multicodec(&val)) {
+        Ok(val) => Ok(val),
+        Err(error) => Err(Error::KEL_INVALID_EVENT.with_source(error)),
+    }
+}
+
+pub fn key_digest(key: &AnyVerifyingKey) -> Result<String, Error> {
+    Ok(digest(&key.to_multicodec()?))
+}
+
+fn digest(bytes: &[u8]) -> String {
+    base64_url::encode(&Multihash::digest(HashAlgorithm::Sha256, bytes).to_bytes())
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct KeyEvent {
+    #[serde(rename = "t")]
+    pub event_type: KeyEventType,
+    #[serde(rename = "d")]
+    pub digest: String,
+    #[serde(rename = "i")]
+    pub prefix: String,
+    #[serde(rename = "s")]
+    pub sequence: u64,
+    #[serde(rename = "p", default, skip_serializing_if = "Option::is_none")]
+    pub previous: Option<String>,
+    #[serde(rename = "kt", default)]
+    pub threshold: usize,
+    #[serde(rename = "k", default, skip_serializing_if = "Vec::is_empty")]
+    pub keys: Vec<String>,
+    #[serde(rename = "nt", default)]
+    pub next_threshold: usize,
+    #[serde(rename = "n", default, skip_serializing_if = "Vec::is_empty")]
+    pub next_keys: Vec<String>,
+    #[serde(rename = "a", default, skip_serializing_if = "Vec::is_empty")]
+    pub anchors: Vec<Value>,
+}
+
+impl KeyEvent {
+    pub fn inception(
+        keys: &[AnyVerifyingKey],
+        threshold: usize,
+        next_keys: &[String],
+        next_threshold: usize,
+    ) -> Result<Self, Error> {
+        let mut event = KeyEvent {
+            event_type: KeyEventType::Inception,
+            digest: String::new(),
+            prefix: String::new(),
+            sequence: 0,
+            previous: None,
+            threshold,
+            keys: keys.iter().map(encode_key).collect::<Result<_, _>>()?,
+            next_threshold,
+            next_keys: next_keys.to_vec(),
+            anchors: Vec::new(),
+        };
+
+        event.digest = event.compute_digest()?;
+        event.prefix = event.digest.clone();
+        Ok(event)
+    }
+
+    fn compute_digest(&self) -> Result<String, Error> {
+        let mut event = self.clone();
+        event.digest = String::new();
+        if event.event_type == KeyEventType::Inception {
+            event.prefix = String::new();
+        }
+
+        Ok(digest(&canonical_json(&to_value(&event)?)))
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        Ok(canonical_json(&to_value(self)?))
+    }
+}
+
+fn to_value(event: &KeyEvent) -> Result<Value, Error> {
+    match serde_json::to_value(event) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::ENCODING_ERROR.with_source(error))
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct IndexedSignature {
+    pub index: usize,
+    pub signature: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct SignedKeyEvent {
+    pub event: KeyEvent,
+    pub signatures: Vec<IndexedSignature>,
+}
+
+impl SignedKeyEvent {
+    pub fn new(event: KeyEvent) -> Self {
+        SignedKeyEvent {
+            event,
+            signatures: Vec::new(),
+        }
+    }
+
+    pub fn sign(
+        mut self,
+        index: usize,
+        key: impl SignFromKey,
+        alg: Algorithm,
+    ) -> Result<Self, Error> {
+        let signature = key.sign_bytes(&self.event.to_bytes()?, alg)?;
+        self.signatures.push(IndexedSignature { index, signature });
+        Ok(self)
+    }
+
+    fn verified_indexes(&self, keys: &[String]) -> Result<Vec<usize>, Error> {
+        let content = self.event.to_bytes()?;
+        let mut indexes: Vec<usize> = Vec::new();
+        for signature in &self.signatures {
+            let key = match keys.get(signature.index) {
+                Some(val) => decode_key(val)?,
+                None => return Err(Error::KEL_INVALID_EVENT),
+            };
+
+            if !key.verify_bytes(&content, &signature.signature, key.algorithm())? {
+                return Err(Error::KEL_THRESHOLD_NOT_MET);
+            }
+            if !indexes.contains(&signature.index) {
+                indexes.push(signature.index);
+            }
+        }
+
+        Ok(indexes)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct KeyState {
+    pub prefix: String,
+    pub sequence: u64,
+    pub digest: String,
+    pub threshold: usize,
+    pub keys: Vec<String>,
+    pub next_threshold: usize,
+    pub next_keys: Vec<String>,
+}
+
+impl KeyState {
+    pub fn verifying_keys(&self) -> Result<Vec<AnyVerifyingKey>, Error> {
+        self.keys.iter().map(|key| decode_key(key)).collect()
+    }
+
+    pub fn is_transferable(&self) -> bool {
+        !self.next_keys.is_empty()
+    }
+
+    fn apply(state: Option<&KeyState>, signed: &SignedKeyEvent) -> Result<KeyState, Error> {
+        let event = &signed.event;
+        if event.digest != event.compute_digest()? {
+            return Err(Error::KEL_INVALID_EVENT);
+        }
+
+        let consistent = match (event.event_type, state) {
+            (KeyEventType::Inception, None) => {
+                event.sequence == 0 && event.previous.is_none() && event.prefix == event.digest
+            }
+            (KeyEventType::Rotation | KeyEventType::Interaction, Some(state)) => {
+                event.prefix == state.prefix
+                    && event.sequence == state.sequence + 1
+                    && event.previous.as_ref() == Some(&state.digest)
+            }
+            _ => false,
+        };
+        if !consistent {
+            return Err(Error::KEL_INVALID_EVENT);
+        }
+
+        let next = match (event.event_type, state) {
+            (KeyEventType::Interaction, Some(state)) => {
+                if !event.keys.is_empty() || !event.next_keys.is_empty() {
+                    return Err(Error::KEL_INVALID_EVENT);
+                }
+
+                KeyState {
+                    sequence: event.sequence,
+                    digest: event.digest.clone(),
+                    ..state.clone()
+                }
+            }
+            _ => KeyState {
+                prefix: event.prefix.clone(),
+                sequence: event.sequence,
+                digest: event.digest.clone(),
+                threshold: event.threshold,
+                keys: event.keys.clone(),
+                next_threshold: event.next_threshold,
+                next_keys: event.next_keys.clone(),
+            },
+        };
+
+        let valid_thresholds = next.threshold >= 1
+            && next.threshold <= next.keys.len()
+            && next.next_threshold <= next.next_keys.len()
+            && (next.next_keys.is_empty() || next.next_threshold >= 1);
+        if !valid_thresholds {
+            return Err(Error::KEL_INVALID_EVENT);
+        }
+
+        let signed_indexes = signed.verified_indexes(&next.keys)?;
+        if signed_indexes.len() < next.threshold {
+            return Err(Error::KEL_THRESHOLD_NOT_MET);
+        }
+
+        if let (KeyEventType::Rotation, Some(state)) = (event.event_type, state) {
+            let mut committed = 0;
+            for key in &next.keys {
+                if !state.next_keys.contains(&key_digest(&decode_key(key)?)?) {
+                    return Err(Error::KEL_ROTATION_NOT_COMMITTED);
+                }
+            }
+            for index in &signed_indexes {
+                if state
+                    .next_keys
+                    .contains(&key_digest(&decode_key(&next.keys[*index])?)?)
+                {
+                    committed += 1;
+                }
+            }
+
+            if !state.is_transferable() || committed < state.next_threshold {
+                return Err(Error::KEL_ROTATION_NOT_COMMITTED);
+            }
+        }
+
+        Ok(next)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug, Default)]
+pub struct KeyEventLog {
+    pub events: Vec<SignedKeyEvent>,
+}
+
+impl KeyEventLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        match serde_json::from_str(json) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::KEL_INVALID_EVENT.with_source(error))
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        match serde_json::to_string(self) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENCODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn state(&self) -> Result<KeyState, Error> {
+        let mut state: Option<KeyState> = None;
+        for signed in &self.events {
+            state = Some(KeyState::apply(state.as_ref(), signed)?);
+        }
+
+        match state {
+            Some(val) => Ok(val),
+            None => Err(Error::KEL_INVALID_EVENT),
+        }
+    }
+
+    pub fn append(&mut self, signed: SignedKeyEvent) -> Result<KeyState, Error> {
+        let state = match self.events.is_empty() {
+            true => None,
+            false => Some(self.state()?),
+        };
+
+        let next = KeyState::apply(state.as_ref(), &signed)?;
+        self.events.push(signed);
+        Ok(next)
+    }
+
+    pub fn rotation(
+        &self,
+        keys: &[AnyVerifyingKey],
+        threshold: usize,
+        next_keys: &[String],
+        next_threshold: usize,
+    ) -> Result<KeyEvent, Error> {
+        let state = self.state()?;
+        let mut event = KeyEvent {
+            event_type: KeyEventType::Rotation,
+            digest: String::new(),
+            prefix: state.prefix,
+            sequence: state.sequence + 1,
+            previous: Some(state.digest),
+            threshold,
+            keys: keys.iter().map(encode_key).collect::<Result<_, _>>()?,
+            next_threshold,
+            next_keys: next_keys.to_vec(),
+            anchors: Vec::new(),
+        };
+
+        event.digest = event.compute_digest()?;
+        Ok(event)
+    }
+
+    pub fn interaction(&self, anchors: Vec<Value>) -> Result<KeyEvent, Error> {
+        let state = self.state()?;
+        let mut event = KeyEvent {
+            event_type: KeyEventType::Interaction,
+            digest: String::new(),
+            prefix: state.prefix,
+            sequence: state.sequence + 1,
+            previous: Some(state.digest),
+            threshold: 0,
+            keys: Vec::new(),
+            next_threshold: 0,
+            next_keys: Vec::new(),
+            anchors,
+        };
+
+        event.digest = event.compute_digest()?;
+        Ok(event)
+    }
+}
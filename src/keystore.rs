@@ -0,0 +1,858 @@
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use chrono::{DateTime, Duration, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use zeroize::Zeroizing;
+
+use crate::{
+    algorithms::Algorithm,
+    clock,
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        kdf::PasswordKdf,
+    },
+    errors::Error,
+    jwk::Jwk,
+    jwt::check_key_algorithm,
+    log,
+    resolver::{
+        ASSERTION_METHOD, AUTHENTICATION, CAPABILITY_DELEGATION, CAPABILITY_INVOCATION,
+        KEY_AGREEMENT,
+    },
+};
+
+pub const DID_CONTEXT_V1: &str = "https://www.w3.org/ns/did/v1";
+pub const JWS_2020_CONTEXT: &str = "https://w3id.org/security/suites/jws-2020/v1";
+pub const JSON_WEB_KEY_2020: &str = "JsonWebKey2020";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct KeyValidity {
+    pub not_before: Option<DateTime<Utc>>,
+    pub not_after: Option<DateTime<Utc>>,
+    pub revoked: bool,
+}
+
+impl KeyValidity {
+    pub fn is_valid_at(&self, at: DateTime<Utc>) -> bool {
+        !self.revoked
+            && self.not_before.is_none_or(|val| at >= val)
+            && self.not_after.is_none_or(|val| at < val)
+    }
+
+    pub fn check(&self, at: Option<DateTime<Utc>>) -> Result<(), Error> {
+        if self.revoked {
+            return Err(Error::KEYSTORE_KEY_REVOKED);
+        }
+
+        match at {
+            Some(val) if !self.is_valid_at(val) => Err(Error::KEYSTORE_KEY_OUTSIDE_VALIDITY),
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub enum KeyPurpose {
+    Authentication,
+    AssertionMethod,
+    KeyAgreement,
+    CapabilityInvocation,
+    CapabilityDelegation,
+}
+
+const KEY_PURPOSES: [KeyPurpose; 5] = [
+    KeyPurpose::Authentication,
+    KeyPurpose::AssertionMethod,
+    KeyPurpose::KeyAgreement,
+    KeyPurpose::CapabilityInvocation,
+    KeyPurpose::CapabilityDelegation,
+];
+
+impl KeyPurpose {
+    pub fn relationship(&self) -> &'static str {
+        match self {
+            KeyPurpose::Authentication => AUTHENTICATION,
+            KeyPurpose::AssertionMethod => ASSERTION_METHOD,
+            KeyPurpose::KeyAgreement => KEY_AGREEMENT,
+            KeyPurpose::CapabilityInvocation => CAPABILITY_INVOCATION,
+            KeyPurpose::CapabilityDelegation => CAPABILITY_DELEGATION,
+        }
+    }
+
+    pub fn from_relationship(relationship: &str) -> Option<Self> {
+        KEY_PURPOSES
+            .into_iter()
+            .find(|purpose| purpose.relationship() == relationship)
+    }
+
+    fn bit(&self) -> u8 {
+        1 << *self as u8
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct KeyPurposes(u8);
+
+impl Default for KeyPurposes {
+    fn default() -> Self {
+        Self::all()
+    }
+}
+
+impl KeyPurposes {
+    pub fn new(allowed: &[KeyPurpose]) -> Self {
+        KeyPurposes(allowed.iter().fold(0, |mask, purpose| mask | purpose.bit()))
+    }
+
+    pub fn all() -> Self {
+        Self::new(&KEY_PURPOSES)
+    }
+
+    pub fn allow(self, purpose: KeyPurpose) -> Self {
+        KeyPurposes(self.0 | purpose.bit())
+    }
+
+    pub fn deny(self, purpose: KeyPurpose) -> Self {
+        KeyPurposes(self.0 & !purpose.bit())
+    }
+
+    pub fn is_allowed(&self, purpose: KeyPurpose) -> bool {
+        self.0 & purpose.bit() != 0
+    }
+
+    pub fn check(&self, purpose: KeyPurpose) -> Result<(), Error> {
+        match self.is_allowed(purpose) {
+            true => Ok(()),
+            false => Err(Error::KEY_PURPOSE_NOT_ALLOWED),
+        }
+    }
+
+    pub fn to_vec(&self) -> Vec<KeyPurpose> {
+        KEY_PURPOSES
+            .into_iter()
+            .filter(|purpose| self.is_allowed(*purpose))
+            .collect()
+    }
+}
+
+pub trait KeyStore<K = AnySigningKey> {
+    fn add(&self, kid: &str, key: K) -> Result<(), Error>;
+
+    fn get(&self, kid: &str) -> Result<Arc<K>, Error>;
+
+    fn delete(&self, kid: &str) -> Result<(), Error>;
+
+    fn list(&self) -> Result<Vec<String>, Error>;
+
+    fn validity(&self, kid: &str) -> Result<KeyValidity, Error>;
+
+    fn set_validity(&self, kid: &str, validity: KeyValidity) -> Result<(), Error>;
+
+    fn purposes(&self, kid: &str) -> Result<KeyPurposes, Error> {
+        self.get(kid)?;
+        Ok(KeyPurposes::all())
+    }
+
+    fn set_purposes(&self, kid: &str, _purposes: KeyPurposes) -> Result<(), Error> {
+        Err(Error::KEYSTORE_ERROR.with_key(kid))
+    }
+
+    fn revoke(&self, kid: &str) -> Result<(), Error> {
+        let validity = self.validity(kid)?;
+        self.set_validity(
+            kid,
+            KeyValidity {
+                revoked: true,
+                ..validity
+            },
+        )
+    }
+}
+
+pub fn signing_key(
+    store: &impl KeyStore<AnySigningKey>,
+    kid: &str,
+    purpose: KeyPurpose,
+) -> Result<Arc<AnySigningKey>, Error> {
+    let key = store.get(kid)?;
+    store.validity(kid)?.check(Some(clock::now()))?;
+    match store.purposes(kid)?.check(purpose) {
+        Ok(_) => Ok(key),
+        Err(error) => Err(error.with_key(kid)),
+    }
+}
+
+type KeyEntries<K> = BTreeMap<String, (Arc<K>, KeyValidity, KeyPurposes)>;
+
+pub struct InMemoryKeyStore<K = AnySigningKey> {
+    keys: Mutex<KeyEntries<K>>,
+}
+
+impl<K> Default for InMemoryKeyStore<K> {
+    fn default() -> Self {
+        InMemoryKeyStore {
+            keys: Mutex::new(BTreeMap::new()),
+        }
+    }
+}
+
+impl<K> InMemoryKeyStore<K> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn keys(&self) -> Result<MutexGuard<'_, KeyEntries<K>>, Error> {
+        match self.keys.lock() {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::KEYSTORE_ERROR.with_source(error.to_string()))
+            }
+        }
+    }
+}
+
+impl<K> KeyStore<K> for InMemoryKeyStore<K> {
+    fn add(&self, kid: &str, key: K) -> Result<(), Error> {
+        let mut keys = self.keys()?;
+        if keys.contains_key(kid) {
+            return Err(Error::KEYSTORE_DUPLICATE_KEY_ID.with_key(kid));
+        }
+
+        keys.insert(
+            String::from(kid),
+            (Arc::new(key), KeyValidity::default(), KeyPurposes::all()),
+        );
+        Ok(())
+    }
+
+    fn get(&self, kid: &str) -> Result<Arc<K>, Error> {
+        match self.keys()?.get(kid) {
+            Some((key, _, _)) => Ok(key.clone()),
+            None => Err(Error::KEYSTORE_KEY_NOT_FOUND.with_key(kid)),
+        }
+    }
+
+    fn delete(&self, kid: &str) -> Result<(), Error> {
+        match self.keys()?.remove(kid) {
+            Some(_) => Ok(()),
+            None => Err(Error::KEYSTORE_KEY_NOT_FOUND.with_key(kid)),
+        }
+    }
+
+    fn list(&self) -> Result<Vec<String>, Error> {
+        Ok(self.keys()?.keys().cloned().collect())
+    }
+
+    fn validity(&self, kid: &str) -> Result<KeyValidity, Error> {
+        match self.keys()?.get(kid) {
+            Some((_, validity, _)) => Ok(*validity),
+            None => Err(Error::KEYSTORE_KEY_NOT_FOUND.with_key(kid)),
+        }
+    }
+
+    fn set_validity(&self, kid: &str, validity: KeyValidity) -> Result<(), Error> {
+        match self.keys()?.get_mut(kid) {
+            Some(val) => {
+                val.1 = validity;
+                Ok(())
+            }
+            None => Err(Error::KEYSTORE_KEY_NOT_FOUND.with_key(kid)),
+        }
+    }
+
+    fn purposes(&self, kid: &str) -> Result<KeyPurposes, Error> {
+        match self.keys()?.get(kid) {
+            Some((_, _, purposes)) => Ok(*purposes),
+            None => Err(Error::KEYSTORE_KEY_NOT_FOUND.with_key(kid)),
+        }
+    }
+
+    fn set_purposes(&self, kid: &str, purposes: KeyPurposes) -> Result<(), Error> {
+        match self.keys()?.get_mut(kid) {
+            Some(val) => {
+                val.2 = purposes;
+                Ok(())
+            }
+            None => Err(Error::KEYSTORE_KEY_NOT_FOUND.with_key(kid)),
+        }
+    }
+}
+
+pub(crate) const FILE_KEY_STORE_VERSION: u32 = 1;
+pub(crate) const FILE_KEY_STORE_ENC: &str = "A256GCM";
+pub(crate) const NONCE_LENGTH: usize = 12;
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct EncryptedKeyFile {
+    pub(crate) version: u32,
+    pub(crate) params: PasswordKdf,
+    pub(crate) enc: String,
+    pub(crate) nonce: String,
+    pub(crate) ciphertext: String,
+}
+
+#[derive(Serialize, Deserialize)]
+pub(crate) struct StoredKey {
+    pub(crate) alg: Algorithm,
+    key: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    not_before: Option<i64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    not_after: Option<i64>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    revoked: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    purposes: Option<Vec<KeyPurpose>>,
+}
+
+pub struct FileKeyStore {
+    path: PathBuf,
+    params: PasswordKdf,
+    ciphertext: Option<(Vec<u8>, Vec<u8>)>,
+    encryption_key: Option<Zeroizing<Vec<u8>>>,
+    keys: InMemoryKeyStore,
+}
+
+impl FileKeyStore {
+    pub fn create(path: impl AsRef<Path>, passphrase: &str) -> Result<Self, Error> {
+        Self::create_with_kdf(path, passphrase, PasswordKdf::argon2id_default())
+    }
+
+    pub fn create_with_kdf(
+        path: impl AsRef<Path>,
+        passphrase: &str,
+        params: PasswordKdf,
+    ) -> Result<Self, Error> {
+        Ok(FileKeyStore {
+            path: path.as_ref().to_path_buf(),
+            encryption_key: Some(derive_key(&params, passphrase)?),
+            params,
+            ciphertext: None,
+            keys: InMemoryKeyStore::new(),
+        })
+    }
+
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Error> {
+        let contents = match fs::read(path.as_ref()) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::KEYSTORE_ERROR.with_source(error));
+            }
+        };
+
+        let file: EncryptedKeyFile = match serde_json::from_slice(&contents) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::KEYSTORE_MALFORMED.with_source(error));
+            }
+        };
+
+        if file.version != FILE_KEY_STORE_VERSION || file.enc != FILE_KEY_STORE_ENC {
+            return Err(Error::KEYSTORE_MALFORMED);
+        }
+
+        let nonce = Jwk::decode_member(&Some(file.nonce))?;
+        if nonce.len() != NONCE_LENGTH {
+            return Err(Error::KEYSTORE_MALFORMED);
+        }
+
+        Ok(FileKeyStore {
+            path: path.as_ref().to_path_buf(),
+            params: match file.params.validate() {
+                Ok(_) => file.params,
+                Err(_) => return Err(Error::KEYSTORE_MALFORMED),
+            },
+            ciphertext: Some((nonce, Jwk::decode_member(&Some(file.ciphertext))?)),
+            encryption_key: None,
+            keys: InMemoryKeyStore::new(),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn is_locked(&self) -> bool {
+        self.encryption_key.is_none()
+    }
+
+    pub fn unlock(&mut self, passphrase: &str) -> Result<(), Error> {
+        let encryption_key = derive_key(&self.params, passphrase)?;
+        let keys = InMemoryKeyStore::new();
+
+        if let Some((nonce, ciphertext)) = &self.ciphertext {
+            let cipher = match Aes256Gcm::new_from_slice(encryption_key.as_slice()) {
+                Ok(val) => val,
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::KEYSTORE_ERROR.with_source(error));
+                }
+            };
+
+            let plaintext = match cipher.decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: &additional_data(&self.params)?,
+                },
+            ) {
+                Ok(val) => Zeroizing::new(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::KEYSTORE_WRONG_PASSPHRASE.with_source(error.to_string()));
+                }
+            };
+
+            let entries: BTreeMap<String, StoredKey> = match serde_json::from_slice(&plaintext) {
+                Ok(val) => val,
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::KEYSTORE_MALFORMED.with_source(error));
+                }
+            };
+
+            import_entries(entries, &keys)?;
+        }
+
+        self.encryption_key = Some(encryption_key);
+        self.keys = keys;
+        Ok(())
+    }
+
+    pub fn lock(&mut self) {
+        self.encryption_key = None;
+        self.keys = InMemoryKeyStore::new();
+    }
+
+    pub fn save(&mut self) -> Result<(), Error> {
+        let encryption_key = match &self.encryption_key {
+            Some(val) => val,
+            None => return Err(Error::KEYSTORE_LOCKED),
+        };
+
+        let entries = export_entries(&self.keys)?;
+        let plaintext = match serde_json::to_vec(&entries) {
+            Ok(val) => Zeroizing::new(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::KEYSTORE_ERROR.with_source(error));
+            }
+        };
+
+        let cipher = match Aes256Gcm::new_from_slice(encryption_key.as_slice()) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::KEYSTORE_ERROR.with_source(error));
+            }
+        };
+
+        let mut nonce = [0u8; NONCE_LENGTH];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let ciphertext = match cipher.encrypt(
+            Nonce::from_slice(&nonce),
+            Payload {
+                msg: &plaintext,
+                aad: &additional_data(&self.params)?,
+            },
+        ) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::KEYSTORE_ERROR.with_source(error.to_string()));
+            }
+        };
+
+        let file = EncryptedKeyFile {
+            version: FILE_KEY_STORE_VERSION,
+            params: self.params.clone(),
+            enc: String::from(FILE_KEY_STORE_ENC),
+            nonce: base64_url::encode(&nonce),
+            ciphertext: base64_url::encode(&ciphertext),
+        };
+
+        let contents = match serde_json::to_vec_pretty(&file) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::KEYSTORE_ERROR.with_source(error));
+            }
+        };
+
+        let temp_path = self.path.with_extension("tmp");
+        if let Err(error) =
+            fs::write(&temp_path, contents).and_then(|_| fs::rename(&temp_path, &self.path))
+        {
+            log::error(error.to_string().as_str());
+            return Err(Error::KEYSTORE_ERROR.with_source(error));
+        }
+
+        self.ciphertext = Some((nonce.to_vec(), ciphertext));
+        Ok(())
+    }
+
+    fn unlocked(&self) -> Result<&InMemoryKeyStore, Error> {
+        match self.encryption_key {
+            Some(_) => Ok(&self.keys),
+            None => Err(Error::KEYSTORE_LOCKED),
+        }
+    }
+}
+
+impl KeyStore for FileKeyStore {
+    fn add(&self, kid: &str, key: AnySigningKey) -> Result<(), Error> {
+        self.unlocked()?.add(kid, key)
+    }
+
+    fn get(&self, kid: &str) -> Result<Arc<AnySigningKey>, Error> {
+        self.unlocked()?.get(kid)
+    }
+
+    fn delete(&self, kid: &str) -> Result<(), Error> {
+        self.unlocked()?.delete(kid)
+    }
+
+    fn list(&self) -> Result<Vec<String>, Error> {
+        self.unlocked()?.list()
+    }
+
+    fn validity(&self, kid: &str) -> Result<KeyValidity, Error> {
+        self.unlocked()?.validity(kid)
+    }
+
+    fn set_validity(&self, kid: &str, validity: KeyValidity) -> Result<(), Error> {
+        self.unlocked()?.set_validity(kid, validity)
+    }
+
+    fn purposes(&self, kid: &str) -> Result<KeyPurposes, Error> {
+        self.unlocked()?.purposes(kid)
+    }
+
+    fn set_purposes(&self, kid: &str, purposes: KeyPurposes) -> Result<(), Error> {
+        self.unlocked()?.set_purposes(kid, purposes)
+    }
+}
+
+fn timestamp_to_datetime(timestamp: Option<i64>) -> Result<Option<DateTime<Utc>>, Error> {
+    match timestamp {
+        Some(val) => match DateTime::from_timestamp(val, 0) {
+            Some(val) => Ok(Some(val)),
+            None => Err(Error::FAILED_TO_CONVERT_TIMESTAMP_TO_DATETTIME),
+        },
+        None => Ok(None),
+    }
+}
+
+pub(crate) fn export_entries(
+    store: &impl KeyStore<AnySigningKey>,
+) -> Result<BTreeMap<String, StoredKey>, Error> {
+    let mut entries = BTreeMap::new();
+    for kid in store.list()? {
+        let key = store.get(&kid)?;
+        let validity = store.validity(&kid)?;
+        let purposes = store.purposes(&kid)?;
+        entries.insert(
+            kid,
+            StoredKey {
+                alg: key.algorithm(),
+                key: base64_url::encode(&Zeroizing::new(key.to_bytes()?)),
+                not_before: validity.not_before.map(|val| val.timestamp()),
+                not_after: validity.not_after.map(|val| val.timestamp()),
+                revoked: validity.revoked,
+                purposes: match purposes == KeyPurposes::all() {
+                    true => None,
+                    false => Some(purposes.to_vec()),
+                },
+            },
+        );
+    }
+
+    Ok(entries)
+}
+
+pub(crate) fn import_entries(
+    entries: BTreeMap<String, StoredKey>,
+    store: &impl KeyStore<AnySigningKey>,
+) -> Result<(), Error> {
+    for (kid, entry) in entries {
+        let bytes = Zeroizing::new(Jwk::decode_member(&Some(entry.key))?);
+        store.add(&kid, AnySigningKey::from_bytes(entry.alg, &bytes)?)?;
+        store.set_validity(
+            &kid,
+            KeyValidity {
+                not_before: timestamp_to_datetime(entry.not_before)?,
+                not_after: timestamp_to_datetime(entry.not_after)?,
+                revoked: entry.revoked,
+            },
+        )?;
+        if let Some(purposes) = entry.purposes {
+            store.set_purposes(&kid, KeyPurposes::new(&purposes))?;
+        }
+    }
+
+    Ok(())
+}
+
+fn derive_key(params: &PasswordKdf, passphrase: &str) -> Result<Zeroizing<Vec<u8>>, Error> {
+    params.derive_key(passphrase.as_bytes(), 32)
+}
+
+pub(crate) fn additional_data(params: &PasswordKdf) -> Result<Vec<u8>, Error> {
+    match serde_json::to_vec(params) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::KEYSTORE_ERROR.with_source(error))
+        }
+    }
+}
+
+pub trait VerifyingKeyResolver {
+    fn resolve(&self, kid: &str) -> Result<Arc<AnyVerifyingKey>, Error>;
+
+    fn resolve_validity(&self, _kid: &str) -> Result<KeyValidity, Error> {
+        Ok(KeyValidity::default())
+    }
+
+    fn resolve_at(
+        &self,
+        kid: &str,
+        at: Option<DateTime<Utc>>,
+    ) -> Result<Arc<AnyVerifyingKey>, Error> {
+        let key = self.resolve(kid)?;
+        self.resolve_validity(kid)?.check(at)?;
+        Ok(key)
+    }
+}
+
+impl<S: KeyStore<AnyVerifyingKey>> VerifyingKeyResolver for S {
+    fn resolve(&self, kid: &str) -> Result<Arc<AnyVerifyingKey>, Error> {
+        self.get(kid)
+    }
+
+    fn resolve_validity(&self, kid: &str) -> Result<KeyValidity, Error> {
+        self.validity(kid)
+    }
+}
+
+pub trait KeyLookup {
+    fn lookup(&self, kid: Option<&str>, alg: Algorithm) -> Result<Arc<AnyVerifyingKey>, Error>;
+}
+
+pub(crate) fn lookup_by_kid(
+    resolver: &impl VerifyingKeyResolver,
+    kid: Option<&str>,
+    alg: Algorithm,
+) -> Result<Arc<AnyVerifyingKey>, Error> {
+    let kid = match kid {
+        Some(val) if !val.is_empty() => val,
+        _ => return Err(Error::KEYSTORE_KEY_NOT_FOUND.with_algorithm(alg)),
+    };
+
+    let key = resolver.resolve_at(kid, None)?;
+    check_key_algorithm(&key, alg)?;
+    Ok(key)
+}
+
+impl<S: KeyStore<AnyVerifyingKey>> KeyLookup for S {
+    fn lookup(&self, kid: Option<&str>, alg: Algorithm) -> Result<Arc<AnyVerifyingKey>, Error> {
+        lookup_by_kid(self, kid, alg)
+    }
+}
+
+#[derive(Default)]
+struct KeyRingState {
+    current: Option<String>,
+    retired: BTreeMap<String, DateTime<Utc>>,
+}
+
+pub struct KeyRing<S: KeyStore = InMemoryKeyStore> {
+    did: String,
+    store: S,
+    state: Mutex<KeyRingState>,
+}
+
+impl<S: KeyStore> KeyRing<S> {
+    pub fn new(did: &str, store: S) -> Self {
+        KeyRing {
+            did: String::from(did),
+            store,
+            state: Mutex::new(KeyRingState::default()),
+        }
+    }
+
+    pub fn did(&self) -> &str {
+        &self.did
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    fn state(&self) -> Result<MutexGuard<'_, KeyRingState>, Error> {
+        match self.state.lock() {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::KEYSTORE_ERROR.with_source(error.to_string()))
+            }
+        }
+    }
+
+    pub fn current_kid(&self) -> Result<String, Error> {
+        match &self.state()?.current {
+            Some(val) => Ok(val.clone()),
+            None => Err(Error::KEYSTORE_KEY_NOT_FOUND),
+        }
+    }
+
+    pub fn retired(&self) -> Result<Vec<(String, DateTime<Utc>)>, Error> {
+        Ok(self
+            .state()?
+            .retired
+            .iter()
+            .map(|(kid, until)| (kid.clone(), *until))
+            .collect())
+    }
+
+    pub fn rotate(&self, alg: Algorithm, grace_period: Duration) -> Result<String, Error> {
+        let mut id = [0u8; 8];
+        rand::thread_rng().fill_bytes(&mut id);
+        let kid = format!("{}#key-{}", self.did, hex::encode(id));
+        self.rotate_to(&kid, AnySigningKey::generate(alg)?, grace_period)?;
+        Ok(kid)
+    }
+
+    pub fn rotate_to(
+        &self,
+        kid: &str,
+        key: AnySigningKey,
+        grace_period: Duration,
+    ) -> Result<(), Error> {
+        let mut state = self.state()?;
+        self.store.add(kid, key)?;
+        if let Some(previous) = state.current.replace(String::from(kid)) {
+            state.retired.insert(previous, clock::now() + grace_period);
+        }
+
+        Ok(())
+    }
+
+    pub fn prune(&self) -> Result<Vec<String>, Error> {
+        let mut state = self.state()?;
+        let now = clock::now();
+        let expired: Vec<String> = state
+            .retired
+            .iter()
+            .filter(|(_, until)| **until <= now)
+            .map(|(kid, _)| kid.clone())
+            .collect();
+
+        for kid in &expired {
+            self.store.delete(kid)?;
+            state.retired.remove(kid);
+        }
+
+        Ok(expired)
+    }
+
+    fn verifiable_kids(&self) -> Result<Vec<String>, Error> {
+        let state = self.state()?;
+        let now = clock::now();
+        Ok(state
+            .current
+            .iter()
+            .cloned()
+            .chain(
+                state
+                    .retired
+                    .iter()
+                    .filter(|(_, until)| **until > now)
+                    .map(|(kid, _)| kid.clone()),
+            )
+            .collect())
+    }
+
+    pub fn jwks(&self) -> Result<Value, Error> {
+        let mut keys = Vec::new();
+        for kid in self.verifiable_kids()? {
+            keys.push(Jwk {
+                kid: Some(kid.clone()),
+                ..self.store.get(&kid)?.verifying_key().to_jwk()?
+            });
+        }
+
+        Ok(json!({ "keys": keys }))
+    }
+
+    pub fn did_document(&self) -> Result<Value, Error> {
+        let kids = self.verifiable_kids()?;
+        let mut methods = Vec::new();
+        for kid in &kids {
+            methods.push(json!({
+                "id": kid,
+                "type": JSON_WEB_KEY_2020,
+                "controller": self.did,
+                "publicKeyJwk": self.store.get(kid)?.verifying_key().to_jwk()?,
+            }));
+        }
+
+        let mut authentication = Vec::new();
+        let mut assertion = Vec::new();
+        for kid in &kids {
+            let purposes = self.store.purposes(kid)?;
+            if authentication.is_empty() && purposes.is_allowed(KeyPurpose::Authentication) {
+                authentication.push(kid);
+            }
+            if purposes.is_allowed(KeyPurpose::AssertionMethod) {
+                assertion.push(kid);
+            }
+        }
+
+        Ok(json!({
+            "@context": [DID_CONTEXT_V1, JWS_2020_CONTEXT],
+            "id": self.did,
+            "verificationMethod": methods,
+            AUTHENTICATION: authentication,
+            ASSERTION_METHOD: assertion,
+        }))
+    }
+}
+
+impl<S: KeyStore> KeyLookup for KeyRing<S> {
+    fn lookup(&self, kid: Option<&str>, alg: Algorithm) -> Result<Arc<AnyVerifyingKey>, Error> {
+        lookup_by_kid(self, kid, alg)
+    }
+}
+
+impl<S: KeyStore> VerifyingKeyResolver for KeyRing<S> {
+    fn resolve(&self, kid: &str) -> Result<Arc<AnyVerifyingKey>, Error> {
+        if !self.verifiable_kids()?.iter().any(|val| val == kid) {
+            return match self.state()?.retired.contains_key(kid) {
+                true => Err(Error::KEYSTORE_KEY_RETIRED.with_key(kid)),
+                false => Err(Error::KEYSTORE_KEY_NOT_FOUND.with_key(kid)),
+            };
+        }
+
+        Ok(Arc::new(self.store.get(kid)?.verifying_key()))
+    }
+
+    fn resolve_validity(&self, kid: &str) -> Result<KeyValidity, Error> {
+        self.store.validity(kid)
+    }
+}
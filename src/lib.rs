@@ -1,7 +1,93 @@
 pub mod algorithms;
+pub mod audit;
+#[cfg(all(feature = "bench", not(target_arch = "wasm32")))]
+pub mod bench;
+pub mod blocklist;
+pub mod caip;
+pub mod clock;
+pub mod conditional;
+pub mod cose;
 pub mod crypto;
-mod errors;
+pub mod cwt;
+pub mod data_integrity;
+pub mod detached;
+pub mod did_configuration;
+pub mod didcomm;
+pub mod dpop;
+pub mod encoding;
+pub mod envelope;
+pub mod errors;
+#[cfg(feature = "es256k")]
+pub mod ethr;
+pub mod export;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod hash;
+#[cfg(feature = "http")]
+pub mod http;
+pub mod ion;
+pub mod jwe;
+pub mod jwk;
+pub mod jws;
 pub mod jwt;
+pub mod kel;
+pub mod keystore;
+pub mod limits;
 mod log;
+pub mod mdoc;
+pub mod metrics;
+#[cfg(feature = "uniffi")]
+pub mod mobile;
+pub mod multicodec;
+pub mod multihash;
+pub mod nonce;
+pub mod oid4vci;
+pub mod oid4vp;
+pub mod redact;
+pub mod replay;
+pub mod resolver;
+pub mod sd_jwt;
+pub mod sd_jwt_vc;
+pub mod self_test;
 pub mod signer;
+pub mod signing_context;
+#[cfg(feature = "es256k")]
+pub mod siwe;
+#[cfg(feature = "eddsa")]
+pub mod solana;
+pub mod status_list;
+#[cfg(feature = "testvectors")]
+pub mod testvectors;
+#[cfg(feature = "timestamp")]
+pub mod timestamp;
+mod trace;
+pub mod trust;
+pub mod vc;
+pub mod verification_method;
 pub mod verifier;
+#[cfg(feature = "wasm-bindings")]
+pub mod wasm;
+pub mod web;
+pub mod webauthn;
+#[cfg(feature = "eddsa")]
+pub mod webvh;
+pub mod zcap;
+
+#[cfg(all(
+    feature = "fips",
+    any(
+        feature = "blake3",
+        feature = "eddsa",
+        feature = "es256k",
+        feature = "mldsa",
+        feature = "nacl"
+    )
+))]
+compile_error!(
+    "the `fips` feature cannot be combined with `blake3`, `eddsa`, `es256k`, `mldsa` or `nacl`; build with `default-features = false`"
+);
+
+#[cfg(feature = "uniffi")]
+use mobile::*;
+#[cfg(feature = "uniffi")]
+uniffi::include_scaffolding!("did_crypto");
@@ -0,0 +1,208 @@
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
+use serde_json::Value;
+
+use crate::{errors::Error, log, resolver::DidResolver};
+
+pub const DEFAULT_MAX_HEADER_SIZE: usize = 8 * 1024;
+pub const DEFAULT_MAX_PAYLOAD_SIZE: usize = 256 * 1024;
+pub const DEFAULT_MAX_SIGNATURE_SIZE: usize = 8 * 1024;
+pub const DEFAULT_MAX_KEY_SIZE: usize = 16 * 1024;
+pub const DEFAULT_MAX_DOCUMENT_SIZE: usize = 256 * 1024;
+pub const DEFAULT_MAX_JSON_DEPTH: usize = 32;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct InputLimits {
+    pub max_header_size: usize,
+    pub max_payload_size: usize,
+    pub max_signature_size: usize,
+    pub max_key_size: usize,
+    pub max_document_size: usize,
+    pub max_json_depth: usize,
+    pub canonical_base64: bool,
+}
+
+impl Default for InputLimits {
+    fn default() -> Self {
+        InputLimits {
+            max_header_size: DEFAULT_MAX_HEADER_SIZE,
+            max_payload_size: DEFAULT_MAX_PAYLOAD_SIZE,
+            max_signature_size: DEFAULT_MAX_SIGNATURE_SIZE,
+            max_key_size: DEFAULT_MAX_KEY_SIZE,
+            max_document_size: DEFAULT_MAX_DOCUMENT_SIZE,
+            max_json_depth: DEFAULT_MAX_JSON_DEPTH,
+            canonical_base64: true,
+        }
+    }
+}
+
+impl InputLimits {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn max_header_size(mut self, size: usize) -> Self {
+        self.max_header_size = size;
+        self
+    }
+
+    pub fn max_payload_size(mut self, size: usize) -> Self {
+        self.max_payload_size = size;
+        self
+    }
+
+    pub fn max_signature_size(mut self, size: usize) -> Self {
+        self.max_signature_size = size;
+        self
+    }
+
+    pub fn max_key_size(mut self, size: usize) -> Self {
+        self.max_key_size = size;
+        self
+    }
+
+    pub fn max_document_size(mut self, size: usize) -> Self {
+        self.max_document_size = size;
+        self
+    }
+
+    pub fn max_json_depth(mut self, depth: usize) -> Self {
+        self.max_json_depth = depth;
+        self
+    }
+
+    pub fn canonical_base64(mut self, canonical: bool) -> Self {
+        self.canonical_base64 = canonical;
+        self
+    }
+
+    pub fn check_size(&self, size: usize, max_size: usize) -> Result<(), Error> {
+        match size > max_size {
+            true => Err(Error::INPUT_TOO_LARGE),
+            false => Ok(()),
+        }
+    }
+
+    pub fn check_json(&self, json: &[u8], max_size: usize) -> Result<(), Error> {
+        self.check_size(json.len(), max_size)?;
+        match json_depth(json) > self.max_json_depth {
+            true => Err(Error::INPUT_TOO_DEEP),
+            false => Ok(()),
+        }
+    }
+
+    pub fn check_value(&self, value: &Value, max_size: usize) -> Result<(), Error> {
+        match serde_json::to_vec(value) {
+            Ok(val) => self.check_json(&val, max_size),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENCODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn decode_base64url(&self, segment: &str, max_size: usize) -> Result<Vec<u8>, Error> {
+        self.check_size(segment.len(), max_size)?;
+        match URL_SAFE_NO_PAD.decode(segment) {
+            Ok(val) => Ok(val),
+            Err(error) if self.canonical_base64 => {
+                log::error(error.to_string().as_str());
+                Err(Error::INPUT_NOT_CANONICAL.with_source(error))
+            }
+            Err(_) => match base64_url::decode(segment.trim_end_matches('=')) {
+                Ok(val) => Ok(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    Err(Error::DECODING_ERROR.with_source(error))
+                }
+            },
+        }
+    }
+
+    fn decode_token_segment(&self, segment: &str, max_size: usize) -> Result<Vec<u8>, Error> {
+        match self.decode_base64url(segment, max_size) {
+            Err(error) if error == Error::INPUT_NOT_CANONICAL => match STANDARD.decode(segment) {
+                Ok(val) => Ok(val),
+                Err(_) => Err(error),
+            },
+            result => result,
+        }
+    }
+
+    pub fn check_token(&self, token: &str) -> Result<(), Error> {
+        let jws = token.split('~').next().unwrap_or(token);
+        let parts: Vec<&str> = jws.split('.').collect();
+        if parts.len() != 3 {
+            return Err(Error::JWT_MALFORMED);
+        }
+
+        let header = self.decode_token_segment(parts[0], self.max_header_size)?;
+        self.check_json(&header, self.max_header_size)?;
+        let payload = self.decode_token_segment(parts[1], self.max_payload_size)?;
+        self.check_json(&payload, self.max_payload_size)?;
+        self.decode_base64url(parts[2], self.max_signature_size)?;
+        Ok(())
+    }
+
+    pub fn check_key(&self, json: &str) -> Result<(), Error> {
+        self.check_json(json.as_bytes(), self.max_key_size)
+    }
+
+    pub fn check_document(&self, json: &[u8]) -> Result<(), Error> {
+        self.check_json(json, self.max_document_size)
+    }
+}
+
+fn json_depth(json: &[u8]) -> usize {
+    let mut depth = 0usize;
+    let mut max_depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    for byte in json {
+        match (in_string, escaped, byte) {
+            (true, true, _) => escaped = false,
+            (true, false, b'\\') => escaped = true,
+            (true, false, b'"') => in_string = false,
+            (true, false, _) => {}
+            (false, _, b'"') => in_string = true,
+            (false, _, b'{' | b'[') => {
+                depth += 1;
+                max_depth = max_depth.max(depth);
+            }
+            (false, _, b'}' | b']') => depth = depth.saturating_sub(1),
+            _ => {}
+        }
+    }
+
+    max_depth
+}
+
+pub struct LimitedResolver<R: DidResolver> {
+    resolver: R,
+    limits: InputLimits,
+}
+
+impl<R: DidResolver> LimitedResolver<R> {
+    pub fn new(resolver: R, limits: InputLimits) -> Self {
+        LimitedResolver { resolver, limits }
+    }
+
+    pub fn inner(&self) -> &R {
+        &self.resolver
+    }
+
+    pub fn limits(&self) -> &InputLimits {
+        &self.limits
+    }
+}
+
+impl<R: DidResolver> DidResolver for LimitedResolver<R> {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        let document = self.resolver.resolve(did)?;
+        self.limits
+            .check_value(&document, self.limits.max_document_size)?;
+        Ok(document)
+    }
+}
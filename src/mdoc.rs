@@ -0,0 +1,599 @@
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use coset::{cbor::value::Value, iana, AsCborValue, CoseKey, HeaderBuilder, KeyType, Label};
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+use sha2::{Digest, Sha256};
+
+use crate::{
+    algorithms::Algorithm,
+    clock,
+    cose::{algorithm_to_cose, CoseSign1},
+    crypto::{
+        any::AnyVerifyingKey, constant_time::constant_time_eq, ecdsa::_256::P256VerifyingKey,
+        SignFromKey, VerifyFromKey,
+    },
+    errors::Error,
+    jwk::Jwk,
+    log,
+};
+
+pub const MDL_DOC_TYPE: &str = "org.iso.18013.5.1.mDL";
+pub const MDL_NAMESPACE: &str = "org.iso.18013.5.1";
+
+const MSO_VERSION: &str = "1.0";
+const DIGEST_ALGORITHM: &str = "SHA-256";
+const DEVICE_AUTHENTICATION: &str = "DeviceAuthentication";
+const X5CHAIN_LABEL: i64 = 33;
+const ENCODED_CBOR_TAG: u64 = 24;
+const TDATE_TAG: u64 = 0;
+
+fn to_cbor(value: &Value) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::new();
+    match coset::cbor::ser::into_writer(value, &mut bytes) {
+        Ok(_) => Ok(bytes),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::ENCODING_ERROR.with_source(error))
+        }
+    }
+}
+
+fn from_cbor(bytes: &[u8]) -> Result<Value, Error> {
+    match coset::cbor::de::from_reader(bytes) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::MDOC_MALFORMED.with_source(error))
+        }
+    }
+}
+
+fn encoded_cbor(value: &Value) -> Result<Value, Error> {
+    Ok(Value::Tag(
+        ENCODED_CBOR_TAG,
+        Box::new(Value::Bytes(to_cbor(value)?)),
+    ))
+}
+
+fn encoded_cbor_bytes(value: &Value) -> Result<&[u8], Error> {
+    match value {
+        Value::Tag(ENCODED_CBOR_TAG, inner) => match inner.as_ref() {
+            Value::Bytes(val) => Ok(val),
+            _ => Err(Error::MDOC_MALFORMED),
+        },
+        _ => Err(Error::MDOC_MALFORMED),
+    }
+}
+
+fn map_get<'a>(map: &'a Value, key: &str) -> Result<&'a Value, Error> {
+    let entries = match map {
+        Value::Map(val) => val,
+        _ => return Err(Error::MDOC_MALFORMED),
+    };
+
+    match entries.iter().find(|(name, _)| name.as_text() == Some(key)) {
+        Some((_, val)) => Ok(val),
+        None => Err(Error::MDOC_MALFORMED),
+    }
+}
+
+fn map_text<'a>(map: &'a Value, key: &str) -> Result<&'a str, Error> {
+    match map_get(map, key)?.as_text() {
+        Some(val) => Ok(val),
+        None => Err(Error::MDOC_MALFORMED),
+    }
+}
+
+fn tdate(time: &DateTime<Utc>) -> Value {
+    Value::Tag(
+        TDATE_TAG,
+        Box::new(Value::Text(time.to_rfc3339_opts(SecondsFormat::Secs, true))),
+    )
+}
+
+fn parse_tdate(value: &Value) -> Result<DateTime<Utc>, Error> {
+    let text = match value {
+        Value::Tag(TDATE_TAG, inner) => inner.as_text(),
+        _ => None,
+    };
+
+    match text.map(DateTime::parse_from_rfc3339) {
+        Some(Ok(val)) => Ok(val.with_timezone(&Utc)),
+        _ => Err(Error::MDOC_MALFORMED),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IssuerSignedItem {
+    pub digest_id: u64,
+    pub random: Vec<u8>,
+    pub element_identifier: String,
+    pub element_value: Value,
+    bytes: Vec<u8>,
+}
+
+impl IssuerSignedItem {
+    pub fn new(
+        digest_id: u64,
+        element_identifier: &str,
+        element_value: Value,
+    ) -> Result<Self, Error> {
+        Self::new_with_rng(digest_id, element_identifier, element_value, &mut OsRng)
+    }
+
+    pub fn new_with_rng(
+        digest_id: u64,
+        element_identifier: &str,
+        element_value: Value,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<Self, Error> {
+        let mut random = vec![0u8; 16];
+        rng.fill_bytes(&mut random);
+
+        let bytes = to_cbor(&Value::Map(vec![
+            (Value::from("digestID"), Value::from(digest_id)),
+            (Value::from("random"), Value::Bytes(random.clone())),
+            (
+                Value::from("elementIdentifier"),
+                Value::from(element_identifier),
+            ),
+            (Value::from("elementValue"), element_value.clone()),
+        ]))?;
+
+        Ok(IssuerSignedItem {
+            digest_id,
+            random,
+            element_identifier: String::from(element_identifier),
+            element_value,
+            bytes,
+        })
+    }
+
+    pub fn from_value(value: &Value) -> Result<Self, Error> {
+        let bytes = encoded_cbor_bytes(value)?.to_vec();
+        let item = from_cbor(&bytes)?;
+
+        let digest_id = match map_get(&item, "digestID")?.as_integer() {
+            Some(val) => match u64::try_from(val) {
+                Ok(val) => val,
+                Err(_) => return Err(Error::MDOC_MALFORMED),
+            },
+            None => return Err(Error::MDOC_MALFORMED),
+        };
+
+        let random = match map_get(&item, "random")?.as_bytes() {
+            Some(val) => val.clone(),
+            None => return Err(Error::MDOC_MALFORMED),
+        };
+
+        Ok(IssuerSignedItem {
+            digest_id,
+            random,
+            element_identifier: String::from(map_text(&item, "elementIdentifier")?),
+            element_value: map_get(&item, "elementValue")?.clone(),
+            bytes,
+        })
+    }
+
+    pub fn to_value(&self) -> Value {
+        Value::Tag(ENCODED_CBOR_TAG, Box::new(Value::Bytes(self.bytes.clone())))
+    }
+
+    pub fn digest(&self) -> Result<Vec<u8>, Error> {
+        Ok(Sha256::digest(to_cbor(&self.to_value())?).to_vec())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ValidityInfo {
+    pub signed: DateTime<Utc>,
+    pub valid_from: DateTime<Utc>,
+    pub valid_until: DateTime<Utc>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MobileSecurityObject {
+    pub version: String,
+    pub digest_algorithm: String,
+    pub value_digests: BTreeMap<String, BTreeMap<u64, Vec<u8>>>,
+    pub device_key: CoseKey,
+    pub doc_type: String,
+    pub validity_info: ValidityInfo,
+}
+
+impl MobileSecurityObject {
+    pub fn to_value(&self) -> Result<Value, Error> {
+        let value_digests = self
+            .value_digests
+            .iter()
+            .map(|(name_space, digests)| {
+                (
+                    Value::from(name_space.as_str()),
+                    Value::Map(
+                        digests
+                            .iter()
+                            .map(|(id, digest)| (Value::from(*id), Value::Bytes(digest.clone())))
+                            .collect(),
+                    ),
+                )
+            })
+            .collect();
+
+        let device_key = match self.device_key.clone().to_cbor_value() {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ENCODING_ERROR.with_source(error.to_string()));
+            }
+        };
+
+        Ok(Value::Map(vec![
+            (Value::from("version"), Value::from(self.version.as_str())),
+            (
+                Value::from("digestAlgorithm"),
+                Value::from(self.digest_algorithm.as_str()),
+            ),
+            (Value::from("valueDigests"), Value::Map(value_digests)),
+            (
+                Value::from("deviceKeyInfo"),
+                Value::Map(vec![(Value::from("deviceKey"), device_key)]),
+            ),
+            (Value::from("docType"), Value::from(self.doc_type.as_str())),
+            (
+                Value::from("validityInfo"),
+                Value::Map(vec![
+                    (Value::from("signed"), tdate(&self.validity_info.signed)),
+                    (
+                        Value::from("validFrom"),
+                        tdate(&self.validity_info.valid_from),
+                    ),
+                    (
+                        Value::from("validUntil"),
+                        tdate(&self.validity_info.valid_until),
+                    ),
+                ]),
+            ),
+        ]))
+    }
+
+    pub fn from_value(value: &Value) -> Result<Self, Error> {
+        let mut value_digests = BTreeMap::new();
+        for (name_space, digests) in map_get(value, "valueDigests")?
+            .as_map()
+            .ok_or(Error::MDOC_MALFORMED)?
+        {
+            let mut name_space_digests = BTreeMap::new();
+            for (id, digest) in digests.as_map().ok_or(Error::MDOC_MALFORMED)? {
+                match (
+                    id.as_integer().and_then(|val| u64::try_from(val).ok()),
+                    digest.as_bytes(),
+                ) {
+                    (Some(id), Some(digest)) => name_space_digests.insert(id, digest.clone()),
+                    _ => return Err(Error::MDOC_MALFORMED),
+                };
+            }
+
+            match name_space.as_text() {
+                Some(val) => value_digests.insert(String::from(val), name_space_digests),
+                None => return Err(Error::MDOC_MALFORMED),
+            };
+        }
+
+        let device_key = match CoseKey::from_cbor_value(
+            map_get(map_get(value, "deviceKeyInfo")?, "deviceKey")?.clone(),
+        ) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::MDOC_MALFORMED.with_source(error.to_string()));
+            }
+        };
+
+        let validity_info = map_get(value, "validityInfo")?;
+        Ok(MobileSecurityObject {
+            version: String::from(map_text(value, "version")?),
+            digest_algorithm: String::from(map_text(value, "digestAlgorithm")?),
+            value_digests,
+            device_key,
+            doc_type: String::from(map_text(value, "docType")?),
+            validity_info: ValidityInfo {
+                signed: parse_tdate(map_get(validity_info, "signed")?)?,
+                valid_from: parse_tdate(map_get(validity_info, "validFrom")?)?,
+                valid_until: parse_tdate(map_get(validity_info, "validUntil")?)?,
+            },
+        })
+    }
+}
+
+fn cose_key_param(key: &CoseKey, label: i64) -> Result<&[u8], Error> {
+    match key
+        .params
+        .iter()
+        .find(|(name, _)| *name == Label::Int(label))
+    {
+        Some((_, Value::Bytes(val))) => Ok(val),
+        _ => Err(Error::MDOC_UNSUPPORTED_DEVICE_KEY),
+    }
+}
+
+fn verify_with_cose_key(
+    signature: &CoseSign1,
+    payload: &[u8],
+    device_key: &CoseKey,
+) -> Result<bool, Error> {
+    let curve = device_key
+        .params
+        .iter()
+        .find(|(name, _)| *name == Label::Int(iana::Ec2KeyParameter::Crv as i64))
+        .and_then(|(_, val)| val.as_integer())
+        .and_then(|val| i64::try_from(val).ok());
+
+    match (&device_key.kty, curve) {
+        (KeyType::Assigned(iana::KeyType::EC2), Some(crv))
+            if crv == iana::EllipticCurve::P_256 as i64 =>
+        {
+            let mut sec1_bytes = vec![0x04];
+            sec1_bytes
+                .extend_from_slice(cose_key_param(device_key, iana::Ec2KeyParameter::X as i64)?);
+            sec1_bytes
+                .extend_from_slice(cose_key_param(device_key, iana::Ec2KeyParameter::Y as i64)?);
+            signature.verify_detached(payload, &[], P256VerifyingKey::from_bytes(&sec1_bytes)?)
+        }
+        (KeyType::Assigned(iana::KeyType::OKP), Some(crv))
+            if crv == iana::EllipticCurve::Ed25519 as i64 =>
+        {
+            let jwk = Jwk {
+                kty: String::from("OKP"),
+                crv: Some(String::from("Ed25519")),
+                x: Some(base64_url::encode(cose_key_param(
+                    device_key,
+                    iana::OkpKeyParameter::X as i64,
+                )?)),
+                ..Default::default()
+            };
+            signature.verify_detached(payload, &[], AnyVerifyingKey::from_jwk(&jwk)?)
+        }
+        _ => Err(Error::MDOC_UNSUPPORTED_DEVICE_KEY),
+    }
+}
+
+pub fn device_authentication_bytes(
+    session_transcript: &Value,
+    doc_type: &str,
+    device_name_spaces: &Value,
+) -> Result<Vec<u8>, Error> {
+    to_cbor(&encoded_cbor(&Value::Array(vec![
+        Value::from(DEVICE_AUTHENTICATION),
+        session_transcript.clone(),
+        Value::from(doc_type),
+        encoded_cbor(device_name_spaces)?,
+    ]))?)
+}
+
+pub fn device_sign(
+    session_transcript: &Value,
+    doc_type: &str,
+    device_name_spaces: &Value,
+    alg: Algorithm,
+    device_key: impl SignFromKey,
+) -> Result<CoseSign1, Error> {
+    CoseSign1::sign_detached(
+        HeaderBuilder::new()
+            .algorithm(algorithm_to_cose(alg)?)
+            .build(),
+        Default::default(),
+        &device_authentication_bytes(session_transcript, doc_type, device_name_spaces)?,
+        &[],
+        device_key,
+    )
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IssuerSigned {
+    pub name_spaces: BTreeMap<String, Vec<IssuerSignedItem>>,
+    pub issuer_auth: CoseSign1,
+}
+
+impl IssuerSigned {
+    pub fn issue(
+        doc_type: &str,
+        name_spaces: &BTreeMap<String, Vec<(String, Value)>>,
+        device_key: CoseKey,
+        validity_info: ValidityInfo,
+        x5chain: &[u8],
+        alg: Algorithm,
+        issuer_key: impl SignFromKey,
+    ) -> Result<Self, Error> {
+        let mut digest_id = 0;
+        let mut items = BTreeMap::new();
+        let mut value_digests = BTreeMap::new();
+        for (name_space, elements) in name_spaces {
+            let mut name_space_items = Vec::new();
+            let mut name_space_digests = BTreeMap::new();
+            for (identifier, value) in elements {
+                let item = IssuerSignedItem::new(digest_id, identifier, value.clone())?;
+                name_space_digests.insert(digest_id, item.digest()?);
+                name_space_items.push(item);
+                digest_id += 1;
+            }
+
+            items.insert(name_space.clone(), name_space_items);
+            value_digests.insert(name_space.clone(), name_space_digests);
+        }
+
+        let mso = MobileSecurityObject {
+            version: String::from(MSO_VERSION),
+            digest_algorithm: String::from(DIGEST_ALGORITHM),
+            value_digests,
+            device_key,
+            doc_type: String::from(doc_type),
+            validity_info,
+        };
+
+        let mut unprotected = HeaderBuilder::new();
+        if !x5chain.is_empty() {
+            unprotected = unprotected.value(X5CHAIN_LABEL, Value::Bytes(x5chain.to_vec()));
+        }
+
+        let issuer_auth = CoseSign1::sign(
+            HeaderBuilder::new()
+                .algorithm(algorithm_to_cose(alg)?)
+                .build(),
+            unprotected.build(),
+            &to_cbor(&encoded_cbor(&mso.to_value()?)?)?,
+            &[],
+            issuer_key,
+        )?;
+
+        Ok(IssuerSigned {
+            name_spaces: items,
+            issuer_auth,
+        })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let value = from_cbor(bytes)?;
+
+        let mut name_spaces = BTreeMap::new();
+        for (name_space, items) in map_get(&value, "nameSpaces")?
+            .as_map()
+            .ok_or(Error::MDOC_MALFORMED)?
+        {
+            let items = match items.as_array() {
+                Some(val) => val
+                    .iter()
+                    .map(IssuerSignedItem::from_value)
+                    .collect::<Result<Vec<IssuerSignedItem>, Error>>()?,
+                None => return Err(Error::MDOC_MALFORMED),
+            };
+
+            match name_space.as_text() {
+                Some(val) => name_spaces.insert(String::from(val), items),
+                None => return Err(Error::MDOC_MALFORMED),
+            };
+        }
+
+        Ok(IssuerSigned {
+            name_spaces,
+            issuer_auth: CoseSign1::from_bytes(&to_cbor(map_get(&value, "issuerAuth")?)?)?,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        let name_spaces = self
+            .name_spaces
+            .iter()
+            .map(|(name_space, items)| {
+                (
+                    Value::from(name_space.as_str()),
+                    Value::Array(items.iter().map(|item| item.to_value()).collect()),
+                )
+            })
+            .collect();
+
+        let issuer_auth = match self.issuer_auth.0.clone().to_cbor_value() {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ENCODING_ERROR.with_source(error.to_string()));
+            }
+        };
+
+        to_cbor(&Value::Map(vec![
+            (Value::from("nameSpaces"), Value::Map(name_spaces)),
+            (Value::from("issuerAuth"), issuer_auth),
+        ]))
+    }
+
+    pub fn mso(&self) -> Result<MobileSecurityObject, Error> {
+        let payload = match self.issuer_auth.payload() {
+            Some(val) => from_cbor(val)?,
+            None => return Err(Error::COSE_MISSING_PAYLOAD),
+        };
+
+        MobileSecurityObject::from_value(&from_cbor(encoded_cbor_bytes(&payload)?)?)
+    }
+
+    pub fn x5chain(&self) -> Option<&[u8]> {
+        self.issuer_auth
+            .0
+            .unprotected
+            .rest
+            .iter()
+            .find(|(label, _)| *label == Label::Int(X5CHAIN_LABEL))
+            .and_then(|(_, value)| value.as_bytes())
+            .map(|val| val.as_slice())
+    }
+
+    pub fn element(&self, name_space: &str, element_identifier: &str) -> Option<&Value> {
+        self.name_spaces
+            .get(name_space)?
+            .iter()
+            .find(|item| item.element_identifier == element_identifier)
+            .map(|item| &item.element_value)
+    }
+
+    pub fn disclose(&self, elements: &[(&str, &str)]) -> Result<Self, Error> {
+        let mut name_spaces: BTreeMap<String, Vec<IssuerSignedItem>> = BTreeMap::new();
+        for (name_space, element_identifier) in elements {
+            let item = match self.name_spaces.get(*name_space).and_then(|items| {
+                items
+                    .iter()
+                    .find(|item| item.element_identifier == *element_identifier)
+            }) {
+                Some(val) => val.clone(),
+                None => return Err(Error::MDOC_ELEMENT_NOT_FOUND),
+            };
+
+            name_spaces
+                .entry(String::from(*name_space))
+                .or_default()
+                .push(item);
+        }
+
+        Ok(IssuerSigned {
+            name_spaces,
+            issuer_auth: self.issuer_auth.clone(),
+        })
+    }
+
+    pub fn verify(&self, issuer_key: impl VerifyFromKey) -> Result<bool, Error> {
+        if !self.issuer_auth.verify(&[], issuer_key)? {
+            return Ok(false);
+        }
+
+        let mso = self.mso()?;
+        if mso.digest_algorithm != DIGEST_ALGORITHM {
+            return Err(Error::UNKNOWN_ALGORITHM);
+        }
+
+        for (name_space, items) in &self.name_spaces {
+            for item in items {
+                match mso
+                    .value_digests
+                    .get(name_space)
+                    .and_then(|digests| digests.get(&item.digest_id))
+                {
+                    Some(digest) if constant_time_eq(digest, &item.digest()?) => {}
+                    _ => return Ok(false),
+                }
+            }
+        }
+
+        let now = clock::now();
+        Ok(mso.validity_info.valid_from <= now && now < mso.validity_info.valid_until)
+    }
+
+    pub fn verify_device_signature(
+        &self,
+        device_signature: &CoseSign1,
+        session_transcript: &Value,
+        device_name_spaces: &Value,
+    ) -> Result<bool, Error> {
+        let mso = self.mso()?;
+        let payload =
+            device_authentication_bytes(session_transcript, &mso.doc_type, device_name_spaces)?;
+        verify_with_cose_key(device_signature, &payload, &mso.device_key)
+    }
+}
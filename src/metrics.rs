@@ -0,0 +1,67 @@
+use std::{
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Serialize, Deserialize)]
+pub enum Operation {
+    Sign,
+    Verify,
+    Encrypt,
+    Decrypt,
+    Resolve,
+}
+
+impl Operation {
+    pub fn to_str(&self) -> &'static str {
+        match self {
+            Operation::Sign => "sign",
+            Operation::Verify => "verify",
+            Operation::Encrypt => "encrypt",
+            Operation::Decrypt => "decrypt",
+            Operation::Resolve => "resolve",
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct OperationMetrics<'a> {
+    pub operation: Operation,
+    pub algorithm: Option<&'a str>,
+    pub latency: Option<Duration>,
+    pub success: bool,
+}
+
+pub trait CryptoMetrics: Send + Sync {
+    fn record(&self, metrics: &OperationMetrics);
+}
+
+impl<F: Fn(&OperationMetrics) + Send + Sync> CryptoMetrics for F {
+    fn record(&self, metrics: &OperationMetrics) {
+        self(metrics)
+    }
+}
+
+static METRICS: RwLock<Option<Arc<dyn CryptoMetrics>>> = RwLock::new(None);
+
+pub fn set_metrics(metrics: Arc<dyn CryptoMetrics>) {
+    if let Ok(mut val) = METRICS.write() {
+        *val = Some(metrics);
+    }
+}
+
+pub fn clear_metrics() {
+    if let Ok(mut val) = METRICS.write() {
+        *val = None;
+    }
+}
+
+pub(crate) fn record(metrics: &OperationMetrics) {
+    if let Ok(val) = METRICS.read() {
+        if let Some(val) = val.as_ref() {
+            val.record(metrics);
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::str::FromStr;
+
+use serde_json::Value;
+
+use crate::{
+    algorithms::Algorithm,
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        VerifyFromKey,
+    },
+    errors::Error,
+    jwt::{Header, Payload, JWT},
+    log, resolver, signer,
+};
+
+#[derive(Debug, thiserror::Error)]
+pub enum FfiError {
+    #[error("{message}")]
+    Failed { message: String },
+}
+
+impl From<Error> for FfiError {
+    fn from(error: Error) -> Self {
+        FfiError::Failed {
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<uniffi::UnexpectedUniFFICallbackError> for FfiError {
+    fn from(error: uniffi::UnexpectedUniFFICallbackError) -> Self {
+        FfiError::Failed {
+            message: error.reason,
+        }
+    }
+}
+
+pub trait DidResolver: Send + Sync {
+    fn resolve(&self, did: String) -> Result<String, FfiError>;
+}
+
+struct ForeignResolver(Box<dyn DidResolver>);
+
+impl resolver::DidResolver for ForeignResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        let document = match self.0.resolve(String::from(did)) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::DID_RESOLUTION_ERROR.with_source(error));
+            }
+        };
+
+        match serde_json::from_str(&document) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::DID_RESOLUTION_ERROR.with_source(error))
+            }
+        }
+    }
+}
+
+pub fn generate_key(alg: String) -> Result<Vec<u8>, FfiError> {
+    let key = AnySigningKey::generate(Algorithm::from_str(&alg)?)?;
+    Ok(key.to_private_bytes()?.to_vec())
+}
+
+pub fn public_key(alg: String, private_key: Vec<u8>) -> Result<Vec<u8>, FfiError> {
+    let key = AnySigningKey::from_bytes(Algorithm::from_str(&alg)?, &private_key)?;
+    Ok(key.to_public_bytes()?)
+}
+
+pub fn sign(alg: String, private_key: Vec<u8>, message: Vec<u8>) -> Result<String, FfiError> {
+    let alg = Algorithm::from_str(&alg)?;
+    let key = AnySigningKey::from_bytes(alg, &private_key)?;
+    Ok(signer::sign(message, key, alg)?)
+}
+
+pub fn verify(
+    alg: String,
+    public_key: Vec<u8>,
+    message: Vec<u8>,
+    signature: String,
+) -> Result<bool, FfiError> {
+    let alg = Algorithm::from_str(&alg)?;
+    let key = AnyVerifyingKey::from_bytes(alg, &public_key)?;
+    Ok(key.verify_bytes(&message, &signature, alg)?)
+}
+
+pub fn sign_jwt(
+    alg: String,
+    private_key: Vec<u8>,
+    kid: String,
+    payload: String,
+) -> Result<String, FfiError> {
+    let alg = Algorithm::from_str(&alg)?;
+    let key = AnySigningKey::from_bytes(alg, &private_key)?;
+    let payload: Value = match serde_json::from_str(&payload) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::JWT_PAYLOAD_DESERIALIZING_ERROR
+                .with_source(error)
+                .into());
+        }
+    };
+
+    let mut jwt = JWT {
+        header: Header::new(kid, alg),
+        payload: Payload(payload),
+        signature: None,
+    };
+    jwt.sign(&key)?;
+    Ok(jwt.to_token()?)
+}
+
+pub fn verify_jwt(token: String, public_key: Vec<u8>) -> Result<bool, FfiError> {
+    let jwt = JWT::from_token(&token)?;
+    let key = AnyVerifyingKey::from_bytes(jwt.header.alg, &public_key)?;
+    Ok(jwt.validate(key)?)
+}
+
+pub fn resolve_keys(
+    resolver: Box<dyn DidResolver>,
+    did_url: String,
+    relationship: String,
+) -> Result<Vec<String>, FfiError> {
+    let keys = resolver::resolve_keys(&ForeignResolver(resolver), &did_url, &relationship)?;
+
+    let mut jwks = Vec::with_capacity(keys.len());
+    for key in keys {
+        jwks.push(key.to_json()?);
+    }
+    Ok(jwks)
+}
@@ -0,0 +1,60 @@
+use crate::errors::Error;
+
+pub const BLS12_381_G1_PUB: u64 = 0xea;
+pub const BLS12_381_G2_PUB: u64 = 0xeb;
+pub const X25519_PUB: u64 = 0xec;
+pub const ED25519_PUB: u64 = 0xed;
+pub const SECP256K1_PUB: u64 = 0xe7;
+pub const P256_PUB: u64 = 0x1200;
+pub const P384_PUB: u64 = 0x1201;
+pub const P521_PUB: u64 = 0x1202;
+pub const MLDSA_65_PUB: u64 = 0x1211;
+pub const ED25519_PRIV: u64 = 0x1300;
+pub const SECP256K1_PRIV: u64 = 0x1301;
+pub const P256_PRIV: u64 = 0x1306;
+pub const P384_PRIV: u64 = 0x1307;
+pub const P521_PRIV: u64 = 0x1308;
+pub const CIDV1: u64 = 0x01;
+pub const SHA2_256: u64 = 0x12;
+pub const SHA2_512: u64 = 0x13;
+pub const SHA3_512: u64 = 0x14;
+pub const SHA3_384: u64 = 0x15;
+pub const SHA3_256: u64 = 0x16;
+pub const SHAKE_128: u64 = 0x18;
+pub const SHAKE_256: u64 = 0x19;
+pub const BLAKE3: u64 = 0x1e;
+pub const SHA2_384: u64 = 0x20;
+pub const RAW: u64 = 0x55;
+pub const DAG_PB: u64 = 0x70;
+pub const DAG_CBOR: u64 = 0x71;
+pub const DAG_JSON: u64 = 0x0129;
+pub const JSON: u64 = 0x0200;
+
+pub fn encode(code: u64, bytes: &[u8]) -> Vec<u8> {
+    let mut encoded = Vec::with_capacity(bytes.len() + 10);
+    let mut value = code;
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            encoded.push(byte);
+            break;
+        }
+        encoded.push(byte | 0x80);
+    }
+
+    encoded.extend_from_slice(bytes);
+    encoded
+}
+
+pub fn decode(bytes: &[u8]) -> Result<(u64, &[u8]), Error> {
+    let mut code: u64 = 0;
+    for (index, byte) in bytes.iter().enumerate().take(9) {
+        code |= ((byte & 0x7f) as u64) << (7 * index);
+        if byte & 0x80 == 0 {
+            return Ok((code, &bytes[index + 1..]));
+        }
+    }
+
+    Err(Error::MULTICODEC_ERROR)
+}
@@ -0,0 +1,170 @@
+use std::{fmt, str::FromStr};
+
+use crate::{
+    crypto::constant_time::constant_time_eq,
+    encoding::{
+        base32_decode, base32_encode, base58_decode, MULTIBASE_BASE32, MULTIBASE_BASE58BTC,
+    },
+    errors::Error,
+    hash::HashAlgorithm,
+    multicodec,
+};
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Multihash {
+    pub algorithm: HashAlgorithm,
+    pub digest: Vec<u8>,
+}
+
+impl Multihash {
+    pub fn digest(algorithm: HashAlgorithm, data: impl AsRef<[u8]>) -> Self {
+        Multihash {
+            algorithm,
+            digest: algorithm.digest(data),
+        }
+    }
+
+    pub fn code(&self) -> u64 {
+        hash_code(self.algorithm)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        multicodec::encode(
+            self.code(),
+            &multicodec::encode(self.digest.len() as u64, &self.digest),
+        )
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let (multihash, rest) = Self::read(bytes)?;
+        match rest.is_empty() {
+            true => Ok(multihash),
+            false => Err(Error::MULTIHASH_MALFORMED),
+        }
+    }
+
+    fn read(bytes: &[u8]) -> Result<(Self, &[u8]), Error> {
+        let (code, rest) = match multicodec::decode(bytes) {
+            Ok(val) => val,
+            Err(error) => return Err(Error::MULTIHASH_MALFORMED.with_source(error)),
+        };
+        let algorithm = hash_algorithm(code)?;
+        let (length, rest) = match multicodec::decode(rest) {
+            Ok(val) => val,
+            Err(error) => return Err(Error::MULTIHASH_MALFORMED.with_source(error)),
+        };
+
+        let length = length as usize;
+        if length != algorithm.output_length() || rest.len() < length {
+            return Err(Error::MULTIHASH_MALFORMED);
+        }
+
+        let multihash = Multihash {
+            algorithm,
+            digest: rest[..length].to_vec(),
+        };
+        Ok((multihash, &rest[length..]))
+    }
+
+    pub fn verify(&self, data: impl AsRef<[u8]>) -> bool {
+        constant_time_eq(&self.algorithm.digest(data), &self.digest)
+    }
+}
+
+pub fn hash_code(algorithm: HashAlgorithm) -> u64 {
+    match algorithm {
+        HashAlgorithm::Sha256 => multicodec::SHA2_256,
+        HashAlgorithm::Sha384 => multicodec::SHA2_384,
+        HashAlgorithm::Sha512 => multicodec::SHA2_512,
+        HashAlgorithm::Sha3_256 => multicodec::SHA3_256,
+        HashAlgorithm::Sha3_384 => multicodec::SHA3_384,
+        HashAlgorithm::Sha3_512 => multicodec::SHA3_512,
+        HashAlgorithm::Shake128 => multicodec::SHAKE_128,
+        HashAlgorithm::Shake256 => multicodec::SHAKE_256,
+        #[cfg(feature = "blake3")]
+        HashAlgorithm::Blake3 => multicodec::BLAKE3,
+    }
+}
+
+pub fn hash_algorithm(code: u64) -> Result<HashAlgorithm, Error> {
+    match code {
+        multicodec::SHA2_256 => Ok(HashAlgorithm::Sha256),
+        multicodec::SHA2_384 => Ok(HashAlgorithm::Sha384),
+        multicodec::SHA2_512 => Ok(HashAlgorithm::Sha512),
+        multicodec::SHA3_256 => Ok(HashAlgorithm::Sha3_256),
+        multicodec::SHA3_384 => Ok(HashAlgorithm::Sha3_384),
+        multicodec::SHA3_512 => Ok(HashAlgorithm::Sha3_512),
+        multicodec::SHAKE_128 => Ok(HashAlgorithm::Shake128),
+        multicodec::SHAKE_256 => Ok(HashAlgorithm::Shake256),
+        #[cfg(feature = "blake3")]
+        multicodec::BLAKE3 => Ok(HashAlgorithm::Blake3),
+        _ => Err(Error::MULTIHASH_UNSUPPORTED),
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Cid {
+    pub codec: u64,
+    pub multihash: Multihash,
+}
+
+impl Cid {
+    pub fn new_v1(codec: u64, algorithm: HashAlgorithm, data: impl AsRef<[u8]>) -> Self {
+        Cid {
+            codec,
+            multihash: Multihash::digest(algorithm, data),
+        }
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        multicodec::encode(
+            multicodec::CIDV1,
+            &multicodec::encode(self.codec, &self.multihash.to_bytes()),
+        )
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let (version, rest) = match multicodec::decode(bytes) {
+            Ok(val) => val,
+            Err(error) => return Err(Error::CID_MALFORMED.with_source(error)),
+        };
+        if version != multicodec::CIDV1 {
+            return Err(Error::CID_MALFORMED);
+        }
+
+        let (codec, rest) = match multicodec::decode(rest) {
+            Ok(val) => val,
+            Err(error) => return Err(Error::CID_MALFORMED.with_source(error)),
+        };
+        let multihash = Multihash::from_bytes(rest)?;
+        Ok(Cid { codec, multihash })
+    }
+
+    pub fn verify(&self, data: impl AsRef<[u8]>) -> bool {
+        self.multihash.verify(data)
+    }
+}
+
+impl fmt::Display for Cid {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", MULTIBASE_BASE32, base32_encode(self.to_bytes()))
+    }
+}
+
+impl FromStr for Cid {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut chars = value.chars();
+        let bytes = match chars.next() {
+            Some(MULTIBASE_BASE32) => base32_decode(chars.as_str()),
+            Some(MULTIBASE_BASE58BTC) => base58_decode(chars.as_str()),
+            _ => return Err(Error::CID_MALFORMED),
+        };
+
+        match bytes {
+            Ok(val) => Self::from_bytes(&val),
+            Err(error) => Err(Error::CID_MALFORMED.with_source(error)),
+        }
+    }
+}
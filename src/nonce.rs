@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+
+use crate::{clock, crypto::entropy, errors::Error};
+
+pub const DEFAULT_NONCE_ENTROPY: usize = 16;
+pub const MIN_NONCE_ENTROPY: usize = 8;
+const EXPIRY_SEPARATOR: char = '.';
+
+fn random(entropy: usize, rng: &mut (impl CryptoRng + RngCore)) -> String {
+    let mut bytes = vec![0u8; entropy];
+    rng.fill_bytes(&mut bytes);
+    base64_url::encode(&bytes)
+}
+
+pub fn generate() -> String {
+    generate_with_rng(&mut OsRng)
+}
+
+pub fn generate_with_rng(rng: &mut (impl CryptoRng + RngCore)) -> String {
+    random(DEFAULT_NONCE_ENTROPY, rng)
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct NonceGenerator {
+    entropy: usize,
+    ttl: Option<Duration>,
+}
+
+impl Default for NonceGenerator {
+    fn default() -> Self {
+        NonceGenerator {
+            entropy: DEFAULT_NONCE_ENTROPY,
+            ttl: None,
+        }
+    }
+}
+
+impl NonceGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn entropy(mut self, bytes: usize) -> Self {
+        self.entropy = bytes;
+        self
+    }
+
+    pub fn expires_in(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    pub fn generate(&self) -> Result<String, Error> {
+        self.generate_with_rng(&mut entropy::rng()?)
+    }
+
+    pub fn generate_with_rng(&self, rng: &mut (impl CryptoRng + RngCore)) -> Result<String, Error> {
+        if self.entropy < MIN_NONCE_ENTROPY {
+            return Err(Error::NONCE_ENTROPY_TOO_LOW);
+        }
+
+        let value = random(self.entropy, rng);
+        let ttl = match self.ttl {
+            Some(val) => val,
+            None => return Ok(value),
+        };
+
+        let expires_at = TimeDelta::from_std(ttl)
+            .ok()
+            .and_then(|ttl| clock::now().checked_add_signed(ttl))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        Ok(format!(
+            "{}{}{}",
+            expires_at.timestamp(),
+            EXPIRY_SEPARATOR,
+            value
+        ))
+    }
+}
+
+pub fn expires_at(nonce: &str) -> Result<Option<DateTime<Utc>>, Error> {
+    let expiry = match nonce.split_once(EXPIRY_SEPARATOR) {
+        Some((expiry, _)) => expiry,
+        None => return Ok(None),
+    };
+
+    match expiry
+        .parse::<i64>()
+        .ok()
+        .and_then(|val| DateTime::from_timestamp(val, 0))
+    {
+        Some(val) => Ok(Some(val)),
+        None => Err(Error::NONCE_MALFORMED),
+    }
+}
+
+pub fn is_expired(nonce: &str) -> Result<bool, Error> {
+    match expires_at(nonce)? {
+        Some(val) => Ok(clock::now() >= val),
+        None => Ok(false),
+    }
+}
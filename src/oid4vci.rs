@@ -0,0 +1,297 @@
+use std::time::Duration;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    algorithms::{Algorithm, AlgorithmFamily},
+    clock,
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    errors::Error,
+    jwk::Jwk,
+    jwt::{check_key_algorithm, reject_alg_none},
+    log,
+    resolver::{resolve_keys, DidResolver, AUTHENTICATION},
+    signer::sign,
+    verifier::verify,
+};
+
+pub const OID4VCI_PROOF_TYP: &str = "openid4vci-proof+jwt";
+pub const DEFAULT_PROOF_MAX_AGE: Duration = Duration::from_secs(300);
+pub const DEFAULT_PROOF_LEEWAY: Duration = Duration::from_secs(5);
+
+#[derive(Serialize, Deserialize, Clone)]
+struct ProofHeader {
+    typ: String,
+    alg: Algorithm,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    kid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    jwk: Option<Jwk>,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ProofClaims {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+    pub aud: String,
+    pub iat: i64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+}
+
+impl ProofClaims {
+    pub fn new(credential_issuer: &str) -> Self {
+        ProofClaims {
+            iss: None,
+            aud: String::from(credential_issuer),
+            iat: clock::now().timestamp(),
+            nonce: None,
+        }
+    }
+
+    pub fn with_client_id(mut self, client_id: &str) -> Self {
+        self.iss = Some(String::from(client_id));
+        self
+    }
+
+    pub fn with_nonce(mut self, c_nonce: &str) -> Self {
+        self.nonce = Some(String::from(c_nonce));
+        self
+    }
+}
+
+fn encode_segment(value: &impl Serialize) -> Result<String, Error> {
+    match serde_json::to_string(value) {
+        Ok(val) => Ok(base64_url::encode(val.as_bytes())),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::ENCODING_ERROR.with_source(error))
+        }
+    }
+}
+
+fn decode_segment<T: DeserializeOwned>(segment: &str) -> Result<T, Error> {
+    let decoded = match base64_url::decode(segment) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::OID4VCI_PROOF_MALFORMED.with_source(error));
+        }
+    };
+
+    match serde_json::from_slice(&decoded) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::OID4VCI_PROOF_MALFORMED.with_source(error))
+        }
+    }
+}
+
+pub struct ProofValidation<'a> {
+    pub credential_issuer: &'a str,
+    pub nonce: Option<&'a str>,
+    pub client_id: Option<&'a str>,
+    pub max_age: Duration,
+    pub leeway: Duration,
+}
+
+impl<'a> ProofValidation<'a> {
+    pub fn new(credential_issuer: &'a str) -> Self {
+        ProofValidation {
+            credential_issuer,
+            nonce: None,
+            client_id: None,
+            max_age: DEFAULT_PROOF_MAX_AGE,
+            leeway: DEFAULT_PROOF_LEEWAY,
+        }
+    }
+
+    pub fn nonce(mut self, c_nonce: &'a str) -> Self {
+        self.nonce = Some(c_nonce);
+        self
+    }
+
+    pub fn client_id(mut self, client_id: &'a str) -> Self {
+        self.client_id = Some(client_id);
+        self
+    }
+
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = max_age;
+        self
+    }
+
+    pub fn leeway(mut self, leeway: Duration) -> Self {
+        self.leeway = leeway;
+        self
+    }
+}
+
+#[derive(Clone)]
+pub struct CredentialProof {
+    pub alg: Algorithm,
+    pub kid: Option<String>,
+    pub jwk: Option<Jwk>,
+    pub claims: ProofClaims,
+    token: String,
+}
+
+impl CredentialProof {
+    pub fn sign(claims: ProofClaims, key: &AnySigningKey) -> Result<Self, Error> {
+        let jwk = match key.verifying_key().to_jwk() {
+            Ok(val) => val,
+            Err(error) => {
+                return Err(Error::OID4VCI_PROOF_INVALID_KEY.with_source(error.to_string()))
+            }
+        };
+        Self::sign_with_header(claims, None, Some(jwk), key)
+    }
+
+    pub fn sign_with_kid(
+        claims: ProofClaims,
+        kid: &str,
+        key: &AnySigningKey,
+    ) -> Result<Self, Error> {
+        Self::sign_with_header(claims, Some(String::from(kid)), None, key)
+    }
+
+    fn sign_with_header(
+        claims: ProofClaims,
+        kid: Option<String>,
+        jwk: Option<Jwk>,
+        key: &AnySigningKey,
+    ) -> Result<Self, Error> {
+        let alg = key.algorithm();
+        if alg.get_family() == AlgorithmFamily::HMAC {
+            return Err(Error::OID4VCI_PROOF_INVALID_KEY.with_algorithm(alg));
+        }
+
+        let header = ProofHeader {
+            typ: String::from(OID4VCI_PROOF_TYP),
+            alg,
+            kid: kid.clone(),
+            jwk: jwk.clone(),
+        };
+        let content = format!("{}.{}", encode_segment(&header)?, encode_segment(&claims)?);
+        let signature = sign(&content, key, alg)?;
+
+        Ok(CredentialProof {
+            alg,
+            kid,
+            jwk,
+            claims,
+            token: format!("{}.{}", content, signature),
+        })
+    }
+
+    pub fn from_token(token: &str) -> Result<Self, Error> {
+        let parts: Vec<&str> = token.split('.').collect();
+        if parts.len() != 3 {
+            return Err(Error::OID4VCI_PROOF_MALFORMED);
+        }
+
+        reject_alg_none(&decode_segment::<Value>(parts[0])?)?;
+        let header: ProofHeader = decode_segment(parts[0])?;
+        if header.typ != OID4VCI_PROOF_TYP {
+            return Err(Error::OID4VCI_PROOF_INVALID_TYP);
+        }
+
+        if header.alg.get_family() == AlgorithmFamily::HMAC {
+            return Err(Error::OID4VCI_PROOF_INVALID_KEY.with_algorithm(header.alg));
+        }
+
+        match (&header.kid, &header.jwk) {
+            (Some(_), None) => {}
+            (None, Some(jwk)) if jwk.d.is_none() && jwk.private.is_none() => {}
+            _ => return Err(Error::OID4VCI_PROOF_INVALID_KEY),
+        }
+
+        Ok(CredentialProof {
+            alg: header.alg,
+            kid: header.kid,
+            jwk: header.jwk,
+            claims: decode_segment(parts[1])?,
+            token: String::from(token),
+        })
+    }
+
+    pub fn to_token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn verifying_key(&self) -> Result<AnyVerifyingKey, Error> {
+        match self.jwk.as_ref().map(AnyVerifyingKey::from_jwk) {
+            Some(Ok(val)) => Ok(val),
+            Some(Err(error)) => {
+                Err(Error::OID4VCI_PROOF_INVALID_KEY.with_source(error.to_string()))
+            }
+            None => Err(Error::OID4VCI_PROOF_INVALID_KEY),
+        }
+    }
+
+    pub fn verify(&self, validation: &ProofValidation) -> Result<bool, Error> {
+        self.verify_with_key(&self.verifying_key()?, validation)
+    }
+
+    pub fn verify_with_resolver(
+        &self,
+        validation: &ProofValidation,
+        resolver: &impl DidResolver,
+    ) -> Result<bool, Error> {
+        let kid = match &self.kid {
+            Some(val) => val,
+            None => return self.verify(validation),
+        };
+
+        for jwk in resolve_keys(resolver, kid, AUTHENTICATION)? {
+            if let Ok(key) = AnyVerifyingKey::from_jwk(&jwk) {
+                if key.algorithm() == self.alg && self.verify_with_key(&key, validation)? {
+                    return Ok(true);
+                }
+            }
+        }
+
+        Ok(false)
+    }
+
+    fn verify_with_key(
+        &self,
+        key: &AnyVerifyingKey,
+        validation: &ProofValidation,
+    ) -> Result<bool, Error> {
+        check_key_algorithm(key, self.alg)?;
+
+        let verified = match self.token.rsplit_once('.') {
+            Some((content, signature)) => verify(content, signature, key, self.alg)?,
+            None => return Err(Error::OID4VCI_PROOF_MALFORMED),
+        };
+        if !verified {
+            return Ok(false);
+        }
+
+        let now = clock::now().timestamp();
+        let max_age = validation.max_age.as_secs() as i64;
+        let leeway = validation.leeway.as_secs() as i64;
+        if self.claims.iat > now + leeway || self.claims.iat < now - max_age - leeway {
+            return Ok(false);
+        }
+
+        if self.claims.aud != validation.credential_issuer {
+            return Ok(false);
+        }
+
+        if validation
+            .nonce
+            .is_some_and(|nonce| self.claims.nonce.as_deref() != Some(nonce))
+        {
+            return Ok(false);
+        }
+
+        match validation.client_id {
+            Some(client_id) => Ok(self.claims.iss.as_deref() == Some(client_id)),
+            None => Ok(true),
+        }
+    }
+}
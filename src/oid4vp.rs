@@ -0,0 +1,414 @@
+use std::time::Duration;
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    algorithms::{Algorithm, AlgorithmFamily},
+    clock,
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    errors::Error,
+    jwt::{check_key_algorithm, reject_alg_none},
+    log,
+    resolver::{did_from_url, resolve_keys, DidResolver, AUTHENTICATION},
+    signer::sign,
+    trust::TrustPolicy,
+    vc::VcJwt,
+    verifier::verify,
+};
+
+pub const DEFAULT_RESPONSE_LIFETIME: Duration = Duration::from_secs(600);
+pub const DEFAULT_RESPONSE_LEEWAY: Duration = Duration::from_secs(5);
+
+#[derive(Serialize, Deserialize, Clone)]
+struct ResponseHeader {
+    typ: String,
+    alg: Algorithm,
+    kid: String,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct PresentationRequest {
+    pub client_id: String,
+    pub nonce: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presentation_definition: Option<Value>,
+}
+
+impl PresentationRequest {
+    pub fn new(client_id: &str, nonce: &str) -> Self {
+        PresentationRequest {
+            client_id: String::from(client_id),
+            nonce: String::from(nonce),
+            state: None,
+            presentation_definition: None,
+        }
+    }
+
+    pub fn with_state(mut self, state: &str) -> Self {
+        self.state = Some(String::from(state));
+        self
+    }
+
+    pub fn with_presentation_definition(mut self, definition: Value) -> Self {
+        self.presentation_definition = Some(definition);
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct VpClaims {
+    pub iss: String,
+    pub aud: String,
+    pub nonce: String,
+    pub iat: i64,
+    pub exp: i64,
+    pub vp: Value,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct AuthorizationResponse {
+    pub vp_token: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presentation_submission: Option<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+}
+
+impl AuthorizationResponse {
+    pub fn new(vp_token: Value, request: &PresentationRequest) -> Self {
+        AuthorizationResponse {
+            vp_token,
+            presentation_submission: None,
+            state: request.state.clone(),
+        }
+    }
+
+    pub fn with_presentation_submission(mut self, submission: Value) -> Self {
+        self.presentation_submission = Some(submission);
+        self
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct ResponseClaims {
+    pub iss: String,
+    pub aud: String,
+    pub iat: i64,
+    pub exp: i64,
+    #[serde(flatten)]
+    pub response: AuthorizationResponse,
+}
+
+fn encode_segment(value: &impl Serialize) -> Result<String, Error> {
+    match serde_json::to_string(value) {
+        Ok(val) => Ok(base64_url::encode(val.as_bytes())),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::ENCODING_ERROR.with_source(error))
+        }
+    }
+}
+
+fn decode_segment<T: DeserializeOwned>(segment: &str) -> Result<T, Error> {
+    let decoded = match base64_url::decode(segment) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::OID4VP_MALFORMED.with_source(error));
+        }
+    };
+
+    match serde_json::from_slice(&decoded) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::OID4VP_MALFORMED.with_source(error))
+        }
+    }
+}
+
+fn sign_token(
+    claims: &impl Serialize,
+    kid: &str,
+    key: &AnySigningKey,
+) -> Result<(Algorithm, String), Error> {
+    let alg = key.algorithm();
+    if alg.get_family() == AlgorithmFamily::HMAC {
+        return Err(Error::OID4VP_INVALID_KEY.with_algorithm(alg));
+    }
+
+    let header = ResponseHeader {
+        typ: String::from("JWT"),
+        alg,
+        kid: String::from(kid),
+    };
+    let content = format!("{}.{}", encode_segment(&header)?, encode_segment(claims)?);
+    let signature = sign(&content, key, alg)?;
+    Ok((alg, format!("{}.{}", content, signature)))
+}
+
+fn parse_token<T: DeserializeOwned>(token: &str) -> Result<(ResponseHeader, T), Error> {
+    let parts: Vec<&str> = token.split('.').collect();
+    if parts.len() != 3 {
+        return Err(Error::OID4VP_MALFORMED);
+    }
+
+    reject_alg_none(&decode_segment::<Value>(parts[0])?)?;
+    let header: ResponseHeader = decode_segment(parts[0])?;
+    if header.kid.is_empty() || header.alg.get_family() == AlgorithmFamily::HMAC {
+        return Err(Error::OID4VP_INVALID_KEY.with_algorithm(header.alg));
+    }
+
+    Ok((header, decode_segment(parts[1])?))
+}
+
+fn verify_token(
+    token: &str,
+    alg: Algorithm,
+    kid: &str,
+    resolver: &impl DidResolver,
+) -> Result<bool, Error> {
+    let (content, signature) = match token.rsplit_once('.') {
+        Some(val) => val,
+        None => return Err(Error::OID4VP_MALFORMED),
+    };
+
+    for jwk in resolve_keys(resolver, kid, AUTHENTICATION)? {
+        if let Ok(key) = AnyVerifyingKey::from_jwk(&jwk) {
+            if key.algorithm() == alg
+                && check_key_algorithm(&key, alg).is_ok()
+                && verify(content, signature, &key, alg)?
+            {
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+fn is_current(iat: i64, exp: i64) -> bool {
+    let now = clock::now().timestamp();
+    let leeway = DEFAULT_RESPONSE_LEEWAY.as_secs() as i64;
+    iat <= now + leeway && exp > now - leeway
+}
+
+fn lifetime() -> (i64, i64) {
+    let iat = clock::now().timestamp();
+    (iat, iat + DEFAULT_RESPONSE_LIFETIME.as_secs() as i64)
+}
+
+#[derive(Clone)]
+pub struct VpToken {
+    pub alg: Algorithm,
+    pub kid: String,
+    pub claims: VpClaims,
+    token: String,
+}
+
+impl VpToken {
+    pub fn sign(
+        vp: Value,
+        request: &PresentationRequest,
+        kid: &str,
+        key: &AnySigningKey,
+    ) -> Result<Self, Error> {
+        let (iat, exp) = lifetime();
+        let claims = VpClaims {
+            iss: String::from(did_from_url(kid)),
+            aud: request.client_id.clone(),
+            nonce: request.nonce.clone(),
+            iat,
+            exp,
+            vp,
+        };
+        let (alg, token) = sign_token(&claims, kid, key)?;
+
+        Ok(VpToken {
+            alg,
+            kid: String::from(kid),
+            claims,
+            token,
+        })
+    }
+
+    pub fn from_token(token: &str) -> Result<Self, Error> {
+        let (header, claims) = parse_token(token)?;
+        Ok(VpToken {
+            alg: header.alg,
+            kid: header.kid,
+            claims,
+            token: String::from(token),
+        })
+    }
+
+    pub fn to_token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn verify(
+        &self,
+        request: &PresentationRequest,
+        resolver: &impl DidResolver,
+    ) -> Result<bool, Error> {
+        if !verify_token(&self.token, self.alg, &self.kid, resolver)? {
+            return Ok(false);
+        }
+
+        Ok(self.claims.iss == did_from_url(&self.kid)
+            && self.claims.aud == request.client_id
+            && self.claims.nonce == request.nonce
+            && is_current(self.claims.iat, self.claims.exp))
+    }
+
+    pub fn credentials(&self) -> Result<Vec<VcJwt>, Error> {
+        match self.claims.vp.get("verifiableCredential") {
+            Some(Value::String(token)) => Ok(vec![VcJwt::from_token(token)?]),
+            Some(Value::Array(tokens)) => tokens
+                .iter()
+                .map(|token| match token.as_str() {
+                    Some(val) => VcJwt::from_token(val),
+                    None => Err(Error::VC_MALFORMED),
+                })
+                .collect(),
+            Some(_) => Err(Error::VC_MALFORMED),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub fn verify_with_policy(
+        &self,
+        request: &PresentationRequest,
+        resolver: &impl DidResolver,
+        policy: &TrustPolicy,
+    ) -> Result<bool, Error> {
+        if !self.verify(request, resolver)? {
+            return Ok(false);
+        }
+
+        for credential in self.credentials()? {
+            if !credential.verify_with_policy(resolver, policy)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
+
+#[derive(Clone)]
+pub struct ResponseJwt {
+    pub alg: Algorithm,
+    pub kid: String,
+    pub claims: ResponseClaims,
+    token: String,
+}
+
+impl ResponseJwt {
+    pub fn sign(
+        response: AuthorizationResponse,
+        request: &PresentationRequest,
+        kid: &str,
+        key: &AnySigningKey,
+    ) -> Result<Self, Error> {
+        let (iat, exp) = lifetime();
+        let claims = ResponseClaims {
+            iss: String::from(did_from_url(kid)),
+            aud: request.client_id.clone(),
+            iat,
+            exp,
+            response,
+        };
+        let (alg, token) = sign_token(&claims, kid, key)?;
+
+        Ok(ResponseJwt {
+            alg,
+            kid: String::from(kid),
+            claims,
+            token,
+        })
+    }
+
+    pub fn from_token(token: &str) -> Result<Self, Error> {
+        let (header, claims) = parse_token(token)?;
+        Ok(ResponseJwt {
+            alg: header.alg,
+            kid: header.kid,
+            claims,
+            token: String::from(token),
+        })
+    }
+
+    pub fn to_token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn vp_tokens(&self) -> Result<Vec<VpToken>, Error> {
+        match &self.claims.response.vp_token {
+            Value::String(token) => Ok(vec![VpToken::from_token(token)?]),
+            Value::Array(tokens) => tokens
+                .iter()
+                .map(|token| match token.as_str() {
+                    Some(val) => VpToken::from_token(val),
+                    None => Err(Error::OID4VP_MALFORMED),
+                })
+                .collect(),
+            _ => Err(Error::OID4VP_MALFORMED),
+        }
+    }
+
+    pub fn verify(
+        &self,
+        request: &PresentationRequest,
+        resolver: &impl DidResolver,
+    ) -> Result<bool, Error> {
+        if !verify_token(&self.token, self.alg, &self.kid, resolver)? {
+            return Ok(false);
+        }
+
+        Ok(self.claims.iss == did_from_url(&self.kid)
+            && self.claims.aud == request.client_id
+            && self.claims.response.state == request.state
+            && is_current(self.claims.iat, self.claims.exp))
+    }
+
+    pub fn verify_presentations(
+        &self,
+        request: &PresentationRequest,
+        resolver: &impl DidResolver,
+    ) -> Result<bool, Error> {
+        if !self.verify(request, resolver)? {
+            return Ok(false);
+        }
+
+        for vp_token in self.vp_tokens()? {
+            if !vp_token.verify(request, resolver)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    pub fn verify_presentations_with_policy(
+        &self,
+        request: &PresentationRequest,
+        resolver: &impl DidResolver,
+        policy: &TrustPolicy,
+    ) -> Result<bool, Error> {
+        if !self.verify(request, resolver)? {
+            return Ok(false);
+        }
+
+        for vp_token in self.vp_tokens()? {
+            if !vp_token.verify_with_policy(request, resolver, policy)? {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+}
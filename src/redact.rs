@@ -0,0 +1,245 @@
+use std::fmt;
+
+use hmac::{Hmac, Mac};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use zeroize::Zeroizing;
+
+pub const DEFAULT_SENSITIVE_CLAIMS: &[&str] = &[
+    "sub",
+    "name",
+    "given_name",
+    "family_name",
+    "middle_name",
+    "nickname",
+    "preferred_username",
+    "email",
+    "phone_number",
+    "address",
+    "birthdate",
+    "nonce",
+    "credentialSubject",
+    "holder",
+];
+
+const DIGEST_LENGTH: usize = 12;
+const SD_JWT_SEPARATOR: char = '~';
+
+#[derive(Clone)]
+pub struct Redactor {
+    claims: Vec<String>,
+    key: Option<Zeroizing<Vec<u8>>>,
+}
+
+impl Default for Redactor {
+    fn default() -> Self {
+        Redactor {
+            claims: DEFAULT_SENSITIVE_CLAIMS
+                .iter()
+                .map(|val| String::from(*val))
+                .collect(),
+            key: None,
+        }
+    }
+}
+
+impl fmt::Debug for Redactor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Redactor")
+            .field("claims", &self.claims)
+            .field("keyed", &self.key.is_some())
+            .finish()
+    }
+}
+
+impl Redactor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn redact_claim(mut self, name: &str) -> Self {
+        if !self.is_sensitive(name) {
+            self.claims.push(String::from(name));
+        }
+        self
+    }
+
+    pub fn preserve_claim(mut self, name: &str) -> Self {
+        self.claims.retain(|val| val != name);
+        self
+    }
+
+    pub fn hmac_key(mut self, key: &[u8]) -> Self {
+        self.key = Some(Zeroizing::new(key.to_vec()));
+        self
+    }
+
+    pub fn is_sensitive(&self, name: &str) -> bool {
+        self.claims.iter().any(|val| val == name)
+    }
+
+    pub fn digest(&self, data: impl AsRef<[u8]>) -> String {
+        match &self.key {
+            Some(key) => match Hmac::<Sha256>::new_from_slice(key) {
+                Ok(mut mac) => {
+                    mac.update(data.as_ref());
+                    let digest = mac.finalize().into_bytes();
+                    format!("hmac:{}", base64_url::encode(&digest[..DIGEST_LENGTH]))
+                }
+                Err(_) => String::from("hmac:"),
+            },
+            None => {
+                let digest = Sha256::digest(data.as_ref());
+                format!("sha256:{}", base64_url::encode(&digest[..DIGEST_LENGTH]))
+            }
+        }
+    }
+
+    fn hash_leaves(&self, value: &Value) -> Value {
+        match value {
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(name, value)| (name.clone(), self.hash_leaves(value)))
+                    .collect(),
+            ),
+            Value::Array(values) => {
+                Value::Array(values.iter().map(|val| self.hash_leaves(val)).collect())
+            }
+            Value::Null => Value::Null,
+            value => Value::from(self.digest(value.to_string())),
+        }
+    }
+
+    pub fn redact_value(&self, value: &Value) -> Value {
+        match value {
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(name, value)| {
+                        let value = match self.is_sensitive(name) {
+                            true => self.hash_leaves(value),
+                            false => self.redact_value(value),
+                        };
+                        (name.clone(), value)
+                    })
+                    .collect::<Map<String, Value>>(),
+            ),
+            Value::Array(values) => {
+                Value::Array(values.iter().map(|val| self.redact_value(val)).collect())
+            }
+            value => value.clone(),
+        }
+    }
+
+    fn redact_segment(&self, segment: &str) -> Value {
+        match base64_url::decode(segment)
+            .ok()
+            .and_then(|val| serde_json::from_slice::<Value>(&val).ok())
+        {
+            Some(val) if val.is_object() => self.redact_value(&val),
+            _ => Value::from(self.digest(segment)),
+        }
+    }
+
+    fn redact_disclosure(&self, disclosure: &str) -> Value {
+        let digest = base64_url::encode(Sha256::digest(disclosure.as_bytes()).as_slice());
+        let decoded = base64_url::decode(disclosure)
+            .ok()
+            .and_then(|val| serde_json::from_slice::<Vec<Value>>(&val).ok());
+
+        match decoded.as_deref() {
+            Some([_, Value::String(name), value]) => {
+                let value = match self.is_sensitive(name) {
+                    true => self.hash_leaves(value),
+                    false => self.redact_value(value),
+                };
+                json!({"digest": digest, "claim": name, "value": value})
+            }
+            Some([_, value]) => json!({"digest": digest, "value": self.hash_leaves(value)}),
+            _ => json!({"digest": digest}),
+        }
+    }
+
+    pub fn redact_token(&self, token: &str) -> Value {
+        let token = token.trim();
+        if token.starts_with('{') {
+            return match serde_json::from_str::<Value>(token) {
+                Ok(val) => self.redact_value(&val),
+                Err(_) => json!({"digest": self.digest(token)}),
+            };
+        }
+
+        if token.contains(SD_JWT_SEPARATOR) {
+            let mut parts: Vec<&str> = token.split(SD_JWT_SEPARATOR).collect();
+            let issuer_jwt = parts.remove(0);
+            let key_binding = match parts.last() {
+                Some(val) if !val.is_empty() => parts.pop(),
+                _ => None,
+            };
+            let disclosures: Vec<Value> = parts
+                .iter()
+                .filter(|val| !val.is_empty())
+                .map(|val| self.redact_disclosure(val))
+                .collect();
+
+            let mut redacted = json!({
+                "jwt": self.redact_token(issuer_jwt),
+                "disclosures": disclosures,
+            });
+            if let Some(key_binding) = key_binding {
+                redacted["kb_jwt"] = self.redact_token(key_binding);
+            }
+            return redacted;
+        }
+
+        let segments: Vec<&str> = token.split('.').collect();
+        match segments.as_slice() {
+            [header, payload, signature] => json!({
+                "header": self.redact_segment(header),
+                "payload": self.redact_segment(payload),
+                "signature": self.digest(signature),
+            }),
+            [header, encrypted_key, iv, ciphertext, tag] => json!({
+                "header": self.redact_segment(header),
+                "encrypted_key": self.digest(encrypted_key),
+                "iv": self.digest(iv),
+                "ciphertext_length": ciphertext.len(),
+                "tag": self.digest(tag),
+            }),
+            _ => json!({"digest": self.digest(token)}),
+        }
+    }
+
+    pub fn display<'a>(&'a self, token: &'a str) -> RedactedToken<'a> {
+        RedactedToken {
+            redactor: self,
+            token,
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    pub fn trace(&self, label: &str, token: &str) {
+        tracing::debug!(
+            target: "did_crypto",
+            token = %self.display(token),
+            "{}",
+            label
+        );
+    }
+}
+
+pub struct RedactedToken<'a> {
+    redactor: &'a Redactor,
+    token: &'a str,
+}
+
+impl fmt::Display for RedactedToken<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.redactor.redact_token(self.token))
+    }
+}
+
+impl fmt::Debug for RedactedToken<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    collections::HashMap,
+    sync::{Mutex, MutexGuard},
+    time::Duration,
+};
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde_json::Value;
+
+use crate::{
+    clock,
+    crypto::VerifyFromKey,
+    dpop::{DpopProof, DpopValidation},
+    errors::Error,
+    jwt::JWT,
+    sd_jwt::SdJwt,
+};
+
+pub const DEFAULT_REPLAY_WINDOW: Duration = Duration::from_secs(300);
+const MIN_PURGE_THRESHOLD: usize = 1024;
+
+pub trait NonceStore: Send + Sync {
+    fn insert(&self, value: &str, expires_at: DateTime<Utc>) -> Result<bool, Error>;
+}
+
+struct MemoryEntries {
+    values: HashMap<String, DateTime<Utc>>,
+    purge_at: usize,
+}
+
+pub struct MemoryNonceStore {
+    entries: Mutex<MemoryEntries>,
+}
+
+impl Default for MemoryNonceStore {
+    fn default() -> Self {
+        MemoryNonceStore {
+            entries: Mutex::new(MemoryEntries {
+                values: HashMap::new(),
+                purge_at: MIN_PURGE_THRESHOLD,
+            }),
+        }
+    }
+}
+
+impl MemoryNonceStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn entries(&self) -> MutexGuard<'_, MemoryEntries> {
+        match self.entries.lock() {
+            Ok(val) => val,
+            Err(error) => error.into_inner(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries().values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn purge_expired(&self) {
+        let now = clock::now();
+        self.entries()
+            .values
+            .retain(|_, expires_at| *expires_at > now);
+    }
+}
+
+impl NonceStore for MemoryNonceStore {
+    fn insert(&self, value: &str, expires_at: DateTime<Utc>) -> Result<bool, Error> {
+        let now = clock::now();
+        let mut entries = self.entries();
+        if entries.values.len() >= entries.purge_at {
+            entries.values.retain(|_, expires_at| *expires_at > now);
+            entries.purge_at = MIN_PURGE_THRESHOLD.max(entries.values.len() * 2);
+        }
+
+        match entries.values.get(value) {
+            Some(seen_until) if *seen_until > now => Ok(false),
+            _ => {
+                entries.values.insert(String::from(value), expires_at);
+                Ok(true)
+            }
+        }
+    }
+}
+
+pub struct ReplayGuard<S: NonceStore = MemoryNonceStore> {
+    store: S,
+    window: Duration,
+}
+
+impl Default for ReplayGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReplayGuard {
+    pub fn new() -> Self {
+        Self::with_store(MemoryNonceStore::new())
+    }
+}
+
+impl<S: NonceStore> ReplayGuard<S> {
+    pub fn with_store(store: S) -> Self {
+        ReplayGuard {
+            store,
+            window: DEFAULT_REPLAY_WINDOW,
+        }
+    }
+
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+
+    pub fn store(&self) -> &S {
+        &self.store
+    }
+
+    fn window_from(&self, start: DateTime<Utc>) -> DateTime<Utc> {
+        TimeDelta::from_std(self.window)
+            .ok()
+            .and_then(|window| start.checked_add_signed(window))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+
+    pub fn check_until(&self, value: &str, expires_at: DateTime<Utc>) -> Result<(), Error> {
+        match self.store.insert(value, expires_at)? {
+            true => Ok(()),
+            false => Err(Error::REPLAY_DETECTED),
+        }
+    }
+
+    pub fn check(&self, value: &str) -> Result<(), Error> {
+        self.check_until(value, self.window_from(clock::now()))
+    }
+
+    pub fn check_jwt(&self, jwt: &JWT) -> Result<(), Error> {
+        let jti = match jwt.payload.0.get("jti").and_then(Value::as_str) {
+            Some(val) => val,
+            None => return Err(Error::JWT_MISSING_JTI),
+        };
+
+        let expires_at = match jwt.payload.0.get("exp").and_then(Value::as_i64) {
+            Some(val) => match DateTime::from_timestamp(val, 0) {
+                Some(val) => val,
+                None => return Err(Error::FAILED_TO_CONVERT_TIMESTAMP_TO_DATETTIME),
+            },
+            None => self.window_from(clock::now()),
+        };
+
+        self.check_until(jti, expires_at)
+    }
+
+    pub fn validate_jwt(&self, jwt: &JWT, public_key: impl VerifyFromKey) -> Result<bool, Error> {
+        if !jwt.validate(public_key)? {
+            return Ok(false);
+        }
+
+        self.check_jwt(jwt)?;
+        Ok(true)
+    }
+
+    pub fn verify_dpop(
+        &self,
+        proof: &DpopProof,
+        validation: &DpopValidation,
+    ) -> Result<bool, Error> {
+        if !proof.verify(validation)? {
+            return Ok(false);
+        }
+
+        let issued_at = match DateTime::from_timestamp(proof.claims.iat, 0) {
+            Some(val) => val,
+            None => return Err(Error::FAILED_TO_CONVERT_TIMESTAMP_TO_DATETTIME),
+        };
+        let expires_at = TimeDelta::from_std(validation.max_age + validation.leeway)
+            .ok()
+            .and_then(|window| issued_at.checked_add_signed(window))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+
+        self.check_until(&proof.claims.jti, expires_at)?;
+        Ok(true)
+    }
+
+    pub fn verify_key_binding(
+        &self,
+        sd_jwt: &SdJwt,
+        holder_key: impl VerifyFromKey,
+        aud: &str,
+        nonce: &str,
+    ) -> Result<bool, Error> {
+        if !sd_jwt.verify_key_binding(holder_key, aud, nonce)? {
+            return Ok(false);
+        }
+
+        self.check(nonce)?;
+        Ok(true)
+    }
+}
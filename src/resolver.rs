@@ -0,0 +1,597 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{DateTime, TimeDelta, Utc};
+use serde_json::Value;
+
+use crate::{
+    algorithms::Algorithm,
+    clock,
+    crypto::{any::AnyVerifyingKey, policy::KeyPolicy},
+    errors::Error,
+    jwk::Jwk,
+    jwt::{check_key_algorithm, reject_alg_none},
+    keystore::{lookup_by_kid, KeyLookup, VerifyingKeyResolver},
+    limits::{InputLimits, LimitedResolver},
+    log,
+    metrics::Operation,
+    trace,
+    verification_method::{has_key_material, VerificationMethod},
+    verifier::{verify, VerificationOptions},
+};
+
+pub const AUTHENTICATION: &str = "authentication";
+pub const ASSERTION_METHOD: &str = "assertionMethod";
+pub const KEY_AGREEMENT: &str = "keyAgreement";
+pub const CAPABILITY_INVOCATION: &str = "capabilityInvocation";
+pub const CAPABILITY_DELEGATION: &str = "capabilityDelegation";
+pub const DEFAULT_RESOLVER_TTL: Duration = Duration::from_secs(300);
+pub const DEFAULT_NEGATIVE_TTL: Duration = Duration::from_secs(30);
+pub const DEFAULT_MAX_ENTRIES: usize = 1024;
+pub const DEFAULT_CLOCK_LEEWAY: Duration = Duration::from_secs(5);
+
+pub trait DidResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error>;
+}
+
+impl<R: DidResolver + ?Sized> DidResolver for &R {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        (**self).resolve(did)
+    }
+}
+
+pub fn did_from_url(did_url: &str) -> &str {
+    match did_url.find(['#', '?', '/']) {
+        Some(index) => &did_url[..index],
+        None => did_url,
+    }
+}
+
+pub(crate) fn absolute_id(did: &str, id: &str) -> String {
+    match id.starts_with('#') {
+        true => format!("{}{}", did, id),
+        false => String::from(id),
+    }
+}
+
+fn method_jwk(did: &str, method: &Value) -> Result<Jwk, Error> {
+    let id = match method.get("id").and_then(|val| val.as_str()) {
+        Some(val) => absolute_id(did, val),
+        None => return Err(Error::DID_RESOLUTION_ERROR),
+    };
+
+    let mut jwk: Jwk = match method.get("publicKeyJwk") {
+        Some(val) => match serde_json::from_value(val.clone()) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::JWK_PARSING_ERROR.with_source(error));
+            }
+        },
+        None if has_key_material(method) => VerificationMethod::from_value(method)?.key.to_jwk()?,
+        None => return Err(Error::JWK_MISSING_MEMBER),
+    };
+
+    jwk.kid = Some(id);
+    Ok(jwk)
+}
+
+pub fn verification_methods(document: &Value, relationship: &str) -> Result<Vec<Jwk>, Error> {
+    let did = match document.get("id").and_then(|val| val.as_str()) {
+        Some(val) => val,
+        None => return Err(Error::DID_RESOLUTION_ERROR),
+    };
+
+    let methods: &[Value] = match document.get("verificationMethod") {
+        Some(Value::Array(val)) => val,
+        _ => &[],
+    };
+
+    let references = match document.get(relationship) {
+        Some(Value::Array(val)) => val,
+        _ => return Ok(Vec::new()),
+    };
+
+    let mut keys = Vec::new();
+    for reference in references {
+        let method = match reference {
+            Value::String(id) => {
+                let id = absolute_id(did, id);
+                match methods.iter().find(|method| {
+                    method
+                        .get("id")
+                        .and_then(|val| val.as_str())
+                        .map(|val| absolute_id(did, val))
+                        == Some(id.clone())
+                }) {
+                    Some(val) => val,
+                    None => return Err(Error::DID_VERIFICATION_METHOD_NOT_FOUND),
+                }
+            }
+            val => val,
+        };
+
+        if !has_key_material(method) && method.get("blockchainAccountId").is_some() {
+            continue;
+        }
+
+        keys.push(method_jwk(did, method)?);
+    }
+
+    Ok(keys)
+}
+
+pub fn resolve_keys(
+    resolver: &impl DidResolver,
+    did_url: &str,
+    relationship: &str,
+) -> Result<Vec<Jwk>, Error> {
+    trace::traced(
+        Operation::Resolve,
+        None,
+        || Some(String::from(did_url)),
+        || resolve_relationship_keys(resolver, did_url, relationship),
+    )
+}
+
+pub fn resolve_verifying_keys(
+    resolver: &impl DidResolver,
+    did_url: &str,
+    relationship: &str,
+    policy: &KeyPolicy,
+) -> Result<Vec<AnyVerifyingKey>, Error> {
+    let keys: Vec<AnyVerifyingKey> = resolve_keys(resolver, did_url, relationship)?
+        .iter()
+        .filter_map(|jwk| AnyVerifyingKey::from_jwk(jwk).ok())
+        .collect();
+
+    match keys.is_empty() {
+        true => Err(Error::DID_VERIFICATION_METHOD_NOT_FOUND),
+        false => policy.check_all(keys),
+    }
+}
+
+pub struct DidKeyResolver<R: DidResolver> {
+    resolver: R,
+    relationship: &'static str,
+}
+
+impl<R: DidResolver> DidKeyResolver<R> {
+    pub fn new(resolver: R, relationship: &'static str) -> Self {
+        DidKeyResolver {
+            resolver,
+            relationship,
+        }
+    }
+
+    pub fn inner(&self) -> &R {
+        &self.resolver
+    }
+}
+
+impl<R: DidResolver> KeyLookup for DidKeyResolver<R> {
+    fn lookup(&self, kid: Option<&str>, alg: Algorithm) -> Result<Arc<AnyVerifyingKey>, Error> {
+        lookup_by_kid(self, kid, alg)
+    }
+}
+
+impl<R: DidResolver> VerifyingKeyResolver for DidKeyResolver<R> {
+    fn resolve(&self, kid: &str) -> Result<Arc<AnyVerifyingKey>, Error> {
+        let keys = resolve_verifying_keys(
+            &self.resolver,
+            kid,
+            self.relationship,
+            &KeyPolicy::permissive(),
+        )?;
+
+        match keys.into_iter().next() {
+            Some(val) => Ok(Arc::new(val)),
+            None => Err(Error::DID_VERIFICATION_METHOD_NOT_FOUND.with_key(kid)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DidVerificationOptions {
+    pub proof_purpose: &'static str,
+    pub key_policy: KeyPolicy,
+    pub verification: VerificationOptions,
+    pub leeway: Duration,
+    pub limits: Option<InputLimits>,
+}
+
+impl Default for DidVerificationOptions {
+    fn default() -> Self {
+        DidVerificationOptions {
+            proof_purpose: ASSERTION_METHOD,
+            key_policy: KeyPolicy::default(),
+            verification: VerificationOptions::default(),
+            leeway: DEFAULT_CLOCK_LEEWAY,
+            limits: None,
+        }
+    }
+}
+
+impl DidVerificationOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn proof_purpose(mut self, relationship: &'static str) -> Self {
+        self.proof_purpose = relationship;
+        self
+    }
+
+    pub fn key_policy(mut self, policy: KeyPolicy) -> Self {
+        self.key_policy = policy;
+        self
+    }
+
+    pub fn verification(mut self, options: VerificationOptions) -> Self {
+        self.verification = options;
+        self
+    }
+
+    pub fn leeway(mut self, leeway: Duration) -> Self {
+        self.leeway = leeway;
+        self
+    }
+
+    pub fn limits(mut self, limits: InputLimits) -> Self {
+        self.limits = Some(limits);
+        self
+    }
+}
+
+pub(crate) fn decode_token_segment(segment: &str, error_kind: Error) -> Result<Value, Error> {
+    let decoded = match base64_url::decode(segment) {
+        Ok(val) => val,
+        Err(_) => match STANDARD.decode(segment) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::DECODING_ERROR.with_source(error));
+            }
+        },
+    };
+
+    match serde_json::from_slice(&decoded) {
+        Ok(Value::Object(val)) => Ok(Value::Object(val)),
+        Ok(_) => Err(error_kind),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(error_kind.with_source(error))
+        }
+    }
+}
+
+pub(crate) fn is_within_validity(payload: &Value, leeway: Duration) -> Result<bool, Error> {
+    let now = clock::now().timestamp();
+    let leeway = leeway.as_secs() as i64;
+    for (claim, error_kind) in [
+        ("exp", Error::JWT_PAYLOAD_FIELD_EXP_IDENTIFICATION_ERROR),
+        ("nbf", Error::JWT_PAYLOAD_FIELD_NBF_IDENTIFICATION_ERROR),
+    ] {
+        let time = match payload.get(claim) {
+            Some(val) => match val.as_i64() {
+                Some(val) => val,
+                None => return Err(error_kind),
+            },
+            None => continue,
+        };
+
+        let expired = match claim {
+            "exp" => time <= now - leeway,
+            _ => time > now + leeway,
+        };
+        if expired {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn verify_token_with_did(
+    token: &str,
+    did_url: &str,
+    resolver: &impl DidResolver,
+    options: &DidVerificationOptions,
+) -> Result<bool, Error> {
+    if let Some(limits) = &options.limits {
+        limits.check_token(token)?;
+    }
+
+    let jws = token.split('~').next().unwrap_or(token);
+    let parts: Vec<&str> = jws.split('.').collect();
+    if parts.len() != 3 || parts[2].is_empty() {
+        return Err(Error::JWT_MALFORMED);
+    }
+
+    let header = decode_token_segment(parts[0], Error::JWT_HEADER_DESERIALIZING_ERROR)?;
+    reject_alg_none(&header)?;
+    let alg: Algorithm = match header.get("alg").cloned().map(serde_json::from_value) {
+        Some(Ok(val)) => val,
+        _ => return Err(Error::UNKNOWN_ALGORITHM),
+    };
+    options.verification.algorithm_policy.check(alg)?;
+
+    let did = did_from_url(did_url);
+    let kid = match header.get("kid").and_then(|val| val.as_str()) {
+        Some(val) => absolute_id(did, val),
+        None => String::from(did_url),
+    };
+    if did_from_url(&kid) != did || (did_url.contains('#') && kid != did_url) {
+        return Ok(false);
+    }
+
+    let payload = decode_token_segment(parts[1], Error::JWT_PAYLOAD_DESERIALIZING_ERROR)?;
+    let issuer = payload.get("iss").and_then(|val| val.as_str());
+    if issuer.is_some_and(|iss| iss.starts_with("did:") && did_from_url(iss) != did) {
+        return Ok(false);
+    }
+    if !is_within_validity(&payload, options.leeway)? {
+        return Ok(false);
+    }
+
+    let content = format!("{}.{}", parts[0], parts[1]);
+    for key in resolve_verifying_keys(resolver, &kid, options.proof_purpose, &options.key_policy)? {
+        if check_key_algorithm(&key, alg).is_ok() && verify(&content, parts[2], &key, alg)? {
+            return Ok(true);
+        }
+    }
+
+    Ok(false)
+}
+
+pub fn verify_with_did(
+    token: &str,
+    did_url: &str,
+    resolver: &impl DidResolver,
+    options: &DidVerificationOptions,
+) -> Result<bool, Error> {
+    let result = match options.limits {
+        Some(limits) => verify_token_with_did(
+            token,
+            did_url,
+            &LimitedResolver::new(resolver, limits),
+            options,
+        ),
+        None => verify_token_with_did(token, did_url, resolver, options),
+    };
+    options.verification.apply(result)
+}
+
+fn resolve_relationship_keys(
+    resolver: &impl DidResolver,
+    did_url: &str,
+    relationship: &str,
+) -> Result<Vec<Jwk>, Error> {
+    let document = resolver.resolve(did_from_url(did_url))?;
+    let keys = verification_methods(&document, relationship)?;
+
+    let keys: Vec<Jwk> = match did_url.contains('#') {
+        true => keys
+            .into_iter()
+            .filter(|key| key.kid.as_deref() == Some(did_url))
+            .collect(),
+        false => keys,
+    };
+
+    match keys.is_empty() {
+        true => Err(Error::DID_VERIFICATION_METHOD_NOT_FOUND),
+        false => Ok(keys),
+    }
+}
+
+enum CachedDocument {
+    Document(Value),
+    Failure(&'static str),
+}
+
+struct CacheEntry {
+    document: CachedDocument,
+    fetched_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+    stale_until: DateTime<Utc>,
+    refreshing: bool,
+}
+
+impl CacheEntry {
+    fn to_result(&self) -> Result<Value, Error> {
+        match &self.document {
+            CachedDocument::Document(val) => Ok(val.clone()),
+            CachedDocument::Failure(message) => Err(Error::Message(message)),
+        }
+    }
+}
+
+struct CacheState {
+    entries: Mutex<HashMap<String, CacheEntry>>,
+    ttl: TimeDelta,
+    negative_ttl: TimeDelta,
+    stale_ttl: TimeDelta,
+    max_entries: usize,
+}
+
+fn time_delta(duration: Duration) -> TimeDelta {
+    TimeDelta::from_std(duration).unwrap_or(TimeDelta::max_value())
+}
+
+impl CacheState {
+    fn entries(&self) -> MutexGuard<'_, HashMap<String, CacheEntry>> {
+        match self.entries.lock() {
+            Ok(val) => val,
+            Err(error) => error.into_inner(),
+        }
+    }
+
+    fn store(&self, did: &str, result: &Result<Value, Error>, now: DateTime<Utc>) {
+        let (document, ttl, stale_ttl) = match result {
+            Ok(val) => (
+                CachedDocument::Document(val.clone()),
+                self.ttl,
+                self.stale_ttl,
+            ),
+            Err(error) => (
+                CachedDocument::Failure(error.message()),
+                self.negative_ttl,
+                TimeDelta::zero(),
+            ),
+        };
+        if self.max_entries == 0 || ttl.is_zero() && stale_ttl.is_zero() {
+            return;
+        }
+
+        let mut entries = self.entries();
+        if !entries.contains_key(did) && entries.len() >= self.max_entries {
+            let oldest = entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.fetched_at)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = oldest {
+                entries.remove(&key);
+            }
+        }
+
+        let expires_at = now
+            .checked_add_signed(ttl)
+            .unwrap_or(DateTime::<Utc>::MAX_UTC);
+        entries.insert(
+            String::from(did),
+            CacheEntry {
+                document,
+                fetched_at: now,
+                expires_at,
+                stale_until: expires_at
+                    .checked_add_signed(stale_ttl)
+                    .unwrap_or(DateTime::<Utc>::MAX_UTC),
+                refreshing: false,
+            },
+        );
+    }
+
+    fn finish_refresh(&self, did: &str) {
+        if let Some(entry) = self.entries().get_mut(did) {
+            entry.refreshing = false;
+        }
+    }
+}
+
+pub struct CachingResolver<R: DidResolver + Send + Sync + 'static> {
+    resolver: Arc<R>,
+    state: Arc<CacheState>,
+}
+
+impl<R: DidResolver + Send + Sync + 'static> CachingResolver<R> {
+    pub fn new(resolver: R) -> Self {
+        CachingResolver {
+            resolver: Arc::new(resolver),
+            state: Arc::new(CacheState {
+                entries: Mutex::new(HashMap::new()),
+                ttl: time_delta(DEFAULT_RESOLVER_TTL),
+                negative_ttl: time_delta(DEFAULT_NEGATIVE_TTL),
+                stale_ttl: TimeDelta::zero(),
+                max_entries: DEFAULT_MAX_ENTRIES,
+            }),
+        }
+    }
+
+    fn configure(mut self, update: impl FnOnce(&mut CacheState)) -> Self {
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            update(state);
+        }
+        self
+    }
+
+    pub fn ttl(self, ttl: Duration) -> Self {
+        self.configure(|state| state.ttl = time_delta(ttl))
+    }
+
+    pub fn negative_ttl(self, ttl: Duration) -> Self {
+        self.configure(|state| state.negative_ttl = time_delta(ttl))
+    }
+
+    pub fn stale_while_revalidate(self, ttl: Duration) -> Self {
+        self.configure(|state| state.stale_ttl = time_delta(ttl))
+    }
+
+    pub fn max_entries(self, max_entries: usize) -> Self {
+        self.configure(|state| state.max_entries = max_entries)
+    }
+
+    pub fn inner(&self) -> &R {
+        &self.resolver
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.entries().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn insert(&self, did: &str, document: Value) {
+        self.state.store(did, &Ok(document), clock::now());
+    }
+
+    pub fn invalidate(&self, did: &str) {
+        self.state.entries().remove(did);
+    }
+
+    pub fn clear(&self) {
+        self.state.entries().clear();
+    }
+
+    fn revalidate(&self, did: &str) {
+        let resolver = self.resolver.clone();
+        let state = self.state.clone();
+        let did = String::from(did);
+        let refresh = move || match resolver.resolve(&did) {
+            Ok(val) => state.store(&did, &Ok(val), clock::now()),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                state.finish_refresh(&did);
+            }
+        };
+
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::spawn(refresh);
+        #[cfg(target_arch = "wasm32")]
+        refresh();
+    }
+}
+
+impl<R: DidResolver + Send + Sync + 'static> DidResolver for CachingResolver<R> {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        let now = clock::now();
+        let stale = match self.state.entries().get_mut(did) {
+            Some(entry) if now < entry.expires_at => return entry.to_result(),
+            Some(entry)
+                if now < entry.stale_until
+                    && matches!(entry.document, CachedDocument::Document(_)) =>
+            {
+                let revalidate = !entry.refreshing;
+                entry.refreshing = true;
+                Some((entry.to_result(), revalidate))
+            }
+            _ => None,
+        };
+
+        if let Some((result, revalidate)) = stale {
+            if revalidate {
+                self.revalidate(did);
+            }
+            return result;
+        }
+
+        let result = self.resolver.resolve(did);
+        self.state.store(did, &result, clock::now());
+        result
+    }
+}
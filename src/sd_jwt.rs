@@ -0,0 +1,491 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    algorithms::Algorithm,
+    clock,
+    crypto::{constant_time::constant_time_eq_str, SignFromKey, VerifyFromKey},
+    errors::Error,
+    hash::HashAlgorithm,
+    jwt::{check_key_algorithm, reject_alg_none, Header, Payload},
+    log,
+    signer::sign,
+    verifier::{verify, VerificationOptions},
+};
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+const SD_ALG: HashAlgorithm = HashAlgorithm::Sha256;
+const KB_JWT_TYP: &str = "kb+jwt";
+
+fn encode_segment(value: &impl Serialize) -> Result<String, Error> {
+    match serde_json::to_string(value) {
+        Ok(val) => Ok(base64_url::encode(val.as_bytes())),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::ENCODING_ERROR.with_source(error))
+        }
+    }
+}
+
+fn decode_segment(segment: &str) -> Result<Value, Error> {
+    let decoded = match base64_url::decode(segment) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::DECODING_ERROR.with_source(error));
+        }
+    };
+
+    match serde_json::from_slice(&decoded) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::SD_JWT_MALFORMED.with_source(error))
+        }
+    }
+}
+
+fn sd_digest(content: &str) -> String {
+    base64_url::encode(&SD_ALG.digest(content))
+}
+
+fn sign_compact(
+    header: &Header,
+    payload: &Payload,
+    key: impl SignFromKey,
+) -> Result<String, Error> {
+    let content = format!("{}.{}", encode_segment(header)?, encode_segment(payload)?);
+    let signature = sign(&content, key, header.alg)?;
+    Ok(format!("{}.{}", content, signature))
+}
+
+fn parse_compact(jwt: &str) -> Result<(Header, Payload), Error> {
+    let parts: Vec<&str> = jwt.split('.').collect();
+    if parts.len() != 3 {
+        return Err(Error::SD_JWT_MALFORMED);
+    }
+
+    let header = decode_segment(parts[0])?;
+    reject_alg_none(&header)?;
+
+    let header: Header = match serde_json::from_value(header) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::JWT_HEADER_DESERIALIZING_ERROR.with_source(error));
+        }
+    };
+
+    Ok((header, Payload(decode_segment(parts[1])?)))
+}
+
+fn verify_compact(jwt: &str, alg: Algorithm, key: impl VerifyFromKey) -> Result<bool, Error> {
+    match jwt.rsplit_once('.') {
+        Some((content, signature)) => {
+            check_key_algorithm(&key, alg)?;
+            verify(content, signature, key, alg)
+        }
+        None => Err(Error::SD_JWT_MALFORMED),
+    }
+}
+
+#[derive(Clone)]
+pub struct Disclosure {
+    salt: String,
+    name: Option<String>,
+    value: Value,
+    encoded: String,
+}
+
+impl Disclosure {
+    pub fn new(name: Option<&str>, value: Value) -> Result<Self, Error> {
+        Self::new_with_rng(name, value, &mut OsRng)
+    }
+
+    pub fn new_with_rng(
+        name: Option<&str>,
+        value: Value,
+        rng: &mut (impl CryptoRng + RngCore),
+    ) -> Result<Self, Error> {
+        let mut salt_bytes = [0u8; 16];
+        rng.fill_bytes(&mut salt_bytes);
+        let salt = base64_url::encode(&salt_bytes);
+
+        let content = match name {
+            Some(name) => Value::Array(vec![
+                Value::from(salt.as_str()),
+                Value::from(name),
+                value.clone(),
+            ]),
+            None => Value::Array(vec![Value::from(salt.as_str()), value.clone()]),
+        };
+
+        Ok(Disclosure {
+            salt,
+            name: name.map(String::from),
+            value,
+            encoded: encode_segment(&content)?,
+        })
+    }
+
+    pub fn from_encoded(encoded: &str) -> Result<Self, Error> {
+        let content = match decode_segment(encoded)? {
+            Value::Array(val) => val,
+            _ => return Err(Error::SD_JWT_INVALID_DISCLOSURE),
+        };
+
+        let (salt, name, value) = match content.as_slice() {
+            [Value::String(salt), Value::String(name), value] => {
+                (salt.clone(), Some(name.clone()), value.clone())
+            }
+            [Value::String(salt), value] => (salt.clone(), None, value.clone()),
+            _ => return Err(Error::SD_JWT_INVALID_DISCLOSURE),
+        };
+
+        if let Some(name) = &name {
+            if name == "_sd" || name == "..." {
+                return Err(Error::SD_JWT_INVALID_DISCLOSURE);
+            }
+        }
+
+        Ok(Disclosure {
+            salt,
+            name,
+            value,
+            encoded: String::from(encoded),
+        })
+    }
+
+    pub fn salt(&self) -> &str {
+        &self.salt
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn value(&self) -> &Value {
+        &self.value
+    }
+
+    pub fn encoded(&self) -> &str {
+        &self.encoded
+    }
+
+    pub fn digest(&self) -> String {
+        sd_digest(&self.encoded)
+    }
+}
+
+#[derive(Clone)]
+pub struct SdJwt {
+    pub header: Header,
+    pub payload: Payload,
+    pub disclosures: Vec<Disclosure>,
+    pub key_binding_jwt: Option<String>,
+    issuer_jwt: String,
+}
+
+impl SdJwt {
+    pub fn issue(
+        header: Header,
+        payload: Payload,
+        disclosable: &[&str],
+        issuer_key: impl SignFromKey,
+    ) -> Result<Self, Error> {
+        let mut claims = match payload.0 {
+            Value::Object(val) => val,
+            _ => return Err(Error::JWT_PAYLOAD_DESERIALIZING_ERROR),
+        };
+
+        let mut disclosures: Vec<Disclosure> = Vec::new();
+        let mut digests: Vec<Value> = Vec::new();
+        for name in disclosable {
+            if *name == "_sd" || *name == "_sd_alg" || *name == "..." {
+                return Err(Error::SD_JWT_RESERVED_CLAIM);
+            }
+
+            let value = match claims.remove(*name) {
+                Some(val) => val,
+                None => return Err(Error::SD_JWT_CLAIM_NOT_FOUND),
+            };
+
+            let disclosure = Disclosure::new(Some(name), value)?;
+            digests.push(Value::from(disclosure.digest()));
+            disclosures.push(disclosure);
+        }
+
+        // The digest order must not leak the original claim order.
+        digests.sort_by(|a, b| a.as_str().cmp(&b.as_str()));
+        claims.insert(String::from("_sd"), Value::Array(digests));
+        claims.insert(String::from("_sd_alg"), Value::from(SD_ALG.to_str()));
+
+        let payload = Payload(Value::Object(claims));
+        let issuer_jwt = sign_compact(&header, &payload, issuer_key)?;
+
+        Ok(SdJwt {
+            header,
+            payload,
+            disclosures,
+            key_binding_jwt: None,
+            issuer_jwt,
+        })
+    }
+
+    pub fn from_token(token: &str) -> Result<Self, Error> {
+        let mut parts: Vec<&str> = token.split('~').collect();
+        if parts.len() < 2 {
+            return Err(Error::SD_JWT_MALFORMED);
+        }
+
+        let key_binding_jwt = match parts.pop() {
+            Some("") | None => None,
+            Some(val) => Some(String::from(val)),
+        };
+
+        let issuer_jwt = String::from(parts[0]);
+        let (header, payload) = parse_compact(&issuer_jwt)?;
+
+        let mut disclosures: Vec<Disclosure> = Vec::new();
+        for encoded in &parts[1..] {
+            disclosures.push(Disclosure::from_encoded(encoded)?);
+        }
+
+        Ok(SdJwt {
+            header,
+            payload,
+            disclosures,
+            key_binding_jwt,
+            issuer_jwt,
+        })
+    }
+
+    pub fn to_token(&self) -> String {
+        match &self.key_binding_jwt {
+            Some(val) => format!("{}{}", self.presentation_content(), val),
+            None => self.presentation_content(),
+        }
+    }
+
+    fn presentation_content(&self) -> String {
+        let mut content = format!("{}~", self.issuer_jwt);
+        for disclosure in &self.disclosures {
+            content.push_str(disclosure.encoded());
+            content.push('~');
+        }
+
+        content
+    }
+
+    pub fn present(&self, claims: &[&str]) -> Self {
+        let disclosures = self
+            .disclosures
+            .iter()
+            .filter(|disclosure| match disclosure.name() {
+                Some(name) => claims.contains(&name),
+                None => false,
+            })
+            .cloned()
+            .collect();
+
+        SdJwt {
+            header: self.header.clone(),
+            payload: self.payload.clone(),
+            disclosures,
+            key_binding_jwt: None,
+            issuer_jwt: self.issuer_jwt.clone(),
+        }
+    }
+
+    pub fn bind_key(
+        &mut self,
+        alg: Algorithm,
+        aud: &str,
+        nonce: &str,
+        holder_key: impl SignFromKey,
+    ) -> Result<(), Error> {
+        let header = Header {
+            typ: String::from(KB_JWT_TYP),
+            alg,
+            kid: String::new(),
+            x5c: Vec::new(),
+        };
+
+        let mut claims = Map::new();
+        claims.insert(String::from("iat"), Value::from(clock::now().timestamp()));
+        claims.insert(String::from("aud"), Value::from(aud));
+        claims.insert(String::from("nonce"), Value::from(nonce));
+        claims.insert(
+            String::from("sd_hash"),
+            Value::from(sd_digest(&self.presentation_content())),
+        );
+
+        self.key_binding_jwt = Some(sign_compact(
+            &header,
+            &Payload(Value::Object(claims)),
+            holder_key,
+        )?);
+        Ok(())
+    }
+
+    pub fn verify(&self, issuer_key: impl VerifyFromKey) -> Result<bool, Error> {
+        if !verify_compact(&self.issuer_jwt, self.header.alg, issuer_key)? {
+            return Ok(false);
+        }
+
+        self.claims()?;
+        Ok(true)
+    }
+
+    pub fn verify_with_options(
+        &self,
+        issuer_key: impl VerifyFromKey,
+        options: &VerificationOptions,
+    ) -> Result<bool, Error> {
+        options.algorithm_policy.check(self.header.alg)?;
+        options.apply(self.verify(issuer_key))
+    }
+
+    pub fn verify_key_binding(
+        &self,
+        holder_key: impl VerifyFromKey,
+        aud: &str,
+        nonce: &str,
+    ) -> Result<bool, Error> {
+        let kb_jwt = match &self.key_binding_jwt {
+            Some(val) => val,
+            None => return Err(Error::SD_JWT_KEY_BINDING_MISSING),
+        };
+
+        let (header, payload) = parse_compact(kb_jwt)?;
+        if header.typ != KB_JWT_TYP {
+            return Err(Error::SD_JWT_MALFORMED);
+        }
+
+        if !verify_compact(kb_jwt, header.alg, holder_key)? {
+            return Ok(false);
+        }
+
+        let expected_hash = sd_digest(&self.presentation_content());
+        Ok(payload
+            .0
+            .get("sd_hash")
+            .and_then(Value::as_str)
+            .is_some_and(|sd_hash| constant_time_eq_str(sd_hash, &expected_hash))
+            && payload.0.get("aud") == Some(&Value::from(aud))
+            && payload.0.get("nonce") == Some(&Value::from(nonce)))
+    }
+
+    pub fn claims(&self) -> Result<Value, Error> {
+        if let Some(alg) = self.payload.0.get("_sd_alg") {
+            if alg.as_str() != Some(SD_ALG.to_str()) {
+                return Err(Error::SD_JWT_UNSUPPORTED_HASH_ALG);
+            }
+        }
+
+        let mut disclosures: HashMap<String, &Disclosure> = HashMap::new();
+        for disclosure in &self.disclosures {
+            if disclosures
+                .insert(disclosure.digest(), disclosure)
+                .is_some()
+            {
+                return Err(Error::SD_JWT_DUPLICATE_DIGEST);
+            }
+        }
+
+        let mut seen: HashSet<String> = HashSet::new();
+        let mut claims = self.payload.0.clone();
+        if let Value::Object(val) = &mut claims {
+            val.remove("_sd_alg");
+        }
+        Self::resolve_disclosures(&mut claims, &disclosures, &mut seen)?;
+
+        if !disclosures.keys().all(|digest| seen.contains(digest)) {
+            return Err(Error::SD_JWT_INVALID_DISCLOSURE);
+        }
+
+        Ok(claims)
+    }
+
+    fn resolve_disclosures(
+        value: &mut Value,
+        disclosures: &HashMap<String, &Disclosure>,
+        seen: &mut HashSet<String>,
+    ) -> Result<(), Error> {
+        match value {
+            Value::Object(object) => {
+                let digests = match object.remove("_sd") {
+                    Some(Value::Array(val)) => val,
+                    Some(_) => return Err(Error::SD_JWT_MALFORMED),
+                    None => Vec::new(),
+                };
+
+                for digest in digests {
+                    let digest = match digest {
+                        Value::String(val) => val,
+                        _ => return Err(Error::SD_JWT_MALFORMED),
+                    };
+
+                    if !seen.insert(digest.clone()) {
+                        return Err(Error::SD_JWT_DUPLICATE_DIGEST);
+                    }
+
+                    let disclosure = match disclosures.get(&digest) {
+                        Some(val) => val,
+                        None => continue,
+                    };
+
+                    let name = match disclosure.name() {
+                        Some(val) => val,
+                        None => return Err(Error::SD_JWT_INVALID_DISCLOSURE),
+                    };
+
+                    if object.contains_key(name) {
+                        return Err(Error::SD_JWT_INVALID_DISCLOSURE);
+                    }
+                    object.insert(String::from(name), disclosure.value().clone());
+                }
+
+                for (_, child) in object.iter_mut() {
+                    Self::resolve_disclosures(child, disclosures, seen)?;
+                }
+            }
+            Value::Array(array) => {
+                let mut resolved: Vec<Value> = Vec::new();
+                for element in array.drain(..) {
+                    let digest = match &element {
+                        Value::Object(object) if object.len() == 1 => {
+                            object.get("...").and_then(|val| val.as_str())
+                        }
+                        _ => None,
+                    };
+
+                    match digest {
+                        Some(digest) => {
+                            if !seen.insert(String::from(digest)) {
+                                return Err(Error::SD_JWT_DUPLICATE_DIGEST);
+                            }
+
+                            if let Some(disclosure) = disclosures.get(digest) {
+                                if disclosure.name().is_some() {
+                                    return Err(Error::SD_JWT_INVALID_DISCLOSURE);
+                                }
+                                resolved.push(disclosure.value().clone());
+                            }
+                        }
+                        None => resolved.push(element),
+                    }
+                }
+
+                for child in resolved.iter_mut() {
+                    Self::resolve_disclosures(child, disclosures, seen)?;
+                }
+                *array = resolved;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,263 @@
+use crate::{
+    algorithms::Algorithm,
+    clock,
+    crypto::{any::AnySigningKey, SignFromKey, VerifyFromKey},
+    errors::Error,
+    jwt::{Confirmation, Header, Payload},
+    keystore::{signing_key, KeyPurpose, KeyStore, VerifyingKeyResolver},
+    log,
+    sd_jwt::SdJwt,
+    verifier::VerificationOptions,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const SD_JWT_VC_TYP: &str = "vc+sd-jwt";
+const DC_SD_JWT_TYP: &str = "dc+sd-jwt";
+const JWT_VC_ISSUER_WELL_KNOWN: &str = "/.well-known/jwt-vc-issuer";
+
+const NON_DISCLOSABLE_CLAIMS: [&str; 7] = ["iss", "nbf", "exp", "cnf", "vct", "status", "iat"];
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct StatusListReference {
+    pub idx: u64,
+    pub uri: String,
+}
+
+pub enum IssuerKeyLocation {
+    DidUrl(String),
+    JwtVcIssuerMetadata {
+        metadata_url: String,
+        kid: Option<String>,
+    },
+}
+
+pub trait IssuerKeyResolver {
+    type Key: VerifyFromKey;
+
+    fn resolve(&self, location: &IssuerKeyLocation) -> Result<Self::Key, Error>;
+}
+
+#[derive(Clone)]
+pub struct SdJwtVc(pub SdJwt);
+
+impl SdJwtVc {
+    pub fn issue(
+        kid: String,
+        alg: Algorithm,
+        payload: Payload,
+        disclosable: &[&str],
+        issuer_key: impl SignFromKey,
+    ) -> Result<Self, Error> {
+        if payload.0.get("vct").and_then(|val| val.as_str()).is_none() {
+            return Err(Error::SD_JWT_VC_MISSING_VCT);
+        }
+
+        if payload.0.get("iss").and_then(|val| val.as_str()).is_none() {
+            return Err(Error::SD_JWT_VC_MISSING_ISSUER);
+        }
+
+        if disclosable
+            .iter()
+            .any(|name| NON_DISCLOSABLE_CLAIMS.contains(name))
+        {
+            return Err(Error::SD_JWT_RESERVED_CLAIM);
+        }
+
+        let header = Header {
+            typ: String::from(SD_JWT_VC_TYP),
+            alg,
+            kid,
+            x5c: Vec::new(),
+        };
+
+        Ok(SdJwtVc(SdJwt::issue(
+            header,
+            payload,
+            disclosable,
+            issuer_key,
+        )?))
+    }
+
+    pub fn issue_with_store(
+        kid: &str,
+        payload: Payload,
+        disclosable: &[&str],
+        store: &impl KeyStore<AnySigningKey>,
+    ) -> Result<Self, Error> {
+        let key = signing_key(store, kid, KeyPurpose::AssertionMethod)?;
+        Self::issue(
+            String::from(kid),
+            key.algorithm(),
+            payload,
+            disclosable,
+            key,
+        )
+    }
+
+    pub fn from_token(token: &str) -> Result<Self, Error> {
+        let sd_jwt = SdJwt::from_token(token)?;
+        if sd_jwt.header.typ != SD_JWT_VC_TYP && sd_jwt.header.typ != DC_SD_JWT_TYP {
+            return Err(Error::SD_JWT_VC_INVALID_TYP);
+        }
+
+        Ok(SdJwtVc(sd_jwt))
+    }
+
+    pub fn to_token(&self) -> String {
+        self.0.to_token()
+    }
+
+    pub fn present(&self, claims: &[&str]) -> Self {
+        SdJwtVc(self.0.present(claims))
+    }
+
+    pub fn vct(&self) -> Option<&str> {
+        self.0.payload.0.get("vct").and_then(|val| val.as_str())
+    }
+
+    pub fn issuer(&self) -> Option<&str> {
+        self.0.payload.0.get("iss").and_then(|val| val.as_str())
+    }
+
+    pub fn cnf(&self) -> Option<&Value> {
+        self.0.payload.0.get("cnf")
+    }
+
+    pub fn confirmation(&self) -> Result<Confirmation, Error> {
+        match self.0.payload.confirmation()? {
+            Some(val) => Ok(val),
+            None => Err(Error::SD_JWT_VC_MISSING_CNF),
+        }
+    }
+
+    pub fn status_list_reference(&self) -> Result<Option<StatusListReference>, Error> {
+        let status_list = match self.0.payload.0.get("status") {
+            Some(val) => match val.get("status_list") {
+                Some(val) => val.clone(),
+                None => return Ok(None),
+            },
+            None => return Ok(None),
+        };
+
+        match serde_json::from_value(status_list) {
+            Ok(val) => Ok(Some(val)),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::SD_JWT_VC_INVALID_STATUS.with_source(error))
+            }
+        }
+    }
+
+    pub fn issuer_key_location(&self) -> Result<IssuerKeyLocation, Error> {
+        let issuer = match self.issuer() {
+            Some(val) => val,
+            None => return Err(Error::SD_JWT_VC_MISSING_ISSUER),
+        };
+        let kid = &self.0.header.kid;
+
+        if issuer.starts_with("did:") {
+            if kid.starts_with("did:") {
+                return Ok(IssuerKeyLocation::DidUrl(kid.clone()));
+            }
+
+            if kid.starts_with('#') {
+                return Ok(IssuerKeyLocation::DidUrl(format!("{}{}", issuer, kid)));
+            }
+
+            return Err(Error::SD_JWT_VC_ISSUER_RESOLUTION_ERROR);
+        }
+
+        let authority = match issuer.strip_prefix("https://") {
+            Some(val) => val,
+            None => return Err(Error::SD_JWT_VC_ISSUER_RESOLUTION_ERROR),
+        };
+
+        let metadata_url = match authority.split_once('/') {
+            Some((host, path)) => format!(
+                "https://{}{}/{}",
+                host,
+                JWT_VC_ISSUER_WELL_KNOWN,
+                path.trim_end_matches('/')
+            ),
+            None => format!("https://{}{}", authority, JWT_VC_ISSUER_WELL_KNOWN),
+        };
+
+        Ok(IssuerKeyLocation::JwtVcIssuerMetadata {
+            metadata_url: String::from(metadata_url.trim_end_matches('/')),
+            kid: match kid.is_empty() {
+                true => None,
+                false => Some(kid.clone()),
+            },
+        })
+    }
+
+    fn check_validity_period(&self) -> Result<bool, Error> {
+        let now = clock::now().timestamp();
+
+        if let Some(exp) = self.0.payload.0.get("exp") {
+            match exp.as_i64() {
+                Some(val) if val <= now => return Ok(false),
+                Some(_) => {}
+                None => return Err(Error::JWT_PAYLOAD_FIELD_EXP_IDENTIFICATION_ERROR),
+            }
+        }
+
+        if let Some(nbf) = self.0.payload.0.get("nbf") {
+            match nbf.as_i64() {
+                Some(val) if val > now => return Ok(false),
+                Some(_) => {}
+                None => return Err(Error::JWT_PAYLOAD_FIELD_NBF_IDENTIFICATION_ERROR),
+            }
+        }
+
+        Ok(true)
+    }
+
+    pub fn verify(&self, issuer_key: impl VerifyFromKey) -> Result<bool, Error> {
+        if self.vct().is_none() {
+            return Err(Error::SD_JWT_VC_MISSING_VCT);
+        }
+
+        if !self.0.verify(issuer_key)? {
+            return Ok(false);
+        }
+
+        self.check_validity_period()
+    }
+
+    pub fn verify_with_options(
+        &self,
+        issuer_key: impl VerifyFromKey,
+        options: &VerificationOptions,
+    ) -> Result<bool, Error> {
+        options.algorithm_policy.check(self.0.header.alg)?;
+        options.apply(self.verify(issuer_key))
+    }
+
+    pub fn verify_with_resolver(&self, resolver: &impl IssuerKeyResolver) -> Result<bool, Error> {
+        let issuer_key = resolver.resolve(&self.issuer_key_location()?)?;
+        self.verify(issuer_key)
+    }
+
+    pub fn verify_with_store(&self, resolver: &impl VerifyingKeyResolver) -> Result<bool, Error> {
+        if self.0.header.kid.is_empty() {
+            return Err(Error::KEYSTORE_KEY_NOT_FOUND);
+        }
+
+        self.verify(resolver.resolve_at(&self.0.header.kid, self.0.payload.issued_at()?)?)
+    }
+
+    pub fn verify_key_binding(
+        &self,
+        holder_key: impl VerifyFromKey,
+        aud: &str,
+        nonce: &str,
+    ) -> Result<bool, Error> {
+        if self.cnf().is_none() {
+            return Err(Error::SD_JWT_VC_MISSING_CNF);
+        }
+
+        self.0.verify_key_binding(holder_key, aud, nonce)
+    }
+}
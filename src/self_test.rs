@@ -0,0 +1,449 @@
+use std::time::Duration;
+
+use sha2::{Digest, Sha256};
+
+use crate::{
+    algorithms::{Algorithm, FIPS_MODE},
+    crypto::{any::AnySigningKey, ecdsa::NonceMode, SignFromKey, VerifyFromKey},
+    errors::Error,
+    log, trace,
+};
+
+const MESSAGE: &[u8] = b"did-crypto known-answer test";
+
+struct KnownAnswer {
+    name: &'static str,
+    alg: Algorithm,
+    key: &'static str,
+    signature: Option<&'static str>,
+    deterministic: bool,
+    public_key_sha256: Option<&'static str>,
+}
+
+#[derive(Debug)]
+pub struct SelfTestResult {
+    pub name: &'static str,
+    pub alg: Algorithm,
+    pub result: Result<(), Error>,
+    pub elapsed: Option<Duration>,
+}
+
+impl SelfTestResult {
+    pub fn passed(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+#[derive(Debug)]
+pub struct SelfTestReport {
+    pub results: Vec<SelfTestResult>,
+    pub fips_mode: bool,
+}
+
+impl SelfTestReport {
+    pub fn passed(&self) -> bool {
+        self.results.iter().all(SelfTestResult::passed)
+    }
+
+    pub fn algorithms(&self) -> Vec<Algorithm> {
+        self.results.iter().map(|result| result.alg).collect()
+    }
+
+    pub fn failures(&self) -> Vec<&SelfTestResult> {
+        self.results
+            .iter()
+            .filter(|result| !result.passed())
+            .collect()
+    }
+
+    pub fn check(&self) -> Result<(), Error> {
+        match self.results.iter().find(|result| !result.passed()) {
+            Some(SelfTestResult {
+                alg,
+                result: Err(error),
+                ..
+            }) => Err(Error::SELF_TEST_FAILED
+                .with_algorithm(*alg)
+                .with_source(error.to_string())),
+            _ => Ok(()),
+        }
+    }
+}
+
+pub fn self_test() -> SelfTestReport {
+    run(known_answers())
+}
+
+pub fn self_test_algorithms(algorithms: &[Algorithm]) -> SelfTestReport {
+    run(known_answers()
+        .into_iter()
+        .filter(|vector| algorithms.contains(&vector.alg))
+        .collect())
+}
+
+fn run(vectors: Vec<KnownAnswer>) -> SelfTestReport {
+    let results = vectors
+        .iter()
+        .map(|vector| {
+            let (result, elapsed) = trace::timed(|| known_answer(vector));
+            if let Err(error) = &result {
+                log::error(format!("self-test {} failed: {}", vector.name, error).as_str());
+            }
+            SelfTestResult {
+                name: vector.name,
+                alg: vector.alg,
+                result,
+                elapsed,
+            }
+        })
+        .collect();
+
+    SelfTestReport {
+        results,
+        fips_mode: FIPS_MODE,
+    }
+}
+
+fn known_answer(vector: &KnownAnswer) -> Result<(), Error> {
+    let key = AnySigningKey::from_bytes(vector.alg, &decode_base64(vector.key)?)?
+        .with_nonce_mode(NonceMode::Deterministic);
+    let verifying_key = key.verifying_key();
+
+    if let Some(expected) = vector.public_key_sha256 {
+        if hex::encode(Sha256::digest(verifying_key.to_bytes()?)) != expected {
+            return Err(Error::SELF_TEST_FAILED.with_algorithm(vector.alg));
+        }
+    }
+
+    if let Some(expected) = vector.signature {
+        if !verifying_key.verify_bytes(MESSAGE, expected, vector.alg)? {
+            return Err(Error::SELF_TEST_FAILED.with_algorithm(vector.alg));
+        }
+
+        let mut corrupted = decode_base64(expected)?;
+        corrupted[0] ^= 0x01;
+        if verifying_key
+            .verify_bytes(MESSAGE, &base64_url::encode(&corrupted), vector.alg)
+            .unwrap_or(false)
+        {
+            return Err(Error::SELF_TEST_FAILED.with_algorithm(vector.alg));
+        }
+    }
+
+    let signature = key.sign_bytes(MESSAGE, vector.alg)?;
+    if vector.deterministic && vector.signature != Some(signature.as_str()) {
+        return Err(Error::SELF_TEST_FAILED.with_algorithm(vector.alg));
+    }
+    if !verifying_key.verify_bytes(MESSAGE, &signature, vector.alg)? {
+        return Err(Error::SELF_TEST_FAILED.with_algorithm(vector.alg));
+    }
+
+    Ok(())
+}
+
+fn decode_base64(content: &str) -> Result<Vec<u8>, Error> {
+    match base64_url::decode(content) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::DECODING_ERROR.with_source(error))
+        }
+    }
+}
+
+const HMAC_KEY: &str = "ZGlkLWNyeXB0byBzZWxmLXRlc3QgSE1BQyBrZXksIDMyKyBieXRlcyBsb25n";
+#[cfg(feature = "rsa")]
+const RSA_KEY: &str = concat!(
+    "MIIEvQIBADANBgkqhkiG9w0BAQEFAASCBKcwggSjAgEAAoIBAQC10LofrH056pg1-JgtyPztNmnf",
+    "juuuxkJudbWHFgZX-yqldjAzr9MlHlxS9R6ZwRmvwX_8lh7XChB7iXmblcu7mCtCT-WUMeCapwSd",
+    "YA5aaGDN03PhbTKsQzKnSY-VO8TGPhsAwbw5nzfCzXKdsJPfqzbtGR5lR4uCpyR3-sJ8kRgvbvJM",
+    "7PWyGUb2zJoSrbOL3ZbY_PgKpuhr6BfKWv8WIgqNFrWGIm4-V8nO2k78Y7pxCcmieRGGw2LVUM92",
+    "l8v58a_hMJ4ollsNMyu3D54CME-ei2xxb1OFqBUn9dghROUxFl4gQhOQ2o1rauolCNAvhsFbgtTg",
+    "mLeGL0eoiOodAgMBAAECggEACKQcsHQveHDynoAL5YMrqC-BnScrAuG31w2fnMW6c_SDPJ1vIYm9",
+    "-IHmo-ez9l1D1gNVjKDq4s_-5Awu3CrkpawQBEUNVXuAYAFHmh4uWHQlA6tE0uYwdhj0f5hiq1ZN",
+    "nhlmCaVrh3aOfSLsC1wQzxzRTbf3ppsdoY5FkLE5jJd5LQFE278KBKM1S1S93D2ntKrqPldGiYoo",
+    "2r9NRJgI-rxnST-XOEiqTJdbDq7iBP6QbvG6am7w7WrvkzWbjezYChuwiNhUOT9MQ2aoz515YiWF",
+    "MOk35AU08iYSnCcVCp0kyNsys67fSc58w6EoIzyiYZbryaEEfOPmfT7QEcbgSQKBgQDsvhFOW41y",
+    "Kegx3gm0oH9bvi_xFYWJnBcaztXa0ZO1POLHaKAccCsqCeOVGo_R0pkenmG6cPOJtOktBLt8sPbK",
+    "y6fDsHrMyeY6s06tcbyX6dHjlvYhNrXw4GceOTy_ofdE2GsR1eCsBgC5B49tN5gmwAcEqk9dLD4I",
+    "psHx8aODpQKBgQDEmtsC5CDiV_1O_v3vCwgmVwHWZhO5kX8l302eb8jdVgqD6nfVXJOAgs8YaEVA",
+    "01y6Jm97u2gJMp8OCythPBKNa7Tms4aycJU_4AMCXjROOigbp2NBt4Vh9XLfnP2AqA3VxQRYfCPj",
+    "TiGYi-Pj9fxqYLNXeLt2EXv1kHrO_5OjGQKBgH6p0qiR40PJmCnWGmtB0-JKB2x9j0zo7pOxRkol",
+    "B1pYkR0PpVqRyQZc9B66b2hbvQDczHzsIeDzoi4r8gPsyp4GCGRy78DR5RRTCy1RRQ4iU-HoyxdK",
+    "Jl4SMhnBlfcRuTJkeCS6wdJIA-r7WImezREufJLKbFv1Z9tJHUtNdabhAoGAWs3fC6XL3l6fWyYA",
+    "qLBisjqLWbfJsnKV1Csf0WZ10myT7LuVX1Ejuz4vYml-WuOEN2HbKb2z3lOoDLdx8LXrJ7AwXEFc",
+    "m_vNozPWj_hslhjcP3F_MXIOw-C2jrsWhp6XzTzytd-YP7rtX2R0--u4CnXhPyw_ANgwdYmmfwah",
+    "7LkCgYEA7CxIOWAr33-vzHp_JkSdrbsr1VJWJOEQkat8FbOqaRYl2hmaF54n7Ngy-dD8YOkFx0GH",
+    "fyEifJKpYbZCfmcwLpwElcoCCbaX2_8cQQdytH1gnsMcNItUN1dtRemMKErBgjpqgdIQXAxn5Xio",
+    "0eR6uHd8czPZwvhYUTmI0t3u22E",
+);
+
+fn known_answers() -> Vec<KnownAnswer> {
+    vec![
+        KnownAnswer {
+            name: "hs256",
+            alg: Algorithm::HS256,
+            key: HMAC_KEY,
+            signature: Some("dRjO8oqzR0QqrXT-73X5zX4yQJTH3KGmxGpewYoaqnE"),
+            deterministic: true,
+            public_key_sha256: None,
+        },
+        KnownAnswer {
+            name: "hs384",
+            alg: Algorithm::HS384,
+            key: HMAC_KEY,
+            signature: Some("xSsuqKGY1IMRFBbwREosPp6N8jZ69yCYaKcbvbS86mbwHT1gh3K2ecjiHPy1NI-m"),
+            deterministic: true,
+            public_key_sha256: None,
+        },
+        KnownAnswer {
+            name: "hs512",
+            alg: Algorithm::HS512,
+            key: HMAC_KEY,
+            signature: Some(concat!(
+                "SQwru13asdmVCOz1AocXjIDWE8ZYIjkRCj46o_wdYCvr7CGz3rwS_hjhISeIVv2NNxkQXBA_togp",
+                "fkQ4cWQ1iA",
+            )),
+            deterministic: true,
+            public_key_sha256: None,
+        },
+        #[cfg(feature = "rsa")]
+        KnownAnswer {
+            name: "rs256",
+            alg: Algorithm::RS256,
+            key: RSA_KEY,
+            signature: Some(concat!(
+                "meB3KKocvhw1yblU_Y4tcHAM2qq6fKNNmXvw3aBaxqI_tliRSH36Xi4EMN82zqmCJRJtiaWi9Je0",
+                "-nhwHq5-qSSOIu4E_wxdYXw8BJ7gHR2P3tMNiB_LoG9mkfXyxA-CQQpu1aSgIl5j5_Hfw1oiebBU",
+                "uTs3LGuVaAQ1_Y64iipom62jkLIKUlBt_U-R1yEG3S95b_URi-LEjVFuaX09B2UZP1Z8i6rMD-US",
+                "hg3Z0keJOeyZO0JByLylc8y50iJpHC8xieRrXd6D51p_ur9WnQhwVo1OC1mu1-x-vwLT76VoEuXf",
+                "zmiYibo2MOpjHN3RD6RozLBanziwTNklXmmjRA",
+            )),
+            deterministic: true,
+            public_key_sha256: None,
+        },
+        #[cfg(feature = "rsa")]
+        KnownAnswer {
+            name: "rs384",
+            alg: Algorithm::RS384,
+            key: RSA_KEY,
+            signature: Some(concat!(
+                "RRDfmlnJ58_5mtgH1s-unfQAluiNPG70xRU_YKG1Uo1sEZ4PBHPnZOXnLRXYbtQ3haaE24-xQE5Z",
+                "RNx14ircouunPz1RVjNDY5u8f5wDYm9Y5J1B9yu-jXNYs8p8Z6N-1NeXfEk01ekwPsT8OxWuPyeW",
+                "xf0glAKIe7CU4X-Jiyz8wEozGFujUiNHF1syodw-TlfLROS-5zFCb_EeZOqbhY3TuDsfjkKS27M-",
+                "xKz-aVgTrjzRfoD9P_zxwyVcUPhuhakOOsiQsGWLCc5SeN5PwUZgL4csTa0tagTCuFxgTEpHCVEa",
+                "QQihFRaFyKnu9BK7Zan7JAs1qwaq2SZbJ4QcyQ",
+            )),
+            deterministic: true,
+            public_key_sha256: None,
+        },
+        #[cfg(feature = "rsa")]
+        KnownAnswer {
+            name: "rs512",
+            alg: Algorithm::RS512,
+            key: RSA_KEY,
+            signature: Some(concat!(
+                "LMygqek3TxhPinOFAqawJ2GxjyG3buEp8Q7Sv-h_Tq38oIUitxPHRn4KfsB0x1TH3Eo1A1rAOQrh",
+                "tAJFaBYfybm77GuGksKU2iqhpBinV5mpMxeeUweDHLw8r28BQhCMoPPlKh03oVFY6hqcRJyn5EtU",
+                "LegIKV5J9A7SEXA4HDOXooV4L-rEkiEXBBvryyV7HY6ml34EvPkGwlAc_MboQtTR1ocUX4Trl_mE",
+                "-UOqPA2rYu9x9vplQRuNTP8Qg5IUF0IcjKUlQPWujR7gpSkdZWyEXgw45wBlUwg3xWohkPqc1X5J",
+                "51d8zdZqpD4UZBus6p0o_GjQWIx2Vljh5-Csxg",
+            )),
+            deterministic: true,
+            public_key_sha256: None,
+        },
+        #[cfg(feature = "rsa")]
+        KnownAnswer {
+            name: "ps256",
+            alg: Algorithm::PS256,
+            key: RSA_KEY,
+            signature: Some(concat!(
+                "A0NLRDrhigpB2r21FRQ00B-nhYS_eWJakfoSGDf75u2zxlJBhb0HZhnytx6q5w8yLCYUtbjO5TB1",
+                "HeWWLGjrgfdsOtZkAhZA2Pbjq55VO8MbobLa6lC2DLdUtvHHo5FmSubpUSl5-un_tIg4OiMtVbWR",
+                "32rjGOIT1WpBgElgpVH7YgpJhWxUK7wUdSRMr7H0SN_hWeIOVSM0hynH1uzWCUFcFnPyDw2mxQSR",
+                "iQDNf4POymv_BDy9QKL8DDUzdnx3sG-jGN3OmDY9YicUC-BHniSKEML7WrR9aJ1smCbONh6-JA0B",
+                "5p107z7wTi_WwDpQPChFAm3NfBTmK8BHrb0VAQ",
+            )),
+            deterministic: false,
+            public_key_sha256: None,
+        },
+        #[cfg(feature = "rsa")]
+        KnownAnswer {
+            name: "ps384",
+            alg: Algorithm::PS384,
+            key: RSA_KEY,
+            signature: Some(concat!(
+                "tSZ93bXgjp74r2iWblxKRrYKfVRQJN8Sn8eKW4PleHHvZDQvg-YHnkCCVZLt4n3ZrXBV5jc5a2c0",
+                "rzkArEW6zgGcqCkvAEZLYEMIxkbQg59La5UKg9eHpsQYa2IA9p_aczA_Ej78qkZSvyzISgj_aa_j",
+                "A_sv2as6I_OK1exSk25M5Wlk4ftahOmdt_7w12w106_YFWsUyDbibXaY7WAVMI1I0qTeF6HDO8Zi",
+                "mzowGo9n7yFET-MgBpge9aCTk5FO_nz4hb2QPl6WuV74SinqVMmrhQtBj0nDIT5-tpLIMbYDggoH",
+                "MsA4jMZBogEpw_XbJEzafJmw6AS6ZwiGs_IpyQ",
+            )),
+            deterministic: false,
+            public_key_sha256: None,
+        },
+        #[cfg(feature = "rsa")]
+        KnownAnswer {
+            name: "ps512",
+            alg: Algorithm::PS512,
+            key: RSA_KEY,
+            signature: Some(concat!(
+                "IOtPso7l87k4bMyNIhCQ8bno_kSsocRJ8H151LzyaxMu8BNRn3z6h0oOiRcn34oZTXxq0YTy9ZpM",
+                "cA07oNxBkIY-hrcFAcBY1n_7nGFLby9kEwPG-O_HQYI6Z6wCdpBQRF1H6LBtqWzEa8tYfGAfNSnz",
+                "tgUfRVWYKrPEdsmdd6O3N1PBgX3wJ30nuBXOk9I3jubWe77OvPCkmcfD_OpCg4nKuNgxStj4Wq8R",
+                "hymKmxj3bnsgSrQUz64OgDmBNNZ-ZPA_OKnQI52NiIGkTH3WuOb4CAreNt8vmOsfO2m5rNblyUXV",
+                "Z-1LrMoKNwlavY9W6LYKodh9ZFFmmW764YuACw",
+            )),
+            deterministic: false,
+            public_key_sha256: None,
+        },
+        KnownAnswer {
+            name: "es256",
+            alg: Algorithm::ES256,
+            key: "Q195AFdmcPt6faOXv7hl-UJVE1lUulXtcE2X66oT72g",
+            signature: Some(concat!(
+                "D34yvG334rthaWFeKzZ9JnWFNSj0W0xEEvFdWNv3Qx5OHuq1tgQ3jH2hY3MGBY2EPDq6yVVDYeyA",
+                "pj18mLOyQQ",
+            )),
+            deterministic: true,
+            public_key_sha256: None,
+        },
+        #[cfg(feature = "es384")]
+        KnownAnswer {
+            name: "es384",
+            alg: Algorithm::ES384,
+            key: "U-YxFhOzT5AGjj4Kq5OXCRwf_2-K0apXwLyB5ZQsqXTMi-LYjura1c6-Vr89KELK",
+            signature: Some(concat!(
+                "aonvIMfNdG33Nvn_Xm69bNvGOGyzc59vUr-eqPP7UASUqlzmnH6FO-KhSCiYld6KsHCMqg7qaoH1",
+                "aXTKT9rKTCMwKTXiuM8u6W2Gn_5IoZwgiThGOaRMyH7JrxtsRmlN",
+            )),
+            deterministic: true,
+            public_key_sha256: None,
+        },
+        #[cfg(feature = "es512")]
+        KnownAnswer {
+            name: "es512",
+            alg: Algorithm::ES512,
+            key: concat!(
+                "AKOLxexczlkAdRQNul4VzPkUfPtfCF3ncfPeM5mg678Z1SvN2K-2FAekg7A4ofWt64oU56EzFd8G",
+                "Ag0U7PjdiT-R",
+            ),
+            signature: Some(concat!(
+                "AcgvtV2COHeIJ1ZgkwwUvJTfl5yB_fc5dKFvT_j5fLtFvX1gAselRwsk2g2cGh32Aow1laOg06mT",
+                "BZVqOayQm4t1ACHpI3_9EwWZHTx73IQB8dueiEORhzonq7hk5lC9Eh0862D7RJDEYeJlOv-tesmu",
+                "tPcdJmKTpxwSbQ4eNxJg543F",
+            )),
+            deterministic: false,
+            public_key_sha256: None,
+        },
+        #[cfg(feature = "es256k")]
+        KnownAnswer {
+            name: "es256k",
+            alg: Algorithm::ES256K,
+            key: "_Uwl6iySLPP6Ooqu7Ku8HLQtx3-sGCk6FUDbMC0JZyQ",
+            signature: Some(concat!(
+                "1GSB18QltKWB45zrZ3ZiyMUHl6Wj788aR4EHAOdqBAEX988MhD3VbFbinX0fbx5Gfi2oJNpbTHDv",
+                "XQk8_2MWfQ",
+            )),
+            deterministic: true,
+            public_key_sha256: None,
+        },
+        #[cfg(feature = "eddsa")]
+        KnownAnswer {
+            name: "eddsa",
+            alg: Algorithm::EdDSA,
+            key: "Bzf8oiXJvOQu1OprloQrYqOZbxiuefM2DvMi0cmLUvE",
+            signature: Some(concat!(
+                "XgEWvzbxBmF760LOjOCq_KAaA1WH18B5O_yGWBv9fjZG_ImMAwqbyUWuTd-ShobaiyhZZjbLo9VA",
+                "fg31A3uzCA",
+            )),
+            deterministic: true,
+            public_key_sha256: None,
+        },
+        #[cfg(feature = "mldsa")]
+        KnownAnswer {
+            name: "mldsa65",
+            alg: Algorithm::MLDSA65,
+            key: "Iwcls3frsS2lfP9Xpcj_kD-zwEy1f0xE406YPXMf0hY",
+            signature: Some(concat!(
+                "V8mbNalPQcSEtuGloNz7R7bIXT5wUmjf_bXKodyGO196zumYQ_N8jnuKBmmQTX5yPcugYN9Qgl1w",
+                "CtrVXOYhl0Oabf0nrb26nSY_qksRMc8j7IkaUaU-XHqkH1uU9s61-UpWiGVEEXu6Ij6ziwtja2C-",
+                "dWVZr_8jvvZJPzr2bAvY7iwkz5NVVpIub9m0S4WMQxAFR12k2VDctfOb1q7xIxPpX4Ks93hEs5gS",
+                "msoissGKsCrgoX04nKR3tXouaOa3asRvBEiMBYFfl2OHyPQNObCEKBtyFHz_HemPGis4l_Rsqk6q",
+                "2xeBhG6Wjqpp6Mvy2XlqZlJTv9b23e6aDA1kOHqMvtEYZsfx8JRfK8QePcVdY0XsWze-1inbPxUj",
+                "eB2lpjfcVwe8Myep5Dez3wKztFJz_U-0oqZLboRSBsmSXE6ApyGScoPXy44VuQPqWX6dP4foFapw",
+                "Ef8WewYDxuRWxNkSAP_iFZe3vuWIS9QHDchd3flWsd0uPOEoznullvroYulOofzbM2fQKK_4x8P9",
+                "nb7-CrPJTX5kFrNYLVb6RZmN9mRpwljpJ1uXfbX6fabC4HIz2usRtANkTZZRDY_hpgKxS1u90U5a",
+                "ux0KwicC06GJj-CNqsWWum3Ty2qqLoxcQNGmHK-i40OZtiYC0Q6D2u85WbhgtPs3sFxIY2ZTngZa",
+                "qsoscMpU2CSLwxqSdvWYnMj_eAaZUaIhYKTP6O4wxEUWhDQ_Jefl3nbdg8zX8BY07Fxj_B2tPWPS",
+                "jEQf2wvqjS6vfAwf7P67Rb7mpfc8daDrKF7h2mM2Ib36LQ41vYKoDyKMl2dI185vGvig5NZFFLGz",
+                "EaPJCUT_nTyukJqPZ8odRyzDT3RNyJvz51SsVIZnK2isEqjuyq5D6hWnCnI3twi5npiqZUKIDsm6",
+                "4_p3giSU_yJIMPpAIEISzoYCnr3JweYU3R0pIiYNx0Mzj-5GepI6ijmMwgTH2TWC9vwm3cq4OHdr",
+                "iOuk0SuDbvTTvMZA1BXlcCxNmWm529hY_8ZEaKf8jGJKC8ruTn0NraUKaN3YSctRs5IulpuiBKHA",
+                "n4-CbPjCVjX3A8EJfEzWkB7fKF2dWpU50JUjTxgYB5S5jItNDa2Mv2b92RGGV-cMtX5wAieSSOu2",
+                "RLdPN1fuQmEyZbHZy7mpE9P_-G2i27f7GnSAbx7tTZVLlH_robkRHxgWzPO9OoPOIyO6aOKG9ncU",
+                "GWRdO6jdIlgxmjATrunzN5HSU_lIlNRRH1mCnXMF96ueko-LP2IxL8_CIzYl6xrn63qOmS0aLxIS",
+                "teEuy8SEyT7tQ_iX-uyoVKVI7qAmmEyu-gfrCTVeiqHihILFlfsUMp67oLCxSVAJIy7ML3XuC5nZ",
+                "ETX2122PsWPl2VhEdXNJ323FVTTyHroC2FnS-xjShgFvLYNgLo5K3etCpp-jhjPDtwsYmFaL0Bnv",
+                "Hb7evWyBQbr3wgtN33GENcWyoixzsdkAcfTSySAXOlB2oeRS7CQSykuXSR1npn8aTUyqrt2B_XHZ",
+                "3zk-LZibe74f6p-bOkvVWFA81BgS_EfCoM2PIcdlGKAEyeZKSJUpAjPmjwpnxmOcTZ__4JCM109F",
+                "MtSCiO9DQGyHuucoFSh5dI7hQmTRl0aCWBu0Q2OybMmKG_Rs8-WdHJfUFVXyqtERxspcTwNJRsp-",
+                "MKvj-nk21rMFwhopPkSnmFO0nBxyFUPvH4nHSl4ssZ93zcJb7vkT4UD3U3O0PZGinSBDL787_7iF",
+                "wVrOovUsfxHV1cvKLSl5zRpOg8m-1XKLwnaYXKD2yWBaGB1lUvNzGTqWqpmjd9cFCcxbPO3vsRvi",
+                "a_AGSrdxLDH7SULk6PLgak7zBa896hHBMk-pzFrG_ktenL2SA34b-ou_7U1UNFMUhqNvOqz3zXkJ",
+                "eZC1U5VbQ4Wj_8jrkWmBN3Kxcv9HsPSoWl0MwjVfYF2hE0IhMXGOx0dDMvSnnaX34y7hvpVcdYZj",
+                "YO808puYjYedrfpfUpd67Sj6doIw4KugjeiSWY1GA4suTnBJwQdbzqKrnfHNMptBYXYnwJQszIZm",
+                "TORmiuDkTlKa6tpSJOGE4bMyK0ck0VwYvVXd22e5cRJFD5lBnhHugeQf1a84UoTbQLEDFZfdHqe7",
+                "Qmj-bU46ijDwV-Q9dm1EZgV27_iocHY8g8h9QXpEt4IGe52ILvJ5dH1Uk8QgBF_4mnRFHISnnzyQ",
+                "CfgLiM_NzCI2g8U7bRvtj8ObWdMjSno2pjnwj2D_i9kI6ySL7t3xDfLS9QM77o3h3PnSUB4DsIT7",
+                "47W02_H6vGNA_9sR-c5Ho6CaEYJuAcgm6iYYtcTCga8TWj3bunhC-0gi-FURwQx_OfCKxqfvy_lu",
+                "MJuPq3R6khRWbPweQO-jYoLpxvsvoKquwqdYwcV5AU3Zi79k5BaRtlpLj1ccQTODxh7ljWuN80sQ",
+                "Y_FWH-TclA68clq25PtBDTiq1u1nvBfCoVWLIsCkw9HTkpHOyr-Y6wuB8JRZG_0xdRH8kefv4iS2",
+                "bnR_IPXxb0iN6w16fERyMzI78Ci-_hTyODvzAwYvyf8bLMRCFLd-Xqe9Uv_DNUFvzJHGof6Btzsf",
+                "_TeScskPJFO7nOKVyOPoR_-ba8LS1Cv0q1fFenp56bP_ah-UvNsvEn5DcGn56QJj04v88Lv30ZU2",
+                "_pJxsuuRLDqcr8RYVjeb0lAws8gl1QkztM_EtWYSf-PYotftYEx0_86dA8EsDcHY8S60cf5h30-A",
+                "6JDNOyFA4v3jEmIIQPBv_m64oCf4RWk-g5JMzDwwoge5Q0OAyc1FDXEElnOe-vpTLvsYt8gFhaDY",
+                "kW-UNPy1l2RqIJF9zA-MToeK8gxkYzpG4KZ2k1phO0b2Y-m0htKbAle_QWlng9wxNRulYaBZ-6LY",
+                "PZHvP9_X8SoPDmKM1IQ2wpLaQd0wCoOOxYxcfD9JcICFKnbu8RtakWOL5vn45_mpGDxgIjtHj88V",
+                "Swr6JlfxQaLiyE4ng6dHBhPmT7B6cqEwmOAXyzf3cKDTGSM40UlgMQm9jTX_P-WmJJFctw4qTiLW",
+                "0x_M_E0akJ3U_BedZcrVOzQdi6ZrYp4gpX3zcCoRFYX0TfWQns3nJIIwpwuXhPLlZ0ASm6xcHC2H",
+                "IhKWZJNZR37VT3l1aW361gXaCqxkj0Et0h0d270l5wZSRCdxiKjRWkTTKA8xVjRuns-yhHiPLrnm",
+                "_7j417WUf55LTOpEa6LyCVXVvasrpSvrpOwio0j1xxkvOY8vk5b-5bI4X3oC_ek4axlpzc_oIdXL",
+                "uRTNfpRdu8AsisBVkiV0TmjkwxDmro8OYc82seUD8ekJVPLsBh4xuPKhUu-Pm4_IuP9aZpuzEBJb",
+                "RHSi7Fpyi1gTnCtR051FXQM425pJlpEkXt_85T_UCVI94je9kF78Y4c6PtuKzf8EKKNRzuxQyd7E",
+                "SZgG_wu8TzSCBirRHlJp4GC6c2YC3wZsttN1Xast7QvuLV_RjcyakrYusBlv1eR49VSiuR7uRH6I",
+                "BB-xN5K3FQtQqmrLLu1N53k5p5fgL-E9EmwTx4nrCxrRRsgwZu5tnrtBPjymR2eUeG2HNPz8jvX2",
+                "yz99F4GI_Ov95ODSXoBtty4NeOWNWIoJw92Pe3m827tlfYH4SSqRx3kPnqktnzoeAkD2zEsJlAqL",
+                "p5hUl8HyUeUTwsmMuY48Gk5kYcsiLGwvIPWVCA04Jdg5g5zobO2gKSEbMKZWhXZlWq_XlcX-B7H9",
+                "Ml1htxKrbKPSN1W3A4ivXlDAxhJqE05lHVqZjfrjaayTTml3EbNUDtNIMs0HISIV0dC2Xtq9pFD_",
+                "1ztTpLJignVSBqV-mW7xV0mWwQTdJS5I8zCP7ue5MjPpM3vh0qM6EjeGWsxfbdABNwvNekKBjZnz",
+                "U4hA2fOBpnzdHdlhNyzLVTZ9gAwc89fB3VVm0dnEda8EvZCmYZ9HDaqhunhnHU2bo98EV5mvw4qK",
+                "L91MlDiro_qoQ0HAfYGhuGxKl5bVqltyteK_iALCwKm9VYj2YNLkZS5uWFpatpnzp5xUl5O6_NRD",
+                "HTatLZTREok5BW2nok8CROv-v6kuawfRCNkHgnyNNznjEdciVwyMmP8ssdM9AW5ULAgt8WGcCsXo",
+                "QyeRfLUl99YpQnx7mfkKwfRhOGF3Bi0I4i8WVb89hvCaiwrvAXC9TY5_WMQlUInxw_WmARrSZ9nF",
+                "IrFoydJ9PM_qA__F3GoD7OpPNIbAxhJSIh2GKqqNlI0Rr53jsFXjDCKdrR8ZFw43I2IlaoBVXeaz",
+                "DBoMvWEApNuhUE183yFb4LeAEomu-8_XbAPAF5vZssZdvOFyO4E7I1jAqAxoZ0aIvELE6DQ9ZpwZ",
+                "mrC16foNFFRfobbvCiWltQsZU3-Kpx1bX6PxJjpRYmZrip2k5AAAAAAAAAAAAAAAAAAAAAAABg0R",
+                "Fxwm",
+            )),
+            deterministic: false,
+            public_key_sha256: Some(
+                "7c06077b0117f812a34375645c1c13fe8fd4f524a2a8932f0c4626cbf67421fd",
+            ),
+        },
+        #[cfg(feature = "mldsa")]
+        KnownAnswer {
+            name: "mldsa65-ed25519",
+            alg: Algorithm::MLDSA65Ed25519,
+            key: concat!(
+                "C6MSYdVTyqFbcmJ_nRh-61MVUiwpkGohMXIPbeYAzlAIyv_eBCSIHPV2c4pjOTCuSzCAo9WJi8kF",
+                "3tLtiOFOZQ",
+            ),
+            signature: None,
+            deterministic: false,
+            public_key_sha256: Some(
+                "79ea8f87725659d0ad71bb8b2a4a21b7914fa335b96c9d4326af8dba116053d7",
+            ),
+        },
+    ]
+}
@@ -1,16 +1,89 @@
+#[cfg(feature = "eddsa")]
+use crate::crypto::eddsa::sign_eddsa;
+#[cfg(feature = "mldsa")]
+use crate::crypto::mldsa::sign_mldsa;
+#[cfg(feature = "rsa")]
+use crate::crypto::rsa::sign_rsa;
 use crate::{
     algorithms::{Algorithm, AlgorithmFamily},
-    crypto::{ecdsa::sign_ec, eddsa::sign_eddsa, hmac::sign_hmac, rsa::sign_rsa, SignFromKey},
+    audit,
+    crypto::{
+        ecdsa::sign_ec,
+        hmac::sign_hmac,
+        signature::{Signature, SignatureEncoding},
+        SignFromKey,
+    },
+    envelope::{EnvelopeFormat, SignatureEnvelope},
     errors::Error,
+    metrics::Operation,
+    trace,
 };
 
-pub fn sign(message: String, key: impl SignFromKey, alg: Algorithm) -> Result<String, Error> {
-    let alg_family = alg.get_family();
-    match alg_family {
-        AlgorithmFamily::HMAC => sign_hmac(message, key, alg),
-        AlgorithmFamily::RSA => sign_rsa(message, key, alg),
-        AlgorithmFamily::EC => sign_ec(message, key, alg),
-        AlgorithmFamily::OKP => sign_eddsa(message, key, alg),
-        _ => return Err(Error::UNKNOWN_ALGORITHM),
+pub fn sign(
+    message: impl AsRef<[u8]>,
+    key: impl SignFromKey,
+    alg: Algorithm,
+) -> Result<String, Error> {
+    let message = message.as_ref();
+    let signature = trace::traced(
+        Operation::Sign,
+        Some(alg.to_str()),
+        || key.key_fingerprint(),
+        || match alg.get_family() {
+            AlgorithmFamily::HMAC => sign_hmac(message, &key, alg),
+            #[cfg(feature = "rsa")]
+            AlgorithmFamily::RSA => sign_rsa(message, &key, alg),
+            AlgorithmFamily::EC => sign_ec(message, &key, alg),
+            #[cfg(feature = "eddsa")]
+            AlgorithmFamily::OKP => sign_eddsa(message, &key, alg),
+            #[cfg(feature = "mldsa")]
+            AlgorithmFamily::AKP => sign_mldsa(message, &key, alg),
+            _ => Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
+        },
+    )?;
+
+    audit::audit(Operation::Sign, alg, || key.key_fingerprint(), &[message]);
+    Ok(signature)
+}
+
+pub fn sign_signature(
+    message: impl AsRef<[u8]>,
+    key: impl SignFromKey,
+    alg: Algorithm,
+) -> Result<Signature, Error> {
+    Signature::from_base64url(&sign(message, key, alg)?, alg)
+}
+
+pub fn sign_encoded(
+    message: impl AsRef<[u8]>,
+    key: impl SignFromKey,
+    alg: Algorithm,
+    encoding: SignatureEncoding,
+) -> Result<String, Error> {
+    Ok(sign_signature(message, key, alg)?.encode(encoding))
+}
+
+pub fn sign_envelope(
+    message: impl AsRef<[u8]>,
+    key: impl SignFromKey,
+    alg: Algorithm,
+    kid: Option<&str>,
+    format: EnvelopeFormat,
+) -> Result<Vec<u8>, Error> {
+    let envelope = SignatureEnvelope::sign(message, key, alg)?;
+    match kid {
+        Some(kid) => envelope.with_kid(kid).encode(format),
+        None => envelope.encode(format),
     }
 }
+
+pub fn sign_batch(
+    messages: &[&[u8]],
+    key: impl SignFromKey,
+    alg: Algorithm,
+) -> Result<Vec<Signature>, Error> {
+    let signatures = key.sign_batch(messages, alg)?;
+
+    audit::audit(Operation::Sign, alg, || key.key_fingerprint(), messages);
+    Ok(signatures)
+}
@@ -0,0 +1,119 @@
+use crate::{
+    algorithms::Algorithm,
+    crypto::{SignFromKey, VerifyFromKey},
+    errors::Error,
+    signer::sign,
+    verifier::verify,
+};
+
+pub const CONTEXT_PREFIX: &[u8] = b"did-crypto/signing-context/v1\0";
+pub const JWT_CONTEXT: &str = "jwt";
+pub const VC_CONTEXT: &str = "vc";
+pub const CHALLENGE_CONTEXT: &str = "challenge";
+pub const RAW_CONTEXT: &str = "raw";
+const MAX_TAG_LENGTH: usize = 255;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SigningContext {
+    tag: String,
+}
+
+impl SigningContext {
+    pub fn new(tag: &str) -> Result<Self, Error> {
+        if tag.is_empty() || tag.len() > MAX_TAG_LENGTH {
+            return Err(Error::SIGNATURE_CONTEXT_INVALID);
+        }
+
+        Ok(SigningContext {
+            tag: String::from(tag),
+        })
+    }
+
+    pub fn jwt() -> Self {
+        SigningContext {
+            tag: String::from(JWT_CONTEXT),
+        }
+    }
+
+    pub fn vc() -> Self {
+        SigningContext {
+            tag: String::from(VC_CONTEXT),
+        }
+    }
+
+    pub fn challenge() -> Self {
+        SigningContext {
+            tag: String::from(CHALLENGE_CONTEXT),
+        }
+    }
+
+    pub fn raw() -> Self {
+        SigningContext {
+            tag: String::from(RAW_CONTEXT),
+        }
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    pub fn frame(&self, message: impl AsRef<[u8]>) -> Vec<u8> {
+        let message = message.as_ref();
+        let mut framed =
+            Vec::with_capacity(CONTEXT_PREFIX.len() + 1 + self.tag.len() + message.len());
+        framed.extend_from_slice(CONTEXT_PREFIX);
+        framed.push(self.tag.len() as u8);
+        framed.extend_from_slice(self.tag.as_bytes());
+        framed.extend_from_slice(message);
+        framed
+    }
+
+    pub fn strip<'a>(&self, framed: &'a [u8]) -> Result<&'a [u8], Error> {
+        let rest = match framed.strip_prefix(CONTEXT_PREFIX) {
+            Some(val) => val,
+            None => return Err(Error::SIGNING_CONTEXT_MISMATCH),
+        };
+
+        match rest.split_first() {
+            Some((len, rest))
+                if *len as usize == self.tag.len() && rest.starts_with(self.tag.as_bytes()) =>
+            {
+                Ok(&rest[self.tag.len()..])
+            }
+            _ => Err(Error::SIGNING_CONTEXT_MISMATCH),
+        }
+    }
+
+    pub fn sign(
+        &self,
+        message: impl AsRef<[u8]>,
+        key: impl SignFromKey,
+        alg: Algorithm,
+    ) -> Result<String, Error> {
+        sign(self.frame(message), key, alg)
+    }
+
+    pub fn verify(
+        &self,
+        message: impl AsRef<[u8]>,
+        signature: impl AsRef<str>,
+        key: impl VerifyFromKey,
+        alg: Algorithm,
+    ) -> Result<bool, Error> {
+        verify(self.frame(message), signature, key, alg)
+    }
+
+    pub fn open(
+        &self,
+        framed: &[u8],
+        signature: impl AsRef<str>,
+        key: impl VerifyFromKey,
+        alg: Algorithm,
+    ) -> Result<Vec<u8>, Error> {
+        let message = self.strip(framed)?;
+        match verify(framed, signature, key, alg)? {
+            true => Ok(message.to_vec()),
+            false => Err(Error::SIGNATURE_INVALID),
+        }
+    }
+}
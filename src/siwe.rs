@@ -0,0 +1,379 @@
+use std::{fmt, iter::Peekable, str::FromStr, str::Split, time::Duration};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+use rand::{rngs::OsRng, CryptoRng, RngCore};
+
+use crate::{
+    caip::{checksum_address, eip155_address, keccak256, parse_eip155_address},
+    clock,
+    crypto::ecdsa::_256k::{P256kSigningKey, P256kVerifyingKey},
+    errors::Error,
+    log,
+};
+
+pub const SIWE_VERSION: &str = "1";
+pub const DEFAULT_SIWE_LEEWAY: Duration = Duration::from_secs(5);
+const PREAMBLE: &str = " wants you to sign in with your Ethereum account:";
+const NONCE_ENTROPY: usize = 16;
+const MIN_NONCE_LENGTH: usize = 8;
+
+pub fn generate_nonce() -> String {
+    generate_nonce_with_rng(&mut OsRng)
+}
+
+pub fn generate_nonce_with_rng(rng: &mut (impl CryptoRng + RngCore)) -> String {
+    let mut bytes = [0u8; NONCE_ENTROPY];
+    rng.fill_bytes(&mut bytes);
+    hex::encode(bytes)
+}
+
+pub fn personal_sign_hash(message: &[u8]) -> [u8; 32] {
+    let mut data = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    data.extend_from_slice(message);
+    keccak256(&data)
+}
+
+pub fn personal_sign(message: &[u8], key: &P256kSigningKey) -> Result<String, Error> {
+    let mut signature = key.sign_prehash_recoverable(&personal_sign_hash(message))?;
+    signature[64] += 27;
+    Ok(format!("0x{}", hex::encode(signature)))
+}
+
+pub fn recover_key(message: &[u8], signature: &str) -> Result<P256kVerifyingKey, Error> {
+    let decoded = match hex::decode(signature.strip_prefix("0x").unwrap_or(signature)) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::DECODING_ERROR.with_source(error));
+        }
+    };
+
+    P256kVerifyingKey::recover_from_prehash(&personal_sign_hash(message), &decoded)
+}
+
+pub fn recover_address(message: &[u8], signature: &str) -> Result<[u8; 20], Error> {
+    Ok(eip155_address(&recover_key(message, signature)?))
+}
+
+fn format_timestamp(value: DateTime<Utc>) -> String {
+    value.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn parse_timestamp(value: &str) -> Result<DateTime<Utc>, Error> {
+    match DateTime::parse_from_rfc3339(value) {
+        Ok(val) => Ok(val.with_timezone(&Utc)),
+        Err(error) => Err(Error::SIWE_MALFORMED.with_source(error)),
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct SiweVerification<'a> {
+    pub domain: &'a str,
+    pub nonce: &'a str,
+    pub time: Option<DateTime<Utc>>,
+    pub leeway: Duration,
+}
+
+impl<'a> SiweVerification<'a> {
+    pub fn new(domain: &'a str, nonce: &'a str) -> Self {
+        SiweVerification {
+            domain,
+            nonce,
+            time: None,
+            leeway: DEFAULT_SIWE_LEEWAY,
+        }
+    }
+
+    pub fn time(mut self, time: DateTime<Utc>) -> Self {
+        self.time = Some(time);
+        self
+    }
+
+    pub fn leeway(mut self, leeway: Duration) -> Self {
+        self.leeway = leeway;
+        self
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SiweMessage {
+    pub scheme: Option<String>,
+    pub domain: String,
+    pub address: [u8; 20],
+    pub statement: Option<String>,
+    pub uri: String,
+    pub version: String,
+    pub chain_id: u64,
+    pub nonce: String,
+    pub issued_at: String,
+    pub expiration_time: Option<String>,
+    pub not_before: Option<String>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    pub fn new(domain: &str, address: [u8; 20], uri: &str, chain_id: u64) -> Self {
+        SiweMessage {
+            scheme: None,
+            domain: String::from(domain),
+            address,
+            statement: None,
+            uri: String::from(uri),
+            version: String::from(SIWE_VERSION),
+            chain_id,
+            nonce: generate_nonce(),
+            issued_at: format_timestamp(clock::now()),
+            expiration_time: None,
+            not_before: None,
+            request_id: None,
+            resources: Vec::new(),
+        }
+    }
+
+    pub fn for_key(domain: &str, key: &P256kVerifyingKey, uri: &str, chain_id: u64) -> Self {
+        Self::new(domain, eip155_address(key), uri, chain_id)
+    }
+
+    pub fn with_scheme(mut self, scheme: &str) -> Self {
+        self.scheme = Some(String::from(scheme));
+        self
+    }
+
+    pub fn with_statement(mut self, statement: &str) -> Self {
+        self.statement = Some(String::from(statement));
+        self
+    }
+
+    pub fn with_nonce(mut self, nonce: &str) -> Self {
+        self.nonce = String::from(nonce);
+        self
+    }
+
+    pub fn issued_at(mut self, time: DateTime<Utc>) -> Self {
+        self.issued_at = format_timestamp(time);
+        self
+    }
+
+    pub fn expires_at(mut self, time: DateTime<Utc>) -> Self {
+        self.expiration_time = Some(format_timestamp(time));
+        self
+    }
+
+    pub fn not_before(mut self, time: DateTime<Utc>) -> Self {
+        self.not_before = Some(format_timestamp(time));
+        self
+    }
+
+    pub fn with_request_id(mut self, request_id: &str) -> Self {
+        self.request_id = Some(String::from(request_id));
+        self
+    }
+
+    pub fn with_resource(mut self, resource: &str) -> Self {
+        self.resources.push(String::from(resource));
+        self
+    }
+
+    pub fn to_message(&self) -> String {
+        self.to_string()
+    }
+
+    pub fn sign(&self, key: &P256kSigningKey) -> Result<String, Error> {
+        if eip155_address(&key.verifying_key()) != self.address {
+            return Err(Error::SIGNING_FAILED);
+        }
+
+        personal_sign(self.to_message().as_bytes(), key)
+    }
+
+    pub fn verify(&self, signature: &str, validation: &SiweVerification) -> Result<bool, Error> {
+        let issued_at = parse_timestamp(&self.issued_at)?;
+        let expiration_time = match &self.expiration_time {
+            Some(val) => Some(parse_timestamp(val)?),
+            None => None,
+        };
+        let not_before = match &self.not_before {
+            Some(val) => Some(parse_timestamp(val)?),
+            None => None,
+        };
+
+        if recover_address(self.to_message().as_bytes(), signature)? != self.address {
+            return Ok(false);
+        }
+        if self.domain != validation.domain || self.nonce != validation.nonce {
+            return Ok(false);
+        }
+
+        let now = validation.time.unwrap_or_else(clock::now);
+        let leeway = chrono::Duration::seconds(validation.leeway.as_secs() as i64);
+        if issued_at > now + leeway {
+            return Ok(false);
+        }
+        if matches!(expiration_time, Some(val) if val + leeway <= now) {
+            return Ok(false);
+        }
+        if matches!(not_before, Some(val) if val > now + leeway) {
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+}
+
+fn next_line<'a>(lines: &mut Peekable<Split<'a, char>>) -> Result<&'a str, Error> {
+    match lines.next() {
+        Some(val) => Ok(val),
+        None => Err(Error::SIWE_MALFORMED),
+    }
+}
+
+fn tagged<'a>(lines: &mut Peekable<Split<'a, char>>, tag: &str) -> Result<&'a str, Error> {
+    match next_line(lines)?.strip_prefix(tag) {
+        Some(val) if !val.is_empty() => Ok(val),
+        _ => Err(Error::SIWE_MALFORMED),
+    }
+}
+
+fn optional<'a>(
+    lines: &mut Peekable<Split<'a, char>>,
+    tag: &str,
+) -> Result<Option<&'a str>, Error> {
+    match lines.peek() {
+        Some(line) if line.starts_with(tag) => tagged(lines, tag).map(Some),
+        _ => Ok(None),
+    }
+}
+
+impl FromStr for SiweMessage {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut lines = value.split('\n').peekable();
+
+        let authority = match next_line(&mut lines)?.strip_suffix(PREAMBLE) {
+            Some(val) => val,
+            None => return Err(Error::SIWE_MALFORMED),
+        };
+        let (scheme, domain) = match authority.split_once("://") {
+            Some((scheme, domain)) => (Some(String::from(scheme)), domain),
+            None => (None, authority),
+        };
+        if domain.is_empty() || domain.contains(char::is_whitespace) {
+            return Err(Error::SIWE_MALFORMED);
+        }
+
+        let address_line = next_line(&mut lines)?;
+        let address = match parse_eip155_address(address_line) {
+            Ok(val) if checksum_address(&val) == address_line => val,
+            _ => return Err(Error::SIWE_MALFORMED),
+        };
+        if !next_line(&mut lines)?.is_empty() {
+            return Err(Error::SIWE_MALFORMED);
+        }
+
+        let statement = match next_line(&mut lines)? {
+            "" => None,
+            val => {
+                if !next_line(&mut lines)?.is_empty() {
+                    return Err(Error::SIWE_MALFORMED);
+                }
+                Some(String::from(val))
+            }
+        };
+
+        let uri = tagged(&mut lines, "URI: ")?;
+        let version = tagged(&mut lines, "Version: ")?;
+        if version != SIWE_VERSION {
+            return Err(Error::SIWE_MALFORMED);
+        }
+        let chain_id = match tagged(&mut lines, "Chain ID: ")?.parse::<u64>() {
+            Ok(val) => val,
+            Err(error) => return Err(Error::SIWE_MALFORMED.with_source(error)),
+        };
+        let nonce = tagged(&mut lines, "Nonce: ")?;
+        if nonce.len() < MIN_NONCE_LENGTH || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(Error::SIWE_MALFORMED);
+        }
+        let issued_at = tagged(&mut lines, "Issued At: ")?;
+        parse_timestamp(issued_at)?;
+        let expiration_time = optional(&mut lines, "Expiration Time: ")?;
+        if let Some(val) = expiration_time {
+            parse_timestamp(val)?;
+        }
+        let not_before = optional(&mut lines, "Not Before: ")?;
+        if let Some(val) = not_before {
+            parse_timestamp(val)?;
+        }
+        let request_id = optional(&mut lines, "Request ID: ")?;
+
+        let mut resources = Vec::new();
+        if lines.peek() == Some(&"Resources:") {
+            lines.next();
+            for line in lines.by_ref() {
+                match line.strip_prefix("- ") {
+                    Some(val) if !val.is_empty() => resources.push(String::from(val)),
+                    _ => return Err(Error::SIWE_MALFORMED),
+                }
+            }
+        }
+        if lines.next().is_some() {
+            return Err(Error::SIWE_MALFORMED);
+        }
+
+        Ok(SiweMessage {
+            scheme,
+            domain: String::from(domain),
+            address,
+            statement,
+            uri: String::from(uri),
+            version: String::from(version),
+            chain_id,
+            nonce: String::from(nonce),
+            issued_at: String::from(issued_at),
+            expiration_time: expiration_time.map(String::from),
+            not_before: not_before.map(String::from),
+            request_id: request_id.map(String::from),
+            resources,
+        })
+    }
+}
+
+impl fmt::Display for SiweMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(scheme) = &self.scheme {
+            write!(f, "{}://", scheme)?;
+        }
+        write!(
+            f,
+            "{}{}\n{}\n\n",
+            self.domain,
+            PREAMBLE,
+            checksum_address(&self.address)
+        )?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{}", statement)?;
+        }
+        write!(
+            f,
+            "\nURI: {}\nVersion: {}\nChain ID: {}\nNonce: {}\nIssued At: {}",
+            self.uri, self.version, self.chain_id, self.nonce, self.issued_at
+        )?;
+        if let Some(val) = &self.expiration_time {
+            write!(f, "\nExpiration Time: {}", val)?;
+        }
+        if let Some(val) = &self.not_before {
+            write!(f, "\nNot Before: {}", val)?;
+        }
+        if let Some(val) = &self.request_id {
+            write!(f, "\nRequest ID: {}", val)?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\nResources:")?;
+            for resource in &self.resources {
+                write!(f, "\n- {}", resource)?;
+            }
+        }
+        Ok(())
+    }
+}
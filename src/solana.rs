@@ -0,0 +1,114 @@
+use std::{fs, path::Path};
+
+use crate::{
+    algorithms::Algorithm,
+    caip::solana_address,
+    crypto::{
+        eddsa::{EDDSASigningKey, EDDSAVerifyingKey},
+        signature::SignatureEncoding,
+    },
+    encoding::{base58_decode, base58_encode},
+    errors::Error,
+    log, signer, verifier,
+};
+
+pub const KEYPAIR_LENGTH: usize = 64;
+
+fn decode_base58(value: &str, error: Error) -> Result<Vec<u8>, Error> {
+    match base58_decode(value) {
+        Ok(val) => Ok(val),
+        Err(source) => Err(error.with_source(source)),
+    }
+}
+
+pub fn public_key_to_base58(key: &EDDSAVerifyingKey) -> String {
+    solana_address(key)
+}
+
+pub fn public_key_from_base58(value: &str) -> Result<EDDSAVerifyingKey, Error> {
+    let mut bytes = decode_base58(value, Error::PUBLIC_KEY_IDENTIFICATION_ERROR)?;
+    EDDSAVerifyingKey::from_bytes(&mut bytes)
+}
+
+pub fn keypair_to_bytes(key: &EDDSASigningKey) -> Vec<u8> {
+    let mut bytes = key.to_bytes();
+    bytes.extend_from_slice(&key.verifying_key().to_bytes());
+    bytes
+}
+
+pub fn keypair_from_bytes(bytes: &[u8]) -> Result<EDDSASigningKey, Error> {
+    if bytes.len() != KEYPAIR_LENGTH {
+        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+    }
+
+    let mut secret = bytes[..32].to_vec();
+    let key = EDDSASigningKey::from_bytes(&mut secret)?;
+    if key.verifying_key().to_bytes() != bytes[32..] {
+        return Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR);
+    }
+
+    Ok(key)
+}
+
+pub fn keypair_to_base58(key: &EDDSASigningKey) -> String {
+    base58_encode(keypair_to_bytes(key))
+}
+
+pub fn keypair_from_base58(value: &str) -> Result<EDDSASigningKey, Error> {
+    keypair_from_bytes(&decode_base58(
+        value,
+        Error::PRIVATE_KEY_IDENTIFICATION_ERROR,
+    )?)
+}
+
+pub fn keypair_to_json(key: &EDDSASigningKey) -> String {
+    serde_json::Value::from(keypair_to_bytes(key)).to_string()
+}
+
+pub fn keypair_from_json(value: &str) -> Result<EDDSASigningKey, Error> {
+    match serde_json::from_str::<Vec<u8>>(value) {
+        Ok(val) => keypair_from_bytes(&val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::PRIVATE_KEY_IDENTIFICATION_ERROR.with_source(error))
+        }
+    }
+}
+
+pub fn read_keypair_file(path: impl AsRef<Path>) -> Result<EDDSASigningKey, Error> {
+    match fs::read_to_string(path) {
+        Ok(val) => keypair_from_json(&val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::FILE_IO_ERROR.with_source(error))
+        }
+    }
+}
+
+pub fn write_keypair_file(key: &EDDSASigningKey, path: impl AsRef<Path>) -> Result<(), Error> {
+    match fs::write(path, keypair_to_json(key)) {
+        Ok(_) => Ok(()),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::FILE_IO_ERROR.with_source(error))
+        }
+    }
+}
+
+pub fn sign_message(message: impl AsRef<[u8]>, key: &EDDSASigningKey) -> Result<String, Error> {
+    signer::sign_encoded(message, key, Algorithm::EdDSA, SignatureEncoding::Base58)
+}
+
+pub fn verify_message(
+    message: impl AsRef<[u8]>,
+    signature: &str,
+    key: &EDDSAVerifyingKey,
+) -> Result<bool, Error> {
+    verifier::verify_encoded(
+        message,
+        signature,
+        SignatureEncoding::Base58,
+        key,
+        Algorithm::EdDSA,
+    )
+}
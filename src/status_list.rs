@@ -0,0 +1,446 @@
+use std::io::{Read, Write};
+#[cfg(feature = "http")]
+use std::{
+    sync::RwLock,
+    time::{Duration, Instant},
+};
+
+use chrono::SecondsFormat;
+use flate2::{read::GzDecoder, write::GzEncoder, Compression};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{clock, errors::Error, log};
+#[cfg(feature = "http")]
+use crate::{http::HttpFetcher, resolver::DidResolver, vc::VcJwt};
+
+pub const MIN_STATUS_LIST_SIZE: usize = 131_072;
+#[cfg(feature = "http")]
+pub const DEFAULT_STATUS_LIST_TTL: Duration = Duration::from_secs(300);
+const MULTIBASE_BASE64URL_PREFIX: char = 'u';
+
+#[derive(Serialize, Deserialize, PartialEq, Eq, Copy, Clone, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum StatusPurpose {
+    Revocation,
+    Suspension,
+    Refresh,
+    Message,
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusListEntry {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "type")]
+    pub entry_type: String,
+    pub status_purpose: StatusPurpose,
+    pub status_list_index: String,
+    pub status_list_credential: String,
+}
+
+impl StatusListEntry {
+    pub fn new(status_list_credential: &str, purpose: StatusPurpose, index: usize) -> Self {
+        StatusListEntry {
+            id: Some(format!("{}#{}", status_list_credential, index)),
+            entry_type: String::from("BitstringStatusListEntry"),
+            status_purpose: purpose,
+            status_list_index: index.to_string(),
+            status_list_credential: String::from(status_list_credential),
+        }
+    }
+
+    pub fn index(&self) -> Result<usize, Error> {
+        match self.status_list_index.parse() {
+            Ok(val) => Ok(val),
+            Err(_) => Err(Error::STATUS_LIST_INVALID_ENTRY),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct BitstringStatusList {
+    bits: Vec<u8>,
+}
+
+impl BitstringStatusList {
+    pub fn new(size: usize) -> Result<Self, Error> {
+        if size < MIN_STATUS_LIST_SIZE || !size.is_multiple_of(8) {
+            return Err(Error::STATUS_LIST_INVALID_SIZE);
+        }
+
+        Ok(BitstringStatusList {
+            bits: vec![0; size / 8],
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.len() * 8
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Result<bool, Error> {
+        match self.bits.get(index / 8) {
+            Some(byte) => Ok(byte & (0x80 >> (index % 8)) != 0),
+            None => Err(Error::STATUS_LIST_INDEX_OUT_OF_RANGE),
+        }
+    }
+
+    pub fn set(&mut self, index: usize, status: bool) -> Result<(), Error> {
+        let byte = match self.bits.get_mut(index / 8) {
+            Some(val) => val,
+            None => return Err(Error::STATUS_LIST_INDEX_OUT_OF_RANGE),
+        };
+
+        match status {
+            true => *byte |= 0x80 >> (index % 8),
+            false => *byte &= !(0x80 >> (index % 8)),
+        }
+        Ok(())
+    }
+
+    pub fn encode(&self) -> Result<String, Error> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::best());
+        let compressed = match encoder.write_all(&self.bits).and_then(|_| encoder.finish()) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ENCODING_ERROR.with_source(error));
+            }
+        };
+
+        Ok(format!(
+            "{}{}",
+            MULTIBASE_BASE64URL_PREFIX,
+            base64_url::encode(&compressed)
+        ))
+    }
+
+    pub fn decode(encoded_list: &str) -> Result<Self, Error> {
+        let encoded = match encoded_list.strip_prefix(MULTIBASE_BASE64URL_PREFIX) {
+            Some(val) => val,
+            None => encoded_list,
+        };
+
+        let compressed = match base64_url::decode(encoded) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::DECODING_ERROR.with_source(error));
+            }
+        };
+
+        let mut bits = Vec::new();
+        if let Err(error) = GzDecoder::new(compressed.as_slice()).read_to_end(&mut bits) {
+            log::error(error.to_string().as_str());
+            return Err(Error::DECODING_ERROR.with_source(error));
+        }
+
+        if bits.len() * 8 < MIN_STATUS_LIST_SIZE {
+            return Err(Error::STATUS_LIST_INVALID_SIZE);
+        }
+
+        Ok(BitstringStatusList { bits })
+    }
+
+    pub fn to_credential(
+        &self,
+        id: &str,
+        issuer: &str,
+        purpose: StatusPurpose,
+    ) -> Result<Value, Error> {
+        Ok(json!({
+            "@context": ["https://www.w3.org/ns/credentials/v2"],
+            "id": id,
+            "type": ["VerifiableCredential", "BitstringStatusListCredential"],
+            "issuer": issuer,
+            "validFrom": clock::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            "credentialSubject": {
+                "id": format!("{}#list", id),
+                "type": "BitstringStatusList",
+                "statusPurpose": purpose,
+                "encodedList": self.encode()?,
+            }
+        }))
+    }
+}
+
+fn status_entries(credential: &Value) -> Result<Vec<StatusListEntry>, Error> {
+    let status = match credential.get("credentialStatus") {
+        Some(Value::Array(val)) => val.clone(),
+        Some(val) => vec![val.clone()],
+        None => return Err(Error::STATUS_LIST_INVALID_ENTRY),
+    };
+
+    let mut entries = Vec::new();
+    for entry in status {
+        if entry.get("type").and_then(|val| val.as_str()) != Some("BitstringStatusListEntry") {
+            continue;
+        }
+
+        match serde_json::from_value(entry) {
+            Ok(val) => entries.push(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::STATUS_LIST_INVALID_ENTRY.with_source(error));
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+fn list_status(entry: &StatusListEntry, status_list_credential: &Value) -> Result<bool, Error> {
+    let subject = match status_list_credential.get("credentialSubject") {
+        Some(val) => val,
+        None => return Err(Error::STATUS_LIST_INVALID_CREDENTIAL),
+    };
+
+    let list_purpose: StatusPurpose = match subject.get("statusPurpose") {
+        Some(val) => match serde_json::from_value(val.clone()) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::STATUS_LIST_INVALID_CREDENTIAL.with_source(error));
+            }
+        },
+        None => return Err(Error::STATUS_LIST_INVALID_CREDENTIAL),
+    };
+
+    if list_purpose != entry.status_purpose {
+        return Err(Error::STATUS_LIST_PURPOSE_MISMATCH);
+    }
+
+    let encoded_list = match subject.get("encodedList").and_then(|val| val.as_str()) {
+        Some(val) => val,
+        None => return Err(Error::STATUS_LIST_INVALID_CREDENTIAL),
+    };
+
+    BitstringStatusList::decode(encoded_list)?.get(entry.index()?)
+}
+
+pub fn check_status(
+    credential: &Value,
+    status_list_credential: &Value,
+    purpose: StatusPurpose,
+) -> Result<bool, Error> {
+    let list_id = match status_list_credential
+        .get("id")
+        .and_then(|val| val.as_str())
+    {
+        Some(val) => val,
+        None => return Err(Error::STATUS_LIST_INVALID_CREDENTIAL),
+    };
+
+    let entry = match status_entries(credential)?
+        .into_iter()
+        .find(|entry| entry.status_purpose == purpose && entry.status_list_credential == list_id)
+    {
+        Some(val) => val,
+        None => return Err(Error::STATUS_LIST_INVALID_ENTRY),
+    };
+
+    list_status(&entry, status_list_credential)
+}
+
+fn issuer_id(credential: &Value) -> Option<&str> {
+    match credential.get("issuer") {
+        Some(Value::String(val)) => Some(val),
+        Some(val) => val.get("id").and_then(Value::as_str),
+        None => None,
+    }
+}
+
+fn issued_list_status(
+    issuer: &str,
+    entry: &StatusListEntry,
+    status_list_credential: &Value,
+) -> Result<bool, Error> {
+    if status_list_credential.get("id").and_then(Value::as_str)
+        != Some(entry.status_list_credential.as_str())
+    {
+        return Err(Error::STATUS_LIST_INVALID_CREDENTIAL);
+    }
+    if issuer_id(status_list_credential) != Some(issuer) {
+        return Err(Error::STATUS_LIST_ISSUER_MISMATCH.with_key(&entry.status_list_credential));
+    }
+
+    list_status(entry, status_list_credential)
+}
+
+pub trait StatusChecker {
+    fn status(&self, issuer: &str, entry: &StatusListEntry) -> Result<bool, Error>;
+}
+
+pub fn check_credential_status(
+    credential: &Value,
+    checker: &impl StatusChecker,
+) -> Result<(), Error> {
+    if credential.get("credentialStatus").is_none() {
+        return Ok(());
+    }
+
+    let issuer = match issuer_id(credential) {
+        Some(val) => val,
+        None => return Err(Error::STATUS_LIST_INVALID_ENTRY),
+    };
+
+    for entry in status_entries(credential)? {
+        let error = match entry.status_purpose {
+            StatusPurpose::Revocation => Error::CREDENTIAL_REVOKED,
+            StatusPurpose::Suspension => Error::CREDENTIAL_SUSPENDED,
+            StatusPurpose::Refresh | StatusPurpose::Message => continue,
+        };
+
+        if checker.status(issuer, &entry)? {
+            return Err(error.with_key(&entry.status_list_credential));
+        }
+    }
+
+    Ok(())
+}
+
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct InMemoryStatusChecker {
+    lists: Vec<Value>,
+}
+
+impl InMemoryStatusChecker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_list(mut self, status_list_credential: Value) -> Self {
+        self.lists.push(status_list_credential);
+        self
+    }
+}
+
+impl StatusChecker for InMemoryStatusChecker {
+    fn status(&self, issuer: &str, entry: &StatusListEntry) -> Result<bool, Error> {
+        let list = match self.lists.iter().find(|list| {
+            list.get("id").and_then(Value::as_str) == Some(entry.status_list_credential.as_str())
+        }) {
+            Some(val) => val,
+            None => {
+                return Err(Error::STATUS_LIST_NOT_FOUND.with_key(&entry.status_list_credential))
+            }
+        };
+
+        issued_list_status(issuer, entry, list)
+    }
+}
+
+#[cfg(feature = "http")]
+struct CachedList {
+    url: String,
+    credential: Value,
+    expires_at: Instant,
+}
+
+#[cfg(feature = "http")]
+pub struct HttpStatusChecker<F: HttpFetcher, R: DidResolver> {
+    fetcher: F,
+    resolver: R,
+    allow_unsecured: bool,
+    default_ttl: Duration,
+    cached: RwLock<Vec<CachedList>>,
+}
+
+#[cfg(feature = "http")]
+impl<F: HttpFetcher, R: DidResolver> HttpStatusChecker<F, R> {
+    pub fn new(fetcher: F, resolver: R) -> Self {
+        HttpStatusChecker {
+            fetcher,
+            resolver,
+            allow_unsecured: false,
+            default_ttl: DEFAULT_STATUS_LIST_TTL,
+            cached: RwLock::new(Vec::new()),
+        }
+    }
+
+    pub fn allow_unsecured(mut self, allow: bool) -> Self {
+        self.allow_unsecured = allow;
+        self
+    }
+
+    pub fn default_ttl(mut self, ttl: Duration) -> Self {
+        self.default_ttl = ttl;
+        self
+    }
+
+    pub fn fetcher(&self) -> &F {
+        &self.fetcher
+    }
+
+    fn cached(&self, url: &str, now: Instant) -> Option<Value> {
+        let cached = match self.cached.read() {
+            Ok(val) => val,
+            Err(error) => error.into_inner(),
+        };
+
+        cached
+            .iter()
+            .find(|list| list.url == url && now < list.expires_at)
+            .map(|list| list.credential.clone())
+    }
+
+    fn fetch(&self, url: &str, now: Instant) -> Result<Value, Error> {
+        let response = self.fetcher.get(url)?;
+        let body = match String::from_utf8(response.body) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::STATUS_LIST_INVALID_CREDENTIAL.with_source(error));
+            }
+        };
+
+        let credential = match body.trim_start().starts_with('{') {
+            true if self.allow_unsecured => match serde_json::from_str(&body) {
+                Ok(val) => val,
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::STATUS_LIST_INVALID_CREDENTIAL.with_source(error));
+                }
+            },
+            true => return Err(Error::STATUS_LIST_INVALID_CREDENTIAL.with_key(url)),
+            false => {
+                let vc_jwt = VcJwt::from_token(body.trim())?;
+                if !vc_jwt.verify_with_resolver(&self.resolver)? {
+                    return Err(Error::STATUS_LIST_INVALID_CREDENTIAL.with_key(url));
+                }
+                vc_jwt.credential.to_value()?
+            }
+        };
+
+        let mut cached = match self.cached.write() {
+            Ok(val) => val,
+            Err(error) => error.into_inner(),
+        };
+        cached.retain(|list| list.url != url && now < list.expires_at);
+        cached.push(CachedList {
+            url: String::from(url),
+            credential: credential.clone(),
+            expires_at: now + response.max_age.unwrap_or(self.default_ttl),
+        });
+        Ok(credential)
+    }
+}
+
+#[cfg(feature = "http")]
+impl<F: HttpFetcher, R: DidResolver> StatusChecker for HttpStatusChecker<F, R> {
+    fn status(&self, issuer: &str, entry: &StatusListEntry) -> Result<bool, Error> {
+        let now = Instant::now();
+        let url = entry.status_list_credential.as_str();
+        let list = match self.cached(url, now) {
+            Some(val) => val,
+            None => self.fetch(url, now)?,
+        };
+
+        issued_list_status(issuer, entry, &list)
+    }
+}
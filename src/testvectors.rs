@@ -0,0 +1,186 @@
+use crate::{
+    algorithms::Algorithm,
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        SignFromKey, VerifyFromKey,
+    },
+    encoding::multibase_encode,
+    errors::Error,
+    jwk::Jwk,
+    log,
+};
+
+pub const DID_KEY_PREFIX: &str = "did:key:";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct JwsVector {
+    pub name: &'static str,
+    pub source: &'static str,
+    pub alg: Algorithm,
+    pub jwk: &'static str,
+    pub signing_input: &'static str,
+    pub signature: &'static str,
+    pub deterministic: bool,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct DidKeyVector {
+    pub name: &'static str,
+    pub source: &'static str,
+    pub alg: Algorithm,
+    pub seed: &'static str,
+    pub did: &'static str,
+}
+
+#[cfg(feature = "eddsa")]
+const RFC8037_ED25519: JwsVector = JwsVector {
+    name: "rfc8037-a4-ed25519",
+    source: "RFC 8037, Appendix A.4",
+    alg: Algorithm::EdDSA,
+    jwk: r#"{"kty":"OKP","crv":"Ed25519","d":"nWGxne_9WmC6hEr0kuwsxERJxWl7MmkZcDusAxyuf2A","x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#,
+    signing_input: "eyJhbGciOiJFZERTQSJ9.RXhhbXBsZSBvZiBFZDI1NTE5IHNpZ25pbmc",
+    signature:
+        "hgyY0il_MGCjP0JzlnLWG1PPOt7-09PGcvMg3AIbQR6dWbhijcNR4ki4iylGjg5BhVsPt9g7sVvpAr_MuM0KAg",
+    deterministic: true,
+};
+
+const RFC7515_ES256: JwsVector = JwsVector {
+    name: "rfc7515-a3-es256",
+    source: "RFC 7515, Appendix A.3",
+    alg: Algorithm::ES256,
+    jwk: r#"{"kty":"EC","crv":"P-256","x":"f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU","y":"x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0","d":"jpsQnnGQmL-YBIffH1136cspYG6-0iY7X1fCE9-E9LI"}"#,
+    signing_input: "eyJhbGciOiJFUzI1NiJ9.eyJpc3MiOiJqb2UiLA0KICJleHAiOjEzMDA4MTkzODAsDQogImh0dHA6Ly9leGFtcGxlLmNvbS9pc19yb290Ijp0cnVlfQ",
+    signature: "DtEhU3ljbEg8L38VWAfUAqOyKAM6-Xx-F4GawxaepmXFCgfTjDxw5djxLa8ISlSApmWQxfKTUJqPP3-Kg6NU1Q",
+    deterministic: false,
+};
+
+#[cfg(feature = "eddsa")]
+const DID_KEY_ED25519_ZERO_SEED: DidKeyVector = DidKeyVector {
+    name: "did-key-ed25519-zero-seed",
+    source: "did:key Method v0.7, Ed25519 test vectors",
+    alg: Algorithm::EdDSA,
+    seed: "0000000000000000000000000000000000000000000000000000000000000000",
+    did: "did:key:z6MkiTBz1ymuepAQ4HEHYSF1H8quG5GLVVQR3djdX3mDooWp",
+};
+
+pub fn jws_vectors() -> Vec<JwsVector> {
+    vec![
+        #[cfg(feature = "eddsa")]
+        RFC8037_ED25519,
+        RFC7515_ES256,
+    ]
+}
+
+pub fn did_key_vectors() -> Vec<DidKeyVector> {
+    vec![
+        #[cfg(feature = "eddsa")]
+        DID_KEY_ED25519_ZERO_SEED,
+    ]
+}
+
+fn mismatch(name: &str) -> Error {
+    Error::TEST_VECTOR_MISMATCH.with_key(name)
+}
+
+impl JwsVector {
+    fn parse_jwk(&self) -> Result<Jwk, Error> {
+        match serde_json::from_str(self.jwk) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::JWK_PARSING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn signing_key(&self) -> Result<AnySigningKey, Error> {
+        AnySigningKey::from_jwk(&self.parse_jwk()?)
+    }
+
+    pub fn verifying_key(&self) -> Result<AnyVerifyingKey, Error> {
+        Ok(self.signing_key()?.verifying_key())
+    }
+
+    pub fn generate(&self) -> Result<String, Error> {
+        self.signing_key()?
+            .sign_bytes(self.signing_input.as_bytes(), self.alg)
+    }
+
+    pub fn compact(&self) -> String {
+        format!("{}.{}", self.signing_input, self.signature)
+    }
+
+    pub fn verify_signature(&self, signature: &str) -> Result<(), Error> {
+        let key = self.verifying_key()?;
+        match key.verify_bytes(self.signing_input.as_bytes(), signature, self.alg) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(mismatch(self.name)),
+            Err(error) => Err(mismatch(self.name).with_source(error)),
+        }
+    }
+
+    pub fn verify_key(&self, key: impl VerifyFromKey) -> Result<(), Error> {
+        match key.verify_bytes(self.signing_input.as_bytes(), self.signature, self.alg) {
+            Ok(true) => Ok(()),
+            Ok(false) => Err(mismatch(self.name)),
+            Err(error) => Err(mismatch(self.name).with_source(error)),
+        }
+    }
+
+    pub fn check(&self) -> Result<(), Error> {
+        self.verify_signature(self.signature)?;
+
+        let generated = self.generate()?;
+        if self.deterministic && generated != self.signature {
+            return Err(mismatch(self.name));
+        }
+        self.verify_signature(&generated)
+    }
+}
+
+impl DidKeyVector {
+    pub fn signing_key(&self) -> Result<AnySigningKey, Error> {
+        let seed = match hex::decode(self.seed) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::DECODING_ERROR.with_source(error));
+            }
+        };
+
+        AnySigningKey::from_bytes(self.alg, &seed)
+    }
+
+    pub fn generate(&self) -> Result<String, Error> {
+        let key = self.signing_key()?.verifying_key();
+        Ok(format!(
+            "{}{}",
+            DID_KEY_PREFIX,
+            multibase_encode(key.to_multicodec()?)
+        ))
+    }
+
+    pub fn verify_did(&self, did: &str) -> Result<(), Error> {
+        match did == self.did {
+            true => Ok(()),
+            false => Err(mismatch(self.name)),
+        }
+    }
+
+    pub fn check(&self) -> Result<(), Error> {
+        self.verify_did(&self.generate()?)
+    }
+}
+
+pub fn check_all() -> Vec<(&'static str, Result<(), Error>)> {
+    let mut results: Vec<(&'static str, Result<(), Error>)> = jws_vectors()
+        .iter()
+        .map(|vector| (vector.name, vector.check()))
+        .collect();
+    results.extend(
+        did_key_vectors()
+            .iter()
+            .map(|vector| (vector.name, vector.check())),
+    );
+    results
+}
@@ -0,0 +1,566 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use cms::{
+    cert::CertificateChoices,
+    content_info::ContentInfo,
+    signed_data::{SignedData, SignerIdentifier, SignerInfo},
+};
+use der::{
+    asn1::{Any, BitString, ObjectIdentifier, OctetString, Uint},
+    Decode, Encode, Sequence, Tag, Tagged,
+};
+use rand::RngCore;
+use x509_cert::{
+    ext::pkix::{ExtendedKeyUsage, SubjectKeyIdentifier},
+    spki::AlgorithmIdentifierOwned,
+    Certificate,
+};
+
+use crate::{
+    algorithms::{Algorithm, AlgorithmFamily},
+    crypto::{
+        any::AnyVerifyingKey, ecdsa::signature_from_der, x509::validate_chain, VerifyFromKey,
+    },
+    errors::Error,
+    hash::HashAlgorithm,
+    log,
+};
+
+pub const TIMESTAMP_QUERY_CONTENT_TYPE: &str = "application/timestamp-query";
+const ID_SIGNED_DATA: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.7.2");
+const ID_CT_TST_INFO: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.16.1.4");
+const ID_CONTENT_TYPE: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.3");
+const ID_MESSAGE_DIGEST: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.9.4");
+const ID_KP_TIME_STAMPING: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.3.6.1.5.5.7.3.8");
+const ID_SHA256: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.1");
+const ID_SHA384: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.2");
+const ID_SHA512: ObjectIdentifier = ObjectIdentifier::new_unwrap("2.16.840.1.101.3.4.2.3");
+const RSA_ENCRYPTION_OID: ObjectIdentifier = ObjectIdentifier::new_unwrap("1.2.840.113549.1.1.1");
+const STATUS_GRANTED: u8 = 0;
+const STATUS_GRANTED_WITH_MODS: u8 = 1;
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct MessageImprint {
+    hash_algorithm: AlgorithmIdentifierOwned,
+    hashed_message: OctetString,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct TimeStampReq {
+    version: u8,
+    message_imprint: MessageImprint,
+    #[asn1(optional = "true")]
+    req_policy: Option<ObjectIdentifier>,
+    #[asn1(optional = "true")]
+    nonce: Option<Uint>,
+    #[asn1(default = "Default::default")]
+    cert_req: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct PkiStatusInfo {
+    status: u8,
+    #[asn1(optional = "true")]
+    status_string: Option<Vec<String>>,
+    #[asn1(optional = "true")]
+    fail_info: Option<BitString>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct TimeStampResp {
+    status: PkiStatusInfo,
+    #[asn1(optional = "true")]
+    time_stamp_token: Option<ContentInfo>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Sequence)]
+struct TstInfo {
+    version: u8,
+    policy: ObjectIdentifier,
+    message_imprint: MessageImprint,
+    serial_number: Uint,
+    gen_time: Any,
+    #[asn1(optional = "true")]
+    accuracy: Option<Any>,
+    #[asn1(default = "Default::default")]
+    ordering: bool,
+    #[asn1(optional = "true")]
+    nonce: Option<Uint>,
+    #[asn1(context_specific = "0", tag_mode = "EXPLICIT", optional = "true")]
+    tsa: Option<Any>,
+    #[asn1(context_specific = "1", tag_mode = "IMPLICIT", optional = "true")]
+    extensions: Option<Vec<Any>>,
+}
+
+fn decoded<T, E: std::fmt::Display>(result: Result<T, E>) -> Result<T, Error> {
+    match result {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::TIMESTAMP_MALFORMED.with_source(error.to_string()))
+        }
+    }
+}
+
+fn hash_oid(hash: HashAlgorithm) -> Result<ObjectIdentifier, Error> {
+    match hash {
+        HashAlgorithm::Sha256 => Ok(ID_SHA256),
+        HashAlgorithm::Sha384 => Ok(ID_SHA384),
+        HashAlgorithm::Sha512 => Ok(ID_SHA512),
+        _ => Err(Error::TIMESTAMP_MALFORMED),
+    }
+}
+
+fn hash_from_oid(oid: ObjectIdentifier) -> Result<HashAlgorithm, Error> {
+    match oid {
+        ID_SHA256 => Ok(HashAlgorithm::Sha256),
+        ID_SHA384 => Ok(HashAlgorithm::Sha384),
+        ID_SHA512 => Ok(HashAlgorithm::Sha512),
+        _ => Err(Error::TIMESTAMP_MALFORMED),
+    }
+}
+
+fn nonce_bytes(nonce: u64) -> Result<Uint, Error> {
+    decoded(Uint::new(&nonce.to_be_bytes()))
+}
+
+fn parse_gen_time(value: &Any) -> Result<DateTime<Utc>, Error> {
+    if value.tag() != Tag::GeneralizedTime {
+        return Err(Error::TIMESTAMP_MALFORMED);
+    }
+
+    let time = decoded(std::str::from_utf8(value.value()))?;
+    match time
+        .strip_suffix('Z')
+        .map(|val| NaiveDateTime::parse_from_str(val, "%Y%m%d%H%M%S%.f"))
+    {
+        Some(Ok(val)) => Ok(val.and_utc()),
+        Some(Err(error)) => {
+            log::error(error.to_string().as_str());
+            Err(Error::TIMESTAMP_MALFORMED.with_source(error))
+        }
+        None => Err(Error::TIMESTAMP_MALFORMED),
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TimestampRequest {
+    hash: HashAlgorithm,
+    digest: Vec<u8>,
+    nonce: Option<u64>,
+    policy: Option<String>,
+    cert_req: bool,
+}
+
+impl TimestampRequest {
+    pub fn new(data: impl AsRef<[u8]>) -> Self {
+        TimestampRequest {
+            hash: HashAlgorithm::Sha256,
+            digest: HashAlgorithm::Sha256.digest(data),
+            nonce: Some(rand::thread_rng().next_u64()),
+            policy: None,
+            cert_req: true,
+        }
+    }
+
+    pub fn from_digest(hash: HashAlgorithm, digest: &[u8]) -> Result<Self, Error> {
+        hash_oid(hash)?;
+        if digest.len() != hash.output_length() {
+            return Err(Error::TIMESTAMP_MALFORMED);
+        }
+
+        Ok(TimestampRequest {
+            hash,
+            digest: digest.to_vec(),
+            ..Self::new([])
+        })
+    }
+
+    pub fn nonce(mut self, nonce: Option<u64>) -> Self {
+        self.nonce = nonce;
+        self
+    }
+
+    pub fn policy(mut self, policy: &str) -> Self {
+        self.policy = Some(String::from(policy));
+        self
+    }
+
+    pub fn cert_req(mut self, cert_req: bool) -> Self {
+        self.cert_req = cert_req;
+        self
+    }
+
+    pub fn hash_algorithm(&self) -> HashAlgorithm {
+        self.hash
+    }
+
+    pub fn digest(&self) -> &[u8] {
+        &self.digest
+    }
+
+    pub fn to_der(&self) -> Result<Vec<u8>, Error> {
+        let request = TimeStampReq {
+            version: 1,
+            message_imprint: MessageImprint {
+                hash_algorithm: AlgorithmIdentifierOwned {
+                    oid: hash_oid(self.hash)?,
+                    parameters: None,
+                },
+                hashed_message: decoded(OctetString::new(self.digest.clone()))?,
+            },
+            req_policy: match &self.policy {
+                Some(val) => Some(decoded(ObjectIdentifier::new(val))?),
+                None => None,
+            },
+            nonce: match self.nonce {
+                Some(val) => Some(nonce_bytes(val)?),
+                None => None,
+            },
+            cert_req: self.cert_req,
+        };
+
+        decoded(request.to_der())
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TimestampInfo {
+    pub policy: String,
+    pub hash: HashAlgorithm,
+    pub digest: Vec<u8>,
+    pub serial_number: Vec<u8>,
+    pub gen_time: DateTime<Utc>,
+    pub nonce: Option<Vec<u8>>,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TimestampToken {
+    der: Vec<u8>,
+    info: TimestampInfo,
+    signed_data: SignedData,
+    content: Vec<u8>,
+}
+
+impl TimestampToken {
+    pub fn from_der(der: &[u8]) -> Result<Self, Error> {
+        let content_info = decoded(ContentInfo::from_der(der))?;
+        if content_info.content_type != ID_SIGNED_DATA {
+            return Err(Error::TIMESTAMP_MALFORMED);
+        }
+
+        let signed_data: SignedData = decoded(content_info.content.decode_as())?;
+        let encap = &signed_data.encap_content_info;
+        let content = match (&encap.econtent, encap.econtent_type == ID_CT_TST_INFO) {
+            (Some(val), true) => decoded(val.decode_as::<OctetString>())?.into_bytes(),
+            _ => return Err(Error::TIMESTAMP_MALFORMED),
+        };
+
+        let tst_info = decoded(TstInfo::from_der(&content))?;
+        let info = TimestampInfo {
+            policy: tst_info.policy.to_string(),
+            hash: hash_from_oid(tst_info.message_imprint.hash_algorithm.oid)?,
+            digest: tst_info.message_imprint.hashed_message.as_bytes().to_vec(),
+            serial_number: tst_info.serial_number.as_bytes().to_vec(),
+            gen_time: parse_gen_time(&tst_info.gen_time)?,
+            nonce: tst_info.nonce.map(|val| val.as_bytes().to_vec()),
+        };
+
+        Ok(TimestampToken {
+            der: der.to_vec(),
+            info,
+            signed_data,
+            content,
+        })
+    }
+
+    pub fn from_response(response: &[u8], request: &TimestampRequest) -> Result<Self, Error> {
+        let response = decoded(TimeStampResp::from_der(response))?;
+        let token = match (response.status.status, response.time_stamp_token) {
+            (STATUS_GRANTED | STATUS_GRANTED_WITH_MODS, Some(val)) => val,
+            (status, _) => {
+                let reason = response
+                    .status
+                    .status_string
+                    .map(|val| val.join("; "))
+                    .unwrap_or_else(|| format!("status {}", status));
+                log::error(reason.as_str());
+                return Err(Error::TIMESTAMP_REJECTED.with_source(reason));
+            }
+        };
+
+        let token = Self::from_der(&decoded(token.to_der())?)?;
+        let nonce = match request.nonce {
+            Some(val) => Some(nonce_bytes(val)?.as_bytes().to_vec()),
+            None => None,
+        };
+        if token.info.hash != request.hash
+            || token.info.digest != request.digest
+            || token.info.nonce != nonce
+            || request
+                .policy
+                .as_ref()
+                .is_some_and(|val| *val != token.info.policy)
+        {
+            return Err(Error::TIMESTAMP_MISMATCH);
+        }
+
+        Ok(token)
+    }
+
+    pub fn to_der(&self) -> &[u8] {
+        &self.der
+    }
+
+    pub fn info(&self) -> &TimestampInfo {
+        &self.info
+    }
+
+    pub fn gen_time(&self) -> DateTime<Utc> {
+        self.info.gen_time
+    }
+
+    pub fn verify(
+        &self,
+        data: impl AsRef<[u8]>,
+        trust_anchors: &[Vec<u8>],
+    ) -> Result<DateTime<Utc>, Error> {
+        self.verify_digest(&self.info.hash.digest(data), trust_anchors)
+    }
+
+    pub fn verify_digest(
+        &self,
+        digest: &[u8],
+        trust_anchors: &[Vec<u8>],
+    ) -> Result<DateTime<Utc>, Error> {
+        if digest != self.info.digest {
+            return Err(Error::TIMESTAMP_MISMATCH);
+        }
+
+        let signer = match self.signed_data.signer_infos.0.as_slice() {
+            [val] => val,
+            _ => return Err(Error::TIMESTAMP_MALFORMED),
+        };
+
+        let chain = self.signer_chain(signer)?;
+        let leaf = decoded(Certificate::from_der(&chain[0]))?;
+        match leaf.tbs_certificate.get::<ExtendedKeyUsage>() {
+            Ok(Some((_, val))) if val.0.contains(&ID_KP_TIME_STAMPING) => {}
+            _ => return Err(Error::X509_CHAIN_INVALID),
+        }
+        let key = validate_chain(&chain, trust_anchors, self.info.gen_time)?;
+
+        self.verify_signer(signer, key)?;
+        Ok(self.info.gen_time)
+    }
+
+    fn certificates(&self) -> Vec<&Certificate> {
+        match &self.signed_data.certificates {
+            Some(val) => val
+                .0
+                .iter()
+                .filter_map(|choice| match choice {
+                    CertificateChoices::Certificate(val) => Some(val),
+                    _ => None,
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn signer_chain(&self, signer: &SignerInfo) -> Result<Vec<Vec<u8>>, Error> {
+        let certificates = self.certificates();
+        let leaf = certificates.iter().find(|certificate| match &signer.sid {
+            SignerIdentifier::IssuerAndSerialNumber(val) => {
+                certificate.tbs_certificate.issuer == val.issuer
+                    && certificate.tbs_certificate.serial_number == val.serial_number
+            }
+            SignerIdentifier::SubjectKeyIdentifier(val) => matches!(
+                certificate.tbs_certificate.get::<SubjectKeyIdentifier>(),
+                Ok(Some((_, key_id))) if key_id == *val
+            ),
+        });
+
+        let mut current = match leaf {
+            Some(val) => *val,
+            None => return Err(Error::X509_CHAIN_INVALID),
+        };
+        let mut chain = vec![decoded(current.to_der())?];
+        while chain.len() <= certificates.len() {
+            let tbs = &current.tbs_certificate;
+            if tbs.issuer == tbs.subject {
+                break;
+            }
+
+            match certificates
+                .iter()
+                .find(|val| val.tbs_certificate.subject == tbs.issuer)
+            {
+                Some(val) => current = val,
+                None => break,
+            }
+            chain.push(decoded(current.to_der())?);
+        }
+
+        Ok(chain)
+    }
+
+    fn verify_signer(&self, signer: &SignerInfo, key: AnyVerifyingKey) -> Result<(), Error> {
+        let attributes = match &signer.signed_attrs {
+            Some(val) => val,
+            None => return Err(Error::TIMESTAMP_MALFORMED),
+        };
+
+        let hash = hash_from_oid(signer.digest_alg.oid)?;
+        let attribute = |oid: ObjectIdentifier| match attributes
+            .iter()
+            .find(|val| val.oid == oid)
+            .map(|val| val.values.as_slice())
+        {
+            Some([val]) => Ok(val),
+            _ => Err(Error::TIMESTAMP_MALFORMED),
+        };
+
+        if decoded(attribute(ID_CONTENT_TYPE)?.decode_as::<ObjectIdentifier>())? != ID_CT_TST_INFO {
+            return Err(Error::TIMESTAMP_MALFORMED);
+        }
+
+        let message_digest = decoded(attribute(ID_MESSAGE_DIGEST)?.decode_as::<OctetString>())?;
+        if message_digest.as_bytes() != hash.digest(&self.content).as_slice() {
+            return Err(Error::TIMESTAMP_INVALID_SIGNATURE);
+        }
+
+        let alg = match key.algorithm().get_family() {
+            AlgorithmFamily::EC if HashAlgorithm::for_algorithm(key.algorithm()) == hash => {
+                key.algorithm()
+            }
+            AlgorithmFamily::RSA => match (signer.signature_algorithm.oid, hash) {
+                (RSA_ENCRYPTION_OID, HashAlgorithm::Sha256) => Algorithm::RS256,
+                (RSA_ENCRYPTION_OID, HashAlgorithm::Sha384) => Algorithm::RS384,
+                (RSA_ENCRYPTION_OID, HashAlgorithm::Sha512) => Algorithm::RS512,
+                _ => return Err(Error::TIMESTAMP_INVALID_SIGNATURE),
+            },
+            AlgorithmFamily::OKP => Algorithm::EdDSA,
+            _ => return Err(Error::TIMESTAMP_INVALID_SIGNATURE),
+        };
+
+        let signature = signer.signature.as_bytes();
+        let signature = match alg.get_family() {
+            AlgorithmFamily::EC => signature_from_der(signature, alg)?,
+            _ => base64_url::encode(signature),
+        };
+
+        let signed_attributes = decoded(attributes.to_der())?;
+        match key
+            .with_algorithm(alg)
+            .verify_bytes(&signed_attributes, &signature, alg)
+        {
+            Ok(true) => Ok(()),
+            _ => Err(Error::TIMESTAMP_INVALID_SIGNATURE),
+        }
+    }
+}
+
+pub trait TsaTransport: Send + Sync {
+    fn post(&self, url: &str, request: &[u8]) -> Result<Vec<u8>, Error>;
+}
+
+#[cfg(feature = "http")]
+#[derive(Clone, Debug)]
+pub struct UreqTsaTransport {
+    agent: ureq::Agent,
+}
+
+#[cfg(feature = "http")]
+impl Default for UreqTsaTransport {
+    fn default() -> Self {
+        UreqTsaTransport {
+            agent: ureq::AgentBuilder::new()
+                .timeout(std::time::Duration::from_secs(10))
+                .build(),
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+impl UreqTsaTransport {
+    pub fn new(agent: ureq::Agent) -> Self {
+        UreqTsaTransport { agent }
+    }
+}
+
+#[cfg(feature = "http")]
+impl TsaTransport for UreqTsaTransport {
+    fn post(&self, url: &str, request: &[u8]) -> Result<Vec<u8>, Error> {
+        let response = match self
+            .agent
+            .post(url)
+            .set("Content-Type", TIMESTAMP_QUERY_CONTENT_TYPE)
+            .send_bytes(request)
+        {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::HTTP_REQUEST_FAILED.with_source(error.to_string()));
+            }
+        };
+
+        let mut body = Vec::new();
+        match std::io::Read::read_to_end(&mut response.into_reader(), &mut body) {
+            Ok(_) => Ok(body),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::HTTP_REQUEST_FAILED.with_source(error))
+            }
+        }
+    }
+}
+
+pub struct TsaClient<T: TsaTransport> {
+    url: String,
+    transport: T,
+    policy: Option<String>,
+}
+
+#[cfg(feature = "http")]
+impl TsaClient<UreqTsaTransport> {
+    pub fn new(url: &str) -> Self {
+        Self::with_transport(url, UreqTsaTransport::default())
+    }
+}
+
+impl<T: TsaTransport> TsaClient<T> {
+    pub fn with_transport(url: &str, transport: T) -> Self {
+        TsaClient {
+            url: String::from(url),
+            transport,
+            policy: None,
+        }
+    }
+
+    pub fn policy(mut self, policy: &str) -> Self {
+        self.policy = Some(String::from(policy));
+        self
+    }
+
+    pub fn timestamp(&self, data: impl AsRef<[u8]>) -> Result<TimestampToken, Error> {
+        self.request(TimestampRequest::new(data))
+    }
+
+    pub fn timestamp_digest(
+        &self,
+        hash: HashAlgorithm,
+        digest: &[u8],
+    ) -> Result<TimestampToken, Error> {
+        self.request(TimestampRequest::from_digest(hash, digest)?)
+    }
+
+    pub fn request(&self, request: TimestampRequest) -> Result<TimestampToken, Error> {
+        let request = match &self.policy {
+            Some(val) => request.policy(val),
+            None => request,
+        };
+
+        let response = self.transport.post(&self.url, &request.to_der()?)?;
+        TimestampToken::from_response(&response, &request)
+    }
+}
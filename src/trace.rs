@@ -0,0 +1,115 @@
+use std::time::Duration;
+
+use crate::{
+    errors::Error,
+    jwe::Jwe,
+    metrics::{self, Operation, OperationMetrics},
+};
+
+pub(crate) trait Outcome {
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    fn outcome(&self) -> &'static str {
+        "ok"
+    }
+
+    fn succeeded(&self) -> bool {
+        true
+    }
+}
+
+impl Outcome for bool {
+    fn outcome(&self) -> &'static str {
+        match self {
+            true => "valid",
+            false => "invalid",
+        }
+    }
+
+    fn succeeded(&self) -> bool {
+        *self
+    }
+}
+
+impl Outcome for String {}
+
+impl<T> Outcome for Vec<T> {}
+
+impl Outcome for Jwe {}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn timed<T>(call: impl FnOnce() -> T) -> (T, Option<Duration>) {
+    let start = std::time::Instant::now();
+    let result = call();
+    (result, Some(start.elapsed()))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub(crate) fn timed<T>(call: impl FnOnce() -> T) -> (T, Option<Duration>) {
+    (call(), None)
+}
+
+fn measured<T: Outcome>(
+    operation: Operation,
+    alg: Option<&str>,
+    call: impl FnOnce() -> Result<T, Error>,
+) -> Result<T, Error> {
+    let (result, latency) = timed(call);
+
+    #[cfg(feature = "tracing")]
+    {
+        let duration_us = latency.map(|val| val.as_micros() as u64);
+        match &result {
+            Ok(val) => tracing::debug!(
+                outcome = val.outcome(),
+                duration_us,
+                "{} completed",
+                operation.to_str()
+            ),
+            Err(error) => tracing::warn!(
+                outcome = "error",
+                error = %error,
+                duration_us,
+                "{} failed",
+                operation.to_str()
+            ),
+        }
+    }
+
+    metrics::record(&OperationMetrics {
+        operation,
+        algorithm: alg,
+        latency,
+        success: matches!(&result, Ok(val) if val.succeeded()),
+    });
+
+    result
+}
+
+#[cfg(feature = "tracing")]
+pub(crate) fn traced<T: Outcome>(
+    operation: Operation,
+    alg: Option<&str>,
+    key: impl FnOnce() -> Option<String>,
+    call: impl FnOnce() -> Result<T, Error>,
+) -> Result<T, Error> {
+    let key = key();
+    let span = tracing::debug_span!(
+        "did_crypto",
+        operation = operation.to_str(),
+        alg,
+        key = key.as_deref()
+    );
+    let _guard = span.enter();
+
+    measured(operation, alg, call)
+}
+
+#[cfg(not(feature = "tracing"))]
+pub(crate) fn traced<T: Outcome>(
+    operation: Operation,
+    alg: Option<&str>,
+    _key: impl FnOnce() -> Option<String>,
+    call: impl FnOnce() -> Result<T, Error>,
+) -> Result<T, Error> {
+    measured(operation, alg, call)
+}
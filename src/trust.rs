@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+
+use crate::{algorithms::Algorithm, data_integrity::ProofOptions, errors::Error};
+
+const WILDCARD: char = '*';
+
+fn matches_pattern(pattern: &str, value: &str) -> bool {
+    let mut parts = pattern.split(WILDCARD);
+    let first = parts.next().unwrap_or_default();
+    let mut rest = match value.strip_prefix(first) {
+        Some(val) => val,
+        None => return false,
+    };
+
+    let parts: Vec<&str> = parts.collect();
+    match parts.split_last() {
+        None => rest.is_empty(),
+        Some((last, middle)) => {
+            for part in middle {
+                match rest.find(part) {
+                    Some(index) => rest = &rest[index + part.len()..],
+                    None => return false,
+                }
+            }
+            rest.ends_with(last)
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct TrustPolicy {
+    issuers: Vec<String>,
+    algorithms: Vec<Algorithm>,
+    cryptosuites: Vec<String>,
+    max_age: Option<Duration>,
+}
+
+impl TrustPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn trust_issuer(mut self, pattern: &str) -> Self {
+        self.issuers.push(String::from(pattern));
+        self
+    }
+
+    pub fn require_algorithm(mut self, alg: Algorithm) -> Self {
+        self.algorithms.push(alg);
+        self
+    }
+
+    pub fn require_cryptosuite(mut self, cryptosuite: &str) -> Self {
+        self.cryptosuites.push(String::from(cryptosuite));
+        self
+    }
+
+    pub fn max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    pub fn is_trusted_issuer(&self, issuer: &str) -> bool {
+        self.issuers.is_empty()
+            || self
+                .issuers
+                .iter()
+                .any(|pattern| matches_pattern(pattern, issuer))
+    }
+
+    pub fn check_issuer(&self, issuer: &str) -> Result<(), Error> {
+        match self.is_trusted_issuer(issuer) {
+            true => Ok(()),
+            false => Err(Error::TRUST_POLICY_ISSUER_NOT_TRUSTED.with_key(issuer)),
+        }
+    }
+
+    pub fn check_algorithm(&self, alg: Algorithm) -> Result<(), Error> {
+        match self.algorithms.is_empty() || self.algorithms.contains(&alg) {
+            true => Ok(()),
+            false => Err(Error::TRUST_POLICY_ALGORITHM_NOT_ALLOWED.with_algorithm(alg)),
+        }
+    }
+
+    pub fn check_cryptosuite(&self, cryptosuite: &str) -> Result<(), Error> {
+        match self.cryptosuites.is_empty() || self.cryptosuites.iter().any(|val| val == cryptosuite)
+        {
+            true => Ok(()),
+            false => Err(Error::TRUST_POLICY_ALGORITHM_NOT_ALLOWED.with_key(cryptosuite)),
+        }
+    }
+
+    pub fn check_age(
+        &self,
+        issued_at: Option<DateTime<Utc>>,
+        at: DateTime<Utc>,
+    ) -> Result<(), Error> {
+        let max_age = match self.max_age {
+            Some(val) => val,
+            None => return Ok(()),
+        };
+
+        let too_old = match (issued_at, TimeDelta::from_std(max_age)) {
+            (Some(issued_at), Ok(max_age)) => at - issued_at > max_age,
+            (Some(_), Err(_)) => false,
+            (None, _) => true,
+        };
+        match too_old {
+            true => Err(Error::TRUST_POLICY_CREDENTIAL_TOO_OLD),
+            false => Ok(()),
+        }
+    }
+
+    pub fn check(
+        &self,
+        issuer: &str,
+        alg: Algorithm,
+        issued_at: Option<DateTime<Utc>>,
+        at: DateTime<Utc>,
+    ) -> Result<(), Error> {
+        self.check_issuer(issuer)?;
+        self.check_algorithm(alg)?;
+        self.check_age(issued_at, at)
+    }
+
+    pub fn check_proof(
+        &self,
+        issuer: &str,
+        options: &ProofOptions,
+        at: DateTime<Utc>,
+    ) -> Result<(), Error> {
+        self.check_issuer(issuer)?;
+        self.check_cryptosuite(&options.cryptosuite)?;
+        self.check_age(options.created, at)
+    }
+}
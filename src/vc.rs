@@ -0,0 +1,699 @@
+use chrono::{DateTime, Utc};
+use coset::{cbor::Value as CborValue, ContentType, Header, HeaderBuilder, Label};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{
+    algorithms::{Algorithm, AlgorithmFamily},
+    clock,
+    cose::{algorithm_to_cose, CoseSign1},
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        VerifyFromKey,
+    },
+    encoding::{self, JsonFormat},
+    errors::Error,
+    jwt::{check_key_algorithm, reject_alg_none},
+    log,
+    resolver::{did_from_url, resolve_keys, DidResolver, ASSERTION_METHOD},
+    signer::sign,
+    status_list::{check_credential_status, StatusChecker},
+    trust::TrustPolicy,
+    verifier::verify,
+};
+
+pub const VC_CONTEXT_V2: &str = "https://www.w3.org/ns/credentials/v2";
+pub const VERIFIABLE_CREDENTIAL_TYPE: &str = "VerifiableCredential";
+pub const VC_JWT_TYP: &str = "vc+jwt";
+pub const VC_CTY: &str = "vc";
+pub const VC_COSE_TYP: &str = "application/vc+cose";
+pub const VC_COSE_CTY: &str = "application/vc";
+pub const JSON_SCHEMA_TYPE: &str = "JsonSchema";
+
+const COSE_TYP_LABEL: i64 = 16;
+
+const REGISTERED_CLAIMS: [&str; 6] = ["iss", "jti", "sub", "iat", "nbf", "exp"];
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(untagged)]
+pub enum Issuer {
+    Id(String),
+    Object {
+        id: String,
+        #[serde(flatten)]
+        properties: Map<String, Value>,
+    },
+}
+
+impl Issuer {
+    pub fn id(&self) -> &str {
+        match self {
+            Issuer::Id(id) => id,
+            Issuer::Object { id, .. } => id,
+        }
+    }
+}
+
+pub(crate) fn one_or_many<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(val) => vec![val],
+        OneOrMany::Many(val) => val,
+    })
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Credential {
+    #[serde(rename = "@context")]
+    pub context: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    #[serde(rename = "type", deserialize_with = "one_or_many")]
+    pub types: Vec<String>,
+    pub issuer: Issuer,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_from: Option<DateTime<Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub valid_until: Option<DateTime<Utc>>,
+    pub credential_subject: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credential_status: Option<Value>,
+    #[serde(flatten)]
+    pub properties: Map<String, Value>,
+}
+
+impl Credential {
+    pub fn new(issuer: &str, credential_type: &str, credential_subject: Value) -> Self {
+        Credential {
+            context: vec![Value::from(VC_CONTEXT_V2)],
+            id: None,
+            types: vec![
+                String::from(VERIFIABLE_CREDENTIAL_TYPE),
+                String::from(credential_type),
+            ],
+            issuer: Issuer::Id(String::from(issuer)),
+            valid_from: None,
+            valid_until: None,
+            credential_subject,
+            credential_status: None,
+            properties: Map::new(),
+        }
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = Some(String::from(id));
+        self
+    }
+
+    pub fn with_valid_from(mut self, valid_from: DateTime<Utc>) -> Self {
+        self.valid_from = Some(valid_from);
+        self
+    }
+
+    pub fn with_valid_until(mut self, valid_until: DateTime<Utc>) -> Self {
+        self.valid_until = Some(valid_until);
+        self
+    }
+
+    pub fn with_status(mut self, credential_status: Value) -> Self {
+        self.credential_status = Some(credential_status);
+        self
+    }
+
+    pub fn with_schema(mut self, id: &str, schema_type: &str) -> Self {
+        let schema = serde_json::json!({ "id": id, "type": schema_type });
+        let schemas = match self.properties.remove("credentialSchema") {
+            Some(Value::Array(mut val)) => {
+                val.push(schema);
+                Value::Array(val)
+            }
+            Some(val) => Value::from(vec![val, schema]),
+            None => schema,
+        };
+        self.properties
+            .insert(String::from("credentialSchema"), schemas);
+        self
+    }
+
+    pub fn credential_schemas(&self) -> Result<Vec<CredentialSchema>, Error> {
+        let schemas = match self.properties.get("credentialSchema") {
+            Some(Value::Array(val)) => val.clone(),
+            Some(val) => vec![val.clone()],
+            None => return Ok(Vec::new()),
+        };
+
+        match serde_json::from_value(Value::Array(schemas)) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::VC_MALFORMED.with_source(error))
+            }
+        }
+    }
+
+    pub fn from_value(value: Value) -> Result<Self, Error> {
+        match serde_json::from_value(value) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::VC_MALFORMED.with_source(error))
+            }
+        }
+    }
+
+    pub fn to_value(&self) -> Result<Value, Error> {
+        match serde_json::to_value(self) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENCODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    pub fn to_json(&self) -> Result<String, Error> {
+        self.to_json_with_format(JsonFormat::Canonical)
+    }
+
+    pub fn to_json_with_format(&self, format: JsonFormat) -> Result<String, Error> {
+        encoding::to_json(self, format)
+    }
+
+    pub fn subject_id(&self) -> Option<&str> {
+        self.credential_subject.get("id").and_then(Value::as_str)
+    }
+
+    pub fn is_valid_at(&self, time: DateTime<Utc>) -> bool {
+        self.valid_from.is_none_or(|valid_from| valid_from <= time)
+            && self
+                .valid_until
+                .is_none_or(|valid_until| time < valid_until)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+pub struct CredentialSchema {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub schema_type: String,
+}
+
+pub trait SchemaValidator {
+    fn validate(&self, schema: &CredentialSchema, credential: &Value) -> Result<(), Error>;
+}
+
+impl<F: Fn(&CredentialSchema, &Value) -> Result<(), Error>> SchemaValidator for F {
+    fn validate(&self, schema: &CredentialSchema, credential: &Value) -> Result<(), Error> {
+        self(schema, credential)
+    }
+}
+
+pub fn validate_schemas(
+    credential: &Credential,
+    validator: &impl SchemaValidator,
+) -> Result<(), Error> {
+    let schemas = credential.credential_schemas()?;
+    if schemas.is_empty() {
+        return Ok(());
+    }
+
+    let value = credential.to_value()?;
+    for schema in &schemas {
+        validator.validate(schema, &value)?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "json-schema")]
+#[derive(Default)]
+pub struct JsonSchemaValidator {
+    schemas: Vec<(String, jsonschema::Validator)>,
+}
+
+#[cfg(feature = "json-schema")]
+impl JsonSchemaValidator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn schema(mut self, id: &str, schema: &Value) -> Result<Self, Error> {
+        let validator = match jsonschema::draft202012::new(schema) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::VC_SCHEMA_MALFORMED
+                    .with_key(id)
+                    .with_source(error.to_string()));
+            }
+        };
+
+        self.schemas.retain(|(val, _)| val != id);
+        self.schemas.push((String::from(id), validator));
+        Ok(self)
+    }
+}
+
+#[cfg(feature = "json-schema")]
+impl SchemaValidator for JsonSchemaValidator {
+    fn validate(&self, schema: &CredentialSchema, credential: &Value) -> Result<(), Error> {
+        if schema.schema_type != JSON_SCHEMA_TYPE {
+            return Err(Error::VC_SCHEMA_UNSUPPORTED.with_key(&schema.id));
+        }
+
+        let validator = match self.schemas.iter().find(|(id, _)| *id == schema.id) {
+            Some((_, val)) => val,
+            None => return Err(Error::VC_SCHEMA_NOT_FOUND.with_key(&schema.id)),
+        };
+
+        match validator.validate(credential) {
+            Ok(()) => Ok(()),
+            Err(error) => {
+                let message = format!("{} at {}", error, error.instance_path);
+                log::error(message.as_str());
+                Err(Error::VC_SCHEMA_VALIDATION_FAILED
+                    .with_key(&schema.id)
+                    .with_source(message))
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone)]
+struct VcJwtHeader {
+    typ: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    cty: Option<String>,
+    alg: Algorithm,
+    kid: String,
+}
+
+fn encode_segment(value: &impl Serialize) -> Result<String, Error> {
+    match serde_json::to_string(value) {
+        Ok(val) => Ok(base64_url::encode(val.as_bytes())),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::ENCODING_ERROR.with_source(error))
+        }
+    }
+}
+
+fn decode_segment<T: DeserializeOwned>(segment: &str) -> Result<T, Error> {
+    let decoded = match base64_url::decode(segment) {
+        Ok(val) => val,
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            return Err(Error::VC_MALFORMED.with_source(error));
+        }
+    };
+
+    match serde_json::from_slice(&decoded) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::VC_MALFORMED.with_source(error))
+        }
+    }
+}
+
+fn registered_claims(credential: &Credential, iat: i64) -> Vec<(&'static str, Value)> {
+    let mut claims = vec![
+        ("iss", Value::from(credential.issuer.id())),
+        ("iat", Value::from(iat)),
+    ];
+    if let Some(id) = &credential.id {
+        claims.push(("jti", Value::from(id.as_str())));
+    }
+    if let Some(subject) = credential.subject_id() {
+        claims.push(("sub", Value::from(subject)));
+    }
+    if let Some(valid_from) = credential.valid_from {
+        claims.push(("nbf", Value::from(valid_from.timestamp())));
+    }
+    if let Some(valid_until) = credential.valid_until {
+        claims.push(("exp", Value::from(valid_until.timestamp())));
+    }
+    claims
+}
+
+#[derive(Clone)]
+pub struct VcJwt {
+    pub alg: Algorithm,
+    pub kid: String,
+    pub issued_at: i64,
+    pub credential: Credential,
+    token: String,
+}
+
+impl VcJwt {
+    pub fn sign(credential: Credential, kid: &str, key: &AnySigningKey) -> Result<Self, Error> {
+        let alg = key.algorithm();
+        if alg.get_family() == AlgorithmFamily::HMAC {
+            return Err(Error::ALGORITHM_NOT_ALLOWED.with_algorithm(alg));
+        }
+
+        let issued_at = clock::now().timestamp();
+        let mut payload = match credential.to_value()? {
+            Value::Object(val) => val,
+            _ => return Err(Error::VC_MALFORMED),
+        };
+        for (name, value) in registered_claims(&credential, issued_at) {
+            payload.insert(String::from(name), value);
+        }
+
+        let header = VcJwtHeader {
+            typ: String::from(VC_JWT_TYP),
+            cty: Some(String::from(VC_CTY)),
+            alg,
+            kid: String::from(kid),
+        };
+        let content = format!("{}.{}", encode_segment(&header)?, encode_segment(&payload)?);
+        let signature = sign(&content, key, alg)?;
+
+        Ok(VcJwt {
+            alg,
+            kid: String::from(kid),
+            issued_at,
+            credential,
+            token: format!("{}.{}", content, signature),
+        })
+    }
+
+    pub fn from_token(token: &str) -> Result<Self, Error> {
+        let parts: Vec<&str> = token.split('.').collect();
+        if parts.len() != 3 {
+            return Err(Error::VC_MALFORMED);
+        }
+
+        reject_alg_none(&decode_segment::<Value>(parts[0])?)?;
+        let header: VcJwtHeader = decode_segment(parts[0])?;
+        if header.typ != VC_JWT_TYP {
+            return Err(Error::VC_JWT_INVALID_TYP);
+        }
+        if header.cty.as_deref().is_some_and(|cty| cty != VC_CTY) {
+            return Err(Error::VC_JWT_INVALID_TYP);
+        }
+
+        let mut payload: Map<String, Value> = decode_segment(parts[1])?;
+        let claims: Vec<(&str, Value)> = REGISTERED_CLAIMS
+            .iter()
+            .filter_map(|name| payload.remove(*name).map(|value| (*name, value)))
+            .collect();
+        let credential = Credential::from_value(Value::Object(payload))?;
+
+        let issued_at = match claims.iter().find(|(name, _)| *name == "iat") {
+            Some((_, value)) => match value.as_i64() {
+                Some(val) => val,
+                None => return Err(Error::VC_MALFORMED),
+            },
+            None => return Err(Error::VC_MALFORMED),
+        };
+
+        let expected = registered_claims(&credential, issued_at);
+        if claims.len() != expected.len()
+            || claims
+                .iter()
+                .any(|claim| !expected.contains(&(claim.0, claim.1.clone())))
+        {
+            return Err(Error::VC_JWT_CLAIM_MISMATCH);
+        }
+
+        Ok(VcJwt {
+            alg: header.alg,
+            kid: header.kid,
+            issued_at,
+            credential,
+            token: String::from(token),
+        })
+    }
+
+    pub fn to_token(&self) -> &str {
+        &self.token
+    }
+
+    pub fn verify(&self, issuer_key: impl VerifyFromKey) -> Result<bool, Error> {
+        check_key_algorithm(&issuer_key, self.alg)?;
+
+        let verified = match self.token.rsplit_once('.') {
+            Some((content, signature)) => verify(content, signature, issuer_key, self.alg)?,
+            None => return Err(Error::VC_MALFORMED),
+        };
+
+        Ok(verified && self.credential.is_valid_at(clock::now()))
+    }
+
+    pub fn verify_with_resolver(&self, resolver: &impl DidResolver) -> Result<bool, Error> {
+        verify_with_issuer_keys(&self.credential, &self.kid, self.alg, resolver, |key| {
+            self.verify(key)
+        })
+    }
+
+    pub fn verify_with_schema(
+        &self,
+        issuer_key: impl VerifyFromKey,
+        validator: &impl SchemaValidator,
+    ) -> Result<bool, Error> {
+        if !self.verify(issuer_key)? {
+            return Ok(false);
+        }
+
+        validate_schemas(&self.credential, validator)?;
+        Ok(true)
+    }
+
+    pub fn verify_with_resolver_and_schema(
+        &self,
+        resolver: &impl DidResolver,
+        validator: &impl SchemaValidator,
+    ) -> Result<bool, Error> {
+        if !self.verify_with_resolver(resolver)? {
+            return Ok(false);
+        }
+
+        validate_schemas(&self.credential, validator)?;
+        Ok(true)
+    }
+
+    pub fn check_policy(&self, policy: &TrustPolicy) -> Result<(), Error> {
+        policy.check(
+            self.credential.issuer.id(),
+            self.alg,
+            DateTime::from_timestamp(self.issued_at, 0),
+            clock::now(),
+        )
+    }
+
+    pub fn verify_with_policy(
+        &self,
+        resolver: &impl DidResolver,
+        policy: &TrustPolicy,
+    ) -> Result<bool, Error> {
+        if !self.verify_with_resolver(resolver)? {
+            return Ok(false);
+        }
+
+        self.check_policy(policy)?;
+        Ok(true)
+    }
+
+    pub fn verify_with_status(
+        &self,
+        resolver: &impl DidResolver,
+        checker: &impl StatusChecker,
+    ) -> Result<bool, Error> {
+        if !self.verify_with_resolver(resolver)? {
+            return Ok(false);
+        }
+
+        check_credential_status(&self.credential.to_value()?, checker)?;
+        Ok(true)
+    }
+}
+
+fn verify_with_issuer_keys(
+    credential: &Credential,
+    kid: &str,
+    alg: Algorithm,
+    resolver: &impl DidResolver,
+    verify_key: impl Fn(&AnyVerifyingKey) -> Result<bool, Error>,
+) -> Result<bool, Error> {
+    if did_from_url(kid) != credential.issuer.id() {
+        return Ok(false);
+    }
+
+    for jwk in resolve_keys(resolver, kid, ASSERTION_METHOD)? {
+        if let Ok(key) = AnyVerifyingKey::from_jwk(&jwk) {
+            if key.algorithm() == alg && verify_key(&key)? {
+                return Ok(true);
+            }
+        }
+    }
+
+    Ok(false)
+}
+
+fn header_text(header: &Header, label: i64) -> Option<&str> {
+    header
+        .rest
+        .iter()
+        .find(|(name, _)| *name == Label::Int(label))
+        .and_then(|(_, value)| value.as_text())
+}
+
+#[derive(Clone, Debug)]
+pub struct VcCose {
+    pub alg: Algorithm,
+    pub kid: String,
+    pub credential: Credential,
+    sign1: CoseSign1,
+}
+
+impl VcCose {
+    pub fn sign(credential: Credential, kid: &str, key: &AnySigningKey) -> Result<Self, Error> {
+        let alg = key.algorithm();
+        let payload = match serde_json::to_vec(&credential) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ENCODING_ERROR.with_source(error));
+            }
+        };
+
+        let protected = HeaderBuilder::new()
+            .algorithm(algorithm_to_cose(alg)?)
+            .key_id(kid.as_bytes().to_vec())
+            .content_type(String::from(VC_COSE_CTY))
+            .value(COSE_TYP_LABEL, CborValue::Text(String::from(VC_COSE_TYP)))
+            .build();
+        let sign1 = CoseSign1::sign(protected, Header::default(), &payload, &[], key)?;
+
+        Ok(VcCose {
+            alg,
+            kid: String::from(kid),
+            credential,
+            sign1,
+        })
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let sign1 = CoseSign1::from_bytes(bytes)?;
+        let header = &sign1.0.protected.header;
+        let content_type = match &header.content_type {
+            Some(ContentType::Text(val)) => Some(val.as_str()),
+            _ => None,
+        };
+        if header_text(header, COSE_TYP_LABEL) != Some(VC_COSE_TYP)
+            || content_type.is_some_and(|cty| cty != VC_COSE_CTY)
+        {
+            return Err(Error::VC_COSE_INVALID_TYP);
+        }
+
+        let kid = match String::from_utf8(sign1.key_id().to_vec()) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::VC_MALFORMED.with_source(error));
+            }
+        };
+
+        let credential = match sign1.payload().map(serde_json::from_slice::<Value>) {
+            Some(Ok(val)) => Credential::from_value(val)?,
+            Some(Err(error)) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::VC_MALFORMED.with_source(error));
+            }
+            None => return Err(Error::COSE_MISSING_PAYLOAD),
+        };
+
+        Ok(VcCose {
+            alg: sign1.algorithm()?,
+            kid,
+            credential,
+            sign1,
+        })
+    }
+
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        self.sign1.to_bytes()
+    }
+
+    pub fn verify(&self, issuer_key: impl VerifyFromKey) -> Result<bool, Error> {
+        check_key_algorithm(&issuer_key, self.alg)?;
+        Ok(self.sign1.verify(&[], issuer_key)? && self.credential.is_valid_at(clock::now()))
+    }
+
+    pub fn verify_with_resolver(&self, resolver: &impl DidResolver) -> Result<bool, Error> {
+        verify_with_issuer_keys(&self.credential, &self.kid, self.alg, resolver, |key| {
+            self.verify(key)
+        })
+    }
+
+    pub fn verify_with_schema(
+        &self,
+        issuer_key: impl VerifyFromKey,
+        validator: &impl SchemaValidator,
+    ) -> Result<bool, Error> {
+        if !self.verify(issuer_key)? {
+            return Ok(false);
+        }
+
+        validate_schemas(&self.credential, validator)?;
+        Ok(true)
+    }
+
+    pub fn verify_with_resolver_and_schema(
+        &self,
+        resolver: &impl DidResolver,
+        validator: &impl SchemaValidator,
+    ) -> Result<bool, Error> {
+        if !self.verify_with_resolver(resolver)? {
+            return Ok(false);
+        }
+
+        validate_schemas(&self.credential, validator)?;
+        Ok(true)
+    }
+
+    pub fn check_policy(&self, policy: &TrustPolicy) -> Result<(), Error> {
+        policy.check(
+            self.credential.issuer.id(),
+            self.alg,
+            self.credential.valid_from,
+            clock::now(),
+        )
+    }
+
+    pub fn verify_with_policy(
+        &self,
+        resolver: &impl DidResolver,
+        policy: &TrustPolicy,
+    ) -> Result<bool, Error> {
+        if !self.verify_with_resolver(resolver)? {
+            return Ok(false);
+        }
+
+        self.check_policy(policy)?;
+        Ok(true)
+    }
+
+    pub fn verify_with_status(
+        &self,
+        resolver: &impl DidResolver,
+        checker: &impl StatusChecker,
+    ) -> Result<bool, Error> {
+        if !self.verify_with_resolver(resolver)? {
+            return Ok(false);
+        }
+
+        check_credential_status(&self.credential.to_value()?, checker)?;
+        Ok(true)
+    }
+}
@@ -0,0 +1,226 @@
+use serde_json::{json, Value};
+
+use crate::{
+    algorithms::Algorithm,
+    crypto::{any::AnyVerifyingKey, decode_hex},
+    encoding::{base58_decode, base58_encode, multibase_decode, multibase_encode},
+    errors::Error,
+    jwk::Jwk,
+    keystore::{JSON_WEB_KEY_2020, JWS_2020_CONTEXT},
+    log,
+};
+
+pub const ED25519_VERIFICATION_KEY_2018: &str = "Ed25519VerificationKey2018";
+pub const ED25519_VERIFICATION_KEY_2020: &str = "Ed25519VerificationKey2020";
+pub const ECDSA_SECP256K1_VERIFICATION_KEY_2019: &str = "EcdsaSecp256k1VerificationKey2019";
+pub const MULTIKEY: &str = "Multikey";
+pub const ED25519_2018_CONTEXT: &str = "https://w3id.org/security/suites/ed25519-2018/v1";
+pub const ED25519_2020_CONTEXT: &str = "https://w3id.org/security/suites/ed25519-2020/v1";
+pub const SECP256K1_2019_CONTEXT: &str = "https://w3id.org/security/suites/secp256k1-2019/v1";
+pub const MULTIKEY_CONTEXT: &str = "https://w3id.org/security/multikey/v1";
+pub const PUBLIC_KEY_JWK: &str = "publicKeyJwk";
+pub const PUBLIC_KEY_MULTIBASE: &str = "publicKeyMultibase";
+pub const PUBLIC_KEY_BASE58: &str = "publicKeyBase58";
+pub const PUBLIC_KEY_HEX: &str = "publicKeyHex";
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum VerificationMethodType {
+    JsonWebKey2020,
+    Ed25519VerificationKey2018,
+    Ed25519VerificationKey2020,
+    EcdsaSecp256k1VerificationKey2019,
+    Multikey,
+}
+
+impl VerificationMethodType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            VerificationMethodType::JsonWebKey2020 => JSON_WEB_KEY_2020,
+            VerificationMethodType::Ed25519VerificationKey2018 => ED25519_VERIFICATION_KEY_2018,
+            VerificationMethodType::Ed25519VerificationKey2020 => ED25519_VERIFICATION_KEY_2020,
+            VerificationMethodType::EcdsaSecp256k1VerificationKey2019 => {
+                ECDSA_SECP256K1_VERIFICATION_KEY_2019
+            }
+            VerificationMethodType::Multikey => MULTIKEY,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [
+            VerificationMethodType::JsonWebKey2020,
+            VerificationMethodType::Ed25519VerificationKey2018,
+            VerificationMethodType::Ed25519VerificationKey2020,
+            VerificationMethodType::EcdsaSecp256k1VerificationKey2019,
+            VerificationMethodType::Multikey,
+        ]
+        .into_iter()
+        .find(|method_type| method_type.name() == name)
+    }
+
+    pub fn context(&self) -> &'static str {
+        match self {
+            VerificationMethodType::JsonWebKey2020 => JWS_2020_CONTEXT,
+            VerificationMethodType::Ed25519VerificationKey2018 => ED25519_2018_CONTEXT,
+            VerificationMethodType::Ed25519VerificationKey2020 => ED25519_2020_CONTEXT,
+            VerificationMethodType::EcdsaSecp256k1VerificationKey2019 => SECP256K1_2019_CONTEXT,
+            VerificationMethodType::Multikey => MULTIKEY_CONTEXT,
+        }
+    }
+
+    pub fn supports(&self, key: &AnyVerifyingKey) -> bool {
+        match self {
+            VerificationMethodType::JsonWebKey2020 => key.to_jwk().is_ok(),
+            VerificationMethodType::Ed25519VerificationKey2018
+            | VerificationMethodType::Ed25519VerificationKey2020 => {
+                key.algorithm() == Algorithm::EdDSA
+            }
+            VerificationMethodType::EcdsaSecp256k1VerificationKey2019 => {
+                key.algorithm() == Algorithm::ES256K
+            }
+            VerificationMethodType::Multikey => key.to_multicodec().is_ok(),
+        }
+    }
+
+    pub fn preferred_for(key: &AnyVerifyingKey) -> Self {
+        match key.algorithm() {
+            Algorithm::EdDSA => VerificationMethodType::Ed25519VerificationKey2020,
+            Algorithm::ES256K => VerificationMethodType::EcdsaSecp256k1VerificationKey2019,
+            _ => match key.to_multicodec() {
+                Ok(_) => VerificationMethodType::Multikey,
+                Err(_) => VerificationMethodType::JsonWebKey2020,
+            },
+        }
+    }
+
+    fn raw_algorithm(&self) -> Option<Algorithm> {
+        match self {
+            VerificationMethodType::Ed25519VerificationKey2018
+            | VerificationMethodType::Ed25519VerificationKey2020 => Some(Algorithm::EdDSA),
+            VerificationMethodType::EcdsaSecp256k1VerificationKey2019 => Some(Algorithm::ES256K),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct VerificationMethod {
+    pub id: String,
+    pub method_type: VerificationMethodType,
+    pub controller: String,
+    pub key: AnyVerifyingKey,
+}
+
+fn string_member(method: &Value, name: &str) -> Result<String, Error> {
+    match method.get(name).and_then(Value::as_str) {
+        Some(val) => Ok(String::from(val)),
+        None => Err(Error::VERIFICATION_METHOD_MALFORMED),
+    }
+}
+
+fn raw_key(method_type: VerificationMethodType, bytes: &[u8]) -> Result<AnyVerifyingKey, Error> {
+    match method_type.raw_algorithm() {
+        Some(alg) => AnyVerifyingKey::from_bytes(alg, bytes),
+        None => Err(Error::VERIFICATION_METHOD_UNSUPPORTED),
+    }
+}
+
+pub fn has_key_material(method: &Value) -> bool {
+    [
+        PUBLIC_KEY_JWK,
+        PUBLIC_KEY_MULTIBASE,
+        PUBLIC_KEY_BASE58,
+        PUBLIC_KEY_HEX,
+    ]
+    .iter()
+    .any(|name| method.get(name).is_some())
+}
+
+impl VerificationMethod {
+    pub fn new(
+        id: &str,
+        controller: &str,
+        method_type: VerificationMethodType,
+        key: AnyVerifyingKey,
+    ) -> Result<Self, Error> {
+        if !method_type.supports(&key) {
+            return Err(Error::VERIFICATION_METHOD_UNSUPPORTED.with_algorithm(key.algorithm()));
+        }
+
+        Ok(VerificationMethod {
+            id: String::from(id),
+            method_type,
+            controller: String::from(controller),
+            key,
+        })
+    }
+
+    pub fn from_value(method: &Value) -> Result<Self, Error> {
+        let method_type = string_member(method, "type")?;
+        let method_type = match VerificationMethodType::from_name(&method_type) {
+            Some(val) => val,
+            None => return Err(Error::VERIFICATION_METHOD_UNSUPPORTED),
+        };
+
+        let key = if let Some(val) = method.get(PUBLIC_KEY_JWK) {
+            match serde_json::from_value::<Jwk>(val.clone()) {
+                Ok(val) => AnyVerifyingKey::from_jwk(&val)?,
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::JWK_PARSING_ERROR.with_source(error));
+                }
+            }
+        } else if let Some(val) = method.get(PUBLIC_KEY_MULTIBASE).and_then(Value::as_str) {
+            AnyVerifyingKey::from_multicodec(&multibase_decode(val)?)?
+        } else if let Some(val) = method.get(PUBLIC_KEY_BASE58).and_then(Value::as_str) {
+            raw_key(method_type, &base58_decode(val)?)?
+        } else if let Some(val) = method.get(PUBLIC_KEY_HEX).and_then(Value::as_str) {
+            raw_key(method_type, &decode_hex(val)?)?
+        } else {
+            return Err(Error::VERIFICATION_METHOD_MALFORMED);
+        };
+
+        Self::new(
+            &string_member(method, "id")?,
+            &string_member(method, "controller")?,
+            method_type,
+            key,
+        )
+    }
+
+    pub fn to_value(&self) -> Result<Value, Error> {
+        let (name, material) = match self.method_type {
+            VerificationMethodType::JsonWebKey2020
+            | VerificationMethodType::EcdsaSecp256k1VerificationKey2019 => {
+                (PUBLIC_KEY_JWK, json!(self.key.to_jwk()?))
+            }
+            VerificationMethodType::Ed25519VerificationKey2018 => (
+                PUBLIC_KEY_BASE58,
+                Value::from(base58_encode(self.key.to_bytes()?)),
+            ),
+            VerificationMethodType::Ed25519VerificationKey2020
+            | VerificationMethodType::Multikey => (
+                PUBLIC_KEY_MULTIBASE,
+                Value::from(multibase_encode(self.key.to_multicodec()?)),
+            ),
+        };
+
+        let mut method = json!({
+            "id": self.id,
+            "type": self.method_type.name(),
+            "controller": self.controller,
+        });
+        method[name] = material;
+        Ok(method)
+    }
+
+    pub fn with_type(self, method_type: VerificationMethodType) -> Result<Self, Error> {
+        Self::new(&self.id, &self.controller, method_type, self.key)
+    }
+
+    pub fn to_jwk(&self) -> Result<Jwk, Error> {
+        Ok(Jwk {
+            kid: Some(self.id.clone()),
+            ..self.key.to_jwk()?
+        })
+    }
+}
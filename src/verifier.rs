@@ -1,23 +1,404 @@
+#[cfg(feature = "eddsa")]
+use crate::crypto::eddsa::verify_eddsa;
+#[cfg(feature = "mldsa")]
+use crate::crypto::mldsa::verify_mldsa;
+#[cfg(feature = "rsa")]
+use crate::crypto::rsa::verify_rsa;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+    time::Duration,
+};
+
 use crate::{
-    algorithms::{Algorithm, AlgorithmFamily},
+    algorithms::{Algorithm, AlgorithmFamily, AlgorithmPolicy},
     crypto::{
-        ecdsa::verify_ec, eddsa::verify_eddsa, hmac::verify_hmac, rsa::verify_rsa, VerifyFromKey,
+        any::AnyVerifyingKey,
+        ecdsa::verify_ec,
+        hmac::verify_hmac,
+        parse::parse_verifying_key,
+        signature::{Signature, SignatureEncoding},
+        VerifyFromKey,
     },
-    errors::Error,
+    envelope::{EnvelopeFormat, SignatureEnvelope},
+    errors::{Error, VerifyError},
+    jwk::{Jwk, Jwks},
+    jwt::{check_key_algorithm, reject_alg_none},
+    metrics::Operation,
+    resolver::{decode_token_segment, is_within_validity, DEFAULT_CLOCK_LEEWAY},
+    trace,
 };
 
+pub const DEFAULT_MAX_PREPARED_HEADERS: usize = 1024;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct VerificationOptions {
+    pub report_malformed_input: bool,
+    pub accept_partial_signatures: bool,
+    pub algorithm_policy: AlgorithmPolicy,
+    pub signature_encoding: SignatureEncoding,
+}
+
+impl Default for VerificationOptions {
+    fn default() -> Self {
+        VerificationOptions {
+            report_malformed_input: true,
+            accept_partial_signatures: false,
+            algorithm_policy: AlgorithmPolicy::default(),
+            signature_encoding: SignatureEncoding::Base64Url,
+        }
+    }
+}
+
+impl VerificationOptions {
+    pub fn lenient() -> Self {
+        VerificationOptions {
+            report_malformed_input: false,
+            accept_partial_signatures: false,
+            algorithm_policy: AlgorithmPolicy::default(),
+            signature_encoding: SignatureEncoding::Base64Url,
+        }
+    }
+
+    pub fn report_malformed_input(mut self, report: bool) -> Self {
+        self.report_malformed_input = report;
+        self
+    }
+
+    pub fn accept_partial_signatures(mut self, accept: bool) -> Self {
+        self.accept_partial_signatures = accept;
+        self
+    }
+
+    pub fn algorithm_policy(mut self, policy: AlgorithmPolicy) -> Self {
+        self.algorithm_policy = policy;
+        self
+    }
+
+    pub fn signature_encoding(mut self, encoding: SignatureEncoding) -> Self {
+        self.signature_encoding = encoding;
+        self
+    }
+
+    pub fn apply(&self, result: Result<bool, Error>) -> Result<bool, Error> {
+        match result {
+            Err(error) if !self.report_malformed_input && error.is_malformed_input() => Ok(false),
+            result => result,
+        }
+    }
+
+    pub fn combine(
+        &self,
+        results: impl IntoIterator<Item = Result<bool, Error>>,
+    ) -> Result<bool, Error> {
+        let mut verified = Vec::new();
+        for result in results {
+            verified.push(self.apply(result)?);
+        }
+
+        if verified.is_empty() {
+            return Ok(false);
+        }
+
+        match self.accept_partial_signatures {
+            true => Ok(verified.contains(&true)),
+            false => Ok(!verified.contains(&false)),
+        }
+    }
+}
+
 pub fn verify(
-    message: String,
-    signature: String,
+    message: impl AsRef<[u8]>,
+    signature: impl AsRef<str>,
     key: impl VerifyFromKey,
     alg: Algorithm,
 ) -> Result<bool, Error> {
-    let alg_family = alg.get_family();
-    match alg_family {
-        AlgorithmFamily::HMAC => verify_hmac(message, signature, key, alg),
-        AlgorithmFamily::EC => verify_ec(message, signature, key, alg),
-        AlgorithmFamily::RSA => verify_rsa(message, signature, key, alg),
-        AlgorithmFamily::OKP => verify_eddsa(message, signature, key, alg),
-        _ => return Err(Error::UNKNOWN_ALGORITHM),
+    trace::traced(
+        Operation::Verify,
+        Some(alg.to_str()),
+        || key.key_fingerprint(),
+        || match alg.get_family() {
+            AlgorithmFamily::HMAC => verify_hmac(message, signature, &key, alg),
+            AlgorithmFamily::EC => verify_ec(message, signature, &key, alg),
+            #[cfg(feature = "rsa")]
+            AlgorithmFamily::RSA => verify_rsa(message, signature, &key, alg),
+            #[cfg(feature = "eddsa")]
+            AlgorithmFamily::OKP => verify_eddsa(message, signature, &key, alg),
+            #[cfg(feature = "mldsa")]
+            AlgorithmFamily::AKP => verify_mldsa(message, signature, &key, alg),
+            _ => Err(Error::UNKNOWN_ALGORITHM.with_algorithm(alg)),
+        },
+    )
+}
+
+pub fn verify_signature(
+    message: impl AsRef<[u8]>,
+    signature: &Signature,
+    key: impl VerifyFromKey,
+) -> Result<bool, Error> {
+    verify(
+        message,
+        signature.to_base64url(),
+        key,
+        signature.algorithm(),
+    )
+}
+
+pub fn verify_batch(
+    items: &[(&[u8], &Signature)],
+    key: impl VerifyFromKey,
+) -> Vec<Result<bool, Error>> {
+    key.verify_batch(items)
+}
+
+pub fn verify_batch_multi<K: VerifyFromKey>(
+    items: &[(&[u8], &Signature, K)],
+) -> Vec<Result<bool, Error>> {
+    items
+        .iter()
+        .map(|(content, signature, key)| key.verify_signature(content, signature))
+        .collect()
+}
+
+pub fn verify_encoded(
+    message: impl AsRef<[u8]>,
+    signature: impl AsRef<str>,
+    encoding: SignatureEncoding,
+    key: impl VerifyFromKey,
+    alg: Algorithm,
+) -> Result<bool, Error> {
+    verify_signature(
+        message,
+        &Signature::decode(signature.as_ref(), alg, encoding)?,
+        key,
+    )
+}
+
+pub fn verify_envelope(
+    message: impl AsRef<[u8]>,
+    envelope: &[u8],
+    format: EnvelopeFormat,
+    key: impl VerifyFromKey,
+    alg: Algorithm,
+) -> Result<bool, Error> {
+    SignatureEnvelope::decode(envelope, format)?.verify_with_algorithm(message, key, alg)
+}
+
+pub fn verify_with_options(
+    message: impl AsRef<[u8]>,
+    signature: impl AsRef<str>,
+    key: impl VerifyFromKey,
+    alg: Algorithm,
+    options: &VerificationOptions,
+) -> Result<bool, Error> {
+    options.algorithm_policy.check(alg)?;
+    options.apply(match options.signature_encoding {
+        SignatureEncoding::Base64Url => verify(message, signature, key, alg),
+        encoding => verify_encoded(message, signature, encoding, key, alg),
+    })
+}
+
+pub fn verify_strict(
+    message: impl AsRef<[u8]>,
+    signature: impl AsRef<str>,
+    key: impl VerifyFromKey,
+    alg: Algorithm,
+) -> Result<(), VerifyError> {
+    if let Some(algorithms) = key.verifying_algorithms() {
+        if !algorithms.contains(&alg) {
+            return match algorithms
+                .iter()
+                .any(|val| val.get_family() == alg.get_family())
+            {
+                true => Err(VerifyError::AlgorithmMismatch),
+                false => Err(VerifyError::KeyMismatch),
+            };
+        }
+    }
+
+    match verify(message, signature, key, alg)? {
+        true => Ok(()),
+        false => Err(VerifyError::InvalidSignature),
+    }
+}
+
+#[derive(Clone)]
+struct PreparedHeader {
+    alg: Algorithm,
+    kid: String,
+}
+
+pub struct PreparedVerifier {
+    keys: RwLock<HashMap<String, Arc<AnyVerifyingKey>>>,
+    headers: RwLock<HashMap<String, PreparedHeader>>,
+    options: VerificationOptions,
+    leeway: Duration,
+    max_headers: usize,
+}
+
+impl Default for PreparedVerifier {
+    fn default() -> Self {
+        PreparedVerifier {
+            keys: RwLock::new(HashMap::new()),
+            headers: RwLock::new(HashMap::new()),
+            options: VerificationOptions::default(),
+            leeway: DEFAULT_CLOCK_LEEWAY,
+            max_headers: DEFAULT_MAX_PREPARED_HEADERS,
+        }
+    }
+}
+
+fn read<T>(lock: &RwLock<T>) -> RwLockReadGuard<'_, T> {
+    match lock.read() {
+        Ok(val) => val,
+        Err(error) => error.into_inner(),
+    }
+}
+
+fn write<T>(lock: &RwLock<T>) -> RwLockWriteGuard<'_, T> {
+    match lock.write() {
+        Ok(val) => val,
+        Err(error) => error.into_inner(),
+    }
+}
+
+impl PreparedVerifier {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn options(mut self, options: VerificationOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn leeway(mut self, leeway: Duration) -> Self {
+        self.leeway = leeway;
+        self
+    }
+
+    pub fn max_headers(mut self, max_headers: usize) -> Self {
+        self.max_headers = max_headers;
+        self
+    }
+
+    pub fn add_key(&self, kid: &str, key: AnyVerifyingKey) {
+        write(&self.keys).insert(String::from(kid), Arc::new(key));
+        write(&self.headers).retain(|_, header| header.kid != kid);
+    }
+
+    pub fn add_jwk(&self, kid: &str, jwk: &Jwk) -> Result<(), Error> {
+        self.add_key(kid, AnyVerifyingKey::from_jwk(jwk)?);
+        Ok(())
+    }
+
+    pub fn add_jwks(&self, jwks: &Jwks) -> Result<usize, Error> {
+        let mut added = 0;
+        for jwk in &jwks.keys {
+            if let Some(kid) = &jwk.kid {
+                self.add_jwk(kid, jwk)?;
+                added += 1;
+            }
+        }
+
+        Ok(added)
+    }
+
+    pub fn add_pem(&self, kid: &str, pem: &str) -> Result<(), Error> {
+        self.add_key(kid, parse_verifying_key(pem)?);
+        Ok(())
+    }
+
+    pub fn remove(&self, kid: &str) -> bool {
+        write(&self.headers).retain(|_, header| header.kid != kid);
+        write(&self.keys).remove(kid).is_some()
+    }
+
+    pub fn kids(&self) -> Vec<String> {
+        let mut kids: Vec<String> = read(&self.keys).keys().cloned().collect();
+        kids.sort();
+        kids
+    }
+
+    pub fn key(&self, kid: &str) -> Result<Arc<AnyVerifyingKey>, Error> {
+        match read(&self.keys).get(kid) {
+            Some(val) => Ok(val.clone()),
+            None => Err(Error::KEYSTORE_KEY_NOT_FOUND.with_key(kid)),
+        }
+    }
+
+    fn verify_prepared(
+        &self,
+        kid: &str,
+        message: &[u8],
+        signature: &str,
+        alg: Algorithm,
+    ) -> Result<bool, Error> {
+        self.options.algorithm_policy.check(alg)?;
+        let key = self.key(kid)?;
+        check_key_algorithm(&*key, alg)?;
+        verify(message, signature, &*key, alg)
+    }
+
+    pub fn verify(
+        &self,
+        kid: &str,
+        message: impl AsRef<[u8]>,
+        signature: impl AsRef<str>,
+        alg: Algorithm,
+    ) -> Result<bool, Error> {
+        self.options
+            .apply(self.verify_prepared(kid, message.as_ref(), signature.as_ref(), alg))
+    }
+
+    fn prepare_header(&self, segment: &str) -> Result<(PreparedHeader, bool), Error> {
+        if let Some(header) = read(&self.headers).get(segment) {
+            return Ok((header.clone(), true));
+        }
+
+        let header = decode_token_segment(segment, Error::JWT_HEADER_DESERIALIZING_ERROR)?;
+        reject_alg_none(&header)?;
+        let alg = match header.get("alg").cloned().map(serde_json::from_value) {
+            Some(Ok(val)) => val,
+            _ => return Err(Error::UNKNOWN_ALGORITHM),
+        };
+        let kid = match header.get("kid").and_then(|val| val.as_str()) {
+            Some(val) => String::from(val),
+            None => return Err(Error::KEYSTORE_KEY_NOT_FOUND),
+        };
+
+        Ok((PreparedHeader { alg, kid }, false))
+    }
+
+    fn verify_prepared_token(&self, token: &str) -> Result<bool, Error> {
+        let jws = token.split('~').next().unwrap_or(token);
+        let parts: Vec<&str> = jws.split('.').collect();
+        if parts.len() != 3 || parts[2].is_empty() {
+            return Err(Error::JWT_MALFORMED);
+        }
+
+        let (header, cached) = self.prepare_header(parts[0])?;
+        let content = &jws[..parts[0].len() + parts[1].len() + 1];
+        if !self.verify_prepared(&header.kid, content.as_bytes(), parts[2], header.alg)? {
+            return Ok(false);
+        }
+
+        if !cached {
+            let mut headers = write(&self.headers);
+            if headers.len() < self.max_headers {
+                headers.insert(String::from(parts[0]), header);
+            }
+        }
+
+        let payload = decode_token_segment(parts[1], Error::JWT_PAYLOAD_DESERIALIZING_ERROR)?;
+        is_within_validity(&payload, self.leeway)
+    }
+
+    pub fn verify_token(&self, token: &str) -> Result<bool, Error> {
+        self.options.apply(self.verify_prepared_token(token))
+    }
+
+    pub fn cached_headers(&self) -> usize {
+        read(&self.headers).len()
     }
 }
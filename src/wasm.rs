@@ -0,0 +1,75 @@
+use std::str::FromStr;
+
+use serde_json::Value;
+use wasm_bindgen::prelude::*;
+
+use crate::{
+    algorithms::Algorithm,
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        VerifyFromKey,
+    },
+    jwt::{Header, Payload, JWT},
+    signer,
+};
+
+#[wasm_bindgen(js_name = generateKey)]
+pub fn generate_key(alg: &str) -> Result<Vec<u8>, JsError> {
+    let key = AnySigningKey::generate(Algorithm::from_str(alg)?)?;
+    Ok(key.to_private_bytes()?.to_vec())
+}
+
+#[wasm_bindgen(js_name = publicKey)]
+pub fn public_key(alg: &str, private_key: &[u8]) -> Result<Vec<u8>, JsError> {
+    let key = AnySigningKey::from_bytes(Algorithm::from_str(alg)?, private_key)?;
+    Ok(key.to_public_bytes()?)
+}
+
+#[wasm_bindgen]
+pub fn sign(alg: &str, private_key: &[u8], message: &[u8]) -> Result<String, JsError> {
+    let alg = Algorithm::from_str(alg)?;
+    let key = AnySigningKey::from_bytes(alg, private_key)?;
+    Ok(signer::sign(message, key, alg)?)
+}
+
+#[wasm_bindgen]
+pub fn verify(
+    alg: &str,
+    public_key: &[u8],
+    message: &[u8],
+    signature: &str,
+) -> Result<bool, JsError> {
+    let alg = Algorithm::from_str(alg)?;
+    let key = AnyVerifyingKey::from_bytes(alg, public_key)?;
+    Ok(key.verify_bytes(message, signature, alg)?)
+}
+
+#[wasm_bindgen(js_name = signJwt)]
+pub fn sign_jwt(
+    alg: &str,
+    private_key: &[u8],
+    kid: &str,
+    payload: &str,
+) -> Result<String, JsError> {
+    let alg = Algorithm::from_str(alg)?;
+    let key = AnySigningKey::from_bytes(alg, private_key)?;
+    let mut jwt = JWT {
+        header: Header::new(String::from(kid), alg),
+        payload: Payload(serde_json::from_str::<Value>(payload)?),
+        signature: None,
+    };
+    jwt.sign(&key)?;
+    Ok(jwt.to_token()?)
+}
+
+#[wasm_bindgen(js_name = verifyJwt)]
+pub fn verify_jwt(token: &str, public_key: &[u8]) -> Result<bool, JsError> {
+    let jwt = JWT::from_token(token)?;
+    let key = AnyVerifyingKey::from_bytes(jwt.header.alg, public_key)?;
+    Ok(jwt.validate(key)?)
+}
+
+#[wasm_bindgen(js_name = decodeJwtPayload)]
+pub fn decode_jwt_payload(token: &str) -> Result<String, JsError> {
+    Ok(JWT::from_token(token)?.payload.0.to_string())
+}
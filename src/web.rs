@@ -0,0 +1,277 @@
+use serde_json::{json, Value};
+
+use crate::{
+    clock,
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    errors::Error,
+    keystore::{KeyStore, DID_CONTEXT_V1},
+    resolver::{ASSERTION_METHOD, AUTHENTICATION},
+    verification_method::{VerificationMethod, VerificationMethodType},
+};
+#[cfg(feature = "http")]
+use crate::{http::HttpFetcher, log, resolver::DidResolver};
+
+pub const WEB_PREFIX: &str = "did:web:";
+pub const WELL_KNOWN_DID_PATH: &str = "/.well-known/did.json";
+pub const WELL_KNOWN_JWKS_PATH: &str = "/.well-known/jwks.json";
+const DID_DOCUMENT_FILE: &str = "did.json";
+const ENCODED_PORT_SEPARATOR: &str = "%3A";
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WebDid {
+    pub did: String,
+    pub domain: String,
+    pub path: Vec<String>,
+}
+
+fn is_valid_segment(segment: &str) -> bool {
+    !segment.is_empty()
+        && segment
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-._~%".contains(c))
+}
+
+impl WebDid {
+    pub fn parse(did: &str) -> Result<Self, Error> {
+        let identifier = match did.strip_prefix(WEB_PREFIX) {
+            Some(val) => val,
+            None => return Err(Error::WEB_INVALID_DID),
+        };
+
+        let mut segments = identifier.split(':');
+        let domain = match segments.next() {
+            Some(val) if is_valid_segment(val) => val.replace(ENCODED_PORT_SEPARATOR, ":"),
+            _ => return Err(Error::WEB_INVALID_DID),
+        };
+
+        let path: Vec<String> = segments.map(String::from).collect();
+        if !path.iter().all(|segment| is_valid_segment(segment)) {
+            return Err(Error::WEB_INVALID_DID);
+        }
+
+        Ok(WebDid {
+            did: String::from(did),
+            domain,
+            path,
+        })
+    }
+
+    pub fn new(domain: &str, path: &[&str]) -> Result<Self, Error> {
+        let mut did = format!(
+            "{}{}",
+            WEB_PREFIX,
+            domain.replace(':', ENCODED_PORT_SEPARATOR)
+        );
+        for segment in path {
+            did.push(':');
+            did.push_str(segment);
+        }
+
+        Self::parse(&did)
+    }
+
+    pub fn document_path(&self) -> String {
+        match self.path.is_empty() {
+            true => String::from(WELL_KNOWN_DID_PATH),
+            false => format!("/{}/{}", self.path.join("/"), DID_DOCUMENT_FILE),
+        }
+    }
+
+    pub fn document_url(&self) -> String {
+        format!("https://{}{}", self.domain, self.document_path())
+    }
+
+    pub fn jwks_url(&self) -> String {
+        format!("https://{}{}", self.domain, WELL_KNOWN_JWKS_PATH)
+    }
+
+    pub fn method_id(&self, kid: &str) -> String {
+        let fragment = match kid.rsplit_once('#') {
+            Some((_, val)) => val,
+            None => kid,
+        };
+
+        format!("{}#{}", self.did, fragment)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct ConsistencyReport {
+    pub id_matches: bool,
+    pub missing: Vec<String>,
+    pub unexpected: Vec<String>,
+    pub mismatched: Vec<String>,
+}
+
+impl ConsistencyReport {
+    pub fn is_consistent(&self) -> bool {
+        self.id_matches
+            && self.missing.is_empty()
+            && self.unexpected.is_empty()
+            && self.mismatched.is_empty()
+    }
+}
+
+pub struct WebDocumentBuilder {
+    did: WebDid,
+    method_type: VerificationMethodType,
+    keys: Vec<(String, AnyVerifyingKey)>,
+    services: Vec<Value>,
+}
+
+impl WebDocumentBuilder {
+    pub fn new(did: WebDid) -> Self {
+        WebDocumentBuilder {
+            did,
+            method_type: VerificationMethodType::JsonWebKey2020,
+            keys: Vec::new(),
+            services: Vec::new(),
+        }
+    }
+
+    pub fn from_keystore(did: WebDid, store: &impl KeyStore<AnySigningKey>) -> Result<Self, Error> {
+        let mut builder = Self::new(did);
+        let now = clock::now();
+        for kid in store.list()? {
+            if store.validity(&kid)?.is_valid_at(now) {
+                builder = builder.key(&kid, store.get(&kid)?.verifying_key());
+            }
+        }
+
+        Ok(builder)
+    }
+
+    pub fn did(&self) -> &WebDid {
+        &self.did
+    }
+
+    pub fn method_type(mut self, method_type: VerificationMethodType) -> Self {
+        self.method_type = method_type;
+        self
+    }
+
+    pub fn key(mut self, kid: &str, key: AnyVerifyingKey) -> Self {
+        self.keys.push((self.did.method_id(kid), key));
+        self
+    }
+
+    pub fn service(mut self, id: &str, service_type: &str, endpoint: &str) -> Self {
+        self.services.push(json!({
+            "id": self.did.method_id(id),
+            "type": service_type,
+            "serviceEndpoint": endpoint,
+        }));
+        self
+    }
+
+    fn methods(&self) -> Result<Vec<VerificationMethod>, Error> {
+        self.keys
+            .iter()
+            .map(|(id, key)| {
+                VerificationMethod::new(id, &self.did.did, self.method_type, key.clone())
+            })
+            .collect()
+    }
+
+    pub fn build(&self) -> Result<Value, Error> {
+        let mut methods = Vec::new();
+        for method in self.methods()? {
+            methods.push(method.to_value()?);
+        }
+
+        let ids: Vec<&str> = self.keys.iter().map(|(id, _)| id.as_str()).collect();
+        let mut document = json!({
+            "@context": [DID_CONTEXT_V1, self.method_type.context()],
+            "id": self.did.did,
+            "verificationMethod": methods,
+            AUTHENTICATION: ids,
+            ASSERTION_METHOD: ids,
+        });
+        if !self.services.is_empty() {
+            document["service"] = Value::from(self.services.clone());
+        }
+
+        Ok(document)
+    }
+
+    pub fn jwks(&self) -> Result<Value, Error> {
+        let mut keys = Vec::new();
+        for method in self.methods()? {
+            keys.push(method.to_jwk()?);
+        }
+
+        Ok(json!({ "keys": keys }))
+    }
+
+    pub fn check(&self, hosted: &Value) -> Result<ConsistencyReport, Error> {
+        let mut report = ConsistencyReport {
+            id_matches: hosted.get("id").and_then(Value::as_str) == Some(self.did.did.as_str()),
+            ..Default::default()
+        };
+
+        let mut hosted_keys = Vec::new();
+        if let Some(Value::Array(methods)) = hosted.get("verificationMethod") {
+            for method in methods {
+                let method = VerificationMethod::from_value(method)?;
+                hosted_keys.push((self.did.method_id(&method.id), method.key));
+            }
+        }
+
+        for (id, key) in &self.keys {
+            match hosted_keys.iter().find(|(hosted_id, _)| hosted_id == id) {
+                Some((_, hosted_key)) if hosted_key == key => {}
+                Some(_) => report.mismatched.push(id.clone()),
+                None => report.missing.push(id.clone()),
+            }
+        }
+
+        report.unexpected = hosted_keys
+            .into_iter()
+            .filter(|(hosted_id, _)| !self.keys.iter().any(|(id, _)| id == hosted_id))
+            .map(|(id, _)| id)
+            .collect();
+
+        Ok(report)
+    }
+
+    #[cfg(feature = "http")]
+    pub fn check_hosted(&self, fetcher: &impl HttpFetcher) -> Result<ConsistencyReport, Error> {
+        self.check(&fetch_document(fetcher, &self.did)?)
+    }
+}
+
+#[cfg(feature = "http")]
+pub fn fetch_document(fetcher: &impl HttpFetcher, did: &WebDid) -> Result<Value, Error> {
+    let response = fetcher.get(&did.document_url())?;
+    match serde_json::from_slice(&response.body) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::DID_RESOLUTION_ERROR.with_source(error))
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+pub struct WebResolver<F: HttpFetcher> {
+    fetcher: F,
+}
+
+#[cfg(feature = "http")]
+impl<F: HttpFetcher> WebResolver<F> {
+    pub fn new(fetcher: F) -> Self {
+        WebResolver { fetcher }
+    }
+}
+
+#[cfg(feature = "http")]
+impl<F: HttpFetcher> DidResolver for WebResolver<F> {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        let did = WebDid::parse(did)?;
+        let document = fetch_document(&self.fetcher, &did)?;
+        match document.get("id").and_then(Value::as_str) == Some(did.did.as_str()) {
+            true => Ok(document),
+            false => Err(Error::DID_RESOLUTION_ERROR),
+        }
+    }
+}
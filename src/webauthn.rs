@@ -0,0 +1,273 @@
+use coset::CoseKey;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    algorithms::AlgorithmFamily,
+    cose::verifying_key_from_cose,
+    crypto::{any::AnyVerifyingKey, ecdsa::signature_from_der, policy::KeyPolicy, VerifyFromKey},
+    errors::Error,
+    log,
+    resolver::{self, DidResolver},
+};
+
+pub const CLIENT_DATA_TYPE_GET: &str = "webauthn.get";
+
+const RP_ID_HASH_LENGTH: usize = 32;
+const AUTHENTICATOR_DATA_LENGTH: usize = 37;
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+
+#[derive(Clone, PartialEq, Eq, Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClientData {
+    #[serde(rename = "type")]
+    pub client_data_type: String,
+    pub challenge: String,
+    pub origin: String,
+    #[serde(default)]
+    pub cross_origin: bool,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AssertionData {
+    pub rp_id_hash: Vec<u8>,
+    pub flags: u8,
+    pub sign_count: u32,
+}
+
+impl AssertionData {
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() < AUTHENTICATOR_DATA_LENGTH {
+            return Err(Error::WEBAUTHN_ASSERTION_MALFORMED);
+        }
+
+        Ok(AssertionData {
+            rp_id_hash: bytes[..RP_ID_HASH_LENGTH].to_vec(),
+            flags: bytes[RP_ID_HASH_LENGTH],
+            sign_count: u32::from_be_bytes([bytes[33], bytes[34], bytes[35], bytes[36]]),
+        })
+    }
+
+    pub fn user_present(&self) -> bool {
+        self.flags & FLAG_USER_PRESENT != 0
+    }
+
+    pub fn user_verified(&self) -> bool {
+        self.flags & FLAG_USER_VERIFIED != 0
+    }
+}
+
+pub struct AssertionValidation<'a> {
+    rp_id: &'a str,
+    origin: &'a str,
+    challenge: &'a [u8],
+    user_verification: bool,
+    cross_origin: bool,
+    previous_sign_count: Option<u32>,
+}
+
+impl<'a> AssertionValidation<'a> {
+    pub fn new(rp_id: &'a str, origin: &'a str, challenge: &'a [u8]) -> Self {
+        AssertionValidation {
+            rp_id,
+            origin,
+            challenge,
+            user_verification: false,
+            cross_origin: false,
+            previous_sign_count: None,
+        }
+    }
+
+    pub fn require_user_verification(mut self) -> Self {
+        self.user_verification = true;
+        self
+    }
+
+    pub fn allow_cross_origin(mut self) -> Self {
+        self.cross_origin = true;
+        self
+    }
+
+    pub fn previous_sign_count(mut self, sign_count: u32) -> Self {
+        self.previous_sign_count = Some(sign_count);
+        self
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AssertionResponse {
+    authenticator_data: String,
+    #[serde(rename = "clientDataJSON")]
+    client_data_json: String,
+    signature: String,
+    #[serde(default)]
+    user_handle: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PublicKeyCredential {
+    raw_id: String,
+    response: AssertionResponse,
+}
+
+fn decode(value: &str) -> Result<Vec<u8>, Error> {
+    match base64_url::decode(value) {
+        Ok(val) => Ok(val),
+        Err(error) => {
+            log::error(error.to_string().as_str());
+            Err(Error::WEBAUTHN_ASSERTION_MALFORMED.with_source(error))
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WebAuthnAssertion {
+    pub credential_id: Vec<u8>,
+    pub authenticator_data: Vec<u8>,
+    pub client_data_json: Vec<u8>,
+    pub signature: Vec<u8>,
+    pub user_handle: Option<Vec<u8>>,
+}
+
+impl WebAuthnAssertion {
+    pub fn from_json(json: &str) -> Result<Self, Error> {
+        let credential: PublicKeyCredential = match serde_json::from_str(json) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::WEBAUTHN_ASSERTION_MALFORMED.with_source(error));
+            }
+        };
+
+        Ok(WebAuthnAssertion {
+            credential_id: decode(&credential.raw_id)?,
+            authenticator_data: decode(&credential.response.authenticator_data)?,
+            client_data_json: decode(&credential.response.client_data_json)?,
+            signature: decode(&credential.response.signature)?,
+            user_handle: match &credential.response.user_handle {
+                Some(val) => Some(decode(val)?),
+                None => None,
+            },
+        })
+    }
+
+    pub fn client_data(&self) -> Result<ClientData, Error> {
+        match serde_json::from_slice(&self.client_data_json) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::WEBAUTHN_ASSERTION_MALFORMED.with_source(error))
+            }
+        }
+    }
+
+    pub fn assertion_data(&self) -> Result<AssertionData, Error> {
+        AssertionData::from_bytes(&self.authenticator_data)
+    }
+
+    pub fn signed_data(&self) -> Vec<u8> {
+        let mut data = self.authenticator_data.clone();
+        data.extend_from_slice(Sha256::digest(&self.client_data_json).as_slice());
+        data
+    }
+
+    fn check(&self, validation: &AssertionValidation) -> Result<AssertionData, Error> {
+        let client_data = self.client_data()?;
+        if client_data.client_data_type != CLIENT_DATA_TYPE_GET
+            || client_data.challenge != base64_url::encode(validation.challenge)
+            || client_data.origin != validation.origin
+            || (client_data.cross_origin && !validation.cross_origin)
+        {
+            return Err(Error::WEBAUTHN_CLIENT_DATA_MISMATCH);
+        }
+
+        let data = self.assertion_data()?;
+        if data.rp_id_hash != Sha256::digest(validation.rp_id.as_bytes()).as_slice() {
+            return Err(Error::WEBAUTHN_RP_ID_MISMATCH);
+        }
+        if !data.user_present() {
+            return Err(Error::WEBAUTHN_USER_NOT_PRESENT);
+        }
+        if validation.user_verification && !data.user_verified() {
+            return Err(Error::WEBAUTHN_USER_NOT_VERIFIED);
+        }
+
+        Ok(data)
+    }
+
+    fn verify_signature(&self, key: &AnyVerifyingKey) -> Result<bool, Error> {
+        let alg = key.algorithm();
+        let signature = match alg.get_family() {
+            AlgorithmFamily::EC => match signature_from_der(&self.signature, alg) {
+                Ok(val) => val,
+                Err(_) => return Ok(false),
+            },
+            _ => base64_url::encode(&self.signature),
+        };
+
+        key.verify_bytes(&self.signed_data(), &signature, alg)
+    }
+
+    fn check_sign_count(
+        data: &AssertionData,
+        validation: &AssertionValidation,
+    ) -> Result<(), Error> {
+        match validation.previous_sign_count {
+            Some(previous)
+                if (previous != 0 || data.sign_count != 0) && data.sign_count <= previous =>
+            {
+                Err(Error::WEBAUTHN_SIGN_COUNT_REGRESSED)
+            }
+            _ => Ok(()),
+        }
+    }
+
+    pub fn verify(
+        &self,
+        key: &AnyVerifyingKey,
+        validation: &AssertionValidation,
+    ) -> Result<bool, Error> {
+        let data = self.check(validation)?;
+        if !self.verify_signature(key)? {
+            return Ok(false);
+        }
+
+        Self::check_sign_count(&data, validation)?;
+        Ok(true)
+    }
+
+    pub fn verify_cose(
+        &self,
+        key: &CoseKey,
+        validation: &AssertionValidation,
+    ) -> Result<bool, Error> {
+        self.verify(&verifying_key_from_cose(key)?, validation)
+    }
+
+    pub fn verify_with_did(
+        &self,
+        resolver: &impl DidResolver,
+        did_url: &str,
+        validation: &AssertionValidation,
+    ) -> Result<bool, Error> {
+        let data = self.check(validation)?;
+        let keys = resolver::resolve_verifying_keys(
+            resolver,
+            did_url,
+            resolver::AUTHENTICATION,
+            &KeyPolicy::default(),
+        )?;
+
+        for key in keys {
+            if self.verify_signature(&key)? {
+                Self::check_sign_count(&data, validation)?;
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
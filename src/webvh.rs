@@ -0,0 +1,529 @@
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    algorithms::Algorithm,
+    clock,
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    data_integrity::{sign_jcs, verify_jcs, ProofOptions, EDDSA_JCS_2022},
+    encoding::{base58_encode, canonical_json, multibase_decode, multibase_encode},
+    errors::Error,
+    hash::HashAlgorithm,
+    log,
+    multihash::Multihash,
+    resolver::ASSERTION_METHOD,
+    web::{WebDid, WEB_PREFIX},
+};
+#[cfg(feature = "http")]
+use crate::{http::HttpFetcher, resolver::DidResolver};
+
+pub const WEBVH_PREFIX: &str = "did:webvh:";
+pub const WEBVH_METHOD: &str = "did:webvh:1.0";
+pub const SCID_PLACEHOLDER: &str = "{SCID}";
+const DID_KEY_PREFIX: &str = "did:key:";
+const LOG_FILE_SUFFIX: &str = "l";
+const CLOCK_LEEWAY_SECONDS: i64 = 5;
+
+fn hash_then_encode(bytes: &[u8]) -> String {
+    base58_encode(Multihash::digest(HashAlgorithm::Sha256, bytes).to_bytes())
+}
+
+pub fn multikey(key: &AnyVerifyingKey) -> Result<String, Error> {
+    match key.algorithm() {
+        Algorithm::EdDSA => Ok(multibase_encode(key.to_multicodec()?)),
+        alg => Err(Error::ALGORITHM_NOT_ALLOWED.with_algorithm(alg)),
+    }
+}
+
+fn key_from_multikey(multikey: &str) -> Result<AnyVerifyingKey, Error> {
+    let key = AnyVerifyingKey::from_multicodec(&multibase_decode(multikey)?)?;
+    match key.algorithm() {
+        Algorithm::EdDSA => Ok(key),
+        alg => Err(Error::ALGORITHM_NOT_ALLOWED.with_algorithm(alg)),
+    }
+}
+
+pub fn next_key_hash(key: &AnyVerifyingKey) -> Result<String, Error> {
+    Ok(hash_then_encode(multikey(key)?.as_bytes()))
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WebvhDid {
+    pub did: String,
+    pub scid: String,
+    pub web: WebDid,
+}
+
+impl WebvhDid {
+    pub fn parse(did: &str) -> Result<Self, Error> {
+        let (scid, location) = match did
+            .strip_prefix(WEBVH_PREFIX)
+            .and_then(|val| val.split_once(':'))
+        {
+            Some((scid, location)) if !scid.is_empty() => (scid, location),
+            _ => return Err(Error::WEBVH_INVALID_DID),
+        };
+
+        let web = match WebDid::parse(&format!("{}{}", WEB_PREFIX, location)) {
+            Ok(val) => val,
+            Err(error) => return Err(Error::WEBVH_INVALID_DID.with_source(error)),
+        };
+
+        Ok(WebvhDid {
+            did: String::from(did),
+            scid: String::from(scid),
+            web,
+        })
+    }
+
+    pub fn template(domain: &str, path: &[&str]) -> Result<String, Error> {
+        let web = WebDid::new(domain, path)?;
+        match web.did.strip_prefix(WEB_PREFIX) {
+            Some(location) => Ok(format!("{}{}:{}", WEBVH_PREFIX, SCID_PLACEHOLDER, location)),
+            None => Err(Error::WEBVH_INVALID_DID),
+        }
+    }
+
+    pub fn log_url(&self) -> String {
+        format!("{}{}", self.web.document_url(), LOG_FILE_SUFFIX)
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LogEntry {
+    pub version_id: String,
+    pub version_time: String,
+    pub parameters: Map<String, Value>,
+    pub state: Value,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub proof: Vec<Value>,
+}
+
+impl LogEntry {
+    fn unsecured(&self, version_id: &str) -> Result<Value, Error> {
+        let entry = LogEntry {
+            version_id: String::from(version_id),
+            proof: Vec::new(),
+            ..self.clone()
+        };
+
+        match serde_json::to_value(entry) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENCODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    fn entry_hash(&self, previous_version_id: &str) -> Result<String, Error> {
+        Ok(hash_then_encode(&canonical_json(
+            &self.unsecured(previous_version_id)?,
+        )))
+    }
+
+    fn version(&self) -> Result<(u64, &str), Error> {
+        match self.version_id.split_once('-') {
+            Some((number, hash)) if !hash.is_empty() => match number.parse::<u64>() {
+                Ok(val) => Ok((val, hash)),
+                Err(error) => Err(Error::WEBVH_INVALID_LOG.with_source(error)),
+            },
+            _ => Err(Error::WEBVH_INVALID_LOG),
+        }
+    }
+
+    fn version_time(&self) -> Result<DateTime<Utc>, Error> {
+        match DateTime::parse_from_rfc3339(&self.version_time) {
+            Ok(val) => Ok(val.with_timezone(&Utc)),
+            Err(error) => Err(Error::WEBVH_INVALID_LOG.with_source(error)),
+        }
+    }
+
+    fn sign(&mut self, signer: &AnySigningKey) -> Result<(), Error> {
+        let key = multikey(&signer.verifying_key())?;
+        let now = clock::now();
+        let config = ProofOptions::new(
+            EDDSA_JCS_2022,
+            &format!("{}{}#{}", DID_KEY_PREFIX, key, key),
+        )
+        .with_created(DateTime::from_timestamp(now.timestamp(), 0).unwrap_or(now))
+        .to_value()?;
+
+        let proof = sign_jcs(
+            &self.unsecured(&self.version_id)?,
+            &config,
+            signer,
+            Algorithm::EdDSA,
+        )?;
+        self.proof = vec![proof];
+        Ok(())
+    }
+
+    fn is_signed_by(&self, update_keys: &[String]) -> Result<bool, Error> {
+        let unsecured = self.unsecured(&self.version_id)?;
+        for proof in &self.proof {
+            let options = ProofOptions::from_value(proof)?;
+            if options.cryptosuite != EDDSA_JCS_2022 || options.proof_purpose != ASSERTION_METHOD {
+                continue;
+            }
+
+            let key = match options
+                .verification_method
+                .strip_prefix(DID_KEY_PREFIX)
+                .and_then(|val| val.split_once('#'))
+            {
+                Some((key, fragment))
+                    if key == fragment && update_keys.iter().any(|val| val == key) =>
+                {
+                    key
+                }
+                _ => continue,
+            };
+
+            if verify_jcs(&unsecured, proof, &key_from_multikey(key)?)? {
+                return Ok(true);
+            }
+        }
+
+        Ok(false)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct WebvhParameters {
+    pub method: String,
+    pub scid: String,
+    pub update_keys: Vec<String>,
+    pub next_key_hashes: Vec<String>,
+    pub portable: bool,
+    pub deactivated: bool,
+}
+
+fn string_list(value: &Value) -> Result<Vec<String>, Error> {
+    match value {
+        Value::Null => Ok(Vec::new()),
+        Value::Array(items) => items
+            .iter()
+            .map(|item| match item.as_str() {
+                Some(val) => Ok(String::from(val)),
+                None => Err(Error::WEBVH_INVALID_LOG),
+            })
+            .collect(),
+        _ => Err(Error::WEBVH_INVALID_LOG),
+    }
+}
+
+impl WebvhParameters {
+    fn apply(&mut self, parameters: &Map<String, Value>) -> Result<(), Error> {
+        for (name, value) in parameters {
+            match (name.as_str(), value) {
+                ("method", Value::String(val)) if val == WEBVH_METHOD => self.method = val.clone(),
+                ("scid", Value::String(val)) => self.scid = val.clone(),
+                ("updateKeys", _) => self.update_keys = string_list(value)?,
+                ("nextKeyHashes", _) => self.next_key_hashes = string_list(value)?,
+                ("portable", Value::Bool(val)) => self.portable = *val,
+                ("deactivated", Value::Bool(val)) => self.deactivated = *val,
+                ("witness", Value::Null) | ("watchers", _) | ("ttl", Value::Number(_)) => {}
+                ("witness", Value::Object(val)) if val.is_empty() => {}
+                _ => return Err(Error::WEBVH_INVALID_LOG.with_key(name)),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct WebvhResolution {
+    pub did: String,
+    pub document: Value,
+    pub version_id: String,
+    pub version_time: DateTime<Utc>,
+    pub parameters: WebvhParameters,
+}
+
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub struct DidLog {
+    pub entries: Vec<LogEntry>,
+}
+
+impl DidLog {
+    pub fn from_jsonl(log: &str) -> Result<Self, Error> {
+        let mut entries = Vec::new();
+        for line in log.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(val) => entries.push(val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::WEBVH_INVALID_LOG.with_source(error));
+                }
+            }
+        }
+
+        Ok(DidLog { entries })
+    }
+
+    pub fn to_jsonl(&self) -> Result<String, Error> {
+        let mut log = String::new();
+        for entry in &self.entries {
+            match serde_json::to_string(entry) {
+                Ok(val) => log.push_str(&val),
+                Err(error) => {
+                    log::error(error.to_string().as_str());
+                    return Err(Error::ENCODING_ERROR.with_source(error));
+                }
+            }
+            log.push('\n');
+        }
+
+        Ok(log)
+    }
+
+    pub fn create(
+        state: Value,
+        mut parameters: Map<String, Value>,
+        signer: &AnySigningKey,
+    ) -> Result<Self, Error> {
+        parameters.insert(String::from("method"), Value::from(WEBVH_METHOD));
+        parameters.insert(String::from("scid"), Value::from(SCID_PLACEHOLDER));
+        if !parameters.contains_key("updateKeys") {
+            parameters.insert(
+                String::from("updateKeys"),
+                json!([multikey(&signer.verifying_key())?]),
+            );
+        }
+
+        let preliminary = LogEntry {
+            version_id: String::from(SCID_PLACEHOLDER),
+            version_time: clock::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            parameters,
+            state,
+            proof: Vec::new(),
+        };
+        let scid = preliminary.entry_hash(SCID_PLACEHOLDER)?;
+        let mut entry: LogEntry = match serde_json::to_string(&preliminary) {
+            Ok(val) => match serde_json::from_str(&val.replace(SCID_PLACEHOLDER, &scid)) {
+                Ok(val) => val,
+                Err(error) => return Err(Error::WEBVH_INVALID_LOG.with_source(error)),
+            },
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ENCODING_ERROR.with_source(error));
+            }
+        };
+
+        entry.version_id = format!("1-{}", entry.entry_hash(&scid)?);
+        entry.sign(signer)?;
+
+        let log = DidLog {
+            entries: vec![entry],
+        };
+        log.verify()?;
+        Ok(log)
+    }
+
+    pub fn append(
+        &mut self,
+        state: Value,
+        parameters: Map<String, Value>,
+        signer: &AnySigningKey,
+    ) -> Result<(), Error> {
+        let previous = match self.entries.last() {
+            Some(val) => val,
+            None => return Err(Error::WEBVH_INVALID_LOG),
+        };
+        let (number, _) = previous.version()?;
+
+        let mut entry = LogEntry {
+            version_id: previous.version_id.clone(),
+            version_time: clock::now().to_rfc3339_opts(SecondsFormat::Secs, true),
+            parameters,
+            state,
+            proof: Vec::new(),
+        };
+        entry.version_id = format!("{}-{}", number + 1, entry.entry_hash(&previous.version_id)?);
+        entry.sign(signer)?;
+
+        self.entries.push(entry);
+        if let Err(error) = self.verify() {
+            self.entries.pop();
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    pub fn update(&mut self, state: Value, signer: &AnySigningKey) -> Result<(), Error> {
+        self.append(state, Map::new(), signer)
+    }
+
+    pub fn rotate(
+        &mut self,
+        update_keys: &[AnyVerifyingKey],
+        next_key_hashes: &[String],
+        signer: &AnySigningKey,
+    ) -> Result<(), Error> {
+        let mut keys = Vec::new();
+        for key in update_keys {
+            keys.push(multikey(key)?);
+        }
+
+        let mut parameters = Map::new();
+        parameters.insert(String::from("updateKeys"), json!(keys));
+        parameters.insert(String::from("nextKeyHashes"), json!(next_key_hashes));
+        self.append(self.current_state()?, parameters, signer)
+    }
+
+    pub fn deactivate(&mut self, signer: &AnySigningKey) -> Result<(), Error> {
+        let mut parameters = Map::new();
+        parameters.insert(String::from("deactivated"), Value::from(true));
+        parameters.insert(String::from("updateKeys"), json!([]));
+        self.append(self.current_state()?, parameters, signer)
+    }
+
+    fn current_state(&self) -> Result<Value, Error> {
+        match self.entries.last() {
+            Some(val) => Ok(val.state.clone()),
+            None => Err(Error::WEBVH_INVALID_LOG),
+        }
+    }
+
+    pub fn verify(&self) -> Result<WebvhResolution, Error> {
+        let mut parameters = WebvhParameters::default();
+        let mut resolution: Option<WebvhResolution> = None;
+        let latest = clock::now() + Duration::seconds(CLOCK_LEEWAY_SECONDS);
+
+        for (index, entry) in self.entries.iter().enumerate() {
+            if parameters.deactivated {
+                return Err(Error::WEBVH_DEACTIVATED);
+            }
+
+            let (number, hash) = entry.version()?;
+            let version_time = entry.version_time()?;
+            let previous = match &resolution {
+                Some(val) if version_time < val.version_time => {
+                    return Err(Error::WEBVH_INVALID_LOG)
+                }
+                Some(val) => val.version_id.clone(),
+                None => match entry.parameters.get("scid").and_then(Value::as_str) {
+                    Some(val) => String::from(val),
+                    None => return Err(Error::WEBVH_INVALID_LOG),
+                },
+            };
+            if number != index as u64 + 1
+                || version_time > latest
+                || entry.entry_hash(&previous)? != hash
+            {
+                return Err(Error::WEBVH_INVALID_LOG);
+            }
+
+            let authorized = match index {
+                0 => {
+                    self.verify_scid(entry, &previous)?;
+                    parameters.apply(&entry.parameters)?;
+                    parameters.update_keys.clone()
+                }
+                _ if !parameters.next_key_hashes.is_empty() => {
+                    let next_key_hashes = parameters.next_key_hashes.clone();
+                    parameters.apply(&entry.parameters)?;
+                    let rotated = entry.parameters.contains_key("updateKeys")
+                        && parameters
+                            .update_keys
+                            .iter()
+                            .all(|key| next_key_hashes.contains(&hash_then_encode(key.as_bytes())));
+                    if !rotated {
+                        return Err(Error::WEBVH_UNAUTHORIZED);
+                    }
+                    parameters.update_keys.clone()
+                }
+                _ => {
+                    let update_keys = parameters.update_keys.clone();
+                    parameters.apply(&entry.parameters)?;
+                    update_keys
+                }
+            };
+
+            if parameters.method != WEBVH_METHOD || !entry.is_signed_by(&authorized)? {
+                return Err(Error::WEBVH_UNAUTHORIZED);
+            }
+
+            let did = match entry.state.get("id").and_then(Value::as_str) {
+                Some(val) => WebvhDid::parse(val)?,
+                None => return Err(Error::WEBVH_INVALID_LOG),
+            };
+            let moved = resolution
+                .as_ref()
+                .is_some_and(|val| val.did != did.did && !parameters.portable);
+            if did.scid != parameters.scid || moved {
+                return Err(Error::WEBVH_INVALID_LOG);
+            }
+
+            resolution = Some(WebvhResolution {
+                did: did.did,
+                document: entry.state.clone(),
+                version_id: entry.version_id.clone(),
+                version_time,
+                parameters: parameters.clone(),
+            });
+        }
+
+        match resolution {
+            Some(val) => Ok(val),
+            None => Err(Error::WEBVH_INVALID_LOG),
+        }
+    }
+
+    fn verify_scid(&self, entry: &LogEntry, scid: &str) -> Result<(), Error> {
+        let preliminary = entry.unsecured(SCID_PLACEHOLDER)?.to_string();
+        let preliminary: Value =
+            match serde_json::from_str(&preliminary.replace(scid, SCID_PLACEHOLDER)) {
+                Ok(val) => val,
+                Err(error) => return Err(Error::WEBVH_INVALID_LOG.with_source(error)),
+            };
+
+        match hash_then_encode(&canonical_json(&preliminary)) == scid {
+            true => Ok(()),
+            false => Err(Error::WEBVH_INVALID_LOG),
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+pub struct WebvhResolver<F: HttpFetcher> {
+    fetcher: F,
+}
+
+#[cfg(feature = "http")]
+impl<F: HttpFetcher> WebvhResolver<F> {
+    pub fn new(fetcher: F) -> Self {
+        WebvhResolver { fetcher }
+    }
+
+    pub fn resolve_log(&self, did: &str) -> Result<WebvhResolution, Error> {
+        let did = WebvhDid::parse(did)?;
+        let response = self.fetcher.get(&did.log_url())?;
+        let log = match String::from_utf8(response.body) {
+            Ok(val) => DidLog::from_jsonl(&val)?,
+            Err(error) => return Err(Error::WEBVH_INVALID_LOG.with_source(error)),
+        };
+
+        let resolution = log.verify()?;
+        match resolution.did == did.did {
+            true => Ok(resolution),
+            false => Err(Error::DID_RESOLUTION_ERROR),
+        }
+    }
+}
+
+#[cfg(feature = "http")]
+impl<F: HttpFetcher> DidResolver for WebvhResolver<F> {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        let resolution = self.resolve_log(did)?;
+        match resolution.parameters.deactivated {
+            true => Err(Error::WEBVH_DEACTIVATED),
+            false => Ok(resolution.document),
+        }
+    }
+}
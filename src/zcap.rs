@@ -0,0 +1,532 @@
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    algorithms::Algorithm,
+    clock,
+    crypto::{policy::KeyPolicy, SignFromKey},
+    data_integrity::{jcs_cryptosuite, sign_jcs, verify_jcs, ProofCheck, ProofOptions},
+    errors::Error,
+    log, nonce,
+    resolver::{
+        did_from_url, resolve_verifying_keys, DidResolver, CAPABILITY_DELEGATION,
+        CAPABILITY_INVOCATION, DEFAULT_CLOCK_LEEWAY,
+    },
+    vc::one_or_many,
+};
+
+pub const ZCAP_CONTEXT_V1: &str = "https://w3id.org/zcap/v1";
+pub const DATA_INTEGRITY_CONTEXT_V2: &str = "https://w3id.org/security/data-integrity/v2";
+pub const ROOT_CAPABILITY_PREFIX: &str = "urn:zcap:root:";
+pub const DEFAULT_MAX_CHAIN_LENGTH: usize = 10;
+const CAPABILITY_ID_PREFIX: &str = "urn:zcap:";
+
+pub fn root_capability_id(invocation_target: &str) -> String {
+    let mut id = String::from(ROOT_CAPABILITY_PREFIX);
+    for byte in invocation_target.bytes() {
+        match byte {
+            b'A'..=b'Z'
+            | b'a'..=b'z'
+            | b'0'..=b'9'
+            | b'-'
+            | b'_'
+            | b'.'
+            | b'!'
+            | b'~'
+            | b'*'
+            | b'\''
+            | b'('
+            | b')' => id.push(byte as char),
+            _ => id.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    id
+}
+
+fn is_attenuated_target(parent: &str, child: &str) -> bool {
+    child == parent
+        || child
+            .strip_prefix(parent)
+            .is_some_and(|rest| rest.starts_with('/') || rest.starts_with('?'))
+}
+
+#[derive(Serialize, Deserialize, Clone, PartialEq, Eq, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Capability {
+    #[serde(rename = "@context")]
+    pub context: Value,
+    pub id: String,
+    pub controller: String,
+    pub invocation_target: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_capability: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<DateTime<Utc>>,
+    #[serde(
+        default,
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub allowed_action: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub caveats: Vec<Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<Value>,
+}
+
+impl Capability {
+    pub fn root(controller: &str, invocation_target: &str) -> Self {
+        Capability {
+            context: Value::from(ZCAP_CONTEXT_V1),
+            id: root_capability_id(invocation_target),
+            controller: String::from(controller),
+            invocation_target: String::from(invocation_target),
+            parent_capability: None,
+            expires: None,
+            allowed_action: Vec::new(),
+            caveats: Vec::new(),
+            proof: None,
+        }
+    }
+
+    pub fn delegate(&self, controller: &str) -> Self {
+        Capability {
+            context: json!([ZCAP_CONTEXT_V1, DATA_INTEGRITY_CONTEXT_V2]),
+            id: format!("{}{}", CAPABILITY_ID_PREFIX, nonce::generate()),
+            controller: String::from(controller),
+            invocation_target: self.invocation_target.clone(),
+            parent_capability: Some(self.id.clone()),
+            expires: self.expires,
+            allowed_action: self.allowed_action.clone(),
+            caveats: Vec::new(),
+            proof: None,
+        }
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.id = String::from(id);
+        self
+    }
+
+    pub fn with_invocation_target(mut self, invocation_target: &str) -> Self {
+        self.invocation_target = String::from(invocation_target);
+        self
+    }
+
+    pub fn with_allowed_action(mut self, actions: &[&str]) -> Self {
+        self.allowed_action = actions.iter().map(|val| String::from(*val)).collect();
+        self
+    }
+
+    pub fn with_expires(mut self, expires: DateTime<Utc>) -> Self {
+        self.expires = Some(expires);
+        self
+    }
+
+    pub fn with_caveat(mut self, caveat: Value) -> Self {
+        self.caveats.push(caveat);
+        self
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.parent_capability.is_none()
+    }
+
+    pub fn from_value(value: &Value) -> Result<Self, Error> {
+        let capability: Self = match serde_json::from_value(value.clone()) {
+            Ok(val) => val,
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                return Err(Error::ZCAP_MALFORMED.with_source(error));
+            }
+        };
+
+        let valid = !capability.id.is_empty()
+            && !capability.controller.is_empty()
+            && !capability.invocation_target.is_empty()
+            && capability.is_root() == capability.proof.is_none();
+        match valid {
+            true => Ok(capability),
+            false => Err(Error::ZCAP_MALFORMED),
+        }
+    }
+
+    pub fn to_value(&self) -> Result<Value, Error> {
+        match serde_json::to_value(self) {
+            Ok(val) => Ok(val),
+            Err(error) => {
+                log::error(error.to_string().as_str());
+                Err(Error::ENCODING_ERROR.with_source(error))
+            }
+        }
+    }
+
+    fn capability_chain(&self) -> Result<&Vec<Value>, Error> {
+        match self
+            .proof
+            .as_ref()
+            .and_then(|proof| proof.get("capabilityChain"))
+            .and_then(Value::as_array)
+        {
+            Some(val) => Ok(val),
+            None => Err(Error::ZCAP_MALFORMED),
+        }
+    }
+
+    fn check_attenuation(&self, parent: &Capability) -> Result<(), Error> {
+        let actions = parent.allowed_action.is_empty()
+            || (!self.allowed_action.is_empty()
+                && self
+                    .allowed_action
+                    .iter()
+                    .all(|action| parent.allowed_action.contains(action)));
+        let expires = match (parent.expires, self.expires) {
+            (Some(parent), Some(child)) => child <= parent,
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+
+        let valid = self.parent_capability.as_ref() == Some(&parent.id)
+            && is_attenuated_target(&parent.invocation_target, &self.invocation_target)
+            && actions
+            && expires;
+        match valid {
+            true => Ok(()),
+            false => Err(Error::ZCAP_INVALID_CHAIN),
+        }
+    }
+
+    pub fn sign(
+        mut self,
+        parent: &Capability,
+        options: ProofOptions,
+        key: impl SignFromKey,
+        alg: Algorithm,
+    ) -> Result<Self, Error> {
+        self.check_attenuation(parent)?;
+
+        let chain = match parent.is_root() {
+            true => vec![Value::from(parent.id.as_str())],
+            false => {
+                let mut chain: Vec<Value> = chain_ids(parent.capability_chain()?)?
+                    .into_iter()
+                    .map(Value::from)
+                    .collect();
+                chain.push(parent.to_value()?);
+                chain
+            }
+        };
+
+        let mut config = options
+            .with_proof_purpose(CAPABILITY_DELEGATION)
+            .to_value()?;
+        config["capabilityChain"] = Value::from(chain);
+
+        self.proof = None;
+        self.proof = Some(sign_jcs(&self.to_value()?, &config, key, alg)?);
+        Ok(self)
+    }
+
+    pub fn invoke(
+        &self,
+        document: &Value,
+        action: &str,
+        options: ProofOptions,
+        key: impl SignFromKey,
+        alg: Algorithm,
+    ) -> Result<Value, Error> {
+        if !document.is_object() {
+            return Err(Error::ZCAP_MALFORMED);
+        }
+
+        let mut config = options
+            .with_proof_purpose(CAPABILITY_INVOCATION)
+            .to_value()?;
+        config["capability"] = match self.is_root() {
+            true => Value::from(self.id.as_str()),
+            false => self.to_value()?,
+        };
+        config["capabilityAction"] = Value::from(action);
+        config["invocationTarget"] = Value::from(self.invocation_target.as_str());
+
+        let mut document = document.clone();
+        document["proof"] = sign_jcs(&document, &config, key, alg)?;
+        Ok(document)
+    }
+}
+
+fn chain_ids(chain: &[Value]) -> Result<Vec<String>, Error> {
+    chain
+        .iter()
+        .map(|entry| match entry {
+            Value::String(id) => Ok(id.clone()),
+            Value::Object(map) => match map.get("id").and_then(Value::as_str) {
+                Some(id) => Ok(String::from(id)),
+                None => Err(Error::ZCAP_MALFORMED),
+            },
+            _ => Err(Error::ZCAP_MALFORMED),
+        })
+        .collect()
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct InvocationCheck {
+    pub invocation_target: String,
+    pub action: String,
+    pub domain: Option<String>,
+    pub challenge: Option<String>,
+}
+
+impl InvocationCheck {
+    pub fn new(invocation_target: &str, action: &str) -> Self {
+        InvocationCheck {
+            invocation_target: String::from(invocation_target),
+            action: String::from(action),
+            domain: None,
+            challenge: None,
+        }
+    }
+
+    pub fn domain(mut self, domain: &str) -> Self {
+        self.domain = Some(String::from(domain));
+        self
+    }
+
+    pub fn challenge(mut self, challenge: &str) -> Self {
+        self.challenge = Some(String::from(challenge));
+        self
+    }
+}
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ZcapInvocation {
+    pub capability: Capability,
+    pub chain: Vec<Capability>,
+    pub invoker: String,
+    pub invocation_target: String,
+    pub action: String,
+}
+
+pub struct ZcapVerifier<R: DidResolver> {
+    resolver: R,
+    roots: Vec<Capability>,
+    key_policy: KeyPolicy,
+    max_chain_length: usize,
+    leeway: Duration,
+}
+
+impl<R: DidResolver> ZcapVerifier<R> {
+    pub fn new(resolver: R) -> Self {
+        ZcapVerifier {
+            resolver,
+            roots: Vec::new(),
+            key_policy: KeyPolicy::default(),
+            max_chain_length: DEFAULT_MAX_CHAIN_LENGTH,
+            leeway: DEFAULT_CLOCK_LEEWAY,
+        }
+    }
+
+    pub fn root(mut self, capability: Capability) -> Self {
+        self.roots.push(capability);
+        self
+    }
+
+    pub fn key_policy(mut self, policy: KeyPolicy) -> Self {
+        self.key_policy = policy;
+        self
+    }
+
+    pub fn max_chain_length(mut self, max_chain_length: usize) -> Self {
+        self.max_chain_length = max_chain_length;
+        self
+    }
+
+    pub fn leeway(mut self, leeway: Duration) -> Self {
+        self.leeway = leeway;
+        self
+    }
+
+    fn root_capability(&self, id: &str) -> Result<Capability, Error> {
+        match self.roots.iter().find(|root| root.id == id) {
+            Some(val) => Ok(val.clone()),
+            None => Err(Error::ZCAP_INVALID_CHAIN),
+        }
+    }
+
+    fn is_expired(&self, capability: &Capability, at: DateTime<Utc>) -> bool {
+        let leeway = TimeDelta::from_std(self.leeway).unwrap_or(TimeDelta::zero());
+        capability
+            .expires
+            .is_some_and(|expires| expires + leeway <= at)
+    }
+
+    fn check_proof(
+        &self,
+        document: &Value,
+        proof: &Value,
+        controller: &str,
+        expected: &ProofCheck,
+        at: DateTime<Utc>,
+    ) -> Result<String, Error> {
+        let options = ProofOptions::from_value(proof)?;
+        options.check_at(expected, at)?;
+
+        let method = options.verification_method;
+        if method != controller && did_from_url(&method) != controller {
+            return Err(Error::ZCAP_UNAUTHORIZED);
+        }
+
+        let keys = resolve_verifying_keys(
+            &self.resolver,
+            &method,
+            &expected.proof_purpose,
+            &self.key_policy,
+        )?;
+        for key in keys {
+            if jcs_cryptosuite(key.algorithm()).is_ok() && verify_jcs(document, proof, &key)? {
+                return Ok(method);
+            }
+        }
+
+        Err(Error::ZCAP_INVALID_PROOF)
+    }
+
+    pub fn verify_delegation(&self, capability: &Value) -> Result<Vec<Capability>, Error> {
+        self.verify_delegation_at(capability, clock::now())
+    }
+
+    pub fn verify_delegation_at(
+        &self,
+        capability: &Value,
+        at: DateTime<Utc>,
+    ) -> Result<Vec<Capability>, Error> {
+        let delegated = Capability::from_value(capability)?;
+        if delegated.is_root() {
+            return match self.root_capability(&delegated.id)? == delegated {
+                true => Ok(vec![delegated]),
+                false => Err(Error::ZCAP_INVALID_CHAIN),
+            };
+        }
+
+        let chain = delegated.capability_chain()?;
+        if chain.len() >= self.max_chain_length {
+            return Err(Error::ZCAP_INVALID_CHAIN);
+        }
+
+        let ids = chain_ids(chain)?;
+        let mut capabilities = match chain.as_slice() {
+            [Value::String(root)] => vec![self.root_capability(root)?],
+            [intermediate @ .., parent @ Value::Object(_)]
+                if !intermediate.is_empty() && intermediate.iter().all(Value::is_string) =>
+            {
+                self.verify_delegation_at(parent, at)?
+            }
+            _ => return Err(Error::ZCAP_MALFORMED),
+        };
+
+        let parent_ids: Vec<&String> = capabilities.iter().map(|val| &val.id).collect();
+        if parent_ids != ids.iter().collect::<Vec<_>>() {
+            return Err(Error::ZCAP_INVALID_CHAIN);
+        }
+
+        let parent = &capabilities[capabilities.len() - 1];
+        delegated.check_attenuation(parent)?;
+        if self.is_expired(&delegated, at) {
+            return Err(Error::ZCAP_EXPIRED);
+        }
+
+        let expected = ProofCheck::new()
+            .proof_purpose(CAPABILITY_DELEGATION)
+            .leeway(self.leeway);
+        let proof = delegated.proof.clone().unwrap_or_default();
+        self.check_proof(capability, &proof, &parent.controller, &expected, at)?;
+
+        capabilities.push(delegated);
+        Ok(capabilities)
+    }
+
+    pub fn verify_invocation(
+        &self,
+        document: &Value,
+        check: &InvocationCheck,
+    ) -> Result<ZcapInvocation, Error> {
+        self.verify_invocation_at(document, check, clock::now(), |_, _| Ok(false))
+    }
+
+    pub fn verify_invocation_at(
+        &self,
+        document: &Value,
+        check: &InvocationCheck,
+        at: DateTime<Utc>,
+        caveat: impl Fn(&Value, &ZcapInvocation) -> Result<bool, Error>,
+    ) -> Result<ZcapInvocation, Error> {
+        let proofs = match document.get("proof") {
+            Some(Value::Array(val)) => val.iter().collect(),
+            Some(val) => vec![val],
+            None => Vec::new(),
+        };
+        let proof = match proofs.into_iter().find(|proof| {
+            proof.get("proofPurpose").and_then(Value::as_str) == Some(CAPABILITY_INVOCATION)
+        }) {
+            Some(val) => val,
+            None => return Err(Error::ZCAP_MALFORMED),
+        };
+
+        let chain = match proof.get("capability") {
+            Some(Value::String(id)) => vec![self.root_capability(id)?],
+            Some(val @ Value::Object(_)) => self.verify_delegation_at(val, at)?,
+            _ => return Err(Error::ZCAP_MALFORMED),
+        };
+        let capability = chain[chain.len() - 1].clone();
+
+        let (action, target) = match (
+            proof.get("capabilityAction").and_then(Value::as_str),
+            proof.get("invocationTarget").and_then(Value::as_str),
+        ) {
+            (Some(action), Some(target)) => (action, target),
+            _ => return Err(Error::ZCAP_MALFORMED),
+        };
+
+        let authorized = action == check.action
+            && target == check.invocation_target
+            && is_attenuated_target(&capability.invocation_target, target)
+            && chain.iter().all(|val| {
+                val.allowed_action.is_empty() || val.allowed_action.iter().any(|a| a == action)
+            });
+        if !authorized {
+            return Err(Error::ZCAP_UNAUTHORIZED);
+        }
+        if chain.iter().any(|val| self.is_expired(val, at)) {
+            return Err(Error::ZCAP_EXPIRED);
+        }
+
+        let mut expected = ProofCheck::new()
+            .proof_purpose(CAPABILITY_INVOCATION)
+            .leeway(self.leeway);
+        if let Some(domain) = &check.domain {
+            expected = expected.domain(domain);
+        }
+        if let Some(challenge) = &check.challenge {
+            expected = expected.challenge(challenge);
+        }
+        let invoker = self.check_proof(document, proof, &capability.controller, &expected, at)?;
+
+        let invocation = ZcapInvocation {
+            capability,
+            chain,
+            invoker,
+            invocation_target: String::from(target),
+            action: String::from(action),
+        };
+        for value in invocation.chain.iter().flat_map(|val| &val.caveats) {
+            if !caveat(value, &invocation)? {
+                return Err(Error::ZCAP_UNAUTHORIZED);
+            }
+        }
+
+        Ok(invocation)
+    }
+}
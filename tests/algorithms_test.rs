@@ -0,0 +1,79 @@
+use did_crypto::{
+    algorithms::{Algorithm, AlgorithmPolicy, FIPS_ALGORITHMS, FIPS_MODE},
+    errors::Error,
+};
+
+#[test]
+pub fn algorithm_names() {
+    for name in [
+        "HS256",
+        "PS384",
+        "ES256K",
+        "ES256K-R",
+        "EdDSA",
+        "ML-DSA-65",
+        "ML-DSA-65-Ed25519",
+    ] {
+        let alg: Algorithm = name.parse().unwrap();
+        assert_eq!(alg.to_string(), name);
+        assert_eq!(
+            serde_json::to_string(&alg).unwrap(),
+            format!("\"{}\"", name)
+        );
+        assert_eq!(
+            serde_json::from_str::<Algorithm>(&format!("\"{}\"", name)).unwrap(),
+            alg
+        );
+    }
+
+    assert!("ES257".parse::<Algorithm>().is_err());
+    assert!("eddsa".parse::<Algorithm>().is_err());
+    assert!(serde_json::from_str::<Algorithm>("\"none\"").is_err());
+}
+
+#[test]
+pub fn algorithm_policy() {
+    let policy = AlgorithmPolicy::new(&[Algorithm::ES256, Algorithm::EdDSA]);
+    assert!(policy.is_allowed(Algorithm::ES256));
+    assert!(policy.is_allowed(Algorithm::EdDSA));
+    assert!(!policy.is_allowed(Algorithm::HS256));
+    assert!(!policy.is_allowed(Algorithm::ES256K));
+    assert!(policy
+        .allow(Algorithm::ES256K)
+        .is_allowed(Algorithm::ES256K));
+
+    let error = policy.check(Algorithm::HS256).err().unwrap();
+    assert_eq!(error, Error::ALGORITHM_NOT_ALLOWED);
+    assert_eq!(error.algorithm(), Some(Algorithm::HS256));
+
+    let asymmetric = AlgorithmPolicy::asymmetric();
+    assert!(!asymmetric.is_allowed(Algorithm::HS384));
+    assert!(asymmetric.is_allowed(Algorithm::MLDSA65Ed25519));
+    assert!(AlgorithmPolicy::default().is_allowed(Algorithm::HS512));
+    assert!(!AlgorithmPolicy::allow_all()
+        .deny(Algorithm::RS256)
+        .is_allowed(Algorithm::RS256));
+}
+
+#[test]
+pub fn fips_algorithm_policy() {
+    let fips = AlgorithmPolicy::fips();
+    for alg in FIPS_ALGORITHMS {
+        assert!(fips.is_allowed(alg));
+    }
+    for alg in [
+        Algorithm::ES256K,
+        Algorithm::ES256KR,
+        Algorithm::EdDSA,
+        Algorithm::MLDSA65,
+        Algorithm::MLDSA65Ed25519,
+    ] {
+        assert_eq!(fips.check(alg).err(), Some(Error::ALGORITHM_NOT_ALLOWED));
+    }
+
+    assert_eq!(FIPS_MODE, cfg!(feature = "fips"));
+    assert_eq!(
+        AlgorithmPolicy::default() == AlgorithmPolicy::fips(),
+        FIPS_MODE
+    );
+}
@@ -0,0 +1,224 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{TimeZone, Utc};
+use coset::{cbor::value::Value, iana, CborSerializable, CoseKeyBuilder};
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{
+        any::AnySigningKey,
+        attestation::{
+            verify_key_attestation_at, AttestationObject, SecurityLevel,
+            ATTESTATION_FORMAT_ANDROID_KEY, ATTESTATION_FORMAT_APPLE, ATTESTATION_FORMAT_PACKED,
+        },
+        ecdsa::signature_to_der,
+        hardware::{HardwareKeyBackend, InMemoryKeyBackend, KeyAttestation},
+        SignFromKey,
+    },
+    errors::Error,
+};
+use sha2::{Digest, Sha256};
+
+const ROOT_CERTIFICATE: &str = "MIIBRzCB7qADAgECAgEBMAoGCCqGSM49BAMCMCMxITAfBgNVBAMMGEV4YW1wbGUgQXR0ZXN0YXRpb24gUm9vdDAeFw0yNjAxMDEwMDAwMDBaFw00NjAxMDEwMDAwMDBaMCMxITAfBgNVBAMMGEV4YW1wbGUgQXR0ZXN0YXRpb24gUm9vdDBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABOTqzAeSNHGCZOA+fkSAgcK+oJoPKawsVAHLZ7EMuHmhDDQmy0TqBNw2JLmbUvCzb1YWOHeVkd04dk04rU1PNeKjEzARMA8GA1UdEwEB/wQFMAMBAf8wCgYIKoZIzj0EAwIDSAAwRQIhALpt1yW7ZR9iaN4ju3GtxkRoYMxEWN0c87yBaEHtT9JbAiBRDMrVIXoutQsZzhIvsyrbvvO0jYz/0lY/QzEyyQ9CRg==";
+const PACKED_ATTESTATION: &str = "o2NmbXRmcGFja2VkZ2F0dFN0bXSjY2FsZyZjc2lnWEgwRgIhALFrBNz6sMqRIgCaEyv3oC4zD1f9QhFG7Pom1Um38jPzAiEAtEm9H+BWfHFMRUvy/3aGIHljZQ/lUoEQgTeO8rJZTNtjeDVjgVkBvzCCAbswggFhoAMCAQICFHylP4RHCgEFVdRWm6/HQTqtsUYWMAoGCCqGSM49BAMCMCMxITAfBgNVBAMMGEV4YW1wbGUgQXR0ZXN0YXRpb24gUm9vdDAeFw0yNjAxMDEwMDAwMDBaFw00NjAxMDEwMDAwMDBaMGMxCzAJBgNVBAYTAlVTMRAwDgYDVQQKDAdFeGFtcGxlMSIwIAYDVQQLDBlBdXRoZW50aWNhdG9yIEF0dGVzdGF0aW9uMR4wHAYDVQQDDBVFeGFtcGxlIEF1dGhlbnRpY2F0b3IwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAAQCvBxYGRdak4SWDLnUeHgIdUQ5TILfyjUBqFC8MZkQdj623u/+eYF1aV5tmm4xO2m35xaN8544RXv3Ys5W+IVoozMwMTAMBgNVHRMBAf8EAjAAMCEGCysGAQQBguUcAQEEBBIEEAECAwQFBgcICQoLDA0ODxAwCgYIKoZIzj0EAwIDSAAwRQIgLJIU2s80sfbb/dhwAzwupAila4Ncz0J0OjLEfgFFdNgCIQCVWaxByfMR9pzFSu7FjZFZ38IW+znOxGD1bbscWm0TfmhhdXRoRGF0YViUo3mm9u6vuaVeN4wRgDTidR5oL6ufLTCrE9ISVYbOGUdFAAAAAAECAwQFBgcICQoLDA0ODxAAEGNyZWRlbnRpYWwtaWQtMDGlAQIDJiABIVggZ/RxB1O16frl4XFsZ51sg4rdkiXYzC0d6yCN+Cg2gyAiWCChiUR0Qf22CUE9Fe8P/WXpsHCSEa4YOKG4+jOOcc/Sig==";
+const ANDROID_KEY_ATTESTATION: &str = "o2NmbXRrYW5kcm9pZC1rZXlnYXR0U3RtdKNjYWxnJmNzaWdYRjBEAiBP4U2SI5E7Xtbrd/4Vr+pgkuLeo8c9fna79i0eRaAi7wIgPMzoO4zGUuIxPtpcNXk6NY3zVHQHnuWFXKloloFnUQBjeDVjgVkBrDCCAagwggFOoAMCAQICFBCTV7cRiO1ICZuznDBJ7B2LAnjhMAoGCCqGSM49BAMCMCMxITAfBgNVBAMMGEV4YW1wbGUgQXR0ZXN0YXRpb24gUm9vdDAeFw0yNjAxMDEwMDAwMDBaFw00NjAxMDEwMDAwMDBaMB8xHTAbBgNVBAMMFEFuZHJvaWQgS2V5c3RvcmUgS2V5MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEnZ0Qq/pfreZ0nNsd3Up+InY2CsbUaSr8nel4Nb8vJlfg86DZER59LiatfUPDdbaGxlyMYaZX2ro+O0kOgSw5eKNkMGIwDAYDVR0TAQH/BAIwADBSBgorBgEEAdZ5AgERBEQwQgIBAwoBAQIBBAoBAQQgLSd3BYBaeksVE7+tIg69Ad45mFPtiRFIxdLpPey2qPgEADAAMA6hBTEDAgECv4U+AwIBADAKBggqhkjOPQQDAgNIADBFAiA+svWR5rFkf1QipxLWbhvjbcl509ckWZbnAnSCgUYapgIhALX7zNmZZlSVUhqH3Fl1N6ipZ4zP0t0F1qMC82cM+JasaGF1dGhEYXRhWJSjeab27q+5pV43jBGANOJ1Hmgvq58tMKsT0hJVhs4ZR0UAAAAAAAAAAAAAAAAAAAAAAAAAAAAQY3JlZGVudGlhbC1pZC0wMaUBAgMmIAEhWCCdnRCr+l+t5nSc2x3dSn4idjYKxtRpKvyd6Xg1vy8mVyJYIODzoNkRHn0uJq19Q8N1tobGXIxhplfauj47SQ6BLDl4";
+const ANDROID_CERTIFICATE: &str = "MIIBqDCCAU6gAwIBAgIUEJNXtxGI7UgJm7OcMEnsHYsCeOEwCgYIKoZIzj0EAwIwIzEhMB8GA1UEAwwYRXhhbXBsZSBBdHRlc3RhdGlvbiBSb290MB4XDTI2MDEwMTAwMDAwMFoXDTQ2MDEwMTAwMDAwMFowHzEdMBsGA1UEAwwUQW5kcm9pZCBLZXlzdG9yZSBLZXkwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAASdnRCr+l+t5nSc2x3dSn4idjYKxtRpKvyd6Xg1vy8mV+DzoNkRHn0uJq19Q8N1tobGXIxhplfauj47SQ6BLDl4o2QwYjAMBgNVHRMBAf8EAjAAMFIGCisGAQQB1nkCAREERDBCAgEDCgEBAgEECgEBBCAtJ3cFgFp6SxUTv60iDr0B3jmYU+2JEUjF0uk97Lao+AQAMAAwDqEFMQMCAQK/hT4DAgEAMAoGCCqGSM49BAMCA0gAMEUCID6y9ZHmsWR/VCKnEtZuG+NtyXnT1yRZlucCdIKBRhqmAiEAtfvM2ZlmVJVSGofcWXU3qKlnjM/S3QXWowLzZwz4lqw=";
+const APPLE_ATTESTATION: &str = "o2NmbXRlYXBwbGVnYXR0U3RtdKFjeDVjgVkBlDCCAZAwggE2oAMCAQICFBOE+23UmC3NQJwkyxKEiT7QL6jQMAoGCCqGSM49BAMCMCMxITAfBgNVBAMMGEV4YW1wbGUgQXR0ZXN0YXRpb24gUm9vdDAeFw0yNjAxMDEwMDAwMDBaFw00NjAxMDEwMDAwMDBaMCYxJDAiBgNVBAMMG0FwcGxlIEFub255bW91cyBBdHRlc3RhdGlvbjBZMBMGByqGSM49AgEGCCqGSM49AwEHA0IABOggCyYp5uNN7xxzU6f9JfxILSMGp3M4F2rm45igpmDlZdzMaBMzWILHXeboLUp6Amg1qVOVJSLL6OgcavTP7gqjRTBDMAwGA1UdEwEB/wQCMAAwMwYJKoZIhvdjZAgCBCYwJKEiBCAfKz4M2iPUu8AbDXatfGMgnCilOgf/YO+62TrVII+8IjAKBggqhkjOPQQDAgNIADBFAiEAq/dHpF1mXqcRCV+303XxzwWl3o/nEvn7wpCAWRiXWBICIFO7mVIxe+lutS7uavDBfwDtM9J8puPmYN75F1sDjez6aGF1dGhEYXRhWJSjeab27q+5pV43jBGANOJ1Hmgvq58tMKsT0hJVhs4ZR0UAAAAAAAAAAAAAAAAAAAAAAAAAAAAQY3JlZGVudGlhbC1pZC0wMaUBAgMmIAEhWCDoIAsmKebjTe8cc1On/SX8SC0jBqdzOBdq5uOYoKZg5SJYIGXczGgTM1iCx13m6C1KegJoNalTlSUiy+joHGr0z+4K";
+const CLIENT_DATA: &str = r#"{"type":"webauthn.create","challenge":"ZGlkLWNyeXB0by1hdHRlc3RhdGlvbg","origin":"https://example.com"}"#;
+
+fn client_data_hash() -> Vec<u8> {
+    Sha256::digest(CLIENT_DATA.as_bytes()).to_vec()
+}
+
+fn anchors() -> Vec<Vec<u8>> {
+    vec![STANDARD.decode(ROOT_CERTIFICATE).unwrap()]
+}
+
+fn attestation(encoded: &str) -> AttestationObject {
+    AttestationObject::from_bytes(&STANDARD.decode(encoded).unwrap()).unwrap()
+}
+
+fn self_attestation(key: &AnySigningKey, format: &str) -> Vec<u8> {
+    let jwk = key.verifying_key().to_jwk().unwrap();
+    let cose_key = CoseKeyBuilder::new_ec2_pub_key(
+        iana::EllipticCurve::P_256,
+        base64_url::decode(&jwk.x.unwrap()).unwrap(),
+        base64_url::decode(&jwk.y.unwrap()).unwrap(),
+    )
+    .algorithm(iana::Algorithm::ES256)
+    .build();
+
+    let mut auth_data = Sha256::digest(b"example.com").to_vec();
+    auth_data.extend_from_slice(&[0x41, 0, 0, 0, 1]);
+    auth_data.extend_from_slice(&[0u8; 16]);
+    auth_data.extend_from_slice(&[0, 4, 1, 2, 3, 4]);
+    auth_data.extend(cose_key.to_vec().unwrap());
+
+    let mut signed = auth_data.clone();
+    signed.extend(client_data_hash());
+    let signature = key.sign_bytes(&signed, Algorithm::ES256).unwrap();
+
+    let object = Value::Map(vec![
+        (Value::from("fmt"), Value::from(format)),
+        (
+            Value::from("attStmt"),
+            Value::Map(vec![
+                (Value::from("alg"), Value::from(-7)),
+                (
+                    Value::from("sig"),
+                    Value::Bytes(signature_to_der(&signature, Algorithm::ES256).unwrap()),
+                ),
+            ]),
+        ),
+        (Value::from("authData"), Value::Bytes(auth_data)),
+    ]);
+    let mut bytes = Vec::new();
+    coset::cbor::ser::into_writer(&object, &mut bytes).unwrap();
+    bytes
+}
+
+#[test]
+pub fn attestation_packed() {
+    let at = Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap();
+    let object = attestation(PACKED_ATTESTATION);
+    assert_eq!(object.format, ATTESTATION_FORMAT_PACKED);
+    assert_eq!(
+        object.auth_data.rp_id_hash,
+        Sha256::digest(b"example.com").to_vec()
+    );
+    assert_eq!(object.auth_data.credential_id, b"credential-id-01".to_vec());
+
+    let verified = object
+        .verify_at(&client_data_hash(), &anchors(), at)
+        .unwrap();
+    assert_eq!(verified.security_level, SecurityLevel::Authenticator);
+    assert_eq!(
+        verified.aaguid,
+        hex::decode("0102030405060708090a0b0c0d0e0f10").unwrap()
+    );
+    assert_eq!(verified.certificate_chain.len(), 1);
+    verified.require_hardware().unwrap();
+    verified
+        .check_key(&object.auth_data.verifying_key().unwrap())
+        .unwrap();
+
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    assert_eq!(
+        verified.check_key(&other.verifying_key()).err(),
+        Some(Error::ATTESTATION_KEY_MISMATCH)
+    );
+    assert_eq!(
+        object.verify_at(b"other", &anchors(), at).err(),
+        Some(Error::ATTESTATION_INVALID)
+    );
+    assert_eq!(
+        object.verify_at(&client_data_hash(), &[], at).err(),
+        Some(Error::X509_CHAIN_UNTRUSTED)
+    );
+}
+
+#[test]
+pub fn attestation_android_key() {
+    let at = Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap();
+    let object = attestation(ANDROID_KEY_ATTESTATION);
+    assert_eq!(object.format, ATTESTATION_FORMAT_ANDROID_KEY);
+
+    let verified = object
+        .verify_at(&client_data_hash(), &anchors(), at)
+        .unwrap();
+    assert_eq!(verified.security_level, SecurityLevel::TrustedEnvironment);
+    verified.require_hardware().unwrap();
+    assert_eq!(
+        object
+            .verify_at(&Sha256::digest(b"other"), &anchors(), at)
+            .err(),
+        Some(Error::ATTESTATION_INVALID)
+    );
+
+    let native = KeyAttestation {
+        format: String::from(ATTESTATION_FORMAT_ANDROID_KEY),
+        statement: Vec::new(),
+        certificate_chain: vec![STANDARD.decode(ANDROID_CERTIFICATE).unwrap()],
+    };
+    let verified = verify_key_attestation_at(&native, &client_data_hash(), &anchors(), at).unwrap();
+    assert_eq!(verified.security_level, SecurityLevel::TrustedEnvironment);
+    verified
+        .check_key(&object.auth_data.verifying_key().unwrap())
+        .unwrap();
+    assert_eq!(
+        verify_key_attestation_at(&native, b"other", &anchors(), at).err(),
+        Some(Error::ATTESTATION_INVALID)
+    );
+}
+
+#[test]
+pub fn attestation_apple() {
+    let at = Utc.with_ymd_and_hms(2030, 1, 1, 0, 0, 0).unwrap();
+    let object = attestation(APPLE_ATTESTATION);
+    assert_eq!(object.format, ATTESTATION_FORMAT_APPLE);
+
+    let verified = object
+        .verify_at(&client_data_hash(), &anchors(), at)
+        .unwrap();
+    assert_eq!(verified.security_level, SecurityLevel::SecureEnclave);
+    verified
+        .check_key(&object.auth_data.verifying_key().unwrap())
+        .unwrap();
+    assert_eq!(
+        object.verify_at(b"other", &anchors(), at).err(),
+        Some(Error::ATTESTATION_INVALID)
+    );
+
+    let mut swapped = attestation(ANDROID_KEY_ATTESTATION);
+    swapped.format = String::from(ATTESTATION_FORMAT_APPLE);
+    assert_eq!(
+        swapped.verify_at(&client_data_hash(), &anchors(), at).err(),
+        Some(Error::ATTESTATION_INVALID)
+    );
+}
+
+#[test]
+pub fn attestation_self_attested_and_unsupported() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let object =
+        AttestationObject::from_bytes(&self_attestation(&key, ATTESTATION_FORMAT_PACKED)).unwrap();
+    assert_eq!(object.auth_data.sign_count, 1);
+
+    let verified = object.verify(&client_data_hash(), &[]).unwrap();
+    assert_eq!(verified.security_level, SecurityLevel::SelfAttested);
+    verified.check_key(&key.verifying_key()).unwrap();
+    assert_eq!(
+        verified.require_hardware().err(),
+        Some(Error::ATTESTATION_NOT_HARDWARE_BACKED)
+    );
+
+    let attestation = KeyAttestation {
+        format: String::from(ATTESTATION_FORMAT_PACKED),
+        statement: self_attestation(&key, ATTESTATION_FORMAT_PACKED),
+        certificate_chain: Vec::new(),
+    };
+    let verified =
+        verify_key_attestation_at(&attestation, &client_data_hash(), &[], Utc::now()).unwrap();
+    assert_eq!(verified.security_level, SecurityLevel::SelfAttested);
+
+    let object = AttestationObject::from_bytes(&self_attestation(&key, "tpm")).unwrap();
+    assert_eq!(
+        object.verify(&client_data_hash(), &[]).err(),
+        Some(Error::ATTESTATION_UNSUPPORTED_FORMAT)
+    );
+
+    let backend = InMemoryKeyBackend::new();
+    let handle = backend.generate_key("wallet", Algorithm::ES256).unwrap();
+    let attestation = backend.attest(&handle, &client_data_hash()).unwrap();
+    assert_eq!(
+        verify_key_attestation_at(&attestation, &client_data_hash(), &[], Utc::now()).err(),
+        Some(Error::ATTESTATION_UNSUPPORTED_FORMAT)
+    );
+
+    assert_eq!(
+        AttestationObject::from_bytes(&[0xa0]).err(),
+        Some(Error::ATTESTATION_MALFORMED)
+    );
+    assert_eq!(
+        AttestationObject::from_bytes(b"not cbor").err(),
+        Some(Error::ATTESTATION_MALFORMED)
+    );
+}
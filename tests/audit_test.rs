@@ -0,0 +1,46 @@
+use std::sync::{Arc, Mutex};
+
+use did_crypto::{
+    algorithms::Algorithm,
+    audit::{self, AuditRecord},
+    crypto::any::AnySigningKey,
+    metrics::Operation,
+    signer::{sign, sign_batch},
+};
+use sha2::{Digest, Sha256};
+
+#[test]
+pub fn signing_key_usage_is_audited() {
+    let records: Arc<Mutex<Vec<AuditRecord>>> = Arc::default();
+    let sink = records.clone();
+    audit::set_audit_sink(Arc::new(move |record: &AuditRecord| {
+        sink.lock().unwrap().push(record.clone());
+    }));
+
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    sign("did:example:123", &key, Algorithm::ES256).unwrap();
+    sign_batch(&[b"first", b"second"], &key, Algorithm::ES256).unwrap();
+    assert!(sign("did:example:123", &key, Algorithm::ES384).is_err());
+
+    audit::clear_audit_sink();
+    sign("did:example:123", &key, Algorithm::ES256).unwrap();
+
+    let records = records.lock().unwrap();
+    let digests: Vec<&str> = records
+        .iter()
+        .map(|record| record.payload_digest.as_str())
+        .collect();
+    let expected: Vec<String> = ["did:example:123", "first", "second"]
+        .iter()
+        .map(|payload| base64_url::encode(Sha256::digest(payload.as_bytes()).as_slice()))
+        .collect();
+    assert_eq!(digests, expected);
+
+    let fingerprint = key.fingerprint().unwrap();
+    for record in records.iter() {
+        assert_eq!(record.key_id.as_deref(), Some(fingerprint.as_str()));
+        assert_eq!(record.operation, Operation::Sign);
+        assert_eq!(record.algorithm, Algorithm::ES256);
+    }
+    assert_eq!(records[1].timestamp, records[2].timestamp);
+}
@@ -0,0 +1,122 @@
+use did_crypto::crypto::bbs::{proof_gen, BbsSigningKey, BbsVerifyingKey};
+
+const KEY_MATERIAL: &str =
+    "746869732d49532d6a7573742d616e2d546573742d494b4d2d746f2d67656e65726174652d246528724074232d6b6579";
+const KEY_INFO: &str =
+    "746869732d49532d616e2d6f7074696f6e616c2d6b65792d696e666f2d746f2d67656e65726174652d246528724074232d6b6579";
+const HEADER: &[u8] = b"did:example:issuer#bbs-key-1";
+
+const MESSAGES: [&[u8]; 4] = [
+    b"given_name: John",
+    b"family_name: Doe",
+    b"birthdate: 1990-01-01",
+    b"nationality: LK",
+];
+
+fn signing_key() -> BbsSigningKey {
+    BbsSigningKey::from_key_material(
+        &hex::decode(KEY_MATERIAL).unwrap(),
+        &hex::decode(KEY_INFO).unwrap(),
+    )
+    .unwrap()
+}
+
+#[test]
+pub fn bbs_signing_and_verifying() {
+    let key = signing_key();
+    let signature = key.sign(HEADER, &MESSAGES).unwrap();
+
+    let verifying_key = BbsVerifyingKey::from_bytes(&key.verifying_key().to_bytes()).unwrap();
+    assert!(verifying_key.verify(&signature, HEADER, &MESSAGES).unwrap());
+
+    let mut tampered = MESSAGES;
+    tampered[1] = b"family_name: Smith";
+    assert!(!verifying_key.verify(&signature, HEADER, &tampered).unwrap());
+    assert!(!verifying_key
+        .verify(&signature, b"another header", &MESSAGES)
+        .unwrap());
+}
+
+#[test]
+pub fn bbs_proof_reveals_subset() {
+    let key = signing_key();
+    let verifying_key = key.verifying_key();
+    let signature = key.sign(HEADER, &MESSAGES).unwrap();
+
+    let proof = proof_gen(
+        &verifying_key,
+        &signature,
+        HEADER,
+        b"nonce-1234",
+        &MESSAGES,
+        &[0, 3],
+    )
+    .unwrap();
+
+    assert!(verifying_key
+        .proof_verify(
+            &proof,
+            HEADER,
+            b"nonce-1234",
+            &[(0, MESSAGES[0]), (3, MESSAGES[3])],
+        )
+        .unwrap());
+    assert!(!verifying_key
+        .proof_verify(
+            &proof,
+            HEADER,
+            b"another-nonce",
+            &[(0, MESSAGES[0]), (3, MESSAGES[3])],
+        )
+        .unwrap());
+    assert!(!verifying_key
+        .proof_verify(
+            &proof,
+            HEADER,
+            b"nonce-1234",
+            &[(0, MESSAGES[0]), (3, b"nationality: US")],
+        )
+        .unwrap());
+}
+
+#[test]
+pub fn bbs_proofs_are_unlinkable() {
+    let key = signing_key();
+    let verifying_key = key.verifying_key();
+    let signature = key.sign(HEADER, &MESSAGES).unwrap();
+
+    let first = proof_gen(&verifying_key, &signature, HEADER, b"", &MESSAGES, &[1]).unwrap();
+    let second = proof_gen(&verifying_key, &signature, HEADER, b"", &MESSAGES, &[1]).unwrap();
+
+    assert_ne!(first, second);
+    assert!(verifying_key
+        .proof_verify(&second, HEADER, b"", &[(1, MESSAGES[1])])
+        .unwrap());
+}
+
+#[test]
+pub fn bbs_proof_rejects_invalid_index() {
+    let key = signing_key();
+    let signature = key.sign(HEADER, &MESSAGES).unwrap();
+
+    assert!(proof_gen(
+        &key.verifying_key(),
+        &signature,
+        HEADER,
+        b"",
+        &MESSAGES,
+        &[4],
+    )
+    .is_err());
+}
+
+#[test]
+pub fn bbs_signing_key_bytes() {
+    let key = signing_key();
+    let bytes = key.to_bytes();
+    assert_eq!(bytes.len(), 32);
+
+    let restored = BbsSigningKey::from_bytes(&bytes).unwrap();
+    assert_eq!(restored.to_bytes(), bytes);
+    assert!(restored.verifying_key() == key.verifying_key());
+}
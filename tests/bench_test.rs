@@ -0,0 +1,78 @@
+#![cfg(feature = "bench")]
+
+use did_crypto::{
+    algorithms::Algorithm,
+    bench::{self, BenchOptions, BenchReport, CountingAllocator, RegressionMetric},
+    errors::Error,
+    metrics::Operation,
+};
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+pub fn bench_sign_and_verify() {
+    let options = BenchOptions::new()
+        .algorithms(&[Algorithm::ES256, Algorithm::HS256])
+        .iterations(20)
+        .warmup(2)
+        .message_size(64);
+    let report = bench::run(&options).unwrap();
+    assert_eq!(report.results.len(), 4);
+
+    let sign = report.get(Algorithm::ES256, Operation::Sign).unwrap();
+    assert_eq!(sign.iterations, 20);
+    assert_eq!(sign.message_size, 64);
+    assert!(sign.ops_per_sec > 0.0);
+    assert!(sign.min <= sign.p50 && sign.p50 <= sign.p99 && sign.p99 <= sign.max);
+    assert!(sign.allocations.unwrap() > 0);
+    assert!(sign.allocated_bytes.unwrap() > 0);
+    assert!(report.get(Algorithm::HS256, Operation::Verify).is_some());
+    assert!(report.get(Algorithm::ES384, Operation::Sign).is_none());
+
+    let parsed = BenchReport::from_json(&report.to_json().unwrap()).unwrap();
+    assert_eq!(parsed, report);
+    assert_eq!(
+        BenchReport::from_json("{}").err(),
+        Some(Error::BENCH_REPORT_MALFORMED)
+    );
+
+    #[cfg(feature = "rsa")]
+    {
+        let rsa = bench::run(
+            &BenchOptions::new()
+                .algorithms(&[Algorithm::RS256, Algorithm::PS256])
+                .iterations(2)
+                .warmup(0),
+        )
+        .unwrap();
+        assert!(rsa.get(Algorithm::PS256, Operation::Verify).is_some());
+    }
+}
+
+#[test]
+pub fn bench_regressions() {
+    let baseline = bench::run(
+        &BenchOptions::new()
+            .algorithms(&[Algorithm::ES256])
+            .iterations(5),
+    )
+    .unwrap();
+    assert!(baseline.compare(&baseline, 0.0).is_empty());
+    assert_eq!(baseline.check_regressions(&baseline, 0.0), Ok(()));
+
+    let mut slower = baseline.clone();
+    slower.results[0].ops_per_sec = baseline.results[0].ops_per_sec / 2.0;
+    slower.results[1].allocations = baseline.results[1].allocations.map(|val| val + 1);
+    let regressions = slower.compare(&baseline, 0.2);
+    assert_eq!(regressions.len(), 2);
+    assert_eq!(regressions[0].metric, RegressionMetric::Throughput);
+    assert_eq!(regressions[0].operation, Operation::Sign);
+    assert_eq!(regressions[1].metric, RegressionMetric::Allocations);
+    assert!(slower.compare(&baseline, 0.6).len() == 1);
+    assert_eq!(
+        slower.check_regressions(&baseline, 0.2).err(),
+        Some(Error::BENCH_REGRESSION)
+    );
+    assert!(BenchReport::default().compare(&baseline, 0.2).is_empty());
+}
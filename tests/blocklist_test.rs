@@ -0,0 +1,95 @@
+use std::sync::Arc;
+
+use chrono::{TimeDelta, Utc};
+use did_crypto::{
+    algorithms::Algorithm,
+    blocklist::{self, MemoryTokenBlocklist, TokenBlocklist},
+    crypto::any::AnySigningKey,
+    errors::Error,
+    jwt::{Header, Payload, JWT},
+    nonce,
+};
+use serde_json::{json, Value};
+
+fn signed_jwt(key: &AnySigningKey, payload: Value) -> JWT {
+    let mut jwt = JWT {
+        header: Header::new(String::from("did:example:123#key-1"), Algorithm::ES256),
+        payload: Payload(payload),
+        signature: None,
+    };
+    jwt.sign(key).unwrap();
+    JWT::from_token(&jwt.to_token().unwrap()).unwrap()
+}
+
+#[test]
+pub fn memory_blocklist_keys_and_expiry() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let exp = Utc::now().timestamp() + 60;
+    let jti = nonce::generate();
+    let jwt = signed_jwt(&key, json!({ "jti": jti, "exp": exp }));
+    let untracked = signed_jwt(&key, json!({ "exp": exp }));
+
+    let keys = blocklist::token_keys(&jwt).unwrap();
+    assert_eq!(keys[0], blocklist::jti_key(&jti));
+    assert_eq!(keys[1], blocklist::hash_key(&jwt).unwrap());
+    assert!(keys[1].starts_with("sha256:"));
+    assert_eq!(
+        blocklist::token_keys(&untracked).unwrap(),
+        vec![blocklist::hash_key(&untracked).unwrap()]
+    );
+    assert_ne!(
+        blocklist::hash_key(&jwt).unwrap(),
+        blocklist::hash_key(&untracked).unwrap()
+    );
+
+    let list = MemoryTokenBlocklist::new();
+    list.check(&jwt).unwrap();
+    list.revoke(&jwt).unwrap();
+    list.revoke(&untracked).unwrap();
+    assert_eq!(list.check(&jwt).err(), Some(Error::JWT_REVOKED));
+    assert_eq!(list.check(&untracked).err(), Some(Error::JWT_REVOKED));
+    assert!(list.is_blocked(&blocklist::jti_key(&jti)).unwrap());
+    assert_eq!(list.len(), 2);
+
+    assert!(list.unblock(&blocklist::jti_key(&jti)));
+    list.check(&jwt).unwrap();
+
+    list.block("jti:expired", Utc::now() - TimeDelta::seconds(1))
+        .unwrap();
+    assert!(!list.is_blocked("jti:expired").unwrap());
+    list.purge_expired();
+    assert_eq!(list.len(), 1);
+
+    let unsigned = JWT {
+        header: Header::new(String::new(), Algorithm::ES256),
+        payload: Payload(json!({})),
+        signature: None,
+    };
+    assert_eq!(
+        list.revoke(&unsigned).err(),
+        Some(Error::JWT_TOKEN_NOT_SIGNED)
+    );
+}
+
+#[test]
+pub fn jwt_validation_consults_blocklist() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let exp = Utc::now().timestamp() + 60;
+    let revoked = signed_jwt(&key, json!({ "jti": nonce::generate(), "exp": exp }));
+    let active = signed_jwt(&key, json!({ "jti": nonce::generate(), "exp": exp }));
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+
+    let list = Arc::new(MemoryTokenBlocklist::new());
+    list.revoke(&revoked).unwrap();
+    blocklist::set_token_blocklist(list.clone());
+
+    assert_eq!(
+        revoked.validate(key.verifying_key()).err(),
+        Some(Error::JWT_REVOKED)
+    );
+    assert!(!revoked.validate(other.verifying_key()).unwrap());
+    assert!(active.validate(key.verifying_key()).unwrap());
+
+    blocklist::clear_token_blocklist();
+    assert!(revoked.validate(key.verifying_key()).unwrap());
+}
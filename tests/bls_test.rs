@@ -0,0 +1,136 @@
+use did_crypto::{
+    crypto::bls::{
+        Bls12381G1SigningKey, Bls12381G1VerifyingKey, Bls12381G2SigningKey, Bls12381G2VerifyingKey,
+    },
+    jwk::Jwk,
+};
+
+const KEY_MATERIAL: &str = "0099ff8877665544332211aabbccddeeff0011223344556677889900aabbccdd";
+const MESSAGE: &[u8] = b"did:example:123#bls-key-1";
+
+#[test]
+pub fn bls_g1_signing_and_verifying() {
+    let key =
+        Bls12381G1SigningKey::from_key_material(&hex::decode(KEY_MATERIAL).unwrap(), &[]).unwrap();
+    let verifying_key =
+        Bls12381G1VerifyingKey::from_bytes(&key.verifying_key().to_bytes()).unwrap();
+
+    let signature = key.sign(MESSAGE);
+    assert!(verifying_key.verify(MESSAGE, &signature).unwrap());
+    assert!(!verifying_key
+        .verify(b"another message", &signature)
+        .unwrap());
+    assert!(verifying_key
+        .verify_possession(&key.prove_possession())
+        .unwrap());
+}
+
+#[test]
+pub fn bls_g2_signing_and_verifying() {
+    let key =
+        Bls12381G2SigningKey::from_key_material(&hex::decode(KEY_MATERIAL).unwrap(), &[]).unwrap();
+    let verifying_key =
+        Bls12381G2VerifyingKey::from_bytes(&key.verifying_key().to_bytes()).unwrap();
+
+    let signature = key.sign(MESSAGE);
+    assert!(verifying_key.verify(MESSAGE, &signature).unwrap());
+    assert!(!verifying_key
+        .verify(b"another message", &signature)
+        .unwrap());
+    assert!(verifying_key
+        .verify_possession(&key.prove_possession())
+        .unwrap());
+}
+
+#[test]
+pub fn bls_g1_aggregation() {
+    let keys: Vec<Bls12381G1SigningKey> = (0..3)
+        .map(|_| Bls12381G1SigningKey::generate().unwrap())
+        .collect();
+    let verifying_keys: Vec<Bls12381G1VerifyingKey> =
+        keys.iter().map(|key| key.verifying_key()).collect();
+
+    let same_message: Vec<String> = keys.iter().map(|key| key.sign(MESSAGE)).collect();
+    let aggregate = Bls12381G1VerifyingKey::aggregate_signatures(
+        &same_message
+            .iter()
+            .map(|sig| sig.as_str())
+            .collect::<Vec<&str>>(),
+    )
+    .unwrap();
+    assert!(
+        Bls12381G1VerifyingKey::fast_aggregate_verify(&verifying_keys, MESSAGE, &aggregate)
+            .unwrap()
+    );
+
+    let messages: [&[u8]; 3] = [b"first", b"second", b"third"];
+    let distinct: Vec<String> = keys
+        .iter()
+        .zip(messages)
+        .map(|(key, message)| key.sign(message))
+        .collect();
+    let aggregate = Bls12381G1VerifyingKey::aggregate_signatures(
+        &distinct
+            .iter()
+            .map(|sig| sig.as_str())
+            .collect::<Vec<&str>>(),
+    )
+    .unwrap();
+    assert!(
+        Bls12381G1VerifyingKey::aggregate_verify(&verifying_keys, &messages, &aggregate).unwrap()
+    );
+    assert!(!Bls12381G1VerifyingKey::aggregate_verify(
+        &verifying_keys,
+        &[b"first", b"third", b"second"],
+        &aggregate
+    )
+    .unwrap());
+}
+
+#[test]
+pub fn bls_g2_aggregation() {
+    let keys: Vec<Bls12381G2SigningKey> = (0..3)
+        .map(|_| Bls12381G2SigningKey::generate().unwrap())
+        .collect();
+    let verifying_keys: Vec<Bls12381G2VerifyingKey> =
+        keys.iter().map(|key| key.verifying_key()).collect();
+
+    let signatures: Vec<String> = keys.iter().map(|key| key.sign(MESSAGE)).collect();
+    let aggregate = Bls12381G2VerifyingKey::aggregate_signatures(
+        &signatures
+            .iter()
+            .map(|sig| sig.as_str())
+            .collect::<Vec<&str>>(),
+    )
+    .unwrap();
+    assert!(
+        Bls12381G2VerifyingKey::fast_aggregate_verify(&verifying_keys, MESSAGE, &aggregate)
+            .unwrap()
+    );
+    assert!(!Bls12381G2VerifyingKey::fast_aggregate_verify(
+        &verifying_keys[..2],
+        MESSAGE,
+        &aggregate
+    )
+    .unwrap());
+}
+
+#[test]
+pub fn bls_key_encodings() {
+    let key =
+        Bls12381G2SigningKey::from_key_material(&hex::decode(KEY_MATERIAL).unwrap(), &[]).unwrap();
+    let verifying_key = key.verifying_key();
+
+    let multicodec = verifying_key.to_multicodec();
+    assert_eq!(&multicodec[..2], &[0xeb, 0x01]);
+    assert!(Bls12381G2VerifyingKey::from_multicodec(&multicodec).unwrap() == verifying_key);
+    assert!(Bls12381G1VerifyingKey::from_multicodec(&multicodec).is_err());
+
+    let jwk = Jwk::from_json(&verifying_key.to_jwk().to_json().unwrap()).unwrap();
+    assert_eq!(jwk.kty, "OKP");
+    assert_eq!(jwk.crv.as_deref(), Some("Bls12381G2"));
+    assert!(Bls12381G2VerifyingKey::from_jwk(&jwk).unwrap() == verifying_key);
+
+    let restored = Bls12381G2SigningKey::from_jwk(&key.to_jwk()).unwrap();
+    assert_eq!(restored.to_bytes(), key.to_bytes());
+}
@@ -0,0 +1,113 @@
+use std::str::FromStr;
+
+use did_crypto::{
+    caip::{checksum_address, parse_eip155_address, AccountId, ChainId, SOLANA_MAINNET},
+    errors::Error,
+};
+
+const ADDRESS: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+
+#[test]
+pub fn caip2_chain_ids() {
+    let chain_id = ChainId::from_str("eip155:1").unwrap();
+    assert_eq!(chain_id, ChainId::eip155(1));
+    assert_eq!(chain_id.eip155_chain_id(), Some(1));
+    assert_eq!(chain_id.to_string(), "eip155:1");
+
+    let chain_id = ChainId::from_str("cosmos:cosmoshub-3").unwrap();
+    assert_eq!(chain_id.namespace, "cosmos");
+    assert_eq!(chain_id.eip155_chain_id(), None);
+
+    for invalid in [
+        "eip155",
+        "EIP155:1",
+        "ab:1",
+        "eip155:",
+        "namespace9:1",
+        "eip155:0123456789abcdef0123456789abcdef0",
+    ] {
+        assert_eq!(
+            ChainId::from_str(invalid).err(),
+            Some(Error::CAIP_INVALID_CHAIN_ID)
+        );
+    }
+}
+
+#[test]
+pub fn caip10_account_ids() {
+    let account = AccountId::from_str(&format!("eip155:1:{}", ADDRESS)).unwrap();
+    assert_eq!(account.chain_id, ChainId::eip155(1));
+    assert_eq!(account.to_string(), format!("eip155:1:{}", ADDRESS));
+    assert_eq!(
+        AccountId::eip155(1, &parse_eip155_address(ADDRESS).unwrap()),
+        account
+    );
+
+    let lower = ADDRESS.to_ascii_lowercase();
+    assert!(AccountId::from_str(&format!("eip155:1:{}", lower)).is_ok());
+    assert_eq!(
+        checksum_address(&parse_eip155_address(&lower).unwrap()),
+        ADDRESS
+    );
+
+    let account = AccountId::from_str(
+        "bip122:000000000019d6689c085ae165831e93:128Lkh3S7CkDTBZ8W7BbpsN3YYizJMp8p6",
+    )
+    .unwrap();
+    assert_eq!(account.chain_id.namespace, "bip122");
+
+    for invalid in [
+        String::from("eip155:1"),
+        format!("eip155:1:{}", ADDRESS.replace('a', "A")),
+        String::from("eip155:1:0x1234"),
+        format!("solana:{}:0x1234", SOLANA_MAINNET),
+        String::from("cosmos:cosmoshub-3:cosmos 1"),
+    ] {
+        assert_eq!(
+            AccountId::from_str(&invalid).err(),
+            Some(Error::CAIP_INVALID_ACCOUNT_ID)
+        );
+    }
+}
+
+#[cfg(feature = "es256k")]
+#[test]
+pub fn caip10_from_secp256k1_key() {
+    use did_crypto::crypto::ecdsa::_256k::P256kSigningKey;
+
+    let key = P256kSigningKey::from_bytes(
+        &hex::decode("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318").unwrap(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        AccountId::from_secp256k1_key(137, &key.verifying_key()).to_string(),
+        "eip155:137:0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+    );
+}
+
+#[cfg(feature = "eddsa")]
+#[test]
+pub fn caip10_from_ed25519_key() {
+    use did_crypto::{
+        algorithms::Algorithm,
+        caip::{parse_solana_address, solana_address},
+        crypto::{any::AnySigningKey, any::AnyVerifyingKey},
+    };
+
+    let key = match AnySigningKey::generate(Algorithm::EdDSA)
+        .unwrap()
+        .verifying_key()
+    {
+        AnyVerifyingKey::EdDSA(key) => key,
+        _ => panic!(),
+    };
+
+    let account = AccountId::from_ed25519_key(SOLANA_MAINNET, &key).unwrap();
+    assert_eq!(account.address, solana_address(&key));
+    assert_eq!(
+        parse_solana_address(&account.address).unwrap().to_vec(),
+        key.to_bytes()
+    );
+    assert_eq!(AccountId::from_str(&account.to_string()).unwrap(), account);
+}
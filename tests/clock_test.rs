@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use did_crypto::{
+    algorithms::Algorithm,
+    clock::{self, Clock, FixedClock, OffsetClock, SystemClock},
+    crypto::any::AnySigningKey,
+    data_integrity::{ProofCheck, ProofOptions},
+    errors::Error,
+    jwt::{Header, Payload, JWT},
+    keystore::{self, InMemoryKeyStore, KeyPurpose, KeyStore, KeyValidity},
+};
+use serde_json::json;
+
+const KID: &str = "did:example:123#key-1";
+
+#[test]
+pub fn clock_sources() {
+    let at = DateTime::from_timestamp(1704067200, 0).unwrap();
+    let fixed = FixedClock::new(at);
+    assert_eq!(fixed.now(), at);
+    fixed.advance(TimeDelta::minutes(5));
+    assert_eq!(fixed.now(), at + TimeDelta::minutes(5));
+    fixed.set(at);
+    assert_eq!(fixed.now(), at);
+
+    let skewed = OffsetClock::new(TimeDelta::hours(-2));
+    assert!(skewed.now() < Utc::now() - TimeDelta::minutes(119));
+    assert!(SystemClock.now() <= Utc::now());
+    assert_eq!((move || at).now(), at);
+}
+
+#[test]
+pub fn validation_uses_configured_clock() {
+    let at = DateTime::from_timestamp(1704067200, 0).unwrap();
+    let fixed = Arc::new(FixedClock::new(at));
+    clock::set_clock(fixed.clone());
+    assert_eq!(clock::now(), at);
+
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let mut jwt = JWT {
+        header: Header::new(String::from(KID), Algorithm::ES256),
+        payload: Payload(json!({
+            "iat": at.timestamp(),
+            "nbf": at.timestamp(),
+            "exp": (at + TimeDelta::hours(1)).timestamp(),
+        })),
+        signature: None,
+    };
+    jwt.sign(&key).unwrap();
+    let verifying_key = key.verifying_key();
+    assert!(jwt.validate(&verifying_key).unwrap());
+
+    let options = ProofOptions::new("ecdsa-jcs-2019", KID)
+        .with_created(at)
+        .with_expires(at + TimeDelta::minutes(5));
+    options.check(&ProofCheck::new()).unwrap();
+
+    let store = InMemoryKeyStore::new();
+    store.add(KID, key).unwrap();
+    store
+        .set_validity(
+            KID,
+            KeyValidity {
+                not_before: Some(at - TimeDelta::days(1)),
+                not_after: Some(at + TimeDelta::days(1)),
+                revoked: false,
+            },
+        )
+        .unwrap();
+    keystore::signing_key(&store, KID, KeyPurpose::AssertionMethod).unwrap();
+
+    fixed.advance(TimeDelta::days(2));
+    assert!(!jwt.validate(&verifying_key).unwrap());
+    assert_eq!(
+        options.check(&ProofCheck::new()).err(),
+        Some(Error::DATA_INTEGRITY_PROOF_EXPIRED)
+    );
+    assert_eq!(
+        keystore::signing_key(&store, KID, KeyPurpose::AssertionMethod).err(),
+        Some(Error::KEYSTORE_KEY_OUTSIDE_VALIDITY)
+    );
+
+    fixed.set(at - TimeDelta::hours(1));
+    assert_eq!(
+        options.check(&ProofCheck::new()).err(),
+        Some(Error::DATA_INTEGRITY_PROOF_EXPIRED)
+    );
+
+    clock::clear_clock();
+    assert!(clock::now() > at + TimeDelta::days(365));
+    assert!(!jwt.validate(&verifying_key).unwrap());
+}
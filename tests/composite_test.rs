@@ -0,0 +1,113 @@
+#![cfg(feature = "mldsa")]
+
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::composite::{MLDSA65Ed25519SigningKey, MLDSA65Ed25519VerifyingKey},
+    jwk::Jwk,
+    signer::sign,
+    verifier::{verify, VerificationOptions},
+};
+
+const KEY: &str = "0101010101010101010101010101010101010101010101010101010101010101aa7f263d0a1a671a4c06ea22800c1391dd8974174f01d0e5a848fe51bdd1bcf8";
+const MESSAGE: &str = "did:example:123#composite-key-1";
+
+fn flip_byte(signature: &str, index: usize) -> String {
+    let mut bytes = base64_url::decode(signature).unwrap();
+    bytes[index] ^= 0x01;
+    base64_url::encode(&bytes)
+}
+
+#[test]
+pub fn mldsa65_ed25519_signing_and_verifying() {
+    let key = MLDSA65Ed25519SigningKey::from_bytes(&hex::decode(KEY).unwrap()).unwrap();
+    let verifying_key = key.verifying_key();
+
+    let signature = sign(String::from(MESSAGE), key, Algorithm::MLDSA65Ed25519).unwrap();
+    let length = base64_url::decode(&signature).unwrap().len();
+    assert_eq!(length, 3309 + 64);
+    assert!(verify(
+        String::from(MESSAGE),
+        signature.clone(),
+        verifying_key.clone(),
+        Algorithm::MLDSA65Ed25519
+    )
+    .unwrap());
+
+    for index in [0, length - 1] {
+        assert!(!verify(
+            String::from(MESSAGE),
+            flip_byte(&signature, index),
+            verifying_key.clone(),
+            Algorithm::MLDSA65Ed25519
+        )
+        .unwrap());
+    }
+
+    let truncated = base64_url::encode(&base64_url::decode(&signature).unwrap()[..3309]);
+    assert!(verify(
+        String::from(MESSAGE),
+        truncated,
+        verifying_key.clone(),
+        Algorithm::MLDSA65Ed25519
+    )
+    .is_err());
+    assert!(verify(
+        String::from(MESSAGE),
+        signature,
+        verifying_key,
+        Algorithm::MLDSA65
+    )
+    .is_err());
+}
+
+#[test]
+pub fn mldsa65_ed25519_key_encodings() {
+    let key = MLDSA65Ed25519SigningKey::from_bytes(&hex::decode(KEY).unwrap()).unwrap();
+    let verifying_key = key.verifying_key();
+    assert_eq!(verifying_key.to_bytes().len(), 1952 + 32);
+    assert!(
+        MLDSA65Ed25519VerifyingKey::from_bytes(&verifying_key.to_bytes()).unwrap() == verifying_key
+    );
+
+    let jwk = Jwk::from_json(&verifying_key.to_jwk().to_json().unwrap()).unwrap();
+    assert_eq!(jwk.kty, "AKP");
+    assert_eq!(jwk.alg.as_deref(), Some("ML-DSA-65-Ed25519"));
+    assert!(MLDSA65Ed25519VerifyingKey::from_jwk(&jwk).unwrap() == verifying_key);
+
+    let restored = MLDSA65Ed25519SigningKey::from_jwk(&key.to_jwk()).unwrap();
+    assert_eq!(restored.to_bytes(), hex::decode(KEY).unwrap());
+    assert!(MLDSA65Ed25519SigningKey::generate().verifying_key() != verifying_key);
+}
+
+#[test]
+pub fn mldsa65_ed25519_partial_verification() {
+    let key = MLDSA65Ed25519SigningKey::from_bytes(&hex::decode(KEY).unwrap()).unwrap();
+    let verifying_key = key.verifying_key();
+    let signature = sign(String::from(MESSAGE), key, Algorithm::MLDSA65Ed25519).unwrap();
+    let length = base64_url::decode(&signature).unwrap().len();
+    let partial = VerificationOptions::default().accept_partial_signatures(true);
+
+    for index in [0, length - 1] {
+        let tampered = flip_byte(&signature, index);
+        assert!(!verifying_key
+            .verify_with_options(MESSAGE.as_bytes(), &tampered, &Default::default())
+            .unwrap());
+        assert!(verifying_key
+            .verify_with_options(MESSAGE.as_bytes(), &tampered, &partial)
+            .unwrap());
+    }
+
+    assert!(!verifying_key
+        .verify_with_options(
+            MESSAGE.as_bytes(),
+            &flip_byte(&flip_byte(&signature, 0), length - 1),
+            &partial
+        )
+        .unwrap());
+    assert!(verifying_key
+        .verify_with_options(MESSAGE.as_bytes(), "AAAA", &VerificationOptions::default())
+        .is_err());
+    assert!(!verifying_key
+        .verify_with_options(MESSAGE.as_bytes(), "AAAA", &VerificationOptions::lenient())
+        .unwrap());
+}
@@ -0,0 +1,173 @@
+#![cfg(feature = "eddsa")]
+
+use std::collections::HashMap;
+
+use did_crypto::{
+    algorithms::Algorithm, conditional::Condition, crypto::any::AnySigningKey, errors::Error,
+    jws::GeneralJws, resolver::DidResolver,
+};
+use serde_json::{json, Value};
+
+const PAYLOAD: &[u8] = b"{\"action\":\"transfer\"}";
+
+struct StaticResolver(HashMap<String, Value>);
+
+impl DidResolver for StaticResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        match self.0.get(did) {
+            Some(val) => Ok(val.clone()),
+            None => Err(Error::DID_RESOLUTION_ERROR),
+        }
+    }
+}
+
+fn key_method(id: &str, controller: &str, key: &AnySigningKey) -> Value {
+    json!({
+        "id": id,
+        "type": "JsonWebKey2020",
+        "controller": controller,
+        "publicKeyJwk": key.verifying_key().to_jwk().unwrap(),
+    })
+}
+
+fn setup() -> (Vec<AnySigningKey>, StaticResolver) {
+    let keys: Vec<AnySigningKey> = (0..4)
+        .map(|_| AnySigningKey::generate(Algorithm::EdDSA).unwrap())
+        .collect();
+    let did = "did:example:dao";
+    let document = json!({
+        "id": did,
+        "verificationMethod": [
+            key_method("#key-3", did, &keys[2]),
+            {
+                "id": "#multisig",
+                "type": "ConditionalProof2022",
+                "controller": did,
+                "threshold": 2,
+                "conditionThreshold": [
+                    key_method("did:example:dao#key-1", did, &keys[0]),
+                    key_method("did:example:dao#key-2", did, &keys[1]),
+                    "#key-3",
+                ],
+            },
+            {
+                "id": "#weighted",
+                "type": "ConditionalProof2022",
+                "controller": did,
+                "threshold": 3,
+                "conditionWeightedThreshold": [
+                    { "condition": key_method("did:example:dao#key-1", did, &keys[0]), "weight": 2 },
+                    { "condition": "#key-3", "weight": 1 },
+                    {
+                        "condition": {
+                            "id": "#treasurer",
+                            "type": "ConditionalProof2022",
+                            "controller": did,
+                            "conditionDelegated": "did:example:treasurer#key-1",
+                        },
+                        "weight": 1,
+                    },
+                ],
+            },
+            {
+                "id": "#loop",
+                "type": "ConditionalProof2022",
+                "controller": did,
+                "conditionOr": ["#loop"],
+            },
+        ],
+    });
+    let treasurer = json!({
+        "id": "did:example:treasurer",
+        "verificationMethod": [key_method("#key-1", "did:example:treasurer", &keys[3])],
+    });
+
+    let documents = HashMap::from([
+        (String::from(did), document),
+        (String::from("did:example:treasurer"), treasurer),
+    ]);
+    (keys, StaticResolver(documents))
+}
+
+#[test]
+pub fn conditional_threshold_verification() {
+    let (keys, resolver) = setup();
+    let condition = Condition::resolve(&resolver, "did:example:dao#multisig").unwrap();
+    assert_eq!(condition.id(), "did:example:dao#multisig");
+
+    let mut jws = GeneralJws::new(PAYLOAD);
+    jws.sign("did:example:dao#key-1", Algorithm::EdDSA, &keys[0])
+        .unwrap();
+    assert_eq!(
+        condition.verify_jws(&jws, &resolver).unwrap_err(),
+        Error::CONDITIONAL_PROOF_NOT_SATISFIED
+    );
+
+    jws.sign("did:example:dao#key-2", Algorithm::EdDSA, &keys[3])
+        .unwrap();
+    assert_eq!(
+        condition.verify_jws(&jws, &resolver).unwrap_err(),
+        Error::CONDITIONAL_PROOF_NOT_SATISFIED
+    );
+
+    jws.sign("did:example:dao#key-3", Algorithm::EdDSA, &keys[2])
+        .unwrap();
+    assert_eq!(
+        condition.verify_jws(&jws, &resolver).unwrap(),
+        vec!["did:example:dao#key-1", "did:example:dao#key-3"]
+    );
+}
+
+#[test]
+pub fn conditional_weighted_and_delegated_verification() {
+    let (keys, resolver) = setup();
+    let condition = Condition::resolve(&resolver, "did:example:dao#weighted").unwrap();
+
+    let mut jws = GeneralJws::new(PAYLOAD);
+    jws.sign("did:example:dao#key-3", Algorithm::EdDSA, &keys[2])
+        .unwrap();
+    jws.sign("did:example:treasurer#key-1", Algorithm::EdDSA, &keys[3])
+        .unwrap();
+    assert_eq!(
+        condition.verify_jws(&jws, &resolver).unwrap_err(),
+        Error::CONDITIONAL_PROOF_NOT_SATISFIED
+    );
+
+    jws.sign("did:example:dao#key-1", Algorithm::EdDSA, &keys[0])
+        .unwrap();
+    assert_eq!(
+        condition.verify_jws(&jws, &resolver).unwrap(),
+        vec![
+            "did:example:dao#key-1",
+            "did:example:dao#key-3",
+            "did:example:treasurer#key-1"
+        ]
+    );
+}
+
+#[test]
+pub fn conditional_malformed_methods() {
+    let (_, resolver) = setup();
+    assert_eq!(
+        Condition::resolve(&resolver, "did:example:dao#loop").unwrap_err(),
+        Error::CONDITIONAL_PROOF_MALFORMED
+    );
+    assert_eq!(
+        Condition::resolve(&resolver, "did:example:dao#missing").unwrap_err(),
+        Error::DID_VERIFICATION_METHOD_NOT_FOUND
+    );
+
+    let document = json!({
+        "id": "did:example:bad",
+        "verificationMethod": [{
+            "id": "#bad",
+            "type": "ConditionalProof2022",
+            "controller": "did:example:bad",
+            "threshold": 0,
+            "conditionThreshold": ["#bad"],
+        }],
+    });
+    assert!(Condition::from_document(&document, "#bad")
+        .unwrap_err()
+        .is_malformed_input());
+}
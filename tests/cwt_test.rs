@@ -0,0 +1,78 @@
+use chrono::Utc;
+use coset::cwt::{ClaimsSetBuilder, Timestamp};
+use did_crypto::{
+    algorithms::Algorithm,
+    cose::CoseSign1,
+    crypto::ecdsa::_256::{P256SigningKey, P256VerifyingKey},
+    cwt::CWT,
+};
+
+const PUBLIC_KEY_256_HEX: &str =
+    "04115b3fa39fae41b4e32f7721ca72f8c1781483647dabd514f08e66128bd47fce9067b90e0488c9c2a9f30f5a266a07841d6c077413ba07e74569b99d4fd3cec6";
+const PRIVATE_KEY_256_HEX: &str =
+    "7af6732f581d005afcf216f6385ff6371029242cc60840dd7d2a7a5503b7d21c";
+
+const RFC8392_PUBLIC_KEY_HEX: &str = "04143329cce7868e416927599cf65a34f3ce2ffda55a7eca69ed8919a394d42f0f60f7f1a780d8a783bfb7a2dd6b2796e8128dbbcef9d3d168db9529971a36e7b9";
+const RFC8392_SIGNED_CWT_HEX: &str = "d28443a10126a104524173796d6d657472696345434453413235365850a70175636f61703a2f2f61732e6578616d706c652e636f6d02656572696b77037818636f61703a2f2f6c696768742e6578616d706c652e636f6d041a5612aeb0051a5610d9f0061a5610d9f007420b7158405427c1ff28d23fbad1f29c4c7c6a555e601d6fa29f9179bc3d7438bacaca5acd08c8d4d4f96131680c429a01f85951ecee743a52b9b63632c57209120e1c9e30";
+
+#[test]
+pub fn cwt_rfc8392_signed_example() {
+    let token = hex::decode(RFC8392_SIGNED_CWT_HEX).unwrap();
+    let public_key =
+        || P256VerifyingKey::from_bytes(&hex::decode(RFC8392_PUBLIC_KEY_HEX).unwrap()).unwrap();
+
+    assert!(CoseSign1::from_bytes(&token)
+        .unwrap()
+        .verify(&[], public_key())
+        .unwrap());
+
+    let (cwt, valid) = CWT::validate_token(&token, public_key()).unwrap();
+    assert!(!valid);
+    assert!(cwt.alg == Algorithm::ES256);
+    assert_eq!(cwt.kid, b"AsymmetricECDSA256");
+    assert_eq!(cwt.claims.issuer.as_deref(), Some("coap://as.example.com"));
+    assert_eq!(cwt.claims.subject.as_deref(), Some("erikw"));
+}
+
+#[test]
+pub fn cwt_signing_and_validating() {
+    let now = Utc::now().timestamp();
+    let mut cwt = CWT::new(
+        b"key-1".to_vec(),
+        Algorithm::ES256,
+        ClaimsSetBuilder::new()
+            .issuer(String::from("did:example:issuer"))
+            .subject(String::from("did:example:device"))
+            .issued_at(Timestamp::WholeSeconds(now))
+            .expiration_time(Timestamp::WholeSeconds(now + 3600))
+            .build(),
+    );
+    assert!(cwt.to_token().is_err());
+
+    cwt.sign(P256SigningKey::from_bytes(&hex::decode(PRIVATE_KEY_256_HEX).unwrap()).unwrap())
+        .unwrap();
+    let token = cwt.to_token().unwrap();
+
+    let public_key =
+        || P256VerifyingKey::from_bytes(&hex::decode(PUBLIC_KEY_256_HEX).unwrap()).unwrap();
+    let (decoded, valid) = CWT::validate_token(&token, public_key()).unwrap();
+    assert!(valid);
+    assert!(decoded.claims == cwt.claims);
+
+    let mut tampered = token.clone();
+    let last = tampered.len() - 1;
+    tampered[last] ^= 0x01;
+    assert!(!CWT::validate_token(&tampered, public_key()).unwrap().1);
+
+    let mut not_yet_valid = CWT::new(
+        Vec::new(),
+        Algorithm::ES256,
+        ClaimsSetBuilder::new()
+            .not_before(Timestamp::WholeSeconds(now + 3600))
+            .build(),
+    );
+    not_yet_valid
+        .sign(P256SigningKey::from_bytes(&hex::decode(PRIVATE_KEY_256_HEX).unwrap()).unwrap())
+        .unwrap();
+    assert!(!not_yet_valid.validate(public_key()).unwrap());
+}
@@ -0,0 +1,246 @@
+use chrono::{DateTime, TimeDelta, Utc};
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    data_integrity::{
+        add_proof, jcs_cryptosuite, proofs, sign_jcs, verify_jcs, verify_proofs, ProofCheck,
+        ProofOptions, DATA_INTEGRITY_PROOF, ECDSA_JCS_2019,
+    },
+    errors::Error,
+    resolver::{DidResolver, ASSERTION_METHOD, AUTHENTICATION},
+};
+use serde_json::{json, Value};
+
+const METHOD: &str = "did:example:issuer#key-1";
+
+#[test]
+pub fn data_integrity_proof_options_round_trip() {
+    let created = DateTime::from_timestamp(1704067200, 0).unwrap();
+    let options = ProofOptions::new("eddsa-jcs-2022", METHOD)
+        .with_proof_purpose(AUTHENTICATION)
+        .with_created(created)
+        .with_expires(created + TimeDelta::hours(1))
+        .with_domain("https://verifier.example")
+        .with_challenge("abc123")
+        .with_previous_proof("urn:uuid:1");
+
+    let value = options.to_value().unwrap();
+    assert_eq!(
+        value,
+        json!({
+            "type": DATA_INTEGRITY_PROOF,
+            "cryptosuite": "eddsa-jcs-2022",
+            "verificationMethod": METHOD,
+            "proofPurpose": "authentication",
+            "created": "2024-01-01T00:00:00Z",
+            "expires": "2024-01-01T01:00:00Z",
+            "domain": ["https://verifier.example"],
+            "challenge": "abc123",
+            "previousProof": ["urn:uuid:1"],
+        })
+    );
+    assert_eq!(ProofOptions::from_value(&value).unwrap(), options);
+
+    let minimal = json!({
+        "type": DATA_INTEGRITY_PROOF,
+        "cryptosuite": "ecdsa-rdfc-2019",
+        "verificationMethod": METHOD,
+        "proofPurpose": "assertionMethod",
+        "domain": "example.com",
+        "proofValue": "z58DAdFfa9",
+    });
+    let parsed = ProofOptions::from_value(&minimal).unwrap();
+    assert_eq!(parsed.domain, vec!["example.com"]);
+    assert_eq!(parsed.created, None);
+
+    for options in [
+        ProofOptions::new("", METHOD),
+        ProofOptions::new("eddsa-jcs-2022", METHOD).with_proof_purpose("signing"),
+        ProofOptions::new("eddsa-jcs-2022", METHOD).with_challenge(""),
+        ProofOptions::new("eddsa-jcs-2022", METHOD)
+            .with_created(created)
+            .with_expires(created),
+    ] {
+        assert_eq!(
+            options.to_value().err(),
+            Some(Error::DATA_INTEGRITY_INVALID_OPTIONS)
+        );
+    }
+
+    let error = ProofOptions::from_value(&json!({"type": DATA_INTEGRITY_PROOF}))
+        .err()
+        .unwrap();
+    assert_eq!(error, Error::DATA_INTEGRITY_PROOF_MALFORMED);
+    assert!(error.is_malformed_input());
+}
+
+#[test]
+pub fn data_integrity_proof_options_check() {
+    let now = Utc::now();
+    let options = ProofOptions::new("eddsa-jcs-2022", METHOD)
+        .with_proof_purpose(AUTHENTICATION)
+        .with_created(now)
+        .with_expires(now + TimeDelta::minutes(5))
+        .with_domain("https://verifier.example")
+        .with_challenge("abc123");
+    let expected = ProofCheck::new()
+        .proof_purpose(AUTHENTICATION)
+        .domain("https://verifier.example")
+        .challenge("abc123");
+    options.check(&expected).unwrap();
+    options
+        .check(&ProofCheck::new().proof_purpose(AUTHENTICATION))
+        .unwrap();
+
+    for expected in [
+        ProofCheck::new(),
+        expected.clone().domain("https://other.example"),
+        expected.clone().challenge("replayed"),
+    ] {
+        assert_eq!(
+            options.check(&expected).err(),
+            Some(Error::DATA_INTEGRITY_PROOF_MISMATCH)
+        );
+    }
+
+    assert_eq!(
+        options
+            .check_at(&expected, now + TimeDelta::minutes(10))
+            .err(),
+        Some(Error::DATA_INTEGRITY_PROOF_EXPIRED)
+    );
+    assert_eq!(
+        options
+            .check_at(&expected, now - TimeDelta::minutes(10))
+            .err(),
+        Some(Error::DATA_INTEGRITY_PROOF_EXPIRED)
+    );
+    options
+        .check_at(&expected, now - TimeDelta::seconds(2))
+        .unwrap();
+}
+
+#[test]
+pub fn data_integrity_jcs_proofs() {
+    let document = json!({"id": "urn:uuid:1", "name": "example"});
+    let key = AnySigningKey::generate(Algorithm::ES384).unwrap();
+    let suite = jcs_cryptosuite(Algorithm::ES384).unwrap();
+    assert_eq!(suite, ECDSA_JCS_2019);
+
+    let config = ProofOptions::new(suite, METHOD).to_value().unwrap();
+    let proof = sign_jcs(&document, &config, &key, Algorithm::ES384).unwrap();
+    assert!(proof["proofValue"].as_str().unwrap().starts_with('z'));
+    assert!(verify_jcs(&document, &proof, &key.verifying_key()).unwrap());
+
+    let mut secured = document.clone();
+    secured["proof"] = proof.clone();
+    assert!(verify_jcs(&secured, &proof, &key.verifying_key()).unwrap());
+
+    let mut tampered = document.clone();
+    tampered["name"] = json!("changed");
+    assert!(!verify_jcs(&tampered, &proof, &key.verifying_key()).unwrap());
+
+    let config = ProofOptions::new("eddsa-jcs-2022", METHOD)
+        .to_value()
+        .unwrap();
+    assert_eq!(
+        sign_jcs(&document, &config, &key, Algorithm::ES384).err(),
+        Some(Error::DATA_INTEGRITY_INVALID_OPTIONS)
+    );
+    assert_eq!(
+        jcs_cryptosuite(Algorithm::RS256).err(),
+        Some(Error::ALGORITHM_NOT_ALLOWED.with_algorithm(Algorithm::RS256))
+    );
+}
+
+struct KeyResolver(Vec<(&'static str, AnyVerifyingKey)>);
+
+impl DidResolver for KeyResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        let key = match self.0.iter().find(|(val, _)| *val == did) {
+            Some((_, key)) => key,
+            None => return Err(Error::DID_RESOLUTION_ERROR),
+        };
+
+        Ok(json!({
+            "id": did,
+            "verificationMethod": [{
+                "id": "#key-1",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": key.to_jwk().unwrap(),
+            }],
+            ASSERTION_METHOD: ["#key-1"],
+        }))
+    }
+}
+
+#[test]
+pub fn data_integrity_proof_chains() {
+    let document = json!({"id": "urn:uuid:1", "name": "example"});
+    let issuer = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let endorser = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let resolver = KeyResolver(vec![
+        ("did:example:issuer", issuer.verifying_key()),
+        ("did:example:endorser", endorser.verifying_key()),
+    ]);
+
+    let config = ProofOptions::new("eddsa-jcs-2022", METHOD)
+        .with_id("urn:uuid:proof-1")
+        .to_value()
+        .unwrap();
+    let signed = add_proof(&document, &config, &issuer, Algorithm::EdDSA).unwrap();
+    assert!(signed["proof"].is_object());
+    assert!(verify_proofs(&signed, &resolver, &ProofCheck::new()).unwrap());
+
+    let config = ProofOptions::new(ECDSA_JCS_2019, "did:example:endorser#key-1")
+        .with_id("urn:uuid:proof-2")
+        .with_previous_proof("urn:uuid:proof-1")
+        .to_value()
+        .unwrap();
+    let endorsed = add_proof(&signed, &config, &endorser, Algorithm::ES256).unwrap();
+    let chain = proofs(&endorsed);
+    assert_eq!(chain.len(), 2);
+    assert!(verify_jcs(&endorsed, &chain[1], &endorser.verifying_key()).unwrap());
+    assert!(!verify_jcs(&document, &chain[0], &endorser.verifying_key()).unwrap());
+    assert!(verify_proofs(&endorsed, &resolver, &ProofCheck::new()).unwrap());
+
+    let mut stripped = endorsed.clone();
+    stripped["proof"] = json!([chain[1].clone()]);
+    assert_eq!(
+        verify_jcs(&stripped, &chain[1], &endorser.verifying_key()).err(),
+        Some(Error::DATA_INTEGRITY_PROOF_CHAIN_INVALID)
+    );
+
+    let mut reordered = endorsed.clone();
+    reordered["proof"] = json!([chain[1].clone(), chain[0].clone()]);
+    assert_eq!(
+        verify_proofs(&reordered, &resolver, &ProofCheck::new()).err(),
+        Some(Error::DATA_INTEGRITY_PROOF_CHAIN_INVALID)
+    );
+
+    let mut replaced = chain[0].clone();
+    replaced["created"] = json!("2024-01-01T00:00:00Z");
+    let mut tampered = endorsed.clone();
+    tampered["proof"] = json!([replaced, chain[1].clone()]);
+    assert!(!verify_proofs(&tampered, &resolver, &ProofCheck::new()).unwrap());
+
+    let wrong = KeyResolver(vec![
+        ("did:example:issuer", endorser.verifying_key()),
+        ("did:example:endorser", endorser.verifying_key()),
+    ]);
+    assert!(!verify_proofs(&endorsed, &wrong, &ProofCheck::new()).unwrap());
+
+    let config = ProofOptions::new(ECDSA_JCS_2019, "did:example:endorser#key-1")
+        .with_previous_proof("urn:uuid:missing")
+        .to_value()
+        .unwrap();
+    assert_eq!(
+        add_proof(&signed, &config, &endorser, Algorithm::ES256).err(),
+        Some(Error::DATA_INTEGRITY_PROOF_CHAIN_INVALID)
+    );
+    assert_eq!(
+        verify_proofs(&document, &resolver, &ProofCheck::new()).err(),
+        Some(Error::DATA_INTEGRITY_PROOF_MALFORMED)
+    );
+}
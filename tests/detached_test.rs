@@ -0,0 +1,82 @@
+use std::{env, fs, io::Cursor, process};
+
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{
+        any::AnySigningKey,
+        signature::{Base64Variant, SignatureEncoding},
+    },
+    detached::{sign_file, sign_reader, signature_path, verify_file, verify_reader},
+    errors::Error,
+};
+
+#[test]
+pub fn sign_and_verify_reader() {
+    let content = vec![7u8; 200_000];
+
+    for alg in [Algorithm::ES256, Algorithm::ES384, Algorithm::EdDSA] {
+        let key = AnySigningKey::generate(alg).unwrap();
+        let signature = sign_reader(Cursor::new(&content), &key, alg).unwrap();
+
+        assert!(verify_reader(Cursor::new(&content), &signature, key.verifying_key()).unwrap());
+        assert!(
+            !verify_reader(Cursor::new(&content[1..]), &signature, key.verifying_key()).unwrap()
+        );
+    }
+}
+
+#[test]
+pub fn sign_and_verify_file() {
+    let dir = env::temp_dir().join(format!("did-crypto-detached-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("credential.json");
+    fs::write(&path, b"{\"id\":\"did:example:123\"}").unwrap();
+
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let sig_path = signature_path(&path);
+    assert_eq!(sig_path, dir.join("credential.json.sig"));
+
+    for encoding in [
+        SignatureEncoding::Base64Url,
+        SignatureEncoding::Base64(Base64Variant::StandardPadded),
+        SignatureEncoding::Hex,
+        SignatureEncoding::Base58,
+        SignatureEncoding::Multibase,
+    ] {
+        let signature = sign_file(&path, &sig_path, &key, Algorithm::ES256, encoding).unwrap();
+        assert_eq!(
+            fs::read_to_string(&sig_path).unwrap(),
+            signature.encode(encoding)
+        );
+        assert!(verify_file(
+            &path,
+            &sig_path,
+            key.verifying_key(),
+            Algorithm::ES256,
+            encoding
+        )
+        .unwrap());
+    }
+
+    fs::write(&path, b"{\"id\":\"did:example:456\"}").unwrap();
+    assert!(!verify_file(
+        &path,
+        &sig_path,
+        key.verifying_key(),
+        Algorithm::ES256,
+        SignatureEncoding::Multibase
+    )
+    .unwrap());
+
+    let error = verify_file(
+        dir.join("missing.json"),
+        &sig_path,
+        key.verifying_key(),
+        Algorithm::ES256,
+        SignatureEncoding::Multibase,
+    )
+    .unwrap_err();
+    assert_eq!(error, Error::FILE_IO_ERROR);
+
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,210 @@
+use chrono::{TimeDelta, Utc};
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    data_integrity::{ProofOptions, ECDSA_JCS_2019},
+    did_configuration::{DidConfiguration, DomainLinkage, LinkedDid},
+    errors::Error,
+    resolver::{DidResolver, ASSERTION_METHOD},
+};
+use serde_json::{json, Value};
+
+const DID: &str = "did:web:identity.example";
+const OTHER_DID: &str = "did:example:other";
+const ORIGIN: &str = "https://identity.example";
+
+struct KeyResolver(Vec<(&'static str, AnyVerifyingKey)>);
+
+impl DidResolver for KeyResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        let key = match self.0.iter().find(|(val, _)| *val == did) {
+            Some((_, key)) => key,
+            None => return Err(Error::DID_RESOLUTION_ERROR),
+        };
+
+        Ok(json!({
+            "id": did,
+            "verificationMethod": [{
+                "id": "#key-1",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": key.to_jwk().unwrap(),
+            }],
+            ASSERTION_METHOD: ["#key-1"],
+        }))
+    }
+}
+
+fn linkage(did: &str) -> DomainLinkage {
+    DomainLinkage::new(did, ORIGIN, Utc::now() + TimeDelta::days(365))
+}
+
+#[test]
+pub fn did_configuration_jwt_linkage() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let resolver = KeyResolver(vec![(DID, key.verifying_key())]);
+    let kid = format!("{}#key-1", DID);
+
+    let expires = Utc::now() + TimeDelta::days(365);
+    let linked_did = DomainLinkage::new(DID, "https://identity.example/", expires)
+        .sign_jwt(&kid, &key, Algorithm::ES256)
+        .unwrap();
+    let parsed = linked_did.linkage().unwrap();
+    assert_eq!(parsed.did, DID);
+    assert_eq!(parsed.origin, ORIGIN);
+    assert_eq!(parsed.expires.timestamp(), expires.timestamp());
+    assert!(linked_did.verify(ORIGIN, &resolver).unwrap());
+    assert!(linked_did
+        .verify("https://identity.example/", &resolver)
+        .unwrap());
+
+    let error = linked_did
+        .verify("https://evil.example", &resolver)
+        .err()
+        .unwrap();
+    assert_eq!(error, Error::DID_CONFIGURATION_ORIGIN_MISMATCH);
+    assert_eq!(error.key(), Some(ORIGIN));
+    assert_eq!(
+        linked_did
+            .verify_at(ORIGIN, &resolver, Utc::now() + TimeDelta::days(366))
+            .err(),
+        Some(Error::DID_CONFIGURATION_EXPIRED)
+    );
+
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let forged = linkage(DID)
+        .sign_jwt(&kid, &other, Algorithm::ES256)
+        .unwrap();
+    assert!(!forged.verify(ORIGIN, &resolver).unwrap());
+
+    assert_eq!(
+        linkage(DID)
+            .sign_jwt("did:example:other#key-1", &key, Algorithm::ES256)
+            .err(),
+        Some(Error::DID_CONFIGURATION_MALFORMED)
+    );
+}
+
+#[test]
+pub fn did_configuration_data_integrity_linkage() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let resolver = KeyResolver(vec![(DID, key.verifying_key())]);
+    let options = ProofOptions::new(ECDSA_JCS_2019, &format!("{}#key-1", DID));
+
+    let linked_did = linkage(DID)
+        .sign_data_integrity(options.clone(), &key, Algorithm::ES256)
+        .unwrap();
+    let document = linked_did.to_value();
+    assert_eq!(document["type"][1], "DomainLinkageCredential");
+    assert_eq!(document["credentialSubject"]["origin"], ORIGIN);
+    assert_eq!(document["proof"]["proofPurpose"], ASSERTION_METHOD);
+    assert!(linked_did.verify(ORIGIN, &resolver).unwrap());
+
+    let mut tampered = document.clone();
+    tampered["credentialSubject"]["origin"] = Value::from("https://evil.example");
+    assert!(!LinkedDid::from_value(&tampered)
+        .unwrap()
+        .verify("https://evil.example", &resolver)
+        .unwrap());
+
+    let mut unsigned = document;
+    unsigned.as_object_mut().unwrap().remove("proof");
+    assert_eq!(
+        LinkedDid::from_value(&unsigned).unwrap().linkage().err(),
+        Some(Error::DID_CONFIGURATION_MALFORMED)
+    );
+}
+
+#[test]
+pub fn did_configuration_resource() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let other_key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let resolver = KeyResolver(vec![
+        (DID, key.verifying_key()),
+        (OTHER_DID, other_key.verifying_key()),
+    ]);
+
+    let configuration = DidConfiguration::new()
+        .with_linked_did(
+            linkage(DID)
+                .sign_jwt(&format!("{}#key-1", DID), &key, Algorithm::ES256)
+                .unwrap(),
+        )
+        .with_linked_did(
+            linkage(OTHER_DID)
+                .sign_jwt(&format!("{}#key-1", OTHER_DID), &key, Algorithm::ES256)
+                .unwrap(),
+        );
+    assert_eq!(
+        DidConfiguration::url("https://identity.example/"),
+        "https://identity.example/.well-known/did-configuration.json"
+    );
+
+    let value = configuration.to_value();
+    assert_eq!(
+        value["@context"],
+        "https://identity.foundation/.well-known/did-configuration/v1"
+    );
+    let configuration = DidConfiguration::from_value(&value).unwrap();
+    assert_eq!(configuration.linked_dids.len(), 2);
+
+    assert!(configuration.verify_did(DID, ORIGIN, &resolver).unwrap());
+    assert!(!configuration
+        .verify_did(OTHER_DID, ORIGIN, &resolver)
+        .unwrap());
+    assert_eq!(
+        configuration
+            .verify_did("did:example:unknown", ORIGIN, &resolver)
+            .err(),
+        Some(Error::DID_CONFIGURATION_NOT_LINKED)
+    );
+    assert_eq!(
+        configuration
+            .verify_did(DID, "https://evil.example", &resolver)
+            .err(),
+        Some(Error::DID_CONFIGURATION_ORIGIN_MISMATCH)
+    );
+    assert_eq!(configuration.verified_dids(ORIGIN, &resolver), vec![DID]);
+
+    let error = DidConfiguration::from_value(&json!({"linked_dids": []}))
+        .err()
+        .unwrap();
+    assert_eq!(error, Error::DID_CONFIGURATION_MALFORMED);
+    assert!(error.is_malformed_input());
+}
+
+#[cfg(feature = "http")]
+#[test]
+pub fn did_configuration_fetch() {
+    use did_crypto::{
+        did_configuration::fetch_configuration,
+        http::{HttpFetcher, HttpResponse},
+    };
+
+    struct HostedFetcher(Value);
+
+    impl HttpFetcher for HostedFetcher {
+        fn get(&self, url: &str) -> Result<HttpResponse, Error> {
+            assert_eq!(
+                url,
+                "https://identity.example/.well-known/did-configuration.json"
+            );
+            Ok(HttpResponse {
+                body: serde_json::to_vec(&self.0).unwrap(),
+                max_age: None,
+            })
+        }
+    }
+
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let resolver = KeyResolver(vec![(DID, key.verifying_key())]);
+    let configuration = DidConfiguration::new().with_linked_did(
+        linkage(DID)
+            .sign_jwt(&format!("{}#key-1", DID), &key, Algorithm::ES256)
+            .unwrap(),
+    );
+
+    let fetched = fetch_configuration(&HostedFetcher(configuration.to_value()), ORIGIN).unwrap();
+    assert_eq!(fetched, configuration);
+    assert!(fetched.verify_did(DID, ORIGIN, &resolver).unwrap());
+}
@@ -0,0 +1,127 @@
+use std::time::Duration;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::AnySigningKey,
+    dpop::{access_token_hash, DpopClaims, DpopProof, DpopValidation, DPOP_TYP},
+    errors::Error,
+};
+use serde_json::Value;
+
+const HTM: &str = "POST";
+const HTU: &str = "https://server.example.com/token";
+const ACCESS_TOKEN: &str = "Kz~8mXK1EalYznwH-LC-1fBAo.4Ljp~zsPE_NeO.gxU";
+
+#[test]
+pub fn dpop_proof_round_trip() {
+    for alg in [Algorithm::ES256, Algorithm::EdDSA, Algorithm::ES384] {
+        let key = AnySigningKey::generate(alg).unwrap();
+        let proof = DpopProof::sign(DpopClaims::new(HTM, HTU), &key).unwrap();
+
+        let header = proof.to_token().split('.').next().unwrap();
+        let header: Value =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header).unwrap()).unwrap();
+        assert_eq!(header["typ"], DPOP_TYP);
+        assert!(header["jwk"].get("d").is_none());
+
+        let parsed = DpopProof::from_token(proof.to_token()).unwrap();
+        assert_eq!(parsed.claims, proof.claims);
+        assert_eq!(
+            parsed.jkt().unwrap(),
+            key.verifying_key().fingerprint().unwrap()
+        );
+        assert!(parsed.verify(&DpopValidation::new(HTM, HTU)).unwrap());
+        assert!(parsed
+            .verify(&DpopValidation::new(
+                HTM,
+                "https://server.example.com/token?x=1"
+            ))
+            .unwrap());
+        assert!(!parsed.verify(&DpopValidation::new("GET", HTU)).unwrap());
+        assert!(!parsed
+            .verify(&DpopValidation::new(HTM, "https://other.example.com/token"))
+            .unwrap());
+    }
+}
+
+#[test]
+pub fn dpop_proof_binds_access_token_nonce_and_key() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let claims = DpopClaims::new("GET", "https://resource.example.org/protected")
+        .with_access_token(ACCESS_TOKEN)
+        .with_nonce("eyJ7S_zG.eyJH0-Z.HX4w-7v");
+    assert_eq!(
+        claims.ath.as_deref(),
+        Some("fUHyO2r2Z3DZ53EsNrWBb0xWXoaNy59IiKCAqksmQEo")
+    );
+    assert_eq!(claims.ath, Some(access_token_hash(ACCESS_TOKEN)));
+
+    let proof = DpopProof::from_token(DpopProof::sign(claims, &key).unwrap().to_token()).unwrap();
+    let jkt = key.verifying_key().fingerprint().unwrap();
+    let validation = DpopValidation::new("GET", "https://resource.example.org/protected")
+        .access_token(ACCESS_TOKEN)
+        .nonce("eyJ7S_zG.eyJH0-Z.HX4w-7v")
+        .jkt(&jkt);
+    assert!(proof.verify(&validation).unwrap());
+
+    assert!(!proof.verify(&validation.access_token("other")).unwrap());
+    let validation = DpopValidation::new("GET", "https://resource.example.org/protected");
+    assert!(!proof.verify(&validation.nonce("other")).unwrap());
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let other_jkt = other.verifying_key().fingerprint().unwrap();
+    let validation = DpopValidation::new("GET", "https://resource.example.org/protected");
+    assert!(!proof.verify(&validation.jkt(&other_jkt)).unwrap());
+}
+
+#[test]
+pub fn dpop_proof_rejects_stale_and_invalid_proofs() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let claims = DpopClaims {
+        iat: Utc::now().timestamp() - 600,
+        ..DpopClaims::new(HTM, HTU)
+    };
+    let proof = DpopProof::sign(claims, &key).unwrap();
+    assert!(!proof.verify(&DpopValidation::new(HTM, HTU)).unwrap());
+    assert!(proof
+        .verify(&DpopValidation::new(HTM, HTU).max_age(Duration::from_secs(900)))
+        .unwrap());
+
+    let mut parts: Vec<String> = DpopProof::sign(DpopClaims::new(HTM, HTU), &key)
+        .unwrap()
+        .to_token()
+        .split('.')
+        .map(String::from)
+        .collect();
+    parts[1] = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&DpopClaims::new("DELETE", HTU)).unwrap());
+    let tampered = DpopProof::from_token(&parts.join(".")).unwrap();
+    assert!(!tampered
+        .verify(&DpopValidation::new("DELETE", HTU))
+        .unwrap());
+
+    let header = URL_SAFE_NO_PAD.encode(r#"{"typ":"JWT","alg":"ES256","jwk":{"kty":"EC"}}"#);
+    assert_eq!(
+        DpopProof::from_token(&format!("{}.{}.sig", header, parts[1])).err(),
+        Some(Error::DPOP_INVALID_TYP)
+    );
+    let jwk = key.to_jwk().unwrap().to_json().unwrap();
+    let header = URL_SAFE_NO_PAD.encode(format!(
+        r#"{{"typ":"dpop+jwt","alg":"ES256","jwk":{}}}"#,
+        jwk
+    ));
+    assert_eq!(
+        DpopProof::from_token(&format!("{}.{}.sig", header, parts[1])).err(),
+        Some(Error::DPOP_INVALID_KEY)
+    );
+    assert_eq!(
+        DpopProof::from_token("not-a-proof").err(),
+        Some(Error::DPOP_MALFORMED)
+    );
+
+    let hmac = AnySigningKey::generate(Algorithm::HS256).unwrap();
+    assert_eq!(
+        DpopProof::sign(DpopClaims::new(HTM, HTU), &hmac).err(),
+        Some(Error::DPOP_INVALID_KEY)
+    );
+}
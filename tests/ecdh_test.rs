@@ -0,0 +1,77 @@
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        ecdh::derive_shared_key,
+        kdf::{hkdf, HashFunction},
+    },
+    errors::Error,
+};
+
+const INFO: &[u8] = b"did-crypto pairing v1";
+
+#[test]
+pub fn ecdh_derives_matching_keys() {
+    for (alg, hash) in [
+        (Algorithm::ES256, HashFunction::Sha256),
+        (Algorithm::ES384, HashFunction::Sha384),
+    ] {
+        let alice = AnySigningKey::generate(alg).unwrap();
+        let bob = AnySigningKey::generate(alg).unwrap();
+
+        let alice_key =
+            derive_shared_key(&alice, &bob.verifying_key(), hash, Some(b"salt"), INFO, 32).unwrap();
+        let bob_key =
+            derive_shared_key(&bob, &alice.verifying_key(), hash, Some(b"salt"), INFO, 32).unwrap();
+        assert_eq!(alice_key, bob_key);
+        assert_eq!(alice_key.len(), 32);
+
+        let other_info = derive_shared_key(
+            &alice,
+            &bob.verifying_key(),
+            hash,
+            Some(b"salt"),
+            b"other",
+            32,
+        )
+        .unwrap();
+        assert_ne!(alice_key, other_info);
+    }
+}
+
+#[test]
+pub fn ecdh_rfc5903_vector() {
+    let initiator = AnySigningKey::from_hex(
+        Algorithm::ES256,
+        "c88f01f510d9ac3f70a292daa2316de544e9aab8afe84049c62a9c57862d1433",
+    )
+    .unwrap();
+    let responder = AnyVerifyingKey::from_hex(
+        Algorithm::ES256,
+        "04d12dfb5289c8d4f81208b70270398c342296970a0bccb74c736fc7554494bf6356fbf3ca366cc23e8157854c13c58d6aac23f046ada30f8353e74f33039872ab",
+    )
+    .unwrap();
+    let shared_secret =
+        hex::decode("d6840f6b42f6edafd13116e0e12565202fef8e9ece7dce03812464d04b9442de").unwrap();
+
+    assert_eq!(
+        *derive_shared_key(&initiator, &responder, HashFunction::Sha256, None, INFO, 42).unwrap(),
+        hkdf(HashFunction::Sha256, None, &shared_secret, INFO, 42).unwrap()
+    );
+}
+
+#[test]
+pub fn ecdh_rejects_unsupported_keys() {
+    let p256 = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let p384 = AnySigningKey::generate(Algorithm::ES384).unwrap();
+    let eddsa = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    for (key, peer) in [
+        (&p256, p384.verifying_key()),
+        (&eddsa, eddsa.verifying_key()),
+    ] {
+        assert_eq!(
+            derive_shared_key(key, &peer, HashFunction::Sha256, None, INFO, 32).err(),
+            Some(Error::ECDH_UNSUPPORTED_KEY)
+        );
+    }
+}
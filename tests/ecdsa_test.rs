@@ -1,11 +1,20 @@
 use did_crypto::{
     algorithms::Algorithm,
-    crypto::ecdsa::{
-        _256k::{P256kSigningKey, P256kVerifyingKey},
-        _256::{P256SigningKey, P256VerifyingKey},
-        _384::{P384SigningKey, P384VerifyingKey},
-        _512::{P512SigningKey, P512VerifyingKey},
+    caip::AccountId,
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        ecdsa::{
+            _256k::{P256kSigningKey, P256kVerifyingKey},
+            der_to_p1363, p1363_length, p1363_to_der, signature_from_der, NonceMode,
+            _256::{P256SigningKey, P256VerifyingKey},
+            _384::{P384SigningKey, P384VerifyingKey},
+            _512::{P512SigningKey, P512VerifyingKey},
+        },
+        signature::Signature,
+        SignFromKey, VerifyFromKey,
     },
+    errors::Error,
+    jwk::Jwk,
     signer::sign,
     verifier::verify,
 };
@@ -321,3 +330,251 @@ pub fn ec256k_hex_signing_and_verifying() {
         }
     })
 }
+
+#[test]
+pub fn ecdsa_der_signature_conversion() {
+    let signature = sign(
+        String::from(EC256_CONTENT),
+        P256SigningKey::from_pem(PRIVATE_KEY_256).unwrap(),
+        Algorithm::ES256,
+    )
+    .unwrap();
+    let der = p256::ecdsa::Signature::from_slice(&base64_url::decode(&signature).unwrap())
+        .unwrap()
+        .to_der();
+    assert_eq!(
+        signature_from_der(der.as_bytes(), Algorithm::ES256).unwrap(),
+        signature
+    );
+
+    let signature = sign(
+        String::from(EC256K_CONTENT),
+        P256kSigningKey::from_pem(PRIVATE_KEY_256K).unwrap(),
+        Algorithm::ES256K,
+    )
+    .unwrap();
+    let low_s =
+        k256::ecdsa::Signature::from_slice(&base64_url::decode(&signature).unwrap()).unwrap();
+    let (r, s) = low_s.split_scalars();
+    let high_s = k256::ecdsa::Signature::from_scalars(r, -*s).unwrap();
+    let converted = signature_from_der(high_s.to_der().as_bytes(), Algorithm::ES256K).unwrap();
+    assert_eq!(converted, signature);
+    assert!(verify(
+        String::from(EC256K_CONTENT),
+        converted,
+        P256kVerifyingKey::from_pem(PUBLIC_KEY_256K).unwrap(),
+        Algorithm::ES256K
+    )
+    .unwrap());
+
+    assert!(signature_from_der(&[0x30, 0x00], Algorithm::ES256).is_err());
+    assert!(signature_from_der(der.as_bytes(), Algorithm::EdDSA).is_err());
+}
+
+#[test]
+pub fn ecdsa_deterministic_and_hedged_nonces() {
+    let key = P256SigningKey::from_pem(PRIVATE_KEY_256).unwrap();
+    let verifying_key = P256VerifyingKey::from_pem(PUBLIC_KEY_256).unwrap();
+    assert_eq!(key.nonce_mode(), NonceMode::Deterministic);
+
+    let first = key
+        .sign_bytes(EC256_CONTENT.as_bytes(), Algorithm::ES256)
+        .unwrap();
+    let second = key
+        .sign_bytes(EC256_CONTENT.as_bytes(), Algorithm::ES256)
+        .unwrap();
+    assert_eq!(first, second);
+
+    let key = key.with_nonce_mode(NonceMode::Hedged);
+    let first = key
+        .sign_bytes(EC256_CONTENT.as_bytes(), Algorithm::ES256)
+        .unwrap();
+    let second = key
+        .sign_bytes(EC256_CONTENT.as_bytes(), Algorithm::ES256)
+        .unwrap();
+    assert_ne!(first, second);
+    assert!(verifying_key
+        .verify_bytes(EC256_CONTENT.as_bytes(), &first, Algorithm::ES256)
+        .unwrap());
+    assert_eq!(
+        key.sign_bytes_with_nonce_mode(EC256_CONTENT.as_bytes(), NonceMode::Deterministic)
+            .unwrap(),
+        P256SigningKey::from_pem(PRIVATE_KEY_256)
+            .unwrap()
+            .sign_bytes(EC256_CONTENT.as_bytes(), Algorithm::ES256)
+            .unwrap()
+    );
+
+    let key = P256kSigningKey::from_pem(PRIVATE_KEY_256K)
+        .unwrap()
+        .with_nonce_mode(NonceMode::Hedged);
+    let first = key
+        .sign_bytes(EC256K_CONTENT.as_bytes(), Algorithm::ES256K)
+        .unwrap();
+    assert_ne!(
+        first,
+        key.sign_bytes(EC256K_CONTENT.as_bytes(), Algorithm::ES256K)
+            .unwrap()
+    );
+    assert!(P256kVerifyingKey::from_pem(PUBLIC_KEY_256K)
+        .unwrap()
+        .verify_bytes(EC256K_CONTENT.as_bytes(), &first, Algorithm::ES256K)
+        .unwrap());
+
+    let any = AnySigningKey::generate(Algorithm::ES256K)
+        .unwrap()
+        .with_nonce_mode(NonceMode::Hedged);
+    assert_eq!(any.nonce_mode(), Some(NonceMode::Hedged));
+    assert_eq!(
+        AnySigningKey::generate(Algorithm::ES384)
+            .unwrap()
+            .nonce_mode(),
+        Some(NonceMode::Deterministic)
+    );
+    assert_eq!(
+        AnySigningKey::generate(Algorithm::EdDSA)
+            .unwrap()
+            .nonce_mode(),
+        None
+    );
+}
+
+#[test]
+pub fn ecdsa_compressed_and_uncompressed_public_keys() {
+    let p256 = P256VerifyingKey::from_bytes(&hex::decode(PUBLIC_KEY_256_HEX).unwrap()).unwrap();
+    let compressed = p256.to_sec1_bytes(true);
+    assert_eq!(compressed.len(), 33);
+    assert_eq!(p256.to_sec1_bytes(false), p256.to_bytes());
+    assert!(P256VerifyingKey::from_bytes(&compressed).unwrap() == p256);
+
+    let p256k = P256kVerifyingKey::from_bytes(&hex::decode(PUBLIC_KEY_256K_HEX).unwrap()).unwrap();
+    let compressed = p256k.to_sec1_bytes(true);
+    assert_eq!(compressed.len(), 33);
+    assert!(P256kVerifyingKey::from_bytes(&compressed).unwrap() == p256k);
+
+    let p384 = P384VerifyingKey::from_bytes(&hex::decode(PUBLIC_KEY_384_HEX).unwrap()).unwrap();
+    let compressed = p384.to_sec1_bytes(true);
+    assert_eq!(compressed.len(), 49);
+    assert!(P384VerifyingKey::from_bytes(&compressed).unwrap() == p384);
+
+    let p512 = P512VerifyingKey::from_bytes(&hex::decode(PUBLIC_KEY_512_HEX).unwrap()).unwrap();
+    let compressed = p512.to_sec1_bytes(true);
+    assert_eq!(compressed.len(), 67);
+    assert_eq!(p512.to_sec1_bytes(false).len(), 133);
+    assert!(P512VerifyingKey::from_bytes(&compressed).unwrap() == p512);
+
+    let signing_key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    assert_eq!(
+        signing_key
+            .verifying_key()
+            .to_sec1_bytes(true)
+            .unwrap()
+            .len(),
+        33
+    );
+    assert!(AnySigningKey::generate(Algorithm::EdDSA)
+        .unwrap()
+        .verifying_key()
+        .to_sec1_bytes(true)
+        .is_err());
+}
+
+#[test]
+pub fn ecdsa_p1363_webcrypto_interop() {
+    let jwk = Jwk::from_json(
+        r#"{"kty":"EC","crv":"P-256","x":"f83OJ3D2xF1Bg8vub9tLe1gHMzV76e8Tus9uPHvRVEU","y":"x_FEzRu9m36HLN_tue659LNpXW6pCyStikYjKIWI5a0"}"#,
+    )
+    .unwrap();
+    let key = AnyVerifyingKey::from_jwk(&jwk).unwrap();
+    let content = "eyJhbGciOiJFUzI1NiJ9.eyJpc3MiOiJqb2UiLA0KICJleHAiOjEzMDA4MTkzODAsDQogImh0dHA6Ly9leGFtcGxlLmNvbS9pc19yb290Ijp0cnVlfQ";
+    let signature =
+        "DtEhU3ljbEg8L38VWAfUAqOyKAM6-Xx-F4GawxaepmXFCgfTjDxw5djxLa8ISlSApmWQxfKTUJqPP3-Kg6NU1Q";
+
+    let p1363 = base64_url::decode(signature).unwrap();
+    assert_eq!(p1363.len(), p1363_length(Algorithm::ES256).unwrap());
+    assert!(verify(content, signature, &key, Algorithm::ES256).unwrap());
+
+    let der = p1363_to_der(&p1363, Algorithm::ES256).unwrap();
+    assert_eq!(der[0], 0x30);
+    assert_eq!(der_to_p1363(&der, Algorithm::ES256).unwrap(), p1363);
+    assert_eq!(
+        verify(content, base64_url::encode(&der), &key, Algorithm::ES256).unwrap_err(),
+        Error::SIGNATURE_IDENTIFICATION_FAILED
+    );
+
+    for alg in [Algorithm::ES256, Algorithm::ES384, Algorithm::ES512] {
+        let signing_key = AnySigningKey::generate(alg).unwrap();
+        let signature = sign("webcrypto", &signing_key, alg).unwrap();
+        let p1363 = base64_url::decode(&signature).unwrap();
+        assert_eq!(Some(p1363.len()), p1363_length(alg));
+        assert_eq!(
+            Signature::from_p1363(&p1363, alg).unwrap().to_base64url(),
+            signature
+        );
+
+        let der = p1363_to_der(&p1363, alg).unwrap();
+        assert_eq!(der_to_p1363(&der, alg).unwrap(), p1363);
+        assert!(verify(
+            "webcrypto",
+            base64_url::encode(&der_to_p1363(&der, alg).unwrap()),
+            signing_key.verifying_key(),
+            alg
+        )
+        .unwrap());
+        assert_eq!(
+            Signature::from_p1363(&der, alg).unwrap_err(),
+            Error::SIGNATURE_IDENTIFICATION_FAILED
+        );
+        assert_eq!(
+            p1363_to_der(&p1363[1..], alg).unwrap_err(),
+            Error::SIGNATURE_IDENTIFICATION_FAILED
+        );
+    }
+
+    assert_eq!(p1363_length(Algorithm::EdDSA), None);
+    assert_eq!(
+        Signature::from_p1363(&[0u8; 64], Algorithm::EdDSA).unwrap_err(),
+        Error::SIGNATURE_ENCODING_UNSUPPORTED
+    );
+}
+
+#[test]
+pub fn ecdsa_es256kr_recoverable_signatures() {
+    let key = AnySigningKey::P256k(P256kSigningKey::from_pem(PRIVATE_KEY_256K).unwrap());
+    let verifying_key = key.verifying_key();
+    let signature = sign(EC256K_CONTENT, &key, Algorithm::ES256KR).unwrap();
+    assert_eq!(base64_url::decode(&signature).unwrap().len(), 65);
+    assert!(verify(
+        EC256K_CONTENT,
+        &signature,
+        &verifying_key,
+        Algorithm::ES256KR
+    )
+    .unwrap());
+    assert!(!verify("other", &signature, &verifying_key, Algorithm::ES256KR).unwrap());
+    assert_eq!(
+        verifying_key.verifying_algorithms(),
+        Some(vec![Algorithm::ES256K, Algorithm::ES256KR])
+    );
+
+    let recovered = P256kVerifyingKey::recover(EC256K_CONTENT.as_bytes(), &signature).unwrap();
+    assert!(recovered == P256kVerifyingKey::from_pem(PUBLIC_KEY_256K).unwrap());
+
+    let account = AccountId::from_secp256k1_key(1, &recovered);
+    assert!(account
+        .verify_recoverable(EC256K_CONTENT.as_bytes(), &signature)
+        .unwrap());
+    assert!(!account.verify_recoverable(b"other", &signature).unwrap());
+
+    let es256k = sign(EC256K_CONTENT, &key, Algorithm::ES256K).unwrap();
+    assert_eq!(
+        verify(EC256K_CONTENT, &es256k, &verifying_key, Algorithm::ES256KR).unwrap_err(),
+        Error::SIGNATURE_IDENTIFICATION_FAILED
+    );
+    assert!(sign(
+        EC256K_CONTENT,
+        AnySigningKey::generate(Algorithm::ES256).unwrap(),
+        Algorithm::ES256KR
+    )
+    .is_err());
+}
@@ -0,0 +1,68 @@
+#![cfg(all(feature = "es256k", feature = "eddsa"))]
+
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        ecies::{decrypt, encrypt, ECIES_EPHEMERAL_KEY_LENGTH},
+    },
+    errors::Error,
+};
+
+const PLAINTEXT: &[u8] = b"{\"credential_offer\":\"urn:uuid:5f1c\"}";
+const AAD: &[u8] = b"did:example:alice#key-agreement-1";
+
+#[test]
+pub fn ecies_round_trip() {
+    for alg in [Algorithm::ES256, Algorithm::ES256K] {
+        let key = AnySigningKey::generate(alg).unwrap();
+        let recipient = AnyVerifyingKey::from_jwk(&key.verifying_key().to_jwk().unwrap()).unwrap();
+
+        let message = encrypt(&recipient, PLAINTEXT, AAD).unwrap();
+        assert_eq!(
+            message.len(),
+            ECIES_EPHEMERAL_KEY_LENGTH + 12 + PLAINTEXT.len() + 16
+        );
+        assert_ne!(message, encrypt(&recipient, PLAINTEXT, AAD).unwrap());
+        assert_eq!(decrypt(&key, &message, AAD).unwrap(), PLAINTEXT);
+
+        assert_eq!(
+            decrypt(&key, &message, b"other").unwrap_err(),
+            Error::ECIES_DECRYPTION_FAILED
+        );
+        let other = AnySigningKey::generate(alg).unwrap();
+        assert_eq!(
+            decrypt(&other, &message, AAD).unwrap_err(),
+            Error::ECIES_DECRYPTION_FAILED
+        );
+    }
+}
+
+#[test]
+pub fn ecies_rejects_bad_input() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let message = encrypt(&key.verifying_key(), PLAINTEXT, &[]).unwrap();
+
+    assert_eq!(
+        decrypt(&key, &message[..40], &[]).unwrap_err(),
+        Error::ECIES_MALFORMED
+    );
+    let mut tampered = message.clone();
+    tampered[0] = 0x07;
+    assert!(decrypt(&key, &tampered, &[])
+        .unwrap_err()
+        .is_malformed_input());
+
+    let secp256k1 = AnySigningKey::generate(Algorithm::ES256K).unwrap();
+    assert!(decrypt(&secp256k1, &message, &[]).is_err());
+
+    let ed25519 = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    assert_eq!(
+        encrypt(&ed25519.verifying_key(), PLAINTEXT, &[]).unwrap_err(),
+        Error::ECIES_UNSUPPORTED_KEY
+    );
+    assert_eq!(
+        decrypt(&ed25519, &message, &[]).unwrap_err(),
+        Error::ECIES_UNSUPPORTED_KEY
+    );
+}
@@ -1,6 +1,10 @@
 use did_crypto::{
     algorithms::Algorithm,
-    crypto::eddsa::{EDDSASigningKey, EDDSAVerifyingKey},
+    crypto::{
+        eddsa::{EDDSASigningKey, EDDSAVerifyingKey},
+        VerifyFromKey,
+    },
+    errors::Error,
     signer::sign,
     verifier::verify,
 };
@@ -73,10 +77,100 @@ pub fn eddsa_hex_signing_and_verifying() {
         EDDSAVerifyingKey::from_bytes(hex::decode(PUBLIC_KEY_HEX).unwrap().as_mut_slice()).unwrap(),
         Algorithm::EdDSA
     ) {
-        Ok(val) => !val,
+        Ok(val) => val,
         Err(error) => {
             println!("{}", error.to_string());
             panic!()
         }
     })
 }
+
+#[test]
+pub fn eddsa_ed25519ctx_rfc8032_vector() {
+    let key = EDDSASigningKey::from_bytes(
+        &mut hex::decode("0305334e381af78f141cb666f6199f57bc3495335a256a95bd2a55bf546663f6")
+            .unwrap(),
+    )
+    .unwrap();
+    let verifying_key = key.verifying_key();
+    assert_eq!(
+        hex::encode(verifying_key.to_bytes()),
+        "dfc9425e4f968f7f0c29f0259cf5f9aed6851c2bb4ad8bfb860cfee0ab248292"
+    );
+
+    let message = hex::decode("f726936d19c800494e3fdaff20b276a8").unwrap();
+    let signature = key.sign_with_context(&message, b"foo").unwrap();
+    assert_eq!(
+        hex::encode(base64_url::decode(&signature).unwrap()),
+        "55a4cc2f70a54e04288c5f4cd1e45a7bb520b36292911876cada7323198dd87a\
+         8b36950b95130022907a7fb7c4e9b2d5f6cca685a587b4b21f4b888e4e7edb0d"
+    );
+    assert!(verifying_key
+        .verify_with_context(&message, &signature, b"foo")
+        .unwrap());
+    assert!(!verifying_key
+        .verify_with_context(&message, &signature, b"bar")
+        .unwrap());
+    assert!(!verifying_key
+        .verify_prehashed(&message, &signature, b"foo")
+        .unwrap());
+    assert_eq!(
+        key.sign_with_context(&message, b"").unwrap_err(),
+        Error::SIGNATURE_CONTEXT_INVALID
+    );
+    assert_eq!(
+        key.sign_with_context(&message, &[0u8; 256]).unwrap_err(),
+        Error::SIGNATURE_CONTEXT_INVALID
+    );
+}
+
+#[test]
+pub fn eddsa_ed25519ph_rfc8032_vector() {
+    let key = EDDSASigningKey::from_bytes(
+        &mut hex::decode("833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42")
+            .unwrap(),
+    )
+    .unwrap();
+    let signature = key.sign_prehashed(b"abc", b"").unwrap();
+    assert_eq!(
+        hex::encode(base64_url::decode(&signature).unwrap()),
+        "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae41\
+         31f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406"
+    );
+
+    let verifying_key = key.verifying_key();
+    assert!(verifying_key
+        .verify_prehashed(b"abc", &signature, b"")
+        .unwrap());
+    assert!(!verifying_key
+        .verify_prehashed(b"abd", &signature, b"")
+        .unwrap());
+    assert!(!verifying_key
+        .verify_bytes(b"abc", &signature, Algorithm::EdDSA)
+        .unwrap());
+}
+
+#[test]
+pub fn any_key_signs_with_context() {
+    use did_crypto::crypto::any::AnySigningKey;
+
+    let key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let signature = key.sign_with_context(b"content", b"did-crypto").unwrap();
+    let verifying_key = key.verifying_key();
+    assert!(verifying_key
+        .verify_with_context(b"content", &signature, b"did-crypto")
+        .unwrap());
+    assert!(!verifying_key
+        .verify_with_context(b"content", &signature, b"other")
+        .unwrap());
+
+    let signature = key.sign_with_context(b"content", b"").unwrap();
+    assert!(verify(b"content", &signature, verifying_key, Algorithm::EdDSA).unwrap());
+
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    assert_eq!(
+        key.sign_with_context(b"content", b"did-crypto")
+            .unwrap_err(),
+        Error::SIGNATURE_CONTEXT_UNSUPPORTED
+    );
+}
@@ -0,0 +1,137 @@
+use did_crypto::{
+    encoding::{
+        base32_decode, base32_encode, base58_decode, base58_encode, base58check_decode,
+        base58check_encode, canonical_json, multibase_decode, multibase_encode, to_json,
+        JsonFormat,
+    },
+    errors::Error,
+    jwk::{Jwk, Jwks},
+};
+use serde_json::json;
+
+#[test]
+pub fn base58btc_vectors() {
+    for (bytes, encoded) in [
+        (b"".to_vec(), ""),
+        (b"Hello World!".to_vec(), "2NEpo7TZRRrLZSi2U"),
+        (vec![0, 0, 0x28, 0x7f, 0xb4, 0xcd], "11233QC4"),
+        (vec![0], "1"),
+    ] {
+        assert_eq!(base58_encode(&bytes), encoded);
+        assert_eq!(base58_decode(encoded).unwrap(), bytes);
+    }
+
+    assert_eq!(base58_decode("0OIl").unwrap_err(), Error::DECODING_ERROR);
+}
+
+#[test]
+pub fn base58check_vectors() {
+    let payload = hex::decode("00010966776006953d5567439e5e39f86a0d273bee").unwrap();
+    let encoded = base58check_encode(&payload);
+    assert_eq!(encoded, "16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvM");
+    assert_eq!(base58check_decode(&encoded).unwrap(), payload);
+
+    assert_eq!(
+        base58check_decode("16UwLL9Risc3QfPqBUvKofHmBQ7wMtjvN").unwrap_err(),
+        Error::BASE58_INVALID_CHECKSUM
+    );
+    assert_eq!(
+        base58check_decode("2g").unwrap_err(),
+        Error::BASE58_INVALID_CHECKSUM
+    );
+}
+
+#[test]
+pub fn multibase_base58btc() {
+    let encoded = multibase_encode(b"Hello World!");
+    assert_eq!(encoded, "z2NEpo7TZRRrLZSi2U");
+    assert_eq!(multibase_decode(&encoded).unwrap(), b"Hello World!");
+
+    assert_eq!(
+        multibase_decode("2NEpo7TZRRrLZSi2U").unwrap_err(),
+        Error::DECODING_ERROR
+    );
+}
+
+#[test]
+pub fn base32_vectors() {
+    for (bytes, encoded) in [
+        ("", ""),
+        ("f", "my"),
+        ("fo", "mzxq"),
+        ("foo", "mzxw6"),
+        ("foob", "mzxw6yq"),
+        ("fooba", "mzxw6ytb"),
+        ("foobar", "mzxw6ytboi"),
+    ] {
+        assert_eq!(base32_encode(bytes), encoded);
+        assert_eq!(base32_decode(encoded).unwrap(), bytes.as_bytes());
+    }
+
+    assert_eq!(base32_decode("MZXW6").unwrap(), b"foo");
+    for invalid in ["m", "mz", "mzxw1", "mzxr"] {
+        assert_eq!(base32_decode(invalid).unwrap_err(), Error::DECODING_ERROR);
+    }
+}
+
+#[test]
+pub fn canonical_json_vectors() {
+    let value: serde_json::Value = serde_json::from_str(
+        r#"{"numbers": [333333333.3333333, 1E30, 4.50, 2e-3, 1E-27], "string": "\u20ac$\u000F\u000aA'\u0042\u0022\u005c\\\"\/", "literals": [null, true, false]}"#,
+    )
+    .unwrap();
+    assert_eq!(
+        String::from_utf8(canonical_json(&value)).unwrap(),
+        r#"{"literals":[null,true,false],"numbers":[333333333.3333333,1e30,4.5,0.002,1e-27],"string":"€$\u000f\nA'B\"\\\\\"/"}"#
+    );
+
+    let value: serde_json::Value =
+        serde_json::from_str(r#"{"\u20ac": 1, "\r": 2, "\ud83d\ude00": 3, "\u00f6": 4, "1": 5}"#)
+            .unwrap();
+    assert_eq!(
+        String::from_utf8(canonical_json(&value)).unwrap(),
+        "{\"\\r\":2,\"1\":5,\"ö\":4,\"€\":1,\"😀\":3}"
+    );
+}
+
+#[test]
+pub fn json_output_formats() {
+    let value = json!({"b": [1, {"d": null, "c": "x"}], "a": {}, "€": []});
+    assert_eq!(
+        to_json(&value, JsonFormat::Canonical).unwrap(),
+        r#"{"a":{},"b":[1,{"c":"x","d":null}],"€":[]}"#
+    );
+    assert_eq!(
+        to_json(&value, JsonFormat::Pretty).unwrap(),
+        "{\n  \"a\": {},\n  \"b\": [\n    1,\n    {\n      \"c\": \"x\",\n      \"d\": null\n    }\n  ],\n  \"€\": []\n}"
+    );
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(&to_json(&value, JsonFormat::Pretty).unwrap())
+            .unwrap(),
+        value
+    );
+
+    let jwk = Jwk {
+        kty: String::from("OKP"),
+        crv: Some(String::from("Ed25519")),
+        x: Some(String::from("11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo")),
+        kid: Some(String::from("key-1")),
+        ..Default::default()
+    };
+    assert_eq!(
+        jwk.to_json().unwrap(),
+        r#"{"crv":"Ed25519","kid":"key-1","kty":"OKP","x":"11qYAYKxCrfVS_7TyWQHOg7hcvPapiMlrwIaaPcHURo"}"#
+    );
+    assert_eq!(Jwk::from_json(&jwk.to_json().unwrap()).unwrap(), jwk);
+    assert!(jwk
+        .to_json_with_format(JsonFormat::Pretty)
+        .unwrap()
+        .starts_with("{\n  \"crv\": \"Ed25519\",\n"));
+
+    let jwks = Jwks { keys: vec![jwk] };
+    assert_eq!(
+        jwks.to_json().unwrap(),
+        to_json(&jwks, JsonFormat::Canonical).unwrap()
+    );
+    assert!(jwks.to_json().unwrap().starts_with(r#"{"keys":[{"crv""#));
+}
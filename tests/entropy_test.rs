@@ -0,0 +1,121 @@
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{
+        any::AnySigningKey,
+        entropy::{self, EntropySource, HealthCheckedRng, OsEntropy, STARTUP_SAMPLES},
+    },
+    errors::Error,
+};
+use rand::RngCore;
+
+fn constant(byte: u8) -> Arc<dyn EntropySource> {
+    Arc::new(move |dest: &mut [u8]| -> Result<(), Error> {
+        dest.fill(byte);
+        Ok(())
+    })
+}
+
+#[test]
+pub fn entropy_startup_checks() {
+    let mut rng = HealthCheckedRng::os().unwrap();
+    assert!(!rng.is_failed());
+    assert_ne!(rng.next_u64(), rng.next_u64());
+    let mut bytes = [0u8; 64];
+    rng.try_fill_bytes(&mut bytes).unwrap();
+    assert_ne!(bytes, [0u8; 64]);
+
+    assert_eq!(
+        HealthCheckedRng::new(constant(0)).err(),
+        Some(Error::ENTROPY_HEALTH_CHECK_FAILED)
+    );
+
+    let repeating: Arc<dyn EntropySource> = Arc::new(|dest: &mut [u8]| -> Result<(), Error> {
+        for (index, byte) in dest.iter_mut().enumerate() {
+            *byte = (index % 16) as u8;
+        }
+        Ok(())
+    });
+    assert_eq!(
+        HealthCheckedRng::new(repeating).err(),
+        Some(Error::ENTROPY_HEALTH_CHECK_FAILED)
+    );
+
+    let biased: Arc<dyn EntropySource> = Arc::new(|dest: &mut [u8]| -> Result<(), Error> {
+        for (index, byte) in dest.iter_mut().enumerate() {
+            *byte = match index % 20 {
+                19 => (index / 20) as u8 | 1,
+                _ => 0,
+            };
+        }
+        Ok(())
+    });
+    assert_eq!(
+        HealthCheckedRng::new(biased).err(),
+        Some(Error::ENTROPY_HEALTH_CHECK_FAILED)
+    );
+
+    let broken: Arc<dyn EntropySource> =
+        Arc::new(|_: &mut [u8]| -> Result<(), Error> { Err(Error::ENTROPY_SOURCE_FAILED) });
+    assert_eq!(
+        HealthCheckedRng::new(broken).err(),
+        Some(Error::ENTROPY_SOURCE_FAILED)
+    );
+}
+
+#[test]
+pub fn entropy_fails_closed() {
+    let drawn = Arc::new(AtomicUsize::new(0));
+    let counter = drawn.clone();
+    let stuck: Arc<dyn EntropySource> = Arc::new(move |dest: &mut [u8]| -> Result<(), Error> {
+        match counter.fetch_add(dest.len(), Ordering::SeqCst) < STARTUP_SAMPLES {
+            true => OsEntropy.fill(dest),
+            false => {
+                dest.fill(0xaa);
+                Ok(())
+            }
+        }
+    });
+
+    let mut rng = HealthCheckedRng::new(stuck).unwrap();
+    let mut bytes = [0u8; 32];
+    assert!(rng.try_fill_bytes(&mut bytes).is_err());
+    assert_eq!(bytes, [0u8; 32]);
+    assert!(rng.is_failed());
+    assert!(rng.clone().is_failed());
+    assert_eq!(
+        rng.try_fill(&mut bytes).err(),
+        Some(Error::ENTROPY_HEALTH_CHECK_FAILED)
+    );
+    assert_eq!(drawn.load(Ordering::SeqCst), STARTUP_SAMPLES + 32);
+}
+
+#[test]
+pub fn entropy_source_hook() {
+    assert_eq!(
+        entropy::set_entropy_source(constant(7)).err(),
+        Some(Error::ENTROPY_HEALTH_CHECK_FAILED)
+    );
+    entropy::rng().unwrap();
+
+    let drawn = Arc::new(AtomicUsize::new(0));
+    let counter = drawn.clone();
+    entropy::set_entropy_source(Arc::new(move |dest: &mut [u8]| -> Result<(), Error> {
+        counter.fetch_add(dest.len(), Ordering::SeqCst);
+        OsEntropy.fill(dest)
+    }))
+    .unwrap();
+    assert_eq!(drawn.load(Ordering::SeqCst), STARTUP_SAMPLES);
+
+    AnySigningKey::generate(Algorithm::ES256).unwrap();
+    assert!(drawn.load(Ordering::SeqCst) > STARTUP_SAMPLES);
+
+    entropy::clear_entropy_source();
+    let before = drawn.load(Ordering::SeqCst);
+    AnySigningKey::generate(Algorithm::ES256).unwrap();
+    assert_eq!(drawn.load(Ordering::SeqCst), before);
+}
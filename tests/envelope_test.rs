@@ -0,0 +1,115 @@
+use chrono::{TimeZone, Utc};
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{any::AnySigningKey, signature::Signature},
+    envelope::{EnvelopeFormat, SignatureEnvelope},
+    errors::Error,
+    signer, verifier,
+};
+use serde_json::Value;
+
+const MESSAGE: &[u8] = b"detached payload";
+
+#[test]
+pub fn envelope_json_round_trip() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let created = Utc.with_ymd_and_hms(2026, 1, 2, 3, 4, 5).unwrap();
+    let envelope = SignatureEnvelope::sign(MESSAGE, &key, Algorithm::ES256)
+        .unwrap()
+        .with_kid("did:example:alice#key-1")
+        .with_created(created);
+
+    let json = envelope.to_json().unwrap();
+    let value: Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["alg"], "ES256");
+    assert_eq!(value["kid"], "did:example:alice#key-1");
+    assert_eq!(value["created"], "2026-01-02T03:04:05Z");
+    assert_eq!(value["sig"], envelope.signature().to_base64url());
+
+    let decoded = SignatureEnvelope::from_json(&json).unwrap();
+    assert_eq!(decoded, envelope);
+    assert_eq!(decoded.kid(), Some("did:example:alice#key-1"));
+    assert_eq!(decoded.created(), Some(created));
+    assert!(decoded.verify(MESSAGE, key.verifying_key()).unwrap());
+    assert!(!decoded
+        .verify(b"other payload", key.verifying_key())
+        .unwrap());
+
+    let bare = SignatureEnvelope::new(Signature::new(vec![1, 2, 3], Algorithm::EdDSA));
+    assert_eq!(bare.to_json().unwrap(), r#"{"alg":"EdDSA","sig":"AQID"}"#);
+}
+
+#[test]
+pub fn envelope_cbor_round_trip() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let envelope = SignatureEnvelope::sign(MESSAGE, &key, Algorithm::ES256)
+        .unwrap()
+        .with_kid("key-1");
+
+    let cbor = envelope.encode(EnvelopeFormat::Cbor).unwrap();
+    let decoded = SignatureEnvelope::decode(&cbor, EnvelopeFormat::Cbor).unwrap();
+    assert_eq!(decoded, envelope);
+    assert!(decoded.verify(MESSAGE, key.verifying_key()).unwrap());
+    assert!(cbor.len() < envelope.to_json().unwrap().len());
+
+    let bare = SignatureEnvelope::new(Signature::new(vec![1, 2, 3], Algorithm::ES256));
+    let decoded = SignatureEnvelope::from_cbor(&bare.to_cbor().unwrap()).unwrap();
+    assert_eq!(decoded.kid(), None);
+    assert_eq!(decoded.created(), None);
+}
+
+#[test]
+pub fn envelope_sign_and_verify() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+
+    for format in [EnvelopeFormat::Json, EnvelopeFormat::Cbor] {
+        let envelope =
+            signer::sign_envelope(MESSAGE, &key, Algorithm::ES256, Some("key-1"), format).unwrap();
+        assert!(verifier::verify_envelope(
+            MESSAGE,
+            &envelope,
+            format,
+            key.verifying_key(),
+            Algorithm::ES256
+        )
+        .unwrap());
+
+        let error = verifier::verify_envelope(
+            MESSAGE,
+            &envelope,
+            format,
+            key.verifying_key(),
+            Algorithm::ES384,
+        )
+        .err()
+        .unwrap();
+        assert_eq!(error, Error::SIGNATURE_ENVELOPE_ALGORITHM_MISMATCH);
+        assert_eq!(error.algorithm(), Some(Algorithm::ES256));
+    }
+}
+
+#[test]
+pub fn envelope_malformed() {
+    for envelope in [
+        r#"{"kid":"key-1","sig":"AQID"}"#,
+        r#"{"alg":"ES256"}"#,
+        r#"{"alg":"ES256","sig":"AQID","extra":true}"#,
+        "not json",
+    ] {
+        let error = SignatureEnvelope::from_json(envelope).err().unwrap();
+        assert_eq!(error, Error::SIGNATURE_ENVELOPE_MALFORMED);
+        assert!(error.is_malformed_input());
+    }
+    assert_eq!(
+        SignatureEnvelope::decode(&[0xff, 0xfe], EnvelopeFormat::Json).err(),
+        Some(Error::SIGNATURE_ENVELOPE_MALFORMED)
+    );
+    assert_eq!(
+        SignatureEnvelope::from_cbor(&[0x01]).err(),
+        Some(Error::SIGNATURE_ENVELOPE_MALFORMED)
+    );
+    assert_eq!(
+        SignatureEnvelope::from_cbor(&[0xa1, 0x63, b'a', b'l', b'g', 0x01]).err(),
+        Some(Error::SIGNATURE_ENVELOPE_MALFORMED)
+    );
+}
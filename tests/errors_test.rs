@@ -0,0 +1,50 @@
+use std::error::Error as StdError;
+
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{any::AnyVerifyingKey, hmac::HMACKey, SignFromKey},
+    errors::Error,
+    keystore::{InMemoryKeyStore, KeyStore},
+};
+
+#[test]
+pub fn errors_preserve_display_and_carry_sources() {
+    let error = AnyVerifyingKey::from_x509_pem("not a certificate")
+        .err()
+        .unwrap();
+    assert_eq!(error, Error::X509_CERTIFICATE_ERROR);
+    assert_eq!(error.to_string(), Error::X509_CERTIFICATE_ERROR.to_string());
+    assert!(error.source().is_some());
+    assert!(Error::X509_CERTIFICATE_ERROR.source().is_none());
+
+    let error = HMACKey::new(String::from("secret"))
+        .sign_bytes("content".as_bytes(), Algorithm::ES256)
+        .err()
+        .unwrap();
+    assert_eq!(error, Error::UNKNOWN_ALGORITHM);
+    assert_eq!(error.algorithm(), Some(Algorithm::ES256));
+    assert!(error.key().is_none());
+
+    let store: InMemoryKeyStore = InMemoryKeyStore::new();
+    let error = store.get("did:example:123#missing").err().unwrap();
+    assert_eq!(error, Error::KEYSTORE_KEY_NOT_FOUND);
+    assert_eq!(error.key(), Some("did:example:123#missing"));
+    assert_eq!(
+        error.message(),
+        Error::KEYSTORE_KEY_NOT_FOUND.to_string().as_str()
+    );
+}
+
+#[test]
+pub fn errors_context_builders() {
+    let error = Error::SIGNING_FAILED
+        .with_algorithm(Algorithm::EdDSA)
+        .with_key("key-1")
+        .with_source(String::from("device unavailable"));
+    assert_eq!(error, Error::SIGNING_FAILED);
+    assert_ne!(error, Error::UNKNOWN_ALGORITHM);
+    assert_eq!(error.algorithm(), Some(Algorithm::EdDSA));
+    assert_eq!(error.key(), Some("key-1"));
+    assert_eq!(error.source().unwrap().to_string(), "device unavailable");
+    assert_eq!(error.to_string(), Error::SIGNING_FAILED.to_string());
+}
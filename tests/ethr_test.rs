@@ -0,0 +1,276 @@
+#![cfg(feature = "es256k")]
+
+use did_crypto::{
+    algorithms::Algorithm,
+    caip::{checksum_address, eip155_address},
+    crypto::{any::AnyVerifyingKey, ecdsa::_256k::P256kSigningKey, SignFromKey, VerifyFromKey},
+    errors::Error,
+    ethr::{EthrDid, EthrResolver},
+    resolver::{resolve_keys, DidResolver, ASSERTION_METHOD, AUTHENTICATION},
+};
+
+const PRIVATE_KEY: &str = "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318";
+const ADDRESS: &str = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+
+fn signing_key() -> P256kSigningKey {
+    P256kSigningKey::from_bytes(&hex::decode(PRIVATE_KEY).unwrap()).unwrap()
+}
+
+#[test]
+pub fn ethr_addresses() {
+    let key = signing_key().verifying_key();
+    assert_eq!(checksum_address(&eip155_address(&key)), ADDRESS);
+
+    let mut address = [0u8; 20];
+    hex::decode_to_slice("5aaeb6053f3e94c9b9a09f33669435e7ef1beaed", &mut address).unwrap();
+    assert_eq!(
+        checksum_address(&address),
+        "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"
+    );
+}
+
+#[test]
+pub fn ethr_did_parsing() {
+    let did = EthrDid::parse(&format!("did:ethr:{}", ADDRESS)).unwrap();
+    assert_eq!(did.chain_id, 1);
+    assert_eq!(did.public_key, None);
+    assert_eq!(did.blockchain_account_id(), format!("eip155:1:{}", ADDRESS));
+
+    let did = EthrDid::parse(&format!("did:ethr:sepolia:{}", ADDRESS)).unwrap();
+    assert_eq!(did.chain_id, 11155111);
+    let did = EthrDid::parse(&format!("did:ethr:0x89:{}", ADDRESS)).unwrap();
+    assert_eq!(did.chain_id, 137);
+
+    let key = signing_key().verifying_key();
+    let did = EthrDid::from_key(&key, 5);
+    assert!(did.did.starts_with("did:ethr:0x5:0x0"));
+    assert_eq!(EthrDid::parse(&did.did).unwrap(), did);
+    assert_eq!(checksum_address(&did.address), ADDRESS);
+
+    assert_eq!(
+        EthrDid::parse("did:ethr:unknown:0x2c7536e3605d9c16a7a3d7b1898e529396a65c23").err(),
+        Some(Error::ETHR_UNKNOWN_NETWORK)
+    );
+    assert_eq!(
+        EthrDid::parse("did:ethr:0x1234").err(),
+        Some(Error::ETHR_INVALID_DID)
+    );
+    assert_eq!(
+        EthrDid::parse("did:example:123").err(),
+        Some(Error::ETHR_INVALID_DID)
+    );
+}
+
+#[test]
+pub fn ethr_default_document() {
+    let signing_key = signing_key();
+    let did = EthrDid::from_key(&signing_key.verifying_key(), 1);
+    let document = EthrResolver.resolve(&did.did).unwrap();
+
+    assert_eq!(document["id"], did.did.as_str());
+    assert_eq!(
+        document["verificationMethod"][0]["blockchainAccountId"],
+        format!("eip155:1:{}", ADDRESS)
+    );
+    assert_eq!(
+        document["authentication"][1],
+        format!("{}#controllerKey", did.did)
+    );
+
+    let keys = resolve_keys(&EthrResolver, &did.did, AUTHENTICATION).unwrap();
+    assert_eq!(keys.len(), 1);
+    let key = AnyVerifyingKey::from_jwk(&keys[0]).unwrap();
+    let signature = signing_key
+        .sign_bytes(b"did:ethr", Algorithm::ES256K)
+        .unwrap();
+    assert!(key
+        .verify_bytes(b"did:ethr", &signature, Algorithm::ES256K)
+        .unwrap());
+
+    let address_only = EthrResolver
+        .resolve(&format!("did:ethr:{}", ADDRESS))
+        .unwrap();
+    assert_eq!(
+        address_only["verificationMethod"].as_array().unwrap().len(),
+        1
+    );
+    assert_eq!(
+        resolve_keys(
+            &EthrResolver,
+            &format!("did:ethr:{}", ADDRESS),
+            ASSERTION_METHOD
+        )
+        .err(),
+        Some(Error::DID_VERIFICATION_METHOD_NOT_FOUND)
+    );
+}
+
+#[cfg(feature = "ethr-registry")]
+mod registry {
+    use std::sync::Mutex;
+
+    use chrono::Utc;
+    use did_crypto::{
+        errors::Error,
+        ethr::{JsonRpc, RegistryEvent, RegistryResolver, ERC1056_REGISTRY},
+        resolver::{resolve_keys, DidResolver, AUTHENTICATION, KEY_AGREEMENT},
+    };
+    use serde_json::{json, Value};
+    use sha3::{Digest, Keccak256};
+
+    const IDENTITY: &str = "2c7536e3605d9c16a7a3d7b1898e529396a65c23";
+    const DELEGATE: &str = "5aaeb6053f3e94c9b9a09f33669435e7ef1beaed";
+
+    fn topic(signature: &str) -> String {
+        format!("0x{}", hex::encode(Keccak256::digest(signature.as_bytes())))
+    }
+
+    fn uint(value: u64) -> String {
+        format!("{:064x}", value)
+    }
+
+    fn bytes32(value: &str) -> String {
+        format!("{:0<64}", hex::encode(value))
+    }
+
+    fn address(value: &str) -> String {
+        format!("{:0>64}", value)
+    }
+
+    fn log(block: u64, signature: &str, data: String) -> Value {
+        json!({
+            "blockNumber": format!("0x{:x}", block),
+            "topics": [topic(signature), format!("0x{}", address(IDENTITY))],
+            "data": format!("0x{}", data),
+        })
+    }
+
+    fn attribute(block: u64, name: &str, value: &[u8], valid_to: u64, previous: u64) -> Value {
+        let padded = format!(
+            "{:0<width$}",
+            hex::encode(value),
+            width = value.len().div_ceil(32) * 64
+        );
+        log(
+            block,
+            "DIDAttributeChanged(address,bytes32,bytes,uint256,uint256)",
+            format!(
+                "{}{}{}{}{}{}",
+                bytes32(name),
+                uint(128),
+                uint(valid_to),
+                uint(previous),
+                uint(value.len() as u64),
+                padded
+            ),
+        )
+    }
+
+    struct MockRpc {
+        calls: Mutex<Vec<String>>,
+        ed25519: Vec<u8>,
+        x25519: Vec<u8>,
+    }
+
+    impl JsonRpc for MockRpc {
+        fn call(&self, method: &str, params: Value) -> Result<Value, Error> {
+            self.calls.lock().unwrap().push(String::from(method));
+            let valid_to = Utc::now().timestamp() as u64 + 3600;
+            match method {
+                "eth_call" => {
+                    assert_eq!(params[0]["to"], ERC1056_REGISTRY);
+                    Ok(Value::from(format!("0x{}", uint(9))))
+                }
+                "eth_getLogs" => match params[0]["fromBlock"].as_str().unwrap() {
+                    "0x9" => Ok(json!([
+                        attribute(9, "did/pub/X25519/enc/base64", &self.x25519, valid_to, 7),
+                        attribute(
+                            9,
+                            "did/svc/MessagingService",
+                            b"https://msg.example.com",
+                            valid_to,
+                            7
+                        ),
+                    ])),
+                    "0x7" => Ok(json!([
+                        log(
+                            7,
+                            "DIDDelegateChanged(address,bytes32,address,uint256,uint256)",
+                            format!(
+                                "{}{}{}{}",
+                                bytes32("sigAuth"),
+                                address(DELEGATE),
+                                uint(valid_to),
+                                uint(4)
+                            )
+                        ),
+                        attribute(7, "did/pub/Ed25519/sigAuth/hex", &self.ed25519, valid_to, 4),
+                    ])),
+                    "0x4" => Ok(json!([attribute(
+                        4,
+                        "did/pub/Ed25519/veriKey/hex",
+                        &[7u8; 32],
+                        1,
+                        0
+                    )])),
+                    _ => panic!(),
+                },
+                _ => panic!(),
+            }
+        }
+    }
+
+    fn mock() -> MockRpc {
+        MockRpc {
+            calls: Mutex::new(Vec::new()),
+            ed25519: vec![1u8; 32],
+            x25519: vec![2u8; 32],
+        }
+    }
+
+    #[test]
+    pub fn ethr_registry_history() {
+        let resolver = RegistryResolver::with_client(1, mock());
+        let history = resolver
+            .history(&hex::decode(IDENTITY).unwrap().try_into().unwrap())
+            .unwrap();
+
+        assert_eq!(history.len(), 5);
+        assert!(matches!(
+            &history[0],
+            RegistryEvent::AttributeChanged { valid_to: 1, .. }
+        ));
+        assert!(matches!(
+            &history[1],
+            RegistryEvent::DelegateChanged { delegate_type, .. } if delegate_type == "sigAuth"
+        ));
+        assert_eq!(resolver.client().calls.lock().unwrap().len(), 4);
+    }
+
+    #[test]
+    pub fn ethr_registry_document() {
+        let resolver = RegistryResolver::with_client(1, mock());
+        let did = format!("did:ethr:0x{}", IDENTITY);
+        let document = resolver.resolve(&did).unwrap();
+
+        let methods = document["verificationMethod"].as_array().unwrap();
+        assert_eq!(methods.len(), 4);
+        assert_eq!(methods[1]["id"], format!("{}#delegate-2", did));
+        assert_eq!(
+            document["service"][0]["serviceEndpoint"],
+            "https://msg.example.com"
+        );
+        assert_eq!(document["keyAgreement"][0], format!("{}#delegate-4", did));
+
+        let keys = resolve_keys(&resolver, &did, AUTHENTICATION).unwrap();
+        assert_eq!(keys.len(), 1);
+        assert_eq!(keys[0].crv.as_deref(), Some("Ed25519"));
+        let keys = resolve_keys(&resolver, &did, KEY_AGREEMENT).unwrap();
+        assert_eq!(keys[0].crv.as_deref(), Some("X25519"));
+
+        assert_eq!(
+            RegistryResolver::with_client(5, mock()).resolve(&did).err(),
+            Some(Error::ETHR_UNKNOWN_NETWORK)
+        );
+    }
+}
@@ -0,0 +1,254 @@
+#![cfg(feature = "eddsa")]
+
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{any::AnySigningKey, kdf::PasswordKdf, x25519::X25519SecretKey},
+    errors::Error,
+    export::{
+        BundleKey, EncryptedDidWallet, ExportedKeyBundle, DID_WALLET_TYP, DID_WALLET_VERSION,
+        EXPORT_BUNDLE_VERSION,
+    },
+    keystore::{FileKeyStore, InMemoryKeyStore, KeyStore},
+    resolver::{CachingResolver, DidResolver},
+};
+use serde_json::{json, Value};
+
+fn store() -> InMemoryKeyStore {
+    let store = InMemoryKeyStore::new();
+    store
+        .add("key-1", AnySigningKey::generate(Algorithm::EdDSA).unwrap())
+        .unwrap();
+    store
+        .add("key-2", AnySigningKey::generate(Algorithm::ES256).unwrap())
+        .unwrap();
+    store.revoke("key-2").unwrap();
+    store
+}
+
+fn kdf() -> PasswordKdf {
+    PasswordKdf::scrypt(10, 8, 1).unwrap()
+}
+
+#[test]
+pub fn export_bundle_with_passphrase() {
+    let source = store();
+    let bundle = ExportedKeyBundle::export_with_kdf(&source, "passphrase", kdf()).unwrap();
+    assert_eq!(bundle.version, EXPORT_BUNDLE_VERSION);
+    assert_eq!(
+        bundle.keys,
+        vec![
+            BundleKey {
+                kid: String::from("key-1"),
+                alg: Algorithm::EdDSA,
+            },
+            BundleKey {
+                kid: String::from("key-2"),
+                alg: Algorithm::ES256,
+            },
+        ]
+    );
+
+    let bundle = ExportedKeyBundle::from_json(&bundle.to_json().unwrap()).unwrap();
+    let target = InMemoryKeyStore::new();
+    assert_eq!(
+        bundle.import_with_passphrase("wrong", &target).unwrap_err(),
+        Error::KEYSTORE_WRONG_PASSPHRASE
+    );
+    assert_eq!(
+        bundle
+            .import_with_passphrase("passphrase", &target)
+            .unwrap(),
+        vec!["key-1", "key-2"]
+    );
+    assert_eq!(
+        target.get("key-1").unwrap().verifying_key(),
+        source.get("key-1").unwrap().verifying_key()
+    );
+    assert!(target.validity("key-2").unwrap().revoked);
+
+    let mut tampered = bundle.clone();
+    tampered.keys[0].alg = Algorithm::ES256K;
+    assert_eq!(
+        tampered
+            .import_with_passphrase("passphrase", &InMemoryKeyStore::new())
+            .unwrap_err(),
+        Error::KEYSTORE_WRONG_PASSPHRASE
+    );
+    assert_eq!(
+        bundle
+            .import_with_key("bob", &X25519SecretKey::generate(), &target)
+            .unwrap_err(),
+        Error::EXPORT_BUNDLE_PROTECTION_MISMATCH
+    );
+}
+
+#[test]
+pub fn export_bundle_to_recipient() {
+    let source = store();
+    let device = X25519SecretKey::generate();
+    let bundle =
+        ExportedKeyBundle::export_to_recipients(&source, &[("device-2", device.public_key())])
+            .unwrap();
+    let bundle = ExportedKeyBundle::from_json(&bundle.to_json().unwrap()).unwrap();
+
+    let target = InMemoryKeyStore::new();
+    assert_eq!(
+        bundle
+            .import_with_key("device-2", &device, &target)
+            .unwrap(),
+        vec!["key-1", "key-2"]
+    );
+    assert_eq!(
+        target.get("key-2").unwrap().verifying_key(),
+        source.get("key-2").unwrap().verifying_key()
+    );
+    assert!(bundle
+        .import_with_key(
+            "device-2",
+            &X25519SecretKey::generate(),
+            &InMemoryKeyStore::new()
+        )
+        .is_err());
+    assert_eq!(
+        bundle
+            .import_with_passphrase("passphrase", &target)
+            .unwrap_err(),
+        Error::EXPORT_BUNDLE_PROTECTION_MISMATCH
+    );
+}
+
+#[test]
+pub fn export_bundle_migrates_key_store_files() {
+    let path = std::env::temp_dir().join(format!("did-crypto-export-{}.json", std::process::id()));
+    let mut file_store = FileKeyStore::create_with_kdf(&path, "passphrase", kdf()).unwrap();
+    let key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let verifying_key = key.verifying_key();
+    file_store.add("legacy", key).unwrap();
+    file_store.save().unwrap();
+    let contents = std::fs::read_to_string(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    let legacy = ExportedKeyBundle::from_json(&contents).unwrap();
+    assert_eq!(legacy.version, 1);
+    assert!(legacy.keys.is_empty());
+
+    let migrated = legacy.migrate("passphrase").unwrap();
+    assert_eq!(migrated.version, EXPORT_BUNDLE_VERSION);
+    assert_eq!(migrated.keys[0].kid, "legacy");
+
+    let target = InMemoryKeyStore::new();
+    migrated
+        .import_with_passphrase("passphrase", &target)
+        .unwrap();
+    assert_eq!(target.get("legacy").unwrap().verifying_key(), verifying_key);
+
+    assert_eq!(
+        ExportedKeyBundle::from_json(r#"{"version":9}"#).unwrap_err(),
+        Error::EXPORT_BUNDLE_UNSUPPORTED_VERSION
+    );
+    assert_eq!(
+        ExportedKeyBundle::from_json("[]").unwrap_err(),
+        Error::EXPORT_BUNDLE_MALFORMED
+    );
+}
+
+struct OfflineResolver;
+
+impl DidResolver for OfflineResolver {
+    fn resolve(&self, _did: &str) -> Result<Value, Error> {
+        Err(Error::DID_RESOLUTION_ERROR)
+    }
+}
+
+#[test]
+pub fn did_wallet_round_trip() {
+    let source = store();
+    let document = json!({
+        "id": "did:example:wallet",
+        "verificationMethod": [{
+            "id": "#key-1",
+            "type": "JsonWebKey2020",
+            "controller": "did:example:wallet",
+            "publicKeyJwk": source.get("key-1").unwrap().verifying_key().to_jwk().unwrap(),
+        }],
+    });
+    let other = json!({"id": "did:example:other"});
+
+    let wallet =
+        EncryptedDidWallet::seal_with_kdf(&[document.clone(), other], &source, "passphrase", kdf())
+            .unwrap();
+    assert_eq!(wallet.version, DID_WALLET_VERSION);
+    assert_eq!(wallet.typ, DID_WALLET_TYP);
+    assert_eq!(wallet.dids, vec!["did:example:other", "did:example:wallet"]);
+    assert_eq!(wallet.keys.len(), 2);
+
+    let json = wallet.to_json().unwrap();
+    assert!(!json.contains("verificationMethod"));
+    let wallet = EncryptedDidWallet::from_json(&json).unwrap();
+
+    let target = InMemoryKeyStore::new();
+    let cache = CachingResolver::new(OfflineResolver);
+    let dids = wallet
+        .import_with_cache("passphrase", &target, &cache)
+        .unwrap();
+    assert_eq!(dids, vec!["did:example:other", "did:example:wallet"]);
+    assert_eq!(cache.resolve("did:example:wallet").unwrap(), document);
+    assert_eq!(
+        target.get("key-1").unwrap().verifying_key(),
+        source.get("key-1").unwrap().verifying_key()
+    );
+    assert!(target.validity("key-2").unwrap().revoked);
+
+    assert_eq!(
+        wallet
+            .import("wrong", &InMemoryKeyStore::new())
+            .unwrap_err(),
+        Error::KEYSTORE_WRONG_PASSPHRASE
+    );
+
+    let mut relabeled = wallet.clone();
+    relabeled.dids = vec![String::from("did:example:attacker")];
+    assert_eq!(
+        relabeled
+            .import("passphrase", &InMemoryKeyStore::new())
+            .unwrap_err(),
+        Error::KEYSTORE_WRONG_PASSPHRASE
+    );
+}
+
+#[test]
+pub fn did_wallet_rejects_invalid_input() {
+    let source = store();
+    for documents in [
+        vec![json!({"name": "no id"})],
+        vec![json!({"id": "did:example:123#key-1"})],
+        vec![
+            json!({"id": "did:example:123"}),
+            json!({"id": "did:example:123"}),
+        ],
+    ] {
+        assert_eq!(
+            EncryptedDidWallet::seal_with_kdf(&documents, &source, "passphrase", kdf())
+                .unwrap_err(),
+            Error::DID_WALLET_MALFORMED
+        );
+    }
+
+    assert_eq!(
+        EncryptedDidWallet::from_json(r#"{"version":9}"#).unwrap_err(),
+        Error::DID_WALLET_UNSUPPORTED_VERSION
+    );
+    assert_eq!(
+        EncryptedDidWallet::from_json("[]").unwrap_err(),
+        Error::DID_WALLET_MALFORMED
+    );
+
+    let mut wallet = EncryptedDidWallet::seal_with_kdf(&[], &source, "passphrase", kdf()).unwrap();
+    wallet.version = 2;
+    assert_eq!(
+        wallet
+            .import("passphrase", &InMemoryKeyStore::new())
+            .unwrap_err(),
+        Error::DID_WALLET_UNSUPPORTED_VERSION
+    );
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "ffi")]
+
+use std::{ffi::CString, ptr};
+
+use did_crypto::{errors::Error, ffi::*};
+
+const MESSAGE: &[u8] = b"did:example:123";
+
+fn buffer(call: impl Fn(*mut u8, *mut usize) -> i32) -> Result<Vec<u8>, i32> {
+    let mut len = 0;
+    match call(ptr::null_mut(), &mut len) {
+        DID_CRYPTO_OK => return Ok(Vec::new()),
+        DID_CRYPTO_ERROR_BUFFER_TOO_SMALL => {}
+        code => return Err(code),
+    }
+
+    let mut out = vec![0u8; len];
+    match call(out.as_mut_ptr(), &mut len) {
+        DID_CRYPTO_OK => Ok(out),
+        code => Err(code),
+    }
+}
+
+#[test]
+pub fn ffi_sign_and_verify() {
+    for name in ["ES256", "EdDSA", "ES256K"] {
+        let alg = CString::new(name).unwrap();
+        let private_key =
+            buffer(|out, out_len| unsafe { did_crypto_generate_key(alg.as_ptr(), out, out_len) })
+                .unwrap();
+        let public_key = buffer(|out, out_len| unsafe {
+            did_crypto_public_key(
+                alg.as_ptr(),
+                private_key.as_ptr(),
+                private_key.len(),
+                out,
+                out_len,
+            )
+        })
+        .unwrap();
+        let signature = buffer(|out, out_len| unsafe {
+            did_crypto_sign(
+                alg.as_ptr(),
+                private_key.as_ptr(),
+                private_key.len(),
+                MESSAGE.as_ptr(),
+                MESSAGE.len(),
+                out,
+                out_len,
+            )
+        })
+        .unwrap();
+        let signature = CString::new(signature).unwrap();
+
+        let mut valid = false;
+        let code = unsafe {
+            did_crypto_verify(
+                alg.as_ptr(),
+                public_key.as_ptr(),
+                public_key.len(),
+                MESSAGE.as_ptr(),
+                MESSAGE.len(),
+                signature.as_ptr(),
+                &mut valid,
+            )
+        };
+        assert_eq!(code, DID_CRYPTO_OK);
+        assert!(valid);
+    }
+}
+
+#[test]
+pub fn ffi_jwk_import_and_export() {
+    let alg = CString::new("ES256").unwrap();
+    let private_key =
+        buffer(|out, out_len| unsafe { did_crypto_generate_key(alg.as_ptr(), out, out_len) })
+            .unwrap();
+
+    let jwk = buffer(|out, out_len| unsafe {
+        did_crypto_private_key_to_jwk(
+            alg.as_ptr(),
+            private_key.as_ptr(),
+            private_key.len(),
+            out,
+            out_len,
+        )
+    })
+    .unwrap();
+    let jwk = CString::new(jwk).unwrap();
+    let imported = buffer(|out, out_len| unsafe {
+        did_crypto_private_key_from_jwk(jwk.as_ptr(), out, out_len)
+    })
+    .unwrap();
+    assert_eq!(imported, private_key);
+
+    let public_key = buffer(|out, out_len| unsafe {
+        did_crypto_public_key(
+            alg.as_ptr(),
+            private_key.as_ptr(),
+            private_key.len(),
+            out,
+            out_len,
+        )
+    })
+    .unwrap();
+    let jwk = buffer(|out, out_len| unsafe {
+        did_crypto_public_key_to_jwk(
+            alg.as_ptr(),
+            public_key.as_ptr(),
+            public_key.len(),
+            out,
+            out_len,
+        )
+    })
+    .unwrap();
+    let jwk = CString::new(jwk).unwrap();
+    let imported = buffer(|out, out_len| unsafe {
+        did_crypto_public_key_from_jwk(jwk.as_ptr(), out, out_len)
+    })
+    .unwrap();
+    assert_eq!(imported, public_key);
+}
+
+#[test]
+pub fn ffi_error_codes() {
+    let alg = CString::new("none").unwrap();
+    let code =
+        buffer(|out, out_len| unsafe { did_crypto_generate_key(alg.as_ptr(), out, out_len) })
+            .err()
+            .unwrap();
+    assert_eq!(code, Error::UNKNOWN_ALGORITHM.code());
+    assert_eq!(Error::from_code(code), Some(Error::UNKNOWN_ALGORITHM));
+
+    let message =
+        buffer(|out, out_len| unsafe { did_crypto_error_message(code, out, out_len) }).unwrap();
+    assert_eq!(
+        String::from_utf8(message).unwrap(),
+        Error::UNKNOWN_ALGORITHM.to_string()
+    );
+
+    let mut len = 0;
+    assert_eq!(
+        unsafe { did_crypto_generate_key(ptr::null(), ptr::null_mut(), &mut len) },
+        DID_CRYPTO_ERROR_NULL_POINTER
+    );
+    assert_eq!(
+        Error::UNKNOWN_ALGORITHM
+            .with_algorithm(did_crypto::algorithms::Algorithm::ES256)
+            .code(),
+        Error::UNKNOWN_ALGORITHM.code()
+    );
+    assert_eq!(Error::from_code(0), None);
+}
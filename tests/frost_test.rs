@@ -0,0 +1,145 @@
+#![cfg(all(feature = "es256k", feature = "eddsa"))]
+
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{
+        any::AnySigningKey,
+        frost::{
+            aggregate, commit, generate_with_dealer, sign, split_key, verify_signature,
+            Ciphersuite, Ed25519Sha512, KeyShare, PublicKeyPackage, Secp256k1Sha256,
+            SignatureShare, SigningCommitments,
+        },
+    },
+    errors::Error,
+    verifier::verify,
+};
+use rand::rngs::OsRng;
+
+const MESSAGE: &[u8] = b"did:example:123#controller rotate";
+
+fn threshold_sign<C: Ciphersuite>(
+    signers: &[&KeyShare<C>],
+    package: &PublicKeyPackage<C>,
+) -> Result<String, Error> {
+    let mut nonces = Vec::new();
+    let mut commitments = Vec::new();
+    for share in signers {
+        let (nonce, commitment) = commit(share, &mut OsRng);
+        nonces.push(nonce);
+        commitments.push(SigningCommitments::from_bytes(&commitment.to_bytes()).unwrap());
+    }
+
+    let mut shares = Vec::new();
+    for (share, nonce) in signers.iter().zip(nonces) {
+        let signature_share = sign(MESSAGE, share, nonce, &commitments)?;
+        shares.push(SignatureShare::from_bytes(&signature_share.to_bytes()).unwrap());
+    }
+
+    aggregate(MESSAGE, &commitments, &shares, package)
+}
+
+#[test]
+pub fn frost_ed25519_split_key_produces_standard_signatures() {
+    let key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let (shares, package) = split_key::<Ed25519Sha512>(&key, 2, 3, &mut OsRng).unwrap();
+    assert_eq!(package.verifying_key().unwrap(), key.verifying_key());
+    assert_eq!(package.max_signers(), 3);
+
+    for share in &shares {
+        package.verify_share(share).unwrap();
+    }
+
+    for signers in [[&shares[0], &shares[1]], [&shares[2], &shares[0]]] {
+        let signature = threshold_sign(&signers, &package).unwrap();
+        assert!(verify(MESSAGE, &signature, key.verifying_key(), Algorithm::EdDSA).unwrap());
+        assert!(verify_signature::<Ed25519Sha512>(
+            &package.group_public_key(),
+            MESSAGE,
+            &signature
+        )
+        .unwrap());
+    }
+
+    let signature = threshold_sign(&[&shares[0], &shares[1], &shares[2]], &package).unwrap();
+    assert!(verify(MESSAGE, &signature, key.verifying_key(), Algorithm::EdDSA).unwrap());
+
+    assert_eq!(
+        threshold_sign(&[&shares[1]], &package).unwrap_err(),
+        Error::FROST_INVALID_COMMITMENTS
+    );
+}
+
+#[test]
+pub fn frost_secp256k1_threshold_signing() {
+    let (shares, package) = generate_with_dealer::<Secp256k1Sha256>(3, 5, &mut OsRng).unwrap();
+    let restored = KeyShare::<Secp256k1Sha256>::from_bytes(&shares[4].to_bytes()).unwrap();
+    assert_eq!(restored.identifier(), 5);
+    package.verify_share(&restored).unwrap();
+
+    let signature = threshold_sign(&[&shares[4], &shares[1], &restored], &package);
+    assert_eq!(signature.unwrap_err(), Error::FROST_INVALID_COMMITMENTS);
+
+    let signature = threshold_sign(&[&shares[4], &shares[1], &shares[2]], &package).unwrap();
+    let group_public_key = package.group_public_key();
+    assert!(verify_signature::<Secp256k1Sha256>(&group_public_key, MESSAGE, &signature).unwrap());
+    assert!(!verify_signature::<Secp256k1Sha256>(
+        &group_public_key,
+        b"another message",
+        &signature
+    )
+    .unwrap());
+    assert_eq!(
+        package.verifying_key().unwrap().algorithm(),
+        Algorithm::ES256K
+    );
+}
+
+#[test]
+pub fn frost_rejects_invalid_shares() {
+    assert_eq!(
+        generate_with_dealer::<Ed25519Sha512>(1, 3, &mut OsRng).unwrap_err(),
+        Error::FROST_INVALID_PARAMETERS
+    );
+    assert_eq!(
+        generate_with_dealer::<Ed25519Sha512>(4, 3, &mut OsRng).unwrap_err(),
+        Error::FROST_INVALID_PARAMETERS
+    );
+
+    let (shares, package) = generate_with_dealer::<Ed25519Sha512>(2, 3, &mut OsRng).unwrap();
+    let (_, other) = generate_with_dealer::<Ed25519Sha512>(2, 3, &mut OsRng).unwrap();
+    assert_eq!(
+        other.verify_share(&shares[0]).unwrap_err(),
+        Error::FROST_INVALID_SHARE
+    );
+
+    let (first, first_commitments) = commit(&shares[0], &mut OsRng);
+    let (second, second_commitments) = commit(&shares[1], &mut OsRng);
+    let commitments = [first_commitments, second_commitments];
+    let first_share = sign(MESSAGE, &shares[0], first, &commitments).unwrap();
+    let second_share = sign(b"another message", &shares[1], second, &commitments).unwrap();
+
+    let error = aggregate(
+        MESSAGE,
+        &commitments,
+        &[first_share, second_share],
+        &package,
+    )
+    .unwrap_err();
+    assert_eq!(error, Error::FROST_INVALID_SIGNATURE_SHARE);
+    assert!(!error.is_malformed_input());
+
+    assert_eq!(
+        SigningCommitments::<Ed25519Sha512>::from_bytes(&[0u8; 66]).unwrap_err(),
+        Error::FROST_MALFORMED
+    );
+    assert_eq!(
+        split_key::<Secp256k1Sha256>(
+            &AnySigningKey::generate(Algorithm::EdDSA).unwrap(),
+            2,
+            3,
+            &mut OsRng
+        )
+        .unwrap_err(),
+        Error::UNKNOWN_ALGORITHM
+    );
+}
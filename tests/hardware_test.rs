@@ -0,0 +1,41 @@
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::hardware::{
+        HardwareKeyBackend, HardwareSigningKey, InMemoryKeyBackend, ATTESTATION_FORMAT_NONE,
+    },
+    jwt::{Header, Payload, JWT},
+};
+use serde_json::json;
+
+#[test]
+pub fn hardware_backend_signing() {
+    let backend = InMemoryKeyBackend::new();
+    let handle = backend
+        .generate_key("did-controller", Algorithm::ES256)
+        .unwrap();
+    assert_eq!(backend.find_key("did-controller").unwrap(), handle);
+    assert!(backend.generate_key("other", Algorithm::EdDSA).is_err());
+
+    let key = HardwareSigningKey::new(&backend, handle.clone());
+    let mut jwt = JWT {
+        header: Header::new(handle.0.clone(), Algorithm::ES256),
+        payload: Payload(json!({ "sub": "did:example:123", "exp": 4102444800i64 })),
+        signature: None,
+    };
+    jwt.sign(&key).unwrap();
+    assert!(jwt.validate(key.verifying_key().unwrap()).unwrap());
+
+    let mut wrong_alg = JWT {
+        header: Header::new(handle.0.clone(), Algorithm::ES384),
+        payload: Payload(json!({})),
+        signature: None,
+    };
+    assert!(wrong_alg.sign(&key).is_err());
+
+    let attestation = backend.attest(&handle, b"challenge").unwrap();
+    assert_eq!(attestation.format, ATTESTATION_FORMAT_NONE);
+
+    backend.delete_key(&handle).unwrap();
+    assert!(backend.public_key(&handle).is_err());
+    assert!(backend.find_key("did-controller").is_err());
+}
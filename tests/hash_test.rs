@@ -0,0 +1,121 @@
+use std::io::{Cursor, Write};
+
+use did_crypto::{
+    algorithms::Algorithm,
+    hash::{shake128, shake256, HashAlgorithm},
+};
+
+const ALL: [HashAlgorithm; 8] = [
+    HashAlgorithm::Sha256,
+    HashAlgorithm::Sha384,
+    HashAlgorithm::Sha512,
+    HashAlgorithm::Sha3_256,
+    HashAlgorithm::Sha3_384,
+    HashAlgorithm::Sha3_512,
+    HashAlgorithm::Shake128,
+    HashAlgorithm::Shake256,
+];
+
+#[test]
+pub fn hash_known_answers() {
+    assert_eq!(
+        hex::encode(HashAlgorithm::Sha256.digest("abc")),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        hex::encode(HashAlgorithm::Sha384.digest("abc")),
+        "cb00753f45a35e8bb5a03d699ac65007272c32ab0eded1631a8b605a43ff5bed8086072ba1e7cc2358baeca134c825a7"
+    );
+    assert_eq!(
+        hex::encode(HashAlgorithm::Sha3_256.digest("abc")),
+        "3a985da74fe225b2045c172d6bd390bd855f086e3e9d525b46bfe24511431532"
+    );
+    assert_eq!(
+        hex::encode(shake128("", 32)),
+        "7f9c2ba4e88f827d616045507605853ed73b8093f6efbc88eb1a6eacfa66ef26"
+    );
+    assert_eq!(
+        hex::encode(shake256("", 64)),
+        "46b9dd2b0ba88d13233b3feb743eeb243fcd52ea62b81b82b50c27646ed5762fd75dc4ddd8c0f200cb05019d67b592f6fc821c49479ab48640292eacb3b7c4be"
+    );
+    assert_eq!(HashAlgorithm::Shake256.digest(""), shake256("", 64));
+}
+
+#[test]
+pub fn hash_streaming_matches_one_shot() {
+    let data = vec![0x5au8; 10_000];
+
+    for hash in ALL {
+        let expected = hash.digest(&data);
+        assert_eq!(expected.len(), hash.output_length());
+        assert_eq!(hash.digest_reader(Cursor::new(&data)).unwrap(), expected);
+
+        let mut hasher = hash.hasher();
+        for chunk in data.chunks(333) {
+            hasher.write_all(chunk).unwrap();
+        }
+        assert_eq!(hasher.algorithm(), hash);
+        assert_eq!(hasher.finalize(), expected);
+    }
+}
+
+#[test]
+pub fn hash_names_and_jose_mappings() {
+    for hash in ALL {
+        assert_eq!(HashAlgorithm::from_name(hash.to_str()), Some(hash));
+    }
+    assert_eq!(HashAlgorithm::from_name("md5"), None);
+
+    assert_eq!(
+        HashAlgorithm::for_algorithm(Algorithm::ES256K),
+        HashAlgorithm::Sha256
+    );
+    assert_eq!(
+        HashAlgorithm::for_algorithm(Algorithm::PS384),
+        HashAlgorithm::Sha384
+    );
+    assert_eq!(
+        HashAlgorithm::for_algorithm(Algorithm::EdDSA),
+        HashAlgorithm::Sha512
+    );
+    assert_eq!(
+        HashAlgorithm::for_algorithm(Algorithm::MLDSA65),
+        HashAlgorithm::Shake256
+    );
+}
+
+#[cfg(feature = "blake3")]
+#[test]
+pub fn hash_blake3() {
+    use did_crypto::hash::{blake3_derive_key, blake3_keyed_hash, Hasher};
+
+    assert_eq!(
+        hex::encode(HashAlgorithm::Blake3.digest("")),
+        "af1349b9f5f9a1a6a0404dea36dcc9499bcb25c9adc112b7cc9a93cae41f3262"
+    );
+    assert_eq!(
+        HashAlgorithm::from_name("blake3"),
+        Some(HashAlgorithm::Blake3)
+    );
+
+    let key = [7u8; 32];
+    assert_eq!(
+        blake3_keyed_hash(&key, "abc"),
+        blake3::keyed_hash(&key, b"abc").as_bytes().to_vec()
+    );
+    assert_ne!(
+        blake3_keyed_hash(&key, "abc"),
+        HashAlgorithm::Blake3.digest("abc")
+    );
+
+    let mut hasher = Hasher::blake3_keyed(&key);
+    hasher.update("ab");
+    hasher.update("c");
+    assert_eq!(hasher.finalize(), blake3_keyed_hash(&key, "abc"));
+
+    let context = "did-crypto 2026-10-16 status list";
+    assert_eq!(
+        blake3_derive_key(context, "secret").to_vec(),
+        blake3::derive_key(context, b"secret").to_vec()
+    );
+}
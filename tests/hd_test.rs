@@ -0,0 +1,90 @@
+use did_crypto::crypto::{
+    hd::{parse_derivation_path, ExtendedPrivateKey, HdCurve},
+    mnemonic::Mnemonic,
+};
+
+const SEED_HEX: &str = "000102030405060708090a0b0c0d0e0f";
+
+#[test]
+pub fn bip32_secp256k1_derivation() {
+    let master =
+        ExtendedPrivateKey::master(HdCurve::Secp256k1, &hex::decode(SEED_HEX).unwrap()).unwrap();
+    assert_eq!(
+        hex::encode(master.private_key()),
+        "e8f32e723decf4051aefac8e2c93c9c5b214313817cdb01a1494b917c8436b35"
+    );
+
+    let vectors = [
+        (
+            "m/0'",
+            "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+        ),
+        (
+            "m/0'/1",
+            "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+        ),
+        (
+            "m/0h/1/2h",
+            "cbce0d719ecf7431d88e6a89fa1483e02e35092af60c042b1df2ff59fa424dca",
+        ),
+        (
+            "m/0'/1/2'/2",
+            "0f479245fb19a38a1954c5c7c0ebab2f9bdfd96a17563ef28a6a4b1a2a764ef4",
+        ),
+    ];
+
+    for (path, private_key) in vectors {
+        let child = master.derive_path(path).unwrap();
+        assert_eq!(hex::encode(child.private_key()), private_key);
+        assert_eq!(child.depth() as usize, path.split('/').count() - 1);
+        assert_eq!(
+            hex::encode(child.secp256k1_signing_key().unwrap().to_bytes()),
+            private_key
+        );
+    }
+
+    assert!(master.ed25519_signing_key().is_err());
+}
+
+#[test]
+pub fn slip10_ed25519_derivation() {
+    let master =
+        ExtendedPrivateKey::master(HdCurve::Ed25519, &hex::decode(SEED_HEX).unwrap()).unwrap();
+    assert_eq!(
+        hex::encode(master.private_key()),
+        "2b4be7f19ee27bbf30c667b642d5f4aa69fd169872f8fc3059c08ebae2eb19e7"
+    );
+
+    let child = master.derive_path("m/0'/1'/2'").unwrap();
+    assert_eq!(
+        hex::encode(child.ed25519_signing_key().unwrap().to_bytes()),
+        "92a5b23c0b8a99e37d07df3fb9966917f5d06e02ddbd909c7e184371463e9fc9"
+    );
+    assert!(master.derive_path("m/0'/1").is_err());
+
+    let mnemonic = Mnemonic::from_entropy(&[0u8; 16]).unwrap();
+    let root = mnemonic.master_key(HdCurve::Ed25519, "").unwrap();
+    let authentication = root.derive_path("m/44'/0'/0'/0'").unwrap();
+    let assertion = root.derive_path("m/44'/0'/0'/1'").unwrap();
+    assert_ne!(authentication.private_key(), assertion.private_key());
+    assert_eq!(
+        authentication.private_key(),
+        root.derive_child(0x8000002c)
+            .unwrap()
+            .derive_path("m/0'/0'/0'")
+            .unwrap()
+            .private_key()
+    );
+}
+
+#[test]
+pub fn derivation_path_parsing() {
+    assert_eq!(parse_derivation_path("m").unwrap(), Vec::<u32>::new());
+    assert_eq!(
+        parse_derivation_path("m/44'/60h/0").unwrap(),
+        vec![0x8000002c, 0x8000003c, 0]
+    );
+    assert!(parse_derivation_path("m/2147483648").is_err());
+    assert!(parse_derivation_path("44'/0'").is_err());
+    assert!(parse_derivation_path("m//1").is_err());
+}
@@ -1,4 +1,12 @@
-use did_crypto::{algorithms::Algorithm, crypto::hmac::HMACKey, signer::sign, verifier::verify};
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{
+        constant_time::{constant_time_eq, constant_time_eq_str},
+        hmac::HMACKey,
+    },
+    signer::sign,
+    verifier::verify,
+};
 
 const PASS_KEY: &'static str = "password for testing purposes.";
 
@@ -41,3 +49,37 @@ pub fn hmac512_signing_and_verifying() {
 
     assert!(verify(String::from(HMAC512_CONTENT), sig, key, Algorithm::HS512).unwrap());
 }
+
+#[test]
+pub fn hmac_rejects_tampered_and_truncated_tags() {
+    let key = HMACKey::new(String::from(PASS_KEY));
+    let sig = sign(String::from(HMAC256_CONTENT), key.clone(), Algorithm::HS256).unwrap();
+    let mut tag = base64_url::decode(&sig).unwrap();
+
+    assert!(!verify(
+        String::from(HMAC256_CONTENT),
+        base64_url::encode(&tag[..16]),
+        key.clone(),
+        Algorithm::HS256
+    )
+    .unwrap());
+
+    tag[0] ^= 1;
+    assert!(!verify(
+        String::from(HMAC256_CONTENT),
+        base64_url::encode(&tag),
+        key,
+        Algorithm::HS256
+    )
+    .unwrap());
+}
+
+#[test]
+pub fn constant_time_comparison() {
+    assert!(constant_time_eq(b"secret", b"secret"));
+    assert!(!constant_time_eq(b"secret", b"secreT"));
+    assert!(!constant_time_eq(b"secret", b"secre"));
+    assert!(constant_time_eq(b"", b""));
+    assert!(constant_time_eq_str("tag", "tag"));
+    assert!(!constant_time_eq_str("tag", "tags"));
+}
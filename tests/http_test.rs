@@ -0,0 +1,175 @@
+#![cfg(feature = "http")]
+
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use chrono::Utc;
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::AnySigningKey,
+    errors::Error,
+    http::{parse_max_age, HttpFetcher, HttpResponse, JwksCache},
+    jwk::{Jwk, Jwks},
+    jwt::{Header, Payload, JWT},
+    keystore::VerifyingKeyResolver,
+};
+use serde_json::json;
+
+const URL: &str = "https://issuer.example/.well-known/jwks.json";
+
+struct MockFetcher {
+    response: Mutex<Option<HttpResponse>>,
+    calls: AtomicUsize,
+}
+
+impl MockFetcher {
+    fn new(jwks: &Jwks, max_age: Option<Duration>) -> Self {
+        MockFetcher {
+            response: Mutex::new(Some(response(jwks, max_age))),
+            calls: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl HttpFetcher for MockFetcher {
+    fn get(&self, url: &str) -> Result<HttpResponse, Error> {
+        assert_eq!(url, URL);
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        match self.response.lock().unwrap().clone() {
+            Some(val) => Ok(val),
+            None => Err(Error::HTTP_REQUEST_FAILED),
+        }
+    }
+}
+
+fn response(jwks: &Jwks, max_age: Option<Duration>) -> HttpResponse {
+    HttpResponse {
+        body: jwks.to_json().unwrap().into_bytes(),
+        max_age,
+    }
+}
+
+fn jwk(key: &AnySigningKey, kid: &str) -> Jwk {
+    Jwk {
+        kid: Some(String::from(kid)),
+        ..key.verifying_key().to_jwk().unwrap()
+    }
+}
+
+fn token(key: &AnySigningKey, kid: &str) -> JWT {
+    let mut jwt = JWT {
+        header: Header::new(String::from(kid), Algorithm::ES256),
+        payload: Payload(json!({ "exp": Utc::now().timestamp() + 10 })),
+        signature: None,
+    };
+    jwt.sign(key).unwrap();
+    jwt
+}
+
+#[test]
+pub fn http_parse_max_age() {
+    assert_eq!(
+        parse_max_age("public, max-age=600"),
+        Some(Duration::from_secs(600))
+    );
+    assert_eq!(parse_max_age("no-store"), Some(Duration::ZERO));
+    assert_eq!(parse_max_age("public"), None);
+}
+
+#[test]
+pub fn jwks_cache_honors_max_age() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let jwks = Jwks {
+        keys: vec![jwk(&key, "key-1")],
+    };
+
+    let cache = JwksCache::with_fetcher(URL, MockFetcher::new(&jwks, None));
+    assert!(cache.validate(&token(&key, "key-1")).unwrap());
+    assert!(cache.validate(&token(&key, "key-1")).unwrap());
+    assert!(cache.resolve("key-1").is_ok());
+    assert_eq!(cache.fetcher().calls.load(Ordering::SeqCst), 1);
+
+    let cache = JwksCache::with_fetcher(URL, MockFetcher::new(&jwks, Some(Duration::ZERO)))
+        .min_refresh_interval(Duration::ZERO);
+    assert!(cache.validate(&token(&key, "key-1")).unwrap());
+    assert!(cache.validate(&token(&key, "key-1")).unwrap());
+    assert_eq!(cache.url(), URL);
+    assert_eq!(cache.fetcher().calls.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+pub fn jwks_cache_refreshes_on_unknown_kid() {
+    let first = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let second = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let fetcher = MockFetcher::new(
+        &Jwks {
+            keys: vec![jwk(&first, "key-1")],
+        },
+        None,
+    );
+    let cache = JwksCache::with_fetcher(URL, fetcher).min_refresh_interval(Duration::ZERO);
+    assert!(cache.validate(&token(&first, "key-1")).unwrap());
+
+    assert_eq!(
+        cache.validate(&token(&second, "key-2")).unwrap_err(),
+        Error::JWKS_KEY_NOT_FOUND
+    );
+}
+
+#[test]
+pub fn jwks_cache_rotation_and_rate_limit() {
+    let first = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let second = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let rotated = Jwks {
+        keys: vec![jwk(&first, "key-1"), jwk(&second, "key-2")],
+    };
+
+    let fetcher = MockFetcher::new(
+        &Jwks {
+            keys: vec![jwk(&first, "key-1")],
+        },
+        None,
+    );
+    let cache = JwksCache::with_fetcher(URL, fetcher).min_refresh_interval(Duration::ZERO);
+    assert!(cache.validate(&token(&first, "key-1")).unwrap());
+    *cache.fetcher().response.lock().unwrap() = Some(response(&rotated, None));
+    assert!(cache.validate(&token(&second, "key-2")).unwrap());
+    assert!(cache.validate(&token(&second, "key-2")).unwrap());
+    assert_eq!(cache.fetcher().calls.load(Ordering::SeqCst), 2);
+
+    let fetcher = MockFetcher::new(
+        &Jwks {
+            keys: vec![jwk(&first, "key-1")],
+        },
+        Some(Duration::ZERO),
+    );
+    let cache = JwksCache::with_fetcher(URL, fetcher).min_refresh_interval(Duration::from_secs(60));
+    assert!(cache.validate(&token(&first, "key-1")).unwrap());
+    *cache.fetcher().response.lock().unwrap() = Some(response(&rotated, None));
+    for _ in 0..3 {
+        assert!(cache.validate(&token(&second, "key-3")).is_err());
+    }
+    assert_eq!(cache.fetcher().calls.load(Ordering::SeqCst), 1);
+}
+
+#[test]
+pub fn jwks_cache_serves_stale_keys_when_fetch_fails() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let fetcher = MockFetcher::new(
+        &Jwks {
+            keys: vec![jwk(&key, "key-1")],
+        },
+        Some(Duration::ZERO),
+    );
+    let cache = JwksCache::with_fetcher(URL, fetcher).min_refresh_interval(Duration::ZERO);
+    assert!(cache.validate(&token(&key, "key-1")).unwrap());
+
+    *cache.fetcher().response.lock().unwrap() = None;
+    assert!(cache.validate(&token(&key, "key-1")).unwrap());
+    assert_eq!(cache.fetcher().calls.load(Ordering::SeqCst), 2);
+}
@@ -0,0 +1,158 @@
+use chrono::Utc;
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::AnySigningKey,
+    errors::Error,
+    ion::{commitment, reveal_value, IonDid, IonDocumentBuilder, IonResolver},
+    jwt::{Header, Payload, JWT},
+    keystore::KeyPurpose,
+    resolver::{verify_with_did, DidResolver, DidVerificationOptions, AUTHENTICATION},
+    verification_method::VerificationMethodType,
+};
+use serde_json::{json, Value};
+
+fn create() -> (AnySigningKey, IonDid) {
+    let signing = AnySigningKey::generate(Algorithm::ES256K).unwrap();
+    let update = AnySigningKey::generate(Algorithm::ES256K).unwrap();
+    let recovery = AnySigningKey::generate(Algorithm::ES256K).unwrap();
+    let document = IonDocumentBuilder::new()
+        .method_type(VerificationMethodType::EcdsaSecp256k1VerificationKey2019)
+        .key(
+            "key-1",
+            signing.verifying_key(),
+            &[KeyPurpose::Authentication, KeyPurpose::AssertionMethod],
+        )
+        .service("domain-1", "LinkedDomains", "https://example.com");
+    let did = IonDid::create(
+        &document,
+        &update.verifying_key(),
+        &recovery.verifying_key(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        did.update_commitment(),
+        Some(commitment(&update.verifying_key()).unwrap().as_str())
+    );
+    assert_eq!(
+        did.recovery_commitment(),
+        Some(commitment(&recovery.verifying_key()).unwrap().as_str())
+    );
+    (signing, did)
+}
+
+#[test]
+pub fn ion_long_form_round_trip() {
+    let (signing, did) = create();
+    assert_eq!(did.suffix.len(), 46);
+    assert!(did.suffix.starts_with("Ei"));
+    assert_eq!(did.short_form(), format!("did:ion:{}", did.suffix));
+
+    let long_form = did.long_form();
+    assert!(long_form.starts_with(&format!("{}:", did.short_form())));
+    assert_eq!(IonDid::parse(&long_form).unwrap(), did);
+
+    let testnet = did.clone().with_network("test");
+    assert!(testnet.long_form().starts_with("did:ion:test:Ei"));
+    assert_eq!(IonDid::parse(&testnet.long_form()).unwrap(), testnet);
+
+    let document = IonResolver.resolve(&long_form).unwrap();
+    assert_eq!(document["id"], long_form.as_str());
+    assert_eq!(document["verificationMethod"][0]["id"], "#key-1");
+    assert_eq!(
+        document["verificationMethod"][0]["type"],
+        "EcdsaSecp256k1VerificationKey2019"
+    );
+    assert_eq!(
+        document["verificationMethod"][0]["publicKeyJwk"]["crv"],
+        "secp256k1"
+    );
+    assert!(document["verificationMethod"][0]["publicKeyJwk"]
+        .get("alg")
+        .is_none());
+    assert_eq!(document[AUTHENTICATION], json!(["#key-1"]));
+    assert_eq!(document["assertionMethod"], json!(["#key-1"]));
+    assert_eq!(document["service"][0]["id"], "#domain-1");
+    assert_eq!(
+        document["service"][0]["serviceEndpoint"],
+        "https://example.com"
+    );
+
+    assert_ne!(
+        reveal_value(&signing.verifying_key()).unwrap(),
+        commitment(&signing.verifying_key()).unwrap()
+    );
+}
+
+#[test]
+pub fn ion_verify_with_long_form_did() {
+    let (signing, did) = create();
+    let long_form = did.long_form();
+    let mut jwt = JWT {
+        header: Header::new(format!("{}#key-1", long_form), Algorithm::ES256K),
+        payload: Payload(json!({"iss": long_form, "exp": Utc::now().timestamp() + 60})),
+        signature: None,
+    };
+    jwt.sign(&signing).unwrap();
+    let token = jwt.to_token().unwrap();
+
+    assert!(verify_with_did(
+        &token,
+        &long_form,
+        &IonResolver,
+        &DidVerificationOptions::new()
+    )
+    .unwrap());
+}
+
+#[test]
+pub fn ion_rejects_invalid_identifiers() {
+    let (_, did) = create();
+    let long_form = did.long_form();
+    let (_, state) = long_form.rsplit_once(':').unwrap();
+
+    let mut tampered = did.clone();
+    tampered.delta["updateCommitment"] = Value::from("EiAtampered");
+    assert_eq!(
+        IonDid::parse(&tampered.long_form()).err(),
+        Some(Error::ION_INVALID_STATE)
+    );
+
+    let other = create().1;
+    assert_eq!(
+        IonDid::parse(&format!("did:ion:{}:{}", other.suffix, state)).err(),
+        Some(Error::ION_INVALID_STATE)
+    );
+
+    for invalid in [
+        did.short_form(),
+        format!("did:web:{}:{}", did.suffix, state),
+        format!("{}:extra:segment", long_form),
+        format!("did:ion:{}:not-json", did.suffix),
+    ] {
+        let error = IonDid::parse(&invalid).err().unwrap();
+        assert_eq!(error, Error::ION_INVALID_DID);
+        assert!(error.is_malformed_input());
+    }
+
+    let signing = AnySigningKey::generate(Algorithm::ES256K).unwrap();
+    for document in [
+        IonDocumentBuilder::new().key("key#1", signing.verifying_key(), &[]),
+        IonDocumentBuilder::new()
+            .key("key-1", signing.verifying_key(), &[])
+            .service("key-1", "LinkedDomains", "https://example.com"),
+        IonDocumentBuilder::new()
+            .method_type(VerificationMethodType::Ed25519VerificationKey2020)
+            .key("key-1", signing.verifying_key(), &[]),
+    ] {
+        assert_eq!(
+            IonDid::create(
+                &document,
+                &signing.verifying_key(),
+                &signing.verifying_key()
+            )
+            .err(),
+            Some(Error::ION_INVALID_DOCUMENT)
+        );
+    }
+}
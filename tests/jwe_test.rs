@@ -0,0 +1,433 @@
+use std::io::{Cursor, Write};
+
+use aes_gcm::{
+    aead::{Aead, KeyInit, Payload},
+    Aes256Gcm, Nonce,
+};
+use did_crypto::{
+    crypto::x25519::X25519SecretKey,
+    errors::Error,
+    jwe::{
+        inflate, ContentEncryption, Jwe, KeyWrapping, DEFAULT_MAX_COMPRESSION_RATIO,
+        DEFAULT_MAX_DECOMPRESSED_SIZE, STREAM_CHUNK_SIZE,
+    },
+};
+use flate2::{write::DeflateEncoder, Compression};
+
+const TYP: &str = "application/didcomm-encrypted+json";
+const BOB_KID: &str = "did:example:bob#key-x25519-1";
+const ALICE_KID: &str = "did:example:alice#key-x25519-1";
+
+#[test]
+pub fn jwe_stream_round_trip() {
+    let bob = X25519SecretKey::generate();
+    let alice = X25519SecretKey::generate();
+
+    for len in [0, 1, STREAM_CHUNK_SIZE, STREAM_CHUNK_SIZE * 3 + 17] {
+        let plaintext: Vec<u8> = (0..len).map(|val| val as u8).collect();
+
+        let mut ciphertext = Vec::new();
+        let jwe = Jwe::encrypt_stream(
+            Cursor::new(&plaintext),
+            &mut ciphertext,
+            TYP,
+            ContentEncryption::A256CBCHS512,
+            &[(BOB_KID, bob.public_key())],
+            Some((ALICE_KID, &alice)),
+        )
+        .unwrap();
+        let chunks = len.div_ceil(STREAM_CHUNK_SIZE).max(1);
+        assert_eq!(ciphertext.len(), len + 16 * chunks);
+
+        let jwe = Jwe::from_json(&jwe.to_json().unwrap()).unwrap();
+        let mut decrypted = Vec::new();
+        let written = jwe
+            .decrypt_stream(
+                BOB_KID,
+                &bob,
+                Some(&alice.public_key()),
+                Cursor::new(&ciphertext),
+                &mut decrypted,
+            )
+            .unwrap();
+        assert_eq!(written, len as u64);
+        assert_eq!(decrypted, plaintext);
+    }
+}
+
+#[test]
+pub fn jwe_stream_rejects_tampering() {
+    let bob = X25519SecretKey::generate();
+    let plaintext = vec![42u8; STREAM_CHUNK_SIZE * 2 + 5];
+
+    let mut ciphertext = Vec::new();
+    let jwe = Jwe::encrypt_stream(
+        Cursor::new(&plaintext),
+        &mut ciphertext,
+        TYP,
+        ContentEncryption::A256GCM,
+        &[(BOB_KID, bob.public_key())],
+        None,
+    )
+    .unwrap();
+
+    let decrypt = |ciphertext: &[u8]| {
+        jwe.decrypt_stream(BOB_KID, &bob, None, Cursor::new(ciphertext), Vec::new())
+    };
+    assert!(decrypt(&ciphertext).is_ok());
+
+    let truncated = &ciphertext[..(STREAM_CHUNK_SIZE + 16) * 2];
+    assert_eq!(
+        decrypt(truncated).unwrap_err(),
+        Error::JWE_DECRYPTION_FAILED
+    );
+
+    let mut reordered = ciphertext.clone();
+    let (first, rest) = reordered.split_at_mut(STREAM_CHUNK_SIZE + 16);
+    first.swap_with_slice(&mut rest[..STREAM_CHUNK_SIZE + 16]);
+    assert_eq!(
+        decrypt(&reordered).unwrap_err(),
+        Error::JWE_DECRYPTION_FAILED
+    );
+
+    let mut flipped = ciphertext.clone();
+    flipped[10] ^= 1;
+    assert_eq!(decrypt(&flipped).unwrap_err(), Error::JWE_DECRYPTION_FAILED);
+
+    let other = X25519SecretKey::generate();
+    assert!(jwe
+        .decrypt_stream(BOB_KID, &other, None, Cursor::new(&ciphertext), Vec::new())
+        .is_err());
+}
+
+#[test]
+pub fn jwe_gcm_key_wrapping() {
+    let plaintext = b"The true sign of intelligence is not knowledge but imagination.";
+    for alg in [KeyWrapping::A128GCMKW, KeyWrapping::A256GCMKW] {
+        let kek: Vec<u8> = (0..alg.key_len()).map(|val| val as u8).collect();
+        for enc in [ContentEncryption::A256GCM, ContentEncryption::A256CBCHS512] {
+            let jwe = Jwe::encrypt_with_kek(plaintext, "JWT", enc, BOB_KID, alg, &kek).unwrap();
+            let header = jwe.protected_header().unwrap();
+            assert_eq!(header["alg"], alg.to_str());
+            assert_eq!(header["enc"], enc.to_str());
+            assert_eq!(header["kid"], BOB_KID);
+
+            let compact = jwe.to_compact().unwrap();
+            assert_eq!(compact.split('.').count(), 5);
+            let parsed = Jwe::from_compact(&compact).unwrap();
+            assert_eq!(parsed, jwe);
+            assert_eq!(parsed.decrypt_with_kek(BOB_KID, &kek).unwrap(), plaintext);
+
+            let mut other = kek.clone();
+            other[0] ^= 1;
+            assert_eq!(
+                parsed.decrypt_with_kek(BOB_KID, &other).err(),
+                Some(Error::JWE_DECRYPTION_FAILED)
+            );
+            assert_eq!(
+                parsed.decrypt_with_kek(ALICE_KID, &kek).err(),
+                Some(Error::JWE_RECIPIENT_NOT_FOUND)
+            );
+        }
+
+        assert_eq!(
+            Jwe::encrypt_with_kek(
+                plaintext,
+                "JWT",
+                ContentEncryption::A256GCM,
+                BOB_KID,
+                alg,
+                &[0u8; 24]
+            )
+            .err(),
+            Some(Error::JWE_INVALID_KEY_LENGTH)
+        );
+    }
+}
+
+#[test]
+pub fn jwe_gcm_key_wrapping_interop() {
+    let kek = [7u8; 32];
+    let plaintext = b"interop";
+    let compact = Jwe::encrypt_with_kek(
+        plaintext,
+        "JWT",
+        ContentEncryption::A256GCM,
+        BOB_KID,
+        KeyWrapping::A256GCMKW,
+        &kek,
+    )
+    .unwrap()
+    .to_compact()
+    .unwrap();
+    let parts: Vec<&str> = compact.split('.').collect();
+    let header: serde_json::Value =
+        serde_json::from_slice(&base64_url::decode(parts[0]).unwrap()).unwrap();
+
+    let mut encrypted_key = base64_url::decode(parts[1]).unwrap();
+    encrypted_key.extend(base64_url::decode(header["tag"].as_str().unwrap()).unwrap());
+    let cek = Aes256Gcm::new_from_slice(&kek)
+        .unwrap()
+        .decrypt(
+            Nonce::from_slice(&base64_url::decode(header["iv"].as_str().unwrap()).unwrap()),
+            encrypted_key.as_slice(),
+        )
+        .unwrap();
+    assert_eq!(cek.len(), 32);
+
+    let mut ciphertext = base64_url::decode(parts[3]).unwrap();
+    ciphertext.extend(base64_url::decode(parts[4]).unwrap());
+    let decrypted = Aes256Gcm::new_from_slice(&cek)
+        .unwrap()
+        .decrypt(
+            Nonce::from_slice(&base64_url::decode(parts[2]).unwrap()),
+            Payload {
+                msg: &ciphertext,
+                aad: parts[0].as_bytes(),
+            },
+        )
+        .unwrap();
+    assert_eq!(decrypted, plaintext);
+
+    let tampered = compact.replacen(
+        parts[0],
+        &base64_url::encode(
+            &serde_json::to_vec(&serde_json::json!({
+                "alg": "A256GCMKW", "enc": "A256GCM", "kid": BOB_KID,
+                "iv": header["iv"], "tag": header["tag"], "typ": "JOSE",
+            }))
+            .unwrap(),
+        ),
+        1,
+    );
+    assert_eq!(
+        Jwe::from_compact(&tampered)
+            .unwrap()
+            .decrypt_with_kek(BOB_KID, &kek)
+            .err(),
+        Some(Error::JWE_DECRYPTION_FAILED)
+    );
+    assert_eq!(Jwe::from_compact("a.b.c").err(), Some(Error::JWE_MALFORMED));
+}
+
+#[test]
+pub fn jwe_multiple_recipients() {
+    let plaintext = b"Hello to every agent of Bob";
+    let phone = X25519SecretKey::generate();
+    let laptop = X25519SecretKey::generate();
+    let alice = X25519SecretKey::generate();
+    let phone_kid = "did:example:bob#key-x25519-phone";
+    let laptop_kid = "did:example:bob#key-x25519-laptop";
+
+    let jwe = Jwe::encrypt(
+        plaintext,
+        TYP,
+        ContentEncryption::A256CBCHS512,
+        &[
+            (phone_kid, phone.public_key()),
+            (laptop_kid, laptop.public_key()),
+        ],
+        Some((ALICE_KID, &alice)),
+    )
+    .unwrap();
+    assert_eq!(jwe.recipient_kids(), vec![phone_kid, laptop_kid]);
+    assert_ne!(
+        jwe.recipients[0].encrypted_key,
+        jwe.recipients[1].encrypted_key
+    );
+    assert_eq!(jwe.to_compact().err(), Some(Error::JWE_MALFORMED));
+
+    let parsed = Jwe::from_json(&jwe.to_json().unwrap()).unwrap();
+    let sender = alice.public_key();
+    for (kid, key) in [(phone_kid, &phone), (laptop_kid, &laptop)] {
+        assert_eq!(parsed.decrypt(kid, key, Some(&sender)).unwrap(), plaintext);
+    }
+    assert_eq!(
+        parsed
+            .decrypt_any(&[(BOB_KID, &alice), (laptop_kid, &laptop)], Some(&sender))
+            .unwrap(),
+        (String::from(laptop_kid), plaintext.to_vec())
+    );
+    assert_eq!(
+        parsed
+            .decrypt_any(&[(BOB_KID, &phone)], Some(&sender))
+            .err(),
+        Some(Error::JWE_RECIPIENT_NOT_FOUND)
+    );
+
+    let mut swapped = parsed.clone();
+    swapped.recipients[0].header.kid = String::from(laptop_kid);
+    swapped.recipients[1].header.kid = String::from(phone_kid);
+    assert_eq!(
+        swapped.decrypt(phone_kid, &phone, Some(&sender)).err(),
+        Some(Error::JWE_DECRYPTION_FAILED)
+    );
+
+    assert_eq!(
+        Jwe::encrypt(
+            plaintext,
+            TYP,
+            ContentEncryption::A256GCM,
+            &[
+                (phone_kid, phone.public_key()),
+                (phone_kid, laptop.public_key())
+            ],
+            None,
+        )
+        .err(),
+        Some(Error::JWE_DUPLICATE_RECIPIENT)
+    );
+}
+
+#[test]
+pub fn jwe_multiple_kek_recipients() {
+    let plaintext = b"Shared with two symmetric keys";
+    let kek_128: Vec<u8> = (0..16).collect();
+    let kek_256: Vec<u8> = (0..32).map(|val| val * 3).collect();
+
+    let jwe = Jwe::encrypt_with_keks(
+        plaintext,
+        "JWT",
+        ContentEncryption::A256GCM,
+        &[
+            (BOB_KID, KeyWrapping::A128GCMKW, &kek_128),
+            (ALICE_KID, KeyWrapping::A256GCMKW, &kek_256),
+        ],
+    )
+    .unwrap();
+    let header = jwe.protected_header().unwrap();
+    assert_eq!(header["enc"], "A256GCM");
+    assert!(header.get("alg").is_none());
+    assert_eq!(jwe.recipients[0].header.alg.as_deref(), Some("A128GCMKW"));
+    assert_eq!(jwe.recipients[1].header.alg.as_deref(), Some("A256GCMKW"));
+    assert_ne!(jwe.recipients[0].header.iv, jwe.recipients[1].header.iv);
+
+    let json: serde_json::Value = serde_json::from_str(&jwe.to_json().unwrap()).unwrap();
+    assert_eq!(json["recipients"][1]["header"]["kid"], ALICE_KID);
+    assert!(json["recipients"][1]["header"]["tag"].is_string());
+
+    let parsed = Jwe::from_json(&jwe.to_json().unwrap()).unwrap();
+    assert_eq!(
+        parsed.decrypt_with_kek(BOB_KID, &kek_128).unwrap(),
+        plaintext
+    );
+    assert_eq!(
+        parsed.decrypt_with_kek(ALICE_KID, &kek_256).unwrap(),
+        plaintext
+    );
+    assert_eq!(
+        parsed.decrypt_with_kek(ALICE_KID, &kek_256[..16]).err(),
+        Some(Error::JWE_INVALID_KEY_LENGTH)
+    );
+
+    let mut tampered = parsed.clone();
+    tampered.recipients[0].header.tag = tampered.recipients[1].header.tag.clone();
+    assert_eq!(
+        tampered.decrypt_with_kek(BOB_KID, &kek_128).err(),
+        Some(Error::JWE_DECRYPTION_FAILED)
+    );
+
+    assert_eq!(
+        Jwe::encrypt_with_keks(plaintext, "JWT", ContentEncryption::A256GCM, &[]).err(),
+        Some(Error::JWE_RECIPIENT_NOT_FOUND)
+    );
+    assert_eq!(
+        Jwe::encrypt_with_keks(
+            plaintext,
+            "JWT",
+            ContentEncryption::A256GCM,
+            &[(BOB_KID, KeyWrapping::A256GCMKW, &kek_128)],
+        )
+        .err(),
+        Some(Error::JWE_INVALID_KEY_LENGTH)
+    );
+}
+
+#[test]
+pub fn jwe_deflate_compression() {
+    let plaintext = "{\"type\":\"https://didcomm.org/basicmessage/2.0/message\"}".repeat(50);
+    let bob = X25519SecretKey::generate();
+    let alice = X25519SecretKey::generate();
+
+    let jwe = Jwe::encrypt_compressed(
+        plaintext.as_bytes(),
+        TYP,
+        ContentEncryption::A256CBCHS512,
+        &[(BOB_KID, bob.public_key())],
+        Some((ALICE_KID, &alice)),
+    )
+    .unwrap();
+    assert_eq!(jwe.protected_header().unwrap()["zip"], "DEF");
+    assert!(base64_url::decode(&jwe.ciphertext).unwrap().len() < plaintext.len() / 4);
+    assert_eq!(
+        jwe.decrypt(BOB_KID, &bob, Some(&alice.public_key()))
+            .unwrap(),
+        plaintext.as_bytes()
+    );
+
+    let kek: Vec<u8> = (0..32).collect();
+    let compact = Jwe::encrypt_with_kek_compressed(
+        plaintext.as_bytes(),
+        "JWT",
+        ContentEncryption::A256GCM,
+        BOB_KID,
+        KeyWrapping::A256GCMKW,
+        &kek,
+    )
+    .unwrap()
+    .to_compact()
+    .unwrap();
+    assert_eq!(
+        Jwe::from_compact(&compact)
+            .unwrap()
+            .decrypt_with_kek(BOB_KID, &kek)
+            .unwrap(),
+        plaintext.as_bytes()
+    );
+
+    let bomb = vec![0u8; DEFAULT_MAX_DECOMPRESSED_SIZE + 1];
+    let jwe = Jwe::encrypt_with_kek_compressed(
+        &bomb,
+        "JWT",
+        ContentEncryption::A256GCM,
+        BOB_KID,
+        KeyWrapping::A256GCMKW,
+        &kek,
+    )
+    .unwrap();
+    assert_eq!(
+        jwe.decrypt_with_kek(BOB_KID, &kek).err(),
+        Some(Error::JWE_DECOMPRESSION_LIMIT_EXCEEDED)
+    );
+}
+
+#[test]
+pub fn jwe_inflate_limits() {
+    let mut encoder = DeflateEncoder::new(Vec::new(), Compression::best());
+    encoder.write_all(&[b'a'; 20_000]).unwrap();
+    let compressed = encoder.finish().unwrap();
+
+    assert_eq!(
+        inflate(&compressed, DEFAULT_MAX_DECOMPRESSED_SIZE, usize::MAX)
+            .unwrap()
+            .len(),
+        20_000
+    );
+    assert_eq!(
+        inflate(
+            &compressed,
+            DEFAULT_MAX_DECOMPRESSED_SIZE,
+            DEFAULT_MAX_COMPRESSION_RATIO
+        )
+        .err(),
+        Some(Error::JWE_DECOMPRESSION_LIMIT_EXCEEDED)
+    );
+    assert_eq!(
+        inflate(&compressed, 19_999, usize::MAX).err(),
+        Some(Error::JWE_DECOMPRESSION_LIMIT_EXCEEDED)
+    );
+    assert_eq!(
+        inflate(&[0xff; 8], DEFAULT_MAX_DECOMPRESSED_SIZE, usize::MAX).err(),
+        Some(Error::JWE_MALFORMED)
+    );
+}
@@ -0,0 +1,137 @@
+#![cfg(feature = "eddsa")]
+
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    errors::Error,
+    jws::{GeneralJws, ThresholdPolicy},
+    keystore::{InMemoryKeyStore, KeyStore},
+    resolver::{DidKeyResolver, DidResolver, ASSERTION_METHOD},
+};
+use serde_json::{json, Value};
+
+const PAYLOAD: &[u8] = b"{\"action\":\"rotate\"}";
+
+struct ControllerResolver(Vec<(String, AnyVerifyingKey)>);
+
+impl DidResolver for ControllerResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        let key = match self.0.iter().find(|(val, _)| val == did) {
+            Some((_, key)) => key,
+            None => return Err(Error::DID_RESOLUTION_ERROR),
+        };
+
+        Ok(json!({
+            "id": did,
+            "verificationMethod": [{
+                "id": "#key-1",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": key.to_jwk().unwrap(),
+            }],
+            ASSERTION_METHOD: ["#key-1"],
+        }))
+    }
+}
+
+fn keys() -> Vec<(String, AnySigningKey)> {
+    [
+        ("did:example:alice", Algorithm::EdDSA),
+        ("did:example:bob", Algorithm::ES256),
+        ("did:example:carol", Algorithm::EdDSA),
+    ]
+    .into_iter()
+    .map(|(did, alg)| (String::from(did), AnySigningKey::generate(alg).unwrap()))
+    .collect()
+}
+
+#[test]
+pub fn jws_general_serialization_round_trip() {
+    let store: InMemoryKeyStore = InMemoryKeyStore::new();
+    store
+        .add("alice", AnySigningKey::generate(Algorithm::EdDSA).unwrap())
+        .unwrap();
+    store
+        .add("bob", AnySigningKey::generate(Algorithm::ES256).unwrap())
+        .unwrap();
+
+    let mut jws = GeneralJws::new(PAYLOAD);
+    jws.sign_with_store(&store, "alice").unwrap();
+    jws.sign_with_store(&store, "bob").unwrap();
+
+    let parsed = GeneralJws::from_json(&jws.to_json().unwrap()).unwrap();
+    assert_eq!(parsed, jws);
+    assert_eq!(parsed.payload().unwrap(), PAYLOAD);
+    assert_eq!(parsed.kids(), vec!["alice", "bob"]);
+    assert_eq!(parsed.signatures[1].algorithm().unwrap(), Algorithm::ES256);
+
+    let public: InMemoryKeyStore<AnyVerifyingKey> = InMemoryKeyStore::new();
+    public
+        .add("alice", store.get("alice").unwrap().verifying_key())
+        .unwrap();
+    assert!(parsed
+        .verify_signature(&parsed.signatures[0], &public)
+        .unwrap());
+
+    assert_eq!(
+        GeneralJws::from_json(r#"{"payload":"e30","signatures":[]}"#).unwrap_err(),
+        Error::JWS_MALFORMED
+    );
+}
+
+#[test]
+pub fn jws_threshold_verification() {
+    let keys = keys();
+    let resolver = DidKeyResolver::new(
+        ControllerResolver(
+            keys.iter()
+                .map(|(did, key)| (did.clone(), key.verifying_key()))
+                .collect(),
+        ),
+        ASSERTION_METHOD,
+    );
+    let signers = ["did:example:alice", "did:example:bob", "did:example:carol"];
+    let policy = ThresholdPolicy::new(2, &signers);
+
+    let mut jws = GeneralJws::new(PAYLOAD);
+    let (did, key) = &keys[0];
+    jws.sign(&format!("{}#key-1", did), key.algorithm(), key)
+        .unwrap();
+    assert_eq!(
+        jws.verify_threshold(&resolver, &policy).unwrap_err(),
+        Error::JWS_THRESHOLD_NOT_MET
+    );
+
+    jws.sign(&format!("{}#key-1", did), key.algorithm(), key)
+        .unwrap();
+    assert_eq!(
+        jws.verify_threshold(&resolver, &policy).unwrap_err(),
+        Error::JWS_THRESHOLD_NOT_MET
+    );
+
+    let outsider = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    jws.sign("did:example:mallory#key-1", Algorithm::EdDSA, &outsider)
+        .unwrap();
+    jws.sign("did:example:carol#key-1", Algorithm::EdDSA, &outsider)
+        .unwrap();
+    assert_eq!(
+        jws.verify_threshold(&resolver, &policy).unwrap_err(),
+        Error::JWS_THRESHOLD_NOT_MET
+    );
+
+    let (did, key) = &keys[1];
+    jws.sign(&format!("{}#key-1", did), key.algorithm(), key)
+        .unwrap();
+    assert_eq!(
+        jws.verify_threshold(&resolver, &policy).unwrap(),
+        vec!["did:example:alice", "did:example:bob"]
+    );
+
+    assert_eq!(policy.threshold(), 2);
+    assert!(jws
+        .verify_threshold(&resolver, &ThresholdPolicy::new(3, &signers))
+        .is_err());
+    assert!(jws
+        .verify_threshold(&resolver, &ThresholdPolicy::new(0, &[]))
+        .is_err());
+}
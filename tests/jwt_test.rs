@@ -1,12 +1,48 @@
-use std::str::FromStr;
+use std::{
+    future::Future,
+    pin::pin,
+    str::FromStr,
+    task::{Context, Poll, Waker},
+};
 
+use base64::{engine::general_purpose::STANDARD, Engine};
 use chrono::Utc;
 use did_crypto::{
     algorithms::Algorithm,
-    crypto::ecdsa::_512::{P512SigningKey, P512VerifyingKey},
-    jwt::{Header, Payload, JWT},
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        ecdsa::_512::{P512SigningKey, P512VerifyingKey},
+        hmac::HMACKey,
+        x25519::X25519SecretKey,
+        AsyncSignFromKey, AsyncVerifyFromKey, SignFromKey,
+    },
+    dpop::{DpopClaims, DpopProof, DpopValidation},
+    errors::Error,
+    jwe::{ContentEncryption, Jwe, KeyWrapping},
+    jwk::{Jwk, Jwks},
+    jwt::{decode_nested, decode_unverified, Confirmation, Header, Payload, RefreshOptions, JWT},
+    keystore::{InMemoryKeyStore, KeyLookup, KeyRing, KeyStore},
+    resolver::{DidKeyResolver, DidResolver, ASSERTION_METHOD},
+    signer::sign,
 };
-use serde_json::Value;
+use serde_json::{json, Value};
+
+struct IssuerResolver(AnyVerifyingKey);
+
+impl DidResolver for IssuerResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        Ok(json!({
+            "id": did,
+            "verificationMethod": [{
+                "id": "#key-1",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": self.0.to_jwk().unwrap(),
+            }],
+            ASSERTION_METHOD: ["#key-1"],
+        }))
+    }
+}
 
 const PUBLIC_KEY: &'static str = "-----BEGIN PUBLIC KEY-----
 MIGbMBAGByqGSM49AgEGBSuBBAAjA4GGAAQBgc4HZz+/fBbC7lmEww0AO3NK9wVZ
@@ -126,3 +162,586 @@ pub fn test_jwt_validate_expire() {
 
     assert!(!validated);
 }
+
+struct RemoteSigner(P512SigningKey);
+
+impl AsyncSignFromKey for RemoteSigner {
+    async fn sign_bytes_async(&self, content: &[u8], alg: Algorithm) -> Result<String, Error> {
+        std::future::ready(()).await;
+        self.0.sign_bytes(content, alg)
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(val) = future.as_mut().poll(&mut context) {
+            return val;
+        }
+    }
+}
+
+#[test]
+pub fn test_jwt_async_signing() {
+    let payload_content: Value = Value::from_str(
+        format!(
+            "{{\"sub\": \"1234567890\", \"exp\": {}}}",
+            Utc::now().timestamp() + 10
+        )
+        .as_str(),
+    )
+    .unwrap();
+
+    let mut jwt = JWT {
+        header: Header::new(String::from("id:129877"), Algorithm::ES512),
+        payload: Payload(payload_content),
+        signature: None,
+    };
+
+    block_on(jwt.sign_async(&RemoteSigner(
+        P512SigningKey::from_pem(PRIVATE_KEY).unwrap(),
+    )))
+    .unwrap();
+    assert!(jwt
+        .validate(P512VerifyingKey::from_pem(PUBLIC_KEY).unwrap())
+        .unwrap());
+
+    block_on(jwt.sign_async(&P512SigningKey::from_pem(PRIVATE_KEY).unwrap())).unwrap();
+    let verifying_key = P512VerifyingKey::from_pem(PUBLIC_KEY).unwrap();
+    let token = jwt.to_token().unwrap();
+    let (content, signature) = token.rsplit_once('.').unwrap();
+    assert!(block_on(verifying_key.verify_bytes_async(
+        content.as_bytes(),
+        signature,
+        Algorithm::ES512
+    ))
+    .unwrap());
+}
+
+#[test]
+pub fn test_jwt_rejects_alg_none() {
+    let payload = STANDARD.encode("{\"sub\":\"1234567890\",\"exp\":4102444800}");
+    for alg in ["none", "None", "NONE"] {
+        let header = STANDARD.encode(format!("{{\"typ\":\"JWT\",\"alg\":\"{}\"}}", alg));
+        for token in [
+            format!("{}.{}.", header, payload),
+            format!("{}.{}.c2lnbmF0dXJl", header, payload),
+        ] {
+            assert_eq!(
+                JWT::from_token(&token).err().unwrap().to_string(),
+                Error::JWT_ALG_NONE.to_string()
+            );
+        }
+    }
+
+    let header = STANDARD.encode("{\"typ\":\"JWT\",\"alg\":\"ES512\"}");
+    assert_eq!(
+        JWT::from_token(&format!("{}.{}.", header, payload))
+            .err()
+            .unwrap()
+            .to_string(),
+        Error::JWT_NO_SIGNATURE_FOUND.to_string()
+    );
+    assert_eq!(
+        JWT::from_token(&format!("{}.{}", header, payload))
+            .err()
+            .unwrap()
+            .to_string(),
+        Error::JWT_MALFORMED.to_string()
+    );
+}
+
+#[test]
+pub fn test_jwt_rejects_key_algorithm_confusion() {
+    let payload_content =
+        Value::from_str(format!("{{\"exp\": {}}}", Utc::now().timestamp() + 10).as_str()).unwrap();
+
+    let mut jwt = JWT {
+        header: Header::new(String::new(), Algorithm::HS256),
+        payload: Payload(payload_content.clone()),
+        signature: None,
+    };
+    jwt.sign(HMACKey::new(String::from(PUBLIC_KEY))).unwrap();
+    assert_eq!(
+        jwt.validate(P512VerifyingKey::from_pem(PUBLIC_KEY).unwrap())
+            .err()
+            .unwrap()
+            .to_string(),
+        Error::JWT_ALGORITHM_KEY_MISMATCH.to_string()
+    );
+    assert!(jwt
+        .validate(HMACKey::new(String::from(PUBLIC_KEY)))
+        .unwrap());
+
+    let mut jwt = JWT {
+        header: Header::new(String::new(), Algorithm::ES512),
+        payload: Payload(payload_content),
+        signature: None,
+    };
+    jwt.sign(P512SigningKey::from_pem(PRIVATE_KEY).unwrap())
+        .unwrap();
+    assert_eq!(
+        jwt.validate(HMACKey::new(String::from(PUBLIC_KEY)))
+            .err()
+            .unwrap()
+            .to_string(),
+        Error::JWT_ALGORITHM_KEY_MISMATCH.to_string()
+    );
+
+    jwt.header.alg = Algorithm::ES256;
+    assert_eq!(
+        jwt.validate(P512VerifyingKey::from_pem(PUBLIC_KEY).unwrap())
+            .err()
+            .unwrap()
+            .to_string(),
+        Error::JWT_ALGORITHM_KEY_MISMATCH.to_string()
+    );
+}
+
+#[test]
+pub fn test_jwt_validate_with_jwks() {
+    let jwk = |key: &AnySigningKey, kid: &str| Jwk {
+        kid: Some(String::from(kid)),
+        key_use: Some(String::from("sig")),
+        ..key.verifying_key().to_jwk().unwrap()
+    };
+    let first = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let second = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let other = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let mut encryption = jwk(&second, "key-enc");
+    encryption.key_use = Some(String::from("enc"));
+
+    let jwks = Jwks::from_json(
+        &Jwks {
+            keys: vec![
+                jwk(&other, "key-0"),
+                jwk(&first, "key-1"),
+                jwk(&second, "key-2"),
+                encryption,
+            ],
+        }
+        .to_json()
+        .unwrap(),
+    )
+    .unwrap();
+    assert_eq!(jwks.select(None, None, Some("sig")).len(), 3);
+    assert_eq!(jwks.select(None, Some(Algorithm::ES256), None).len(), 3);
+    assert_eq!(jwks.verifying_keys(None, Algorithm::ES256).len(), 2);
+
+    let sign = |key: &AnySigningKey, kid: &str| {
+        let mut jwt = JWT {
+            header: Header::new(String::from(kid), Algorithm::ES256),
+            payload: Payload(serde_json::json!({ "exp": Utc::now().timestamp() + 10 })),
+            signature: None,
+        };
+        jwt.sign(key).unwrap();
+        jwt
+    };
+
+    assert!(sign(&second, "key-2").validate_with_jwks(&jwks).unwrap());
+    assert!(sign(&second, "").validate_with_jwks(&jwks).unwrap());
+    assert!(sign(&second, "key-2").validate_with_store(&jwks).unwrap());
+    assert!(!sign(&second, "key-1").validate_with_jwks(&jwks).unwrap());
+    assert_eq!(
+        sign(&second, "key-enc")
+            .validate_with_jwks(&jwks)
+            .unwrap_err(),
+        Error::JWKS_KEY_NOT_FOUND
+    );
+    assert_eq!(
+        sign(&second, "key-0")
+            .validate_with_jwks(&jwks)
+            .unwrap_err(),
+        Error::JWKS_KEY_NOT_FOUND
+    );
+}
+
+#[test]
+pub fn jwt_cnf_binds_holder_key() {
+    let issuer = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let holder = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let other = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+
+    for confirmation in [
+        Confirmation::from_key(&holder.verifying_key()).unwrap(),
+        Confirmation::thumbprint(&holder.verifying_key()).unwrap(),
+    ] {
+        let payload = Payload(serde_json::json!({ "exp": Utc::now().timestamp() + 60 }))
+            .with_confirmation(&confirmation)
+            .unwrap();
+        let mut jwt = JWT {
+            header: Header::new(String::new(), Algorithm::ES256),
+            payload,
+            signature: None,
+        };
+        jwt.sign(&issuer).unwrap();
+
+        let jwt = JWT::from_token(&jwt.to_token().unwrap()).unwrap();
+        let cnf = jwt.confirmation().unwrap();
+        assert_eq!(cnf, confirmation);
+        assert_eq!(
+            cnf.jkt().unwrap(),
+            holder.verifying_key().fingerprint().unwrap()
+        );
+        assert!(cnf.matches(&holder.verifying_key()).unwrap());
+        assert!(!cnf.matches(&other.verifying_key()).unwrap());
+
+        let signature = sign("challenge", &holder, Algorithm::EdDSA).unwrap();
+        assert!(cnf
+            .verify_possession(
+                "challenge",
+                &signature,
+                &holder.verifying_key(),
+                Algorithm::EdDSA
+            )
+            .unwrap());
+        let signature = sign("challenge", &other, Algorithm::EdDSA).unwrap();
+        assert!(!cnf
+            .verify_possession(
+                "challenge",
+                &signature,
+                &other.verifying_key(),
+                Algorithm::EdDSA
+            )
+            .unwrap());
+
+        let validation = DpopValidation::new("GET", "https://resource.example.org/");
+        let proof = DpopProof::sign(
+            DpopClaims::new("GET", "https://resource.example.org/"),
+            &holder,
+        )
+        .unwrap();
+        assert!(cnf.verify_dpop(&proof, &validation).unwrap());
+        let proof = DpopProof::sign(
+            DpopClaims::new("GET", "https://resource.example.org/"),
+            &other,
+        )
+        .unwrap();
+        assert!(!cnf.verify_dpop(&proof, &validation).unwrap());
+    }
+
+    assert_eq!(
+        Confirmation::from_key(&holder.verifying_key())
+            .unwrap()
+            .verifying_key()
+            .unwrap(),
+        holder.verifying_key()
+    );
+    assert_eq!(
+        Payload(serde_json::json!({ "cnf": { "kid": "key-1" } }))
+            .confirmation()
+            .err(),
+        Some(Error::JWT_CNF_INVALID)
+    );
+    let private_jwk = serde_json::to_value(holder.to_jwk().unwrap()).unwrap();
+    assert_eq!(
+        Payload(serde_json::json!({ "cnf": { "jwk": private_jwk } }))
+            .confirmation()
+            .err(),
+        Some(Error::JWT_CNF_INVALID)
+    );
+    let jwt = JWT {
+        header: Header::new(String::new(), Algorithm::ES256),
+        payload: Payload(serde_json::json!({})),
+        signature: None,
+    };
+    assert_eq!(jwt.confirmation().err(), Some(Error::JWT_CNF_MISSING));
+}
+
+#[test]
+pub fn jwt_decode_unverified() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let mut jwt = JWT {
+        header: Header::new(String::from("did:example:tenant-a#key-1"), Algorithm::ES256),
+        payload: Payload(
+            Value::from_str(
+                r#"{"iss": "did:example:tenant-a", "aud": ["api", "admin"], "exp": 1}"#,
+            )
+            .unwrap(),
+        ),
+        signature: None,
+    };
+    jwt.sign(&key).unwrap();
+    let token = jwt.to_token().unwrap();
+
+    let unverified = decode_unverified(&token).unwrap();
+    assert_eq!(unverified.alg(), Some(Algorithm::ES256));
+    assert_eq!(unverified.kid(), Some("did:example:tenant-a#key-1"));
+    assert_eq!(unverified.typ(), Some("JWT"));
+    assert_eq!(unverified.issuer(), Some("did:example:tenant-a"));
+    assert_eq!(unverified.audience(), vec!["api", "admin"]);
+    assert_eq!(unverified.untrusted_claims()["exp"], 1);
+    assert_eq!(unverified.token(), token);
+
+    let url_safe = "eyJhbGciOiJFZERTQSIsImtpZCI6ImsifQ.eyJhdWQiOiJhcGkifQ.c2ln~disclosure~";
+    let unverified = decode_unverified(url_safe).unwrap();
+    assert_eq!(unverified.alg(), Some(Algorithm::EdDSA));
+    assert_eq!(unverified.audience(), vec!["api"]);
+    assert_eq!(unverified.issuer(), None);
+
+    assert_eq!(
+        decode_unverified("eyJhbGciOiJub25lIn0.e30.").err(),
+        Some(Error::JWT_ALG_NONE)
+    );
+    assert_eq!(
+        decode_unverified("e30.e30").err(),
+        Some(Error::JWT_MALFORMED)
+    );
+    assert!(decode_unverified("e30.WzFd.c2ln")
+        .err()
+        .unwrap()
+        .is_malformed_input());
+}
+
+#[test]
+pub fn jwt_validate_with_lookup() {
+    let kid = "did:example:issuer#key-1";
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let mut jwt = JWT {
+        header: Header::new(String::from(kid), Algorithm::ES256),
+        payload: Payload(json!({"exp": Utc::now().timestamp() + 60})),
+        signature: None,
+    };
+    jwt.sign(&key).unwrap();
+    let token = jwt.to_token().unwrap();
+
+    let mut jwk = key.verifying_key().to_jwk().unwrap();
+    jwk.kid = Some(String::from(kid));
+    let jwks = Jwks { keys: vec![jwk] };
+    let store: InMemoryKeyStore<AnyVerifyingKey> = InMemoryKeyStore::new();
+    store.add(kid, key.verifying_key()).unwrap();
+    let did = DidKeyResolver::new(IssuerResolver(key.verifying_key()), ASSERTION_METHOD);
+
+    assert!(jwt.validate_with_lookup(&jwks).unwrap());
+    assert!(jwt.validate_with_lookup(&store).unwrap());
+    assert!(jwt.validate_with_lookup(&did).unwrap());
+    assert!(JWT::validate_token_with_lookup(&token, &did).unwrap().1);
+
+    assert_eq!(
+        jwks.lookup(None, Algorithm::ES256).unwrap().as_ref(),
+        &key.verifying_key()
+    );
+    assert_eq!(
+        jwks.lookup(Some(kid), Algorithm::ES384).err(),
+        Some(Error::JWKS_KEY_NOT_FOUND)
+    );
+    assert_eq!(
+        store.lookup(Some(kid), Algorithm::EdDSA).err(),
+        Some(Error::JWT_ALGORITHM_KEY_MISMATCH)
+    );
+    assert_eq!(
+        store.lookup(None, Algorithm::ES256).err(),
+        Some(Error::KEYSTORE_KEY_NOT_FOUND)
+    );
+
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let did = DidKeyResolver::new(IssuerResolver(other.verifying_key()), ASSERTION_METHOD);
+    assert!(!jwt.validate_with_lookup(&did).unwrap());
+}
+
+#[test]
+pub fn jwt_refresh_with_key_rotation() {
+    let ring: KeyRing = KeyRing::new("did:example:session", InMemoryKeyStore::new());
+    let first = ring
+        .rotate(Algorithm::ES256, chrono::Duration::days(1))
+        .unwrap();
+    let now = Utc::now().timestamp();
+    let issue = |exp: i64, kid: &str, key: &AnySigningKey| {
+        let mut jwt = JWT {
+            header: Header::new(String::from(kid), key.algorithm()),
+            payload: Payload(json!({
+                "sub": "alice",
+                "scope": "read",
+                "iat": now - 600,
+                "exp": exp,
+                "jti": "abc",
+            })),
+            signature: None,
+        };
+        jwt.sign(key).unwrap();
+        jwt.to_token().unwrap()
+    };
+    let token = issue(now + 60, &first, &ring.store().get(&first).unwrap());
+
+    let second = ring
+        .rotate(Algorithm::EdDSA, chrono::Duration::days(1))
+        .unwrap();
+    let options = RefreshOptions::new().lifetime(std::time::Duration::from_secs(1800));
+    let refreshed = JWT::refresh_with_ring(&token, &ring, &options).unwrap();
+    let jwt = JWT::from_token(&refreshed).unwrap();
+    assert_eq!(jwt.header.kid, second);
+    assert_eq!(jwt.header.alg, Algorithm::EdDSA);
+    assert_eq!(jwt.payload.0["sub"], "alice");
+    assert_eq!(jwt.payload.0["scope"], "read");
+    assert_eq!(jwt.payload.0["auth_time"], now - 600);
+    assert_ne!(jwt.payload.0["jti"], "abc");
+    assert!((jwt.payload.0["exp"].as_i64().unwrap() - (now + 1800)).abs() <= 5);
+    assert!(jwt.validate_with_lookup(&ring).unwrap());
+
+    let refreshed = JWT::refresh_with_ring(&refreshed, &ring, &options).unwrap();
+    let jwt = JWT::from_token(&refreshed).unwrap();
+    assert_eq!(jwt.payload.0["auth_time"], now - 600);
+
+    let windowed = options.refresh_window(std::time::Duration::from_secs(10));
+    assert_eq!(
+        JWT::refresh_with_ring(&token, &ring, &windowed).err(),
+        Some(Error::JWT_REFRESH_NOT_ALLOWED)
+    );
+
+    let capped = options.max_session(std::time::Duration::from_secs(1200));
+    let jwt = JWT::from_token(&JWT::refresh_with_ring(&token, &ring, &capped).unwrap()).unwrap();
+    assert_eq!(jwt.payload.0["exp"], now + 600);
+    let ended = options.max_session(std::time::Duration::from_secs(300));
+    assert_eq!(
+        JWT::refresh_with_ring(&token, &ring, &ended).err(),
+        Some(Error::JWT_REFRESH_NOT_ALLOWED)
+    );
+
+    let expired = issue(now - 30, &first, &ring.store().get(&first).unwrap());
+    assert_eq!(
+        JWT::refresh_with_ring(&expired, &ring, &options).err(),
+        Some(Error::JWT_EXPIRED)
+    );
+    let grace = options.expired_grace(std::time::Duration::from_secs(60));
+    assert!(JWT::refresh_with_ring(&expired, &ring, &grace).is_ok());
+
+    let forged = issue(
+        now + 60,
+        &first,
+        &AnySigningKey::generate(Algorithm::ES256).unwrap(),
+    );
+    assert_eq!(
+        JWT::refresh_with_ring(&forged, &ring, &options).err(),
+        Some(Error::JWT_SIGNATURE_INVALID)
+    );
+
+    let foreign = "did:example:other#key-1";
+    ring.store()
+        .add(foreign, AnySigningKey::generate(Algorithm::ES256).unwrap())
+        .unwrap();
+    assert_eq!(
+        JWT::refresh(&token, &ring, ring.store(), foreign, &options).err(),
+        Some(Error::JWT_REFRESH_KEY_MISMATCH)
+    );
+}
+
+#[test]
+pub fn jwt_nested_sign_then_encrypt() {
+    let kid = "did:example:issuer#key-1";
+    let recipient_kid = "did:example:bob#key-x25519-1";
+    let key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let store: InMemoryKeyStore<AnyVerifyingKey> = InMemoryKeyStore::new();
+    store.add(kid, key.verifying_key()).unwrap();
+    let bob = X25519SecretKey::generate();
+
+    let sign = |payload: Value| {
+        let mut jwt = JWT {
+            header: Header::new(String::from(kid), Algorithm::EdDSA),
+            payload: Payload(payload),
+            signature: None,
+        };
+        jwt.sign(&key).unwrap();
+        jwt
+    };
+    let now = Utc::now().timestamp();
+    let jwt = sign(json!({"sub": "alice", "exp": now + 60}));
+
+    let jwe = Jwe::encrypt_jwt(
+        &jwt,
+        ContentEncryption::A256GCM,
+        &[(recipient_kid, bob.public_key())],
+        None,
+    )
+    .unwrap();
+    assert!(jwe.is_nested_jwt().unwrap());
+    assert_eq!(jwe.protected_header().unwrap()["cty"], "JWT");
+    let token = jwe.to_compact().unwrap();
+
+    let decoded =
+        decode_nested(&token, |jwe| jwe.decrypt(recipient_kid, &bob, None), &store).unwrap();
+    assert_eq!(decoded.payload.0["sub"], "alice");
+
+    let other = X25519SecretKey::generate();
+    assert!(decode_nested(
+        &token,
+        |jwe| jwe.decrypt(recipient_kid, &other, None),
+        &store
+    )
+    .is_err());
+
+    let kek = [7u8; 32];
+    let jwe = Jwe::encrypt_jwt_with_kek(
+        &sign(json!({"exp": now - 60})),
+        ContentEncryption::A256GCM,
+        recipient_kid,
+        KeyWrapping::A256GCMKW,
+        &kek,
+    )
+    .unwrap();
+    assert_eq!(
+        decode_nested(
+            &jwe.to_compact().unwrap(),
+            |jwe| jwe.decrypt_with_kek(recipient_kid, &kek),
+            &store
+        )
+        .err(),
+        Some(Error::JWT_EXPIRED)
+    );
+
+    let jwe = Jwe::encrypt_jwt_with_kek(
+        &sign(json!({"exp": now + 60, "nbf": now + 600})),
+        ContentEncryption::A256GCM,
+        recipient_kid,
+        KeyWrapping::A256GCMKW,
+        &kek,
+    )
+    .unwrap();
+    assert_eq!(
+        decode_nested(
+            &jwe.to_compact().unwrap(),
+            |jwe| jwe.decrypt_with_kek(recipient_kid, &kek),
+            &store
+        )
+        .err(),
+        Some(Error::JWT_NOT_YET_VALID)
+    );
+
+    let forged_store: InMemoryKeyStore<AnyVerifyingKey> = InMemoryKeyStore::new();
+    forged_store
+        .add(
+            kid,
+            AnySigningKey::generate(Algorithm::EdDSA)
+                .unwrap()
+                .verifying_key(),
+        )
+        .unwrap();
+    assert_eq!(
+        decode_nested(
+            &token,
+            |jwe| jwe.decrypt(recipient_kid, &bob, None),
+            &forged_store
+        )
+        .err(),
+        Some(Error::JWT_SIGNATURE_INVALID)
+    );
+
+    let plain = Jwe::encrypt_with_kek(
+        jwt.to_token().unwrap().as_bytes(),
+        "JWT",
+        ContentEncryption::A256GCM,
+        recipient_kid,
+        KeyWrapping::A256GCMKW,
+        &kek,
+    )
+    .unwrap();
+    assert_eq!(
+        decode_nested(
+            &plain.to_compact().unwrap(),
+            |jwe| jwe.decrypt_with_kek(recipient_kid, &kek),
+            &store
+        )
+        .err(),
+        Some(Error::JWT_NOT_NESTED)
+    );
+}
@@ -0,0 +1,154 @@
+use did_crypto::{
+    crypto::{
+        kdf::{hkdf, hkdf_expand, hkdf_extract, HashFunction, PasswordKdf, PasswordKdfHeader},
+        x25519::X25519SecretKey,
+    },
+    errors::Error,
+};
+use serde_json::json;
+
+const IKM_HEX: &str = "0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b0b";
+const SALT_HEX: &str = "000102030405060708090a0b0c";
+const INFO_HEX: &str = "f0f1f2f3f4f5f6f7f8f9";
+const PASSPHRASE: &str = "correct horse battery staple";
+
+#[test]
+pub fn hkdf_rfc5869_vectors() {
+    let ikm = hex::decode(IKM_HEX).unwrap();
+    let salt = hex::decode(SALT_HEX).unwrap();
+    let info = hex::decode(INFO_HEX).unwrap();
+
+    let prk = hkdf_extract(HashFunction::Sha256, Some(&salt), &ikm);
+    assert_eq!(
+        hex::encode(&prk),
+        "077709362c2e32df0ddc3f0dc47bba6390b6c73bb50f9c3122ec844ad7c2b3e5"
+    );
+    assert_eq!(
+        hex::encode(hkdf_expand(HashFunction::Sha256, &prk, &info, 42).unwrap()),
+        "3cb25f25faacd57a90434f64d0362f2a2d2d0a90cf1a5a4c5db02d56ecc4c5bf34007208d5b887185865"
+    );
+    assert_eq!(
+        hex::encode(hkdf(HashFunction::Sha256, None, &ikm, &[], 42).unwrap()),
+        "8da4e775a563c18f715f802a063c5a31b8a11f5c5ee1879ec3454e5f3c738d2d9d201395faa4b61a96c8"
+    );
+
+    let vectors = [
+        (
+            HashFunction::Sha384,
+            "9b5097a86038b805309076a44b3a9f38063e25b516dcbf369f394cfab43685f748b6457763e4f0204fc5",
+        ),
+        (
+            HashFunction::Sha512,
+            "832390086cda71fb47625bb5ceb168e4c8e26a1a16ed34d9fc7fe92c1481579338da362cb8d9f925d7cb",
+        ),
+    ];
+
+    for (hash, okm) in vectors {
+        assert_eq!(
+            hkdf_extract(hash, Some(&salt), &ikm).len(),
+            hash.output_length()
+        );
+        assert_eq!(
+            hex::encode(hkdf(hash, Some(&salt), &ikm, &info, 42).unwrap()),
+            okm
+        );
+    }
+}
+
+#[test]
+pub fn hkdf_invalid_lengths() {
+    let ikm = hex::decode(IKM_HEX).unwrap();
+    let prk = hkdf_extract(HashFunction::Sha256, None, &ikm);
+
+    assert!(hkdf_expand(HashFunction::Sha256, &prk, &[], 255 * 32).is_ok());
+    assert!(hkdf_expand(HashFunction::Sha256, &prk, &[], 255 * 32 + 1).is_err());
+    assert!(hkdf_expand(HashFunction::Sha256, &prk[..16], &[], 32).is_err());
+}
+
+#[test]
+pub fn hkdf_shared_secret_key_agreement() {
+    let alice = X25519SecretKey::generate();
+    let bob = X25519SecretKey::generate();
+
+    let alice_key = hkdf(
+        HashFunction::Sha256,
+        None,
+        &alice.diffie_hellman(&bob.public_key()).unwrap(),
+        b"A256GCM",
+        32,
+    )
+    .unwrap();
+    let bob_key = hkdf(
+        HashFunction::Sha256,
+        None,
+        &bob.diffie_hellman(&alice.public_key()).unwrap(),
+        b"A256GCM",
+        32,
+    )
+    .unwrap();
+
+    assert_eq!(alice_key, bob_key);
+    assert_eq!(alice_key.len(), 32);
+}
+
+#[test]
+pub fn password_kdf_scrypt_vector() {
+    let kdf: PasswordKdf = serde_json::from_value(json!({
+        "kdf": "scrypt",
+        "salt": "TmFDbA",
+        "log_n": 10,
+        "r": 8,
+        "p": 16
+    }))
+    .unwrap();
+
+    assert_eq!(kdf.name(), "scrypt");
+    assert_eq!(
+        hex::encode(kdf.derive_key(b"password", 64).unwrap().as_slice()),
+        "fdbabe1c9d3472007856e7190d01e9fe7c6ad7cbc8237830e77376634b3731622eaf30d92e22a3886ff109279d9830dac727afb94a83ee6d8360cbdfa2cc0640"
+    );
+}
+
+#[test]
+pub fn password_kdf_header() {
+    let kdf = PasswordKdf::argon2id(1024, 1, 1).unwrap();
+    let key = kdf.derive_key(PASSPHRASE.as_bytes(), 32).unwrap();
+    assert_eq!(key.len(), 32);
+    assert_eq!(key, kdf.derive_key(PASSPHRASE.as_bytes(), 32).unwrap());
+    assert_ne!(key, kdf.derive_key(b"wrong passphrase", 32).unwrap());
+    assert_ne!(
+        key,
+        PasswordKdf::argon2id(1024, 1, 1)
+            .unwrap()
+            .derive_key(PASSPHRASE.as_bytes(), 32)
+            .unwrap()
+    );
+
+    let header = PasswordKdfHeader::new(kdf);
+    let json = header.to_json().unwrap();
+    assert!(json.starts_with("{\"version\":1,\"kdf\":\"argon2id\",\"salt\":"));
+
+    let restored = PasswordKdfHeader::from_json(&json).unwrap();
+    assert_eq!(restored, header);
+    assert_eq!(restored.derive_key(PASSPHRASE.as_bytes(), 32).unwrap(), key);
+
+    let future = json.replacen("\"version\":1", "\"version\":2", 1);
+    assert_eq!(
+        PasswordKdfHeader::from_json(&future)
+            .err()
+            .unwrap()
+            .to_string(),
+        Error::KDF_UNSUPPORTED_VERSION.to_string()
+    );
+    assert_eq!(
+        PasswordKdfHeader::from_json(&json.replace("argon2id", "pbkdf2"))
+            .err()
+            .unwrap()
+            .to_string(),
+        Error::KDF_INVALID_PARAMETERS.to_string()
+    );
+
+    assert!(PasswordKdf::scrypt(10, 0, 1).is_err());
+    assert!(PasswordKdf::argon2id(1, 1, 1).is_err());
+    assert_eq!(PasswordKdf::scrypt_default().name(), "scrypt");
+}
@@ -0,0 +1,218 @@
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::AnySigningKey,
+    errors::Error,
+    kel::{key_digest, KeyEvent, KeyEventLog, KeyEventType, SignedKeyEvent},
+};
+use serde_json::json;
+
+fn keys(alg: Algorithm, count: usize) -> Vec<AnySigningKey> {
+    (0..count)
+        .map(|_| AnySigningKey::generate(alg).unwrap())
+        .collect()
+}
+
+fn digests(keys: &[AnySigningKey]) -> Vec<String> {
+    keys.iter()
+        .map(|key| key_digest(&key.verifying_key()).unwrap())
+        .collect()
+}
+
+#[test]
+pub fn kel_inception_interaction_and_rotation() {
+    let current = keys(Algorithm::ES256, 1);
+    let next = keys(Algorithm::EdDSA, 1);
+    let after = keys(Algorithm::ES256, 1);
+
+    let mut kel = KeyEventLog::new();
+    let inception =
+        KeyEvent::inception(&[current[0].verifying_key()], 1, &digests(&next), 1).unwrap();
+    assert_eq!(inception.prefix, inception.digest);
+    let state = kel
+        .append(
+            SignedKeyEvent::new(inception.clone())
+                .sign(0, &current[0], Algorithm::ES256)
+                .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(state.sequence, 0);
+    assert!(state.is_transferable());
+
+    let interaction = kel
+        .interaction(vec![json!({"d": "credential-digest"})])
+        .unwrap();
+    assert_eq!(interaction.event_type, KeyEventType::Interaction);
+    kel.append(
+        SignedKeyEvent::new(interaction)
+            .sign(0, &current[0], Algorithm::ES256)
+            .unwrap(),
+    )
+    .unwrap();
+
+    let rotation = kel
+        .rotation(&[next[0].verifying_key()], 1, &digests(&after), 1)
+        .unwrap();
+    assert_eq!(
+        kel.clone()
+            .append(
+                SignedKeyEvent::new(rotation.clone())
+                    .sign(0, &current[0], Algorithm::ES256)
+                    .unwrap()
+            )
+            .err(),
+        Some(Error::KEL_THRESHOLD_NOT_MET)
+    );
+    let state = kel
+        .append(
+            SignedKeyEvent::new(rotation)
+                .sign(0, &next[0], Algorithm::EdDSA)
+                .unwrap(),
+        )
+        .unwrap();
+    assert_eq!(state.sequence, 2);
+    assert_eq!(state.prefix, inception.prefix);
+    assert_eq!(
+        state.verifying_keys().unwrap(),
+        vec![next[0].verifying_key()]
+    );
+
+    let parsed = KeyEventLog::from_json(&kel.to_json().unwrap()).unwrap();
+    assert_eq!(parsed, kel);
+    assert_eq!(parsed.state().unwrap(), state);
+
+    let uncommitted = keys(Algorithm::ES256, 1);
+    let rotation = kel
+        .rotation(&[uncommitted[0].verifying_key()], 1, &[], 0)
+        .unwrap();
+    assert_eq!(
+        kel.append(
+            SignedKeyEvent::new(rotation)
+                .sign(0, &uncommitted[0], Algorithm::ES256)
+                .unwrap()
+        )
+        .err(),
+        Some(Error::KEL_ROTATION_NOT_COMMITTED)
+    );
+}
+
+#[test]
+pub fn kel_multisig_thresholds() {
+    let current = keys(Algorithm::ES256, 3);
+    let next = keys(Algorithm::ES256, 3);
+    let verifying: Vec<_> = current.iter().map(AnySigningKey::verifying_key).collect();
+    let inception = KeyEvent::inception(&verifying, 2, &digests(&next), 2).unwrap();
+
+    let mut kel = KeyEventLog::new();
+    assert_eq!(
+        kel.append(
+            SignedKeyEvent::new(inception.clone())
+                .sign(0, &current[0], Algorithm::ES256)
+                .unwrap()
+                .sign(0, &current[0], Algorithm::ES256)
+                .unwrap()
+        )
+        .err(),
+        Some(Error::KEL_THRESHOLD_NOT_MET)
+    );
+    assert_eq!(
+        kel.append(
+            SignedKeyEvent::new(inception.clone())
+                .sign(0, &current[0], Algorithm::ES256)
+                .unwrap()
+                .sign(1, &current[2], Algorithm::ES256)
+                .unwrap()
+        )
+        .err(),
+        Some(Error::KEL_THRESHOLD_NOT_MET)
+    );
+    kel.append(
+        SignedKeyEvent::new(inception)
+            .sign(0, &current[0], Algorithm::ES256)
+            .unwrap()
+            .sign(2, &current[2], Algorithm::ES256)
+            .unwrap(),
+    )
+    .unwrap();
+
+    let rotated: Vec<_> = next.iter().map(AnySigningKey::verifying_key).collect();
+    let rotation = kel.rotation(&rotated[..2], 1, &[], 0).unwrap();
+    assert_eq!(
+        kel.clone()
+            .append(
+                SignedKeyEvent::new(rotation.clone())
+                    .sign(0, &next[0], Algorithm::ES256)
+                    .unwrap()
+            )
+            .err(),
+        Some(Error::KEL_ROTATION_NOT_COMMITTED)
+    );
+    let state = kel
+        .append(
+            SignedKeyEvent::new(rotation)
+                .sign(0, &next[0], Algorithm::ES256)
+                .unwrap()
+                .sign(1, &next[1], Algorithm::ES256)
+                .unwrap(),
+        )
+        .unwrap();
+    assert!(!state.is_transferable());
+
+    let rotation = kel.rotation(&rotated[2..], 1, &[], 0).unwrap();
+    assert_eq!(
+        kel.append(
+            SignedKeyEvent::new(rotation)
+                .sign(0, &next[2], Algorithm::ES256)
+                .unwrap()
+        )
+        .err(),
+        Some(Error::KEL_ROTATION_NOT_COMMITTED)
+    );
+}
+
+#[test]
+pub fn kel_rejects_tampered_events() {
+    let current = keys(Algorithm::EdDSA, 1);
+    let next = keys(Algorithm::EdDSA, 1);
+    let inception =
+        KeyEvent::inception(&[current[0].verifying_key()], 1, &digests(&next), 1).unwrap();
+    let signed = SignedKeyEvent::new(inception)
+        .sign(0, &current[0], Algorithm::EdDSA)
+        .unwrap();
+
+    let mut tampered = signed.clone();
+    tampered.event.next_keys = Vec::new();
+    let error = KeyEventLog::new().append(tampered).err().unwrap();
+    assert_eq!(error, Error::KEL_INVALID_EVENT);
+    assert!(error.is_malformed_input());
+
+    let mut kel = KeyEventLog::new();
+    kel.append(signed.clone()).unwrap();
+    assert_eq!(kel.append(signed).err(), Some(Error::KEL_INVALID_EVENT));
+
+    let mut interaction = kel.interaction(Vec::new()).unwrap();
+    interaction.sequence = 5;
+    assert_eq!(
+        kel.append(
+            SignedKeyEvent::new(interaction)
+                .sign(0, &current[0], Algorithm::EdDSA)
+                .unwrap()
+        )
+        .err(),
+        Some(Error::KEL_INVALID_EVENT)
+    );
+
+    let interaction = kel.interaction(Vec::new()).unwrap();
+    assert_eq!(
+        kel.append(
+            SignedKeyEvent::new(interaction)
+                .sign(3, &current[0], Algorithm::EdDSA)
+                .unwrap()
+        )
+        .err(),
+        Some(Error::KEL_INVALID_EVENT)
+    );
+    assert_eq!(
+        KeyEventLog::new().state().err(),
+        Some(Error::KEL_INVALID_EVENT)
+    );
+}
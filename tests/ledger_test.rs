@@ -0,0 +1,96 @@
+use std::cell::RefCell;
+
+use did_crypto::{
+    crypto::ledger::{
+        ethereum_address, parse_derivation_path, personal_message_hash, verify_personal_message,
+        LedgerEthereumSigner, LedgerTransport,
+    },
+    errors::Error,
+};
+use k256::ecdsa::SigningKey;
+
+const PRIVATE_KEY_HEX: &str = "7af6732f2a2356a3a3288cb1f3a0fea1fda6a4b1e3ecc6cfc33ac8b1f1e1d21c";
+const DERIVATION_PATH: &str = "m/44'/60'/0'/0/0";
+
+struct MockLedger {
+    key: SigningKey,
+    message: RefCell<(usize, Vec<u8>)>,
+}
+
+impl LedgerTransport for MockLedger {
+    fn exchange(&self, apdu: &[u8]) -> Result<Vec<u8>, Error> {
+        let data = &apdu[5..];
+        let mut response = match (apdu[1], apdu[2]) {
+            (0x02, _) => {
+                let point = self.key.verifying_key().to_encoded_point(false);
+                let address = ethereum_address(point.as_bytes()).unwrap();
+                let mut response = vec![point.len() as u8];
+                response.extend_from_slice(point.as_bytes());
+                response.push(40);
+                response.extend_from_slice(&address.as_bytes()[2..]);
+                response
+            }
+            (0x08, p1) => {
+                let mut message = self.message.borrow_mut();
+                if p1 == 0x00 {
+                    let offset = 1 + data[0] as usize * 4;
+                    let length = u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap());
+                    *message = (length as usize, data[offset + 4..].to_vec());
+                } else {
+                    message.1.extend_from_slice(data);
+                }
+
+                if message.1.len() < message.0 {
+                    Vec::new()
+                } else {
+                    let (signature, recovery_id) = self
+                        .key
+                        .sign_prehash_recoverable(&personal_message_hash(&message.1))
+                        .unwrap();
+                    let mut response = vec![27 + recovery_id.to_byte()];
+                    response.extend_from_slice(&signature.to_bytes());
+                    response
+                }
+            }
+            _ => return Ok(vec![0x6d, 0x00]),
+        };
+
+        response.extend_from_slice(&[0x90, 0x00]);
+        Ok(response)
+    }
+}
+
+#[test]
+pub fn ledger_ethereum_personal_sign() {
+    let transport = MockLedger {
+        key: SigningKey::from_slice(&hex::decode(PRIVATE_KEY_HEX).unwrap()).unwrap(),
+        message: RefCell::new((0, Vec::new())),
+    };
+    let signer = LedgerEthereumSigner::new(transport, DERIVATION_PATH).unwrap();
+    let address = signer.address().unwrap();
+    assert_eq!(
+        signer.public_key().unwrap().to_bytes(),
+        signer.verifying_key().unwrap().to_bytes()
+    );
+
+    let short_message = b"did:ethr proof";
+    let signature = signer.sign_personal_message(short_message).unwrap();
+    assert_eq!(signature.len(), 65);
+    assert!(verify_personal_message(short_message, &signature, &address).unwrap());
+    assert!(!verify_personal_message(b"tampered", &signature, &address).unwrap());
+
+    let long_message = vec![0x41u8; 600];
+    let signature = signer.sign_personal_message(&long_message).unwrap();
+    assert!(verify_personal_message(&long_message, &signature, &address).unwrap());
+}
+
+#[test]
+pub fn ledger_derivation_path_parsing() {
+    assert_eq!(
+        parse_derivation_path(DERIVATION_PATH).unwrap(),
+        vec![0x8000002c, 0x8000003c, 0x80000000, 0, 0]
+    );
+    assert!(parse_derivation_path("44'/60'").is_err());
+    assert!(parse_derivation_path("m/x").is_err());
+    assert!(parse_derivation_path("m").is_err());
+}
@@ -0,0 +1,136 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    errors::Error,
+    jwk::{Jwk, Jwks},
+    jwt::{Header, Payload, JWT},
+    limits::{InputLimits, LimitedResolver},
+    resolver::{verify_with_did, DidResolver, DidVerificationOptions},
+    verifier::VerificationOptions,
+};
+use serde_json::{json, Value};
+
+const DID: &str = "did:example:issuer";
+
+struct IssuerResolver(AnyVerifyingKey, Value);
+
+impl DidResolver for IssuerResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        Ok(json!({
+            "id": did,
+            "verificationMethod": [{
+                "id": "#key-1",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": self.0.to_jwk().unwrap(),
+            }],
+            "assertionMethod": ["#key-1"],
+            "service": self.1,
+        }))
+    }
+}
+
+fn nested(depth: usize) -> Value {
+    (0..depth).fold(json!("leaf"), |value, _| json!([value]))
+}
+
+fn sign(key: &AnySigningKey, payload: Value) -> String {
+    let mut jwt = JWT {
+        header: Header::new(String::from("#key-1"), Algorithm::ES256),
+        payload: Payload(payload),
+        signature: None,
+    };
+    jwt.sign(key).unwrap();
+    jwt.to_token().unwrap()
+}
+
+#[test]
+pub fn limits_check_tokens() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let limits = InputLimits::default();
+    let token = sign(&key, json!({"exp": Utc::now().timestamp() + 60}));
+    limits.check_token(&token).unwrap();
+    assert!(JWT::from_token_with_limits(&token, &limits)
+        .unwrap()
+        .validate(key.verifying_key())
+        .unwrap());
+
+    assert_eq!(
+        JWT::from_token_with_limits(&token, &limits.max_payload_size(8)).err(),
+        Some(Error::INPUT_TOO_LARGE)
+    );
+    assert_eq!(
+        limits
+            .check_token(&sign(&key, json!({"claim": nested(40)})))
+            .err(),
+        Some(Error::INPUT_TOO_DEEP)
+    );
+    limits
+        .check_token(&sign(&key, json!({"claim": "[[[[[[[[[[\"]]]]]"})))
+        .unwrap();
+
+    let header = URL_SAFE_NO_PAD.encode(r#"{"alg":"ES256"}"#);
+    limits
+        .check_token(&format!("{}.e30.c2lnbmF0dXJl", header))
+        .unwrap();
+    for token in [
+        format!("{}.e30.c2lnbmF0dXJlMQ==", header),
+        format!("{}.e31.c2ln", header),
+    ] {
+        let error = limits.check_token(&token).err().unwrap();
+        assert_eq!(error, Error::INPUT_NOT_CANONICAL);
+        assert!(error.is_malformed_input());
+    }
+    limits
+        .canonical_base64(false)
+        .check_token(&format!("{}.e30.c2lnbmF0dXJlMQ==", header))
+        .unwrap();
+}
+
+#[test]
+pub fn limits_check_keys() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let json = key.verifying_key().to_jwk().unwrap().to_json().unwrap();
+    let limits = InputLimits::default();
+    assert_eq!(
+        Jwk::from_json_with_limits(&json, &limits).unwrap(),
+        Jwk::from_json(&json).unwrap()
+    );
+    assert_eq!(
+        Jwk::from_json_with_limits(&json, &limits.max_key_size(16)).err(),
+        Some(Error::INPUT_TOO_LARGE)
+    );
+
+    let jwks = format!(r#"{{"keys":[{}],"extra":{}}}"#, json, nested(40));
+    assert_eq!(
+        Jwks::from_json_with_limits(&jwks, &limits).err(),
+        Some(Error::INPUT_TOO_DEEP)
+    );
+}
+
+#[test]
+pub fn limits_verify_with_did() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let token = sign(
+        &key,
+        json!({"iss": DID, "exp": Utc::now().timestamp() + 60}),
+    );
+    let options = DidVerificationOptions::new().limits(InputLimits::default());
+
+    let resolver = IssuerResolver(key.verifying_key(), json!([]));
+    assert!(verify_with_did(&token, DID, &resolver, &options).unwrap());
+
+    let deep = IssuerResolver(key.verifying_key(), nested(40));
+    assert!(verify_with_did(&token, DID, &deep, &DidVerificationOptions::new()).unwrap());
+    assert_eq!(
+        verify_with_did(&token, DID, &deep, &options).unwrap_err(),
+        Error::INPUT_TOO_DEEP
+    );
+    let lenient = options.verification(VerificationOptions::lenient());
+    assert!(!verify_with_did(&token, DID, &deep, &lenient).unwrap());
+
+    let limited = LimitedResolver::new(resolver, InputLimits::new().max_document_size(64));
+    assert_eq!(limited.resolve(DID).unwrap_err(), Error::INPUT_TOO_LARGE);
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "logging")]
+
+use std::sync::Mutex;
+
+use did_crypto::crypto::ecdsa::_256::P256SigningKey;
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+struct TestLogger {
+    records: Mutex<Vec<(Level, String, String)>>,
+}
+
+impl Log for TestLogger {
+    fn enabled(&self, _metadata: &Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &Record) {
+        self.records.lock().unwrap().push((
+            record.level(),
+            String::from(record.target()),
+            record.args().to_string(),
+        ));
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: TestLogger = TestLogger {
+    records: Mutex::new(Vec::new()),
+};
+
+#[test]
+pub fn errors_are_reported_to_installed_logger() {
+    log::set_logger(&LOGGER).unwrap();
+    log::set_max_level(LevelFilter::Error);
+
+    assert!(P256SigningKey::from_pem("not a key").is_err());
+
+    let records = LOGGER.records.lock().unwrap();
+    assert!(!records.is_empty());
+    assert!(records
+        .iter()
+        .all(|(level, target, _)| *level == Level::Error && target == "did_crypto"));
+}
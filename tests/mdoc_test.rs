@@ -0,0 +1,110 @@
+use std::collections::BTreeMap;
+
+use chrono::{Duration, Utc};
+use coset::{cbor::value::Value, iana, CoseKeyBuilder};
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::ecdsa::_256::{P256SigningKey, P256VerifyingKey},
+    mdoc::{device_sign, IssuerSigned, ValidityInfo, MDL_DOC_TYPE, MDL_NAMESPACE},
+};
+
+const PUBLIC_KEY_256_HEX: &str =
+    "04115b3fa39fae41b4e32f7721ca72f8c1781483647dabd514f08e66128bd47fce9067b90e0488c9c2a9f30f5a266a07841d6c077413ba07e74569b99d4fd3cec6";
+const PRIVATE_KEY_256_HEX: &str =
+    "7af6732f581d005afcf216f6385ff6371029242cc60840dd7d2a7a5503b7d21c";
+
+fn issue(valid_until: Duration) -> IssuerSigned {
+    let public_key = hex::decode(PUBLIC_KEY_256_HEX).unwrap();
+    let device_key = CoseKeyBuilder::new_ec2_pub_key(
+        iana::EllipticCurve::P_256,
+        public_key[1..33].to_vec(),
+        public_key[33..].to_vec(),
+    )
+    .build();
+
+    let mut name_spaces = BTreeMap::new();
+    name_spaces.insert(
+        String::from(MDL_NAMESPACE),
+        vec![
+            (String::from("family_name"), Value::from("Doe")),
+            (String::from("given_name"), Value::from("John")),
+            (String::from("age_over_18"), Value::Bool(true)),
+        ],
+    );
+
+    let now = Utc::now();
+    IssuerSigned::issue(
+        MDL_DOC_TYPE,
+        &name_spaces,
+        device_key,
+        ValidityInfo {
+            signed: now,
+            valid_from: now - Duration::minutes(1),
+            valid_until: now + valid_until,
+        },
+        &[],
+        Algorithm::ES256,
+        P256SigningKey::from_bytes(&hex::decode(PRIVATE_KEY_256_HEX).unwrap()).unwrap(),
+    )
+    .unwrap()
+}
+
+fn public_key() -> P256VerifyingKey {
+    P256VerifyingKey::from_bytes(&hex::decode(PUBLIC_KEY_256_HEX).unwrap()).unwrap()
+}
+
+#[test]
+pub fn mdoc_issue_disclose_and_verify() {
+    let issuer_signed = issue(Duration::days(365));
+    let decoded = IssuerSigned::from_bytes(&issuer_signed.to_bytes().unwrap()).unwrap();
+    assert!(decoded.name_spaces == issuer_signed.name_spaces);
+    assert!(decoded.verify(public_key()).unwrap());
+
+    let mso = decoded.mso().unwrap();
+    assert_eq!(mso.doc_type, MDL_DOC_TYPE);
+    assert_eq!(mso.value_digests[MDL_NAMESPACE].len(), 3);
+
+    let disclosed = decoded.disclose(&[(MDL_NAMESPACE, "age_over_18")]).unwrap();
+    assert!(disclosed.verify(public_key()).unwrap());
+    assert_eq!(
+        disclosed.element(MDL_NAMESPACE, "age_over_18"),
+        Some(&Value::Bool(true))
+    );
+    assert!(disclosed.element(MDL_NAMESPACE, "family_name").is_none());
+    assert!(decoded.disclose(&[(MDL_NAMESPACE, "portrait")]).is_err());
+
+    let mut tampered = disclosed.clone();
+    tampered.name_spaces.get_mut(MDL_NAMESPACE).unwrap()[0] =
+        issue(Duration::days(365)).name_spaces[MDL_NAMESPACE][2].clone();
+    assert!(!tampered.verify(public_key()).unwrap());
+
+    assert!(!issue(Duration::minutes(-1)).verify(public_key()).unwrap());
+}
+
+#[test]
+pub fn mdoc_device_authentication() {
+    let issuer_signed = issue(Duration::days(365));
+    let session_transcript = Value::Array(vec![Value::Null, Value::Null, Value::from("handover")]);
+    let device_name_spaces = Value::Map(Vec::new());
+
+    let device_signature = device_sign(
+        &session_transcript,
+        MDL_DOC_TYPE,
+        &device_name_spaces,
+        Algorithm::ES256,
+        P256SigningKey::from_bytes(&hex::decode(PRIVATE_KEY_256_HEX).unwrap()).unwrap(),
+    )
+    .unwrap();
+    assert!(device_signature.payload().is_none());
+
+    assert!(issuer_signed
+        .verify_device_signature(&device_signature, &session_transcript, &device_name_spaces)
+        .unwrap());
+    assert!(!issuer_signed
+        .verify_device_signature(
+            &device_signature,
+            &Value::Array(vec![Value::Null, Value::Null, Value::from("other")]),
+            &device_name_spaces
+        )
+        .unwrap());
+}
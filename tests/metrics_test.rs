@@ -0,0 +1,72 @@
+use std::sync::{Arc, Mutex};
+
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{any::AnySigningKey, x25519::X25519SecretKey},
+    jwe::{ContentEncryption, Jwe},
+    metrics::{self, Operation, OperationMetrics},
+    signer::sign,
+    verifier::verify,
+};
+
+type Records = Arc<Mutex<Vec<(Operation, Option<String>, bool)>>>;
+
+#[test]
+pub fn operations_are_reported_to_metrics() {
+    let records: Records = Arc::default();
+    let recorder = records.clone();
+    metrics::set_metrics(Arc::new(move |metrics: &OperationMetrics| {
+        assert!(metrics.latency.is_some());
+        recorder.lock().unwrap().push((
+            metrics.operation,
+            metrics.algorithm.map(String::from),
+            metrics.success,
+        ));
+    }));
+
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let signature = sign("did:example:123", &key, Algorithm::ES256).unwrap();
+    verify(
+        "did:example:123",
+        &signature,
+        key.verifying_key(),
+        Algorithm::ES256,
+    )
+    .unwrap();
+    verify(
+        "did:example:456",
+        &signature,
+        key.verifying_key(),
+        Algorithm::ES256,
+    )
+    .unwrap();
+    assert!(sign("did:example:123", &key, Algorithm::ES384).is_err());
+
+    let recipient = X25519SecretKey::generate();
+    let jwe = Jwe::encrypt(
+        b"did:example:123",
+        "application/didcomm-encrypted+json",
+        ContentEncryption::A256GCM,
+        &[("did:example:bob#key-1", recipient.public_key())],
+        None,
+    )
+    .unwrap();
+    jwe.decrypt("did:example:bob#key-1", &recipient, None)
+        .unwrap();
+
+    metrics::clear_metrics();
+    sign("did:example:123", &key, Algorithm::ES256).unwrap();
+
+    let es256 = Some(String::from("ES256"));
+    assert_eq!(
+        *records.lock().unwrap(),
+        vec![
+            (Operation::Sign, es256.clone(), true),
+            (Operation::Verify, es256.clone(), true),
+            (Operation::Verify, es256, false),
+            (Operation::Sign, Some(String::from("ES384")), false),
+            (Operation::Encrypt, Some(String::from("A256GCM")), true),
+            (Operation::Decrypt, None, true),
+        ]
+    );
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "mldsa")]
+
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{
+        mldsa::{MLDSA65SigningKey, MLDSA65VerifyingKey},
+        SignFromKey,
+    },
+    jwk::Jwk,
+    signer::sign,
+    verifier::verify,
+};
+
+const SEED: &str = "0101010101010101010101010101010101010101010101010101010101010101";
+const MESSAGE: &str = "did:example:123#mldsa-key-1";
+
+#[test]
+pub fn mldsa65_signing_and_verifying() {
+    let key = MLDSA65SigningKey::from_bytes(&hex::decode(SEED).unwrap()).unwrap();
+    let verifying_key = MLDSA65VerifyingKey::from_bytes(&key.verifying_key().to_bytes()).unwrap();
+
+    let signature = sign(String::from(MESSAGE), key, Algorithm::MLDSA65).unwrap();
+    assert_eq!(base64_url::decode(&signature).unwrap().len(), 3309);
+    assert!(verify(
+        String::from(MESSAGE),
+        signature.clone(),
+        verifying_key.clone(),
+        Algorithm::MLDSA65
+    )
+    .unwrap());
+    assert!(!verify(
+        String::from("another message"),
+        signature.clone(),
+        verifying_key.clone(),
+        Algorithm::MLDSA65
+    )
+    .unwrap());
+    assert!(verify(
+        String::from(MESSAGE),
+        signature,
+        verifying_key,
+        Algorithm::EdDSA
+    )
+    .is_err());
+
+    assert!(MLDSA65SigningKey::generate()
+        .sign_bytes(MESSAGE.as_bytes(), Algorithm::ES256)
+        .is_err());
+}
+
+#[test]
+pub fn mldsa65_key_encodings() {
+    let key = MLDSA65SigningKey::from_bytes(&hex::decode(SEED).unwrap()).unwrap();
+    let verifying_key = key.verifying_key();
+    assert_eq!(verifying_key.to_bytes().len(), 1952);
+
+    let multicodec = verifying_key.to_multicodec();
+    assert_eq!(&multicodec[..2], &[0x91, 0x24]);
+    assert!(MLDSA65VerifyingKey::from_multicodec(&multicodec).unwrap() == verifying_key);
+
+    let jwk = Jwk::from_json(&verifying_key.to_jwk().to_json().unwrap()).unwrap();
+    assert_eq!(jwk.kty, "AKP");
+    assert_eq!(jwk.alg.as_deref(), Some("ML-DSA-65"));
+    assert!(MLDSA65VerifyingKey::from_jwk(&jwk).unwrap() == verifying_key);
+
+    let restored = MLDSA65SigningKey::from_jwk(&key.to_jwk()).unwrap();
+    assert_eq!(restored.to_bytes(), hex::decode(SEED).unwrap());
+
+    let mismatched = Jwk {
+        public: MLDSA65SigningKey::generate().to_jwk().public,
+        ..key.to_jwk()
+    };
+    assert!(MLDSA65SigningKey::from_jwk(&mismatched).is_err());
+}
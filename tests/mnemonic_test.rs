@@ -0,0 +1,62 @@
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{mnemonic::Mnemonic, SignFromKey},
+};
+
+const PHRASE: &str =
+    "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+const TREZOR_SEED_HEX: &str = "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04";
+const ED25519_MASTER_KEY_HEX: &str =
+    "560f9f3c94558b6551928bb781cf6092c6b8800b4fc544af2c9444ed126d51aa";
+const SECP256K1_MASTER_KEY_HEX: &str =
+    "1837c1be8e2995ec11cda2b066151be2cfb48adf9e47b151d46adab3a21cdf67";
+
+#[test]
+pub fn mnemonic_seed_and_master_keys() {
+    let mnemonic = Mnemonic::from_entropy(&[0u8; 16]).unwrap();
+    assert_eq!(mnemonic.phrase(), PHRASE);
+    assert_eq!(hex::encode(*mnemonic.to_seed("TREZOR")), TREZOR_SEED_HEX);
+
+    let restored = Mnemonic::from_phrase(PHRASE).unwrap();
+    assert_eq!(
+        hex::encode(restored.ed25519_signing_key("").unwrap().to_bytes()),
+        ED25519_MASTER_KEY_HEX
+    );
+    assert_eq!(
+        hex::encode(restored.secp256k1_signing_key("").unwrap().to_bytes()),
+        SECP256K1_MASTER_KEY_HEX
+    );
+    assert_ne!(
+        restored.ed25519_signing_key("TREZOR").unwrap().to_bytes(),
+        restored.ed25519_signing_key("").unwrap().to_bytes()
+    );
+
+    assert!(Mnemonic::from_phrase("abandon abandon abandon").is_err());
+    assert!(Mnemonic::from_phrase(&PHRASE.replace("about", "abandon")).is_err());
+}
+
+#[test]
+pub fn mnemonic_generation() {
+    for word_count in [12, 24] {
+        let mnemonic = Mnemonic::generate(word_count).unwrap();
+        assert_eq!(mnemonic.word_count(), word_count);
+
+        let restored = Mnemonic::from_phrase(&mnemonic.phrase()).unwrap();
+        let signature = restored
+            .ed25519_signing_key("passphrase")
+            .unwrap()
+            .sign_bytes("did:key".as_bytes(), Algorithm::EdDSA)
+            .unwrap();
+        assert_eq!(
+            signature,
+            mnemonic
+                .ed25519_signing_key("passphrase")
+                .unwrap()
+                .sign_bytes("did:key".as_bytes(), Algorithm::EdDSA)
+                .unwrap()
+        );
+    }
+
+    assert!(Mnemonic::generate(13).is_err());
+    assert!(Mnemonic::generate(27).is_err());
+}
@@ -0,0 +1,100 @@
+#![cfg(feature = "uniffi")]
+
+use chrono::Utc;
+use did_crypto::{
+    jwk::Jwk,
+    mobile::{self, DidResolver, FfiError},
+};
+use serde_json::json;
+
+const ALICE: &str = "did:example:alice";
+
+struct StaticResolver(String);
+
+impl DidResolver for StaticResolver {
+    fn resolve(&self, did: String) -> Result<String, FfiError> {
+        match did.as_str() {
+            ALICE => Ok(self.0.clone()),
+            _ => Err(FfiError::Failed {
+                message: String::from("not found"),
+            }),
+        }
+    }
+}
+
+#[test]
+pub fn mobile_sign_and_verify() {
+    for alg in ["ES256", "EdDSA"] {
+        let private_key = mobile::generate_key(String::from(alg)).unwrap();
+        let public_key = mobile::public_key(String::from(alg), private_key.clone()).unwrap();
+
+        let signature = mobile::sign(
+            String::from(alg),
+            private_key.clone(),
+            ALICE.as_bytes().to_vec(),
+        )
+        .unwrap();
+        assert!(mobile::verify(
+            String::from(alg),
+            public_key.clone(),
+            ALICE.as_bytes().to_vec(),
+            signature
+        )
+        .unwrap());
+
+        let payload = json!({ "iss": ALICE, "exp": Utc::now().timestamp() + 60 }).to_string();
+        let token = mobile::sign_jwt(
+            String::from(alg),
+            private_key,
+            String::from("key-1"),
+            payload,
+        )
+        .unwrap();
+        assert!(mobile::verify_jwt(token, public_key).unwrap());
+    }
+
+    assert!(matches!(
+        mobile::generate_key(String::from("none")),
+        Err(FfiError::Failed { .. })
+    ));
+}
+
+#[test]
+pub fn mobile_resolve_keys() {
+    let private_key = mobile::generate_key(String::from("EdDSA")).unwrap();
+    let public_key = mobile::public_key(String::from("EdDSA"), private_key).unwrap();
+    let document = json!({
+        "id": ALICE,
+        "verificationMethod": [{
+            "id": "#key-1",
+            "type": "JsonWebKey2020",
+            "controller": ALICE,
+            "publicKeyJwk": {
+                "kty": "OKP",
+                "crv": "Ed25519",
+                "x": base64_url::encode(&public_key),
+            },
+        }],
+        "assertionMethod": ["#key-1"],
+    });
+    let resolver = || Box::new(StaticResolver(document.to_string()));
+
+    let keys = mobile::resolve_keys(
+        resolver(),
+        String::from("did:example:alice#key-1"),
+        String::from("assertionMethod"),
+    )
+    .unwrap();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(
+        Jwk::from_json(&keys[0]).unwrap().kid.as_deref(),
+        Some("did:example:alice#key-1")
+    );
+
+    assert!(mobile::resolve_keys(
+        resolver(),
+        String::from("did:example:bob"),
+        String::from("assertionMethod"),
+    )
+    .is_err());
+}
@@ -0,0 +1,91 @@
+use std::str::FromStr;
+
+use did_crypto::{
+    errors::Error,
+    hash::HashAlgorithm,
+    multicodec::{DAG_JSON, RAW, SHA2_256},
+    multihash::{Cid, Multihash},
+};
+
+const HELLO_WORLD_SHA256: &str = "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9";
+
+#[test]
+pub fn multihash_round_trip() {
+    let multihash = Multihash::digest(HashAlgorithm::Sha256, b"hello world");
+    assert_eq!(multihash.code(), SHA2_256);
+    assert_eq!(
+        hex::encode(multihash.to_bytes()),
+        format!("1220{}", HELLO_WORLD_SHA256)
+    );
+    assert_eq!(
+        Multihash::from_bytes(&multihash.to_bytes()).unwrap(),
+        multihash
+    );
+    assert!(multihash.verify(b"hello world"));
+    assert!(!multihash.verify(b"hello world!"));
+
+    for algorithm in [
+        HashAlgorithm::Sha384,
+        HashAlgorithm::Sha512,
+        HashAlgorithm::Sha3_256,
+        HashAlgorithm::Shake256,
+    ] {
+        let multihash = Multihash::digest(algorithm, b"document");
+        assert_eq!(
+            Multihash::from_bytes(&multihash.to_bytes()).unwrap(),
+            multihash
+        );
+    }
+
+    let bytes = multihash.to_bytes();
+    for (bytes, error) in [
+        (bytes[..20].to_vec(), Error::MULTIHASH_MALFORMED),
+        (
+            [bytes.as_slice(), &[0]].concat(),
+            Error::MULTIHASH_MALFORMED,
+        ),
+        (
+            [&[0x12, 0x10], &bytes[2..18]].concat(),
+            Error::MULTIHASH_MALFORMED,
+        ),
+        (
+            [&[0x11, 0x14], &[0u8; 20][..]].concat(),
+            Error::MULTIHASH_UNSUPPORTED,
+        ),
+    ] {
+        assert_eq!(Multihash::from_bytes(&bytes).unwrap_err(), error);
+    }
+}
+
+#[test]
+pub fn cid_v1_round_trip() {
+    let cid = Cid::new_v1(RAW, HashAlgorithm::Sha256, b"hello world");
+    assert_eq!(
+        cid.to_string(),
+        "bafkreifzjut3te2nhyekklss27nh3k72ysco7y32koao5eei66wof36n5e"
+    );
+    assert_eq!(
+        hex::encode(cid.to_bytes()),
+        format!("01551220{}", HELLO_WORLD_SHA256)
+    );
+    assert_eq!(Cid::from_str(&cid.to_string()).unwrap(), cid);
+    assert_eq!(
+        Cid::from_str("zb2rhj7crUKTQYRGCRATFaQ6YFLTde2YzdqbbhAASkL9uRDXn").unwrap(),
+        cid
+    );
+    assert!(cid.verify(b"hello world"));
+
+    let document = br#"{"id":"did:example:123"}"#;
+    let cid = Cid::new_v1(DAG_JSON, HashAlgorithm::Sha256, document);
+    assert_eq!(Cid::from_bytes(&cid.to_bytes()).unwrap(), cid);
+    assert!(cid.to_string().starts_with("baguqeera"));
+
+    for invalid in [
+        "QmaozNR7DZHQK1ZcU9p7QdrshMvXqWK6gpu5rmrkPdT3L4",
+        "bafkrei",
+        "b!!",
+    ] {
+        let error = Cid::from_str(invalid).unwrap_err();
+        assert!(error.is_malformed_input());
+    }
+}
@@ -0,0 +1,121 @@
+#![cfg(all(feature = "nacl", feature = "eddsa"))]
+
+use did_crypto::{
+    crypto::{
+        eddsa::EDDSASigningKey,
+        nacl::{
+            box_decrypt, box_encrypt, generate_nonce, seal, unseal, BoxCipher, SEALED_BOX_OVERHEAD,
+        },
+        x25519::{X25519PublicKey, X25519SecretKey},
+    },
+    errors::Error,
+};
+
+const ALICE_SECRET_HEX: &str = "0102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f20";
+const BOB_SECRET_HEX: &str = "2122232425262728292a2b2c2d2e2f303132333435363738393a3b3c3d3e3f40";
+const NONCE_HEX: &str = "6465666768696a6b6c6d6e6f707172737475767778797a7b";
+const MESSAGE: &[u8] = b"hello from a libsodium wallet";
+const BOX_HEX: &str =
+    "7be80a6406b833c24494a9d105e1d36f9650c39e02f9c7b2ec29e6a9425eb6725158dcaf68c96c252ac45346b3";
+const XCHACHA_BOX_HEX: &str =
+    "42effd8372a59a30478c0fbf3dcc6d3993378eb54040633bac081575d843b28e684eb322fdb02fefa54581949d";
+const SEALED_BOX_HEX: &str = "c5b410f8616614ba459af787028319ee46e08ba39d3663a3f89de8dc21c2fc3ea69f528a50e60bbbc14940e944eb41077ae4e397ddb0424e72cca94eabb6737f2512c9166ec896ea15a87e12f2";
+const ED25519_SEED_HEX: &str = "c8c9cacbcccdcecfd0d1d2d3d4d5d6d7d8d9dadbdcdddedfe0e1e2e3e4e5e6e7";
+const ED25519_X25519_PUBLIC_HEX: &str =
+    "1dc8a2732109c536ecfde497e86acb649ffffb2160aac0a4c6e960e12f79ed04";
+
+fn secret_key(value: &str) -> X25519SecretKey {
+    X25519SecretKey::from_bytes(&hex::decode(value).unwrap()).unwrap()
+}
+
+#[test]
+pub fn nacl_box_matches_libsodium() {
+    let alice = secret_key(ALICE_SECRET_HEX);
+    let bob = secret_key(BOB_SECRET_HEX);
+    let nonce = hex::decode(NONCE_HEX).unwrap();
+
+    for (cipher, expected) in [
+        (BoxCipher::XSalsa20Poly1305, BOX_HEX),
+        (BoxCipher::XChaCha20Poly1305, XCHACHA_BOX_HEX),
+    ] {
+        let ciphertext = box_encrypt(cipher, MESSAGE, &nonce, &bob.public_key(), &alice).unwrap();
+        assert_eq!(hex::encode(&ciphertext), expected);
+        assert_eq!(
+            box_decrypt(cipher, &ciphertext, &nonce, &alice.public_key(), &bob).unwrap(),
+            MESSAGE
+        );
+    }
+
+    let ciphertext = hex::decode(BOX_HEX).unwrap();
+    assert_eq!(
+        box_decrypt(
+            BoxCipher::XChaCha20Poly1305,
+            &ciphertext,
+            &nonce,
+            &alice.public_key(),
+            &bob
+        )
+        .unwrap_err(),
+        Error::NACL_DECRYPTION_FAILED
+    );
+    assert_eq!(
+        box_decrypt(
+            BoxCipher::XSalsa20Poly1305,
+            &ciphertext,
+            &nonce[..12],
+            &alice.public_key(),
+            &bob
+        )
+        .unwrap_err(),
+        Error::NACL_MALFORMED
+    );
+    assert_eq!(
+        box_encrypt(
+            BoxCipher::XSalsa20Poly1305,
+            MESSAGE,
+            &generate_nonce(),
+            &X25519PublicKey::from_bytes(&[0; 32]).unwrap(),
+            &alice
+        )
+        .unwrap_err(),
+        Error::KEY_AGREEMENT_FAILED
+    );
+}
+
+#[test]
+pub fn nacl_sealed_box() {
+    let bob = secret_key(BOB_SECRET_HEX);
+    assert_eq!(
+        unseal(&hex::decode(SEALED_BOX_HEX).unwrap(), &bob).unwrap(),
+        MESSAGE
+    );
+
+    let sealed = seal(MESSAGE, &bob.public_key()).unwrap();
+    assert_eq!(sealed.len(), MESSAGE.len() + SEALED_BOX_OVERHEAD);
+    assert_eq!(unseal(&sealed, &bob).unwrap(), MESSAGE);
+    assert_eq!(
+        unseal(&sealed, &X25519SecretKey::generate()).unwrap_err(),
+        Error::NACL_DECRYPTION_FAILED
+    );
+    assert!(unseal(&sealed[..40], &bob)
+        .unwrap_err()
+        .is_malformed_input());
+}
+
+#[test]
+pub fn nacl_ed25519_key_conversion() {
+    let signing_key =
+        EDDSASigningKey::from_bytes(&mut hex::decode(ED25519_SEED_HEX).unwrap()).unwrap();
+    let public_key = signing_key.verifying_key().to_x25519().unwrap();
+    assert_eq!(
+        hex::encode(public_key.to_bytes()),
+        ED25519_X25519_PUBLIC_HEX
+    );
+    assert_eq!(signing_key.to_x25519().unwrap().public_key(), public_key);
+
+    let sealed = seal(MESSAGE, &public_key).unwrap();
+    assert_eq!(
+        unseal(&sealed, &signing_key.to_x25519().unwrap()).unwrap(),
+        MESSAGE
+    );
+}
@@ -0,0 +1,69 @@
+use std::{collections::HashSet, time::Duration};
+
+use chrono::Utc;
+use did_crypto::{
+    didcomm::Message,
+    errors::Error,
+    nonce::{self, NonceGenerator},
+};
+use rand::{rngs::StdRng, SeedableRng};
+use serde_json::json;
+
+fn is_url_safe(value: &str) -> bool {
+    value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || "-_.".contains(c))
+}
+
+#[test]
+pub fn nonce_generation() {
+    let nonces: HashSet<String> = (0..100).map(|_| nonce::generate()).collect();
+    assert_eq!(nonces.len(), 100);
+    assert!(nonces.iter().all(|val| val.len() == 22 && is_url_safe(val)));
+
+    let nonce = NonceGenerator::new().entropy(32).generate().unwrap();
+    assert_eq!(nonce.len(), 43);
+    assert_eq!(nonce::expires_at(&nonce).unwrap(), None);
+    assert!(!nonce::is_expired(&nonce).unwrap());
+
+    assert_eq!(
+        NonceGenerator::new().entropy(4).generate().err(),
+        Some(Error::NONCE_ENTROPY_TOO_LOW)
+    );
+    assert_eq!(
+        nonce::generate_with_rng(&mut StdRng::seed_from_u64(7)),
+        nonce::generate_with_rng(&mut StdRng::seed_from_u64(7))
+    );
+}
+
+#[test]
+pub fn nonce_expiry() {
+    let nonce = NonceGenerator::new()
+        .expires_in(Duration::from_secs(60))
+        .generate()
+        .unwrap();
+    assert!(is_url_safe(&nonce));
+    let expires_at = nonce::expires_at(&nonce).unwrap().unwrap();
+    assert!(expires_at > Utc::now() + chrono::Duration::seconds(55));
+    assert!(!nonce::is_expired(&nonce).unwrap());
+
+    let nonce = NonceGenerator::new()
+        .expires_in(Duration::ZERO)
+        .generate()
+        .unwrap();
+    assert!(nonce::is_expired(&nonce).unwrap());
+
+    assert_eq!(
+        nonce::expires_at("soon.abc").err(),
+        Some(Error::NONCE_MALFORMED)
+    );
+}
+
+#[test]
+pub fn didcomm_thread_ids_are_random() {
+    let first = Message::new("1", "https://example.org/ping", json!({})).start_thread();
+    let second = Message::new("2", "https://example.org/ping", json!({})).start_thread();
+
+    assert!(first.thid.is_some());
+    assert_ne!(first.thid, second.thid);
+}
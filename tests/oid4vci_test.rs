@@ -0,0 +1,144 @@
+use std::time::Duration;
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    errors::Error,
+    oid4vci::{CredentialProof, ProofClaims, ProofValidation, OID4VCI_PROOF_TYP},
+    resolver::{DidResolver, AUTHENTICATION},
+};
+use serde_json::{json, Value};
+
+const CREDENTIAL_ISSUER: &str = "https://credential-issuer.example.com";
+const CLIENT_ID: &str = "s6BhdRkqt3";
+const C_NONCE: &str = "tZignsnFbp";
+const HOLDER_DID: &str = "did:example:holder";
+
+struct HolderResolver(AnyVerifyingKey);
+
+impl DidResolver for HolderResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        if did != HOLDER_DID {
+            return Err(Error::DID_RESOLUTION_ERROR);
+        }
+
+        Ok(json!({
+            "id": did,
+            "verificationMethod": [{
+                "id": "#key-1",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": self.0.to_jwk().unwrap(),
+            }],
+            AUTHENTICATION: ["#key-1"],
+        }))
+    }
+}
+
+#[test]
+pub fn oid4vci_proof_round_trip() {
+    for alg in [Algorithm::ES256, Algorithm::EdDSA] {
+        let key = AnySigningKey::generate(alg).unwrap();
+        let claims = ProofClaims::new(CREDENTIAL_ISSUER)
+            .with_client_id(CLIENT_ID)
+            .with_nonce(C_NONCE);
+        let proof = CredentialProof::sign(claims, &key).unwrap();
+
+        let header = proof.to_token().split('.').next().unwrap();
+        let header: Value =
+            serde_json::from_slice(&URL_SAFE_NO_PAD.decode(header).unwrap()).unwrap();
+        assert_eq!(header["typ"], OID4VCI_PROOF_TYP);
+        assert!(header["jwk"].get("d").is_none());
+        assert!(header.get("kid").is_none());
+
+        let parsed = CredentialProof::from_token(proof.to_token()).unwrap();
+        assert_eq!(parsed.claims, proof.claims);
+        let validation = ProofValidation::new(CREDENTIAL_ISSUER)
+            .nonce(C_NONCE)
+            .client_id(CLIENT_ID);
+        assert!(parsed.verify(&validation).unwrap());
+
+        assert!(!parsed
+            .verify(&ProofValidation::new("https://other.example.com"))
+            .unwrap());
+        assert!(!parsed
+            .verify(&ProofValidation::new(CREDENTIAL_ISSUER).nonce("other"))
+            .unwrap());
+        assert!(!parsed
+            .verify(&ProofValidation::new(CREDENTIAL_ISSUER).client_id("other"))
+            .unwrap());
+    }
+}
+
+#[test]
+pub fn oid4vci_proof_with_kid() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let resolver = HolderResolver(key.verifying_key());
+    let kid = format!("{}#key-1", HOLDER_DID);
+    let claims = ProofClaims::new(CREDENTIAL_ISSUER).with_nonce(C_NONCE);
+    let proof = CredentialProof::sign_with_kid(claims.clone(), &kid, &key).unwrap();
+
+    let parsed = CredentialProof::from_token(proof.to_token()).unwrap();
+    assert_eq!(parsed.kid.as_deref(), Some(kid.as_str()));
+    assert!(parsed.jwk.is_none());
+    let validation = ProofValidation::new(CREDENTIAL_ISSUER).nonce(C_NONCE);
+    assert!(parsed.verify_with_resolver(&validation, &resolver).unwrap());
+    assert_eq!(
+        parsed.verify(&validation).err(),
+        Some(Error::OID4VCI_PROOF_INVALID_KEY)
+    );
+
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let forged = CredentialProof::sign_with_kid(claims, &kid, &other).unwrap();
+    assert!(!forged.verify_with_resolver(&validation, &resolver).unwrap());
+}
+
+#[test]
+pub fn oid4vci_proof_rejects_stale_and_invalid_proofs() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let claims = ProofClaims {
+        iat: Utc::now().timestamp() - 600,
+        ..ProofClaims::new(CREDENTIAL_ISSUER)
+    };
+    let proof = CredentialProof::sign(claims, &key).unwrap();
+    assert!(!proof
+        .verify(&ProofValidation::new(CREDENTIAL_ISSUER))
+        .unwrap());
+    assert!(proof
+        .verify(&ProofValidation::new(CREDENTIAL_ISSUER).max_age(Duration::from_secs(900)))
+        .unwrap());
+
+    let parts: Vec<&str> = proof.to_token().split('.').collect();
+    let header =
+        URL_SAFE_NO_PAD.encode(r#"{"typ":"JWT","alg":"ES256","kid":"did:example:holder#key-1"}"#);
+    assert_eq!(
+        CredentialProof::from_token(&format!("{}.{}.sig", header, parts[1])).err(),
+        Some(Error::OID4VCI_PROOF_INVALID_TYP)
+    );
+    let jwk = key.to_jwk().unwrap().to_json().unwrap();
+    let header = URL_SAFE_NO_PAD.encode(format!(
+        r#"{{"typ":"openid4vci-proof+jwt","alg":"ES256","jwk":{}}}"#,
+        jwk
+    ));
+    assert_eq!(
+        CredentialProof::from_token(&format!("{}.{}.sig", header, parts[1])).err(),
+        Some(Error::OID4VCI_PROOF_INVALID_KEY)
+    );
+    let header = URL_SAFE_NO_PAD.encode(r#"{"typ":"openid4vci-proof+jwt","alg":"ES256"}"#);
+    assert_eq!(
+        CredentialProof::from_token(&format!("{}.{}.sig", header, parts[1])).err(),
+        Some(Error::OID4VCI_PROOF_INVALID_KEY)
+    );
+    assert_eq!(
+        CredentialProof::from_token("not-a-proof").err(),
+        Some(Error::OID4VCI_PROOF_MALFORMED)
+    );
+
+    let hmac = AnySigningKey::generate(Algorithm::HS256).unwrap();
+    assert_eq!(
+        CredentialProof::sign_with_kid(ProofClaims::new(CREDENTIAL_ISSUER), "kid", &hmac).err(),
+        Some(Error::OID4VCI_PROOF_INVALID_KEY)
+    );
+}
@@ -0,0 +1,131 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    errors::Error,
+    oid4vp::{AuthorizationResponse, PresentationRequest, ResponseJwt, VpToken},
+    resolver::{DidResolver, AUTHENTICATION},
+};
+use serde_json::{json, Value};
+
+const CLIENT_ID: &str = "https://verifier.example.org/cb";
+const NONCE: &str = "n-0S6_WzA2Mj";
+const STATE: &str = "eyJhb...6-sVA";
+const HOLDER_DID: &str = "did:example:holder";
+
+struct HolderResolver(AnyVerifyingKey);
+
+impl DidResolver for HolderResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        if did != HOLDER_DID {
+            return Err(Error::DID_RESOLUTION_ERROR);
+        }
+
+        Ok(json!({
+            "id": did,
+            "verificationMethod": [{
+                "id": "#key-1",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": self.0.to_jwk().unwrap(),
+            }],
+            AUTHENTICATION: ["#key-1"],
+        }))
+    }
+}
+
+fn presentation() -> Value {
+    json!({
+        "@context": ["https://www.w3.org/2018/credentials/v1"],
+        "type": ["VerifiablePresentation"],
+        "verifiableCredential": ["eyJhbGciOiJFUzI1NiJ9.e30.c2ln"],
+    })
+}
+
+#[test]
+pub fn oid4vp_vp_token_round_trip() {
+    for alg in [Algorithm::ES256, Algorithm::EdDSA] {
+        let key = AnySigningKey::generate(alg).unwrap();
+        let resolver = HolderResolver(key.verifying_key());
+        let kid = format!("{}#key-1", HOLDER_DID);
+        let request = PresentationRequest::new(CLIENT_ID, NONCE);
+
+        let vp_token = VpToken::sign(presentation(), &request, &kid, &key).unwrap();
+        assert_eq!(vp_token.claims.iss, HOLDER_DID);
+        let parsed = VpToken::from_token(vp_token.to_token()).unwrap();
+        assert_eq!(parsed.claims, vp_token.claims);
+        assert_eq!(parsed.kid, kid);
+        assert!(parsed.verify(&request, &resolver).unwrap());
+
+        assert!(!parsed
+            .verify(&PresentationRequest::new(CLIENT_ID, "other"), &resolver)
+            .unwrap());
+        assert!(!parsed
+            .verify(
+                &PresentationRequest::new("https://other.example.org", NONCE),
+                &resolver
+            )
+            .unwrap());
+
+        let other = AnySigningKey::generate(alg).unwrap();
+        let forged = VpToken::sign(presentation(), &request, &kid, &other).unwrap();
+        assert!(!forged.verify(&request, &resolver).unwrap());
+    }
+}
+
+#[test]
+pub fn oid4vp_jarm_response() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let resolver = HolderResolver(key.verifying_key());
+    let kid = format!("{}#key-1", HOLDER_DID);
+    let request = PresentationRequest::new(CLIENT_ID, NONCE).with_state(STATE);
+
+    let vp_token = VpToken::sign(presentation(), &request, &kid, &key).unwrap();
+    let response = AuthorizationResponse::new(Value::from(vp_token.to_token()), &request)
+        .with_presentation_submission(json!({"id": "submission-1", "definition_id": "pd-1"}));
+    let jarm = ResponseJwt::sign(response, &request, &kid, &key).unwrap();
+
+    let payload = jarm.to_token().split('.').nth(1).unwrap();
+    let payload: Value = serde_json::from_slice(&URL_SAFE_NO_PAD.decode(payload).unwrap()).unwrap();
+    assert_eq!(payload["aud"], CLIENT_ID);
+    assert_eq!(payload["state"], STATE);
+    assert_eq!(payload["vp_token"], vp_token.to_token());
+
+    let parsed = ResponseJwt::from_token(jarm.to_token()).unwrap();
+    assert_eq!(parsed.claims, jarm.claims);
+    assert!(parsed.verify(&request, &resolver).unwrap());
+    assert!(parsed.verify_presentations(&request, &resolver).unwrap());
+    assert_eq!(parsed.vp_tokens().unwrap()[0].claims, vp_token.claims);
+
+    let replayed = PresentationRequest::new(CLIENT_ID, "other").with_state(STATE);
+    assert!(parsed.verify(&replayed, &resolver).unwrap());
+    assert!(!parsed.verify_presentations(&replayed, &resolver).unwrap());
+    let other_state = PresentationRequest::new(CLIENT_ID, NONCE).with_state("other");
+    assert!(!parsed.verify(&other_state, &resolver).unwrap());
+}
+
+#[test]
+pub fn oid4vp_rejects_invalid_tokens() {
+    let request = PresentationRequest::new(CLIENT_ID, NONCE);
+    let hmac = AnySigningKey::generate(Algorithm::HS256).unwrap();
+    assert_eq!(
+        VpToken::sign(presentation(), &request, HOLDER_DID, &hmac).err(),
+        Some(Error::OID4VP_INVALID_KEY)
+    );
+
+    let header = URL_SAFE_NO_PAD.encode(r#"{"typ":"JWT","alg":"ES256","kid":""}"#);
+    assert_eq!(
+        VpToken::from_token(&format!("{}.e30.sig", header)).err(),
+        Some(Error::OID4VP_INVALID_KEY)
+    );
+    let header =
+        URL_SAFE_NO_PAD.encode(r#"{"typ":"JWT","alg":"ES256","kid":"did:example:holder"}"#);
+    assert_eq!(
+        ResponseJwt::from_token(&format!("{}.e30.sig", header)).err(),
+        Some(Error::OID4VP_MALFORMED)
+    );
+    assert!(VpToken::from_token("not-a-token")
+        .err()
+        .unwrap()
+        .is_malformed_input());
+}
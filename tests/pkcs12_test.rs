@@ -0,0 +1,109 @@
+#![cfg(feature = "pkcs12")]
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{ecdsa::_256::P256VerifyingKey, pkcs12::Pkcs12Bundle, SignFromKey, VerifyFromKey},
+    errors::Error,
+};
+
+const PASSWORD: &str = "changeit";
+const PUBLIC_KEY_256_HEX: &str =
+    "04115b3fa39fae41b4e32f7721ca72f8c1781483647dabd514f08e66128bd47fce9067b90e0488c9c2a9f30f5a266a07841d6c077413ba07e74569b99d4fd3cec6";
+const PRIVATE_KEY_256_HEX: &str =
+    "7af6732f581d005afcf216f6385ff6371029242cc60840dd7d2a7a5503b7d21c";
+const P12_AES: &str = "MIIEaQIBAzCCBB8GCSqGSIb3DQEHAaCCBBAEggQMMIIECDCCApoGCSqGSIb3DQEHBqCCAoswggKH
+AgEAMIICgAYJKoZIhvcNAQcBMF8GCSqGSIb3DQEFDTBSMDEGCSqGSIb3DQEFDDAkBBADmjL6Y1e0
+UmmB2IIIFfhOAgIIADAMBggqhkiG9w0CCQUAMB0GCWCGSAFlAwQBKgQQFfOXOEdDKPVe7Bk6bZAc
+AICCAhDHwzNnrdZ7/pORQGhjK02nk/nJVklAx57U98QDSNTNoBjTYTEPPV3y6x/MzvIxTXNYMDkQ
+iAoQdAJsKuG5jjUNu8DHb+gfJ5MahdVDCGplnTuTr6ZcbCHSHCjJiKrOZY/BSZh53yT/AE0uaU8f
+T29a4FNtF4JvOppoyR1WgihhZNqw0atcg8ifcg/AS2HoXCmMss4AsDAvIjxfqXW0ydvVLHCamhMg
+C3VqXw6WO8Ca/dLOMS6JasBQ+nuBbtdV2TnhnKBot3jMWMHspNJRWW9W5tW6SRLLhcvWqcR6Jmss
+sI5CajfkbzUGcZqLuAJNsTTe81mzC7/NBmDwG6/d60Hj8PXvcbxatobeDloD3qIW19FwA3wOjmYl
+zlwYez13dIoyNbyy6f0fW7zIRlM/MXLkCPHOdtWhvU/ZZ8tDrCzCySlzgJ0dRckD972BjqqXn2TC
+IYEC+H1CYpYzQjHtaSx6dg3Uy/RbQRtm88iAB4Ntf0DWSainxxRp1+HHdaJgQhxOh/JJKjm0q43E
+b7AfOhHeTEql/FfpjBGAdcTffTT3bQoIxEsfV1Mbi6EYxRYNlZ66p+efDx59KmdFjrKNoD9oNnvf
+1ESbPeQBnfRmh/+bbDIE3riIw2o78uQoxfCMjYZoeBPC+V1QN7siHQi77Z8xsbuE2C1Pn9R49dhb
+buhOVDI7dExctB7uFoR+IkQFvVMwggFmBgkqhkiG9w0BBwGgggFXBIIBUzCCAU8wggFLBgsqhkiG
+9w0BDAoBAqCB9zCB9DBfBgkqhkiG9w0BBQ0wUjAxBgkqhkiG9w0BBQwwJAQQ5vzkLBfT7oaisVND
+0dFeQwICCAAwDAYIKoZIhvcNAgkFADAdBglghkgBZQMEASoEEGjC6diNOCo5Tp3vL3JamFMEgZBW
+PVVzzE9S71fCpv5Uhxbl1tm3NhUNp1XL3HVa7H6cUNQ6dpUkNskCvs4mhlr7k+PF64ImZ1md+pce
+k2m+3AP/zOYXUZUOUjdrsgImqoamTTphmyK3+q7tXY1e3GolHyWTzDwjexZ50YgxHgqriWzPBHLD
+azdQG9oatjlgevV1AA7tTWC99yHti3ir8ZD+ApIxQjAbBgkqhkiG9w0BCRQxDh4MAGkAcwBzAHUA
+ZQByMCMGCSqGSIb3DQEJFTEWBBTQv+uPN32JyHH3VzMDY6QfHKCe3TBBMDEwDQYJYIZIAWUDBAIB
+BQAEII1kCNLUreMDfqam33BsJwicSrXovI57Bv5yXCgvoLmyBAhGye/2tSrZeQICCAA=";
+const P12_LEGACY_3DES: &str =
+    "MIID0wIBAzCCA5kGCSqGSIb3DQEHAaCCA4oEggOGMIIDgjCCAlcGCSqGSIb3DQEHBqCCAkgwggJE
+AgEAMIICPQYJKoZIhvcNAQcBMBwGCiqGSIb3DQEMAQMwDgQIQsY1iDMcL68CAggAgIICEOVE945Y
+Tr2giYOw6X6hCAXPSj/fluvpBs/He7Wik4Vc6N38fvWEG2ZRJ34BEVEoKbO8MoaDb55DLb3Yv9rL
+mrgqaIAbPKcSpSDda+gbyJiR9bCFyyz0LEzdHzd/xIhZa3Mvl3g40xfYuLbW9llF03379uHmK6AK
+t3ACp7MLNjdb7ResqU/tDEKxU8tiyDWX9AA6wh9yEToubhVgJ+EyrmNMRgU1yzW4s10PH3oBtA1K
+QAprJSOdOsR9ggniRe9f8dhxojyT/YyMSD9itn5i4T8Qyesn+WX9mm//87XDvHHKPbKp+cBKegaS
+hkBD8RsQRiyOVnhUs4TPZjJSwOfWMbY4PmDw52uO/7cdGoMkpHXIgFniUWFWUP++0bLrCqtDczZO
+03T28MvmZXx8pe8JTY8fIc20hpvvKT6t/aChBLB0gzs4d7l0ZCY2o+rF1Ko2MMKkcNXeWlms146S
+onlPWwF9ElzgLDU2g6eMc2EXwcoYeruqwVtxTQYigoD10ec9oX5D+9nn4Ipw6p5kyU5bhkpszWpA
+H2QQnKpX3n6doh+o965GJCwLG5DKc60sg63Kw70MLpO3H/HMus7jKWZh0h/FEIMueKh6OjQ9/K5E
+SNjagfLaJorEKzpQ0y+GZTaPWR1lEG7QWIjraBB2EpP5fB9oB0Ocb+vhJa9D8gN0ulqULYNFzKml
+CxI9DQc6kLc+ZDCCASMGCSqGSIb3DQEHAaCCARQEggEQMIIBDDCCAQgGCyqGSIb3DQEMCgECoIG0
+MIGxMBwGCiqGSIb3DQEMAQMwDgQI0C/vdWBO9xACAggABIGQd2PWFiMLAjyQ92/yzd2nFJyPeMbu
+SQd6rXMIN74nZa6hWXhK74IJLj4+SPD7Tn5K1mHytjjy3P0wLU5BNRpFw4Ck+HK/Jd9+cYhqcpd4
+40nerZ3/t/BS0ZLKoAK846lDydeCMIJr1UQe//X5EpQMiDbQVjqRkGwqNyrzHMFEjWerHoOve/CB
+6Nmj/ZygrVyXMUIwGwYJKoZIhvcNAQkUMQ4eDABpAHMAcwB1AGUAcjAjBgkqhkiG9w0BCRUxFgQU
+0L/rjzd9ichx91czA2OkHxygnt0wMTAhMAkGBSsOAwIaBQAEFKNBnEqu0GwhxdFGsPkFQ4G9sPuT
+BAgaARCertgLkAICCAA=";
+
+fn decode(bundle: &str) -> Vec<u8> {
+    STANDARD.decode(bundle.replace('\n', "")).unwrap()
+}
+
+#[test]
+pub fn pkcs12_import() {
+    for bundle in [P12_AES, P12_LEGACY_3DES] {
+        let bundle = Pkcs12Bundle::from_der(&decode(bundle), PASSWORD).unwrap();
+        assert_eq!(bundle.keys().len(), 1);
+        assert!(bundle.certificates().is_empty());
+
+        let entry = bundle.key("issuer").unwrap();
+        assert_eq!(entry.key.algorithm(), Algorithm::ES256);
+        assert_eq!(
+            hex::encode(entry.key.to_bytes().unwrap()),
+            PRIVATE_KEY_256_HEX
+        );
+        assert_eq!(entry.chain.len(), 1);
+        assert_eq!(entry.chain[0].subject, "CN=did:example:issuer");
+        assert_eq!(entry.chain[0].issuer, entry.chain[0].subject);
+
+        let signature = entry
+            .key
+            .sign_bytes("did:example:issuer".as_bytes(), Algorithm::ES256)
+            .unwrap();
+        assert!(
+            P256VerifyingKey::from_bytes(&hex::decode(PUBLIC_KEY_256_HEX).unwrap())
+                .unwrap()
+                .verify(
+                    String::from("did:example:issuer"),
+                    signature,
+                    Algorithm::ES256
+                )
+                .unwrap()
+        );
+    }
+}
+
+#[test]
+pub fn pkcs12_wrong_password() {
+    assert_eq!(
+        Pkcs12Bundle::from_der(&decode(P12_AES), "wrong")
+            .err()
+            .unwrap()
+            .to_string(),
+        Error::PKCS12_WRONG_PASSWORD.to_string()
+    );
+    assert_eq!(
+        Pkcs12Bundle::from_der(&[0x30, 0x00], PASSWORD)
+            .err()
+            .unwrap()
+            .to_string(),
+        Error::PKCS12_ERROR.to_string()
+    );
+}
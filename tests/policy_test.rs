@@ -0,0 +1,182 @@
+#![cfg(all(feature = "rsa", feature = "es256k", feature = "eddsa"))]
+
+use chrono::Utc;
+use did_crypto::{
+    algorithms::{Algorithm, AlgorithmPolicy},
+    crypto::{
+        any::AnySigningKey,
+        policy::{Curve, KeyPolicy, PolicyResolver},
+        rsa::RsaSigningKey,
+    },
+    errors::Error,
+    jwk::{Jwk, Jwks},
+    jwt::{Header, Payload, JWT},
+    keystore::VerifyingKeyResolver,
+    resolver::{resolve_verifying_keys, DidResolver, AUTHENTICATION},
+};
+use rand::rngs::OsRng;
+use rsa::pkcs8::EncodePrivateKey;
+use serde_json::{json, Value};
+
+fn weak_rsa_key() -> AnySigningKey {
+    let key = rsa::RsaPrivateKey::new(&mut OsRng, 1024).unwrap();
+    AnySigningKey::from_pkcs8_der(key.to_pkcs8_der().unwrap().as_bytes()).unwrap()
+}
+
+fn jwk(key: &AnySigningKey, kid: &str) -> Jwk {
+    Jwk {
+        kid: Some(String::from(kid)),
+        ..key.verifying_key().to_jwk().unwrap()
+    }
+}
+
+fn sign(key: &AnySigningKey, kid: &str) -> JWT {
+    let mut jwt = JWT {
+        header: Header::new(String::from(kid), key.algorithm()),
+        payload: Payload(json!({ "exp": Utc::now().timestamp() + 10 })),
+        signature: None,
+    };
+    jwt.sign(key).unwrap();
+    jwt
+}
+
+struct StaticResolver(Value);
+
+impl DidResolver for StaticResolver {
+    fn resolve(&self, _did: &str) -> Result<Value, Error> {
+        Ok(self.0.clone())
+    }
+}
+
+#[test]
+pub fn key_policy_checks_keys() {
+    let weak = weak_rsa_key();
+    let strong = AnySigningKey::Rsa(RsaSigningKey::generate(2048).unwrap(), Algorithm::RS256);
+    let p256 = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let p256k = AnySigningKey::generate(Algorithm::ES256K).unwrap();
+    let ed25519 = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+
+    let policy = KeyPolicy::default();
+    assert_eq!(
+        policy.check(&weak.verifying_key()).unwrap_err(),
+        Error::KEY_POLICY_VIOLATION
+    );
+    assert!(policy.check(&strong.verifying_key()).is_ok());
+    assert!(KeyPolicy::permissive().check(&weak.verifying_key()).is_ok());
+    assert!(KeyPolicy::new()
+        .min_rsa_bits(3072)
+        .check(&strong.verifying_key())
+        .is_err());
+
+    let policy = KeyPolicy::new().ban_curve(Curve::Secp256k1);
+    assert!(policy.is_curve_banned(Curve::Secp256k1));
+    assert!(policy.check(&p256.verifying_key()).is_ok());
+    assert_eq!(
+        policy.check(&p256k.verifying_key()).unwrap_err(),
+        Error::KEY_POLICY_VIOLATION
+    );
+    assert!(policy
+        .allow_curve(Curve::Secp256k1)
+        .check(&p256k.verifying_key())
+        .is_ok());
+
+    let policy = KeyPolicy::new().algorithms(AlgorithmPolicy::new(&[Algorithm::ES256]));
+    assert!(policy.check(&p256.verifying_key()).is_ok());
+    assert!(policy.check(&ed25519.verifying_key()).is_err());
+
+    assert!(policy.check_jwk(&jwk(&p256, "p256")).is_ok());
+    assert_eq!(
+        policy.check_jwk(&jwk(&ed25519, "ed")).unwrap_err(),
+        Error::KEY_POLICY_VIOLATION
+    );
+
+    assert_eq!(Curve::from_name("P-384"), Some(Curve::P384));
+    assert_eq!(Curve::from_name("brainpoolP256r1"), None);
+    assert_eq!(Curve::of(&p256k.verifying_key()), Some(Curve::Secp256k1));
+}
+
+#[test]
+pub fn key_policy_on_jwks() {
+    let p256k = AnySigningKey::generate(Algorithm::ES256K).unwrap();
+    let jwks = Jwks {
+        keys: vec![jwk(&p256k, "k1")],
+    };
+    let jwt = sign(&p256k, "k1");
+    let policy = KeyPolicy::new().ban_curve(Curve::Secp256k1);
+
+    assert!(jwt.validate_with_jwks(&jwks).unwrap());
+    assert_eq!(
+        jwt.validate_with_jwks_policy(&jwks, &policy).unwrap_err(),
+        Error::KEY_POLICY_VIOLATION
+    );
+    assert_eq!(
+        jwks.verifying_keys_with_policy(None, Algorithm::ES256K, &policy)
+            .unwrap_err(),
+        Error::KEY_POLICY_VIOLATION
+    );
+
+    let resolver = PolicyResolver::new(jwks, policy);
+    assert_eq!(
+        resolver.resolve("k1").unwrap_err(),
+        Error::KEY_POLICY_VIOLATION
+    );
+    assert!(
+        PolicyResolver::new(resolver.inner().clone(), KeyPolicy::default())
+            .resolve("k1")
+            .is_ok()
+    );
+    assert_eq!(
+        jwt.validate_with_store(&resolver).unwrap_err(),
+        Error::KEY_POLICY_VIOLATION
+    );
+
+    let p256 = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let jwks = Jwks {
+        keys: vec![jwk(&p256, "p256")],
+    };
+    assert!(sign(&p256, "p256")
+        .validate_with_jwks_policy(&jwks, &policy)
+        .unwrap());
+}
+
+#[test]
+pub fn key_policy_on_did_documents() {
+    let p256k = AnySigningKey::generate(Algorithm::ES256K).unwrap();
+    let p256 = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let did = "did:example:123";
+    let resolver = StaticResolver(json!({
+        "id": did,
+        "verificationMethod": [
+            {
+                "id": "#k1",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": p256k.verifying_key().to_jwk().unwrap(),
+            },
+            {
+                "id": "#p256",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": p256.verifying_key().to_jwk().unwrap(),
+            },
+        ],
+        "authentication": ["#k1", "#p256"],
+    }));
+
+    let policy = KeyPolicy::new().ban_curve(Curve::Secp256k1);
+    let keys = resolve_verifying_keys(&resolver, did, AUTHENTICATION, &policy).unwrap();
+    assert_eq!(keys.len(), 1);
+    assert_eq!(keys[0].algorithm(), Algorithm::ES256);
+
+    assert_eq!(
+        resolve_verifying_keys(&resolver, "did:example:123#k1", AUTHENTICATION, &policy)
+            .unwrap_err(),
+        Error::KEY_POLICY_VIOLATION
+    );
+    assert_eq!(
+        resolve_verifying_keys(&resolver, did, AUTHENTICATION, &KeyPolicy::default())
+            .unwrap()
+            .len(),
+        2
+    );
+}
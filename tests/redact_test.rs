@@ -0,0 +1,125 @@
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::AnySigningKey,
+    jwt::{Header, Payload},
+    redact::Redactor,
+    sd_jwt::SdJwt,
+    signer::sign,
+};
+use serde_json::{json, Value};
+
+const EMAIL: &str = "alice@example.com";
+
+fn compact(header: &Value, payload: &Value, key: &AnySigningKey) -> String {
+    let content = format!(
+        "{}.{}",
+        base64_url::encode(&header.to_string()),
+        base64_url::encode(&payload.to_string())
+    );
+    let signature = sign(&content, key, Algorithm::ES256).unwrap();
+    format!("{}.{}", content, signature)
+}
+
+#[test]
+pub fn redact_jwt_claims() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let token = compact(
+        &json!({"alg": "ES256", "kid": "did:example:issuer#key-1"}),
+        &json!({
+            "iss": "did:example:issuer",
+            "sub": "did:example:alice",
+            "email": EMAIL,
+            "exp": 1767225600,
+            "vc": {"credentialSubject": {"id": "did:example:alice", "degree": {"name": "BSc"}}},
+        }),
+        &key,
+    );
+
+    let redactor = Redactor::new();
+    let redacted = redactor.redact_token(&token);
+    assert_eq!(redacted["header"]["kid"], "did:example:issuer#key-1");
+    assert_eq!(redacted["payload"]["iss"], "did:example:issuer");
+    assert_eq!(redacted["payload"]["exp"], 1767225600);
+    assert_eq!(
+        redacted["payload"]["email"],
+        redactor.digest(Value::from(EMAIL).to_string())
+    );
+    assert!(redacted["payload"]["sub"]
+        .as_str()
+        .unwrap()
+        .starts_with("sha256:"));
+    assert!(
+        redacted["payload"]["vc"]["credentialSubject"]["degree"]["name"]
+            .as_str()
+            .unwrap()
+            .starts_with("sha256:")
+    );
+    assert!(redacted["signature"]
+        .as_str()
+        .unwrap()
+        .starts_with("sha256:"));
+
+    let rendered = redactor.display(&token).to_string();
+    assert!(!rendered.contains(EMAIL));
+    assert!(!rendered.contains("did:example:alice"));
+    assert!(!rendered.contains(token.rsplit('.').next().unwrap()));
+
+    let custom = Redactor::new()
+        .preserve_claim("sub")
+        .redact_claim("iss")
+        .hmac_key(b"log-pepper");
+    let redacted = custom.redact_token(&token);
+    assert_eq!(redacted["payload"]["sub"], "did:example:alice");
+    assert!(redacted["payload"]["iss"]
+        .as_str()
+        .unwrap()
+        .starts_with("hmac:"));
+    assert_ne!(
+        redacted["payload"]["email"],
+        redactor.digest("\"alice@example.com\"")
+    );
+    assert_eq!(redacted, custom.redact_token(&token));
+}
+
+#[test]
+pub fn redact_sd_jwt_and_documents() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let sd_jwt = SdJwt::issue(
+        Header::new(String::from("key-1"), Algorithm::ES256),
+        Payload(json!({"iss": "did:example:issuer", "email": EMAIL, "age_over_18": true})),
+        &["email", "age_over_18"],
+        &key,
+    )
+    .unwrap();
+
+    let redactor = Redactor::new();
+    let redacted = redactor.redact_token(&sd_jwt.to_token());
+    assert_eq!(redacted["jwt"]["payload"]["iss"], "did:example:issuer");
+    let disclosures = redacted["disclosures"].as_array().unwrap();
+    assert_eq!(disclosures.len(), 2);
+    for disclosure in &sd_jwt.disclosures {
+        let entry = disclosures
+            .iter()
+            .find(|val| val["digest"] == disclosure.digest())
+            .unwrap();
+        assert_eq!(entry["claim"], disclosure.name().unwrap());
+    }
+    assert!(!redacted.to_string().contains(EMAIL));
+    assert!(!redacted
+        .to_string()
+        .contains(&sd_jwt.disclosures[0].salt().to_string()));
+
+    let document = json!({
+        "type": ["VerifiableCredential"],
+        "credentialSubject": {"id": "did:example:alice", "emails": [EMAIL, null]},
+        "proof": {"type": "DataIntegrityProof", "proofValue": "z58DAdFfa9"},
+    });
+    let redacted = redactor.redact_token(&document.to_string());
+    assert_eq!(redacted["type"], document["type"]);
+    assert_eq!(redacted["proof"], document["proof"]);
+    assert_eq!(redacted["credentialSubject"]["emails"][1], Value::Null);
+    assert!(!redacted.to_string().contains(EMAIL));
+
+    let opaque = redactor.redact_token("not-a-token");
+    assert_eq!(opaque, json!({"digest": redactor.digest("not-a-token")}));
+}
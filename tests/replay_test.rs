@@ -0,0 +1,152 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::AnySigningKey,
+    dpop::{DpopClaims, DpopProof, DpopValidation},
+    errors::Error,
+    jwt::{Header, Payload, JWT},
+    nonce,
+    replay::{MemoryNonceStore, NonceStore, ReplayGuard},
+    sd_jwt::SdJwt,
+};
+use serde_json::json;
+
+fn signed_jwt(key: &AnySigningKey, payload: serde_json::Value) -> JWT {
+    let mut jwt = JWT {
+        header: Header::new(String::new(), Algorithm::ES256),
+        payload: Payload(payload),
+        signature: None,
+    };
+    jwt.sign(key).unwrap();
+    JWT::from_token(&jwt.to_token().unwrap()).unwrap()
+}
+
+#[test]
+pub fn replay_guard_rejects_reused_values() {
+    let guard = ReplayGuard::new();
+    let value = nonce::generate();
+
+    guard.check(&value).unwrap();
+    assert_eq!(guard.check(&value).err(), Some(Error::REPLAY_DETECTED));
+    guard.check(&nonce::generate()).unwrap();
+    assert_eq!(guard.store().len(), 2);
+
+    guard.check_until("expired", Utc::now()).unwrap();
+    guard.check("expired").unwrap();
+    guard.store().purge_expired();
+    assert_eq!(guard.store().len(), 3);
+}
+
+#[test]
+pub fn replay_guard_validates_jwt_once() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let guard = ReplayGuard::new();
+    let exp = Utc::now().timestamp() + 60;
+
+    let jwt = signed_jwt(&key, json!({ "jti": nonce::generate(), "exp": exp }));
+    assert!(guard.validate_jwt(&jwt, key.verifying_key()).unwrap());
+    assert_eq!(
+        guard.validate_jwt(&jwt, key.verifying_key()).err(),
+        Some(Error::REPLAY_DETECTED)
+    );
+
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let jwt = signed_jwt(&key, json!({ "jti": nonce::generate(), "exp": exp }));
+    assert!(!guard.validate_jwt(&jwt, other.verifying_key()).unwrap());
+    assert!(guard.validate_jwt(&jwt, key.verifying_key()).unwrap());
+
+    let jwt = signed_jwt(&key, json!({ "exp": exp }));
+    assert_eq!(
+        guard.validate_jwt(&jwt, key.verifying_key()).err(),
+        Some(Error::JWT_MISSING_JTI)
+    );
+}
+
+#[test]
+pub fn replay_guard_verifies_dpop_and_key_binding_once() {
+    let key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let guard = ReplayGuard::new().window(Duration::from_secs(60));
+
+    let proof = DpopProof::sign(
+        DpopClaims::new("POST", "https://server.example.com/token"),
+        &key,
+    )
+    .unwrap();
+    let validation = DpopValidation::new("POST", "https://server.example.com/token");
+    assert!(guard.verify_dpop(&proof, &validation).unwrap());
+    assert_eq!(
+        guard.verify_dpop(&proof, &validation).err(),
+        Some(Error::REPLAY_DETECTED)
+    );
+
+    let issuer = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let mut presentation = SdJwt::issue(
+        Header::new(String::new(), Algorithm::ES256),
+        Payload(json!({ "given_name": "John" })),
+        &["given_name"],
+        &issuer,
+    )
+    .unwrap();
+    let challenge = nonce::generate();
+    presentation
+        .bind_key(
+            Algorithm::EdDSA,
+            "https://verifier.example.com",
+            &challenge,
+            &key,
+        )
+        .unwrap();
+
+    let presentation = SdJwt::from_token(&presentation.to_token()).unwrap();
+    assert!(guard
+        .verify_key_binding(
+            &presentation,
+            key.verifying_key(),
+            "https://verifier.example.com",
+            &challenge,
+        )
+        .unwrap());
+    assert_eq!(
+        guard
+            .verify_key_binding(
+                &presentation,
+                key.verifying_key(),
+                "https://verifier.example.com",
+                &challenge,
+            )
+            .err(),
+        Some(Error::REPLAY_DETECTED)
+    );
+}
+
+#[derive(Default)]
+struct CountingStore {
+    inner: MemoryNonceStore,
+    calls: Arc<AtomicUsize>,
+}
+
+impl NonceStore for CountingStore {
+    fn insert(&self, value: &str, expires_at: DateTime<Utc>) -> Result<bool, Error> {
+        self.calls.fetch_add(1, Ordering::SeqCst);
+        self.inner.insert(value, expires_at)
+    }
+}
+
+#[test]
+pub fn replay_guard_uses_custom_store() {
+    let store = CountingStore::default();
+    let calls = store.calls.clone();
+    let guard = ReplayGuard::with_store(store);
+
+    guard.check("abc").unwrap();
+    guard.check("abc").unwrap_err();
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+}
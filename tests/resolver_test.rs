@@ -0,0 +1,234 @@
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
+
+use chrono::Utc;
+use did_crypto::{
+    algorithms::{Algorithm, AlgorithmPolicy},
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    errors::Error,
+    jwt::{Header, Payload, JWT},
+    resolver::{
+        verify_with_did, CachingResolver, DidResolver, DidVerificationOptions, AUTHENTICATION,
+    },
+    vc::{Credential, VcJwt},
+    verifier::VerificationOptions,
+};
+use serde_json::{json, Value};
+
+const DID: &str = "did:example:123";
+
+struct CountingResolver(Arc<AtomicUsize>);
+
+impl DidResolver for CountingResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        let count = self.0.fetch_add(1, Ordering::SeqCst) + 1;
+        match did.starts_with("did:example:") {
+            true => Ok(json!({ "id": did, "version": count })),
+            false => Err(Error::DID_RESOLUTION_ERROR),
+        }
+    }
+}
+
+fn resolver() -> (CachingResolver<CountingResolver>, Arc<AtomicUsize>) {
+    let calls = Arc::new(AtomicUsize::new(0));
+    (CachingResolver::new(CountingResolver(calls.clone())), calls)
+}
+
+#[test]
+pub fn caching_resolver_reuses_documents() {
+    let (resolver, calls) = resolver();
+
+    let first = resolver.resolve(DID).unwrap();
+    let second = resolver.resolve(DID).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+    assert_eq!(resolver.len(), 1);
+
+    resolver.invalidate(DID);
+    assert_eq!(resolver.resolve(DID).unwrap()["version"], 2);
+    resolver.clear();
+    assert!(resolver.is_empty());
+}
+
+#[test]
+pub fn caching_resolver_caches_failures() {
+    let (resolver, calls) = resolver();
+
+    for _ in 0..3 {
+        assert_eq!(
+            resolver.resolve("did:unknown:123").err(),
+            Some(Error::DID_RESOLUTION_ERROR)
+        );
+    }
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    let (resolver, calls) = resolver_with(|resolver| resolver.negative_ttl(Duration::ZERO));
+    resolver.resolve("did:unknown:123").unwrap_err();
+    resolver.resolve("did:unknown:123").unwrap_err();
+    assert_eq!(calls.load(Ordering::SeqCst), 2);
+    assert!(resolver.is_empty());
+}
+
+#[test]
+pub fn caching_resolver_is_bounded() {
+    let (resolver, calls) = resolver_with(|resolver| resolver.max_entries(2));
+
+    resolver.resolve("did:example:1").unwrap();
+    thread::sleep(Duration::from_millis(2));
+    resolver.resolve("did:example:2").unwrap();
+    resolver.resolve("did:example:3").unwrap();
+    assert_eq!(resolver.len(), 2);
+
+    resolver.resolve("did:example:2").unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 3);
+    resolver.resolve("did:example:1").unwrap();
+    assert_eq!(calls.load(Ordering::SeqCst), 4);
+}
+
+#[test]
+pub fn caching_resolver_serves_stale_while_revalidating() {
+    let (resolver, calls) = resolver_with(|resolver| {
+        resolver
+            .ttl(Duration::ZERO)
+            .stale_while_revalidate(Duration::from_secs(60))
+    });
+
+    assert_eq!(resolver.resolve(DID).unwrap()["version"], 1);
+    assert_eq!(resolver.resolve(DID).unwrap()["version"], 1);
+
+    let mut version = Value::Null;
+    for _ in 0..100 {
+        version = resolver.resolve(DID).unwrap()["version"].clone();
+        if version == 2 {
+            break;
+        }
+        thread::sleep(Duration::from_millis(10));
+    }
+    assert_eq!(version, 2);
+    assert!(calls.load(Ordering::SeqCst) >= 2);
+}
+
+fn resolver_with(
+    configure: impl FnOnce(CachingResolver<CountingResolver>) -> CachingResolver<CountingResolver>,
+) -> (CachingResolver<CountingResolver>, Arc<AtomicUsize>) {
+    let (resolver, calls) = resolver();
+    (configure(resolver), calls)
+}
+
+struct IssuerResolver(AnyVerifyingKey, AnyVerifyingKey);
+
+impl DidResolver for IssuerResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        Ok(json!({
+            "id": did,
+            "verificationMethod": [{
+                "id": "#assert",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": self.0.to_jwk().unwrap(),
+            }, {
+                "id": "#auth",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": self.1.to_jwk().unwrap(),
+            }],
+            "assertionMethod": ["#assert"],
+            "authentication": ["#auth"],
+        }))
+    }
+}
+
+#[test]
+pub fn verify_with_did_selects_method_and_purpose() {
+    let assertion_key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let auth_key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let resolver = IssuerResolver(assertion_key.verifying_key(), auth_key.verifying_key());
+    let options = DidVerificationOptions::new();
+
+    let credential = Credential::new(DID, "ExampleCredential", json!({"id": "did:example:bob"}));
+    let vc_jwt = VcJwt::sign(
+        credential.clone(),
+        &format!("{}#assert", DID),
+        &assertion_key,
+    )
+    .unwrap();
+    assert!(verify_with_did(vc_jwt.to_token(), DID, &resolver, &options).unwrap());
+    assert!(verify_with_did(
+        vc_jwt.to_token(),
+        &format!("{}#assert", DID),
+        &resolver,
+        &options
+    )
+    .unwrap());
+    assert!(!verify_with_did(
+        vc_jwt.to_token(),
+        &format!("{}#auth", DID),
+        &resolver,
+        &options
+    )
+    .unwrap());
+    assert!(!verify_with_did(vc_jwt.to_token(), "did:example:other", &resolver, &options).unwrap());
+
+    let vc_jwt = VcJwt::sign(credential, &format!("{}#auth", DID), &auth_key).unwrap();
+    assert_eq!(
+        verify_with_did(vc_jwt.to_token(), DID, &resolver, &options).unwrap_err(),
+        Error::DID_VERIFICATION_METHOD_NOT_FOUND
+    );
+    let authentication = options.proof_purpose(AUTHENTICATION);
+    assert!(verify_with_did(vc_jwt.to_token(), DID, &resolver, &authentication).unwrap());
+
+    let eddsa_only = options.verification(
+        VerificationOptions::default().algorithm_policy(AlgorithmPolicy::new(&[Algorithm::EdDSA])),
+    );
+    let es256 = VcJwt::sign(
+        Credential::new(DID, "ExampleCredential", json!({})),
+        &format!("{}#assert", DID),
+        &assertion_key,
+    )
+    .unwrap();
+    assert_eq!(
+        verify_with_did(es256.to_token(), DID, &resolver, &eddsa_only).unwrap_err(),
+        Error::ALGORITHM_NOT_ALLOWED
+    );
+}
+
+#[test]
+pub fn verify_with_did_checks_jwt_claims() {
+    let assertion_key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let auth_key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let resolver = IssuerResolver(assertion_key.verifying_key(), auth_key.verifying_key());
+    let options = DidVerificationOptions::new();
+
+    let sign = |payload: Value| {
+        let mut jwt = JWT {
+            header: Header::new(String::from("#assert"), Algorithm::ES256),
+            payload: Payload(payload),
+            signature: None,
+        };
+        jwt.sign(&assertion_key).unwrap();
+        jwt.to_token().unwrap()
+    };
+
+    let now = Utc::now().timestamp();
+    let token = sign(json!({"iss": DID, "exp": now + 60}));
+    assert!(verify_with_did(&token, DID, &resolver, &options).unwrap());
+    assert!(verify_with_did(&format!("{}~", token), DID, &resolver, &options).unwrap());
+
+    let expired = sign(json!({"iss": DID, "exp": now - 60}));
+    assert!(!verify_with_did(&expired, DID, &resolver, &options).unwrap());
+    let other_issuer = sign(json!({"iss": "did:example:other", "exp": now + 60}));
+    assert!(!verify_with_did(&other_issuer, DID, &resolver, &options).unwrap());
+
+    assert_eq!(
+        verify_with_did("not-a-token", DID, &resolver, &options).unwrap_err(),
+        Error::JWT_MALFORMED
+    );
+    let lenient = options.verification(VerificationOptions::lenient());
+    assert!(!verify_with_did("!!.e30.c2ln", DID, &resolver, &lenient).unwrap());
+}
@@ -0,0 +1,41 @@
+use did_crypto::{
+    algorithms::{Algorithm, FIPS_MODE},
+    errors::Error,
+    self_test::{self_test, self_test_algorithms},
+};
+
+#[test]
+pub fn self_test_known_answers() {
+    let report = self_test();
+    for result in &report.results {
+        assert!(result.passed(), "{}: {:?}", result.name, result.result);
+    }
+    assert!(report.passed());
+    assert!(report.failures().is_empty());
+    assert_eq!(report.check(), Ok(()));
+    assert_eq!(report.fips_mode, FIPS_MODE);
+
+    let algorithms = report.algorithms();
+    assert!(algorithms.contains(&Algorithm::HS256));
+    assert!(algorithms.contains(&Algorithm::ES256));
+    #[cfg(feature = "rsa")]
+    assert!(algorithms.contains(&Algorithm::PS512));
+    #[cfg(feature = "eddsa")]
+    assert!(algorithms.contains(&Algorithm::EdDSA));
+    #[cfg(feature = "mldsa")]
+    assert!(algorithms.contains(&Algorithm::MLDSA65Ed25519));
+    #[cfg(not(target_arch = "wasm32"))]
+    assert!(report.results.iter().all(|result| result.elapsed.is_some()));
+}
+
+#[test]
+pub fn self_test_selected_algorithms() {
+    let report = self_test_algorithms(&[Algorithm::ES256, Algorithm::HS512]);
+    assert_eq!(
+        report.algorithms(),
+        vec![Algorithm::HS512, Algorithm::ES256]
+    );
+    assert!(report.passed());
+    assert!(self_test_algorithms(&[]).results.is_empty());
+    assert_ne!(Error::SELF_TEST_FAILED.code(), 0);
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "serde")]
+
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        ecdsa::_256::P256VerifyingKey,
+        eddsa::EDDSAVerifyingKey,
+    },
+    jwk::Jwk,
+};
+use serde::{Deserialize, Serialize};
+
+const PUBLIC_KEY_256_HEX: &str =
+    "04115b3fa39fae41b4e32f7721ca72f8c1781483647dabd514f08e66128bd47fce9067b90e0488c9c2a9f30f5a266a07841d6c077413ba07e74569b99d4fd3cec6";
+
+#[derive(Serialize, Deserialize)]
+struct VerificationMethod {
+    id: String,
+    #[serde(rename = "publicKeyJwk")]
+    public_key_jwk: AnyVerifyingKey,
+}
+
+#[test]
+pub fn public_keys_serialize_as_jwk() {
+    let key = P256VerifyingKey::from_bytes(&hex::decode(PUBLIC_KEY_256_HEX).unwrap()).unwrap();
+    let json = serde_json::to_string(&key).unwrap();
+    let jwk = Jwk::from_json(&json).unwrap();
+    assert_eq!(jwk.kty, "EC");
+    assert_eq!(jwk.crv.as_deref(), Some("P-256"));
+    assert!(jwk.d.is_none());
+    assert!(serde_json::from_str::<P256VerifyingKey>(&json).unwrap() == key);
+    assert!(serde_json::from_str::<EDDSAVerifyingKey>(&json).is_err());
+
+    let method = VerificationMethod {
+        id: String::from("did:example:123#key-1"),
+        public_key_jwk: AnySigningKey::generate(Algorithm::EdDSA)
+            .unwrap()
+            .verifying_key(),
+    };
+    let json = serde_json::to_string(&method).unwrap();
+    let parsed: VerificationMethod = serde_json::from_str(&json).unwrap();
+    assert!(parsed.public_key_jwk == method.public_key_jwk);
+    assert_eq!(parsed.public_key_jwk.algorithm(), Algorithm::EdDSA);
+}
+
+#[cfg(feature = "serde-secret")]
+#[test]
+pub fn signing_keys_serialize_with_serde_secret() {
+    for alg in [
+        Algorithm::ES256,
+        Algorithm::ES256K,
+        Algorithm::ES384,
+        Algorithm::EdDSA,
+        Algorithm::MLDSA65,
+    ] {
+        let key = AnySigningKey::generate(alg).unwrap();
+        let json = serde_json::to_string(&key).unwrap();
+        let parsed: AnySigningKey = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.algorithm(), alg);
+        assert_eq!(parsed.to_bytes().unwrap(), key.to_bytes().unwrap());
+    }
+
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let mut jwk = key.to_jwk().unwrap();
+    jwk.x = AnySigningKey::generate(Algorithm::ES256)
+        .unwrap()
+        .verifying_key()
+        .to_jwk()
+        .unwrap()
+        .x;
+    assert!(serde_json::from_str::<AnySigningKey>(&jwk.to_json().unwrap()).is_err());
+    assert!(serde_json::to_string(&AnySigningKey::generate(Algorithm::HS256).unwrap()).is_err());
+}
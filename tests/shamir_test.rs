@@ -0,0 +1,84 @@
+use did_crypto::{
+    crypto::shamir::{recover, split, Share},
+    errors::Error,
+};
+
+const SEED: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+#[test]
+pub fn shamir_split_and_recover() {
+    let secret = hex::decode(SEED).unwrap();
+    let shares = split(&secret, 3, 5).unwrap();
+    assert_eq!(shares.len(), 5);
+    assert!(shares.iter().all(|share| share.threshold() == 3));
+
+    for subset in [[0, 1, 2], [4, 2, 0], [1, 3, 4]] {
+        let selected: Vec<Share> = subset.iter().map(|index| shares[*index].clone()).collect();
+        assert_eq!(recover(&selected).unwrap().as_slice(), secret.as_slice());
+    }
+    assert_eq!(recover(&shares).unwrap().as_slice(), secret.as_slice());
+
+    assert_eq!(
+        recover(&[shares[0].clone(), shares[1].clone(), shares[1].clone()]).unwrap_err(),
+        Error::SHAMIR_INSUFFICIENT_SHARES
+    );
+    assert_eq!(recover(&[]).unwrap_err(), Error::SHAMIR_INSUFFICIENT_SHARES);
+
+    assert_eq!(
+        split(&secret, 1, 5).unwrap_err(),
+        Error::SHAMIR_INVALID_PARAMETERS
+    );
+    assert_eq!(
+        split(&secret, 4, 3).unwrap_err(),
+        Error::SHAMIR_INVALID_PARAMETERS
+    );
+}
+
+#[test]
+pub fn shamir_share_serialization() {
+    let secret = hex::decode(SEED).unwrap();
+    let shares = split(&secret, 2, 3).unwrap();
+
+    let encoded: Vec<String> = shares
+        .iter()
+        .map(|share| share.to_base58().to_string())
+        .collect();
+    let decoded: Vec<Share> = encoded
+        .iter()
+        .map(|share| Share::from_base58(share).unwrap())
+        .collect();
+    assert_eq!(decoded, shares);
+    assert_eq!(decoded[2].index(), 3);
+
+    let mut corrupted = shares[0].to_bytes().to_vec();
+    corrupted[6] ^= 0x01;
+    assert_eq!(
+        Share::from_bytes(&corrupted).unwrap_err(),
+        Error::SHAMIR_INVALID_SHARE
+    );
+    assert!(Share::from_bytes(&corrupted)
+        .unwrap_err()
+        .is_malformed_input());
+
+    let other = split(&secret, 2, 3).unwrap();
+    let mixed = [shares[0].clone(), other[1].clone()];
+    let error = recover(&mixed).unwrap_err();
+    assert_eq!(error, Error::SHAMIR_RECOVERY_FAILED);
+}
+
+#[cfg(feature = "eddsa")]
+#[test]
+pub fn shamir_signing_key_backup() {
+    use did_crypto::{
+        algorithms::Algorithm,
+        crypto::{
+            any::AnySigningKey,
+            shamir::{recover_key, split_key},
+        },
+    };
+
+    let key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let shares = split_key(&key, 2, 3).unwrap();
+    let recovered = recover_key(&shares[1..], Algorithm::EdDSA).unwrap();
+    assert_eq!(recovered.verifying_key(), key.verifying_key());
+}
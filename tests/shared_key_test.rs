@@ -0,0 +1,57 @@
+use std::{sync::Arc, thread};
+
+use chrono::Utc;
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{any::AnySigningKey, shared::SharedSigningKey, SignFromKey, VerifyFromKey},
+    jwt::{Header, Payload, JWT},
+    signer, verifier,
+};
+use serde_json::json;
+
+#[test]
+pub fn shared_key_handles() {
+    let key: SharedSigningKey = AnySigningKey::generate(Algorithm::ES256).unwrap().into();
+    let verifying_key = key.shared_verifying_key();
+    assert_eq!(key.handles(), 1);
+
+    let workers: Vec<_> = (0..4)
+        .map(|index| {
+            let key = key.clone();
+            let verifying_key = verifying_key.clone();
+            thread::spawn(move || {
+                let message = format!("request-{}", index);
+                let signature = key.sign_bytes(message.as_bytes(), key.algorithm()).unwrap();
+                assert!(verifying_key
+                    .verify_bytes(message.as_bytes(), &signature, Algorithm::ES256)
+                    .unwrap());
+                signature
+            })
+        })
+        .collect();
+    for worker in workers {
+        assert!(!worker.join().unwrap().is_empty());
+    }
+
+    let clone = key.clone();
+    assert!(clone.ptr_eq(&key));
+    assert_eq!(key.handles(), 2);
+    assert_eq!(format!("{:?}", clone), format!("{:?}", *key));
+
+    let signature = signer::sign_signature(b"payload", key.clone(), Algorithm::ES256).unwrap();
+    assert!(verifier::verify_signature(b"payload", &signature, verifying_key.clone()).unwrap());
+
+    let mut jwt = JWT {
+        header: Header::new(String::from("did:example:123#key-1"), Algorithm::ES256),
+        payload: Payload(json!({ "sub": "did:example:123", "exp": Utc::now().timestamp() + 60 })),
+        signature: None,
+    };
+    jwt.sign(&key).unwrap();
+    assert!(jwt.validate(&*verifying_key).unwrap());
+
+    let arc = Arc::new(AnySigningKey::generate(Algorithm::ES256).unwrap());
+    let shared = SharedSigningKey::from(arc.clone());
+    assert!(Arc::ptr_eq(shared.as_arc(), &arc));
+    assert!(!shared.ptr_eq(&key));
+    assert_eq!(shared.into_arc().algorithm(), Algorithm::ES256);
+}
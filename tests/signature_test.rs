@@ -0,0 +1,200 @@
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{
+        any::AnySigningKey,
+        signature::{Base64Variant, Signature, SignatureEncoding},
+        SignFromKey, VerifyFromKey,
+    },
+    errors::Error,
+    signer::{sign_encoded, sign_signature},
+    verifier::{verify_encoded, verify_signature, verify_with_options, VerificationOptions},
+};
+
+const MESSAGE: &str = "did:example:123";
+
+#[test]
+pub fn signature_encodings_round_trip() {
+    for alg in [
+        Algorithm::ES256,
+        Algorithm::ES256K,
+        Algorithm::ES384,
+        Algorithm::ES512,
+        Algorithm::EdDSA,
+    ] {
+        let key = AnySigningKey::generate(alg).unwrap();
+        let signature = key.sign_signature(MESSAGE.as_bytes(), alg).unwrap();
+        assert_eq!(signature.algorithm(), alg);
+        assert_eq!(signature.to_base64url(), String::from(signature.clone()),);
+
+        assert_eq!(
+            Signature::from_base64url(&signature.to_base64url(), alg).unwrap(),
+            signature
+        );
+        assert_eq!(
+            Signature::from_hex(&signature.to_hex(), alg).unwrap(),
+            signature
+        );
+        assert_eq!(
+            Signature::from_hex(&signature.to_hex_prefixed(), alg).unwrap(),
+            signature
+        );
+        assert!(signature.to_multibase().starts_with('z'));
+        assert_eq!(
+            Signature::from_multibase(&signature.to_multibase(), alg).unwrap(),
+            signature
+        );
+
+        assert!(key
+            .verifying_key()
+            .verify_signature(MESSAGE.as_bytes(), &signature)
+            .unwrap());
+        assert!(verify_signature(String::from(MESSAGE), &signature, key.verifying_key()).unwrap());
+
+        if alg == Algorithm::EdDSA {
+            assert_eq!(
+                signature.to_der().err().unwrap(),
+                Error::SIGNATURE_ENCODING_UNSUPPORTED
+            );
+        } else {
+            let der = signature.to_der().unwrap();
+            assert_eq!(der[0], 0x30);
+            assert_eq!(Signature::from_der(&der, alg).unwrap(), signature);
+        }
+    }
+}
+
+#[test]
+pub fn signature_parsing_errors() {
+    assert_eq!(
+        Signature::from_hex("zz", Algorithm::ES256).err().unwrap(),
+        Error::DECODING_ERROR
+    );
+    assert_eq!(
+        Signature::from_multibase("uAAAA", Algorithm::ES256)
+            .err()
+            .unwrap(),
+        Error::SIGNATURE_ENCODING_UNSUPPORTED
+    );
+    assert_eq!(
+        Signature::from_der(&[0x30, 0x00], Algorithm::ES256)
+            .err()
+            .unwrap(),
+        Error::SIGNATURE_IDENTIFICATION_FAILED
+    );
+    assert_eq!(
+        Signature::from_der(&[0x30, 0x00], Algorithm::EdDSA)
+            .err()
+            .unwrap(),
+        Error::SIGNATURE_ENCODING_UNSUPPORTED
+    );
+
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let signature = sign_signature(String::from(MESSAGE), &key, Algorithm::ES256).unwrap();
+    assert_eq!(signature.as_bytes().len(), 64);
+    assert!(verify_signature(String::from(MESSAGE), &signature, key.verifying_key()).unwrap());
+    assert!(!key
+        .verifying_key()
+        .verify_signature(
+            b"did:example:456",
+            &Signature::new(signature.as_bytes().to_vec(), Algorithm::ES256)
+        )
+        .unwrap());
+}
+
+#[test]
+pub fn multibase_proof_values() {
+    let key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let proof_value = sign_encoded(
+        String::from(MESSAGE),
+        &key,
+        Algorithm::EdDSA,
+        SignatureEncoding::Multibase,
+    )
+    .unwrap();
+    assert!(proof_value.starts_with('z'));
+    assert!(verify_encoded(
+        String::from(MESSAGE),
+        &proof_value,
+        SignatureEncoding::Multibase,
+        key.verifying_key(),
+        Algorithm::EdDSA
+    )
+    .unwrap());
+
+    let options = VerificationOptions::default().signature_encoding(SignatureEncoding::Multibase);
+    assert!(verify_with_options(
+        String::from(MESSAGE),
+        proof_value.clone(),
+        key.verifying_key(),
+        Algorithm::EdDSA,
+        &options
+    )
+    .unwrap());
+    assert_eq!(
+        verify_with_options(
+            String::from(MESSAGE),
+            proof_value.replacen('z', "u", 1),
+            key.verifying_key(),
+            Algorithm::EdDSA,
+            &options
+        )
+        .err()
+        .unwrap(),
+        Error::SIGNATURE_ENCODING_UNSUPPORTED
+    );
+    assert!(!verify_with_options(
+        String::from(MESSAGE),
+        proof_value.replacen('z', "u", 1),
+        key.verifying_key(),
+        Algorithm::EdDSA,
+        &options.report_malformed_input(false)
+    )
+    .unwrap());
+}
+
+#[test]
+pub fn base64_variants_on_verify() {
+    let bytes = vec![0xfb, 0xff, 0xbf];
+    let signature = Signature::new(bytes.clone(), Algorithm::ES256);
+    assert_eq!(signature.to_base64(Base64Variant::Standard), "+/+/");
+    assert_eq!(
+        Signature::new(vec![0xfb, 0xff], Algorithm::ES256).to_base64(Base64Variant::StandardPadded),
+        "+/8="
+    );
+    for encoded in ["+/+/", "-_-_", "+/8=", "-_8", "-/8="] {
+        assert!(Signature::from_base64(encoded, Algorithm::ES256, Base64Variant::Auto).is_ok());
+    }
+    assert_eq!(
+        Signature::from_base64("+/+/", Algorithm::ES256, Base64Variant::UrlSafe)
+            .err()
+            .unwrap(),
+        Error::DECODING_ERROR
+    );
+    assert_eq!(
+        Signature::from_base64("-_-_", Algorithm::ES256, Base64Variant::UrlSafe)
+            .unwrap()
+            .as_bytes(),
+        bytes.as_slice()
+    );
+
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let padded = sign_encoded(
+        String::from(MESSAGE),
+        &key,
+        Algorithm::ES256,
+        SignatureEncoding::Base64(Base64Variant::StandardPadded),
+    )
+    .unwrap();
+    for variant in [Base64Variant::StandardPadded, Base64Variant::Auto] {
+        let options =
+            VerificationOptions::default().signature_encoding(SignatureEncoding::Base64(variant));
+        assert!(verify_with_options(
+            String::from(MESSAGE),
+            padded.clone(),
+            key.verifying_key(),
+            Algorithm::ES256,
+            &options
+        )
+        .unwrap());
+    }
+}
@@ -0,0 +1,85 @@
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::AnySigningKey,
+    errors::Error,
+    signer::sign,
+    signing_context::{SigningContext, CONTEXT_PREFIX},
+    verifier::verify,
+};
+
+#[test]
+pub fn signing_context_separates_domains() {
+    for alg in [Algorithm::ES256, Algorithm::EdDSA, Algorithm::RS256] {
+        let key = AnySigningKey::generate(alg).unwrap();
+        let verifying_key = key.verifying_key();
+        let message = b"nonce-123";
+
+        let challenge = SigningContext::challenge();
+        let signature = challenge.sign(message, &key, alg).unwrap();
+        assert!(challenge
+            .verify(message, &signature, &verifying_key, alg)
+            .unwrap());
+        assert!(!SigningContext::vc()
+            .verify(message, &signature, &verifying_key, alg)
+            .unwrap());
+        assert!(!verify(message, &signature, &verifying_key, alg).unwrap());
+
+        let raw = sign(message, &key, alg).unwrap();
+        assert!(!challenge
+            .verify(message, &raw, &verifying_key, alg)
+            .unwrap());
+    }
+}
+
+#[test]
+pub fn signing_context_frames_and_opens() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let context = SigningContext::new("example.com/login").unwrap();
+    assert_eq!(context.tag(), "example.com/login");
+
+    let framed = context.frame(b"payload");
+    assert!(framed.starts_with(CONTEXT_PREFIX));
+    assert_eq!(context.strip(&framed).unwrap(), b"payload");
+    assert_eq!(
+        SigningContext::new("example.com/logi")
+            .unwrap()
+            .strip(&framed)
+            .err(),
+        Some(Error::SIGNING_CONTEXT_MISMATCH)
+    );
+    assert_eq!(
+        SigningContext::raw().strip(b"payload").err(),
+        Some(Error::SIGNING_CONTEXT_MISMATCH)
+    );
+
+    let signature = sign(&framed, &key, Algorithm::ES256).unwrap();
+    assert_eq!(
+        context
+            .open(&framed, &signature, key.verifying_key(), Algorithm::ES256)
+            .unwrap(),
+        b"payload"
+    );
+    assert_eq!(
+        SigningContext::jwt()
+            .open(&framed, &signature, key.verifying_key(), Algorithm::ES256)
+            .err(),
+        Some(Error::SIGNING_CONTEXT_MISMATCH)
+    );
+
+    let other = context.frame(b"other");
+    assert_eq!(
+        context
+            .open(&other, &signature, key.verifying_key(), Algorithm::ES256)
+            .err(),
+        Some(Error::SIGNATURE_INVALID)
+    );
+
+    assert_eq!(
+        SigningContext::new("").err(),
+        Some(Error::SIGNATURE_CONTEXT_INVALID)
+    );
+    assert_eq!(
+        SigningContext::new(&"a".repeat(256)).err(),
+        Some(Error::SIGNATURE_CONTEXT_INVALID)
+    );
+}
@@ -0,0 +1,169 @@
+#![cfg(feature = "es256k")]
+
+use std::str::FromStr;
+
+use chrono::{TimeDelta, Utc};
+use did_crypto::{
+    caip::{checksum_address, eip155_address},
+    crypto::ecdsa::_256k::P256kSigningKey,
+    errors::Error,
+    siwe::{personal_sign, recover_address, SiweMessage, SiweVerification},
+};
+
+const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+const ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+fn signing_key() -> P256kSigningKey {
+    P256kSigningKey::from_bytes(&hex::decode(PRIVATE_KEY).unwrap()).unwrap()
+}
+
+#[test]
+pub fn siwe_message_round_trip() {
+    let key = signing_key();
+    let message = SiweMessage::for_key(
+        "example.com",
+        &key.verifying_key(),
+        "https://example.com/login",
+        1,
+    )
+    .with_scheme("https")
+    .with_statement("Sign in to Example")
+    .with_nonce("32891756abcd")
+    .expires_at(Utc::now() + TimeDelta::minutes(10))
+    .with_request_id("req-1")
+    .with_resource("ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/")
+    .with_resource("https://example.com/my-web2-claim.json");
+
+    let text = message.to_message();
+    assert!(text.starts_with(&format!(
+        "https://example.com wants you to sign in with your Ethereum account:\n{}\n\nSign in to Example\n\nURI: https://example.com/login\nVersion: 1\nChain ID: 1\nNonce: 32891756abcd\nIssued At: ",
+        ADDRESS
+    )));
+    assert!(text.ends_with("\nResources:\n- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/\n- https://example.com/my-web2-claim.json"));
+    assert_eq!(SiweMessage::from_str(&text).unwrap(), message);
+
+    let minimal = SiweMessage::new(
+        "example.com",
+        eip155_address(&key.verifying_key()),
+        "https://example.com",
+        10,
+    );
+    let text = minimal.to_message();
+    assert!(text.contains(&format!("{}\n\n\nURI: https://example.com\n", ADDRESS)));
+    assert_eq!(SiweMessage::from_str(&text).unwrap(), minimal);
+}
+
+#[test]
+pub fn siwe_sign_and_verify() {
+    let key = signing_key();
+    assert_eq!(
+        checksum_address(&eip155_address(&key.verifying_key())),
+        ADDRESS
+    );
+
+    let now = Utc::now();
+    let message = SiweMessage::for_key(
+        "example.com",
+        &key.verifying_key(),
+        "https://example.com",
+        1,
+    )
+    .with_nonce("abcdef123456")
+    .expires_at(now + TimeDelta::minutes(5));
+    let signature = message.sign(&key).unwrap();
+    assert_eq!(signature.len(), 132);
+    assert!(signature.ends_with("1b") || signature.ends_with("1c"));
+
+    let parsed = SiweMessage::from_str(&message.to_message()).unwrap();
+    let validation = SiweVerification::new("example.com", "abcdef123456");
+    assert!(parsed.verify(&signature, &validation).unwrap());
+
+    assert!(!parsed
+        .verify(
+            &signature,
+            &SiweVerification::new("evil.com", "abcdef123456")
+        )
+        .unwrap());
+    assert!(!parsed
+        .verify(
+            &signature,
+            &SiweVerification::new("example.com", "otherNonce1")
+        )
+        .unwrap());
+    assert!(!parsed
+        .verify(&signature, &validation.time(now + TimeDelta::minutes(10)))
+        .unwrap());
+
+    let mut tampered = parsed.clone();
+    tampered.chain_id = 5;
+    assert!(!tampered.verify(&signature, &validation).unwrap());
+
+    let other = P256kSigningKey::from_bytes(&[7u8; 32]).unwrap();
+    assert_eq!(message.sign(&other).unwrap_err(), Error::SIGNING_FAILED);
+}
+
+#[test]
+pub fn siwe_not_before() {
+    let key = signing_key();
+    let now = Utc::now();
+    let message = SiweMessage::for_key(
+        "example.com",
+        &key.verifying_key(),
+        "https://example.com",
+        1,
+    )
+    .with_nonce("abcdef123456")
+    .not_before(now + TimeDelta::minutes(5));
+    let signature = message.sign(&key).unwrap();
+
+    let validation = SiweVerification::new("example.com", "abcdef123456");
+    assert!(!message.verify(&signature, &validation).unwrap());
+    assert!(message
+        .verify(&signature, &validation.time(now + TimeDelta::minutes(6)))
+        .unwrap());
+}
+
+#[test]
+pub fn siwe_personal_sign_recovers_address() {
+    let key = signing_key();
+    let signature = personal_sign(b"hello", &key).unwrap();
+    let address = recover_address(b"hello", &signature).unwrap();
+    assert_eq!(checksum_address(&address), ADDRESS);
+    assert_ne!(
+        checksum_address(&recover_address(b"hellO", &signature).unwrap()),
+        ADDRESS
+    );
+
+    assert!(recover_address(b"hello", "0x1234").is_err());
+    assert!(recover_address(b"hello", "not hex").is_err());
+}
+
+#[test]
+pub fn siwe_rejects_malformed_messages() {
+    let key = signing_key();
+    let message = SiweMessage::for_key(
+        "example.com",
+        &key.verifying_key(),
+        "https://example.com",
+        1,
+    )
+    .with_nonce("abcdef123456");
+    let text = message.to_message();
+
+    for bad in [
+        text.replace(" wants you", " would like you"),
+        text.replace(ADDRESS, &ADDRESS.to_lowercase()),
+        text.replace("Version: 1", "Version: 2"),
+        text.replace("Chain ID: 1", "Chain ID: one"),
+        text.replace("abcdef123456", "abc"),
+        text.replace("abcdef123456", "abcdef-123456"),
+        text.replace("Issued At: ", "Issued At: yesterday "),
+        format!("{}\nExtra: line", text),
+        format!("{}\nResources:\nnot a list item", text),
+    ] {
+        assert_eq!(
+            SiweMessage::from_str(&bad).unwrap_err(),
+            Error::SIWE_MALFORMED
+        );
+    }
+}
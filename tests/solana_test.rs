@@ -0,0 +1,103 @@
+#![cfg(feature = "eddsa")]
+
+use std::{env, fs, process};
+
+use did_crypto::{
+    crypto::eddsa::EDDSASigningKey,
+    errors::Error,
+    solana::{
+        keypair_from_base58, keypair_from_bytes, keypair_from_json, keypair_to_base58,
+        keypair_to_bytes, keypair_to_json, public_key_from_base58, public_key_to_base58,
+        read_keypair_file, sign_message, verify_message, write_keypair_file,
+    },
+};
+
+const SECRET_KEY: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+const PUBLIC_KEY: &str = "FVen3X669xLzsi6N2V91DoiyzHzg1uAgqiT8jZ9nS96Z";
+const SIGNATURE: &str =
+    "5awYiUvGiDFA33EJjj4TXJG44a5afJc8QjWRpGgQiu6b23jCr7yndW2fmp9ujwqJVe32J456wV3VF78Asb1obnTc";
+
+fn signing_key() -> EDDSASigningKey {
+    EDDSASigningKey::from_bytes(&mut hex::decode(SECRET_KEY).unwrap()).unwrap()
+}
+
+#[test]
+pub fn solana_public_key_base58() {
+    let key = signing_key().verifying_key();
+    assert_eq!(public_key_to_base58(&key), PUBLIC_KEY);
+    assert_eq!(public_key_from_base58(PUBLIC_KEY).unwrap(), key);
+
+    assert_eq!(
+        public_key_from_base58("0OIl").unwrap_err(),
+        Error::PUBLIC_KEY_IDENTIFICATION_ERROR
+    );
+    assert_eq!(
+        public_key_from_base58("FVen3X669xLzsi6N2V91").unwrap_err(),
+        Error::PUBLIC_KEY_IDENTIFICATION_ERROR
+    );
+}
+
+#[test]
+pub fn solana_keypair_encodings() {
+    let key = signing_key();
+    let bytes = keypair_to_bytes(&key);
+    assert_eq!(bytes.len(), 64);
+    assert_eq!(hex::encode(&bytes[..32]), SECRET_KEY);
+
+    let decoded = keypair_from_base58(&keypair_to_base58(&key)).unwrap();
+    assert_eq!(decoded.to_bytes(), key.to_bytes());
+
+    let json = keypair_to_json(&key);
+    assert!(json.starts_with("[157,97,177,"));
+    assert_eq!(keypair_from_json(&json).unwrap().to_bytes(), key.to_bytes());
+
+    let mut mismatched = bytes.clone();
+    mismatched[63] ^= 1;
+    assert_eq!(
+        keypair_from_bytes(&mismatched).unwrap_err(),
+        Error::PRIVATE_KEY_IDENTIFICATION_ERROR
+    );
+    assert_eq!(
+        keypair_from_bytes(&bytes[..32]).unwrap_err(),
+        Error::PRIVATE_KEY_IDENTIFICATION_ERROR
+    );
+    assert_eq!(
+        keypair_from_json("[1,2,3]").unwrap_err(),
+        Error::PRIVATE_KEY_IDENTIFICATION_ERROR
+    );
+}
+
+#[test]
+pub fn solana_keypair_file() {
+    let dir = env::temp_dir().join(format!("did-crypto-solana-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("id.json");
+
+    let key = signing_key();
+    write_keypair_file(&key, &path).unwrap();
+    assert_eq!(read_keypair_file(&path).unwrap().to_bytes(), key.to_bytes());
+
+    assert_eq!(
+        read_keypair_file(dir.join("missing.json")).unwrap_err(),
+        Error::FILE_IO_ERROR
+    );
+
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+pub fn solana_sign_message() {
+    let key = signing_key();
+    let signature = sign_message(b"", &key).unwrap();
+    assert_eq!(signature, SIGNATURE);
+    assert!(verify_message(b"", &signature, &key.verifying_key()).unwrap());
+    assert!(!verify_message(b"x", &signature, &key.verifying_key()).unwrap());
+
+    let signature = sign_message(b"hello solana", &key).unwrap();
+    let public_key = public_key_from_base58(PUBLIC_KEY).unwrap();
+    assert!(verify_message(b"hello solana", &signature, &public_key).unwrap());
+    assert_eq!(
+        verify_message(b"hello solana", "0OIl", &public_key).unwrap_err(),
+        Error::DECODING_ERROR
+    );
+}
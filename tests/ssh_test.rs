@@ -0,0 +1,141 @@
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{
+        any::AnySigningKey,
+        ssh::{from_openssh_public_key, to_openssh_public_key, SshSignature},
+    },
+    errors::Error,
+    hash::HashAlgorithm,
+};
+
+const MESSAGE: &[u8] = b"did:web:example.com configuration\n";
+const NAMESPACE: &str = "file";
+
+const P256_PUBLIC_KEY: &str = "ecdsa-sha2-nistp256 AAAAE2VjZHNhLXNoYTItbmlzdHAyNTYAAAAIbmlzdHAyNTYAAABBBB9ALlOLfb3gxDktPGqpD/87xsw57gNIDrfgUsHco+Gc+5lhjythxPXEbTCpBGY0j90emea/8GCnpP7M5w9/JNw= bob@example.com";
+const P256_SIGNATURE: &str = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAAGgAAAATZWNkc2Etc2hhMi1uaXN0cDI1NgAAAAhuaXN0cDI1NgAAAE
+EEH0AuU4t9veDEOS08aqkP/zvGzDnuA0gOt+BSwdyj4Zz7mWGPK2HE9cRtMKkEZjSP3R6Z
+5r/wYKek/sznD38k3AAAAARmaWxlAAAAAAAAAAZzaGE1MTIAAABkAAAAE2VjZHNhLXNoYT
+ItbmlzdHAyNTYAAABJAAAAIQDyh/HuJmceCuMq8btmEVkzLxb0F63x2GzBsCMmzVDe4QAA
+ACBrC1c7FRt+lTml8x2QXoJ9KuO9z8d60KuUaVpIlju3wA==
+-----END SSH SIGNATURE-----
+";
+
+#[test]
+pub fn ssh_verify_openssh_ecdsa_signature() {
+    let key = from_openssh_public_key(P256_PUBLIC_KEY).unwrap();
+    assert_eq!(key.algorithm(), Algorithm::ES256);
+    assert_eq!(
+        to_openssh_public_key(&key, Some("bob@example.com")).unwrap(),
+        P256_PUBLIC_KEY
+    );
+
+    let signature = SshSignature::from_armored(P256_SIGNATURE).unwrap();
+    assert_eq!(signature.namespace(), NAMESPACE);
+    assert_eq!(signature.hash_algorithm(), HashAlgorithm::Sha512);
+    assert_eq!(signature.public_key(), &key);
+    assert!(signature.verify(MESSAGE, NAMESPACE).unwrap());
+    assert!(signature.verify_with_key(MESSAGE, NAMESPACE, &key).unwrap());
+    assert!(!signature.verify(b"tampered", NAMESPACE).unwrap());
+    assert_eq!(signature.to_armored().unwrap(), P256_SIGNATURE);
+
+    let error = signature.verify(MESSAGE, "git").err().unwrap();
+    assert_eq!(error, Error::SSH_SIGNATURE_NAMESPACE_MISMATCH);
+    assert_eq!(error.key(), Some(NAMESPACE));
+
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    assert!(!signature
+        .verify_with_key(MESSAGE, NAMESPACE, &other.verifying_key())
+        .unwrap());
+}
+
+#[cfg(feature = "eddsa")]
+#[test]
+pub fn ssh_verify_openssh_ed25519_signature() {
+    let public_key = "ssh-ed25519 AAAAC3NzaC1lZDI1NTE5AAAAIKsPuwO6EaqCJVyBSoQIyQ6m/k+3WLevFdk5nm3mHdBE alice@example.com";
+    let armored = "-----BEGIN SSH SIGNATURE-----
+U1NIU0lHAAAAAQAAADMAAAALc3NoLWVkMjU1MTkAAAAgqw+7A7oRqoIlXIFKhAjJDqb+T7
+dYt68V2TmebeYd0EQAAAAEZmlsZQAAAAAAAAAGc2hhNTEyAAAAUwAAAAtzc2gtZWQyNTUx
+OQAAAEAnGvRLBoK+o7Q0Lnx1HJ8DXuYcSpgWp8H7XMz5xJLn3vhsFuFejvkQ2286rUb4a3
+lbHLeLDq0YtEeKGlBbaL8L
+-----END SSH SIGNATURE-----
+";
+
+    let key = from_openssh_public_key(public_key).unwrap();
+    let signature = SshSignature::from_armored(armored).unwrap();
+    assert!(signature.verify_with_key(MESSAGE, NAMESPACE, &key).unwrap());
+    assert_eq!(signature.to_armored().unwrap(), armored);
+}
+
+#[test]
+pub fn ssh_sign_round_trip() {
+    let mut algorithms = vec![Algorithm::ES256];
+    #[cfg(feature = "eddsa")]
+    algorithms.push(Algorithm::EdDSA);
+    #[cfg(feature = "es384")]
+    algorithms.push(Algorithm::ES384);
+    #[cfg(feature = "es512")]
+    algorithms.push(Algorithm::ES512);
+
+    for alg in algorithms {
+        let key = AnySigningKey::generate(alg).unwrap();
+        let signature = SshSignature::sign(MESSAGE, &key, NAMESPACE).unwrap();
+        let decoded = SshSignature::from_armored(&signature.to_armored().unwrap()).unwrap();
+        assert_eq!(decoded, signature);
+        assert!(decoded
+            .verify_with_key(MESSAGE, NAMESPACE, &key.verifying_key())
+            .unwrap());
+
+        let line = to_openssh_public_key(&key.verifying_key(), None).unwrap();
+        assert_eq!(from_openssh_public_key(&line).unwrap(), key.verifying_key());
+    }
+
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let signature =
+        SshSignature::sign_reader(MESSAGE, &key, NAMESPACE, HashAlgorithm::Sha256).unwrap();
+    assert_eq!(signature.hash_algorithm(), HashAlgorithm::Sha256);
+    assert!(signature.verify_reader(MESSAGE, NAMESPACE).unwrap());
+}
+
+#[test]
+pub fn ssh_malformed() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    assert_eq!(
+        SshSignature::sign(MESSAGE, &key, "").err(),
+        Some(Error::SSH_SIGNATURE_NAMESPACE_MISMATCH)
+    );
+    assert_eq!(
+        SshSignature::sign_reader(MESSAGE, &key, NAMESPACE, HashAlgorithm::Sha384).err(),
+        Some(Error::SSH_SIGNATURE_MALFORMED)
+    );
+
+    let bytes = SshSignature::from_armored(P256_SIGNATURE)
+        .unwrap()
+        .to_bytes()
+        .unwrap();
+    for bytes in [
+        &bytes[..bytes.len() - 1],
+        &[bytes.as_slice(), &[0]].concat(),
+        b"SSHSIG",
+    ] {
+        let error = SshSignature::from_bytes(bytes).err().unwrap();
+        assert_eq!(error, Error::SSH_SIGNATURE_MALFORMED);
+        assert!(error.is_malformed_input());
+    }
+    assert_eq!(
+        SshSignature::from_armored(
+            "-----BEGIN SSH SIGNATURE-----\n!!\n-----END SSH SIGNATURE-----"
+        )
+        .err(),
+        Some(Error::SSH_SIGNATURE_MALFORMED)
+    );
+
+    assert_eq!(
+        from_openssh_public_key("ssh-rsa AAAAB3NzaC1yc2EAAAADAQABAAAAAQE=").err(),
+        Some(Error::KEY_FORMAT_UNSUPPORTED)
+    );
+    assert_eq!(
+        from_openssh_public_key("ssh-ed25519").err(),
+        Some(Error::KEY_FORMAT_UNRECOGNIZED)
+    );
+}
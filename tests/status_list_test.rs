@@ -0,0 +1,236 @@
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    errors::Error,
+    resolver::{DidResolver, ASSERTION_METHOD},
+    status_list::{
+        check_credential_status, check_status, BitstringStatusList, InMemoryStatusChecker,
+        StatusChecker, StatusListEntry, StatusPurpose, MIN_STATUS_LIST_SIZE,
+    },
+    vc::{Credential, VcJwt},
+};
+use serde_json::{json, Value};
+
+const STATUS_LIST_ID: &str = "https://example.com/credentials/status/3";
+
+#[test]
+pub fn status_list_encode_and_decode() {
+    let mut list = BitstringStatusList::new(MIN_STATUS_LIST_SIZE).unwrap();
+    list.set(0, true).unwrap();
+    list.set(94567, true).unwrap();
+    list.set(94567, false).unwrap();
+    list.set(131071, true).unwrap();
+
+    let encoded = list.encode().unwrap();
+    assert!(encoded.starts_with('u'));
+
+    let decoded = BitstringStatusList::decode(&encoded).unwrap();
+    assert!(decoded == list);
+    assert!(decoded.get(0).unwrap());
+    assert!(!decoded.get(94567).unwrap());
+    assert!(decoded.get(131071).unwrap());
+    assert!(decoded.get(131072).is_err());
+}
+
+#[test]
+pub fn status_list_rejects_small_lists() {
+    assert!(BitstringStatusList::new(1024).is_err());
+}
+
+#[test]
+pub fn status_list_check_credential_status() {
+    let mut list = BitstringStatusList::new(MIN_STATUS_LIST_SIZE).unwrap();
+    list.set(94567, true).unwrap();
+    let status_list_credential = list
+        .to_credential(
+            STATUS_LIST_ID,
+            "did:example:12345",
+            StatusPurpose::Revocation,
+        )
+        .unwrap();
+
+    let revoked = json!({
+        "id": "https://example.com/credentials/23894672394",
+        "credentialStatus": StatusListEntry::new(STATUS_LIST_ID, StatusPurpose::Revocation, 94567),
+    });
+    let valid = json!({
+        "id": "https://example.com/credentials/23894672395",
+        "credentialStatus": [
+            StatusListEntry::new(STATUS_LIST_ID, StatusPurpose::Revocation, 94568),
+        ],
+    });
+
+    assert!(check_status(&revoked, &status_list_credential, StatusPurpose::Revocation).unwrap());
+    assert!(!check_status(&valid, &status_list_credential, StatusPurpose::Revocation).unwrap());
+    assert!(check_status(&valid, &status_list_credential, StatusPurpose::Suspension).is_err());
+}
+
+const ISSUER_DID: &str = "did:example:12345";
+const SUSPENSION_LIST_ID: &str = "https://example.com/credentials/status/4";
+
+struct IssuerResolver(AnyVerifyingKey);
+
+impl DidResolver for IssuerResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        if did != ISSUER_DID {
+            return Err(Error::DID_RESOLUTION_ERROR);
+        }
+
+        Ok(json!({
+            "id": did,
+            "verificationMethod": [{
+                "id": "#key-1",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": self.0.to_jwk().unwrap(),
+            }],
+            ASSERTION_METHOD: ["#key-1"],
+        }))
+    }
+}
+
+fn status_list(id: &str, purpose: StatusPurpose, set: &[usize]) -> Value {
+    let mut list = BitstringStatusList::new(MIN_STATUS_LIST_SIZE).unwrap();
+    for index in set {
+        list.set(*index, true).unwrap();
+    }
+    list.to_credential(id, ISSUER_DID, purpose).unwrap()
+}
+
+fn credential(index: usize) -> Credential {
+    Credential::new(
+        ISSUER_DID,
+        "ExampleCredential",
+        json!({"id": "did:example:holder"}),
+    )
+    .with_status(json!([
+        StatusListEntry::new(STATUS_LIST_ID, StatusPurpose::Revocation, index),
+        StatusListEntry::new(SUSPENSION_LIST_ID, StatusPurpose::Suspension, index),
+    ]))
+}
+
+#[test]
+pub fn status_list_in_memory_checker() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let resolver = IssuerResolver(key.verifying_key());
+    let kid = format!("{}#key-1", ISSUER_DID);
+    let checker = InMemoryStatusChecker::new()
+        .with_list(status_list(STATUS_LIST_ID, StatusPurpose::Revocation, &[7]))
+        .with_list(status_list(
+            SUSPENSION_LIST_ID,
+            StatusPurpose::Suspension,
+            &[9],
+        ));
+
+    let valid = VcJwt::sign(credential(1), &kid, &key).unwrap();
+    assert!(valid.verify_with_status(&resolver, &checker).unwrap());
+
+    let revoked = VcJwt::sign(credential(7), &kid, &key).unwrap();
+    let error = revoked
+        .verify_with_status(&resolver, &checker)
+        .err()
+        .unwrap();
+    assert_eq!(error, Error::CREDENTIAL_REVOKED);
+    assert_eq!(error.key(), Some(STATUS_LIST_ID));
+
+    let suspended = VcJwt::sign(credential(9), &kid, &key).unwrap();
+    assert_eq!(
+        suspended.verify_with_status(&resolver, &checker).err(),
+        Some(Error::CREDENTIAL_SUSPENDED)
+    );
+
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let forged = VcJwt::sign(credential(7), &kid, &other).unwrap();
+    assert!(!forged.verify_with_status(&resolver, &checker).unwrap());
+
+    let without_status = Credential::new(ISSUER_DID, "ExampleCredential", json!({}))
+        .to_value()
+        .unwrap();
+    assert!(check_credential_status(&without_status, &InMemoryStatusChecker::new()).is_ok());
+    assert_eq!(
+        check_credential_status(
+            &credential(1).to_value().unwrap(),
+            &InMemoryStatusChecker::new()
+        )
+        .err(),
+        Some(Error::STATUS_LIST_NOT_FOUND)
+    );
+
+    let entry = StatusListEntry::new(STATUS_LIST_ID, StatusPurpose::Revocation, 7);
+    assert!(checker.status(ISSUER_DID, &entry).unwrap());
+    assert_eq!(
+        checker.status("did:example:other", &entry).err(),
+        Some(Error::STATUS_LIST_ISSUER_MISMATCH)
+    );
+}
+
+#[cfg(feature = "http")]
+#[test]
+pub fn status_list_http_checker() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use did_crypto::{
+        http::{HttpFetcher, HttpResponse},
+        status_list::HttpStatusChecker,
+    };
+
+    struct ListFetcher {
+        body: String,
+        calls: AtomicUsize,
+    }
+
+    impl HttpFetcher for ListFetcher {
+        fn get(&self, url: &str) -> Result<HttpResponse, Error> {
+            assert_eq!(url, STATUS_LIST_ID);
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            Ok(HttpResponse {
+                body: self.body.clone().into_bytes(),
+                max_age: None,
+            })
+        }
+    }
+
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let kid = format!("{}#key-1", ISSUER_DID);
+    let list = status_list(STATUS_LIST_ID, StatusPurpose::Revocation, &[7]);
+    let list_jwt = VcJwt::sign(Credential::from_value(list.clone()).unwrap(), &kid, &key).unwrap();
+    let revoked = StatusListEntry::new(STATUS_LIST_ID, StatusPurpose::Revocation, 7);
+    let valid = StatusListEntry::new(STATUS_LIST_ID, StatusPurpose::Revocation, 1);
+
+    let checker = HttpStatusChecker::new(
+        ListFetcher {
+            body: String::from(list_jwt.to_token()),
+            calls: AtomicUsize::new(0),
+        },
+        IssuerResolver(key.verifying_key()),
+    );
+    assert!(checker.status(ISSUER_DID, &revoked).unwrap());
+    assert!(!checker.status(ISSUER_DID, &valid).unwrap());
+    assert_eq!(checker.fetcher().calls.load(Ordering::SeqCst), 1);
+
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let forged = HttpStatusChecker::new(
+        ListFetcher {
+            body: String::from(list_jwt.to_token()),
+            calls: AtomicUsize::new(0),
+        },
+        IssuerResolver(other.verifying_key()),
+    );
+    assert_eq!(
+        forged.status(ISSUER_DID, &revoked).err(),
+        Some(Error::STATUS_LIST_INVALID_CREDENTIAL)
+    );
+
+    let unsecured = ListFetcher {
+        body: list.to_string(),
+        calls: AtomicUsize::new(0),
+    };
+    let checker = HttpStatusChecker::new(unsecured, IssuerResolver(key.verifying_key()));
+    assert_eq!(
+        checker.status(ISSUER_DID, &revoked).err(),
+        Some(Error::STATUS_LIST_INVALID_CREDENTIAL)
+    );
+    let checker = checker.allow_unsecured(true);
+    assert!(checker.status(ISSUER_DID, &revoked).unwrap());
+    assert!(!checker.status(ISSUER_DID, &valid).unwrap());
+}
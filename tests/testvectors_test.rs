@@ -0,0 +1,68 @@
+#![cfg(feature = "testvectors")]
+
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::AnySigningKey,
+    errors::Error,
+    testvectors::{check_all, did_key_vectors, jws_vectors, JwsVector},
+};
+
+#[test]
+pub fn testvectors_check_all() {
+    let results = check_all();
+    assert_eq!(results.len(), jws_vectors().len() + did_key_vectors().len());
+    for (name, result) in results {
+        assert!(result.is_ok(), "{}", name);
+    }
+}
+
+#[test]
+pub fn testvectors_jws_es256() {
+    let vector = jws_vectors()
+        .into_iter()
+        .find(|vector| vector.alg == Algorithm::ES256)
+        .unwrap();
+
+    let signature = vector.generate().unwrap();
+    assert!(vector.verify_signature(&signature).is_ok());
+    assert!(vector.verify_key(vector.verifying_key().unwrap()).is_ok());
+    assert!(vector.compact().ends_with(vector.signature));
+
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let error = vector.verify_key(other.verifying_key()).err().unwrap();
+    assert_eq!(error, Error::TEST_VECTOR_MISMATCH);
+    assert_eq!(error.key(), Some(vector.name));
+
+    let tampered = JwsVector {
+        signing_input: "eyJhbGciOiJFUzI1NiJ9.e30",
+        ..vector
+    };
+    assert_eq!(tampered.check().err(), Some(Error::TEST_VECTOR_MISMATCH));
+}
+
+#[cfg(feature = "eddsa")]
+#[test]
+pub fn testvectors_eddsa_and_did_key() {
+    use did_crypto::testvectors::DidKeyVector;
+
+    let vector = jws_vectors()
+        .into_iter()
+        .find(|vector| vector.alg == Algorithm::EdDSA)
+        .unwrap();
+    assert_eq!(vector.generate().unwrap(), vector.signature);
+
+    let did_key = did_key_vectors()[0];
+    assert_eq!(did_key.generate().unwrap(), did_key.did);
+    assert_eq!(
+        did_key
+            .verify_did("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK")
+            .err(),
+        Some(Error::TEST_VECTOR_MISMATCH)
+    );
+
+    let wrong_seed = DidKeyVector {
+        seed: "0101010101010101010101010101010101010101010101010101010101010101",
+        ..did_key
+    };
+    assert_eq!(wrong_seed.check().err(), Some(Error::TEST_VECTOR_MISMATCH));
+}
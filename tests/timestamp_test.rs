@@ -0,0 +1,135 @@
+#![cfg(feature = "timestamp")]
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{TimeZone, Utc};
+use did_crypto::{
+    crypto::x509::certificate_pem_to_der,
+    errors::Error,
+    hash::HashAlgorithm,
+    timestamp::{TimestampRequest, TimestampToken, TsaClient, TsaTransport},
+};
+
+const DATA: &[u8] = b"did-crypto timestamp fixture";
+const NONCE: u64 = 0x0B6F4BD1F98A59D1;
+const TSA_ROOT: &str = "-----BEGIN CERTIFICATE-----
+MIIBhDCCASqgAwIBAgIUIoxL6MUwupfdXm0RUSITn2LbCxUwCgYIKoZIzj0EAwIw
+HzEdMBsGA1UEAwwUZGlkLWNyeXB0byBUZXN0IFJvb3QwIBcNMjYxMDE2MTUxMjIx
+WhgPMjEyNjA5MjIxNTEyMjFaMB8xHTAbBgNVBAMMFGRpZC1jcnlwdG8gVGVzdCBS
+b290MFkwEwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEgjCmgPJ466YMzZ24AwtZgNE2
+NwE/Pqw0MOaSPkybYKLvD6FaZUmKWZ9DguIiKTGnH4n5LtLoDyU2+6Z4Zxpiw6NC
+MEAwDwYDVR0TAQH/BAUwAwEB/zAOBgNVHQ8BAf8EBAMCAQYwHQYDVR0OBBYEFP0l
+0hRL7sOozILJ1q8eJdPiJ7ByMAoGCCqGSM49BAMCA0gAMEUCIQDR0YcNs6x37pRv
+q3io00IKoKAbWXCBacylm3VvVwHC5AIgONdHjRiX4M6qnPGErOHk0dqmGV++M/fU
+wWR4Nx0ixdc=
+-----END CERTIFICATE-----";
+const RESPONSE: &str =
+    "MIIDjjADAgEAMIIDhQYJKoZIhvcNAQcCoIIDdjCCA3ICAQMxDzANBglghkgBZQMEAgEFADByBgsq\
+hkiG9w0BCRABBKBjBGEwXwIBAQYEKgMEATAxMA0GCWCGSAFlAwQCAQUABCD6t3rY00z3yaKpyHNC\
+y3g9+uyH+7D9vtzSilf/ZpdcDwIBAhgPMjAyNjEwMTYxNTEyMjFaMAMCAQECCAtvS9H5ilnRoIIB\
+qjCCAaYwggFMoAMCAQICAQIwCgYIKoZIzj0EAwIwHzEdMBsGA1UEAwwUZGlkLWNyeXB0byBUZXN0\
+IFJvb3QwIBcNMjYxMDE2MTUxMjIxWhgPMjEyNjA5MjIxNTEyMjFaMB4xHDAaBgNVBAMME2RpZC1j\
+cnlwdG8gVGVzdCBUU0EwWTATBgcqhkjOPQIBBggqhkjOPQMBBwNCAASXdl6kpXYlGa+XXDXlxEoe\
+v2o0/KC29TLHRHSt48Bki6QNGSShGia5Le8S6+MtrP3RBW7VQQ5UlfYjoOPiakUao3gwdjAMBgNV\
+HRMBAf8EAjAAMA4GA1UdDwEB/wQEAwIHgDAWBgNVHSUBAf8EDDAKBggrBgEFBQcDCDAdBgNVHQ4E\
+FgQUupGC36fxfAT6+ZX/+YKiXqx4Da8wHwYDVR0jBBgwFoAU/SXSFEvuw6jMgsnWrx4l0+InsHIw\
+CgYIKoZIzj0EAwIDSAAwRQIge5NKIkfZbmcQiwJguUA2jERkJFfkigmHrFkZTQLFXSMCIQD8537M\
+pZ3yZ5770mjAOPR15XAPxnoHboBYJhwtRdblzTGCATgwggE0AgEBMCQwHzEdMBsGA1UEAwwUZGlk\
+LWNyeXB0byBUZXN0IFJvb3QCAQIwDQYJYIZIAWUDBAIBBQCggaQwGgYJKoZIhvcNAQkDMQ0GCyqG\
+SIb3DQEJEAEEMBwGCSqGSIb3DQEJBTEPFw0yNjEwMTYxNTEyMjFaMC8GCSqGSIb3DQEJBDEiBCBN\
+nsj24abygcKbSanW7JuCzyhBqB1WcjpOo3cC9IBdajA3BgsqhkiG9w0BCRACLzEoMCYwJDAiBCA5\
+35ISfNKX62/480LJYFWdw66rXYmw5uV8CSWtYJOuezAKBggqhkjOPQQDAgRHMEUCIGuCYxkvTVsW\
+p+/ktB6gU6wQtLxDT5KuuQ+IqZqpu2dWAiEA7Y+AcASX8tZvnvX6LSy/kIYqBOj05hnfrXLuKXqg\
+6co=";
+const REJECTED: &str = "300c300a02010230050c03626164";
+
+struct MockTransport(Vec<u8>);
+
+impl TsaTransport for MockTransport {
+    fn post(&self, _url: &str, request: &[u8]) -> Result<Vec<u8>, Error> {
+        assert!(!request.is_empty());
+        Ok(self.0.clone())
+    }
+}
+
+fn response() -> Vec<u8> {
+    STANDARD.decode(RESPONSE.replace('\n', "")).unwrap()
+}
+
+#[test]
+pub fn timestamp_client_and_verification() {
+    let client = TsaClient::with_transport("https://tsa.example", MockTransport(response()));
+    let token = client
+        .request(TimestampRequest::new(DATA).nonce(Some(NONCE)))
+        .unwrap();
+    assert_eq!(token.info().policy, "1.2.3.4.1");
+    assert_eq!(token.info().hash, HashAlgorithm::Sha256);
+    assert_eq!(token.info().serial_number, vec![2]);
+
+    let anchors = vec![certificate_pem_to_der(TSA_ROOT).unwrap()];
+    let gen_time = Utc.with_ymd_and_hms(2026, 10, 16, 15, 12, 21).unwrap();
+    assert_eq!(token.verify(DATA, &anchors).unwrap(), gen_time);
+    assert_eq!(token.gen_time(), gen_time);
+
+    let token = TimestampToken::from_der(token.to_der()).unwrap();
+    assert_eq!(
+        token
+            .verify_digest(&HashAlgorithm::Sha256.digest(DATA), &anchors)
+            .unwrap(),
+        gen_time
+    );
+    assert_eq!(
+        token.verify(b"other data", &anchors).unwrap_err(),
+        Error::TIMESTAMP_MISMATCH
+    );
+    assert_eq!(
+        token.verify(DATA, &[]).unwrap_err(),
+        Error::X509_CHAIN_UNTRUSTED
+    );
+}
+
+#[test]
+pub fn timestamp_response_checks() {
+    let client = TsaClient::with_transport("https://tsa.example", MockTransport(response()));
+    assert_eq!(
+        client
+            .request(TimestampRequest::new(DATA).nonce(Some(NONCE + 1)))
+            .unwrap_err(),
+        Error::TIMESTAMP_MISMATCH
+    );
+    assert_eq!(
+        client.timestamp(b"other data").unwrap_err(),
+        Error::TIMESTAMP_MISMATCH
+    );
+
+    let client = TsaClient::with_transport(
+        "https://tsa.example",
+        MockTransport(hex::decode(REJECTED).unwrap()),
+    );
+    assert_eq!(
+        client.timestamp(DATA).unwrap_err(),
+        Error::TIMESTAMP_REJECTED
+    );
+
+    let mut tampered = response();
+    let index = tampered.len() - 40;
+    tampered[index] ^= 0x01;
+    let token =
+        TimestampToken::from_response(&tampered, &TimestampRequest::new(DATA).nonce(Some(NONCE)))
+            .unwrap();
+    let anchors = vec![certificate_pem_to_der(TSA_ROOT).unwrap()];
+    assert!(token.verify(DATA, &anchors).is_err());
+
+    assert!(TimestampToken::from_der(b"not a token")
+        .unwrap_err()
+        .is_malformed_input());
+    assert_eq!(
+        TimestampRequest::from_digest(HashAlgorithm::Sha256, &[0u8; 20]).unwrap_err(),
+        Error::TIMESTAMP_MALFORMED
+    );
+    assert!(
+        TimestampRequest::from_digest(HashAlgorithm::Sha384, &[0u8; 48])
+            .unwrap()
+            .to_der()
+            .is_ok()
+    );
+}
@@ -0,0 +1,135 @@
+#![cfg(feature = "tracing")]
+
+use std::{
+    fmt::Debug,
+    sync::{Arc, Mutex},
+};
+
+use did_crypto::{
+    algorithms::Algorithm, crypto::any::AnySigningKey, redact::Redactor, signer::sign,
+    verifier::verify,
+};
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+type Records = Arc<Mutex<Vec<Vec<(String, String)>>>>;
+
+#[derive(Default)]
+struct Fields(Vec<(String, String)>);
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0
+            .push((String::from(field.name()), String::from(value)));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0
+            .push((String::from(field.name()), format!("{:?}", value)));
+    }
+}
+
+#[derive(Clone, Default)]
+struct TestSubscriber {
+    spans: Records,
+    events: Records,
+}
+
+impl Subscriber for TestSubscriber {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = Fields::default();
+        span.record(&mut fields);
+
+        let mut spans = self.spans.lock().unwrap();
+        spans.push(fields.0);
+        Id::from_u64(spans.len() as u64)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        self.events.lock().unwrap().push(fields.0);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+fn field<'a>(fields: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    fields
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+#[test]
+pub fn sign_and_verify_are_traced() {
+    let subscriber = TestSubscriber::default();
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let fingerprint = key.fingerprint().unwrap();
+
+    tracing::subscriber::with_default(subscriber.clone(), || {
+        let signature = sign("did:example:123", &key, Algorithm::ES256).unwrap();
+        assert!(verify(
+            "did:example:456",
+            &signature,
+            key.verifying_key(),
+            Algorithm::ES256
+        )
+        .is_ok());
+        assert!(sign("did:example:123", &key, Algorithm::ES384).is_err());
+    });
+
+    let spans = subscriber.spans.lock().unwrap();
+    assert_eq!(spans.len(), 3);
+    for span in spans.iter() {
+        assert!(field(span, "alg").is_some());
+        assert_eq!(field(span, "key"), Some(fingerprint.as_str()));
+    }
+    assert_eq!(field(&spans[0], "operation"), Some("sign"));
+    assert_eq!(field(&spans[1], "operation"), Some("verify"));
+
+    let events = subscriber.events.lock().unwrap();
+    let outcomes: Vec<&str> = events
+        .iter()
+        .filter_map(|event| field(event, "outcome"))
+        .collect();
+    assert_eq!(outcomes, vec!["ok", "invalid", "error"]);
+    assert!(events
+        .iter()
+        .all(|event| field(event, "duration_us").is_some()));
+}
+
+#[test]
+pub fn redacted_tokens_are_traced() {
+    let subscriber = TestSubscriber::default();
+    let token = format!(
+        "{}.{}.c2lnbmF0dXJl",
+        base64_url::encode(r#"{"alg":"ES256"}"#),
+        base64_url::encode(r#"{"iss":"did:example:issuer","email":"alice@example.com"}"#)
+    );
+
+    tracing::subscriber::with_default(subscriber.clone(), || {
+        Redactor::new().trace("received token", &token);
+    });
+
+    let events = subscriber.events.lock().unwrap();
+    assert_eq!(events.len(), 1);
+    let traced = field(&events[0], "token").unwrap();
+    assert!(traced.contains("did:example:issuer"));
+    assert!(!traced.contains("alice@example.com"));
+    assert!(!traced.contains("c2lnbmF0dXJl"));
+    assert_eq!(field(&events[0], "message"), Some("received token"));
+}
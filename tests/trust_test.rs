@@ -0,0 +1,220 @@
+use std::time::Duration;
+
+use chrono::{TimeDelta, Utc};
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    data_integrity::{ProofOptions, EDDSA_JCS_2022},
+    errors::Error,
+    oid4vp::{AuthorizationResponse, PresentationRequest, ResponseJwt, VpToken},
+    resolver::{DidResolver, ASSERTION_METHOD, AUTHENTICATION},
+    trust::TrustPolicy,
+    vc::{Credential, VcCose, VcJwt},
+};
+use serde_json::{json, Value};
+
+const ISSUER_DID: &str = "did:web:registrar.university.example";
+const HOLDER_DID: &str = "did:example:holder";
+const CLIENT_ID: &str = "https://verifier.example.org/cb";
+const NONCE: &str = "n-0S6_WzA2Mj";
+
+struct KeyResolver(Vec<(&'static str, AnyVerifyingKey)>);
+
+impl DidResolver for KeyResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        let key = match self.0.iter().find(|(val, _)| *val == did) {
+            Some((_, key)) => key,
+            None => return Err(Error::DID_RESOLUTION_ERROR),
+        };
+
+        Ok(json!({
+            "id": did,
+            "verificationMethod": [{
+                "id": "#key-1",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": key.to_jwk().unwrap(),
+            }],
+            ASSERTION_METHOD: ["#key-1"],
+            AUTHENTICATION: ["#key-1"],
+        }))
+    }
+}
+
+fn credential(issuer: &str) -> Credential {
+    Credential::new(
+        issuer,
+        "ExampleDegreeCredential",
+        json!({"id": HOLDER_DID, "degree": "Bachelor of Science"}),
+    )
+}
+
+#[test]
+pub fn trust_policy_checks() {
+    let now = Utc::now();
+    let policy = TrustPolicy::new()
+        .trust_issuer("did:web:*.university.example")
+        .trust_issuer("did:key:z6Mk*")
+        .require_algorithm(Algorithm::EdDSA)
+        .require_cryptosuite(EDDSA_JCS_2022)
+        .max_age(Duration::from_secs(3600));
+
+    assert!(policy.is_trusted_issuer(ISSUER_DID));
+    assert!(policy.is_trusted_issuer("did:key:z6MkhaXgBZDvotDkL5257faiztiGiC2QtKLGpbnnEGta2doK"));
+    assert!(!policy.is_trusted_issuer("did:web:university.example"));
+    assert!(!policy.is_trusted_issuer("did:web:evil.example/university.example.x"));
+    assert!(!policy.is_trusted_issuer("did:key:zQ3sh"));
+    assert!(TrustPolicy::new().is_trusted_issuer("did:example:anyone"));
+
+    let error = policy.check_issuer("did:example:other").err().unwrap();
+    assert_eq!(error, Error::TRUST_POLICY_ISSUER_NOT_TRUSTED);
+    assert_eq!(error.key(), Some("did:example:other"));
+    assert!(error.is_policy_violation());
+    assert!(!Error::SIGNATURE_INVALID.is_policy_violation());
+
+    assert!(policy.check_algorithm(Algorithm::EdDSA).is_ok());
+    assert_eq!(
+        policy.check_algorithm(Algorithm::ES256).err(),
+        Some(Error::TRUST_POLICY_ALGORITHM_NOT_ALLOWED)
+    );
+    assert_eq!(
+        policy.check_cryptosuite("ecdsa-rdfc-2019").err(),
+        Some(Error::TRUST_POLICY_ALGORITHM_NOT_ALLOWED)
+    );
+
+    assert!(policy
+        .check_age(Some(now - TimeDelta::minutes(59)), now)
+        .is_ok());
+    assert_eq!(
+        policy
+            .check_age(Some(now - TimeDelta::minutes(61)), now)
+            .err(),
+        Some(Error::TRUST_POLICY_CREDENTIAL_TOO_OLD)
+    );
+    assert_eq!(
+        policy.check_age(None, now).err(),
+        Some(Error::TRUST_POLICY_CREDENTIAL_TOO_OLD)
+    );
+    assert!(TrustPolicy::new().check_age(None, now).is_ok());
+
+    let options = ProofOptions::new(EDDSA_JCS_2022, "did:key:z6Mk#z6Mk").with_created(now);
+    assert!(policy.check_proof("did:key:z6Mk", &options, now).is_ok());
+    assert_eq!(
+        policy
+            .check_proof("did:key:z6Mk", &options, now + TimeDelta::hours(2))
+            .err(),
+        Some(Error::TRUST_POLICY_CREDENTIAL_TOO_OLD)
+    );
+}
+
+#[test]
+pub fn trust_policy_vc_verification() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let resolver = KeyResolver(vec![(ISSUER_DID, key.verifying_key())]);
+    let kid = format!("{}#key-1", ISSUER_DID);
+    let trusted = TrustPolicy::new().trust_issuer("did:web:*.university.example");
+
+    let vc_jwt = VcJwt::sign(credential(ISSUER_DID), &kid, &key).unwrap();
+    assert!(vc_jwt.verify_with_policy(&resolver, &trusted).unwrap());
+    assert_eq!(
+        vc_jwt
+            .verify_with_policy(
+                &resolver,
+                &trusted.clone().require_algorithm(Algorithm::EdDSA)
+            )
+            .err(),
+        Some(Error::TRUST_POLICY_ALGORITHM_NOT_ALLOWED)
+    );
+    assert_eq!(
+        vc_jwt
+            .verify_with_policy(
+                &resolver,
+                &TrustPolicy::new().trust_issuer("did:web:gov.example")
+            )
+            .err(),
+        Some(Error::TRUST_POLICY_ISSUER_NOT_TRUSTED)
+    );
+
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let forged = VcJwt::sign(credential(ISSUER_DID), &kid, &other).unwrap();
+    assert!(!forged.verify_with_policy(&resolver, &trusted).unwrap());
+
+    let fresh = credential(ISSUER_DID).with_valid_from(Utc::now() - TimeDelta::days(1));
+    let vc_cose = VcCose::sign(fresh, &kid, &key).unwrap();
+    assert!(vc_cose
+        .verify_with_policy(
+            &resolver,
+            &trusted.clone().max_age(Duration::from_secs(172800))
+        )
+        .unwrap());
+    assert_eq!(
+        vc_cose
+            .verify_with_policy(&resolver, &trusted.max_age(Duration::from_secs(3600)))
+            .err(),
+        Some(Error::TRUST_POLICY_CREDENTIAL_TOO_OLD)
+    );
+}
+
+#[test]
+pub fn trust_policy_presentation_verification() {
+    let issuer_key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let holder_key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let untrusted_did = "did:web:diploma-mill.example";
+    let resolver = KeyResolver(vec![
+        (ISSUER_DID, issuer_key.verifying_key()),
+        (untrusted_did, issuer_key.verifying_key()),
+        (HOLDER_DID, holder_key.verifying_key()),
+    ]);
+    let policy = TrustPolicy::new().trust_issuer(ISSUER_DID);
+    let request = PresentationRequest::new(CLIENT_ID, NONCE);
+    let holder_kid = format!("{}#key-1", HOLDER_DID);
+
+    let present = |issuer: &str| {
+        let vc_jwt = VcJwt::sign(
+            credential(issuer),
+            &format!("{}#key-1", issuer),
+            &issuer_key,
+        )
+        .unwrap();
+        VpToken::sign(
+            json!({
+                "@context": ["https://www.w3.org/ns/credentials/v2"],
+                "type": ["VerifiablePresentation"],
+                "verifiableCredential": [vc_jwt.to_token()],
+            }),
+            &request,
+            &holder_kid,
+            &holder_key,
+        )
+        .unwrap()
+    };
+
+    let vp_token = present(ISSUER_DID);
+    assert_eq!(vp_token.credentials().unwrap().len(), 1);
+    assert!(vp_token
+        .verify_with_policy(&request, &resolver, &policy)
+        .unwrap());
+
+    let response = AuthorizationResponse::new(Value::from(vp_token.to_token()), &request);
+    let response_jwt = ResponseJwt::sign(response, &request, &holder_kid, &holder_key).unwrap();
+    assert!(response_jwt
+        .verify_presentations_with_policy(&request, &resolver, &policy)
+        .unwrap());
+
+    let vp_token = present(untrusted_did);
+    assert!(vp_token.verify(&request, &resolver).unwrap());
+    assert_eq!(
+        vp_token
+            .verify_with_policy(&request, &resolver, &policy)
+            .err(),
+        Some(Error::TRUST_POLICY_ISSUER_NOT_TRUSTED)
+    );
+    let response = AuthorizationResponse::new(Value::from(vp_token.to_token()), &request);
+    let response_jwt = ResponseJwt::sign(response, &request, &holder_kid, &holder_key).unwrap();
+    assert_eq!(
+        response_jwt
+            .verify_presentations_with_policy(&request, &resolver, &policy)
+            .err(),
+        Some(Error::TRUST_POLICY_ISSUER_NOT_TRUSTED)
+    );
+}
@@ -0,0 +1,354 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, TimeDelta, Utc};
+use coset::{cbor::Value as CborValue, ContentType, Header, Label};
+use did_crypto::{
+    algorithms::Algorithm,
+    cose::{protected_header, CoseSign1},
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    errors::Error,
+    resolver::{DidResolver, ASSERTION_METHOD},
+    vc::{
+        validate_schemas, Credential, CredentialSchema, Issuer, VcCose, VcJwt, JSON_SCHEMA_TYPE,
+        VC_CTY, VC_JWT_TYP,
+    },
+};
+use serde_json::{json, Value};
+
+const ISSUER_DID: &str = "did:example:issuer";
+const CREDENTIAL_JSON: &str = r#"{
+  "@context": ["https://www.w3.org/ns/credentials/v2", "https://www.w3.org/ns/credentials/examples/v2"],
+  "id": "http://university.example/credentials/3732",
+  "type": ["VerifiableCredential", "ExampleDegreeCredential"],
+  "issuer": {"id": "did:example:issuer", "name": "Example University"},
+  "validFrom": "2010-01-01T00:00:00Z",
+  "credentialSubject": {
+    "id": "did:example:ebfeb1f712ebc6f1c276e12ec21",
+    "degree": {"type": "ExampleBachelorDegree", "name": "Bachelor of Science and Arts"}
+  },
+  "credentialSchema": {"id": "https://example.org/examples/degree.json", "type": "JsonSchema"}
+}"#;
+
+struct IssuerResolver(AnyVerifyingKey);
+
+impl DidResolver for IssuerResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        if did != ISSUER_DID {
+            return Err(Error::DID_RESOLUTION_ERROR);
+        }
+
+        Ok(json!({
+            "id": did,
+            "verificationMethod": [{
+                "id": "#key-1",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": self.0.to_jwk().unwrap(),
+            }],
+            ASSERTION_METHOD: ["#key-1"],
+        }))
+    }
+}
+
+fn decode(segment: &str) -> Value {
+    serde_json::from_slice(&URL_SAFE_NO_PAD.decode(segment).unwrap()).unwrap()
+}
+
+#[test]
+pub fn vc_jwt_round_trip() {
+    let credential =
+        Credential::from_value(serde_json::from_str(CREDENTIAL_JSON).unwrap()).unwrap();
+    assert_eq!(credential.issuer.id(), ISSUER_DID);
+    assert_eq!(
+        credential.valid_from,
+        Some(DateTime::from_timestamp(1262304000, 0).unwrap())
+    );
+    assert!(credential.properties.contains_key("credentialSchema"));
+
+    for alg in [Algorithm::ES256, Algorithm::EdDSA] {
+        let key = AnySigningKey::generate(alg).unwrap();
+        let kid = format!("{}#key-1", ISSUER_DID);
+        let vc_jwt = VcJwt::sign(credential.clone(), &kid, &key).unwrap();
+
+        let parts: Vec<&str> = vc_jwt.to_token().split('.').collect();
+        let header = decode(parts[0]);
+        assert_eq!(header["typ"], VC_JWT_TYP);
+        assert_eq!(header["cty"], VC_CTY);
+        assert_eq!(header["kid"], kid.as_str());
+        let payload = decode(parts[1]);
+        assert_eq!(payload["iss"], ISSUER_DID);
+        assert_eq!(payload["jti"], "http://university.example/credentials/3732");
+        assert_eq!(payload["sub"], "did:example:ebfeb1f712ebc6f1c276e12ec21");
+        assert_eq!(payload["nbf"], 1262304000);
+        assert_eq!(payload["credentialSubject"], credential.credential_subject);
+
+        let parsed = VcJwt::from_token(vc_jwt.to_token()).unwrap();
+        assert_eq!(parsed.credential, credential);
+        assert_eq!(parsed.issued_at, vc_jwt.issued_at);
+        assert!(parsed.verify(key.verifying_key()).unwrap());
+        assert!(parsed
+            .verify_with_resolver(&IssuerResolver(key.verifying_key()))
+            .unwrap());
+
+        let other = AnySigningKey::generate(alg).unwrap();
+        assert!(!parsed
+            .verify_with_resolver(&IssuerResolver(other.verifying_key()))
+            .unwrap());
+    }
+}
+
+#[test]
+pub fn vc_jwt_validity_and_issuer_binding() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let resolver = IssuerResolver(key.verifying_key());
+    let subject = json!({"id": "did:example:holder", "name": "Alice"});
+
+    let expired = Credential::new(ISSUER_DID, "ExampleCredential", subject.clone())
+        .with_valid_until(Utc::now() - TimeDelta::days(1));
+    let vc_jwt = VcJwt::sign(expired, "did:example:issuer#key-1", &key).unwrap();
+    assert!(!vc_jwt.verify(key.verifying_key()).unwrap());
+
+    let credential = Credential::new("did:example:other", "ExampleCredential", subject);
+    assert_eq!(
+        credential.issuer,
+        Issuer::Id(String::from("did:example:other"))
+    );
+    let vc_jwt = VcJwt::sign(credential, "did:example:issuer#key-1", &key).unwrap();
+    assert!(vc_jwt.verify(key.verifying_key()).unwrap());
+    assert!(!vc_jwt.verify_with_resolver(&resolver).unwrap());
+}
+
+#[test]
+pub fn vc_jwt_rejects_invalid_tokens() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let credential =
+        Credential::from_value(serde_json::from_str(CREDENTIAL_JSON).unwrap()).unwrap();
+    let token = VcJwt::sign(credential, "did:example:issuer#key-1", &key)
+        .unwrap()
+        .to_token()
+        .to_string();
+    let parts: Vec<&str> = token.split('.').collect();
+
+    let header = URL_SAFE_NO_PAD.encode(r#"{"typ":"JWT","alg":"ES256","kid":"k"}"#);
+    assert_eq!(
+        VcJwt::from_token(&format!("{}.{}.{}", header, parts[1], parts[2])).err(),
+        Some(Error::VC_JWT_INVALID_TYP)
+    );
+
+    let mut payload = decode(parts[1]);
+    payload["iss"] = Value::from("did:example:mallory");
+    let payload = URL_SAFE_NO_PAD.encode(serde_json::to_vec(&payload).unwrap());
+    assert_eq!(
+        VcJwt::from_token(&format!("{}.{}.{}", parts[0], payload, parts[2])).err(),
+        Some(Error::VC_JWT_CLAIM_MISMATCH)
+    );
+
+    let payload = URL_SAFE_NO_PAD.encode(r#"{"iss":"did:example:issuer","iat":0}"#);
+    assert_eq!(
+        VcJwt::from_token(&format!("{}.{}.{}", parts[0], payload, parts[2])).err(),
+        Some(Error::VC_MALFORMED)
+    );
+
+    let hmac = AnySigningKey::generate(Algorithm::HS256).unwrap();
+    assert_eq!(
+        VcJwt::sign(
+            Credential::new(ISSUER_DID, "ExampleCredential", json!({})),
+            "k",
+            &hmac
+        )
+        .err(),
+        Some(Error::ALGORITHM_NOT_ALLOWED)
+    );
+}
+
+#[test]
+pub fn vc_cose_round_trip() {
+    let credential =
+        Credential::from_value(serde_json::from_str(CREDENTIAL_JSON).unwrap()).unwrap();
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let kid = format!("{}#key-1", ISSUER_DID);
+    let vc_cose = VcCose::sign(credential.clone(), &kid, &key).unwrap();
+    let bytes = vc_cose.to_bytes().unwrap();
+    assert_eq!(bytes[0], 0xd2);
+
+    let sign1 = CoseSign1::from_bytes(&bytes).unwrap();
+    let header = &sign1.0.protected.header;
+    assert_eq!(
+        header.content_type,
+        Some(ContentType::Text(String::from("application/vc")))
+    );
+    assert_eq!(
+        header.rest,
+        vec![(
+            Label::Int(16),
+            CborValue::Text(String::from("application/vc+cose"))
+        )]
+    );
+    assert_eq!(
+        serde_json::from_slice::<Value>(sign1.payload().unwrap()).unwrap(),
+        credential.to_value().unwrap()
+    );
+
+    let parsed = VcCose::from_bytes(&bytes).unwrap();
+    assert_eq!(parsed.credential, credential);
+    assert_eq!(parsed.kid, kid);
+    assert!(parsed.verify(key.verifying_key()).unwrap());
+    assert!(parsed
+        .verify_with_resolver(&IssuerResolver(key.verifying_key()))
+        .unwrap());
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    assert!(!parsed.verify(other.verifying_key()).unwrap());
+
+    let plain = CoseSign1::sign(
+        protected_header(Algorithm::ES256, kid.as_bytes()).unwrap(),
+        Header::default(),
+        sign1.payload().unwrap(),
+        &[],
+        &key,
+    )
+    .unwrap();
+    assert_eq!(
+        VcCose::from_bytes(&plain.to_bytes().unwrap()).err(),
+        Some(Error::VC_COSE_INVALID_TYP)
+    );
+}
+
+#[test]
+pub fn vc_schema_validation_hook() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let credential =
+        Credential::from_value(serde_json::from_str(CREDENTIAL_JSON).unwrap()).unwrap();
+    assert_eq!(
+        credential.credential_schemas().unwrap(),
+        vec![CredentialSchema {
+            id: String::from("https://example.org/examples/degree.json"),
+            schema_type: String::from(JSON_SCHEMA_TYPE),
+        }]
+    );
+
+    let has_degree = |schema: &CredentialSchema, credential: &Value| match credential
+        ["credentialSubject"]
+        .get("degree")
+    {
+        Some(_) => Ok(()),
+        None => Err(Error::VC_SCHEMA_VALIDATION_FAILED.with_key(&schema.id)),
+    };
+
+    let vc_jwt = VcJwt::sign(credential.clone(), "did:example:issuer#key-1", &key).unwrap();
+    assert!(vc_jwt
+        .verify_with_schema(key.verifying_key(), &has_degree)
+        .unwrap());
+    assert!(vc_jwt
+        .verify_with_resolver_and_schema(&IssuerResolver(key.verifying_key()), &has_degree)
+        .unwrap());
+
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    assert!(!vc_jwt
+        .verify_with_schema(other.verifying_key(), &has_degree)
+        .unwrap());
+
+    let mut invalid = credential.clone();
+    invalid.credential_subject = json!({"id": "did:example:holder"});
+    let vc_cose = VcCose::sign(invalid.clone(), "did:example:issuer#key-1", &key).unwrap();
+    let error = vc_cose
+        .verify_with_schema(key.verifying_key(), &has_degree)
+        .err()
+        .unwrap();
+    assert_eq!(error, Error::VC_SCHEMA_VALIDATION_FAILED);
+    assert!(error.is_schema_error());
+    assert!(!error.is_malformed_input());
+    assert_eq!(
+        error.key(),
+        Some("https://example.org/examples/degree.json")
+    );
+
+    let unschematized = Credential::new(ISSUER_DID, "ExampleCredential", json!({}));
+    assert!(validate_schemas(&unschematized, &has_degree).is_ok());
+
+    let multiple = unschematized
+        .with_schema("https://example.org/a.json", JSON_SCHEMA_TYPE)
+        .with_schema("https://example.org/b.json", JSON_SCHEMA_TYPE);
+    assert_eq!(multiple.credential_schemas().unwrap().len(), 2);
+    assert_eq!(
+        validate_schemas(&multiple, &has_degree).err(),
+        Some(Error::VC_SCHEMA_VALIDATION_FAILED)
+    );
+
+    invalid
+        .properties
+        .insert(String::from("credentialSchema"), json!("not-an-object"));
+    assert_eq!(
+        invalid.credential_schemas().err(),
+        Some(Error::VC_MALFORMED)
+    );
+}
+
+#[cfg(feature = "json-schema")]
+#[test]
+pub fn vc_json_schema_validation() {
+    use did_crypto::vc::{JsonSchemaValidator, SchemaValidator};
+
+    let schema_id = "https://example.org/examples/degree.json";
+    let validator = JsonSchemaValidator::new()
+        .schema(
+            schema_id,
+            &json!({
+                "$schema": "https://json-schema.org/draft/2020-12/schema",
+                "type": "object",
+                "required": ["credentialSubject"],
+                "properties": {
+                    "credentialSubject": {
+                        "type": "object",
+                        "required": ["degree"],
+                        "properties": {
+                            "degree": {
+                                "type": "object",
+                                "required": ["type", "name"],
+                            }
+                        }
+                    }
+                }
+            }),
+        )
+        .unwrap();
+
+    let key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let credential =
+        Credential::from_value(serde_json::from_str(CREDENTIAL_JSON).unwrap()).unwrap();
+    let vc_jwt = VcJwt::sign(credential.clone(), "did:example:issuer#key-1", &key).unwrap();
+    assert!(vc_jwt
+        .verify_with_schema(key.verifying_key(), &validator)
+        .unwrap());
+
+    let mut invalid = credential.clone();
+    invalid.credential_subject["degree"] = json!({"type": "ExampleBachelorDegree"});
+    let vc_jwt = VcJwt::sign(invalid, "did:example:issuer#key-1", &key).unwrap();
+    assert_eq!(
+        vc_jwt
+            .verify_with_schema(key.verifying_key(), &validator)
+            .err(),
+        Some(Error::VC_SCHEMA_VALIDATION_FAILED)
+    );
+
+    let unknown = CredentialSchema {
+        id: String::from("https://example.org/unknown.json"),
+        schema_type: String::from(JSON_SCHEMA_TYPE),
+    };
+    assert_eq!(
+        validator.validate(&unknown, &json!({})).err(),
+        Some(Error::VC_SCHEMA_NOT_FOUND)
+    );
+    let credential_schema = CredentialSchema {
+        id: String::from(schema_id),
+        schema_type: String::from("JsonSchemaCredential"),
+    };
+    assert_eq!(
+        validator.validate(&credential_schema, &json!({})).err(),
+        Some(Error::VC_SCHEMA_UNSUPPORTED)
+    );
+    assert_eq!(
+        JsonSchemaValidator::new()
+            .schema(schema_id, &json!({"type": 12}))
+            .err(),
+        Some(Error::VC_SCHEMA_MALFORMED)
+    );
+}
@@ -0,0 +1,174 @@
+#![cfg(all(feature = "es256k", feature = "eddsa"))]
+
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        policy::KeyPolicy,
+    },
+    encoding::base58_encode,
+    errors::Error,
+    resolver::{resolve_verifying_keys, DidResolver, AUTHENTICATION},
+    verification_method::{
+        VerificationMethod, VerificationMethodType, MULTIKEY_CONTEXT, PUBLIC_KEY_BASE58,
+        PUBLIC_KEY_JWK, PUBLIC_KEY_MULTIBASE,
+    },
+};
+use serde_json::{json, Value};
+
+const DID: &str = "did:example:123";
+const EDDSA_PUBLIC_KEY_HEX: &str =
+    "7b6df71975950d5ea15ac090c57d462f73d3a48644fbcf2c6d5db838adf136b5";
+
+struct StaticResolver(Value);
+
+impl DidResolver for StaticResolver {
+    fn resolve(&self, _did: &str) -> Result<Value, Error> {
+        Ok(self.0.clone())
+    }
+}
+
+fn ed25519() -> AnyVerifyingKey {
+    AnyVerifyingKey::from_hex(Algorithm::EdDSA, EDDSA_PUBLIC_KEY_HEX).unwrap()
+}
+
+#[test]
+pub fn verification_method_round_trips() {
+    let secp256k1 = AnySigningKey::generate(Algorithm::ES256K)
+        .unwrap()
+        .verifying_key();
+    let p256 = AnySigningKey::generate(Algorithm::ES256)
+        .unwrap()
+        .verifying_key();
+
+    for (method_type, key, member) in [
+        (
+            VerificationMethodType::JsonWebKey2020,
+            p256.clone(),
+            PUBLIC_KEY_JWK,
+        ),
+        (
+            VerificationMethodType::Ed25519VerificationKey2018,
+            ed25519(),
+            PUBLIC_KEY_BASE58,
+        ),
+        (
+            VerificationMethodType::Ed25519VerificationKey2020,
+            ed25519(),
+            PUBLIC_KEY_MULTIBASE,
+        ),
+        (
+            VerificationMethodType::EcdsaSecp256k1VerificationKey2019,
+            secp256k1.clone(),
+            PUBLIC_KEY_JWK,
+        ),
+        (
+            VerificationMethodType::Multikey,
+            p256.clone(),
+            PUBLIC_KEY_MULTIBASE,
+        ),
+        (
+            VerificationMethodType::Multikey,
+            secp256k1.clone(),
+            PUBLIC_KEY_MULTIBASE,
+        ),
+    ] {
+        let method =
+            VerificationMethod::new(&format!("{}#key-1", DID), DID, method_type, key).unwrap();
+        let value = method.to_value().unwrap();
+        assert_eq!(value["type"], method_type.name());
+        assert!(value.get(member).is_some());
+
+        let parsed = VerificationMethod::from_value(&value).unwrap();
+        assert_eq!(parsed, method);
+        assert_eq!(parsed.to_value().unwrap(), value);
+    }
+
+    assert_eq!(
+        VerificationMethodType::from_name("Multikey"),
+        Some(VerificationMethodType::Multikey)
+    );
+    assert_eq!(VerificationMethodType::Multikey.context(), MULTIKEY_CONTEXT);
+    assert_eq!(
+        VerificationMethodType::preferred_for(&ed25519()),
+        VerificationMethodType::Ed25519VerificationKey2020
+    );
+}
+
+#[test]
+pub fn verification_method_conversions() {
+    let method = VerificationMethod::from_value(&json!({
+        "id": "#key-1",
+        "type": "Ed25519VerificationKey2018",
+        "controller": DID,
+        "publicKeyBase58": base58_encode(hex::decode(EDDSA_PUBLIC_KEY_HEX).unwrap()),
+    }))
+    .unwrap();
+    assert_eq!(method.key, ed25519());
+
+    let value = method
+        .clone()
+        .with_type(VerificationMethodType::Multikey)
+        .unwrap()
+        .to_value()
+        .unwrap();
+    assert!(value[PUBLIC_KEY_MULTIBASE]
+        .as_str()
+        .unwrap()
+        .starts_with("z6Mk"));
+    assert_eq!(method.to_jwk().unwrap().kid.as_deref(), Some("#key-1"));
+
+    let secp256k1 = AnySigningKey::generate(Algorithm::ES256K)
+        .unwrap()
+        .verifying_key();
+    let method = VerificationMethod::from_value(&json!({
+        "id": "#key-2",
+        "type": "EcdsaSecp256k1VerificationKey2019",
+        "controller": DID,
+        "publicKeyHex": hex::encode(secp256k1.to_sec1_bytes(true).unwrap()),
+    }))
+    .unwrap();
+    assert_eq!(method.key, secp256k1);
+
+    assert_eq!(
+        method
+            .with_type(VerificationMethodType::Ed25519VerificationKey2020)
+            .unwrap_err(),
+        Error::VERIFICATION_METHOD_UNSUPPORTED
+    );
+    assert_eq!(
+        VerificationMethod::from_value(&json!({
+            "id": "#key-3",
+            "type": "RsaVerificationKey2018",
+            "controller": DID,
+            "publicKeyPem": "",
+        }))
+        .unwrap_err(),
+        Error::VERIFICATION_METHOD_UNSUPPORTED
+    );
+    assert_eq!(
+        VerificationMethod::from_value(&json!({
+            "id": "#key-4",
+            "type": "Multikey",
+            "controller": DID,
+        }))
+        .unwrap_err(),
+        Error::VERIFICATION_METHOD_MALFORMED
+    );
+}
+
+#[test]
+pub fn resolver_reads_multikey_methods() {
+    let method =
+        VerificationMethod::new("#key-1", DID, VerificationMethodType::Multikey, ed25519())
+            .unwrap();
+    let resolver = StaticResolver(json!({
+        "id": DID,
+        "verificationMethod": [method.to_value().unwrap()],
+        "authentication": ["#key-1"],
+    }));
+
+    let keys =
+        resolve_verifying_keys(&resolver, DID, AUTHENTICATION, &KeyPolicy::default()).unwrap();
+    assert_eq!(keys, vec![ed25519()]);
+}
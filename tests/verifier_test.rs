@@ -0,0 +1,351 @@
+use std::str::FromStr;
+
+use chrono::Utc;
+use did_crypto::{
+    algorithms::{Algorithm, AlgorithmPolicy},
+    crypto::{any::AnySigningKey, hmac::HMACKey, signature::Signature, SignFromKey},
+    errors::{Error, VerifyError},
+    jwk::Jwks,
+    jwt::{Header, Payload, JWT},
+    verifier::{
+        verify_batch, verify_batch_multi, verify_strict, verify_with_options, PreparedVerifier,
+        VerificationOptions,
+    },
+};
+use serde_json::Value;
+
+const MESSAGE: &str = "did:example:123";
+
+#[test]
+pub fn verify_strict_reports_failure_reasons() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let signature = key
+        .sign_bytes(MESSAGE.as_bytes(), Algorithm::ES256)
+        .unwrap();
+    let verifying_key = key.verifying_key();
+
+    assert!(verify_strict(
+        String::from(MESSAGE),
+        signature.clone(),
+        &verifying_key,
+        Algorithm::ES256
+    )
+    .is_ok());
+    assert_eq!(
+        verify_strict(
+            String::from("did:example:456"),
+            signature.clone(),
+            &verifying_key,
+            Algorithm::ES256
+        ),
+        Err(VerifyError::InvalidSignature)
+    );
+    assert_eq!(
+        verify_strict(
+            String::from(MESSAGE),
+            String::from("not*base64"),
+            &verifying_key,
+            Algorithm::ES256
+        ),
+        Err(VerifyError::MalformedSignature)
+    );
+    assert_eq!(
+        verify_strict(
+            String::from(MESSAGE),
+            base64_url::encode(&[0u8; 12]),
+            &verifying_key,
+            Algorithm::ES256
+        ),
+        Err(VerifyError::MalformedSignature)
+    );
+    assert_eq!(
+        verify_strict(
+            String::from(MESSAGE),
+            signature.clone(),
+            &verifying_key,
+            Algorithm::ES384
+        ),
+        Err(VerifyError::AlgorithmMismatch)
+    );
+    assert_eq!(
+        verify_strict(
+            String::from(MESSAGE),
+            signature,
+            &verifying_key,
+            Algorithm::HS256
+        ),
+        Err(VerifyError::KeyMismatch)
+    );
+}
+
+#[test]
+pub fn verify_strict_hmac_key_mismatch() {
+    let key = HMACKey::new(String::from("secret"));
+    let signature = key
+        .sign_bytes(MESSAGE.as_bytes(), Algorithm::HS256)
+        .unwrap();
+
+    assert!(verify_strict(
+        String::from(MESSAGE),
+        signature.clone(),
+        &key,
+        Algorithm::HS256
+    )
+    .is_ok());
+    assert_eq!(
+        verify_strict(String::from(MESSAGE), signature, &key, Algorithm::ES256),
+        Err(VerifyError::KeyMismatch)
+    );
+    assert_eq!(
+        VerifyError::from(Error::JWT_EXPIRED),
+        VerifyError::Failed(Error::JWT_EXPIRED)
+    );
+}
+
+#[test]
+pub fn verification_options_malformed_input() {
+    let key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let verifying_key = key.verifying_key();
+    let lenient = VerificationOptions::lenient();
+
+    assert_eq!(
+        verify_with_options(
+            String::from(MESSAGE),
+            String::from("AAAA"),
+            &verifying_key,
+            Algorithm::EdDSA,
+            &VerificationOptions::default()
+        )
+        .err()
+        .unwrap(),
+        Error::SIGNATURE_IDENTIFICATION_FAILED
+    );
+    assert!(!verify_with_options(
+        String::from(MESSAGE),
+        String::from("AAAA"),
+        &verifying_key,
+        Algorithm::EdDSA,
+        &lenient
+    )
+    .unwrap());
+    assert!(verify_with_options(
+        String::from(MESSAGE),
+        String::from("AAAA"),
+        &verifying_key,
+        Algorithm::ES256,
+        &lenient
+    )
+    .is_err());
+
+    let mut jwt = JWT {
+        header: Header::new(String::new(), Algorithm::EdDSA),
+        payload: Payload(Value::from_str("{\"exp\": \"tomorrow\"}").unwrap()),
+        signature: None,
+    };
+    jwt.sign(&key).unwrap();
+    assert_eq!(
+        jwt.validate(&verifying_key).err().unwrap(),
+        Error::JWT_PAYLOAD_FIELD_EXP_IDENTIFICATION_ERROR
+    );
+    assert!(!jwt.validate_with_options(&verifying_key, &lenient).unwrap());
+
+    jwt.payload =
+        Payload(Value::from_str(&format!("{{\"exp\": {}}}", Utc::now().timestamp() + 60)).unwrap());
+    jwt.sign(&key).unwrap();
+    assert!(jwt.validate_with_options(&verifying_key, &lenient).unwrap());
+}
+
+#[test]
+pub fn verification_options_combine() {
+    let all = VerificationOptions::default();
+    let partial = all.accept_partial_signatures(true);
+
+    assert!(all.combine([Ok(true), Ok(true)]).unwrap());
+    assert!(!all.combine([Ok(true), Ok(false)]).unwrap());
+    assert!(partial.combine([Ok(true), Ok(false)]).unwrap());
+    assert!(!partial.combine([Ok(false), Ok(false)]).unwrap());
+    assert!(!partial.combine([]).unwrap());
+    assert!(partial
+        .combine([Ok(true), Err(Error::DECODING_ERROR)])
+        .is_err());
+    assert!(partial
+        .report_malformed_input(false)
+        .combine([Ok(true), Err(Error::DECODING_ERROR)])
+        .unwrap());
+}
+
+#[test]
+pub fn verification_algorithm_policy() {
+    let policy = AlgorithmPolicy::new(&[Algorithm::ES256, Algorithm::EdDSA]);
+    let options = VerificationOptions::lenient().algorithm_policy(policy);
+    let payload =
+        Value::from_str(&format!("{{\"exp\": {}}}", Utc::now().timestamp() + 60)).unwrap();
+
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let mut jwt = JWT {
+        header: Header::new(String::new(), Algorithm::ES256),
+        payload: Payload(payload.clone()),
+        signature: None,
+    };
+    jwt.sign(&key).unwrap();
+    assert!(jwt
+        .validate_with_options(key.verifying_key(), &options)
+        .unwrap());
+    assert!(JWT::from_token_with_policy(&jwt.to_token().unwrap(), &policy).is_ok());
+
+    let hmac = HMACKey::new(String::from("secret"));
+    let mut downgraded = JWT {
+        header: Header::new(String::new(), Algorithm::HS256),
+        payload: Payload(payload),
+        signature: None,
+    };
+    downgraded.sign(&hmac).unwrap();
+    assert_eq!(
+        downgraded
+            .validate_with_options(&hmac, &options)
+            .err()
+            .unwrap(),
+        Error::ALGORITHM_NOT_ALLOWED
+    );
+    assert_eq!(
+        JWT::from_token_with_policy(&downgraded.to_token().unwrap(), &policy)
+            .err()
+            .unwrap(),
+        Error::ALGORITHM_NOT_ALLOWED
+    );
+    assert_eq!(
+        verify_with_options(
+            String::from(MESSAGE),
+            hmac.sign_bytes(MESSAGE.as_bytes(), Algorithm::HS256)
+                .unwrap(),
+            &hmac,
+            Algorithm::HS256,
+            &options
+        )
+        .err()
+        .unwrap(),
+        Error::ALGORITHM_NOT_ALLOWED
+    );
+}
+
+#[test]
+pub fn batch_verification() {
+    let messages: Vec<Vec<u8>> = (0..8)
+        .map(|i| format!("did:example:{}", i).into_bytes())
+        .collect();
+
+    for alg in [Algorithm::EdDSA, Algorithm::ES256] {
+        let key = AnySigningKey::generate(alg).unwrap();
+        let signatures: Vec<Signature> = messages
+            .iter()
+            .map(|message| key.sign_signature(message, alg).unwrap())
+            .collect();
+        let mut items: Vec<(&[u8], &Signature)> = messages
+            .iter()
+            .map(|message| message.as_slice())
+            .zip(signatures.iter())
+            .collect();
+
+        let results = verify_batch(&items, key.verifying_key());
+        assert_eq!(results.len(), messages.len());
+        assert!(results.iter().all(|result| *result.as_ref().unwrap()));
+
+        items[3].0 = b"did:example:tampered";
+        let results = verify_batch(&items, key.verifying_key());
+        for (i, result) in results.into_iter().enumerate() {
+            assert_eq!(result.unwrap(), i != 3);
+        }
+    }
+
+    let eddsa = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let p256 = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let eddsa_signature = eddsa
+        .sign_signature(MESSAGE.as_bytes(), Algorithm::EdDSA)
+        .unwrap();
+    let p256_signature = p256
+        .sign_signature(MESSAGE.as_bytes(), Algorithm::ES256)
+        .unwrap();
+    let results = verify_batch_multi(&[
+        (MESSAGE.as_bytes(), &eddsa_signature, eddsa.verifying_key()),
+        (MESSAGE.as_bytes(), &p256_signature, p256.verifying_key()),
+        (MESSAGE.as_bytes(), &p256_signature, eddsa.verifying_key()),
+    ]);
+    assert!(results[0].as_ref().unwrap());
+    assert!(results[1].as_ref().unwrap());
+    assert!(results[2].is_err());
+    assert!(verify_batch(&[], eddsa.verifying_key()).is_empty());
+}
+
+#[test]
+pub fn prepared_verifier_tokens() {
+    let now = Utc::now().timestamp();
+    let keys: Vec<AnySigningKey> = [Algorithm::ES256, Algorithm::EdDSA]
+        .into_iter()
+        .map(|alg| AnySigningKey::generate(alg).unwrap())
+        .collect();
+    let mut jwks = Jwks::default();
+    for (i, key) in keys.iter().enumerate() {
+        let mut jwk = key.verifying_key().to_jwk().unwrap();
+        jwk.kid = Some(format!("key-{}", i));
+        jwks.keys.push(jwk);
+    }
+
+    let verifier = PreparedVerifier::new();
+    assert_eq!(verifier.add_jwks(&jwks).unwrap(), 2);
+    assert_eq!(verifier.kids(), vec!["key-0", "key-1"]);
+
+    let sign = |i: usize, exp: i64| {
+        let mut jwt = JWT {
+            header: Header::new(format!("key-{}", i), keys[i].algorithm()),
+            payload: Payload(Value::from_str(&format!("{{\"exp\": {}}}", exp)).unwrap()),
+            signature: None,
+        };
+        jwt.sign(&keys[i]).unwrap();
+        jwt.to_token().unwrap()
+    };
+
+    for i in 0..2 {
+        assert!(verifier.verify_token(&sign(i, now + 60)).unwrap());
+        assert!(verifier.verify_token(&sign(i, now + 120)).unwrap());
+        assert!(!verifier.verify_token(&sign(i, now - 60)).unwrap());
+    }
+    assert_eq!(verifier.cached_headers(), 2);
+
+    let token = sign(0, now + 60);
+    let (content, _) = token.rsplit_once('.').unwrap();
+    let forged = format!(
+        "{}.{}",
+        content,
+        keys[1]
+            .sign_bytes(content.as_bytes(), Algorithm::EdDSA)
+            .unwrap()
+    );
+    assert!(!verifier.verify_token(&forged).unwrap());
+
+    let message = MESSAGE.as_bytes();
+    let signature = keys[1].sign_bytes(message, Algorithm::EdDSA).unwrap();
+    assert!(verifier
+        .verify("key-1", message, &signature, Algorithm::EdDSA)
+        .unwrap());
+    assert_eq!(
+        verifier
+            .verify("key-0", message, &signature, Algorithm::EdDSA)
+            .err(),
+        Some(Error::JWT_ALGORITHM_KEY_MISMATCH)
+    );
+
+    assert!(verifier.remove("key-0"));
+    assert_eq!(verifier.cached_headers(), 1);
+    assert_eq!(
+        verifier.verify_token(&token).err().unwrap(),
+        Error::KEYSTORE_KEY_NOT_FOUND
+    );
+
+    let limited = PreparedVerifier::new()
+        .max_headers(0)
+        .options(VerificationOptions::lenient());
+    limited.add_key("key-1", keys[1].verifying_key());
+    assert!(limited.verify_token(&sign(1, now + 60)).unwrap());
+    assert_eq!(limited.cached_headers(), 0);
+    assert!(!limited.verify_token("!!.e30.c2ln").unwrap());
+}
@@ -0,0 +1,88 @@
+#![cfg(target_arch = "wasm32")]
+
+use chrono::Utc;
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{any::AnySigningKey, hmac::HMACKey, SignFromKey, VerifyFromKey},
+    jwt::{Header, Payload, JWT},
+    signer::sign,
+    verifier::verify,
+};
+use serde_json::json;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+const MESSAGE: &str = "did:example:123";
+
+#[wasm_bindgen_test]
+pub fn sign_and_verify_all_algorithms() {
+    for alg in [
+        Algorithm::ES256,
+        Algorithm::ES256K,
+        Algorithm::ES384,
+        Algorithm::ES512,
+        Algorithm::EdDSA,
+        Algorithm::RS256,
+    ] {
+        let key = AnySigningKey::generate(alg).unwrap();
+        let signature = key.sign_bytes(MESSAGE.as_bytes(), alg).unwrap();
+
+        assert!(key
+            .verifying_key()
+            .verify_bytes(MESSAGE.as_bytes(), &signature, alg)
+            .unwrap());
+    }
+
+    let key = HMACKey::new(String::from("secret"));
+    let signature = sign(MESSAGE, &key, Algorithm::HS256).unwrap();
+    assert!(verify(MESSAGE, &signature, &key, Algorithm::HS256).unwrap());
+}
+
+#[wasm_bindgen_test]
+pub fn generated_keys_use_js_randomness() {
+    let first = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let second = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+
+    assert_ne!(
+        *first.to_private_bytes().unwrap(),
+        *second.to_private_bytes().unwrap()
+    );
+}
+
+#[wasm_bindgen_test]
+pub fn jwt_round_trip() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let mut jwt = JWT {
+        header: Header::new(String::new(), Algorithm::ES256),
+        payload: Payload(json!({ "iss": MESSAGE, "exp": Utc::now().timestamp() + 60 })),
+        signature: None,
+    };
+    jwt.sign(&key).unwrap();
+
+    let token = jwt.to_token().unwrap();
+    assert!(JWT::from_token(&token)
+        .unwrap()
+        .validate(key.verifying_key())
+        .unwrap());
+}
+
+#[cfg(feature = "wasm-bindings")]
+#[wasm_bindgen_test]
+pub fn wasm_bindings_round_trip() {
+    use did_crypto::wasm;
+
+    for alg in ["ES256", "EdDSA"] {
+        let private_key = wasm::generate_key(alg).unwrap();
+        let public_key = wasm::public_key(alg, &private_key).unwrap();
+
+        let signature = wasm::sign(alg, &private_key, MESSAGE.as_bytes()).unwrap();
+        assert!(wasm::verify(alg, &public_key, MESSAGE.as_bytes(), &signature).unwrap());
+        assert!(!wasm::verify(alg, &public_key, b"did:example:456", &signature).unwrap());
+
+        let payload = json!({ "iss": MESSAGE, "exp": Utc::now().timestamp() + 60 }).to_string();
+        let token = wasm::sign_jwt(alg, &private_key, "key-1", &payload).unwrap();
+        assert!(wasm::verify_jwt(&token, &public_key).unwrap());
+        assert_eq!(wasm::decode_jwt_payload(&token).unwrap(), payload);
+    }
+
+    assert!(wasm::generate_key("none").is_err());
+}
@@ -0,0 +1,177 @@
+#![cfg(feature = "eddsa")]
+
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::AnySigningKey,
+    errors::Error,
+    keystore::{InMemoryKeyStore, KeyStore},
+    resolver::{resolve_verifying_keys, DidResolver, AUTHENTICATION},
+    verification_method::VerificationMethodType,
+    web::{WebDid, WebDocumentBuilder},
+};
+use serde_json::{json, Value};
+
+struct StaticResolver(Value);
+
+impl DidResolver for StaticResolver {
+    fn resolve(&self, _did: &str) -> Result<Value, Error> {
+        Ok(self.0.clone())
+    }
+}
+
+#[test]
+pub fn web_did_urls() {
+    let did = WebDid::parse("did:web:w3c-ccg.github.io").unwrap();
+    assert_eq!(
+        did.document_url(),
+        "https://w3c-ccg.github.io/.well-known/did.json"
+    );
+
+    let did = WebDid::parse("did:web:w3c-ccg.github.io:user:alice").unwrap();
+    assert_eq!(did.path, vec!["user", "alice"]);
+    assert_eq!(did.document_path(), "/user/alice/did.json");
+    assert_eq!(
+        did.document_url(),
+        "https://w3c-ccg.github.io/user/alice/did.json"
+    );
+
+    let did = WebDid::new("example.com:3000", &["user"]).unwrap();
+    assert_eq!(did.did, "did:web:example.com%3A3000:user");
+    assert_eq!(did.domain, "example.com:3000");
+    assert_eq!(did.document_url(), "https://example.com:3000/user/did.json");
+    assert_eq!(
+        did.method_id("did:web:other#key-1"),
+        "did:web:example.com%3A3000:user#key-1"
+    );
+
+    for invalid in ["did:web:", "did:key:z6Mk", "did:web:example.com::user"] {
+        assert_eq!(WebDid::parse(invalid).unwrap_err(), Error::WEB_INVALID_DID);
+    }
+}
+
+#[test]
+pub fn web_document_generation() {
+    let store = InMemoryKeyStore::new();
+    store
+        .add("key-1", AnySigningKey::generate(Algorithm::EdDSA).unwrap())
+        .unwrap();
+    store
+        .add("key-2", AnySigningKey::generate(Algorithm::ES256).unwrap())
+        .unwrap();
+    store
+        .add(
+            "revoked",
+            AnySigningKey::generate(Algorithm::ES256).unwrap(),
+        )
+        .unwrap();
+    store.revoke("revoked").unwrap();
+
+    let did = WebDid::parse("did:web:example.com").unwrap();
+    let builder = WebDocumentBuilder::from_keystore(did, &store)
+        .unwrap()
+        .service("hub", "LinkedDomains", "https://example.com");
+    let document = builder.build().unwrap();
+
+    assert_eq!(document["id"], "did:web:example.com");
+    assert_eq!(document["verificationMethod"].as_array().unwrap().len(), 2);
+    assert_eq!(
+        document[AUTHENTICATION],
+        json!(["did:web:example.com#key-1", "did:web:example.com#key-2"])
+    );
+    assert_eq!(document["service"][0]["id"], "did:web:example.com#hub");
+
+    let jwks = builder.jwks().unwrap();
+    assert_eq!(jwks["keys"][1]["kid"], "did:web:example.com#key-2");
+
+    let keys = resolve_verifying_keys(
+        &StaticResolver(document),
+        "did:web:example.com#key-2",
+        AUTHENTICATION,
+        &Default::default(),
+    )
+    .unwrap();
+    assert_eq!(keys, vec![store.get("key-2").unwrap().verifying_key()]);
+
+    let document = builder
+        .method_type(VerificationMethodType::Multikey)
+        .build()
+        .unwrap();
+    assert_eq!(document["verificationMethod"][0]["type"], "Multikey");
+    assert!(document["verificationMethod"][0]["publicKeyMultibase"].is_string());
+}
+
+#[test]
+pub fn web_document_consistency() {
+    let did = WebDid::parse("did:web:example.com").unwrap();
+    let key_1 = AnySigningKey::generate(Algorithm::EdDSA)
+        .unwrap()
+        .verifying_key();
+    let key_2 = AnySigningKey::generate(Algorithm::EdDSA)
+        .unwrap()
+        .verifying_key();
+    let hosted = WebDocumentBuilder::new(did.clone())
+        .key("key-1", key_1.clone())
+        .key("key-2", key_2.clone())
+        .build()
+        .unwrap();
+
+    let local = WebDocumentBuilder::new(did.clone())
+        .key("key-1", key_1.clone())
+        .key("key-2", key_2.clone())
+        .method_type(VerificationMethodType::Ed25519VerificationKey2020);
+    assert!(local.check(&hosted).unwrap().is_consistent());
+
+    let rotated = AnySigningKey::generate(Algorithm::EdDSA)
+        .unwrap()
+        .verifying_key();
+    let local = WebDocumentBuilder::new(did)
+        .key("key-1", rotated)
+        .key("key-3", key_2);
+    let report = local.check(&hosted).unwrap();
+    assert!(!report.is_consistent());
+    assert!(report.id_matches);
+    assert_eq!(report.mismatched, vec!["did:web:example.com#key-1"]);
+    assert_eq!(report.missing, vec!["did:web:example.com#key-3"]);
+    assert_eq!(report.unexpected, vec!["did:web:example.com#key-2"]);
+
+    let mut moved = hosted.clone();
+    moved["id"] = json!("did:web:example.org");
+    assert!(!local.check(&moved).unwrap().id_matches);
+}
+
+#[cfg(feature = "http")]
+#[test]
+pub fn web_resolver_fetches_hosted_document() {
+    use did_crypto::{
+        http::{HttpFetcher, HttpResponse},
+        web::WebResolver,
+    };
+
+    struct HostedFetcher(Value);
+
+    impl HttpFetcher for HostedFetcher {
+        fn get(&self, url: &str) -> Result<HttpResponse, Error> {
+            assert_eq!(url, "https://example.com/.well-known/did.json");
+            Ok(HttpResponse {
+                body: serde_json::to_vec(&self.0).unwrap(),
+                max_age: None,
+            })
+        }
+    }
+
+    let did = WebDid::parse("did:web:example.com").unwrap();
+    let builder = WebDocumentBuilder::new(did).key(
+        "key-1",
+        AnySigningKey::generate(Algorithm::EdDSA)
+            .unwrap()
+            .verifying_key(),
+    );
+    let fetcher = HostedFetcher(builder.build().unwrap());
+    assert!(builder.check_hosted(&fetcher).unwrap().is_consistent());
+
+    let resolver = WebResolver::new(fetcher);
+    assert_eq!(
+        resolver.resolve("did:web:example.com").unwrap()["id"],
+        "did:web:example.com"
+    );
+}
@@ -0,0 +1,264 @@
+use coset::{iana, CoseKeyBuilder};
+use did_crypto::{
+    algorithms::Algorithm,
+    cose::verifying_key_from_cose,
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        ecdsa::signature_to_der,
+        SignFromKey,
+    },
+    errors::Error,
+    resolver::{DidResolver, AUTHENTICATION},
+    webauthn::{AssertionValidation, WebAuthnAssertion},
+};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+
+const RP_ID: &str = "example.com";
+const ORIGIN: &str = "https://example.com";
+const CHALLENGE: &[u8] = b"did-crypto-webauthn-challenge";
+const DID: &str = "did:example:passkey";
+
+fn assertion(
+    key: &AnySigningKey,
+    client_data: Value,
+    flags: u8,
+    sign_count: u32,
+) -> WebAuthnAssertion {
+    let mut authenticator_data = Sha256::digest(RP_ID.as_bytes()).to_vec();
+    authenticator_data.push(flags);
+    authenticator_data.extend_from_slice(&sign_count.to_be_bytes());
+    let client_data_json = client_data.to_string().into_bytes();
+
+    let mut assertion = WebAuthnAssertion {
+        credential_id: b"credential-id-01".to_vec(),
+        authenticator_data,
+        client_data_json,
+        signature: Vec::new(),
+        user_handle: None,
+    };
+    let signature = key
+        .sign_bytes(&assertion.signed_data(), key.algorithm())
+        .unwrap();
+    assertion.signature = match key.algorithm() {
+        Algorithm::EdDSA => base64_url::decode(&signature).unwrap(),
+        alg => signature_to_der(&signature, alg).unwrap(),
+    };
+    assertion
+}
+
+fn client_data(challenge: &[u8]) -> Value {
+    json!({
+        "type": "webauthn.get",
+        "challenge": base64_url::encode(challenge),
+        "origin": ORIGIN,
+        "crossOrigin": false,
+    })
+}
+
+struct PasskeyResolver(AnyVerifyingKey);
+
+impl DidResolver for PasskeyResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        Ok(json!({
+            "id": did,
+            "verificationMethod": [{
+                "id": "#passkey-1",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": self.0.to_jwk().unwrap(),
+            }],
+            AUTHENTICATION: ["#passkey-1"],
+        }))
+    }
+}
+
+#[test]
+pub fn webauthn_assertion_verification() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let jwk = key.verifying_key().to_jwk().unwrap();
+    let cose_key = CoseKeyBuilder::new_ec2_pub_key(
+        iana::EllipticCurve::P_256,
+        base64_url::decode(&jwk.x.unwrap()).unwrap(),
+        base64_url::decode(&jwk.y.unwrap()).unwrap(),
+    )
+    .algorithm(iana::Algorithm::ES256)
+    .build();
+    let validation = AssertionValidation::new(RP_ID, ORIGIN, CHALLENGE);
+
+    let signed = assertion(&key, client_data(CHALLENGE), 0x05, 7);
+    assert!(signed.verify(&key.verifying_key(), &validation).unwrap());
+    assert!(signed.verify_cose(&cose_key, &validation).unwrap());
+    assert_eq!(signed.assertion_data().unwrap().sign_count, 7);
+    assert!(signed
+        .verify(
+            &key.verifying_key(),
+            &AssertionValidation::new(RP_ID, ORIGIN, CHALLENGE)
+                .require_user_verification()
+                .previous_sign_count(6)
+        )
+        .unwrap());
+
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    assert!(!signed.verify(&other.verifying_key(), &validation).unwrap());
+    let mut tampered = signed.clone();
+    tampered.authenticator_data[36] ^= 1;
+    assert!(!tampered.verify(&key.verifying_key(), &validation).unwrap());
+
+    assert_eq!(
+        signed
+            .verify(
+                &key.verifying_key(),
+                &AssertionValidation::new(RP_ID, ORIGIN, b"other")
+            )
+            .err(),
+        Some(Error::WEBAUTHN_CLIENT_DATA_MISMATCH)
+    );
+    assert_eq!(
+        signed
+            .verify(
+                &key.verifying_key(),
+                &AssertionValidation::new(RP_ID, "https://evil.example", CHALLENGE)
+            )
+            .err(),
+        Some(Error::WEBAUTHN_CLIENT_DATA_MISMATCH)
+    );
+    assert_eq!(
+        signed
+            .verify(
+                &key.verifying_key(),
+                &AssertionValidation::new("other.example", ORIGIN, CHALLENGE)
+            )
+            .err(),
+        Some(Error::WEBAUTHN_RP_ID_MISMATCH)
+    );
+    assert_eq!(
+        signed
+            .verify(
+                &key.verifying_key(),
+                &AssertionValidation::new(RP_ID, ORIGIN, CHALLENGE).previous_sign_count(7)
+            )
+            .err(),
+        Some(Error::WEBAUTHN_SIGN_COUNT_REGRESSED)
+    );
+
+    let absent = assertion(&key, client_data(CHALLENGE), 0x00, 0);
+    assert_eq!(
+        absent.verify(&key.verifying_key(), &validation).err(),
+        Some(Error::WEBAUTHN_USER_NOT_PRESENT)
+    );
+    let unverified = assertion(&key, client_data(CHALLENGE), 0x01, 0);
+    assert!(unverified
+        .verify(
+            &key.verifying_key(),
+            &AssertionValidation::new(RP_ID, ORIGIN, CHALLENGE).previous_sign_count(0)
+        )
+        .unwrap());
+    assert_eq!(
+        unverified
+            .verify(
+                &key.verifying_key(),
+                &AssertionValidation::new(RP_ID, ORIGIN, CHALLENGE).require_user_verification()
+            )
+            .err(),
+        Some(Error::WEBAUTHN_USER_NOT_VERIFIED)
+    );
+
+    let mut cross_origin = client_data(CHALLENGE);
+    cross_origin["crossOrigin"] = json!(true);
+    let cross_origin = assertion(&key, cross_origin, 0x01, 0);
+    assert_eq!(
+        cross_origin.verify(&key.verifying_key(), &validation).err(),
+        Some(Error::WEBAUTHN_CLIENT_DATA_MISMATCH)
+    );
+    assert!(cross_origin
+        .verify(
+            &key.verifying_key(),
+            &AssertionValidation::new(RP_ID, ORIGIN, CHALLENGE).allow_cross_origin()
+        )
+        .unwrap());
+
+    let mut create = client_data(CHALLENGE);
+    create["type"] = json!("webauthn.create");
+    assert_eq!(
+        assertion(&key, create, 0x01, 0)
+            .verify(&key.verifying_key(), &validation)
+            .err(),
+        Some(Error::WEBAUTHN_CLIENT_DATA_MISMATCH)
+    );
+}
+
+#[test]
+pub fn webauthn_assertion_json_and_did() {
+    let key = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let signed = assertion(&key, client_data(CHALLENGE), 0x05, 1);
+    let json = json!({
+        "id": base64_url::encode(&signed.credential_id),
+        "rawId": base64_url::encode(&signed.credential_id),
+        "type": "public-key",
+        "response": {
+            "authenticatorData": base64_url::encode(&signed.authenticator_data),
+            "clientDataJSON": base64_url::encode(&signed.client_data_json),
+            "signature": base64_url::encode(&signed.signature),
+            "userHandle": base64_url::encode(b"user-1"),
+        },
+    })
+    .to_string();
+
+    let parsed = WebAuthnAssertion::from_json(&json).unwrap();
+    assert_eq!(parsed.user_handle, Some(b"user-1".to_vec()));
+    assert_eq!(parsed.client_data().unwrap().origin, ORIGIN);
+    assert_eq!(
+        WebAuthnAssertion::from_json("{}").err(),
+        Some(Error::WEBAUTHN_ASSERTION_MALFORMED)
+    );
+
+    let validation = AssertionValidation::new(RP_ID, ORIGIN, CHALLENGE);
+    let resolver = PasskeyResolver(key.verifying_key());
+    assert!(parsed.verify_with_did(&resolver, DID, &validation).unwrap());
+    assert!(parsed
+        .verify_with_did(&resolver, &format!("{}#passkey-1", DID), &validation)
+        .unwrap());
+
+    let other = AnySigningKey::generate(Algorithm::ES256).unwrap();
+    let resolver = PasskeyResolver(other.verifying_key());
+    assert!(!parsed.verify_with_did(&resolver, DID, &validation).unwrap());
+
+    let mut truncated = parsed.clone();
+    truncated.authenticator_data.truncate(20);
+    assert_eq!(
+        truncated.verify_with_did(&resolver, DID, &validation).err(),
+        Some(Error::WEBAUTHN_ASSERTION_MALFORMED)
+    );
+}
+
+#[test]
+#[cfg(feature = "eddsa")]
+pub fn webauthn_assertion_eddsa() {
+    let key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let jwk = key.verifying_key().to_jwk().unwrap();
+    let cose_key = CoseKeyBuilder::new_okp_key()
+        .param(
+            iana::OkpKeyParameter::Crv as i64,
+            coset::cbor::value::Value::from(iana::EllipticCurve::Ed25519 as i64),
+        )
+        .param(
+            iana::OkpKeyParameter::X as i64,
+            coset::cbor::value::Value::Bytes(base64_url::decode(&jwk.x.unwrap()).unwrap()),
+        )
+        .build();
+    assert_eq!(
+        verifying_key_from_cose(&cose_key).unwrap().algorithm(),
+        Algorithm::EdDSA
+    );
+
+    let signed = assertion(&key, client_data(CHALLENGE), 0x05, 0);
+    let validation = AssertionValidation::new(RP_ID, ORIGIN, CHALLENGE);
+    assert!(signed.verify_cose(&cose_key, &validation).unwrap());
+
+    let unsupported = CoseKeyBuilder::new_symmetric_key(vec![0; 32]).build();
+    assert_eq!(
+        signed.verify_cose(&unsupported, &validation).err(),
+        Some(Error::COSE_KEY_UNSUPPORTED)
+    );
+}
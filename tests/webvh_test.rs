@@ -0,0 +1,213 @@
+#![cfg(feature = "eddsa")]
+
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::AnySigningKey,
+    errors::Error,
+    webvh::{multikey, next_key_hash, DidLog, WebvhDid, SCID_PLACEHOLDER, WEBVH_METHOD},
+};
+use serde_json::{json, Map, Value};
+
+fn document(did: &str, key: &AnySigningKey) -> Value {
+    json!({
+        "@context": ["https://www.w3.org/ns/did/v1"],
+        "id": did,
+        "verificationMethod": [{
+            "id": format!("{}#key-1", did),
+            "type": "Multikey",
+            "controller": did,
+            "publicKeyMultibase": multikey(&key.verifying_key()).unwrap(),
+        }],
+        "assertionMethod": [format!("{}#key-1", did)],
+    })
+}
+
+fn create(update_key: &AnySigningKey, parameters: Map<String, Value>) -> DidLog {
+    let template = WebvhDid::template("example.com", &["dids", "issuer"]).unwrap();
+    assert_eq!(
+        template,
+        format!("did:webvh:{}:example.com:dids:issuer", SCID_PLACEHOLDER)
+    );
+    let signing = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    DidLog::create(document(&template, &signing), parameters, update_key).unwrap()
+}
+
+#[test]
+pub fn webvh_create_update_and_rotate() {
+    let update_key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let mut log = create(&update_key, Map::new());
+
+    let resolution = log.verify().unwrap();
+    let did = WebvhDid::parse(&resolution.did).unwrap();
+    assert_eq!(did.scid, resolution.parameters.scid);
+    assert_eq!(did.log_url(), "https://example.com/dids/issuer/did.jsonl");
+    assert!(!resolution.did.contains(SCID_PLACEHOLDER));
+    assert_eq!(resolution.document["id"], resolution.did.as_str());
+    assert_eq!(resolution.parameters.method, WEBVH_METHOD);
+    assert_eq!(
+        resolution.parameters.update_keys,
+        vec![multikey(&update_key.verifying_key()).unwrap()]
+    );
+    assert!(resolution.version_id.starts_with("1-"));
+    assert_eq!(log.entries[0].proof[0]["cryptosuite"], "eddsa-jcs-2022");
+
+    let signing = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    log.update(document(&resolution.did, &signing), &update_key)
+        .unwrap();
+    assert!(log.verify().unwrap().version_id.starts_with("2-"));
+
+    let new_key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    log.rotate(&[new_key.verifying_key()], &[], &update_key)
+        .unwrap();
+    assert_eq!(
+        log.update(document(&resolution.did, &signing), &update_key)
+            .err(),
+        Some(Error::WEBVH_UNAUTHORIZED)
+    );
+    assert_eq!(log.entries.len(), 3);
+    log.update(document(&resolution.did, &signing), &new_key)
+        .unwrap();
+
+    let parsed = DidLog::from_jsonl(&log.to_jsonl().unwrap()).unwrap();
+    assert_eq!(parsed, log);
+    let resolution = parsed.verify().unwrap();
+    assert!(resolution.version_id.starts_with("4-"));
+    assert_eq!(
+        resolution.document["verificationMethod"][0]["publicKeyMultibase"],
+        multikey(&signing.verifying_key()).unwrap()
+    );
+
+    log.deactivate(&new_key).unwrap();
+    assert!(log.verify().unwrap().parameters.deactivated);
+    assert_eq!(
+        log.update(document(&resolution.did, &signing), &new_key)
+            .err(),
+        Some(Error::WEBVH_DEACTIVATED)
+    );
+}
+
+#[test]
+pub fn webvh_pre_rotation() {
+    let update_key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let next_key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let mut parameters = Map::new();
+    parameters.insert(
+        String::from("nextKeyHashes"),
+        json!([next_key_hash(&next_key.verifying_key()).unwrap()]),
+    );
+    let mut log = create(&update_key, parameters);
+
+    let other = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    for (keys, signer) in [
+        (vec![next_key.verifying_key()], &update_key),
+        (vec![other.verifying_key()], &other),
+    ] {
+        assert_eq!(
+            log.rotate(&keys, &[], signer).err(),
+            Some(Error::WEBVH_UNAUTHORIZED)
+        );
+    }
+    let state = log.entries[0].state.clone();
+    assert_eq!(
+        log.update(state, &update_key).err(),
+        Some(Error::WEBVH_UNAUTHORIZED)
+    );
+
+    log.rotate(&[next_key.verifying_key()], &[], &next_key)
+        .unwrap();
+    assert!(log.verify().unwrap().parameters.next_key_hashes.is_empty());
+}
+
+#[test]
+pub fn webvh_rejects_tampered_logs() {
+    let update_key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let mut log = create(&update_key, Map::new());
+    let did = log.verify().unwrap().did;
+    let signing = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    log.update(document(&did, &signing), &update_key).unwrap();
+
+    let mut tampered = log.clone();
+    tampered.entries[1].state["assertionMethod"] = json!([]);
+    assert_eq!(tampered.verify().err(), Some(Error::WEBVH_INVALID_LOG));
+
+    let mut tampered = log.clone();
+    tampered.entries.swap(0, 1);
+    assert_eq!(tampered.verify().err(), Some(Error::WEBVH_INVALID_LOG));
+
+    let mut tampered = log.clone();
+    tampered.entries.remove(0);
+    assert_eq!(tampered.verify().err(), Some(Error::WEBVH_INVALID_LOG));
+
+    let mut tampered = log.clone();
+    let other = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    tampered.entries[1].proof[0]["verificationMethod"] = Value::from(format!(
+        "did:key:{0}#{0}",
+        multikey(&other.verifying_key()).unwrap()
+    ));
+    assert_eq!(tampered.verify().err(), Some(Error::WEBVH_UNAUTHORIZED));
+
+    let mut tampered = log.clone();
+    tampered.entries[1]
+        .parameters
+        .insert(String::from("witness"), json!({"threshold": 1}));
+    assert_eq!(tampered.verify().err(), Some(Error::WEBVH_INVALID_LOG));
+
+    let moved = document(&did.replace("example.com", "example.org"), &signing);
+    assert_eq!(
+        log.update(moved, &update_key).err(),
+        Some(Error::WEBVH_INVALID_LOG)
+    );
+
+    assert_eq!(
+        DidLog::from_jsonl("{not json").err(),
+        Some(Error::WEBVH_INVALID_LOG)
+    );
+    for invalid in [
+        "did:web:example.com",
+        "did:webvh:example.com",
+        "did:webvh::example.com",
+    ] {
+        let error = WebvhDid::parse(invalid).err().unwrap();
+        assert_eq!(error, Error::WEBVH_INVALID_DID);
+        assert!(error.is_malformed_input());
+    }
+}
+
+#[cfg(feature = "http")]
+#[test]
+pub fn webvh_resolver_fetches_log() {
+    use did_crypto::{
+        http::{HttpFetcher, HttpResponse},
+        resolver::DidResolver,
+        webvh::WebvhResolver,
+    };
+
+    struct LogFetcher(String);
+
+    impl HttpFetcher for LogFetcher {
+        fn get(&self, url: &str) -> Result<HttpResponse, Error> {
+            assert_eq!(url, "https://example.com/dids/issuer/did.jsonl");
+            Ok(HttpResponse {
+                body: self.0.clone().into_bytes(),
+                max_age: None,
+            })
+        }
+    }
+
+    let update_key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let mut log = create(&update_key, Map::new());
+    let resolution = log.verify().unwrap();
+
+    let resolver = WebvhResolver::new(LogFetcher(log.to_jsonl().unwrap()));
+    assert_eq!(
+        resolver.resolve(&resolution.did).unwrap(),
+        resolution.document
+    );
+
+    log.deactivate(&update_key).unwrap();
+    let resolver = WebvhResolver::new(LogFetcher(log.to_jsonl().unwrap()));
+    assert_eq!(
+        resolver.resolve(&resolution.did).err(),
+        Some(Error::WEBVH_DEACTIVATED)
+    );
+}
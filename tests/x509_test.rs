@@ -0,0 +1,286 @@
+#![cfg(feature = "mldsa")]
+
+use chrono::{Duration, TimeZone, Utc};
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::{
+        any::{AnySigningKey, AnyVerifyingKey},
+        ecdsa::{_256::P256VerifyingKey, _384::P384VerifyingKey},
+        eddsa::EDDSAVerifyingKey,
+        mldsa::MLDSA65VerifyingKey,
+        policy::KeyPolicy,
+        rsa::RsaVerifyingKey,
+        x509::{
+            certificate_from_der, certificate_pem_to_der, certificate_request_from_der,
+            certificate_request_from_pem, CertificateSigningRequest, SelfSignedCertificate,
+        },
+        VerifyFromKey,
+    },
+    errors::Error,
+};
+use x509_cert::{
+    der::{asn1::OctetString, oid::AssociatedOid, Encode},
+    ext::{
+        pkix::{BasicConstraints, SubjectAltName},
+        Extension,
+    },
+    request::ExtensionReq,
+};
+
+const PUBLIC_KEY_256_HEX: &str =
+    "04115b3fa39fae41b4e32f7721ca72f8c1781483647dabd514f08e66128bd47fce9067b90e0488c9c2a9f30f5a266a07841d6c077413ba07e74569b99d4fd3cec6";
+const EDDSA_PUBLIC_KEY_HEX: &str =
+    "7b6df71975950d5ea15ac090c57d462f73d3a48644fbcf2c6d5db838adf136b5";
+const P256_CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----
+MIIBjzCCATWgAwIBAgIUTrm8KMM15L+akbHt4HCbVRvGr3cwCgYIKoZIzj0EAwIw
+HTEbMBkGA1UEAwwSZGlkOmV4YW1wbGU6aXNzdWVyMB4XDTI2MTAxNjEwNTA0NVoX
+DTM2MTAxMzEwNTA0NVowHTEbMBkGA1UEAwwSZGlkOmV4YW1wbGU6aXNzdWVyMFkw
+EwYHKoZIzj0CAQYIKoZIzj0DAQcDQgAEEVs/o5+uQbTjL3chynL4wXgUg2R9q9UU
+8I5mEovUf86QZ7kOBIjJwqnzD1omageEHWwHdBO6B+dFabmdT9POxqNTMFEwHQYD
+VR0OBBYEFJqoWPOmzWob7HYM8hKNpdNaxM50MB8GA1UdIwQYMBaAFJqoWPOmzWob
+7HYM8hKNpdNaxM50MA8GA1UdEwEB/wQFMAMBAf8wCgYIKoZIzj0EAwIDSAAwRQIg
+X+53g5u8KrXbUq5g7MsCYuNDAEusN6yKaOK7KUlmS34CIQDBRRxyZctdksMfQQBH
+ET6TtHCDTTa3loPp0Nt7xRBjFw==
+-----END CERTIFICATE-----
+";
+const EDDSA_CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----
+MIIBUTCCAQOgAwIBAgIUSSE9I0azuqkZNiM0gS9kPZ3+qFgwBQYDK2VwMB4xHDAa
+BgNVBAMME2RpZDpleGFtcGxlOmVkMjU1MTkwHhcNMjYxMDE2MTA1NDA5WhcNMzYx
+MDEzMTA1NDA5WjAeMRwwGgYDVQQDDBNkaWQ6ZXhhbXBsZTplZDI1NTE5MCowBQYD
+K2VwAyEAe233GXWVDV6hWsCQxX1GL3PTpIZE+88sbV24OK3xNrWjUzBRMB0GA1Ud
+DgQWBBTWWaMzpYaDk1WIKau/rRb0yE7h8zAfBgNVHSMEGDAWgBTWWaMzpYaDk1WI
+Kau/rRb0yE7h8zAPBgNVHRMBAf8EBTADAQH/MAUGAytlcANBAFrjCjsjFmX+ZoCj
+91+K9OKfpU+kc5XiMnUCqexlTcuOaKirOf1j2ZrH/8SZH2Q3Ny+cjzqBRZ6wFNZt
+zoWAxgQ=
+-----END CERTIFICATE-----
+";
+const RSA_CERTIFICATE: &str = "-----BEGIN CERTIFICATE-----
+MIICEDCCAXmgAwIBAgIULgSTX7nkajz8XLsY4yS1OPIHMZswDQYJKoZIhvcNAQEL
+BQAwGjEYMBYGA1UEAwwPZGlkOmV4YW1wbGU6cnNhMB4XDTI2MTAxNjEwNTQwOVoX
+DTM2MTAxMzEwNTQwOVowGjEYMBYGA1UEAwwPZGlkOmV4YW1wbGU6cnNhMIGfMA0G
+CSqGSIb3DQEBAQUAA4GNADCBiQKBgQCtgN+cr7UtvkKutBLkgq5fQRd/OD8fzH3j
+c51H8W2wUDdat4rmGdOvR0wD62psx5ck8LhRk7p18xE1IuBNOmf314VUt55pjnRA
+xdU4SCvmU4xpKFcC4sq0WHwRkjHUhevvhS+DdM7WA65JOeAgNm/HS2OS84a6tWN3
+Th3tgy5u2wIDAQABo1MwUTAdBgNVHQ4EFgQUbgIdWgkI99uRf/AJQiCxlRsU3A0w
+HwYDVR0jBBgwFoAUbgIdWgkI99uRf/AJQiCxlRsU3A0wDwYDVR0TAQH/BAUwAwEB
+/zANBgkqhkiG9w0BAQsFAAOBgQAgXlTRp/Bqd3ju/+vZ8qJVerAH6fodMwhcMdhp
+sZE16Eel6jnCjA2jhwdlyjbLYJ3UMZxu5Jz5jwby6RSOxXdjn8YReDpuo76leYYo
+MxBpdqoXfpvd6LUHQEEyOakTSVggJmTmVPh6nGomas4GBxzMTziqxvq/0DDnEFLN
+ghCsgQ==
+-----END CERTIFICATE-----
+";
+const RSA_SIGNATURE: &str = "nXq4M95jmBRbwExeDNlcmby-gDktZz7HkHBGwFZx-oui6b4Mvc5IItm_b56Y0BEZf-ebfGrbTOq1t1jPhYx6MdtEbeuUBV6cV8SEvw8FQ0qBWG8Ly-8-9tC4FHrB2e2BgSgaSJHUANKAJoBcrCJD8JjZ18Zg5wmWRUr4hkP84aU";
+const MESSAGE: &str = "did:example:123";
+const DID: &str = "did:web:example.com";
+
+#[test]
+pub fn verifying_keys_from_x509() {
+    assert_eq!(
+        hex::encode(
+            P256VerifyingKey::from_x509_pem(P256_CERTIFICATE)
+                .unwrap()
+                .to_bytes()
+        ),
+        PUBLIC_KEY_256_HEX
+    );
+    assert_eq!(
+        hex::encode(
+            P256VerifyingKey::from_x509_der(&certificate_pem_to_der(P256_CERTIFICATE).unwrap())
+                .unwrap()
+                .to_bytes()
+        ),
+        PUBLIC_KEY_256_HEX
+    );
+    assert_eq!(
+        hex::encode(
+            EDDSAVerifyingKey::from_x509_pem(EDDSA_CERTIFICATE)
+                .unwrap()
+                .to_bytes()
+        ),
+        EDDSA_PUBLIC_KEY_HEX
+    );
+    assert!(RsaVerifyingKey::from_x509_pem(RSA_CERTIFICATE)
+        .unwrap()
+        .verify_bytes(MESSAGE.as_bytes(), RSA_SIGNATURE, Algorithm::RS256)
+        .unwrap());
+
+    assert!(P384VerifyingKey::from_x509_pem(P256_CERTIFICATE).is_err());
+    assert!(MLDSA65VerifyingKey::from_x509_pem(EDDSA_CERTIFICATE).is_err());
+    assert_eq!(
+        P256VerifyingKey::from_x509_pem("-----BEGIN CERTIFICATE-----\n-----END CERTIFICATE-----\n")
+            .err()
+            .unwrap()
+            .to_string(),
+        Error::X509_CERTIFICATE_ERROR.to_string()
+    );
+}
+
+#[test]
+pub fn any_verifying_key_from_x509() {
+    match AnyVerifyingKey::from_x509_pem(P256_CERTIFICATE).unwrap() {
+        AnyVerifyingKey::P256(key) => assert_eq!(hex::encode(key.to_bytes()), PUBLIC_KEY_256_HEX),
+        _ => panic!("expected a P-256 key"),
+    }
+
+    let key = AnyVerifyingKey::from_x509_pem(EDDSA_CERTIFICATE).unwrap();
+    assert_eq!(key.algorithm(), Algorithm::EdDSA);
+
+    let key = AnyVerifyingKey::from_x509_pem(RSA_CERTIFICATE).unwrap();
+    assert_eq!(key.algorithm(), Algorithm::RS256);
+    assert!(key
+        .verify_bytes(MESSAGE.as_bytes(), RSA_SIGNATURE, Algorithm::RS256)
+        .unwrap());
+    assert_eq!(
+        KeyPolicy::default().check(&key).unwrap_err(),
+        Error::KEY_POLICY_VIOLATION
+    );
+    assert!(KeyPolicy::permissive().check(&key).is_ok());
+}
+
+#[test]
+pub fn self_signed_certificate_generation() {
+    let not_before = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+    let not_after = not_before + Duration::days(365);
+
+    for alg in [
+        Algorithm::ES256,
+        Algorithm::ES256K,
+        Algorithm::ES384,
+        Algorithm::ES512,
+        Algorithm::EdDSA,
+        Algorithm::RS256,
+        Algorithm::MLDSA65,
+    ] {
+        let key = AnySigningKey::generate(alg).unwrap();
+        let der = SelfSignedCertificate::new(&format!("CN={}", DID), not_before, not_after)
+            .did(DID)
+            .sign(&key)
+            .unwrap();
+
+        let certificate = certificate_from_der(&der).unwrap();
+        let tbs = &certificate.tbs_certificate;
+        assert_eq!(tbs.subject.to_string(), format!("CN={}", DID));
+        assert_eq!(tbs.issuer, tbs.subject);
+        assert_eq!(
+            tbs.validity.not_before.to_unix_duration().as_secs() as i64,
+            not_before.timestamp()
+        );
+        assert_eq!(
+            tbs.validity.not_after.to_unix_duration().as_secs() as i64,
+            not_after.timestamp()
+        );
+
+        let extensions = tbs.extensions.as_ref().unwrap();
+        assert_eq!(extensions.len(), 1);
+        assert_eq!(extensions[0].extn_id.to_string(), "2.5.29.17");
+        assert!(!extensions[0].critical);
+        assert!(extensions[0]
+            .extn_value
+            .as_bytes()
+            .windows(DID.len())
+            .any(|val| val == DID.as_bytes()));
+
+        assert_eq!(
+            AnyVerifyingKey::from_x509_der(&der)
+                .unwrap()
+                .to_spki_der()
+                .unwrap(),
+            key.verifying_key().to_spki_der().unwrap()
+        );
+    }
+}
+
+#[test]
+pub fn self_signed_certificate_without_subject() {
+    let key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let pem = SelfSignedCertificate::new("", Utc::now(), Utc::now() + Duration::days(1))
+        .did(DID)
+        .sign_pem(&key)
+        .unwrap();
+    assert!(pem.starts_with("-----BEGIN CERTIFICATE-----"));
+
+    let certificate = certificate_from_der(&certificate_pem_to_der(&pem).unwrap()).unwrap();
+    assert!(certificate.tbs_certificate.subject.0.is_empty());
+    assert!(certificate.tbs_certificate.extensions.unwrap()[0].critical);
+
+    assert!(
+        SelfSignedCertificate::new("CN=hmac", Utc::now(), Utc::now())
+            .sign(&AnySigningKey::generate(Algorithm::HS256).unwrap())
+            .is_err()
+    );
+}
+
+#[test]
+pub fn certificate_signing_request_generation() {
+    let basic_constraints = Extension {
+        extn_id: BasicConstraints::OID,
+        critical: true,
+        extn_value: OctetString::new(
+            BasicConstraints {
+                ca: false,
+                path_len_constraint: None,
+            }
+            .to_der()
+            .unwrap(),
+        )
+        .unwrap(),
+    };
+
+    for alg in [
+        Algorithm::ES256,
+        Algorithm::ES256K,
+        Algorithm::ES384,
+        Algorithm::ES512,
+        Algorithm::EdDSA,
+        Algorithm::RS256,
+        Algorithm::MLDSA65,
+    ] {
+        let key = AnySigningKey::generate(alg).unwrap();
+        let der = CertificateSigningRequest::new(&format!("CN={},O=Example", DID))
+            .did(DID)
+            .extension(basic_constraints.clone())
+            .sign(&key)
+            .unwrap();
+
+        let request = certificate_request_from_der(&der).unwrap();
+        assert_eq!(
+            request.info.subject.to_string(),
+            format!("CN={},O=Example", DID)
+        );
+        assert_eq!(
+            request.info.public_key.to_der().unwrap(),
+            key.verifying_key().to_spki_der().unwrap()
+        );
+
+        let attributes = request.info.attributes.as_slice();
+        assert_eq!(attributes.len(), 1);
+        assert_eq!(attributes[0].oid, ExtensionReq::OID);
+        let extensions: Vec<Extension> = attributes[0].values.as_slice()[0].decode_as().unwrap();
+        assert_eq!(extensions.len(), 2);
+        assert_eq!(extensions[0].extn_id, SubjectAltName::OID);
+        assert!(!extensions[0].critical);
+        assert_eq!(extensions[1], basic_constraints);
+    }
+
+    let key = AnySigningKey::generate(Algorithm::EdDSA).unwrap();
+    let pem = CertificateSigningRequest::new("")
+        .did(DID)
+        .sign_pem(&key)
+        .unwrap();
+    assert!(pem.starts_with("-----BEGIN CERTIFICATE REQUEST-----"));
+
+    let request = certificate_request_from_pem(&pem).unwrap();
+    assert!(request.info.subject.0.is_empty());
+
+    let request = certificate_request_from_der(
+        &CertificateSigningRequest::new("CN=plain")
+            .sign(&key)
+            .unwrap(),
+    )
+    .unwrap();
+    assert!(request.info.attributes.is_empty());
+
+    assert!(CertificateSigningRequest::new("CN=hmac")
+        .sign(&AnySigningKey::generate(Algorithm::HS256).unwrap())
+        .is_err());
+}
@@ -0,0 +1,286 @@
+use chrono::{TimeDelta, Utc};
+use did_crypto::{
+    algorithms::Algorithm,
+    crypto::any::{AnySigningKey, AnyVerifyingKey},
+    data_integrity::{jcs_cryptosuite, ProofOptions},
+    errors::Error,
+    resolver::DidResolver,
+    zcap::{root_capability_id, Capability, InvocationCheck, ZcapVerifier},
+};
+use serde_json::{json, Value};
+
+const TARGET: &str = "https://example.com/api/items";
+
+struct ControllerResolver(Vec<(&'static str, AnyVerifyingKey)>);
+
+impl DidResolver for ControllerResolver {
+    fn resolve(&self, did: &str) -> Result<Value, Error> {
+        let key = match self.0.iter().find(|(val, _)| *val == did) {
+            Some((_, key)) => key,
+            None => return Err(Error::DID_RESOLUTION_ERROR),
+        };
+
+        Ok(json!({
+            "id": did,
+            "verificationMethod": [{
+                "id": "#key-1",
+                "type": "JsonWebKey2020",
+                "controller": did,
+                "publicKeyJwk": key.to_jwk().unwrap(),
+            }],
+            "capabilityDelegation": ["#key-1"],
+            "capabilityInvocation": ["#key-1"],
+        }))
+    }
+}
+
+struct Party {
+    did: &'static str,
+    key: AnySigningKey,
+    alg: Algorithm,
+}
+
+impl Party {
+    fn new(did: &'static str, alg: Algorithm) -> Self {
+        Party {
+            did,
+            key: AnySigningKey::generate(alg).unwrap(),
+            alg,
+        }
+    }
+
+    fn options(&self) -> ProofOptions {
+        ProofOptions::new(
+            jcs_cryptosuite(self.alg).unwrap(),
+            &format!("{}#key-1", self.did),
+        )
+    }
+}
+
+fn setup() -> (Party, Party, Party, ZcapVerifier<ControllerResolver>) {
+    let alice = Party::new("did:example:alice", Algorithm::EdDSA);
+    let bob = Party::new("did:example:bob", Algorithm::ES256);
+    let carol = Party::new("did:example:carol", Algorithm::ES384);
+    let resolver = ControllerResolver(vec![
+        (alice.did, alice.key.verifying_key()),
+        (bob.did, bob.key.verifying_key()),
+        (carol.did, carol.key.verifying_key()),
+    ]);
+    let verifier = ZcapVerifier::new(resolver).root(Capability::root(alice.did, TARGET));
+    (alice, bob, carol, verifier)
+}
+
+#[test]
+pub fn zcap_root_invocation() {
+    let (alice, bob, _, verifier) = setup();
+    let root = Capability::root(alice.did, TARGET);
+    assert_eq!(
+        root.id,
+        "urn:zcap:root:https%3A%2F%2Fexample.com%2Fapi%2Fitems"
+    );
+    assert_eq!(root.id, root_capability_id(TARGET));
+
+    let request = json!({"item": "42"});
+    let invoked = root
+        .invoke(&request, "read", alice.options(), &alice.key, alice.alg)
+        .unwrap();
+    assert_eq!(invoked["proof"]["capability"], json!(root.id));
+
+    let invocation = verifier
+        .verify_invocation(&invoked, &InvocationCheck::new(TARGET, "read"))
+        .unwrap();
+    assert_eq!(invocation.invoker, "did:example:alice#key-1");
+    assert_eq!(invocation.chain, vec![root.clone()]);
+
+    assert_eq!(
+        verifier
+            .verify_invocation(&invoked, &InvocationCheck::new(TARGET, "write"))
+            .err(),
+        Some(Error::ZCAP_UNAUTHORIZED)
+    );
+
+    let mut tampered = invoked.clone();
+    tampered["item"] = json!("43");
+    assert_eq!(
+        verifier
+            .verify_invocation(&tampered, &InvocationCheck::new(TARGET, "read"))
+            .err(),
+        Some(Error::ZCAP_INVALID_PROOF)
+    );
+
+    let invoked = root
+        .invoke(&request, "read", bob.options(), &bob.key, bob.alg)
+        .unwrap();
+    assert_eq!(
+        verifier
+            .verify_invocation(&invoked, &InvocationCheck::new(TARGET, "read"))
+            .err(),
+        Some(Error::ZCAP_UNAUTHORIZED)
+    );
+
+    let other = Capability::root(alice.did, "https://example.com/other");
+    let invoked = other
+        .invoke(&request, "read", alice.options(), &alice.key, alice.alg)
+        .unwrap();
+    assert_eq!(
+        verifier
+            .verify_invocation(
+                &invoked,
+                &InvocationCheck::new("https://example.com/other", "read")
+            )
+            .err(),
+        Some(Error::ZCAP_INVALID_CHAIN)
+    );
+}
+
+#[test]
+pub fn zcap_delegation_chain() {
+    let (alice, bob, carol, verifier) = setup();
+    let root = Capability::root(alice.did, TARGET);
+    let expires = Utc::now() + TimeDelta::hours(1);
+
+    let to_bob = root
+        .delegate(bob.did)
+        .with_allowed_action(&["read", "write"])
+        .with_expires(expires)
+        .sign(&root, alice.options(), &alice.key, alice.alg)
+        .unwrap();
+    let to_carol = to_bob
+        .delegate(carol.did)
+        .with_invocation_target(&format!("{}/42", TARGET))
+        .with_allowed_action(&["read"])
+        .sign(&to_bob, bob.options(), &bob.key, bob.alg)
+        .unwrap();
+    assert_eq!(
+        to_carol.proof.as_ref().unwrap()["capabilityChain"],
+        json!([root.id, to_bob.to_value().unwrap()])
+    );
+
+    let chain = verifier
+        .verify_delegation(&to_carol.to_value().unwrap())
+        .unwrap();
+    assert_eq!(chain, vec![root.clone(), to_bob.clone(), to_carol.clone()]);
+
+    let target = format!("{}/42", TARGET);
+    let invoked = to_carol
+        .invoke(&json!({}), "read", carol.options(), &carol.key, carol.alg)
+        .unwrap();
+    let invocation = verifier
+        .verify_invocation(&invoked, &InvocationCheck::new(&target, "read"))
+        .unwrap();
+    assert_eq!(invocation.capability, to_carol);
+    assert_eq!(invocation.invoker, "did:example:carol#key-1");
+
+    let invoked_write = to_carol
+        .invoke(&json!({}), "write", carol.options(), &carol.key, carol.alg)
+        .unwrap();
+    assert_eq!(
+        verifier
+            .verify_invocation(&invoked_write, &InvocationCheck::new(&target, "write"))
+            .err(),
+        Some(Error::ZCAP_UNAUTHORIZED)
+    );
+
+    let after = expires + TimeDelta::minutes(10);
+    assert_eq!(
+        verifier
+            .verify_invocation_at(
+                &invoked,
+                &InvocationCheck::new(&target, "read"),
+                after,
+                |_, _| Ok(true)
+            )
+            .err(),
+        Some(Error::ZCAP_EXPIRED)
+    );
+
+    assert_eq!(
+        to_bob
+            .delegate(carol.did)
+            .with_allowed_action(&["delete"])
+            .sign(&to_bob, bob.options(), &bob.key, bob.alg)
+            .err(),
+        Some(Error::ZCAP_INVALID_CHAIN)
+    );
+    assert_eq!(
+        to_bob
+            .delegate(carol.did)
+            .with_invocation_target("https://example.com/api/itemsx")
+            .sign(&to_bob, bob.options(), &bob.key, bob.alg)
+            .err(),
+        Some(Error::ZCAP_INVALID_CHAIN)
+    );
+
+    let by_bob = root
+        .delegate(carol.did)
+        .sign(&root, bob.options(), &bob.key, bob.alg)
+        .unwrap();
+    assert_eq!(
+        verifier
+            .verify_delegation(&by_bob.to_value().unwrap())
+            .err(),
+        Some(Error::ZCAP_UNAUTHORIZED)
+    );
+
+    let mut tampered = to_carol.to_value().unwrap();
+    tampered["proof"]["capabilityChain"][1]["allowedAction"] = json!(["read", "write", "delete"]);
+    assert_eq!(
+        verifier.verify_delegation(&tampered).err(),
+        Some(Error::ZCAP_INVALID_PROOF)
+    );
+
+    let (_, _, _, short) = setup();
+    let short = short.max_chain_length(2);
+    assert_eq!(
+        short.verify_delegation(&to_carol.to_value().unwrap()).err(),
+        Some(Error::ZCAP_INVALID_CHAIN)
+    );
+}
+
+#[test]
+pub fn zcap_caveats_and_malformed_input() {
+    let (alice, bob, _, verifier) = setup();
+    let root = Capability::root(alice.did, TARGET);
+    let to_bob = root
+        .delegate(bob.did)
+        .with_caveat(json!({"type": "MaxAmount", "amount": 10}))
+        .sign(&root, alice.options(), &alice.key, alice.alg)
+        .unwrap();
+
+    let invoked = to_bob
+        .invoke(
+            &json!({"amount": 5}),
+            "pay",
+            bob.options(),
+            &bob.key,
+            bob.alg,
+        )
+        .unwrap();
+    let check = InvocationCheck::new(TARGET, "pay");
+    assert_eq!(
+        verifier.verify_invocation(&invoked, &check).err(),
+        Some(Error::ZCAP_UNAUTHORIZED)
+    );
+
+    let max_amount =
+        |caveat: &Value, _: &_| {
+            Ok(caveat["type"] == "MaxAmount"
+                && invoked["amount"].as_i64() <= caveat["amount"].as_i64())
+        };
+    let invocation = verifier
+        .verify_invocation_at(&invoked, &check, Utc::now(), max_amount)
+        .unwrap();
+    assert_eq!(invocation.action, "pay");
+
+    let error = Capability::from_value(&json!({"id": "urn:zcap:1"}))
+        .err()
+        .unwrap();
+    assert_eq!(error, Error::ZCAP_MALFORMED);
+    assert!(error.is_malformed_input());
+    assert_eq!(
+        verifier
+            .verify_invocation(&json!({"amount": 5}), &check)
+            .err(),
+        Some(Error::ZCAP_MALFORMED)
+    );
+}